@@ -0,0 +1,35 @@
+//! Scope the classes of a stylesheet to the components that use it with css modules.
+//!
+//! The `#[css_module]` attribute renames every class in a stylesheet with a hash of its path and adds a constant for
+//! each class to the struct. Both stylesheets below have a `.title` class, but they never conflict. `dx` combines
+//! every css module into one stylesheet, so the app only loads a single file.
+//!
+//! Css modules need to be bundled by `dx` which renames the classes in the stylesheet.
+
+use dioxus::prelude::*;
+
+#[css_module("/examples/assets/card.css")]
+struct Card;
+
+#[css_module("/examples/assets/banner.css")]
+struct Banner;
+
+fn main() {
+    dioxus::launch(app);
+}
+
+fn app() -> Element {
+    rsx! {
+        // The stylesheets of both modules are the same file, so it is only loaded once
+        document::Stylesheet { href: Card::STYLESHEET }
+        document::Stylesheet { href: Banner::STYLESHEET }
+
+        div { class: Banner::banner,
+            h1 { class: Banner::title, "Css modules" }
+        }
+        div { class: Card::card,
+            h2 { class: Card::title, "Scoped classes" }
+            p { "This title uses the .title class from card.css" }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! This example shows how to replace the native titlebar of a window with one rendered by the app.
+//! The titlebar moves the window when it is dragged, and the window controls minimize, maximize and close it.
+//! This example is not supported on the mobile or web renderers.
+
+use dioxus::desktop::titlebar::{DragRegion, WindowControls};
+use dioxus::desktop::Config;
+use dioxus::prelude::*;
+
+const STYLE: &str = r#"
+    body { margin: 0; font-family: sans-serif; }
+    .titlebar { display: flex; align-items: center; height: 32px; background: #1f2937; color: white; }
+    /* Leave space for the native buttons on macOS */
+    .title { flex: 1; padding-left: 80px; }
+    .controls { display: flex; height: 100%; }
+    [data-dioxus-window-control] { width: 46px; border: none; background: none; color: inherit; }
+    [data-dioxus-window-control]:hover { background: #374151; }
+    [data-dioxus-window-control="close"]:hover { background: #dc2626; }
+"#;
+
+fn main() {
+    dioxus::LaunchBuilder::new()
+        .with_cfg(Config::new().with_custom_titlebar())
+        .launch(app)
+}
+
+fn app() -> Element {
+    rsx! {
+        style { {STYLE} }
+        DragRegion { class: "titlebar",
+            span { class: "title", "Custom titlebar" }
+            WindowControls { class: "controls" }
+        }
+        main { padding: "16px", "Drag the titlebar to move the window and double click it to maximize." }
+    }
+}
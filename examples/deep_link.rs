@@ -0,0 +1,53 @@
+//! This example shows how deep links like `dioxusnotes://notes/42` open a route of the router.
+//! Register the scheme with `deep_link_protocols` in the `[bundle]` section of `Dioxus.toml` and bundle the app.
+//! On Windows and Linux you can also try it by passing the link as an argument: `cargo run --example deep_link -- dioxusnotes://notes/42`
+//! This example is not supported on the web renderer.
+
+use dioxus::desktop::{launch_url, use_deep_link};
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus::launch(|| rsx! { Router::<Route> {} });
+}
+
+#[derive(Routable, Clone, PartialEq)]
+enum Route {
+    #[layout(Links)]
+    #[route("/")]
+    Home {},
+    #[route("/notes/:id")]
+    Note { id: usize },
+}
+
+#[component]
+fn Links() -> Element {
+    let mut last_link = use_signal(launch_url);
+    use_deep_link(move |url| last_link.set(Some(url)));
+
+    rsx! {
+        nav {
+            Link { to: Route::Home {}, "Home" }
+            if let Some(url) = last_link() {
+                " Opened from {url}"
+            }
+        }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! {
+        h1 { "Notes" }
+        for id in 1..4 {
+            p {
+                Link { to: Route::Note { id }, "Note {id}" }
+            }
+        }
+    }
+}
+
+#[component]
+fn Note(id: usize) -> Element {
+    rsx! { h1 { "Note {id}" } }
+}
@@ -0,0 +1,83 @@
+//! This example shows how to open and save text files with the native file dialogs.
+//! Files the app is launched with, or that are opened with the app from the file manager, are opened too.
+//! Register the extensions the app can open with `file_associations` in the `[bundle]` section of `Dioxus.toml`.
+//! This example is not supported on the mobile or web renderers.
+
+use dioxus::desktop::dialog::{OpenDialog, SaveDialog};
+use dioxus::desktop::{launch_files, use_open_files};
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+fn main() {
+    dioxus::launch(app);
+}
+
+fn app() -> Element {
+    let mut path = use_signal(|| None::<PathBuf>);
+    let mut text = use_signal(String::new);
+
+    let mut open = move |file: PathBuf| {
+        text.set(std::fs::read_to_string(&file).unwrap_or_default());
+        path.set(Some(file));
+    };
+
+    use_hook(move || {
+        if let Some(file) = launch_files().pop() {
+            open(file);
+        }
+    });
+    use_open_files(move |mut files| {
+        if let Some(file) = files.pop() {
+            open(file);
+        }
+    });
+
+    rsx! {
+        div {
+            button {
+                onclick: move |_| async move {
+                    let picked = OpenDialog::new()
+                        .filter("Text", &["txt", "md"])
+                        .show()
+                        .await;
+                    if let Some(file) = picked.into_iter().next() {
+                        open(file);
+                    }
+                },
+                "Open"
+            }
+            button {
+                onclick: move |_| async move {
+                    let target = match path() {
+                        Some(path) => Some(path),
+                        None => {
+                            SaveDialog::new()
+                                .file_name("untitled.txt")
+                                .filter("Text", &["txt"])
+                                .show()
+                                .await
+                        }
+                    };
+                    if let Some(target) = target {
+                        if std::fs::write(&target, text()).is_ok() {
+                            path.set(Some(target));
+                        }
+                    }
+                },
+                "Save"
+            }
+            span {
+                match path() {
+                    Some(path) => path.display().to_string(),
+                    None => "Untitled".to_string(),
+                }
+            }
+        }
+        textarea {
+            rows: 20,
+            cols: 80,
+            value: "{text}",
+            oninput: move |event| text.set(event.value()),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! This example shows how to build a tray-first app with Dioxus desktop.
+//! Closing the window hides it in the tray, and the tray menu shows it again or quits the app.
+//! This example is not supported on the mobile or web renderers.
+
+use dioxus::desktop::trayicon::{use_tray, Tray, TrayEvent, TrayMenu};
+use dioxus::desktop::{use_tray_event, window, Config, WindowCloseBehaviour};
+use dioxus::prelude::*;
+
+fn main() {
+    // Hide windows instead of closing them so the app keeps running in the tray
+    let config = Config::new().with_close_behaviour(WindowCloseBehaviour::WindowHides);
+
+    dioxus::LaunchBuilder::new().with_cfg(config).launch(app)
+}
+
+fn app() -> Element {
+    let mut paused = use_signal(|| false);
+    let mut count = use_signal(|| 0);
+
+    // The tray icon lives as long as this component
+    use_tray(|| {
+        Tray::new().tooltip("Dioxus counter").menu(
+            TrayMenu::new()
+                .item("show", "Show window")
+                .item("increment", "Increment")
+                .check("pause", "Pause", false)
+                .separator()
+                .quit("Quit"),
+        )
+    });
+
+    // Clicks on the icon and its menu arrive here
+    use_tray_event(move |event| match event {
+        TrayEvent::Menu(id) if id == "show" => {
+            window().set_visible(true);
+            window().set_focus();
+        }
+        TrayEvent::Menu(id) if id == "increment" && !paused() => count += 1,
+        TrayEvent::Menu(id) if id == "pause" => paused.toggle(),
+        _ => {}
+    });
+
+    rsx! {
+        div {
+            h1 { "Tray" }
+            p { "Count: {count}" }
+            p { "Close the window to hide it in the tray" }
+        }
+    }
+}
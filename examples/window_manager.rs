@@ -0,0 +1,71 @@
+//! Window manager example
+//!
+//! This example shows how to open, list and close windows with the window manager, and how to share state and send
+//! messages between them. Every window runs its own virtualdom, so state is shared through `use_shared_signal`
+//! and messages are delivered with `use_window_message`.
+//! This example is not supported on the mobile or web renderers.
+
+use dioxus::desktop::{
+    use_shared_signal, use_window_manager, use_window_message, window_manager, Config,
+};
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus::LaunchBuilder::desktop().launch(app);
+}
+
+/// A message the main window sends to every note
+#[derive(Clone)]
+struct Highlight(bool);
+
+/// The number of clicks in all windows
+struct Clicks(usize);
+
+fn app() -> Element {
+    let windows = use_window_manager();
+    let mut clicks = use_shared_signal(|| Clicks(0));
+    let mut highlighted = use_signal(|| false);
+
+    rsx! {
+        h1 { "Notes" }
+        p { "{clicks.read().0} clicks in all windows" }
+        button {
+            onclick: move |_| {
+                clicks.write().0 += 1;
+                let number = windows.windows().len();
+                windows.open(Note, NoteProps { number }, Config::new());
+            },
+            "Open a note"
+        }
+        button {
+            onclick: move |_| {
+                highlighted.toggle();
+                window_manager().broadcast(Highlight(highlighted()));
+            },
+            "Highlight notes"
+        }
+        button {
+            onclick: move |_| {
+                for window in window_manager().windows().into_iter().skip(1) {
+                    window.close();
+                }
+            },
+            "Close all notes"
+        }
+    }
+}
+
+#[component]
+fn Note(number: usize) -> Element {
+    let mut clicks = use_shared_signal(|| Clicks(0));
+    let mut highlighted = use_signal(|| false);
+    use_window_message(move |Highlight(highlight)| highlighted.set(highlight));
+
+    rsx! {
+        div { background_color: if highlighted() { "yellow" } else { "white" },
+            h2 { "Note {number}" }
+            button { onclick: move |_| clicks.write().0 += 1, "Click" }
+            p { "{clicks.read().0} clicks in all windows" }
+        }
+    }
+}
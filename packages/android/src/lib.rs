@@ -0,0 +1,59 @@
+//! Helpers for calling into java from the Android parts of Dioxus.
+
+pub use jni;
+
+use jni::{
+    objects::{JClass, JObject, JValue},
+    JNIEnv, JavaVM,
+};
+
+/// Run a function with the JNI environment of the current thread and the context of the app.
+///
+/// If the function fails, the java exception it left pending is cleared.
+pub fn with_env<T>(
+    f: impl FnOnce(&mut JNIEnv, &JObject) -> jni::errors::Result<T>,
+) -> jni::errors::Result<T> {
+    let ctx = ndk_context::android_context();
+    let vm = unsafe { JavaVM::from_raw(ctx.vm().cast()) }?;
+    let mut env = vm.attach_current_thread()?;
+    let context = unsafe { JObject::from_raw(ctx.context().cast()) };
+
+    let result = f(&mut env, &context);
+    clear_exception(&mut env, result)
+}
+
+/// Clear the java exception a failed call left pending and return the result of the call
+pub fn clear_exception<T>(
+    env: &mut JNIEnv,
+    result: jni::errors::Result<T>,
+) -> jni::errors::Result<T> {
+    if result.is_err() {
+        // Don't leave the exception pending for the next call into java
+        _ = env.exception_clear();
+    }
+    result
+}
+
+/// Load a class with the class loader of the app. The name can be separated with dots or slashes.
+///
+/// `FindClass` only sees the classes of the system on threads that were started in rust, and classes from libraries
+/// like androidx are not visible to it on the UI thread either.
+pub fn load_class<'a>(
+    env: &mut JNIEnv<'a>,
+    context: &JObject,
+    name: &str,
+) -> jni::errors::Result<JClass<'a>> {
+    let loader = env
+        .call_method(context, "getClassLoader", "()Ljava/lang/ClassLoader;", &[])?
+        .l()?;
+    let name = env.new_string(name.replace('/', "."))?;
+    let class = env
+        .call_method(
+            &loader,
+            "loadClass",
+            "(Ljava/lang/String;)Ljava/lang/Class;",
+            &[JValue::Object(&name)],
+        )?
+        .l()?;
+    Ok(class.into())
+}
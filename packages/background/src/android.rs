@@ -0,0 +1,63 @@
+//! Wakes the app with WorkManager through `dev.dioxus.main.Background`, which `dx` adds to Android apps
+
+use crate::store::PendingTask;
+use crate::TaskId;
+use dioxus_android::{load_class, with_env};
+use jni::{
+    objects::{JClass, JValue},
+    sys::{jboolean, jlong},
+    JNIEnv,
+};
+use std::path::PathBuf;
+
+/// Ask WorkManager to run the task if the app is in the background when it is due
+pub(crate) fn schedule(task: &PendingTask) {
+    let delay = task.not_before.saturating_sub(crate::native::now());
+    let result = with_env(|env, context| {
+        let background = load_class(env, context, "dev.dioxus.main.Background")?;
+        env.call_static_method(
+            background,
+            "schedule",
+            "(Landroid/content/Context;JZJ)V",
+            &[
+                JValue::Object(context),
+                JValue::Long(task.id.0 as jlong),
+                JValue::Bool(task.requires_network.into()),
+                JValue::Long(delay as jlong),
+            ],
+        )?;
+        Ok(())
+    });
+
+    if let Err(err) = result {
+        tracing::error!("Failed to schedule the background task with WorkManager: {err}");
+    }
+}
+
+/// Get the directory of the app for private files
+pub(crate) fn files_dir() -> Option<PathBuf> {
+    let result = with_env(|env, context| {
+        let dir = env
+            .call_method(context, "getFilesDir", "()Ljava/io/File;", &[])?
+            .l()?;
+        let path = env
+            .call_method(&dir, "getAbsolutePath", "()Ljava/lang/String;", &[])?
+            .l()?;
+        let path: String = env.get_string(&path.into())?.into();
+        Ok(PathBuf::from(path))
+    });
+
+    result
+        .map_err(|err| tracing::error!("Failed to get the files directory of the app: {err}"))
+        .ok()
+}
+
+/// Called by `dev.dioxus.main.BackgroundWorker` when WorkManager runs a task. Returns false to retry the task later.
+#[no_mangle]
+pub extern "system" fn Java_dev_dioxus_main_Background_runTask(
+    _env: JNIEnv,
+    _class: JClass,
+    id: jlong,
+) -> jboolean {
+    crate::native::run_pending(Some(TaskId(id as u64))).into()
+}
@@ -0,0 +1,118 @@
+//! Wakes the app with BGTaskScheduler. The identifier of the task has to be listed under
+//! `BGTaskSchedulerPermittedIdentifiers` in the Info.plist, which `dx` does for iOS apps.
+
+use crate::store::PendingTask;
+use block::ConcreteBlock;
+use objc::runtime::{Object, BOOL, NO};
+use objc::{class, msg_send, sel, sel_impl};
+use std::{ffi::CStr, sync::Once};
+
+#[link(name = "BackgroundTasks", kind = "framework")]
+extern "C" {}
+
+/// Register the handler iOS calls when it gives the app time to run the pending tasks. This has to happen before the
+/// app finishes launching.
+pub(crate) fn register_launch_handler() {
+    static REGISTERED: Once = Once::new();
+    REGISTERED.call_once(|| unsafe {
+        let handler = ConcreteBlock::new(|task: *mut Object| {
+            // The task is released after the block returns
+            let _: *mut Object = msg_send![task, retain];
+            let task = task as usize;
+
+            let expired = ConcreteBlock::new(move || {
+                let _: () = msg_send![task as *mut Object, setTaskCompletedWithSuccess: NO];
+            })
+            .copy();
+            let _: () = msg_send![task as *mut Object, setExpirationHandler: &*expired];
+
+            std::thread::spawn(move || {
+                let done = crate::native::run_pending(None);
+                let _: () =
+                    msg_send![task as *mut Object, setTaskCompletedWithSuccess: done as BOOL];
+                let _: () = msg_send![task as *mut Object, release];
+            });
+        })
+        .copy();
+
+        let scheduler: *mut Object = msg_send![class!(BGTaskScheduler), sharedScheduler];
+        let identifier = identifier();
+        let registered: BOOL = msg_send![
+            scheduler,
+            registerForTaskWithIdentifier: identifier
+            usingQueue: std::ptr::null_mut::<Object>()
+            launchHandler: &*handler
+        ];
+        let _: () = msg_send![identifier, release];
+
+        if registered == NO {
+            tracing::error!("Failed to register the background task handler with BGTaskScheduler");
+        }
+    });
+}
+
+/// Ask iOS for time to run the pending tasks. There is one request for every task, which waits until the earliest
+/// task is due and for the network if any task needs it.
+pub(crate) fn schedule(pending: &[PendingTask]) {
+    let Some(not_before) = pending.iter().map(|task| task.not_before).min() else {
+        return;
+    };
+    let requires_network = pending.iter().any(|task| task.requires_network);
+
+    unsafe {
+        let identifier = identifier();
+        let request: *mut Object = msg_send![class!(BGProcessingTaskRequest), alloc];
+        let request: *mut Object = msg_send![request, initWithIdentifier: identifier];
+        let _: () = msg_send![identifier, release];
+
+        let _: () = msg_send![request, setRequiresNetworkConnectivity: requires_network as BOOL];
+        let date: *mut Object = msg_send![
+            class!(NSDate),
+            dateWithTimeIntervalSince1970: not_before as f64 / 1000.0
+        ];
+        let _: () = msg_send![request, setEarliestBeginDate: date];
+
+        let scheduler: *mut Object = msg_send![class!(BGTaskScheduler), sharedScheduler];
+        let mut error: *mut Object = std::ptr::null_mut();
+        let submitted: BOOL = msg_send![scheduler, submitTaskRequest: request error: &mut error];
+        let _: () = msg_send![request, release];
+
+        if submitted == NO {
+            tracing::error!(
+                "Failed to schedule the background task with BGTaskScheduler: {}",
+                describe(error)
+            );
+        }
+    }
+}
+
+/// The identifier of the task, which is the bundle identifier followed by `.dioxus-background`
+unsafe fn identifier() -> *mut Object {
+    let bundle: *mut Object = msg_send![class!(NSBundle), mainBundle];
+    let bundle_identifier: *mut Object = msg_send![bundle, bundleIdentifier];
+    let bundle_identifier = match bundle_identifier.is_null() {
+        true => String::new(),
+        false => to_string(bundle_identifier),
+    };
+
+    let identifier = format!("{bundle_identifier}.dioxus-background");
+    // NSUTF8StringEncoding
+    let string: *mut Object = msg_send![class!(NSString), alloc];
+    msg_send![string, initWithBytes: identifier.as_ptr() length: identifier.len() encoding: 4usize]
+}
+
+unsafe fn describe(error: *mut Object) -> String {
+    if error.is_null() {
+        return "unknown error".to_string();
+    }
+    let description: *mut Object = msg_send![error, localizedDescription];
+    to_string(description)
+}
+
+unsafe fn to_string(string: *mut Object) -> String {
+    let utf8: *const std::ffi::c_char = msg_send![string, UTF8String];
+    match utf8.is_null() {
+        true => String::new(),
+        false => CStr::from_ptr(utf8).to_string_lossy().into_owned(),
+    }
+}
@@ -0,0 +1,271 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+use dioxus_lib::prelude::*;
+use dioxus_worker::WorkerMessage;
+use futures_channel::mpsc::{unbounded, UnboundedSender};
+use futures_util::StreamExt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+mod store;
+use store::PendingTask;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(not(target_arch = "wasm32"))]
+use native as backend;
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::set_storage_dir;
+
+#[cfg(target_os = "android")]
+mod android;
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_arch = "wasm32")]
+mod web;
+#[cfg(target_arch = "wasm32")]
+use web as backend;
+
+/// The id of a task that was scheduled with [`schedule_background`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TaskId(u64);
+
+/// An error from scheduling a background task or reading its result
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackgroundError {
+    /// No task was registered with this name with [`register_background_task`]
+    Unregistered(String),
+    /// The task panicked or its input couldn't be read
+    Failed(String),
+    /// The input or output couldn't be serialized
+    Serialization(String),
+    /// The queue of tasks couldn't be saved
+    Storage(String),
+}
+
+impl Display for BackgroundError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackgroundError::Unregistered(name) => {
+                write!(f, "No background task is registered as {name:?}")
+            }
+            BackgroundError::Failed(message) => write!(f, "The background task failed: {message}"),
+            BackgroundError::Serialization(message) => {
+                write!(
+                    f,
+                    "Failed to serialize a value of a background task: {message}"
+                )
+            }
+            BackgroundError::Storage(message) => {
+                write!(f, "Failed to save the background tasks: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BackgroundError {}
+
+/// A task to run in the background. Schedule it with [`schedule_background`].
+#[derive(Debug, Clone)]
+pub struct BackgroundTask {
+    name: String,
+    input: Result<String, String>,
+    requires_network: bool,
+    delay: Duration,
+}
+
+impl BackgroundTask {
+    /// Create a task that runs the function registered as `name` with [`register_background_task`] with the input
+    pub fn new(name: impl Into<String>, input: impl Serialize) -> Self {
+        Self {
+            name: name.into(),
+            input: serde_json::to_string(&input).map_err(|err| err.to_string()),
+            requires_network: false,
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Wait until the device is online to run the task.
+    ///
+    /// This is only checked on the web and when Android or iOS wakes the app to run the task. Desktop apps run the
+    /// task right away.
+    pub fn requires_network(mut self, requires_network: bool) -> Self {
+        self.requires_network = requires_network;
+        self
+    }
+
+    /// Wait at least this long before running the task
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// The output of a background task that finished. Get them with [`use_background_results`] or
+/// [`take_background_results`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackgroundResult {
+    id: TaskId,
+    name: String,
+    output: Result<String, String>,
+}
+
+impl BackgroundResult {
+    /// The id [`schedule_background`] returned for the task
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// The name the task was registered with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Deserialize the output of the task
+    pub fn output<O: DeserializeOwned>(&self) -> Result<O, BackgroundError> {
+        let output = self
+            .output
+            .as_ref()
+            .map_err(|err| BackgroundError::Failed(err.clone()))?;
+        serde_json::from_str(output).map_err(|err| BackgroundError::Serialization(err.to_string()))
+    }
+}
+
+/// Register a function that background tasks can run by name.
+///
+/// Register every task in `main` before the app is launched. Tasks are saved to disk when they are scheduled, and the
+/// tasks that didn't finish before the app exited start again once their function is registered on the next launch.
+/// iOS only lets apps register for background work before they finish launching.
+///
+/// The task is a function instead of a closure because it may run in a later launch of the app, or in a web worker.
+/// Anything it needs has to be passed in as the input.
+pub fn register_background_task<I: WorkerMessage, O: WorkerMessage>(
+    name: &'static str,
+    task: fn(I) -> O,
+) {
+    backend::register(name, task);
+}
+
+/// Run a task in the background.
+///
+/// The task is saved before it starts so it survives the app exiting:
+/// - On desktop, the task runs on its own thread. It keeps running when the window is closed until the app exits.
+/// - On Android, the task is scheduled with WorkManager, which wakes the app to finish it if the app went to the
+///   background.
+/// - On iOS, the task is scheduled with BGTaskScheduler, which gives the app time to finish pending tasks later.
+/// - On the web, the task runs in a web worker. Tasks that need the network wait until the device is online, and the
+///   service worker `dx` generates uses background sync to start them once the connection is back.
+///
+/// Tasks that still didn't finish run again on the next launch. Their output is delivered to
+/// [`use_background_results`], even if the app was restarted in between.
+pub fn schedule_background(task: BackgroundTask) -> Result<TaskId, BackgroundError> {
+    if !backend::is_registered(&task.name) {
+        return Err(BackgroundError::Unregistered(task.name));
+    }
+    let input = task.input.map_err(BackgroundError::Serialization)?;
+    let not_before = backend::now() + task.delay.as_millis() as u64;
+
+    let task = store::update(|store| {
+        let task = PendingTask {
+            id: TaskId(store.next_id),
+            name: task.name,
+            input,
+            requires_network: task.requires_network,
+            not_before,
+        };
+        store.next_id += 1;
+        store.pending.push(task.clone());
+        task
+    })?;
+
+    let id = task.id;
+    backend::schedule(task);
+    Ok(id)
+}
+
+/// Take the results of the background tasks that finished since the last time they were taken. Each result is only
+/// returned once.
+pub fn take_background_results() -> Vec<BackgroundResult> {
+    store::update(|store| std::mem::take(&mut store.results)).unwrap_or_else(|err| {
+        tracing::error!("{err}");
+        Vec::new()
+    })
+}
+
+/// Get the results of background tasks as they finish, including the tasks that finished while the app was closed.
+///
+/// The results are taken with [`take_background_results`], so only one component should read them.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_background::{register_background_task, schedule_background, use_background_results, BackgroundTask};
+///
+/// fn upload(path: String) -> usize {
+///     // Upload the file and return the number of bytes sent
+///     # 0
+/// }
+///
+/// fn main() {
+///     register_background_task("upload", upload);
+///     dioxus::launch(App);
+/// }
+///
+/// fn App() -> Element {
+///     let results = use_background_results();
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| {
+///                 _ = schedule_background(BackgroundTask::new("upload", "photo.jpg").requires_network(true));
+///             },
+///             "Upload"
+///         }
+///         for result in results() {
+///             if let Ok(bytes) = result.output::<usize>() {
+///                 p { "Uploaded {bytes} bytes" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_background_results() -> Signal<Vec<BackgroundResult>> {
+    let mut results = use_signal(Vec::new);
+
+    use_hook(|| {
+        let (sender, mut receiver) = unbounded();
+        listeners().lock().unwrap().push(sender);
+        spawn(async move {
+            loop {
+                let finished = take_background_results();
+                if !finished.is_empty() {
+                    results.write().extend(finished);
+                }
+                if receiver.next().await.is_none() {
+                    break;
+                }
+            }
+        });
+    });
+
+    results
+}
+
+/// The hooks that wait for tasks to finish
+fn listeners() -> &'static Mutex<Vec<UnboundedSender<()>>> {
+    static LISTENERS: OnceLock<Mutex<Vec<UnboundedSender<()>>>> = OnceLock::new();
+    LISTENERS.get_or_init(Default::default)
+}
+
+/// Wake the hooks after a task finished
+pub(crate) fn notify_finished() {
+    listeners()
+        .lock()
+        .unwrap()
+        .retain(|listener| listener.unbounded_send(()).is_ok());
+}
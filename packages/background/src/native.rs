@@ -0,0 +1,183 @@
+//! Background tasks on their own threads for desktop and mobile. Android and iOS are also asked to wake the app for
+//! tasks that didn't finish before it went to the background.
+
+use crate::store::{self, PendingTask, Store};
+use crate::TaskId;
+use dioxus_worker::WorkerMessage;
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{Arc, Mutex, OnceLock},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+type Task = Arc<dyn Fn(&str) -> Result<String, String> + Send + Sync>;
+
+/// The registered tasks by name
+fn tasks() -> &'static Mutex<HashMap<&'static str, Task>> {
+    static TASKS: OnceLock<Mutex<HashMap<&'static str, Task>>> = OnceLock::new();
+    TASKS.get_or_init(Default::default)
+}
+
+/// The tasks that are running in this process
+fn running() -> &'static Mutex<HashSet<TaskId>> {
+    static RUNNING: OnceLock<Mutex<HashSet<TaskId>>> = OnceLock::new();
+    RUNNING.get_or_init(Default::default)
+}
+
+static STORAGE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Save the queue of background tasks in a different directory. Call this before any task is registered.
+///
+/// By default the queue is saved in the local data directory of the user in a folder named after the executable, or
+/// in the files directory of the app on Android.
+pub fn set_storage_dir(dir: impl Into<PathBuf>) {
+    *STORAGE_DIR.lock().unwrap() = Some(dir.into());
+}
+
+pub(crate) fn register<I: WorkerMessage, O: WorkerMessage>(name: &'static str, task: fn(I) -> O) {
+    let task: Task = Arc::new(move |input: &str| {
+        let input = serde_json::from_str(input).map_err(|err| err.to_string())?;
+        let output = catch_unwind(AssertUnwindSafe(|| task(input))).map_err(panic_message)?;
+        serde_json::to_string(&output).map_err(|err| err.to_string())
+    });
+    tasks().lock().unwrap().insert(name, task);
+
+    #[cfg(target_os = "ios")]
+    crate::ios::register_launch_handler();
+
+    // Start the tasks that were interrupted when the app exited
+    for task in store::pending() {
+        if task.name == name {
+            spawn(task);
+        }
+    }
+}
+
+pub(crate) fn is_registered(name: &str) -> bool {
+    tasks().lock().unwrap().contains_key(name)
+}
+
+pub(crate) fn schedule(task: PendingTask) {
+    #[cfg(target_os = "android")]
+    crate::android::schedule(&task);
+    #[cfg(target_os = "ios")]
+    crate::ios::schedule(&store::pending());
+
+    spawn(task);
+}
+
+/// Run a task on a new thread. The thread is detached, so it keeps running until the task finishes or the process
+/// exits.
+fn spawn(task: PendingTask) {
+    if !running().lock().unwrap().insert(task.id) {
+        return;
+    }
+
+    let id = task.id;
+    let spawned = thread::Builder::new()
+        .name(format!("dioxus-background-{}", task.name))
+        .spawn(move || {
+            thread::sleep(Duration::from_millis(task.not_before.saturating_sub(now())));
+            run(&task);
+        });
+
+    if let Err(err) = spawned {
+        tracing::error!("Failed to start the background task: {err}");
+        running().lock().unwrap().remove(&id);
+    }
+}
+
+/// Run a task on the current thread and save its result
+fn run(task: &PendingTask) {
+    let handler = tasks().lock().unwrap().get(task.name.as_str()).cloned();
+    if let Some(handler) = handler {
+        let output = handler(&task.input);
+        match store::finish(task.id, output) {
+            Ok(()) => crate::notify_finished(),
+            Err(err) => tracing::error!("{err}"),
+        }
+    }
+    running().lock().unwrap().remove(&task.id);
+}
+
+/// Run the pending tasks that are due on the current thread, or only one task. This is called when the OS wakes the
+/// app. Returns false if some of the tasks couldn't run yet and should be tried again later.
+#[cfg(any(target_os = "android", target_os = "ios"))]
+pub(crate) fn run_pending(only: Option<TaskId>) -> bool {
+    // The OS may start the app without launching it, so the tasks were never registered
+    if tasks().lock().unwrap().is_empty() {
+        return false;
+    }
+
+    let mut done = true;
+    for task in store::pending() {
+        if only.is_some_and(|id| id != task.id) {
+            continue;
+        }
+        let ready = is_registered(&task.name) && task.not_before <= now();
+        if !ready || !running().lock().unwrap().insert(task.id) {
+            done = false;
+            continue;
+        }
+        run(&task);
+    }
+    done
+}
+
+/// The current time in milliseconds since the unix epoch
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_millis() as u64)
+}
+
+fn storage_path() -> Option<PathBuf> {
+    let dir = STORAGE_DIR.lock().unwrap().clone();
+
+    #[cfg(target_os = "android")]
+    let dir = dir.or_else(crate::android::files_dir);
+
+    #[cfg(not(target_os = "android"))]
+    let dir = dir.or_else(|| {
+        let exe = std::env::current_exe().ok()?;
+        Some(dirs::data_local_dir()?.join(exe.file_stem()?))
+    });
+
+    Some(dir?.join("dioxus-background.json"))
+}
+
+pub(crate) fn load() -> Store {
+    let Some(contents) = storage_path().and_then(|path| std::fs::read(path).ok()) else {
+        return Store::default();
+    };
+    serde_json::from_slice(&contents).unwrap_or_else(|err| {
+        tracing::error!("The saved background tasks are corrupted and were dropped: {err}");
+        Store::default()
+    })
+}
+
+pub(crate) fn save(store: &Store) -> Result<(), String> {
+    let path = storage_path().ok_or("The app has no data directory")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    }
+    let contents = serde_json::to_vec(store).map_err(|err| err.to_string())?;
+    // Write the whole file at once so a crash never leaves half of the queue behind
+    let temporary = path.with_extension("json.tmp");
+    std::fs::write(&temporary, contents).map_err(|err| err.to_string())?;
+    std::fs::rename(temporary, path).map_err(|err| err.to_string())
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "the background task panicked".to_string(),
+        },
+    }
+}
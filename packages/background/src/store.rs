@@ -0,0 +1,57 @@
+//! The queue of background tasks. It is saved after every change so tasks and their results outlive the app.
+
+use crate::{backend, BackgroundError, BackgroundResult, TaskId};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct Store {
+    pub(crate) next_id: u64,
+    /// The tasks that didn't finish yet
+    pub(crate) pending: Vec<PendingTask>,
+    /// The results that were not taken yet
+    pub(crate) results: Vec<BackgroundResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PendingTask {
+    pub(crate) id: TaskId,
+    pub(crate) name: String,
+    /// The input serialized to json
+    pub(crate) input: String,
+    pub(crate) requires_network: bool,
+    /// The time to start the task at in milliseconds since the unix epoch
+    pub(crate) not_before: u64,
+}
+
+/// Load the queue, change it and save it again
+pub(crate) fn update<R>(f: impl FnOnce(&mut Store) -> R) -> Result<R, BackgroundError> {
+    // Tasks finish on other threads on native platforms
+    static LOCK: Mutex<()> = Mutex::new(());
+    let _lock = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    let mut store = backend::load();
+    let result = f(&mut store);
+    backend::save(&store).map_err(BackgroundError::Storage)?;
+    Ok(result)
+}
+
+/// Get the tasks that didn't finish yet
+pub(crate) fn pending() -> Vec<PendingTask> {
+    backend::load().pending
+}
+
+/// Move a task from the queue to the results
+pub(crate) fn finish(id: TaskId, output: Result<String, String>) -> Result<(), BackgroundError> {
+    update(|store| {
+        let Some(index) = store.pending.iter().position(|task| task.id == id) else {
+            return;
+        };
+        let task = store.pending.remove(index);
+        store.results.push(BackgroundResult {
+            id,
+            name: task.name,
+            output,
+        });
+    })
+}
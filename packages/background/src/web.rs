@@ -0,0 +1,166 @@
+//! Background tasks in a web worker. The queue is kept in local storage so tasks that didn't finish run again the
+//! next time the app is opened.
+//!
+//! Pages can't run code once they are closed, so background sync only decides when the tasks start. Tasks that need
+//! the network wait for the device to come back online, and the service worker `dx` generates wakes the open pages
+//! when the browser fires the sync event for them.
+
+use crate::store::{self, PendingTask, Store};
+use crate::TaskId;
+use dioxus_worker::{Worker, WorkerMessage};
+use futures_util::future::LocalBoxFuture;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    sync::Once,
+};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+const STORAGE_KEY: &str = "dioxus-background";
+
+#[wasm_bindgen(inline_js = r#"
+export function listen(on_wake) {
+    window.addEventListener("online", () => on_wake());
+    if ("serviceWorker" in navigator) {
+        navigator.serviceWorker.addEventListener("message", (event) => {
+            if (event.data && event.data.type === "DIOXUS_BACKGROUND_SYNC") {
+                on_wake();
+            }
+        });
+    }
+}
+
+export function is_online() {
+    return navigator.onLine;
+}
+
+export function register_sync() {
+    if (!("serviceWorker" in navigator)) {
+        return;
+    }
+    navigator.serviceWorker.getRegistration().then((registration) => {
+        // Background sync is not available in every browser
+        if (registration && registration.sync) {
+            return registration.sync.register("dioxus-background");
+        }
+    }).catch((error) => console.error("Failed to register background sync", error));
+}
+
+export function sleep(millis) {
+    return new Promise((resolve) => setTimeout(resolve, millis));
+}
+"#)]
+extern "C" {
+    fn listen(on_wake: &Closure<dyn FnMut()>);
+    fn is_online() -> bool;
+    fn register_sync();
+    fn sleep(millis: f64) -> js_sys::Promise;
+}
+
+type Task = Rc<dyn Fn(String) -> LocalBoxFuture<'static, Result<String, String>>>;
+
+thread_local! {
+    /// The registered tasks by name
+    static TASKS: RefCell<HashMap<&'static str, Task>> = Default::default();
+    /// The tasks that are running in this page
+    static RUNNING: RefCell<HashSet<TaskId>> = Default::default();
+}
+
+pub(crate) fn register<I: WorkerMessage, O: WorkerMessage>(name: &'static str, task: fn(I) -> O) {
+    let worker = Worker::new(task);
+    let task: Task = Rc::new(move |input: String| {
+        let worker = worker.clone();
+        Box::pin(async move {
+            let input = serde_json::from_str(&input).map_err(|err| err.to_string())?;
+            let output = worker.run(input).await.map_err(|err| err.to_string())?;
+            serde_json::to_string(&output).map_err(|err| err.to_string())
+        })
+    });
+    TASKS.with_borrow_mut(|tasks| tasks.insert(name, task));
+
+    static LISTENING: Once = Once::new();
+    LISTENING.call_once(|| {
+        let on_wake = Closure::<dyn FnMut()>::new(run_pending);
+        listen(&on_wake);
+        // The listeners live as long as the page
+        on_wake.forget();
+    });
+
+    // Start the tasks that were interrupted when the page was closed
+    run_pending();
+}
+
+pub(crate) fn is_registered(name: &str) -> bool {
+    TASKS.with_borrow(|tasks| tasks.contains_key(name))
+}
+
+pub(crate) fn schedule(task: PendingTask) {
+    if task.requires_network && !is_online() {
+        register_sync();
+    }
+    run_pending();
+}
+
+/// Start every pending task that can run in this page
+fn run_pending() {
+    let online = is_online();
+    for task in store::pending() {
+        if task.requires_network && !online {
+            continue;
+        }
+        let Some(handler) = TASKS.with_borrow(|tasks| tasks.get(task.name.as_str()).cloned())
+        else {
+            continue;
+        };
+        if !RUNNING.with_borrow_mut(|running| running.insert(task.id)) {
+            continue;
+        }
+
+        spawn_local(async move {
+            let wait = task.not_before.saturating_sub(now());
+            if wait > 0 {
+                _ = JsFuture::from(sleep(wait as f64)).await;
+            }
+
+            // Tasks that went offline while they waited start again from the online event
+            if !task.requires_network || is_online() {
+                let output = handler(task.input.clone()).await;
+                match store::finish(task.id, output) {
+                    Ok(()) => crate::notify_finished(),
+                    Err(err) => tracing::error!("{err}"),
+                }
+            }
+            RUNNING.with_borrow_mut(|running| running.remove(&task.id));
+        });
+    }
+}
+
+/// The current time in milliseconds since the unix epoch
+pub(crate) fn now() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+pub(crate) fn load() -> Store {
+    let Some(contents) = local_storage().and_then(|storage| storage.get_item(STORAGE_KEY).ok()?)
+    else {
+        return Store::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|err| {
+        tracing::error!("The saved background tasks are corrupted and were dropped: {err}");
+        Store::default()
+    })
+}
+
+pub(crate) fn save(store: &Store) -> Result<(), String> {
+    let storage = local_storage().ok_or("Local storage is not available")?;
+    let contents = serde_json::to_string(store).map_err(|err| err.to_string())?;
+    storage
+        .set_item(STORAGE_KEY, &contents)
+        .map_err(|err| format!("{err:?}"))
+}
@@ -0,0 +1,125 @@
+use dioxus::prelude::*;
+use dioxus_background::{
+    register_background_task, schedule_background, set_storage_dir, take_background_results,
+    use_background_results, BackgroundError, BackgroundResult, BackgroundTask, TaskId,
+};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+/// The queue and the results are shared by the whole process, so the tests run one at a time
+fn setup() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    let guard = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    let dir = std::env::temp_dir().join(format!("dioxus-background-{}", std::process::id()));
+    set_storage_dir(dir);
+    register_background_task("double", |n: u64| n * 2);
+    register_background_task("fail", |_: ()| -> u64 { panic!("out of space") });
+    _ = take_background_results();
+
+    guard
+}
+
+/// Wait for the result of a task without a component
+fn wait_for(id: TaskId) -> BackgroundResult {
+    let start = Instant::now();
+    loop {
+        if let Some(result) = take_background_results()
+            .into_iter()
+            .find(|result| result.id() == id)
+        {
+            return result;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "the task never finished"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn delivers_results() {
+    let _guard = setup();
+
+    let id = schedule_background(BackgroundTask::new("double", 21)).unwrap();
+    let result = wait_for(id);
+
+    assert_eq!(result.name(), "double");
+    assert_eq!(result.output::<u64>(), Ok(42));
+    // Results are only delivered once
+    assert!(take_background_results().is_empty());
+}
+
+#[test]
+fn reports_panics() {
+    let _guard = setup();
+
+    let id = schedule_background(BackgroundTask::new("fail", ())).unwrap();
+
+    assert_eq!(
+        wait_for(id).output::<u64>(),
+        Err(BackgroundError::Failed("out of space".into()))
+    );
+}
+
+#[test]
+fn rejects_unregistered_tasks() {
+    let _guard = setup();
+
+    assert_eq!(
+        schedule_background(BackgroundTask::new("missing", 1)),
+        Err(BackgroundError::Unregistered("missing".into()))
+    );
+}
+
+#[test]
+fn waits_for_the_delay() {
+    let _guard = setup();
+
+    let start = Instant::now();
+    let id =
+        schedule_background(BackgroundTask::new("double", 1).delay(Duration::from_millis(200)))
+            .unwrap();
+
+    assert_eq!(wait_for(id).output::<u64>(), Ok(2));
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[test]
+fn hook_delivers_results_to_components() {
+    static RESULT: AtomicU64 = AtomicU64::new(0);
+    let _guard = setup();
+
+    fn app() -> Element {
+        let results = use_background_results();
+        use_hook(|| schedule_background(BackgroundTask::new("double", 50)).unwrap());
+        if let Some(result) = results.read().first() {
+            RESULT.store(result.output().unwrap(), Ordering::SeqCst);
+        }
+        rsx! {}
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    // The lock of the test is held outside of the runtime so it isn't held across an await
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while RESULT.load(Ordering::SeqCst) == 0 {
+                dom.wait_for_work().await;
+                dom.render_immediate(&mut dioxus_core::NoOpMutations);
+            }
+        })
+        .await
+        .unwrap();
+    });
+
+    assert_eq!(RESULT.load(Ordering::SeqCst), 100);
+}
@@ -0,0 +1,206 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// A drawing command of a [`Context2d`]. Commands either call a method or set a property of the
+/// [`CanvasRenderingContext2D`](https://developer.mozilla.org/en-US/docs/Web/API/CanvasRenderingContext2D) they are
+/// replayed on.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub(crate) enum Command {
+    Call {
+        call: &'static str,
+        args: Vec<Value>,
+    },
+    Set {
+        set: &'static str,
+        value: Value,
+    },
+}
+
+/// Records 2D drawing commands for a [`Canvas`](crate::Canvas).
+///
+/// The methods mirror the
+/// [`CanvasRenderingContext2D`](https://developer.mozilla.org/en-US/docs/Web/API/CanvasRenderingContext2D) API. The
+/// commands are replayed in the webview or browser that shows the canvas, so drawing works the same in every renderer.
+/// Coordinates are in css pixels. The canvas is scaled to the device pixel ratio before the commands run, so drawings
+/// stay sharp on high density screens.
+///
+/// Every frame starts from the default state of the context, so styles set in one frame don't carry over to the next.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Context2d {
+    pub(crate) commands: Vec<Command>,
+}
+
+impl Context2d {
+    fn call(&mut self, call: &'static str, args: impl IntoIterator<Item = Value>) -> &mut Self {
+        self.commands.push(Command::Call {
+            call,
+            args: args.into_iter().collect(),
+        });
+        self
+    }
+
+    fn set(&mut self, set: &'static str, value: impl Into<Value>) -> &mut Self {
+        self.commands.push(Command::Set {
+            set,
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Set the css color, gradient or pattern shapes are filled with
+    pub fn set_fill_style(&mut self, style: &str) -> &mut Self {
+        self.set("fillStyle", style)
+    }
+
+    /// Set the css color, gradient or pattern lines are drawn with
+    pub fn set_stroke_style(&mut self, style: &str) -> &mut Self {
+        self.set("strokeStyle", style)
+    }
+
+    /// Set the width of lines
+    pub fn set_line_width(&mut self, width: f64) -> &mut Self {
+        self.set("lineWidth", width)
+    }
+
+    /// Set the css font text is drawn with, like `16px sans-serif`
+    pub fn set_font(&mut self, font: &str) -> &mut Self {
+        self.set("font", font)
+    }
+
+    /// Set how text is aligned to the position it is drawn at: `start`, `end`, `left`, `right` or `center`
+    pub fn set_text_align(&mut self, align: &str) -> &mut Self {
+        self.set("textAlign", align)
+    }
+
+    /// Set the baseline of text: `top`, `hanging`, `middle`, `alphabetic`, `ideographic` or `bottom`
+    pub fn set_text_baseline(&mut self, baseline: &str) -> &mut Self {
+        self.set("textBaseline", baseline)
+    }
+
+    /// Set the opacity everything is drawn with, from `0.0` to `1.0`
+    pub fn set_global_alpha(&mut self, alpha: f64) -> &mut Self {
+        self.set("globalAlpha", alpha)
+    }
+
+    /// Clear a rectangle to transparent black
+    pub fn clear_rect(&mut self, x: f64, y: f64, width: f64, height: f64) -> &mut Self {
+        self.call("clearRect", [x, y, width, height].map(Value::from))
+    }
+
+    /// Fill a rectangle with the fill style
+    pub fn fill_rect(&mut self, x: f64, y: f64, width: f64, height: f64) -> &mut Self {
+        self.call("fillRect", [x, y, width, height].map(Value::from))
+    }
+
+    /// Draw the outline of a rectangle with the stroke style
+    pub fn stroke_rect(&mut self, x: f64, y: f64, width: f64, height: f64) -> &mut Self {
+        self.call("strokeRect", [x, y, width, height].map(Value::from))
+    }
+
+    /// Start a new path
+    pub fn begin_path(&mut self) -> &mut Self {
+        self.call("beginPath", [])
+    }
+
+    /// Draw a line from the current point back to the start of the path
+    pub fn close_path(&mut self) -> &mut Self {
+        self.call("closePath", [])
+    }
+
+    /// Start a new sub path at a point
+    pub fn move_to(&mut self, x: f64, y: f64) -> &mut Self {
+        self.call("moveTo", [x, y].map(Value::from))
+    }
+
+    /// Add a straight line to a point to the path
+    pub fn line_to(&mut self, x: f64, y: f64) -> &mut Self {
+        self.call("lineTo", [x, y].map(Value::from))
+    }
+
+    /// Add a quadratic bézier curve to the path
+    pub fn quadratic_curve_to(&mut self, cpx: f64, cpy: f64, x: f64, y: f64) -> &mut Self {
+        self.call("quadraticCurveTo", [cpx, cpy, x, y].map(Value::from))
+    }
+
+    /// Add a cubic bézier curve to the path
+    pub fn bezier_curve_to(
+        &mut self,
+        cp1x: f64,
+        cp1y: f64,
+        cp2x: f64,
+        cp2y: f64,
+        x: f64,
+        y: f64,
+    ) -> &mut Self {
+        self.call(
+            "bezierCurveTo",
+            [cp1x, cp1y, cp2x, cp2y, x, y].map(Value::from),
+        )
+    }
+
+    /// Add a circular arc around a center to the path. The angles are in radians, clockwise from the positive x axis.
+    pub fn arc(
+        &mut self,
+        x: f64,
+        y: f64,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    ) -> &mut Self {
+        self.call(
+            "arc",
+            [x, y, radius, start_angle, end_angle].map(Value::from),
+        )
+    }
+
+    /// Add a rectangle to the path
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) -> &mut Self {
+        self.call("rect", [x, y, width, height].map(Value::from))
+    }
+
+    /// Fill the path with the fill style
+    pub fn fill(&mut self) -> &mut Self {
+        self.call("fill", [])
+    }
+
+    /// Draw the path with the stroke style
+    pub fn stroke(&mut self) -> &mut Self {
+        self.call("stroke", [])
+    }
+
+    /// Fill text at a position with the fill style
+    pub fn fill_text(&mut self, text: &str, x: f64, y: f64) -> &mut Self {
+        self.call("fillText", [text.into(), x.into(), y.into()])
+    }
+
+    /// Draw the outline of text at a position with the stroke style
+    pub fn stroke_text(&mut self, text: &str, x: f64, y: f64) -> &mut Self {
+        self.call("strokeText", [text.into(), x.into(), y.into()])
+    }
+
+    /// Push the current styles and transform onto a stack
+    pub fn save(&mut self) -> &mut Self {
+        self.call("save", [])
+    }
+
+    /// Pop the styles and transform saved by the last [`Context2d::save`]
+    pub fn restore(&mut self) -> &mut Self {
+        self.call("restore", [])
+    }
+
+    /// Move the origin of everything drawn after this
+    pub fn translate(&mut self, x: f64, y: f64) -> &mut Self {
+        self.call("translate", [x, y].map(Value::from))
+    }
+
+    /// Rotate everything drawn after this clockwise around the origin. The angle is in radians.
+    pub fn rotate(&mut self, angle: f64) -> &mut Self {
+        self.call("rotate", [Value::from(angle)])
+    }
+
+    /// Scale everything drawn after this
+    pub fn scale(&mut self, x: f64, y: f64) -> &mut Self {
+        self.call("scale", [x, y].map(Value::from))
+    }
+}
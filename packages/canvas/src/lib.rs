@@ -0,0 +1,351 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+use dioxus_lib::document::{self, Eval};
+use dioxus_lib::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+mod context;
+#[cfg(feature = "web")]
+mod web;
+pub use context::*;
+
+/// Watches the size of the canvas and the device pixel ratio of the screen it is on, and sends the size to rust when
+/// either changes
+const WATCH_SIZE: &str = r#"
+const [id, resize] = await dioxus.recv();
+const canvas = document.getElementById(id);
+if (canvas) {
+    let last = "";
+    const report = () => {
+        if (!canvas.isConnected) {
+            observer.disconnect();
+            return;
+        }
+        const rect = canvas.getBoundingClientRect();
+        const size = { width: rect.width, height: rect.height, device_pixel_ratio: window.devicePixelRatio || 1 };
+        const key = JSON.stringify(size);
+        if (key === last) {
+            return;
+        }
+        last = key;
+        // Canvases transferred to an OffscreenCanvas are resized from rust
+        if (resize) {
+            canvas.width = Math.max(1, Math.round(size.width * size.device_pixel_ratio));
+            canvas.height = Math.max(1, Math.round(size.height * size.device_pixel_ratio));
+        }
+        dioxus.send(size);
+    };
+    const observer = new ResizeObserver(report);
+    observer.observe(canvas);
+    // The device pixel ratio changes when the window moves to another screen or the page is zoomed
+    const watchRatio = () => matchMedia(`(resolution: ${window.devicePixelRatio}dppx)`)
+        .addEventListener("change", () => { report(); watchRatio(); }, { once: true });
+    watchRatio();
+    report();
+}
+"#;
+
+/// Replays the frames drawn with a [`Context2d`] on the canvas
+const PAINT: &str = r#"
+const id = await dioxus.recv();
+const canvas = document.getElementById(id);
+const context = canvas && canvas.getContext("2d");
+while (context) {
+    const frame = await dioxus.recv();
+    context.save();
+    context.setTransform(frame.scale, 0, 0, frame.scale, 0, 0);
+    for (const command of frame.commands) {
+        if ("call" in command) {
+            context[command.call](...command.args);
+        } else {
+            context[command.set] = command.value;
+        }
+    }
+    context.restore();
+}
+"#;
+
+/// The size of a [`Canvas`] on the screen
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CanvasSize {
+    /// The width of the canvas in css pixels
+    pub width: f64,
+    /// The height of the canvas in css pixels
+    pub height: f64,
+    /// The number of device pixels per css pixel of the screen the canvas is on
+    pub device_pixel_ratio: f64,
+}
+
+impl CanvasSize {
+    /// The width of the canvas in device pixels. This is the width of the drawing buffer of the canvas.
+    pub fn pixel_width(&self) -> u32 {
+        (self.width * self.device_pixel_ratio).round().max(1.0) as u32
+    }
+
+    /// The height of the canvas in device pixels. This is the height of the drawing buffer of the canvas.
+    pub fn pixel_height(&self) -> u32 {
+        (self.height * self.device_pixel_ratio).round().max(1.0) as u32
+    }
+}
+
+/// A frame of a [`Canvas`] passed to [`CanvasProps::onpaint`]. Frames can be kept to draw again later, like in an
+/// animation loop.
+#[derive(Clone)]
+pub struct CanvasFrame {
+    size: CanvasSize,
+    surface: Surface,
+}
+
+impl CanvasFrame {
+    /// Get the size of the canvas when the frame was painted
+    pub fn size(&self) -> CanvasSize {
+        self.size
+    }
+
+    /// Draw on the canvas. The commands are sent to the canvas in one batch after the closure returns.
+    pub fn draw(&self, draw: impl FnOnce(&mut Context2d)) {
+        let mut context = Context2d::default();
+        draw(&mut context);
+        self.surface.paint(self.size, &context.commands);
+    }
+
+    /// Get the [`OffscreenCanvas`](web_sys::OffscreenCanvas) the canvas was transferred to. Use it to draw with
+    /// WebGL, WebGPU or the 2D context directly. It is `None` outside of the browser.
+    ///
+    /// The drawing buffer is resized to [`CanvasSize::pixel_width`] and [`CanvasSize::pixel_height`] before every
+    /// paint. Don't mix a 2D context from this canvas with [`CanvasFrame::draw`]; a canvas only has one kind of
+    /// context.
+    #[cfg(feature = "web")]
+    pub fn offscreen_canvas(&self) -> Option<web_sys::OffscreenCanvas> {
+        self.surface
+            .0
+            .offscreen
+            .borrow()
+            .as_ref()
+            .map(|offscreen| offscreen.canvas())
+    }
+}
+
+impl Debug for CanvasFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CanvasFrame")
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Where the frames of a canvas are drawn
+#[derive(Clone)]
+struct Surface(Rc<SurfaceState>);
+
+struct SurfaceState {
+    id: String,
+    painter: RefCell<Option<Eval>>,
+    #[cfg(feature = "web")]
+    offscreen: RefCell<Option<web::Offscreen>>,
+}
+
+impl Surface {
+    fn new(id: String) -> Self {
+        Self(Rc::new(SurfaceState {
+            id,
+            painter: RefCell::new(None),
+            #[cfg(feature = "web")]
+            offscreen: RefCell::new(None),
+        }))
+    }
+
+    /// Connect to the canvas element after it is mounted. Web apps draw on an OffscreenCanvas from rust, everything
+    /// else replays the frames in the webview.
+    fn attach(&self) {
+        #[cfg(feature = "web")]
+        if let Some(offscreen) = web::Offscreen::transfer(&self.0.id) {
+            *self.0.offscreen.borrow_mut() = Some(offscreen);
+            return;
+        }
+
+        let painter = document::eval(PAINT);
+        if let Err(err) = painter.send(&self.0.id) {
+            tracing::error!("Failed to connect to the canvas: {err}");
+        }
+        *self.0.painter.borrow_mut() = Some(painter);
+    }
+
+    /// Check if the element is resized in the webview instead of from rust
+    fn resizes_element(&self) -> bool {
+        #[cfg(feature = "web")]
+        if self.0.offscreen.borrow().is_some() {
+            return false;
+        }
+        true
+    }
+
+    fn resize(&self, _size: CanvasSize) {
+        #[cfg(feature = "web")]
+        if let Some(offscreen) = &*self.0.offscreen.borrow() {
+            offscreen.resize(_size);
+        }
+    }
+
+    fn paint(&self, size: CanvasSize, commands: &[Command]) {
+        #[cfg(feature = "web")]
+        if let Some(offscreen) = &*self.0.offscreen.borrow() {
+            offscreen.paint(size.device_pixel_ratio, commands);
+            return;
+        }
+
+        #[derive(Serialize)]
+        struct Frame<'a> {
+            scale: f64,
+            commands: &'a [Command],
+        }
+
+        if let Some(painter) = &*self.0.painter.borrow() {
+            let frame = Frame {
+                scale: size.device_pixel_ratio,
+                commands,
+            };
+            if let Err(err) = painter.send(frame) {
+                tracing::error!("Failed to draw on the canvas: {err}");
+            }
+        }
+    }
+}
+
+/// The properties of the [`Canvas`] component
+#[derive(Props, Clone, PartialEq)]
+pub struct CanvasProps {
+    /// Paint the canvas. This runs once the size of the canvas is known, every time the canvas is resized or moves to
+    /// a screen with a different device pixel ratio, and when signals read in the callback change.
+    pub onpaint: Callback<CanvasFrame>,
+
+    /// Called when the canvas is clicked. The element coordinates of the event are in the same css pixels the canvas
+    /// is drawn in.
+    pub onclick: Option<EventHandler<MouseEvent>>,
+
+    /// Called when a pointer is pressed on the canvas
+    pub onpointerdown: Option<EventHandler<PointerEvent>>,
+
+    /// Called when a pointer moves over the canvas
+    pub onpointermove: Option<EventHandler<PointerEvent>>,
+
+    /// Called when a pointer is released on the canvas
+    pub onpointerup: Option<EventHandler<PointerEvent>>,
+
+    /// Called when the user scrolls over the canvas
+    pub onwheel: Option<EventHandler<WheelEvent>>,
+
+    /// Additional attributes for the canvas element. Size the canvas with css; the drawing buffer follows the size of
+    /// the element.
+    #[props(extends = canvas, extends = GlobalAttributes)]
+    pub attributes: Vec<Attribute>,
+}
+
+/// A `canvas` element to draw charts, games and other custom graphics inside of the component tree.
+///
+/// The canvas keeps its drawing buffer at the size of the element in device pixels and calls
+/// [`CanvasProps::onpaint`] with a [`CanvasFrame`] whenever it needs to be painted again. Frames drawn with
+/// [`CanvasFrame::draw`] work in every renderer: web apps draw on an
+/// [`OffscreenCanvas`](https://developer.mozilla.org/en-US/docs/Web/API/OffscreenCanvas) with the `web` feature, and
+/// desktop, mobile and liveview apps replay the frames in the webview.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_canvas::{Canvas, CanvasFrame};
+///
+/// fn Chart() -> Element {
+///     let values = use_signal(|| vec![3.0, 7.0, 4.0, 9.0]);
+///
+///     rsx! {
+///         Canvas {
+///             style: "width: 100%; height: 200px",
+///             onpaint: move |frame: CanvasFrame| {
+///                 let size = frame.size();
+///                 let values = values.read();
+///                 let width = size.width / values.len() as f64;
+///                 frame.draw(|context| {
+///                     context.clear_rect(0.0, 0.0, size.width, size.height);
+///                     context.set_fill_style("steelblue");
+///                     for (i, value) in values.iter().enumerate() {
+///                         let height = size.height * value / 10.0;
+///                         context.fill_rect(i as f64 * width, size.height - height, width - 4.0, height);
+///                     }
+///                 });
+///             },
+///         }
+///     }
+/// }
+/// ```
+///
+/// The `wgpu` surface of a native renderer is not supported yet. Apps that need WebGL or WebGPU on the web can draw on
+/// [`CanvasFrame::offscreen_canvas`] with the `web` feature.
+#[component]
+pub fn Canvas(props: CanvasProps) -> Element {
+    let id = use_hook(|| format!("dioxus-canvas-{}", current_scope_id().unwrap().0));
+    let surface = use_hook(|| Surface::new(id.clone()));
+    let mut size = use_signal(|| None::<CanvasSize>);
+
+    // Connect to the canvas and start watching its size once it is mounted
+    use_effect({
+        let surface = surface.clone();
+        move || {
+            surface.attach();
+            let mut watcher = document::eval(WATCH_SIZE);
+            if let Err(err) = watcher.send((&surface.0.id, surface.resizes_element())) {
+                tracing::error!("Failed to watch the size of the canvas: {err}");
+                return;
+            }
+            spawn(async move {
+                while let Ok(new_size) = watcher.recv::<CanvasSize>().await {
+                    size.set(Some(new_size));
+                }
+            });
+        }
+    });
+
+    let onpaint = props.onpaint;
+    use_effect(move || {
+        let Some(size) = size() else {
+            return;
+        };
+        surface.resize(size);
+        onpaint.call(CanvasFrame {
+            size,
+            surface: surface.clone(),
+        });
+    });
+
+    rsx! {
+        canvas {
+            id,
+            onclick: move |event| {
+                if let Some(handler) = props.onclick {
+                    handler.call(event);
+                }
+            },
+            onpointerdown: move |event| {
+                if let Some(handler) = props.onpointerdown {
+                    handler.call(event);
+                }
+            },
+            onpointermove: move |event| {
+                if let Some(handler) = props.onpointermove {
+                    handler.call(event);
+                }
+            },
+            onpointerup: move |event| {
+                if let Some(handler) = props.onpointerup {
+                    handler.call(event);
+                }
+            },
+            onwheel: move |event| {
+                if let Some(handler) = props.onwheel {
+                    handler.call(event);
+                }
+            },
+            ..props.attributes,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{CanvasSize, Command};
+use js_sys::{Array, Function, Reflect};
+use serde_json::Value;
+use std::cell::OnceCell;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{HtmlCanvasElement, OffscreenCanvas};
+
+/// A canvas element transferred to an [`OffscreenCanvas`] that rust draws on directly
+pub(crate) struct Offscreen {
+    canvas: OffscreenCanvas,
+    /// The 2D context is only created for the first frame drawn with a [`crate::Context2d`], so apps can get a WebGL or
+    /// WebGPU context from the canvas instead
+    context: OnceCell<Option<JsValue>>,
+}
+
+impl Offscreen {
+    /// Transfer control of the canvas element with this id to an [`OffscreenCanvas`]. Returns `None` outside of the
+    /// browser or if the browser doesn't support offscreen canvases.
+    pub(crate) fn transfer(id: &str) -> Option<Self> {
+        if !cfg!(target_arch = "wasm32") {
+            return None;
+        }
+
+        let element = web_sys::window()?.document()?.get_element_by_id(id)?;
+        let canvas = element
+            .dyn_into::<HtmlCanvasElement>()
+            .ok()?
+            .transfer_control_to_offscreen()
+            .inspect_err(|err| tracing::error!("Failed to transfer the canvas offscreen: {err:?}"))
+            .ok()?;
+
+        Some(Self {
+            canvas,
+            context: OnceCell::new(),
+        })
+    }
+
+    pub(crate) fn canvas(&self) -> OffscreenCanvas {
+        self.canvas.clone()
+    }
+
+    pub(crate) fn resize(&self, size: CanvasSize) {
+        let (width, height) = (size.pixel_width(), size.pixel_height());
+        if self.canvas.width() != width {
+            self.canvas.set_width(width);
+        }
+        if self.canvas.height() != height {
+            self.canvas.set_height(height);
+        }
+    }
+
+    pub(crate) fn paint(&self, scale: f64, commands: &[Command]) {
+        let context = self.context.get_or_init(|| {
+            self.canvas
+                .get_context("2d")
+                .inspect_err(|err| tracing::error!("Failed to get the 2d context: {err:?}"))
+                .ok()
+                .flatten()
+                .map(JsValue::from)
+        });
+        let Some(context) = context else {
+            return;
+        };
+
+        if let Err(err) = replay(context, scale, commands) {
+            tracing::error!("Failed to draw on the canvas: {err:?}");
+        }
+    }
+}
+
+/// Run the commands on a 2D context the same way the webview replays them
+fn replay(context: &JsValue, scale: f64, commands: &[Command]) -> Result<(), JsValue> {
+    let call = |name: &str, args: Array| -> Result<(), JsValue> {
+        let method: Function = Reflect::get(context, &JsValue::from_str(name))?.dyn_into()?;
+        Reflect::apply(&method, context, &args)?;
+        Ok(())
+    };
+
+    call("save", Array::new())?;
+    let transform = [scale, 0.0, 0.0, scale, 0.0, 0.0];
+    call(
+        "setTransform",
+        transform.into_iter().map(JsValue::from_f64).collect(),
+    )?;
+    for command in commands {
+        match command {
+            Command::Call { call: name, args } => call(name, args.iter().map(to_js).collect())?,
+            Command::Set { set, value } => {
+                Reflect::set(context, &JsValue::from_str(set), &to_js(value))?;
+            }
+        }
+    }
+    call("restore", Array::new())
+}
+
+fn to_js(value: &Value) -> JsValue {
+    match value {
+        Value::Bool(value) => JsValue::from_bool(*value),
+        Value::Number(value) => JsValue::from_f64(value.as_f64().unwrap_or_default()),
+        Value::String(value) => JsValue::from_str(value),
+        _ => JsValue::NULL,
+    }
+}
@@ -0,0 +1,230 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_canvas::*;
+use dioxus_document::{Document, Eval, EvalError, Evaluator};
+use dioxus_ssr::testing::ComponentHarness;
+use generational_box::{Owner, UnsyncStorage};
+use serde_json::{json, Value};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+/// A script evaluated by [`ScriptedDocument`]
+#[derive(Default)]
+struct Script {
+    js: String,
+    sent: RefCell<Vec<Value>>,
+    replies: RefCell<VecDeque<Value>>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl Script {
+    fn reply(&self, value: Value) {
+        self.replies.borrow_mut().push_back(value);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn sent(&self) -> Vec<Value> {
+        self.sent.borrow().clone()
+    }
+}
+
+struct ScriptEvaluator(Rc<Script>);
+
+impl Evaluator for ScriptEvaluator {
+    fn send(&self, data: Value) -> Result<(), EvalError> {
+        self.0.sent.borrow_mut().push(data);
+        Ok(())
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        match self.0.replies.borrow_mut().pop_front() {
+            Some(value) => Poll::Ready(Ok(value)),
+            None => {
+                *self.0.waker.borrow_mut() = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    fn poll_join(&mut self, _: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        Poll::Pending
+    }
+}
+
+/// A document that plays the part of the webview for the scripts of the canvas
+#[derive(Default)]
+struct ScriptedDocument {
+    owner: Owner<UnsyncStorage>,
+    scripts: RefCell<Vec<Rc<Script>>>,
+}
+
+impl ScriptedDocument {
+    fn script(&self, contains: &str) -> Rc<Script> {
+        self.scripts
+            .borrow()
+            .iter()
+            .find(|script| script.js.contains(contains))
+            .cloned()
+            .unwrap_or_else(|| panic!("no script contains {contains:?}"))
+    }
+
+    /// The script that watches the size of the canvas
+    fn watcher(&self) -> Rc<Script> {
+        self.script("ResizeObserver")
+    }
+
+    /// The frames sent to the script that paints the canvas
+    fn frames(&self) -> Vec<Value> {
+        self.script("getContext").sent()[1..].to_vec()
+    }
+}
+
+impl Document for ScriptedDocument {
+    fn eval(&self, js: String) -> Eval {
+        let script = Rc::new(Script {
+            js,
+            ..Default::default()
+        });
+        self.scripts.borrow_mut().push(script.clone());
+        Eval::new(
+            self.owner
+                .insert(Box::new(ScriptEvaluator(script)) as Box<dyn Evaluator>),
+        )
+    }
+}
+
+fn harness(app: fn() -> Element) -> (ComponentHarness, Rc<ScriptedDocument>) {
+    let document = Rc::new(ScriptedDocument::default());
+    let harness = ComponentHarness::new(app).with_context(document.clone() as Rc<dyn Document>);
+    (harness, document)
+}
+
+fn size(width: f64, height: f64, device_pixel_ratio: f64) -> Value {
+    json!({ "width": width, "height": height, "device_pixel_ratio": device_pixel_ratio })
+}
+
+#[test]
+fn frames_are_painted_at_the_size_of_the_canvas() {
+    fn App() -> Element {
+        rsx! {
+            Canvas {
+                class: "chart",
+                onpaint: move |frame: CanvasFrame| {
+                    let size = frame.size();
+                    frame.draw(|context| {
+                        context.set_fill_style("red").fill_rect(0.0, 0.0, size.width, size.height);
+                    });
+                },
+            }
+        }
+    }
+
+    let (mut harness, document) = harness(App);
+    let html = harness.render();
+    assert!(html.starts_with(r#"<canvas id="dioxus-canvas-"#), "{html}");
+    assert!(html.contains(r#"class="chart""#), "{html}");
+
+    // Both scripts find the canvas by its id, and the webview resizes the drawing buffer
+    let id = json!(harness.find_by_attribute("class", "chart").attribute("id"));
+    assert_eq!(document.script("getContext").sent(), std::slice::from_ref(&id));
+    assert_eq!(document.watcher().sent(), [json!([id, true])]);
+
+    // Nothing is painted until the size is known
+    assert!(document.frames().is_empty());
+
+    document.watcher().reply(size(200.0, 100.0, 2.0));
+    harness.settle();
+    assert_eq!(
+        document.frames(),
+        [json!({
+            "scale": 2.0,
+            "commands": [
+                { "set": "fillStyle", "value": "red" },
+                { "call": "fillRect", "args": [0.0, 0.0, 200.0, 100.0] },
+            ],
+        })]
+    );
+
+    // Moving to a screen with a different pixel ratio paints again
+    document.watcher().reply(size(200.0, 100.0, 3.0));
+    harness.settle();
+    assert_eq!(document.frames().len(), 2);
+    assert_eq!(document.frames()[1]["scale"], 3.0);
+}
+
+#[test]
+fn frames_are_painted_again_when_the_signals_they_read_change() {
+    fn App() -> Element {
+        let mut count = use_signal(|| 1);
+        rsx! {
+            button { id: "more", onclick: move |_| count += 1 }
+            Canvas {
+                onpaint: move |frame: CanvasFrame| {
+                    let count = count();
+                    frame.draw(|context| {
+                        for i in 0..count {
+                            context.fill_rect(i as f64 * 10.0, 0.0, 5.0, 5.0);
+                        }
+                    });
+                },
+            }
+        }
+    }
+
+    let (mut harness, document) = harness(App);
+    harness.render();
+    document.watcher().reply(size(100.0, 100.0, 1.0));
+    harness.settle();
+    assert_eq!(
+        document.frames()[0]["commands"].as_array().unwrap().len(),
+        1
+    );
+
+    harness.find_by_attribute("id", "more").click();
+    harness.settle();
+    assert_eq!(document.frames().len(), 2);
+    assert_eq!(
+        document.frames()[1]["commands"].as_array().unwrap().len(),
+        2
+    );
+}
+
+#[test]
+fn events_on_the_canvas_are_routed_to_the_handlers() {
+    fn App() -> Element {
+        let mut clicks = use_signal(|| 0);
+        rsx! {
+            p { "{clicks}" }
+            Canvas {
+                class: "target",
+                onclick: move |_| clicks += 1,
+                onpaint: |_| {},
+            }
+        }
+    }
+
+    let (mut harness, _document) = harness(App);
+    assert!(harness.render().starts_with("<p>0</p>"));
+
+    harness.find_by_attribute("class", "target").click();
+    assert!(harness.render().starts_with("<p>1</p>"));
+}
+
+#[test]
+fn pixel_sizes_round_to_the_drawing_buffer() {
+    let size = CanvasSize {
+        width: 100.5,
+        height: 0.0,
+        device_pixel_ratio: 1.5,
+    };
+    assert_eq!(size.pixel_width(), 151);
+    // The drawing buffer is never empty
+    assert_eq!(size.pixel_height(), 1);
+}
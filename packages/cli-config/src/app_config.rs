@@ -0,0 +1,174 @@
+//! Typed app configuration that is read from the environment.
+//!
+//! Define the configuration once with the `dioxus_config!` macro. Public fields are read from the `[env.<name>]`
+//! table of the Dioxus.toml that the CLI bakes into every build. On the server, public and secret fields are read
+//! from runtime environment variables, and public fields fall back to the baked in value.
+//!
+//! Secret fields only exist when the `server` feature of your crate is enabled, so they can't be read, or shipped
+//! to the client, by accident.
+
+use std::{fmt::Display, str::FromStr};
+
+/// The prefix of the environment variables the CLI sets while building the app for every public config value
+pub const CONFIG_ENV_PREFIX: &str = "DIOXUS_CONFIG_";
+
+/// A configuration struct created with the `dioxus_config!` macro.
+pub trait DioxusConfig: Sized + Send + Sync + 'static {
+    /// Read the configuration from the environment
+    fn load() -> Result<Self, ConfigError>;
+
+    /// Get the configuration of the app. The configuration is loaded the first time this is called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a required value is missing or can't be parsed.
+    fn get() -> &'static Self;
+}
+
+/// Get the configuration of the app. The configuration is loaded once and shared for the lifetime of the app, so
+/// this can be called inside and outside of components.
+///
+/// ```rust, ignore
+/// dioxus_config! {
+///     pub struct AppConfig {
+///         pub api_url: String,
+///     }
+/// }
+///
+/// fn App() -> Element {
+///     let config = use_config::<AppConfig>();
+///     rsx! { "The api is hosted at {config.api_url}" }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if a required value is missing or can't be parsed.
+pub fn use_config<T: DioxusConfig>() -> &'static T {
+    T::get()
+}
+
+/// An error that occurred while loading a [`DioxusConfig`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// A required value wasn't set
+    Missing {
+        /// The name of the environment variable
+        key: &'static str,
+    },
+    /// A value couldn't be parsed into the type of its field
+    Invalid {
+        /// The name of the environment variable
+        key: &'static str,
+        /// The value of the environment variable
+        value: String,
+        /// Why the value couldn't be parsed
+        error: String,
+    },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Missing { key } => write!(
+                f,
+                "the config value `{key}` is not set. Add it to the `[env]` table in your Dioxus.toml or set the `{key}` environment variable on the server"
+            ),
+            ConfigError::Invalid { key, value, error } => {
+                write!(f, "the config value `{key}` is invalid ({value:?}): {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Read a config value. Runtime environment variables are only read on the server and take priority over the value
+/// that was baked in when the app was built.
+#[doc(hidden)]
+pub fn __read_config_value<T>(
+    key: &'static str,
+    built: Option<&'static str>,
+    runtime: bool,
+) -> Result<Option<T>, ConfigError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = match runtime {
+        true => std::env::var(key).ok().or_else(|| built.map(String::from)),
+        false => built.map(String::from),
+    };
+
+    value
+        .map(|value| {
+            value.parse().map_err(|err: T::Err| ConfigError::Invalid {
+                key,
+                error: err.to_string(),
+                value,
+            })
+        })
+        .transpose()
+}
+
+/// Read a config value that must be set
+#[doc(hidden)]
+pub fn __require_config_value<T>(
+    key: &'static str,
+    built: Option<&'static str>,
+    runtime: bool,
+) -> Result<T, ConfigError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    __read_config_value(key, built, runtime)?.ok_or(ConfigError::Missing { key })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_values_override_baked_public_values() {
+        const KEY: &str = "DIOXUS_TEST_CONFIG_API_URL";
+        let read = |runtime| __read_config_value::<String>(KEY, Some("baked"), runtime).unwrap();
+
+        std::env::remove_var(KEY);
+        assert_eq!(read(true).as_deref(), Some("baked"));
+
+        std::env::set_var(KEY, "runtime");
+        assert_eq!(read(true).as_deref(), Some("runtime"));
+        // Clients only see the baked value
+        assert_eq!(read(false).as_deref(), Some("baked"));
+        std::env::remove_var(KEY);
+    }
+
+    #[test]
+    fn secrets_are_only_read_at_runtime() {
+        const KEY: &str = "DIOXUS_TEST_CONFIG_DATABASE_URL";
+
+        std::env::remove_var(KEY);
+        assert_eq!(
+            __require_config_value::<String>(KEY, None, true),
+            Err(ConfigError::Missing { key: KEY })
+        );
+
+        std::env::set_var(KEY, "postgres://localhost");
+        assert_eq!(
+            __require_config_value::<String>(KEY, None, true).as_deref(),
+            Ok("postgres://localhost")
+        );
+        std::env::remove_var(KEY);
+    }
+
+    #[test]
+    fn invalid_values_are_errors() {
+        const KEY: &str = "DIOXUS_TEST_CONFIG_PAGE_SIZE";
+        let error = __read_config_value::<usize>(KEY, Some("many"), false).unwrap_err();
+        assert!(matches!(
+            error,
+            ConfigError::Invalid { key: KEY, ref value, .. } if value == "many"
+        ));
+    }
+}
@@ -56,6 +56,9 @@ use std::{
     path::PathBuf,
 };
 
+mod app_config;
+pub use app_config::*;
+
 pub const CLI_ENABLED_ENV: &str = "DIOXUS_CLI_ENABLED";
 pub const SERVER_IP_ENV: &str = "IP";
 pub const SERVER_PORT_ENV: &str = "PORT";
@@ -63,6 +66,7 @@ pub const DEVSERVER_RAW_ADDR_ENV: &str = "DIOXUS_DEVSERVER_ADDR";
 pub const ALWAYS_ON_TOP_ENV: &str = "DIOXUS_ALWAYS_ON_TOP";
 pub const ASSET_ROOT_ENV: &str = "DIOXUS_ASSET_ROOT";
 pub const APP_TITLE_ENV: &str = "DIOXUS_APP_TITLE";
+pub const CROSS_ORIGIN_ISOLATED_ENV: &str = "DIOXUS_CROSS_ORIGIN_ISOLATED";
 
 #[deprecated(since = "0.6.0", note = "The CLI currently does not set this.")]
 #[doc(hidden)]
@@ -195,6 +199,14 @@ pub fn always_on_top() -> Option<bool> {
         .and_then(|s| s.parse().ok())
 }
 
+/// Check if the server should send the headers that make the page cross-origin isolated.
+///
+/// The CLI sets this for apps built with `--threads`, since browsers only share memory between web workers on
+/// cross-origin isolated pages.
+pub fn cross_origin_isolated() -> bool {
+    read_env_config!("DIOXUS_CROSS_ORIGIN_ISOLATED").is_some_and(|value| value == "true")
+}
+
 /// Check if the CLI is enabled when running the application.
 ///
 /// The CLI *always* sets this value to true when running the application.
@@ -8,7 +8,9 @@ use lightningcss::{
     stylesheet::{MinifyOptions, ParserOptions, StyleSheet},
     targets::{Browsers, Targets},
 };
-use manganis_core::CssAssetOptions;
+use manganis_core::{scope_css_module, AssetOptions, CssAssetOptions, CssModuleAssetOptions};
+
+use crate::AssetManifest;
 
 pub(crate) fn process_css(
     css_options: &CssAssetOptions,
@@ -78,6 +80,82 @@ pub(crate) fn minify_css(css: &str) -> anyhow::Result<String> {
     Ok(res.code)
 }
 
+/// Read a css module, scope its classes and minify it if the options ask for it
+fn css_module_contents(options: &CssModuleAssetOptions, source: &Path) -> anyhow::Result<String> {
+    let css = std::fs::read_to_string(source)?;
+    let css = scope_css_module(&css, options.scope()).css;
+
+    if !options.minified() {
+        return Ok(css);
+    }
+    match minify_css(&css) {
+        Ok(minified) => Ok(minified),
+        Err(err) => {
+            tracing::error!(
+                "Failed to minify css module; Falling back to unminified css. Error: {}",
+                err
+            );
+            Ok(css)
+        }
+    }
+}
+
+pub(crate) fn process_css_module(
+    options: &CssModuleAssetOptions,
+    source: &Path,
+    output_path: &Path,
+) -> anyhow::Result<()> {
+    let css = css_module_contents(options, source)?;
+
+    std::fs::write(output_path, css).with_context(|| {
+        format!(
+            "Failed to write css module to output location: {}",
+            output_path.display()
+        )
+    })?;
+
+    Ok(())
+}
+
+/// Combine every css module in the manifest into the single stylesheet the modules share.
+///
+/// The stylesheet doesn't have a hash in its name, so unlike [`crate::process_file_to`] this always overwrites the
+/// output. Modules are written in the order of their paths so the stylesheet is the same between builds.
+pub fn process_css_modules(manifest: &AssetManifest, output_path: &Path) -> anyhow::Result<()> {
+    let mut modules: Vec<_> = manifest
+        .assets
+        .iter()
+        .filter_map(|(source, asset)| match asset.options() {
+            AssetOptions::CssModule(options) => Some((source, options)),
+            _ => None,
+        })
+        .collect();
+    if modules.is_empty() {
+        return Ok(());
+    }
+    modules.sort_by_key(|(source, _)| *source);
+
+    let mut css = String::new();
+    for (source, options) in modules {
+        let contents = css_module_contents(options, source)
+            .with_context(|| format!("Failed to process css module {}", source.display()))?;
+        css.push_str(&contents);
+        css.push('\n');
+    }
+
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(output_path, css).with_context(|| {
+        format!(
+            "Failed to write css modules to output location: {}",
+            output_path.display()
+        )
+    })?;
+
+    Ok(())
+}
+
 /// Process an scss/sass file into css.
 pub(crate) fn process_scss(
     scss_options: &CssAssetOptions,
@@ -2,11 +2,11 @@ use anyhow::Context;
 use manganis_core::{AssetOptions, CssAssetOptions, ImageAssetOptions, JsAssetOptions};
 use std::path::Path;
 
-use crate::css::process_scss;
+use crate::css::{process_css_module, process_scss};
 
 use super::{
-    css::process_css, folder::process_folder, image::process_image, js::process_js,
-    json::process_json,
+    css::process_css, folder::process_folder, font::process_font, image::process_image,
+    js::process_js, json::process_json,
 };
 
 /// Process a specific file asset with the given options reading from the source and writing to the output path
@@ -72,6 +72,12 @@ pub fn process_file_to(
         AssetOptions::Folder(_) => {
             process_folder(source, output_path)?;
         }
+        AssetOptions::CssModule(options) => {
+            process_css_module(options, source, output_path)?;
+        }
+        AssetOptions::Font(options) => {
+            process_font(options, source, output_path)?;
+        }
         _ => {
             tracing::warn!("Unknown asset options: {:?}", options);
         }
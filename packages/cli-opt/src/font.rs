@@ -0,0 +1,53 @@
+use std::path::Path;
+
+use anyhow::Context;
+use manganis_core::FontAssetOptions;
+
+/// Subset a font to the unicode ranges in the options and convert it to woff2 with `pyftsubset` from
+/// [fonttools](https://github.com/fonttools/fonttools). If fonttools isn't installed, the font is copied as is.
+pub(crate) fn process_font(
+    font_options: &FontAssetOptions,
+    source: &Path,
+    output_path: &Path,
+) -> anyhow::Result<()> {
+    let unicodes = match font_options.unicode_ranges() {
+        [] => "*".to_string(),
+        ranges => ranges
+            .iter()
+            .map(|range| range.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+    };
+
+    let output = std::process::Command::new("pyftsubset")
+        .arg(source)
+        .arg(format!("--output-file={}", output_path.display()))
+        .arg("--flavor=woff2")
+        .arg(format!("--unicodes={unicodes}"))
+        // Keep every OpenType feature and the names so the font renders the same as the original
+        .arg("--layout-features=*")
+        .arg("--name-IDs=*")
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => return Ok(()),
+        Ok(output) => tracing::warn!(
+            "Failed to subset font {}, copying the original font instead: {}",
+            source.display(),
+            String::from_utf8_lossy(&output.stderr)
+        ),
+        Err(_) => tracing::warn!(
+            "Copying font {} without subsetting or compressing it. Install fonttools with `pip install fonttools brotli` to convert fonts to woff2",
+            source.display()
+        ),
+    }
+
+    std::fs::copy(source, output_path).with_context(|| {
+        format!(
+            "Failed to write font to output location: {}",
+            output_path.display()
+        )
+    })?;
+
+    Ok(())
+}
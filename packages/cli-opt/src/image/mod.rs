@@ -1,8 +1,9 @@
 use std::path::Path;
 
 use anyhow::Context;
+use image::DynamicImage;
 use jpg::compress_jpg;
-use manganis_core::{ImageAssetOptions, ImageFormat, ImageSize};
+use manganis_core::{responsive_variant_path, ImageAssetOptions, ImageFormat, ImageSize};
 use png::compress_png;
 
 mod jpg;
@@ -23,6 +24,13 @@ pub(crate) fn process_image(
         }
     }
 
+    // Generate the responsive variants next to the bundled image before it is compressed
+    if let Ok(image) = &image {
+        if !image_options.responsive_widths().is_empty() {
+            process_responsive_variants(image_options, image, output_path);
+        }
+    }
+
     match (image, image_options.format()) {
         (image, ImageFormat::Png) => {
             compress_png(image?, output_path);
@@ -30,18 +38,8 @@ pub(crate) fn process_image(
         (image, ImageFormat::Jpg) => {
             compress_jpg(image?, output_path)?;
         }
-        (Ok(image), ImageFormat::Avif) => {
-            if let Err(error) = image.save(output_path) {
-                tracing::error!("Failed to save avif image: {} with path {}. You must have the avif feature enabled to use avif assets", error, output_path.display());
-            }
-        }
-        (Ok(image), ImageFormat::Webp) => {
-            if let Err(err) = image.save(output_path) {
-                tracing::error!("Failed to save webp image: {}. You must have the avif feature enabled to use webp assets", err);
-            }
-        }
-        (Ok(image), _) => {
-            image.save(output_path)?;
+        (Ok(image), format) => {
+            save_image(image, format, output_path)?;
         }
         // If we can't decode the image or it is of an unknown type, we just copy the file
         _ => {
@@ -60,3 +58,63 @@ pub(crate) fn process_image(
 
     Ok(())
 }
+
+/// Write a decoded image in a format that isn't compressed with a dedicated encoder
+fn save_image(image: DynamicImage, format: ImageFormat, output_path: &Path) -> anyhow::Result<()> {
+    match format {
+        ImageFormat::Avif => {
+            if let Err(error) = image.save(output_path) {
+                tracing::error!("Failed to save avif image: {} with path {}. You must have the avif feature enabled to use avif assets", error, output_path.display());
+            }
+        }
+        ImageFormat::Webp => {
+            if let Err(err) = image.save(output_path) {
+                tracing::error!("Failed to save webp image: {}. You must have the avif feature enabled to use webp assets", err);
+            }
+        }
+        _ => {
+            image.save(output_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write every width and format of a responsive image next to the bundled image
+fn process_responsive_variants(
+    image_options: &ImageAssetOptions,
+    image: &DynamicImage,
+    output_path: &Path,
+) {
+    let Some(bundled_name) = output_path.file_name().and_then(|name| name.to_str()) else {
+        return;
+    };
+
+    for &width in image_options.responsive_widths() {
+        // Never scale images up. Widths larger than the source reuse the original size
+        let resized = if width < image.width() {
+            image.resize(width, u32::MAX, image::imageops::FilterType::Lanczos3)
+        } else {
+            image.clone()
+        };
+
+        for format in image_options.responsive_formats() {
+            let variant_path =
+                output_path.with_file_name(responsive_variant_path(bundled_name, width, format));
+            let result = match format {
+                ImageFormat::Png => {
+                    compress_png(resized.clone(), &variant_path);
+                    Ok(())
+                }
+                ImageFormat::Jpg => compress_jpg(resized.clone(), &variant_path),
+                format => save_image(resized.clone(), format, &variant_path),
+            };
+            if let Err(err) = result {
+                tracing::error!(
+                    "Failed to write responsive image variant {}: {err}",
+                    variant_path.display()
+                );
+            }
+        }
+    }
+}
@@ -9,10 +9,12 @@ use std::{collections::HashMap, path::PathBuf};
 mod css;
 mod file;
 mod folder;
+mod font;
 mod image;
 mod js;
 mod json;
 
+pub use css::process_css_modules;
 pub use file::process_file_to;
 
 /// A manifest of all assets collected from dependencies
@@ -0,0 +1,38 @@
+package dev.dioxus.main;
+
+import android.content.Context
+import androidx.work.Constraints
+import androidx.work.NetworkType
+import androidx.work.OneTimeWorkRequestBuilder
+import androidx.work.WorkManager
+import androidx.work.Worker
+import androidx.work.WorkerParameters
+import androidx.work.workDataOf
+import java.util.concurrent.TimeUnit
+
+// Schedules the tasks of `dioxus_background::schedule_background` with WorkManager. The tasks and their inputs are
+// saved by rust, so the work request only carries the id of the task.
+object Background {
+    @JvmStatic
+    fun schedule(context: Context, id: Long, requiresNetwork: Boolean, delayMillis: Long) {
+        val network = if (requiresNetwork) NetworkType.CONNECTED else NetworkType.NOT_REQUIRED
+        val request = OneTimeWorkRequestBuilder<BackgroundWorker>()
+            .setConstraints(Constraints.Builder().setRequiredNetworkType(network).build())
+            .setInitialDelay(delayMillis, TimeUnit.MILLISECONDS)
+            .setInputData(workDataOf("id" to id))
+            .build()
+        WorkManager.getInstance(context).enqueue(request)
+    }
+
+    @JvmStatic
+    external fun runTask(id: Long): Boolean
+}
+
+class BackgroundWorker(context: Context, params: WorkerParameters) : Worker(context, params) {
+    override fun doWork(): Result {
+        // WorkManager may start the process without the activity that loads the app
+        System.loadLibrary("dioxusmain")
+        val id = inputData.getLong("id", -1)
+        return if (Background.runTask(id)) Result.success() else Result.retry()
+    }
+}
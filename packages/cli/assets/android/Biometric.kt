@@ -0,0 +1,50 @@
+package dev.dioxus.main;
+
+import android.app.Activity
+import android.hardware.biometrics.BiometricManager
+import android.hardware.biometrics.BiometricPrompt
+import android.os.Build
+import android.os.CancellationSignal
+
+// Shows the biometric prompt for `dioxus_mobile::authenticate`. The prompt needs a callback object, which can't be
+// created from rust, so the result is passed back through `onResult`.
+object Biometric {
+    // Returned when the version of Android is too old for the biometric prompt
+    private const val UNSUPPORTED = -1
+
+    @JvmStatic
+    fun availability(activity: Activity): Int {
+        if (Build.VERSION.SDK_INT < Build.VERSION_CODES.Q) {
+            return UNSUPPORTED
+        }
+        val manager = activity.getSystemService(BiometricManager::class.java)
+        return manager.canAuthenticate()
+    }
+
+    @JvmStatic
+    fun authenticate(activity: Activity, title: String, cancel: String, id: Long) {
+        if (Build.VERSION.SDK_INT < Build.VERSION_CODES.P) {
+            onResult(id, UNSUPPORTED, null)
+            return
+        }
+        val executor = activity.mainExecutor
+        val prompt = BiometricPrompt.Builder(activity)
+            .setTitle(title)
+            .setNegativeButton(cancel, executor) { _, _ ->
+                onResult(id, BiometricPrompt.BIOMETRIC_ERROR_USER_CANCELED, null)
+            }
+            .build()
+        prompt.authenticate(CancellationSignal(), executor, object : BiometricPrompt.AuthenticationCallback() {
+            override fun onAuthenticationSucceeded(result: BiometricPrompt.AuthenticationResult) {
+                onResult(id, 0, null)
+            }
+
+            override fun onAuthenticationError(errorCode: Int, errString: CharSequence) {
+                onResult(id, errorCode, errString.toString())
+            }
+        })
+    }
+
+    @JvmStatic
+    external fun onResult(id: Long, code: Int, message: String?)
+}
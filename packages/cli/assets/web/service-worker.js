@@ -0,0 +1,97 @@
+// The service worker dx generates for offline support. It precaches the app shell, the wasm bundle and every asset
+// of this build, and serves them from the cache. A new build installs a new version of the worker that waits until
+// the app applies the update.
+const VERSION = "{version}";
+const CACHE = `dx-precache-${VERSION}`;
+const INDEX = "{index}";
+const PRECACHE = {precache};
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(caches.open(CACHE).then((cache) => cache.addAll(PRECACHE)));
+});
+
+self.addEventListener("activate", (event) => {
+  // Remove the caches of older builds
+  event.waitUntil(
+    caches.keys().then((keys) =>
+      Promise.all(
+        keys
+          .filter((key) => key.startsWith("dx-precache-") && key !== CACHE)
+          .map((key) => caches.delete(key))
+      )
+    )
+  );
+});
+
+// The app asks the waiting worker to take over when the user accepts the update
+self.addEventListener("message", (event) => {
+  if (event.data && event.data.type === "SKIP_WAITING") {
+    self.skipWaiting();
+  }
+});
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  if (request.method !== "GET") {
+    return;
+  }
+
+  // Pages are loaded from the network so they stay fresh, and from the cached app shell when offline
+  if (request.mode === "navigate") {
+    event.respondWith(
+      fetch(request).catch(() =>
+        caches.open(CACHE).then((cache) => cache.match(INDEX))
+      )
+    );
+    return;
+  }
+
+  // Everything this build precached is served from the cache
+  event.respondWith(
+    caches
+      .open(CACHE)
+      .then((cache) => cache.match(request, { ignoreSearch: true }))
+      .then((cached) => cached || fetch(request))
+  );
+});
+
+// Push messages are shown as notifications. Messages sent as a `PushNotification` from dioxus-fullstack are json
+// with a title, and any other payload is shown as the body of the notification.
+self.addEventListener("push", (event) => {
+  let message = {};
+  if (event.data) {
+    try {
+      message = event.data.json();
+    } catch {
+      message = { body: event.data.text() };
+    }
+  }
+  const { title = "", url, ...options } = message;
+  event.waitUntil(self.registration.showNotification(title, { ...options, data: { url } }));
+});
+
+// Clicking a notification focuses the page it links to, or opens it if the app isn't open there
+self.addEventListener("notificationclick", (event) => {
+  event.notification.close();
+  const data = event.notification.data || {};
+  const url = new URL(data.url || INDEX, self.location.origin).href;
+  event.waitUntil(
+    self.clients.matchAll({ type: "window", includeUncontrolled: true }).then((windows) => {
+      const open = windows.find((client) => client.url === url);
+      return open ? open.focus() : self.clients.openWindow(url);
+    })
+  );
+});
+
+// Background sync for dioxus-background. The tasks run in the app, so once the device is back online the worker wakes
+// the pages that are open to start them.
+self.addEventListener("sync", (event) => {
+  if (event.tag !== "dioxus-background") {
+    return;
+  }
+  event.waitUntil(
+    self.clients.matchAll({ type: "window" }).then((windows) => {
+      windows.forEach((client) => client.postMessage({ type: "DIOXUS_BACKGROUND_SYNC" }));
+    })
+  );
+});
@@ -0,0 +1,12 @@
+// The entrypoint of the threads in apps built with `dx build --threads`. Every thread loads the same wasm module as
+// the page with the memory of the page, then runs a thread of the rayon pool until the page closes.
+import init, * as bindings from "./{app_name}.js";
+
+self.onmessage = async (event) => {
+  const { module, memory } = event.data;
+  await init({ module_or_path: module, memory });
+
+  // Tell the page the thread started before it blocks on the pool
+  self.postMessage({ started: true });
+  bindings.__dx_thread_start();
+};
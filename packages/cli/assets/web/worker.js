@@ -0,0 +1,23 @@
+// The entrypoint dioxus-worker runs in web workers. It loads the same wasm module as the page and runs the
+// functions the page sends to it.
+import init, * as bindings from "./{app_name}.js";
+
+let ready = null;
+
+self.onmessage = async (event) => {
+  const message = event.data;
+
+  if (message.type === "init") {
+    // Reuse the module the page already compiled instead of fetching it again
+    ready = init({ module_or_path: message.module });
+    return;
+  }
+
+  try {
+    await ready;
+    const output = bindings.__dx_worker_run(message.trampoline, message.task, message.input);
+    self.postMessage({ id: message.id, output });
+  } catch (error) {
+    self.postMessage({ id: message.id, error: String(error) });
+  }
+};
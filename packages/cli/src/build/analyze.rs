@@ -0,0 +1,343 @@
+//! Size analysis of the wasm bundle for `dx build --analyze`.
+//!
+//! The report lists every chunk of the bundle with the size of its sections, functions and data, and groups the
+//! functions by the crate they came from. Functions are only named if the name section survives the build, so
+//! `--analyze` keeps it even in release builds. The name section itself is counted separately since it isn't
+//! shipped without `--analyze`.
+
+use serde::Serialize;
+use std::{collections::HashMap, path::Path};
+use wasmparser::{KnownCustom, Name, Parser, Payload};
+
+const TREEMAP_HTML: &str = include_str!("../../assets/web/analyze.html");
+
+/// The sizes of every chunk in the bundle
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct SizeReport {
+    /// The size of all chunks together without their name sections, in bytes
+    pub(crate) total: u64,
+    /// The budget for the size of the wasm from the `Dioxus.toml`, in bytes
+    pub(crate) budget: Option<u64>,
+    pub(crate) chunks: Vec<ChunkReport>,
+}
+
+/// The sizes inside one wasm module of the bundle
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ChunkReport {
+    /// The name of the chunk. Bundles without code splitting only have the `main` chunk.
+    pub(crate) name: String,
+    /// The file of the chunk, relative to the output directory
+    pub(crate) file: String,
+    /// The size of the file, in bytes
+    pub(crate) size: u64,
+    pub(crate) sections: Vec<SectionSize>,
+    /// The crates the code of the chunk came from, largest first
+    pub(crate) crates: Vec<CrateSize>,
+    /// The function bodies of the chunk, largest first
+    pub(crate) functions: Vec<FunctionSize>,
+    /// The static data of the chunk, like string literals and lookup tables
+    pub(crate) data: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct SectionSize {
+    pub(crate) name: String,
+    pub(crate) size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct CrateSize {
+    pub(crate) name: String,
+    pub(crate) size: u64,
+    pub(crate) functions: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct FunctionSize {
+    /// The demangled name of the function, without the hash
+    pub(crate) name: String,
+    #[serde(rename = "crate")]
+    pub(crate) krate: String,
+    pub(crate) size: u64,
+}
+
+impl SizeReport {
+    pub(crate) fn new(chunks: Vec<ChunkReport>, budget: Option<u64>) -> Self {
+        Self {
+            total: chunks
+                .iter()
+                .map(|chunk| chunk.size - chunk.section_size("custom: name"))
+                .sum(),
+            budget,
+            chunks,
+        }
+    }
+
+    /// Check if the bundle is larger than its budget
+    pub(crate) fn over_budget(&self) -> bool {
+        self.budget.is_some_and(|budget| self.total > budget)
+    }
+
+    /// An html page with a treemap of the chunks, crates and functions in the report
+    pub(crate) fn to_html(&self) -> String {
+        // Escape the closing script tag in case a function name contains it
+        let report = serde_json::to_string(self)
+            .expect("the report is always serializable")
+            .replace("</", "<\\/");
+        TREEMAP_HTML.replace("{report}", &report)
+    }
+}
+
+impl ChunkReport {
+    /// Measure a wasm module
+    pub(crate) fn from_wasm(name: &str, file: &str, wasm: &[u8]) -> anyhow::Result<Self> {
+        let mut sections = Vec::new();
+        let mut bodies = Vec::new();
+        let mut imported_functions = 0;
+        let mut names = HashMap::new();
+        let mut data = 0;
+
+        for payload in Parser::new(0).parse_all(wasm) {
+            let payload = payload?;
+            if let Some((id, range)) = payload.as_section() {
+                let label = match &payload {
+                    Payload::CustomSection(section) => format!("custom: {}", section.name()),
+                    _ => section_name(id).to_string(),
+                };
+                sections.push(SectionSize {
+                    name: label,
+                    size: range.len() as u64,
+                });
+            }
+
+            match payload {
+                Payload::ImportSection(imports) => {
+                    for import in imports {
+                        if let wasmparser::TypeRef::Func(_) = import?.ty {
+                            imported_functions += 1;
+                        }
+                    }
+                }
+                Payload::CodeSectionEntry(body) => bodies.push(body.range().len() as u64),
+                Payload::DataSection(segments) => {
+                    for segment in segments {
+                        data += segment?.data.len() as u64;
+                    }
+                }
+                Payload::CustomSection(section) => {
+                    if let KnownCustom::Name(reader) = section.as_known() {
+                        for subsection in reader {
+                            // Functions are all we need, so a name section we can't read only loses the names
+                            let Ok(Name::Function(map)) = subsection else {
+                                continue;
+                            };
+                            for naming in map.into_iter().flatten() {
+                                names.insert(naming.index, naming.name);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Imported functions come first in the function index space, but have no body
+        let mut functions: Vec<FunctionSize> = bodies
+            .into_iter()
+            .enumerate()
+            .map(|(index, size)| {
+                let name = names
+                    .get(&(index as u32 + imported_functions))
+                    .map(|name| format!("{:#}", rustc_demangle::demangle(name)))
+                    .unwrap_or_else(|| format!("[function {index}]"));
+                FunctionSize {
+                    krate: crate_of(&name),
+                    name,
+                    size,
+                }
+            })
+            .collect();
+        functions.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        let mut crates: HashMap<&str, CrateSize> = HashMap::new();
+        for function in &functions {
+            let krate = crates
+                .entry(function.krate.as_str())
+                .or_insert_with(|| CrateSize {
+                    name: function.krate.clone(),
+                    size: 0,
+                    functions: 0,
+                });
+            krate.size += function.size;
+            krate.functions += 1;
+        }
+        let mut crates: Vec<CrateSize> = crates.into_values().collect();
+        crates.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(Self {
+            name: name.to_string(),
+            file: file.to_string(),
+            size: wasm.len() as u64,
+            sections,
+            crates,
+            functions,
+            data,
+        })
+    }
+
+    fn section_size(&self, name: &str) -> u64 {
+        self.sections
+            .iter()
+            .filter(|section| section.name == name)
+            .map(|section| section.size)
+            .sum()
+    }
+
+    /// Measure a wasm file in the output directory
+    pub(crate) fn from_file(name: &str, out_dir: &Path, file: &str) -> anyhow::Result<Self> {
+        let wasm = std::fs::read(out_dir.join(file))?;
+        Self::from_wasm(name, file, &wasm)
+    }
+}
+
+fn section_name(id: u8) -> &'static str {
+    match id {
+        0 => "custom",
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "datacount",
+        13 => "tag",
+        _ => "unknown",
+    }
+}
+
+/// Find the crate a demangled function name belongs to. Trait impls like `<alloc::vec::Vec<T> as core::fmt::Debug>::fmt`
+/// are counted towards the crate of the type, or the crate of the trait if the type is a primitive or generic.
+fn crate_of(name: &str) -> String {
+    if name.starts_with("__wbg") || name.starts_with("__wbindgen") {
+        return "[wasm-bindgen]".to_string();
+    }
+
+    let krate = first_segment(name).or_else(|| {
+        let (_, name) = name.strip_prefix('<')?.split_once(" as ")?;
+        first_segment(name)
+    });
+    krate.unwrap_or("[unknown]").to_string()
+}
+
+fn first_segment(path: &str) -> Option<&str> {
+    let path = path.trim_start_matches(['<', '&', '*']);
+    let path = path.strip_prefix("mut ").unwrap_or(path);
+    let path = path.strip_prefix("dyn ").unwrap_or(path);
+    let (krate, _) = path.split_once("::")?;
+    let valid = !krate.is_empty() && krate.chars().all(|c| c.is_alphanumeric() || c == '_');
+    valid.then_some(krate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_crate_of_functions() {
+        assert_eq!(
+            crate_of("dioxus_core::virtual_dom::VirtualDom::rebuild"),
+            "dioxus_core"
+        );
+        assert_eq!(
+            crate_of("<alloc::vec::Vec<T,A> as core::ops::drop::Drop>::drop"),
+            "alloc"
+        );
+        assert_eq!(crate_of("<char as core::fmt::Debug>::fmt"), "core");
+        assert_eq!(crate_of("<&mut T as core::fmt::Debug>::fmt"), "core");
+        assert_eq!(crate_of("__wbindgen_malloc"), "[wasm-bindgen]");
+        assert_eq!(crate_of("[function 3]"), "[unknown]");
+        assert_eq!(crate_of("memcpy"), "[unknown]");
+    }
+
+    #[test]
+    fn measures_functions_and_data() {
+        // An imported function, two functions with bodies of different sizes, and a data segment
+        let wasm = test_module();
+        let chunk = ChunkReport::from_wasm("main", "wasm/app_bg.wasm", &wasm).unwrap();
+
+        assert_eq!(chunk.size, wasm.len() as u64);
+        assert_eq!(chunk.data, 5);
+        assert_eq!(
+            chunk
+                .functions
+                .iter()
+                .map(|function| (function.name.as_str(), function.krate.as_str()))
+                .collect::<Vec<_>>(),
+            [("app::big", "app"), ("dep::small", "dep")]
+        );
+        assert!(chunk.functions[0].size > chunk.functions[1].size);
+        assert_eq!(chunk.crates[0].name, "app");
+        assert!(chunk.sections.iter().any(|section| section.name == "code"));
+        assert!(chunk
+            .sections
+            .iter()
+            .any(|section| section.name == "custom: name"));
+
+        let report = SizeReport::new(vec![chunk], Some(10));
+        assert!(report.over_budget());
+        assert!(report.to_html().contains("\"app::big\""));
+    }
+
+    /// Encode a small module by hand so the test doesn't need a wasm toolchain
+    fn test_module() -> Vec<u8> {
+        fn section(id: u8, contents: &[u8]) -> Vec<u8> {
+            let mut section = vec![id, contents.len() as u8];
+            section.extend_from_slice(contents);
+            section
+        }
+        fn name(name: &str) -> Vec<u8> {
+            let mut bytes = vec![name.len() as u8];
+            bytes.extend_from_slice(name.as_bytes());
+            bytes
+        }
+
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        // type 0: () -> ()
+        wasm.extend(section(1, &[1, 0x60, 0, 0]));
+        // import env.f: type 0
+        let mut import = vec![1];
+        import.extend(name("env"));
+        import.extend(name("f"));
+        import.extend([0, 0]);
+        wasm.extend(section(2, &import));
+        // two functions of type 0
+        wasm.extend(section(3, &[2, 0, 0]));
+        // memory with one page
+        wasm.extend(section(5, &[1, 0, 1]));
+        // function 1 calls the import three times, function 2 is empty
+        wasm.extend(section(
+            10,
+            &[2, 8, 0, 0x10, 0, 0x10, 0, 0x10, 0, 0x0b, 2, 0, 0x0b],
+        ));
+        // an active data segment at offset 0 with 5 bytes
+        let mut data = vec![1, 0, 0x41, 0, 0x0b, 5];
+        data.extend_from_slice(b"hello");
+        wasm.extend(section(11, &data));
+        // the function names, mangled like rustc does
+        let mut names = vec![2, 1];
+        names.extend(name("_ZN3app3big17h0123456789abcdefE"));
+        names.push(2);
+        names.extend(name("_ZN3dep5small17h0123456789abcdefE"));
+        let mut subsection = vec![1, names.len() as u8];
+        subsection.extend(names);
+        let mut custom = name("name");
+        custom.extend(subsection);
+        wasm.extend(section(0, &custom));
+        wasm
+    }
+}
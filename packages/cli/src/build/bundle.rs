@@ -1,11 +1,12 @@
+use super::analyze::{ChunkReport, SizeReport};
 use super::prerender::pre_render_static_routes;
-use super::templates::InfoPlistData;
+use super::templates::{DocumentType, InfoPlistData, UrlType};
 use crate::wasm_bindgen::WasmBindgenBuilder;
 use crate::{BuildRequest, Platform};
 use crate::{Result, TraceSrc};
 use anyhow::Context;
-use dioxus_cli_opt::{process_file_to, AssetManifest};
-use manganis_core::AssetOptions;
+use dioxus_cli_opt::{process_css_modules, process_file_to, AssetManifest};
+use manganis_core::{AssetOptions, CSS_MODULES_BUNDLED_PATH};
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use std::collections::HashSet;
 use std::future::Future;
@@ -320,6 +321,8 @@ impl AppBundle {
             //                     wasm/
             //                        app.wasm
             //                        glue.js
+            //                        dx-worker.js
+            //                        dx-thread.js (only in builds with --threads)
             //                        snippets/
             //                            ...
             //                     assets/
@@ -336,10 +339,25 @@ impl AppBundle {
                 // We put it behind the "wasm-opt" feature flag so that it can be disabled when iterating on the cli
                 self.run_wasm_opt(&self.build.exe_dir())?;
 
+                // Measure the final wasm for `--analyze` and the size budget
+                self.report_wasm_size()?;
+
+                // Write the entrypoint web workers load the app from
+                std::fs::write(
+                    self.build.exe_dir().join("dx-worker.js"),
+                    self.build.worker_entrypoint(),
+                )?;
+                if self.build.web_threads() {
+                    std::fs::write(
+                        self.build.exe_dir().join("dx-thread.js"),
+                        self.build.thread_entrypoint(),
+                    )?;
+                }
+
                 // Write the index.html file with the pre-configured contents we got from pre-rendering
                 std::fs::write(
                     self.build.root_dir().join("index.html"),
-                    self.build.prepare_html()?,
+                    self.build.prepare_html(&self.app.assets)?,
                 )?;
             }
 
@@ -388,7 +406,11 @@ impl AppBundle {
             .assets
             .assets
             .values()
-            .map(|a| asset_dir.join(a.bundled_path()))
+            .flat_map(|a| {
+                // Responsive images also keep the variants that were generated next to them
+                std::iter::once(a.bundled_path().to_string()).chain(a.responsive_variant_paths())
+            })
+            .map(|path| asset_dir.join(path))
             .collect();
         // one possible implementation of walking a directory only visiting files
         fn remove_old_assets<'a>(
@@ -425,9 +447,21 @@ impl AppBundle {
         // todo(jon): we also want to eventually include options for each asset's optimization and compression, which we currently aren't
         let mut assets_to_transfer = vec![];
 
-        // Queue the bundled assets
+        // Queue the bundled assets. Css modules are combined into one stylesheet after the rest are copied
         for (asset, bundled) in &self.app.assets.assets {
-            let from = asset.clone();
+            if let AssetOptions::CssModule(_) = bundled.options() {
+                continue;
+            }
+            // Remote assets are downloaded and checked against their sha256 before they are processed
+            let from = match bundled.sha256() {
+                Some(sha256) => {
+                    self.build
+                        .krate
+                        .fetch_remote_asset(bundled.absolute_source_path(), sha256)
+                        .await?
+                }
+                None => asset.clone(),
+            };
             let to = asset_dir.join(bundled.bundled_path());
             tracing::debug!("Copying asset {from:?} to {to:?}");
             assets_to_transfer.push((from, to, *bundled.options()));
@@ -474,6 +508,8 @@ impl AppBundle {
         .await
         .map_err(|e| anyhow::anyhow!("A task failed while trying to copy assets: {e}"))??;
 
+        process_css_modules(&self.app.assets, &asset_dir.join(CSS_MODULES_BUNDLED_PATH))?;
+
         Ok(())
     }
 
@@ -535,7 +571,24 @@ impl AppBundle {
             // These are served as folders, not appimages, so we don't need to do anything special (I think?)
             // Eventually maybe write some secrets/.env files for the server?
             // We could also distribute them as a deb/rpm for linux and msi for windows
-            Platform::Web => {}
+            //
+            // Web builds can opt into a web app manifest and a service worker that caches the app for offline use.
+            // The manifest comes first so the service worker can precache it.
+            Platform::Web => {
+                let manifest = self.build.root_dir().join("manifest.webmanifest");
+                if self.build.krate.config.web.manifest.enabled {
+                    self.build.write_web_manifest()?;
+                } else {
+                    _ = std::fs::remove_file(manifest);
+                }
+
+                let dest = self.build.root_dir().join("service-worker.js");
+                if self.build.should_generate_service_worker() {
+                    std::fs::write(dest, self.build.service_worker(&self.app.assets)?)?;
+                } else {
+                    _ = std::fs::remove_file(dest);
+                }
+            }
             Platform::Server => {}
             Platform::Liveview => {}
         }
@@ -622,7 +675,8 @@ impl AppBundle {
             .debug(keep_debug)
             .demangle(keep_debug)
             .keep_debug(keep_debug)
-            .remove_name_section(!keep_debug)
+            // The analysis needs the names of the functions
+            .remove_name_section(!keep_debug && !self.build.build.analyze)
             .remove_producers_section(!keep_debug)
             .out_name(&name)
             .out_dir(&bindgen_outdir)
@@ -663,10 +717,13 @@ impl AppBundle {
             let wasm_file =
                 bindgen_outdir.join(format!("{}_bg.wasm", self.build.krate.executable_name()));
             let old_size = wasm_file.metadata()?.len();
+            if self.build.web_threads() {
+                options.enable_feature(wasm_opt::Feature::Atomics);
+            }
             options
                 // WASM bindgen relies on reference types
                 .enable_feature(wasm_opt::Feature::ReferenceTypes)
-                .debug_info(self.build.krate.config.web.wasm_opt.debug)
+                .debug_info(self.build.krate.config.web.wasm_opt.debug || self.build.build.analyze)
                 .run(&wasm_file, &wasm_file)
                 .map_err(|err| crate::Error::Other(anyhow::anyhow!(err)))?;
 
@@ -683,6 +740,59 @@ impl AppBundle {
         Ok(())
     }
 
+    /// Check the wasm against the size budget and write the reports of `--analyze` next to the public folder
+    fn report_wasm_size(&self) -> Result<()> {
+        let budget = self.build.krate.config.web.size_budget.wasm_kb;
+        if !self.build.build.analyze && budget.is_none() {
+            return Ok(());
+        }
+
+        let file = format!("wasm/{}_bg.wasm", self.build.krate.executable_name());
+        let chunk = ChunkReport::from_file("main", &self.build.root_dir(), &file)
+            .context("Failed to analyze the wasm bundle")?;
+        let report = SizeReport::new(vec![chunk], budget.map(|kb| kb * 1024));
+
+        if report.over_budget() {
+            tracing::warn!(
+                "The wasm bundle is {}KB, which is over the budget of {}KB in the Dioxus.toml. Run `dx build --analyze` to see where the size comes from.",
+                report.total / 1024,
+                budget.unwrap_or_default()
+            );
+        }
+
+        if self.build.build.analyze {
+            let out_dir = self.build.platform_dir();
+            std::fs::write(
+                out_dir.join("analysis.json"),
+                serde_json::to_string_pretty(&report)
+                    .context("Failed to serialize the size analysis")?,
+            )?;
+            std::fs::write(out_dir.join("analysis.html"), report.to_html())?;
+
+            for chunk in &report.chunks {
+                let largest = chunk
+                    .crates
+                    .iter()
+                    .take(5)
+                    .map(|krate| format!("{} ({}KB)", krate.name, krate.size / 1024))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                tracing::info!(
+                    "Chunk {} is {}KB with {}KB of data. Largest crates: {largest}",
+                    chunk.name,
+                    chunk.size / 1024,
+                    chunk.data / 1024
+                );
+            }
+            tracing::info!(
+                "Wrote the size analysis to {}",
+                out_dir.join("analysis.html").display()
+            );
+        }
+
+        Ok(())
+    }
+
     async fn pre_render_ssg_routes(&self) -> Result<()> {
         // Run SSG and cache static routes
         if !self.build.build.ssg {
@@ -707,11 +817,32 @@ impl AppBundle {
                     bundle_name: self.build.krate.bundled_app_name(),
                     executable_name: self.build.platform_exe_name(),
                     bundle_identifier: self.build.krate.bundle_identifier(),
+                    document_types: DocumentType::from_bundle(&self.build.krate.config.bundle),
+                    url_types: UrlType::from_bundle(
+                        &self.build.krate.config.bundle,
+                        &self.build.krate.bundle_identifier(),
+                    ),
+                    face_id_usage_description: self.face_id_usage_description(),
                 },
             )
             .map_err(|e| e.into())
     }
 
+    fn face_id_usage_description(&self) -> String {
+        self.build
+            .krate
+            .config
+            .bundle
+            .face_id_usage_description
+            .clone()
+            .unwrap_or_else(|| {
+                format!(
+                    "{} uses Face ID to sign you in.",
+                    self.build.krate.bundled_app_name()
+                )
+            })
+    }
+
     fn ios_plist_contents(&self) -> Result<String> {
         handlebars::Handlebars::new()
             .render_template(
@@ -721,6 +852,12 @@ impl AppBundle {
                     bundle_name: self.build.krate.bundled_app_name(),
                     executable_name: self.build.platform_exe_name(),
                     bundle_identifier: self.build.krate.bundle_identifier(),
+                    document_types: DocumentType::from_bundle(&self.build.krate.config.bundle),
+                    url_types: UrlType::from_bundle(
+                        &self.build.krate.config.bundle,
+                        &self.build.krate.bundle_identifier(),
+                    ),
+                    face_id_usage_description: self.face_id_usage_description(),
                 },
             )
             .map_err(|e| e.into())
@@ -5,10 +5,12 @@
 //! Uses a request -> response architecture that allows you to monitor the progress with an optional message
 //! receiver.
 
+mod analyze;
 mod builder;
 mod bundle;
 mod prerender;
 mod progress;
+mod remote;
 mod request;
 mod templates;
 mod verify;
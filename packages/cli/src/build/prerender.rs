@@ -66,10 +66,14 @@ pub(crate) async fn pre_render_static_routes(server_exe: &Path) -> anyhow::Resul
         }
     }
 
-    let routes = routes.expect(
+    let mut routes = routes.expect(
         "static routes should exist or an error should have been returned on the last attempt",
     );
 
+    // Routes enumerated from dynamic segments may overlap with the static routes. Only render each route once
+    routes.sort();
+    routes.dedup();
+
     // Create a pool of futures that cache each route
     let mut resolved_routes = routes
         .into_iter()
@@ -0,0 +1,149 @@
+use crate::{DioxusCrate, Result};
+use anyhow::Context;
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+impl DioxusCrate {
+    /// Get the local copy of a remote asset, downloading it if it isn't cached yet. The cache is keyed by the sha256
+    /// of the asset so builds work offline once every remote asset has been downloaded.
+    pub(crate) async fn fetch_remote_asset(&self, url: &str, sha256: &str) -> Result<PathBuf> {
+        let cache_dir = self.out_dir().join("remote-assets");
+        cached_or_download(&cache_dir, url, sha256, || async {
+            tracing::debug!("Downloading remote asset {url}");
+            let contents = reqwest::get(url)
+                .await
+                .and_then(|response| response.error_for_status())
+                .with_context(|| format!("Failed to download remote asset {url}"))?
+                .bytes()
+                .await
+                .with_context(|| format!("Failed to download remote asset {url}"))?;
+            Ok(contents.to_vec())
+        })
+        .await
+    }
+}
+
+/// Get the cached copy of a remote asset if it still matches its sha256, or download it and write it to the cache
+/// if the download matches the sha256
+async fn cached_or_download<F, Fut>(
+    cache_dir: &Path,
+    url: &str,
+    sha256: &str,
+    download: F,
+) -> Result<PathBuf>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<Vec<u8>>>,
+{
+    // Keep the extension of the url so the asset is processed like a local file of the same type
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let cached = match Path::new(path).extension() {
+        Some(extension) => cache_dir.join(format!("{sha256}.{}", extension.to_string_lossy())),
+        None => cache_dir.join(sha256),
+    };
+
+    // Someone may have tampered with the cache, so check the contents every time
+    if let Ok(contents) = tokio::fs::read(&cached).await {
+        if hex_sha256(&contents) == sha256 {
+            return Ok(cached);
+        }
+        tracing::warn!("The cached copy of {url} doesn't match its sha256, downloading it again");
+    }
+
+    let contents = download().await?;
+    let actual = hex_sha256(&contents);
+    if actual != sha256 {
+        return Err(anyhow::anyhow!(
+            "The remote asset {url} doesn't match the sha256 in asset!(). Expected {sha256} but the download has {actual}"
+        )
+        .into());
+    }
+
+    tokio::fs::create_dir_all(cache_dir).await?;
+    tokio::fs::write(&cached, &contents).await?;
+
+    Ok(cached)
+}
+
+fn hex_sha256(contents: &[u8]) -> String {
+    Sha256::digest(contents)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    const URL: &str = "https://cdn.example.com/lib.css?v=2";
+    const CSS: &[u8] = b"body { color: red; }";
+
+    /// Run `cached_or_download` with a fake download, returning the result and whether it downloaded anything
+    async fn fetch(cache_dir: &Path, sha256: &str, contents: &[u8]) -> (Result<PathBuf>, bool) {
+        let downloaded = Cell::new(false);
+        let result = cached_or_download(cache_dir, URL, sha256, || async {
+            downloaded.set(true);
+            Ok(contents.to_vec())
+        })
+        .await;
+        (result, downloaded.get())
+    }
+
+    #[tokio::test]
+    async fn downloads_are_cached_by_their_sha256() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let sha256 = hex_sha256(CSS);
+
+        let (path, downloaded) = fetch(cache_dir.path(), &sha256, CSS).await;
+        let path = path.unwrap();
+        assert!(downloaded);
+        assert_eq!(path, cache_dir.path().join(format!("{sha256}.css")));
+        assert_eq!(std::fs::read(&path).unwrap(), CSS);
+
+        // The second build works offline
+        let (cached, downloaded) = fetch(cache_dir.path(), &sha256, b"").await;
+        assert!(!downloaded);
+        assert_eq!(cached.unwrap(), path);
+    }
+
+    #[tokio::test]
+    async fn mismatched_downloads_fail_the_build() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let sha256 = hex_sha256(CSS);
+
+        let (result, downloaded) = fetch(cache_dir.path(), &sha256, b"body { color: blue; }").await;
+        assert!(downloaded);
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("doesn't match the sha256"), "{error}");
+
+        // Nothing is written to the cache
+        assert!(!cache_dir.path().join(format!("{sha256}.css")).exists());
+    }
+
+    #[tokio::test]
+    async fn tampered_cache_entries_are_downloaded_again() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let sha256 = hex_sha256(CSS);
+        let cached = cache_dir.path().join(format!("{sha256}.css"));
+        std::fs::write(&cached, b"body { display: none; }").unwrap();
+
+        let (path, downloaded) = fetch(cache_dir.path(), &sha256, CSS).await;
+        assert!(downloaded);
+        assert_eq!(path.unwrap(), cached);
+        assert_eq!(std::fs::read(&cached).unwrap(), CSS);
+    }
+
+    #[tokio::test]
+    async fn tampered_cache_entries_are_rejected_if_the_download_does_not_match() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let sha256 = hex_sha256(CSS);
+        let cached = cache_dir.path().join(format!("{sha256}.css"));
+        std::fs::write(&cached, b"body { display: none; }").unwrap();
+
+        let (result, _) = fetch(cache_dir.path(), &sha256, b"body { display: none; }").await;
+        assert!(result.is_err());
+    }
+}
@@ -1,12 +1,15 @@
 use super::{progress::ProgressTx, BuildArtifacts};
 use crate::dioxus_crate::DioxusCrate;
 use crate::{link::LinkAction, BuildArgs};
-use crate::{AppBundle, Platform, Result, TraceSrc};
+use crate::{run_css_pipelines, AppBundle, CssPipeline, Platform, Result, TraceSrc};
 use anyhow::Context;
-use dioxus_cli_config::{APP_TITLE_ENV, ASSET_ROOT_ENV};
+use dioxus_cli_config::{
+    APP_TITLE_ENV, ASSET_ROOT_ENV, CONFIG_ENV_PREFIX, CROSS_ORIGIN_ISOLATED_ENV,
+};
 use dioxus_cli_opt::AssetManifest;
 use serde::Deserialize;
 use std::{
+    borrow::Cow,
     path::{Path, PathBuf},
     process::Stdio,
     time::Instant,
@@ -31,10 +34,10 @@ pub(crate) struct BuildRequest {
 impl BuildRequest {
     pub fn new(krate: DioxusCrate, build: BuildArgs, progress: ProgressTx) -> Self {
         Self {
+            custom_target_dir: build.target_dir.clone(),
             build,
             krate,
             progress,
-            custom_target_dir: None,
         }
     }
 
@@ -52,6 +55,11 @@ impl BuildRequest {
             }
         );
 
+        // Generate the stylesheets first so the asset!() macros see the latest version
+        let mut css_pipelines =
+            CssPipeline::from_config(&self.krate.crate_dir(), &self.krate.config.application.css);
+        run_css_pipelines(&mut css_pipelines).await?;
+
         let (app, server) = match self.build.force_sequential {
             true => self.build_sequential().await?,
             false => self.build_concurrent().await?,
@@ -115,6 +123,11 @@ impl BuildRequest {
 
         let mut cmd = Command::new("cargo");
 
+        // Rebuilding the standard library with shared memory is only available on nightly
+        if self.web_threads() {
+            cmd.arg("+nightly");
+        }
+
         cmd.arg("rustc")
             .current_dir(self.krate.crate_dir())
             .arg("--message-format")
@@ -308,6 +321,11 @@ impl BuildRequest {
                 cargo_args.push("--target".to_string());
                 cargo_args.push(target.to_string());
             }
+
+            // The prebuilt standard library for wasm doesn't support atomics, so it has to be built with the app
+            if self.web_threads() {
+                cargo_args.push("-Zbuild-std=panic_abort,std".to_string());
+            }
         }
 
         // We always run in verbose since the CLI itself is the one doing the presentation
@@ -362,6 +380,20 @@ impl BuildRequest {
         rust_flags
     }
 
+    /// Check if this is the web build of an app built with `--threads`
+    pub(crate) fn web_threads(&self) -> bool {
+        self.build.threads && self.build.platform() == Platform::Web
+    }
+
+    pub(crate) fn web_threads_rust_flags(&self) -> String {
+        let mut rust_flags = std::env::var("RUSTFLAGS").unwrap_or_default();
+
+        // Shared memory between web workers needs atomics, and bulk memory to initialize the memory once
+        rust_flags.push_str(" -Ctarget-feature=+atomics,+bulk-memory,+mutable-globals");
+
+        rust_flags
+    }
+
     /// Create the list of features we need to pass to cargo to build the app by merging together
     /// either the client or server features depending on if we're building a server or not.
     pub(crate) fn target_features(&self) -> Vec<String> {
@@ -496,7 +528,7 @@ impl BuildRequest {
         Ok(manifest)
     }
 
-    fn env_vars(&self) -> Result<Vec<(&str, String)>> {
+    fn env_vars(&self) -> Result<Vec<(Cow<'static, str>, String)>> {
         let mut env_vars = vec![];
 
         if self.build.platform() == Platform::Android {
@@ -523,30 +555,33 @@ impl BuildRequest {
             "#
             );
 
-            env_vars.push(("ANDROID_NATIVE_API_LEVEL", min_sdk_version.to_string()));
-            env_vars.push(("TARGET_AR", ar_path.display().to_string()));
-            env_vars.push(("TARGET_CC", target_cc.display().to_string()));
-            env_vars.push(("TARGET_CXX", target_cxx.display().to_string()));
-            env_vars.push(("ANDROID_NDK_ROOT", ndk.display().to_string()));
+            env_vars.push((
+                "ANDROID_NATIVE_API_LEVEL".into(),
+                min_sdk_version.to_string(),
+            ));
+            env_vars.push(("TARGET_AR".into(), ar_path.display().to_string()));
+            env_vars.push(("TARGET_CC".into(), target_cc.display().to_string()));
+            env_vars.push(("TARGET_CXX".into(), target_cxx.display().to_string()));
+            env_vars.push(("ANDROID_NDK_ROOT".into(), ndk.display().to_string()));
 
             // attempt to set java_home to the android studio java home if it exists.
             // https://stackoverflow.com/questions/71381050/java-home-is-set-to-an-invalid-directory-android-studio-flutter
             // attempt to set java_home to the android studio java home if it exists and java_home was not already set
             if let Some(java_home) = java_home {
                 tracing::debug!("Setting JAVA_HOME to {java_home:?}");
-                env_vars.push(("JAVA_HOME", java_home.display().to_string()));
+                env_vars.push(("JAVA_HOME".into(), java_home.display().to_string()));
             }
 
-            env_vars.push(("WRY_ANDROID_PACKAGE", "dev.dioxus.main".to_string()));
-            env_vars.push(("WRY_ANDROID_LIBRARY", "dioxusmain".to_string()));
+            env_vars.push(("WRY_ANDROID_PACKAGE".into(), "dev.dioxus.main".to_string()));
+            env_vars.push(("WRY_ANDROID_LIBRARY".into(), "dioxusmain".to_string()));
             env_vars.push((
-                "WRY_ANDROID_KOTLIN_FILES_OUT_DIR",
+                "WRY_ANDROID_KOTLIN_FILES_OUT_DIR".into(),
                 self.wry_android_kotlin_files_out_dir()
                     .display()
                     .to_string(),
             ));
 
-            env_vars.push(("RUSTFLAGS", self.android_rust_flags()))
+            env_vars.push(("RUSTFLAGS".into(), self.android_rust_flags()))
 
             // todo(jon): the guide for openssl recommends extending the path to include the tools dir
             //            in practice I couldn't get this to work, but this might eventually become useful.
@@ -569,13 +604,29 @@ impl BuildRequest {
             // env_vars.push(("PATH", extended_path));
         };
 
+        if self.web_threads() {
+            env_vars.push(("RUSTFLAGS".into(), self.web_threads_rust_flags()));
+        }
+
         // If this is a release build, bake the base path and title
         // into the binary with env vars
         if self.build.release {
             if let Some(base_path) = &self.krate.config.web.app.base_path {
-                env_vars.push((ASSET_ROOT_ENV, base_path.clone()));
+                env_vars.push((ASSET_ROOT_ENV.into(), base_path.clone()));
+            }
+            env_vars.push((
+                APP_TITLE_ENV.into(),
+                self.krate.config.web.app.title.clone(),
+            ));
+            // The server of a threaded app has to send the headers that let the page share memory
+            if self.build.threads {
+                env_vars.push((CROSS_ORIGIN_ISOLATED_ENV.into(), "true".to_string()));
             }
-            env_vars.push((APP_TITLE_ENV, self.krate.config.web.app.title.clone()));
+        }
+
+        // Bake the public config values of the environment into the binary for `dioxus_config!`
+        for (key, value) in self.krate.config.env_values(self.build.environment())? {
+            env_vars.push((format!("{CONFIG_ENV_PREFIX}{key}").into(), value));
         }
 
         Ok(env_vars)
@@ -772,10 +823,20 @@ impl BuildRequest {
         struct HbsTypes {
             application_id: String,
             app_name: String,
+            url_schemes: Vec<String>,
         }
         let hbs_data = HbsTypes {
             application_id: self.krate.full_mobile_app_name(),
             app_name: self.krate.bundled_app_name(),
+            url_schemes: self
+                .krate
+                .config
+                .bundle
+                .deep_link_protocols
+                .iter()
+                .flatten()
+                .flat_map(|protocol| protocol.schemes.iter().cloned())
+                .collect(),
         };
 
         // Top-level gradle config
@@ -840,6 +901,19 @@ impl BuildRequest {
             )?,
         )?;
 
+        // The biometric prompt of dioxus-mobile calls back into rust through this class
+        write(
+            self.wry_android_kotlin_files_out_dir().join("Biometric.kt"),
+            include_bytes!("../../assets/android/Biometric.kt"),
+        )?;
+
+        // WorkManager runs the tasks of dioxus-background through this class
+        write(
+            self.wry_android_kotlin_files_out_dir()
+                .join("Background.kt"),
+            include_bytes!("../../assets/android/Background.kt"),
+        )?;
+
         // Write the res folder
         let res = app_main.join("res");
         create_dir_all(&res)?;
@@ -4,4 +4,68 @@ pub struct InfoPlistData {
     pub bundle_name: String,
     pub bundle_identifier: String,
     pub executable_name: String,
+    pub document_types: Vec<DocumentType>,
+    pub url_types: Vec<UrlType>,
+    pub face_id_usage_description: String,
+}
+
+/// An entry of `CFBundleDocumentTypes` for a file association of the bundle
+#[derive(serde::Serialize)]
+pub struct DocumentType {
+    pub name: String,
+    pub role: &'static str,
+    pub extensions: Vec<String>,
+}
+
+impl DocumentType {
+    pub fn from_bundle(bundle: &crate::BundleConfig) -> Vec<Self> {
+        bundle
+            .file_associations
+            .iter()
+            .flatten()
+            .filter(|association| !association.ext.is_empty())
+            .map(|association| {
+                let extensions: Vec<String> = association
+                    .ext
+                    .iter()
+                    .map(|ext| ext.trim_start_matches('.').to_string())
+                    .collect();
+                DocumentType {
+                    name: association
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| extensions[0].clone()),
+                    role: association.role.as_str(),
+                    extensions,
+                }
+            })
+            .collect()
+    }
+}
+
+/// An entry of `CFBundleURLTypes` for a deep link protocol of the bundle
+#[derive(serde::Serialize)]
+pub struct UrlType {
+    pub name: String,
+    pub role: &'static str,
+    pub schemes: Vec<String>,
+}
+
+impl UrlType {
+    pub fn from_bundle(bundle: &crate::BundleConfig, bundle_identifier: &str) -> Vec<Self> {
+        bundle
+            .deep_link_protocols
+            .iter()
+            .flatten()
+            .filter(|protocol| !protocol.schemes.is_empty())
+            .map(|protocol| UrlType {
+                name: protocol
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("{bundle_identifier}.{}", protocol.schemes[0])),
+                role: protocol.role.as_str(),
+                schemes: protocol.schemes.clone(),
+            })
+            .collect()
+    }
 }
@@ -49,6 +49,33 @@ impl BuildRequest {
                 .await?;
         }
 
+        // Threaded builds rebuild the standard library for wasm with nightly rust
+        if self.web_threads() {
+            if !rustup.has_nightly() {
+                tracing::info!(
+                    "Building with --threads requires nightly rust to be installed. Installing..."
+                );
+                let _ = Command::new("rustup")
+                    .args(["toolchain", "install", "nightly", "--profile", "minimal"])
+                    .output()
+                    .await?;
+            }
+            let _ = Command::new("rustup")
+                .args(["component", "add", "rust-src", "--toolchain", "nightly"])
+                .output()
+                .await?;
+            let _ = Command::new("rustup")
+                .args([
+                    "target",
+                    "add",
+                    "wasm32-unknown-unknown",
+                    "--toolchain",
+                    "nightly",
+                ])
+                .output()
+                .await?;
+        }
+
         // Wasm bindgen
         let krate_bindgen_version = self.krate.wasm_bindgen_version().ok_or(anyhow!(
             "failed to detect wasm-bindgen version, unable to proceed"
@@ -2,14 +2,25 @@ use dioxus_cli_config::format_base_path_meta_element;
 
 use crate::error::Result;
 use crate::BuildRequest;
+use anyhow::Context;
+use dioxus_cli_opt::{process_file_to, AssetManifest};
+use manganis_core::{AssetOptions, ImageAssetOptions, ImageSize, CSS_MODULES_BUNDLED_PATH};
 use std::fmt::Write;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::{Path, PathBuf};
 
 const DEFAULT_HTML: &str = include_str!("../../assets/web/index.html");
 const TOAST_HTML: &str = include_str!("../../assets/web/toast.html");
+const WORKER_JS: &str = include_str!("../../assets/web/worker.js");
+const THREAD_JS: &str = include_str!("../../assets/web/thread.js");
+const SERVICE_WORKER_JS: &str = include_str!("../../assets/web/service-worker.js");
+const INSTALL_PROMPT_HTML: &str = r#"<script>
+            // The browser only offers to install the app once, possibly before the app loads, so keep the event for dioxus-web
+            window.addEventListener("beforeinstallprompt", (event) => { window.__dx_install_prompt = event; });
+            </script>"#;
 
 impl BuildRequest {
-    pub(crate) fn prepare_html(&self) -> Result<String> {
+    pub(crate) fn prepare_html(&self, assets: &AssetManifest) -> Result<String> {
         let mut html = {
             let crate_root: &Path = &self.krate.crate_dir();
             let custom_html_file = crate_root.join("index.html");
@@ -17,11 +28,36 @@ impl BuildRequest {
         };
 
         // Inject any resources from the config into the html
-        self.inject_resources(&mut html)?;
+        self.inject_resources(assets, &mut html)?;
 
         // Inject loading scripts if they are not already present
         self.inject_loading_scripts(&mut html);
 
+        // Point dioxus-web at the service worker so it registers it when the app starts
+        if self.should_generate_service_worker() {
+            let service_worker = format!(
+                r#"<meta name="dioxus-service-worker" content="{}service-worker.js">"#,
+                self.url_prefix()
+            );
+            replace_or_insert_before("{service_worker}", "</head", &service_worker, &mut html);
+        }
+
+        // Link the web app manifest and keep the install prompt around until the app asks for it
+        if self.krate.config.web.manifest.enabled {
+            let mut manifest_head = format!(
+                r#"<link rel="manifest" href="{}manifest.webmanifest">"#,
+                self.url_prefix()
+            );
+            if let Some(theme_color) = &self.krate.config.web.manifest.theme_color {
+                write!(
+                    &mut manifest_head,
+                    r#"<meta name="theme-color" content="{theme_color}">"#
+                )?;
+            }
+            manifest_head.push_str(INSTALL_PROMPT_HTML);
+            replace_or_insert_before("{web_manifest}", "</head", &manifest_head, &mut html);
+        }
+
         // Replace any special placeholders in the HTML with resolved values
         self.replace_template_placeholders(&mut html);
 
@@ -37,7 +73,7 @@ impl BuildRequest {
     }
 
     // Inject any resources from the config into the html
-    fn inject_resources(&self, html: &mut String) -> Result<()> {
+    fn inject_resources(&self, assets: &AssetManifest, html: &mut String) -> Result<()> {
         // Collect all resources into a list of styles and scripts
         let resources = &self.krate.config.web.resource;
         let mut style_list = resources.style.clone().unwrap_or_default();
@@ -82,10 +118,21 @@ impl BuildRequest {
             self.send_resource_deprecation_warning(script_list, ResourceType::Script);
         }
 
-        // Inject any resources from manganis into the head
-        // if let Some(assets) = assets {
-        //     head_resources.push_str(&assets.head());
-        // }
+        // Preload the fonts from manganis so they start loading before the css that uses them
+        let mut fonts: Vec<_> = assets
+            .assets
+            .values()
+            .filter(|asset| matches!(asset.options(), AssetOptions::Font(font) if font.preloaded()))
+            .map(|asset| asset.bundled_path())
+            .collect();
+        fonts.sort();
+        let prefix = self.url_prefix();
+        for font in fonts {
+            writeln!(
+                &mut head_resources,
+                "<link rel=\"preload\" href=\"{prefix}assets/{font}\" as=\"font\" type=\"font/woff2\" crossorigin=\"anonymous\">",
+            )?;
+        }
 
         replace_or_insert_before("{style_include}", "</head", &head_resources, html);
 
@@ -133,6 +180,195 @@ impl BuildRequest {
         );
     }
 
+    /// The script web workers load the app from. `dioxus-worker` starts it next to the wasm-bindgen output.
+    pub(crate) fn worker_entrypoint(&self) -> String {
+        WORKER_JS.replace("{app_name}", &self.krate.executable_name())
+    }
+
+    /// The script the threads of `dioxus_worker::threads` load the app from in builds with `--threads`
+    pub(crate) fn thread_entrypoint(&self) -> String {
+        THREAD_JS.replace("{app_name}", &self.krate.executable_name())
+    }
+
+    /// Check if the build should include a service worker. The `--service-worker` flag wins over the config, which
+    /// only applies to release builds so stale files are never cached while serving.
+    pub(crate) fn should_generate_service_worker(&self) -> bool {
+        let config = &self.krate.config.web.service_worker;
+        self.build
+            .service_worker
+            .unwrap_or(config.enabled && !self.is_dev_build())
+    }
+
+    /// The service worker that precaches the index, the wasm-bindgen output and every asset of this build.
+    ///
+    /// The cache version is derived from the contents of the build so a new deploy installs a new worker.
+    pub(crate) fn service_worker(&self, assets: &AssetManifest) -> Result<String> {
+        let config = &self.krate.config.web.service_worker;
+        let prefix = self.url_prefix();
+        let mut hasher = DefaultHasher::new();
+        let mut precache = vec![prefix.clone()];
+
+        // The wasm-bindgen output doesn't have hashed names, so its contents decide the version
+        let exe_dir = self.exe_dir();
+        let mut wasm_files: Vec<_> = walkdir::WalkDir::new(&exe_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| !matches!(path.extension().and_then(|e| e.to_str()), Some("br" | "gz")))
+            .collect();
+        wasm_files.sort();
+        for path in wasm_files {
+            std::fs::read(&path)?.hash(&mut hasher);
+            let relative = path
+                .strip_prefix(&exe_dir)
+                .context("wasm-bindgen output escaped the wasm dir")?;
+            precache.push(format!("{prefix}wasm/{}", web_path(relative)));
+        }
+
+        // Assets from the manifest have the hash of their contents in their names
+        let mut bundled: Vec<_> = assets
+            .assets
+            .values()
+            .map(|asset| format!("{prefix}assets/{}", asset.bundled_path()))
+            .collect();
+        // Except for the stylesheet every css module is combined into
+        if let Ok(css_modules) = std::fs::read(self.asset_dir().join(CSS_MODULES_BUNDLED_PATH)) {
+            css_modules.hash(&mut hasher);
+        }
+        bundled.sort();
+        bundled.dedup();
+        precache.extend(bundled);
+
+        // Legacy assets keep their names, so their contents decide the version too
+        for from in self.krate.legacy_asset_dir_files() {
+            let Some(parent) = from.parent() else {
+                continue;
+            };
+            for entry in walkdir::WalkDir::new(&from).sort_by_file_name() {
+                let entry = entry.context("Failed to read the legacy asset dir")?;
+                if entry.file_type().is_file() {
+                    std::fs::read(entry.path())?.hash(&mut hasher);
+                    let relative = entry
+                        .path()
+                        .strip_prefix(parent)
+                        .context("Legacy asset escaped the asset dir")?;
+                    precache.push(format!("{prefix}assets/{}", web_path(relative)));
+                }
+            }
+        }
+
+        // The web app manifest and its icons are written next to the index
+        if self.krate.config.web.manifest.enabled {
+            let root_dir = self.root_dir();
+            let icons = self.web_manifest_icons().into_iter().map(|(_, path)| path);
+            for path in std::iter::once("manifest.webmanifest".to_string()).chain(icons) {
+                std::fs::read(root_dir.join(&path))?.hash(&mut hasher);
+                precache.push(format!("{prefix}{path}"));
+            }
+        }
+
+        precache.extend(config.precache.iter().cloned());
+        precache.hash(&mut hasher);
+
+        Ok(SERVICE_WORKER_JS
+            .replace("{version}", &format!("{:016x}", hasher.finish()))
+            .replace("{index}", &prefix)
+            .replace(
+                "{precache}",
+                &serde_json::to_string_pretty(&precache)
+                    .context("Failed to serialize the precache list")?,
+            ))
+    }
+
+    /// Write the web app manifest and the icons it lists into the public dir
+    pub(crate) fn write_web_manifest(&self) -> Result<()> {
+        let config = &self.krate.config.web.manifest;
+        let prefix = self.url_prefix();
+        let root_dir = self.root_dir();
+
+        // Remove the icons of older builds since their sizes may have changed
+        _ = std::fs::remove_dir_all(root_dir.join("icons"));
+
+        let mut icons = vec![];
+        match &config.icon {
+            Some(icon) => {
+                let source = self.krate.crate_dir().join(icon);
+                for (size, path) in self.web_manifest_icons() {
+                    let options = ImageAssetOptions::new()
+                        .with_png()
+                        .with_size(ImageSize::Manual {
+                            width: size,
+                            height: size,
+                        })
+                        .into_asset_options();
+                    process_file_to(&options, &source, &root_dir.join(&path))
+                        .with_context(|| format!("Failed to generate the app icon from {source:?}"))?;
+                    icons.push(serde_json::json!({
+                        "src": format!("{prefix}{path}"),
+                        "sizes": format!("{size}x{size}"),
+                        "type": "image/png",
+                    }));
+                }
+            }
+            None => tracing::warn!(
+                "The web app manifest has no icon. Browsers only offer to install apps with an icon, set `web.manifest.icon` in the Dioxus.toml"
+            ),
+        }
+
+        let name = config
+            .name
+            .clone()
+            .unwrap_or_else(|| self.krate.config.web.app.title.clone());
+        let mut manifest = serde_json::json!({
+            "name": name,
+            "short_name": config.short_name.as_ref().unwrap_or(&name),
+            "start_url": config.start_url.as_ref().unwrap_or(&prefix),
+            "scope": prefix,
+            "display": config.display,
+            "icons": icons,
+        });
+        let optional = [
+            ("description", &config.description),
+            ("theme_color", &config.theme_color),
+            ("background_color", &config.background_color),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                manifest[key] = value.as_str().into();
+            }
+        }
+
+        std::fs::write(
+            root_dir.join("manifest.webmanifest"),
+            serde_json::to_string_pretty(&manifest)
+                .context("Failed to serialize the web app manifest")?,
+        )?;
+
+        Ok(())
+    }
+
+    /// The sizes and paths relative to the public dir of the icons in the web app manifest
+    fn web_manifest_icons(&self) -> Vec<(u32, String)> {
+        let config = &self.krate.config.web.manifest;
+        if config.icon.is_none() {
+            return vec![];
+        }
+        config
+            .icon_sizes
+            .iter()
+            .map(|size| (*size, format!("icons/icon-{size}.png")))
+            .collect()
+    }
+
+    /// The absolute url the app is served under, with a trailing slash
+    fn url_prefix(&self) -> String {
+        match self.krate.config.web.app.base_path() {
+            "." => "/".to_string(),
+            base_path => format!("/{base_path}/"),
+        }
+    }
+
     /// Replace any special placeholders in the HTML with resolved values
     fn replace_template_placeholders(&self, html: &mut String) {
         let base_path = self.krate.config.web.app.base_path();
@@ -190,6 +426,14 @@ enum ResourceType {
     Script,
 }
 
+/// Join the components of a relative path with `/` for use in a url
+fn web_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 /// Replace a string or insert the new contents before a marker
 fn replace_or_insert_before(
     replace: &str,
@@ -1,6 +1,7 @@
 use crate::{
-    config::BundleConfig, CustomSignCommandSettings, DebianSettings, MacOsSettings,
-    NSISInstallerMode, NsisSettings, PackageType, WebviewInstallMode, WindowsSettings, WixSettings,
+    config::BundleConfig, BundleTypeRole, CustomSignCommandSettings, DebianSettings,
+    DeepLinkProtocol, FileAssociation, MacOsSettings, NSISInstallerMode, NsisSettings, PackageType,
+    WebviewInstallMode, WindowsSettings, WixSettings,
 };
 
 impl From<NsisSettings> for tauri_bundler::NsisSettings {
@@ -34,6 +35,12 @@ impl From<BundleConfig> for tauri_bundler::BundleSettings {
             short_description: val.short_description,
             long_description: val.long_description,
             external_bin: val.external_bin,
+            file_associations: val
+                .file_associations
+                .map(|associations| associations.into_iter().map(Into::into).collect()),
+            deep_link_protocols: val
+                .deep_link_protocols
+                .map(|protocols| protocols.into_iter().map(Into::into).collect()),
             deb: val.deb.map(Into::into).unwrap_or_default(),
             macos: val.macos.map(Into::into).unwrap_or_default(),
             windows: val.windows.map(Into::into).unwrap_or_default(),
@@ -42,6 +49,44 @@ impl From<BundleConfig> for tauri_bundler::BundleSettings {
     }
 }
 
+impl From<FileAssociation> for tauri_utils::config::FileAssociation {
+    fn from(val: FileAssociation) -> Self {
+        tauri_utils::config::FileAssociation {
+            ext: val
+                .ext
+                .into_iter()
+                .map(|ext| tauri_utils::config::AssociationExt(ext.trim_start_matches('.').into()))
+                .collect(),
+            name: val.name,
+            description: val.description,
+            role: val.role.into(),
+            mime_type: val.mime_type,
+        }
+    }
+}
+
+impl From<DeepLinkProtocol> for tauri_utils::config::DeepLinkProtocol {
+    fn from(val: DeepLinkProtocol) -> Self {
+        tauri_utils::config::DeepLinkProtocol {
+            schemes: val.schemes,
+            name: val.name,
+            role: val.role.into(),
+        }
+    }
+}
+
+impl From<BundleTypeRole> for tauri_utils::config::BundleTypeRole {
+    fn from(val: BundleTypeRole) -> Self {
+        match val {
+            BundleTypeRole::Editor => tauri_utils::config::BundleTypeRole::Editor,
+            BundleTypeRole::Viewer => tauri_utils::config::BundleTypeRole::Viewer,
+            BundleTypeRole::Shell => tauri_utils::config::BundleTypeRole::Shell,
+            BundleTypeRole::QLGenerator => tauri_utils::config::BundleTypeRole::QLGenerator,
+            BundleTypeRole::None => tauri_utils::config::BundleTypeRole::None,
+        }
+    }
+}
+
 impl From<DebianSettings> for tauri_bundler::DebianSettings {
     fn from(val: DebianSettings) -> Self {
         tauri_bundler::DebianSettings {
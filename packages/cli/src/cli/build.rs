@@ -20,6 +20,11 @@ pub(crate) struct BuildArgs {
     #[clap(long)]
     pub(crate) profile: Option<String>,
 
+    /// The environment to load public config values from. The values are read from the `[env.<name>]` table of the
+    /// Dioxus.toml [default: "release" for release builds, "dev" otherwise]
+    #[clap(long)]
+    pub(crate) env: Option<String>,
+
     /// Build with custom profile for the fullstack server
     #[clap(long, default_value_t = PROFILE_SERVER.to_string())]
     pub(crate) server_profile: String,
@@ -43,6 +48,10 @@ pub(crate) struct BuildArgs {
     #[serde(default)]
     pub(crate) skip_assets: bool,
 
+    /// The cargo target directory to build in [default: the target directory of the workspace]
+    #[clap(long)]
+    pub(crate) target_dir: Option<PathBuf>,
+
     /// Extra arguments passed to cargo build
     #[clap(last = true)]
     pub(crate) cargo_args: Vec<String>,
@@ -58,12 +67,41 @@ pub(crate) struct BuildArgs {
     #[clap(long, default_value_t = true)]
     pub(crate) debug_symbols: bool,
 
+    /// Generate a service worker that caches the app for offline use [default: false - unless enabled in the Dioxus.toml for release builds]
+    #[arg(long, default_missing_value="true", num_args=0..=1)]
+    pub(crate) service_worker: Option<bool>,
+
+    /// Analyze the size of the wasm bundle and write `analysis.json` and an `analysis.html` treemap next to the build [default: false]
+    ///
+    /// The report breaks every chunk down into sections, crates and functions. The name section of the wasm is kept so
+    /// functions can be named, which makes the bundle larger than it would be without `--analyze`.
+    #[clap(long)]
+    #[serde(default)]
+    pub(crate) analyze: bool,
+
+    /// Build the web app with shared memory so rayon can run work on web workers with `dioxus_worker::threads` [default: false]
+    ///
+    /// This builds with nightly rust and the `atomics` and `bulk-memory` target features, and serves the app with the
+    /// headers browsers require before they share memory with web workers.
+    #[clap(long)]
+    #[serde(default)]
+    pub(crate) threads: bool,
+
     /// Information about the target to build
     #[clap(flatten)]
     pub(crate) target_args: TargetArgs,
 }
 
 impl BuildArgs {
+    /// The name of the environment to load config values from
+    pub(crate) fn environment(&self) -> &str {
+        match &self.env {
+            Some(env) => env,
+            None if self.release => "release",
+            None => "dev",
+        }
+    }
+
     pub async fn run_cmd(mut self) -> Result<StructuredOutput> {
         tracing::info!("Building project...");
 
@@ -111,6 +149,13 @@ impl BuildArgs {
             .platform
             .expect("Platform to be set after autodetection");
 
+        if self.threads && platform != Platform::Web {
+            return Err(anyhow::anyhow!(
+                "`--threads` only applies to web builds. Native platforms can already use threads."
+            )
+            .into());
+        }
+
         // Add any features required to turn on the client
         self.target_args
             .client_features
@@ -1,6 +1,6 @@
 use crate::{AppBundle, BuildArgs, Builder, DioxusCrate, Platform};
 use anyhow::Context;
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
 use tauri_bundler::{BundleBinary, BundleSettings, PackageSettings, SettingsBuilder};
 
 use super::*;
@@ -32,6 +32,25 @@ pub struct Bundle {
     #[clap(long)]
     pub outdir: Option<PathBuf>,
 
+    /// Generate signed update archives and a `latest.json` manifest for apps that use `dioxus-updater`
+    #[clap(long)]
+    pub updater: bool,
+
+    /// The file with the private key used to sign update archives. A new key pair is generated if the file doesn't
+    /// exist. Falls back to the `DIOXUS_UPDATER_PRIVATE_KEY` environment variable.
+    #[clap(long)]
+    pub update_key: Option<PathBuf>,
+
+    /// The output directory of the previous `dx bundle --updater` run. A delta archive with only the files that
+    /// changed since that release is generated next to the full archive.
+    #[clap(long)]
+    pub update_from: Option<PathBuf>,
+
+    /// The url the update archives will be uploaded to. Without it, the urls in `latest.json` are relative to the
+    /// manifest.
+    #[clap(long)]
+    pub update_base_url: Option<String>,
+
     /// The arguments for the dioxus build
     #[clap(flatten)]
     pub(crate) build_arguments: BuildArgs,
@@ -66,7 +85,8 @@ impl Bundle {
         match self.build_arguments.platform() {
             // By default, mac/win/linux work with tauri bundle
             Platform::MacOS | Platform::Linux | Platform::Windows => {
-                let bundles = self.bundle_desktop(krate, bundle)?;
+                let app_dir = bundle.build.root_dir();
+                let bundles = self.bundle_desktop(&krate, bundle)?;
 
                 tracing::info!("Bundled app successfully!");
                 tracing::info!("App produced {} outputs:", bundles.len());
@@ -95,6 +115,13 @@ impl Bundle {
                     }
                 }
 
+                if self.updater {
+                    for src in self.bundle_updater(&krate, &app_dir)? {
+                        tracing::info!("updater - [{}]", src.display());
+                        bundle_paths.push(src);
+                    }
+                }
+
                 cmd_result = StructuredOutput::BundleOutput {
                     bundles: bundle_paths,
                 };
@@ -132,7 +159,7 @@ impl Bundle {
 
     fn bundle_desktop(
         &self,
-        krate: DioxusCrate,
+        krate: &DioxusCrate,
         bundle: AppBundle,
     ) -> Result<Vec<tauri_bundler::Bundle>, Error> {
         _ = std::fs::remove_dir_all(krate.bundle_dir(self.build_arguments.platform()));
@@ -249,4 +276,167 @@ impl Bundle {
 
         Ok(bundles)
     }
+
+    /// Write the signed update archives and the `latest.json` manifest `dioxus-updater` checks for new versions.
+    ///
+    /// If the output directory already has a manifest for the same version, the archives for this target are merged
+    /// into it so every platform can be bundled into the same directory.
+    fn bundle_updater(&self, krate: &DioxusCrate, app_dir: &Path) -> Result<Vec<PathBuf>> {
+        let out_dir = match &self.outdir {
+            Some(outdir) => outdir.clone(),
+            None => krate
+                .bundle_dir(self.build_arguments.platform())
+                .join("updater"),
+        };
+        std::fs::create_dir_all(&out_dir).context("Failed to create the updater directory")?;
+
+        let private_key = self.update_private_key()?;
+        let version = krate.package().version.to_string();
+        let target = self.update_target();
+        let name = krate.executable_name();
+        let mut outputs = vec![];
+
+        let archive = dioxus_updater::create_archive(app_dir)
+            .context("Failed to create the update archive")?;
+        let file_name = format!("{name}-{version}-{target}.tar.gz");
+        let (size, signature) =
+            write_signed_archive(&out_dir, &file_name, &archive, &private_key, &mut outputs)?;
+        let mut platform = dioxus_updater::PlatformUpdate {
+            url: self.update_url(&file_name),
+            signature,
+            size,
+            deltas: vec![],
+        };
+
+        if let Some(previous_dir) = &self.update_from {
+            let (from, previous) = previous_release(previous_dir, &target)?;
+            let previous_app =
+                tempfile::tempdir().context("Failed to create a temporary directory")?;
+            dioxus_updater::apply_archive(&previous, previous_app.path())
+                .context("Failed to extract the previous release")?;
+
+            let delta = dioxus_updater::create_delta_archive(previous_app.path(), app_dir)
+                .context("Failed to create the delta update archive")?;
+            let file_name = format!("{name}-{from}-{version}-{target}.delta.tar.gz");
+            let (size, signature) =
+                write_signed_archive(&out_dir, &file_name, &delta, &private_key, &mut outputs)?;
+            platform.deltas.push(dioxus_updater::DeltaUpdate {
+                from,
+                url: self.update_url(&file_name),
+                signature,
+                size,
+            });
+        }
+
+        let manifest_path = out_dir.join("latest.json");
+        let mut manifest = std::fs::read_to_string(&manifest_path)
+            .ok()
+            .and_then(|manifest| {
+                serde_json::from_str::<dioxus_updater::UpdateManifest>(&manifest).ok()
+            })
+            .filter(|manifest| manifest.version == version)
+            .unwrap_or_else(|| dioxus_updater::UpdateManifest::new(&version));
+        manifest.platforms.insert(target, platform);
+        std::fs::write(
+            &manifest_path,
+            serde_json::to_string_pretty(&manifest).context("Failed to serialize latest.json")?,
+        )
+        .context("Failed to write latest.json")?;
+        outputs.push(manifest_path);
+
+        Ok(outputs)
+    }
+
+    /// Read the private key used to sign updates, generating a new key pair if the key file doesn't exist yet
+    fn update_private_key(&self) -> Result<String> {
+        let Some(path) = &self.update_key else {
+            return std::env::var("DIOXUS_UPDATER_PRIVATE_KEY").map_err(|_| {
+                anyhow::anyhow!("Signing updates requires a private key. Pass `--update-key <file>` or set DIOXUS_UPDATER_PRIVATE_KEY").into()
+            });
+        };
+
+        if path.exists() {
+            let key = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read the update key {}", path.display()))?;
+            return Ok(key.trim().to_string());
+        }
+
+        let keys = dioxus_updater::KeyPair::generate().context("Failed to generate update keys")?;
+        std::fs::write(path, &keys.private_key)
+            .with_context(|| format!("Failed to write the update key {}", path.display()))?;
+        tracing::info!(
+            "Generated a new update signing key at {}. Keep it secret and reuse it for every release.",
+            path.display()
+        );
+        tracing::info!(
+            "Pass the public key to `Updater::new` in your app: {}",
+            keys.public_key
+        );
+
+        Ok(keys.private_key)
+    }
+
+    /// The `{os}-{arch}` name of the target in the update manifest. This matches `dioxus_updater::current_target`
+    /// in the bundled app.
+    fn update_target(&self) -> String {
+        let os = match self.build_arguments.platform() {
+            Platform::MacOS => "macos",
+            Platform::Windows => "windows",
+            _ => "linux",
+        };
+        let arch = match &self.build_arguments.target_args.target {
+            Some(triple) => triple.split('-').next().unwrap_or(std::env::consts::ARCH),
+            None => std::env::consts::ARCH,
+        };
+        format!("{os}-{arch}")
+    }
+
+    fn update_url(&self, file_name: &str) -> String {
+        match &self.update_base_url {
+            Some(base) => format!("{}/{file_name}", base.trim_end_matches('/')),
+            None => file_name.to_string(),
+        }
+    }
+}
+
+/// Write an update archive and its `.sig` file. Returns the size and signature of the archive.
+fn write_signed_archive(
+    out_dir: &Path,
+    file_name: &str,
+    archive: &[u8],
+    private_key: &str,
+    outputs: &mut Vec<PathBuf>,
+) -> Result<(u64, String)> {
+    let signature =
+        dioxus_updater::sign(archive, private_key).context("Failed to sign the update archive")?;
+
+    let path = out_dir.join(file_name);
+    std::fs::write(&path, archive)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    let signature_path = out_dir.join(format!("{file_name}.sig"));
+    std::fs::write(&signature_path, &signature)
+        .with_context(|| format!("Failed to write {}", signature_path.display()))?;
+
+    outputs.push(path);
+    outputs.push(signature_path);
+
+    Ok((archive.len() as u64, signature))
+}
+
+/// Find the version and full archive of the target in the output directory of a previous release
+fn previous_release(dir: &Path, target: &str) -> Result<(String, Vec<u8>)> {
+    let manifest = std::fs::read_to_string(dir.join("latest.json"))
+        .with_context(|| format!("No latest.json in the previous release {}", dir.display()))?;
+    let manifest: dioxus_updater::UpdateManifest =
+        serde_json::from_str(&manifest).context("Failed to parse the previous latest.json")?;
+    let platform = manifest
+        .platforms
+        .get(target)
+        .with_context(|| format!("The previous release has no archive for {target}"))?;
+
+    let file_name = platform.url.rsplit('/').next().unwrap_or(&platform.url);
+    let archive = std::fs::read(dir.join(file_name))
+        .with_context(|| format!("Failed to read the previous archive {file_name}"))?;
+
+    Ok((manifest.version, archive))
 }
@@ -10,6 +10,7 @@ pub(crate) mod link;
 pub(crate) mod run;
 pub(crate) mod serve;
 pub(crate) mod target;
+pub(crate) mod test;
 pub(crate) mod translate;
 pub(crate) mod verbosity;
 
@@ -86,6 +87,10 @@ pub(crate) enum Commands {
     #[clap(name = "run")]
     Run(run::RunArgs),
 
+    /// Run the project's browser tests in a headless browser.
+    #[clap(name = "test")]
+    Test(test::TestArgs),
+
     /// Dioxus config file controls.
     #[clap(subcommand)]
     #[clap(name = "config")]
@@ -106,6 +111,7 @@ impl Display for Commands {
             Commands::Check(_) => write!(f, "check"),
             Commands::Bundle(_) => write!(f, "bundle"),
             Commands::Run(_) => write!(f, "run"),
+            Commands::Test(_) => write!(f, "test"),
         }
     }
 }
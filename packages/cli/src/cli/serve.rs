@@ -26,6 +26,30 @@ pub(crate) struct ServeArgs {
     #[clap(long)]
     pub(crate) cross_origin_policy: bool,
 
+    /// Serve the app over https with a locally-trusted certificate [default: false - unless enabled in the Dioxus.toml]
+    ///
+    /// The certificate is generated with `mkcert` if it is installed, or with `openssl` otherwise.
+    #[arg(long, default_missing_value="true", num_args=0..=1)]
+    pub(crate) https: Option<bool>,
+
+    /// Serve several packages of the workspace in one session, e.g. `--apps web,admin,api`
+    ///
+    /// Each app gets its own devserver port, counting up from `--port`, and its own cargo target directory so the
+    /// builds don't block each other. Press `tab` to switch between the apps in the TUI.
+    #[clap(long, value_delimiter = ',')]
+    pub(crate) apps: Vec<String>,
+
+    /// The lowest level of the app's logs to show [default: info]. Press `l` to change it while serving.
+    ///
+    /// This only filters logs written with `dioxus::logger`. The output of `dx` itself is controlled with
+    /// `--verbose` and `--trace`.
+    #[clap(long)]
+    pub(crate) log_level: Option<tracing::Level>,
+
+    /// Only show the app's logs from these modules and their children, e.g. `--log-filter my_app::db,my_app::auth`
+    #[clap(long, value_delimiter = ',')]
+    pub(crate) log_filter: Vec<String>,
+
     /// Additional arguments to pass to the executable
     #[clap(long)]
     pub(crate) args: Vec<String>,
@@ -55,7 +79,13 @@ impl ServeArgs {
     }
 
     pub(crate) async fn load_krate(&mut self) -> Result<DioxusCrate> {
-        let krate = DioxusCrate::new(&self.build_arguments.target_args)?;
+        let mut krate = DioxusCrate::new(&self.build_arguments.target_args)?;
+
+        // The https flag overrides the https setting in the Dioxus.toml
+        if let Some(https) = self.https {
+            krate.config.web.https.enabled = Some(https);
+        }
+
         self.resolve(&krate).await?;
         Ok(krate)
     }
@@ -87,6 +117,25 @@ impl ServeArgs {
         Ok(())
     }
 
+    /// The arguments for each app being served. Without `--apps`, this is just the app selected by the target args.
+    pub(crate) fn app_args(&self) -> Vec<ServeArgs> {
+        if self.apps.is_empty() {
+            return vec![self.clone()];
+        }
+
+        self.apps
+            .iter()
+            .enumerate()
+            .map(|(idx, package)| {
+                let mut args = self.clone();
+                args.apps.clear();
+                args.build_arguments.target_args.package = Some(package.clone());
+                args.address.port = self.address.port + idx as u16;
+                args
+            })
+            .collect()
+    }
+
     pub(crate) fn should_hotreload(&self) -> bool {
         self.hot_reload.unwrap_or(true)
     }
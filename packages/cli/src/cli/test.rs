@@ -0,0 +1,621 @@
+use super::*;
+use crate::{wasm_bindgen::WasmBindgen, wasm_bindgen::WasmBindgenBuilder, DioxusCrate, Platform};
+use anyhow::{anyhow, Context};
+use base64::Engine;
+use serde_json::{json, Value};
+use std::{
+    net::SocketAddr,
+    path::Path,
+    time::{Duration, Instant},
+};
+use tokio::process::{Child, Command};
+
+/// Run the `#[wasm_bindgen_test]` tests of the project in a headless browser
+#[derive(Clone, Debug, Parser)]
+pub(crate) struct TestArgs {
+    /// Build the tests in release mode [default: false]
+    #[clap(long, short)]
+    pub(crate) release: bool,
+
+    /// Only build and run the integration test with this name
+    #[clap(long)]
+    pub(crate) test: Option<String>,
+
+    /// The browser to run the tests in. Defaults to the first one with a WebDriver on the PATH
+    #[clap(long, value_enum)]
+    pub(crate) browser: Option<TestBrowser>,
+
+    /// Show the browser window instead of running it headless [default: false]
+    #[clap(long)]
+    pub(crate) headed: bool,
+
+    /// Connect to an already running WebDriver server instead of spawning one
+    #[clap(long)]
+    pub(crate) webdriver: Option<String>,
+
+    /// How long a single test binary may run before it is aborted, in seconds
+    #[clap(long, default_value_t = 300)]
+    pub(crate) timeout: u64,
+
+    /// The directory screenshots of failed test binaries are written to [default: target/dx/test/screenshots]
+    #[clap(long)]
+    pub(crate) screenshots: Option<PathBuf>,
+
+    /// Information about the target to build
+    #[clap(flatten)]
+    pub(crate) target_args: TargetArgs,
+
+    /// Arguments passed to the test harness, like a test name filter or `--include-ignored`
+    #[clap(last = true)]
+    pub(crate) args: Vec<String>,
+}
+
+/// A browser we know how to drive through WebDriver
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum TestBrowser {
+    Chrome,
+    Firefox,
+    Safari,
+}
+
+/// The outcome of running one test binary in the browser
+struct TestRun {
+    passed: bool,
+    failures: Vec<String>,
+    screenshot: Option<PathBuf>,
+}
+
+impl TestArgs {
+    pub(crate) async fn test(self) -> Result<StructuredOutput> {
+        let krate =
+            DioxusCrate::new(&self.target_args).context("Failed to load Dioxus workspace")?;
+
+        let bindgen_version = self.verify_tooling(&krate).await?;
+
+        tracing::info!("Building tests...");
+        let binaries = self.build_tests(&krate).await?;
+        if binaries.is_empty() {
+            tracing::warn!("No test binaries were produced");
+            return Ok(StructuredOutput::Success);
+        }
+
+        // Every test binary gets its own folder with the generated bindings and a page that runs them
+        let test_dir = krate.out_dir().join("test");
+        let mut suites = vec![];
+        for binary in binaries {
+            let name = binary
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .context("Test binary has no name")?
+                .to_string();
+
+            tracing::debug!("Running wasm-bindgen on {}", binary.display());
+            let out_dir = test_dir.join(&name);
+            _ = std::fs::remove_dir_all(&out_dir);
+            std::fs::create_dir_all(&out_dir)?;
+
+            WasmBindgenBuilder::new(bindgen_version.clone())
+                .input_path(&binary)
+                .target("web")
+                .debug(true)
+                .keep_debug(true)
+                .demangle(true)
+                .out_name("test")
+                .out_dir(&out_dir)
+                .build()
+                .run()
+                .await
+                .context("Failed to generate wasm-bindgen bindings")?;
+
+            if !out_dir.join("test.js").exists() {
+                return Err(anyhow!("wasm-bindgen did not generate bindings for {name}").into());
+            }
+
+            std::fs::write(out_dir.join("index.html"), TEST_HTML)?;
+            std::fs::write(out_dir.join("run.js"), harness_js(&self.args))?;
+            suites.push(name);
+        }
+
+        let addr = serve_tests(&test_dir).await?;
+        let (driver, mut driver_process) = self.start_webdriver().await?;
+
+        let screenshot_dir = self
+            .screenshots
+            .clone()
+            .unwrap_or_else(|| test_dir.join("screenshots"));
+
+        let mut failed = vec![];
+        let mut result = Ok(());
+        for suite in &suites {
+            tracing::info!("Running {suite}");
+
+            let url = format!("http://{addr}/{suite}/index.html");
+            match self.run_suite(&driver, &url, suite, &screenshot_dir).await {
+                Ok(run) if run.passed => {}
+                Ok(run) => {
+                    if let Some(screenshot) = &run.screenshot {
+                        tracing::error!("Screenshot of {suite} saved to {}", screenshot.display());
+                    }
+                    failed.push((suite.clone(), run));
+                }
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+        }
+
+        driver.close().await;
+        if let Some(process) = driver_process.as_mut() {
+            _ = process.kill().await;
+        }
+        result?;
+
+        if !failed.is_empty() {
+            for (suite, run) in &failed {
+                tracing::error!("{suite} failed:");
+                for test in &run.failures {
+                    tracing::error!("    {test}");
+                }
+            }
+
+            return Err(
+                anyhow!("{} of {} test binaries failed", failed.len(), suites.len()).into(),
+            );
+        }
+
+        tracing::info!("All {} test binaries passed", suites.len());
+
+        Ok(StructuredOutput::Success)
+    }
+
+    /// Make sure the wasm target and the wasm-bindgen cli that matches the crate are installed
+    async fn verify_tooling(&self, krate: &DioxusCrate) -> Result<String> {
+        match crate::RustupShow::from_cli().await {
+            Ok(rustup) if !rustup.has_wasm32_unknown_unknown() => {
+                tracing::info!(
+                    "Tests require wasm32-unknown-unknown to be installed. Installing..."
+                );
+                let _ = Command::new("rustup")
+                    .args(["target", "add", "wasm32-unknown-unknown"])
+                    .output()
+                    .await?;
+            }
+            Ok(_) => {}
+            Err(err) => tracing::warn!("Failed to verify the wasm target is installed: {err}"),
+        }
+
+        let version = krate.wasm_bindgen_version().ok_or(anyhow!(
+            "failed to detect wasm-bindgen version. Add wasm-bindgen-test to your dev-dependencies to write browser tests"
+        ))?;
+
+        if !WasmBindgen::verify_install(&version).await? {
+            WasmBindgen::install(&version)
+                .await
+                .context("failed to install wasm-bindgen-cli")?;
+        }
+
+        Ok(version)
+    }
+
+    /// Run `cargo test --no-run` for the wasm target, returning the test binaries it produced
+    async fn build_tests(&self, krate: &DioxusCrate) -> Result<Vec<PathBuf>> {
+        let mut cmd = Command::new("cargo");
+        cmd.arg("test")
+            .arg("--no-run")
+            .arg("--target")
+            .arg("wasm32-unknown-unknown")
+            .arg("--message-format")
+            .arg("json-diagnostic-rendered-ansi")
+            .arg("--package")
+            .arg(&krate.package().name)
+            .current_dir(krate.crate_dir());
+
+        if self.release {
+            cmd.arg("--release");
+        }
+
+        if let Some(test) = &self.test {
+            cmd.arg("--test").arg(test);
+        }
+
+        if self.target_args.no_default_features {
+            cmd.arg("--no-default-features");
+        }
+
+        let mut features = self.target_args.features.clone();
+        features.extend(self.target_args.client_features.clone());
+        if features.is_empty() {
+            // Enable the web renderer so component tests can mount into the test page
+            let web = krate.feature_for_platform(Platform::Web);
+            if krate.package().features.contains_key(&web) {
+                features.push(web);
+            }
+        }
+        if !features.is_empty() {
+            cmd.arg("--features").arg(features.join(" "));
+        }
+
+        tracing::trace!("Test cargo args: {:#?}", cmd);
+
+        let output = cmd
+            .stderr(Stdio::inherit())
+            .output()
+            .await
+            .context("Failed to spawn cargo test")?;
+
+        let mut binaries = vec![];
+        for message in cargo_metadata::Message::parse_stream(output.stdout.as_slice()).flatten() {
+            match message {
+                cargo_metadata::Message::CompilerMessage(msg) => {
+                    if let Some(rendered) = msg.message.rendered {
+                        tracing::info!("{rendered}");
+                    }
+                }
+                cargo_metadata::Message::CompilerArtifact(artifact) if artifact.profile.test => {
+                    binaries.extend(artifact.executable.map(|exe| exe.into_std_path_buf()));
+                }
+                _ => {}
+            }
+        }
+
+        if !output.status.success() {
+            return Err(anyhow!("Failed to build the tests").into());
+        }
+
+        Ok(binaries)
+    }
+
+    /// Connect to the WebDriver server, spawning one for the selected browser if we weren't given a url
+    async fn start_webdriver(&self) -> Result<(WebDriver, Option<Child>)> {
+        let browser = match self.browser {
+            Some(browser) => browser,
+            None => TestBrowser::autodetect().context(
+                "Could not find a WebDriver on the PATH. Install chromedriver, geckodriver or safaridriver, or pass --webdriver",
+            )?,
+        };
+
+        let (url, process) = match &self.webdriver {
+            Some(url) => (url.trim_end_matches('/').to_string(), None),
+            None => {
+                let port = std::net::TcpListener::bind("127.0.0.1:0")?
+                    .local_addr()?
+                    .port();
+                let process = browser
+                    .driver_command(port)
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .kill_on_drop(true)
+                    .spawn()
+                    .with_context(|| format!("Failed to launch {}", browser.driver()))?;
+                (format!("http://127.0.0.1:{port}"), Some(process))
+            }
+        };
+
+        let driver = WebDriver::connect(&url, browser.capabilities(!self.headed)).await?;
+
+        Ok((driver, process))
+    }
+
+    /// Load the test page of a single test binary and wait for the harness to report its results
+    async fn run_suite(
+        &self,
+        driver: &WebDriver,
+        url: &str,
+        suite: &str,
+        screenshot_dir: &Path,
+    ) -> Result<TestRun> {
+        driver.navigate(url).await?;
+
+        let deadline = Instant::now() + Duration::from_secs(self.timeout);
+        let mut printed = 0;
+        let (output, passed) = loop {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let state = driver
+                .execute(
+                    "return [document.getElementById('output').textContent, window.__dx_test_done];",
+                )
+                .await?;
+
+            let output = state[0].as_str().unwrap_or_default().to_string();
+
+            // Forward the complete lines the harness printed since the last poll
+            if let Some(end) = output.rfind('\n') {
+                if end + 1 > printed {
+                    for line in output[printed..end].lines() {
+                        tracing::info!("[{suite}] {line}");
+                    }
+                    printed = end + 1;
+                }
+            }
+
+            if let Some(passed) = state[1].as_bool() {
+                break (output, passed);
+            }
+
+            if Instant::now() > deadline {
+                break (output, false);
+            }
+        };
+
+        let failures = failed_tests(&output);
+        if passed {
+            return Ok(TestRun {
+                passed,
+                failures,
+                screenshot: None,
+            });
+        }
+
+        // The page still shows whatever the failing tests rendered, which is usually the quickest way to debug them
+        let screenshot = match driver.screenshot().await {
+            Ok(png) => {
+                std::fs::create_dir_all(screenshot_dir)?;
+                let path = screenshot_dir.join(format!("{suite}.png"));
+                std::fs::write(&path, png)?;
+                Some(path)
+            }
+            Err(err) => {
+                tracing::warn!("Failed to take a screenshot of {suite}: {err}");
+                None
+            }
+        };
+
+        Ok(TestRun {
+            passed,
+            failures,
+            screenshot,
+        })
+    }
+}
+
+impl TestBrowser {
+    fn autodetect() -> Option<Self> {
+        [Self::Chrome, Self::Firefox, Self::Safari]
+            .into_iter()
+            .find(|browser| browser.driver_path().is_some())
+    }
+
+    /// The name of the WebDriver executable for this browser
+    fn driver(self) -> &'static str {
+        match self {
+            Self::Chrome => "chromedriver",
+            Self::Firefox => "geckodriver",
+            Self::Safari => "safaridriver",
+        }
+    }
+
+    /// Find the WebDriver either through the same environment variables wasm-bindgen-test-runner uses or on the PATH
+    fn driver_path(self) -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os(self.driver().to_uppercase()) {
+            return Some(PathBuf::from(path));
+        }
+
+        let exe = format!("{}{}", self.driver(), std::env::consts::EXE_SUFFIX);
+        std::env::split_paths(&std::env::var_os("PATH")?)
+            .map(|dir| dir.join(&exe))
+            .find(|path| path.is_file())
+    }
+
+    fn driver_command(self, port: u16) -> Command {
+        let mut cmd = Command::new(
+            self.driver_path()
+                .unwrap_or_else(|| PathBuf::from(self.driver())),
+        );
+        match self {
+            Self::Chrome => cmd.arg(format!("--port={port}")),
+            Self::Firefox | Self::Safari => cmd.arg("--port").arg(port.to_string()),
+        };
+        cmd
+    }
+
+    fn capabilities(self, headless: bool) -> Value {
+        match self {
+            Self::Chrome => {
+                let mut args = vec![
+                    "--no-sandbox",
+                    "--disable-dev-shm-usage",
+                    "--window-size=1280,800",
+                ];
+                if headless {
+                    args.push("--headless=new");
+                }
+                json!({ "goog:chromeOptions": { "args": args } })
+            }
+            Self::Firefox => {
+                let args = if headless { vec!["-headless"] } else { vec![] };
+                json!({ "moz:firefoxOptions": { "args": args } })
+            }
+            // Safari doesn't support running headless
+            Self::Safari => json!({}),
+        }
+    }
+}
+
+/// A minimal client for the subset of the W3C WebDriver protocol the test runner needs
+struct WebDriver {
+    client: reqwest::Client,
+    session: String,
+}
+
+impl WebDriver {
+    /// Wait for the WebDriver server at `url` to come up and start a new browser session
+    async fn connect(url: &str, capabilities: Value) -> Result<Self> {
+        let client = reqwest::Client::new();
+
+        let deadline = Instant::now() + Duration::from_secs(15);
+        loop {
+            let ready = client
+                .get(format!("{url}/status"))
+                .send()
+                .await
+                .is_ok_and(|res| res.status().is_success());
+            if ready {
+                break;
+            }
+            if Instant::now() > deadline {
+                return Err(anyhow!("Timed out waiting for the WebDriver at {url}").into());
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        let response: Value = client
+            .post(format!("{url}/session"))
+            .json(&json!({ "capabilities": { "alwaysMatch": capabilities } }))
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        let session = response["value"]["sessionId"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Failed to start a browser session: {}", response["value"]))?;
+
+        Ok(Self {
+            session: format!("{url}/session/{session}"),
+            client,
+        })
+    }
+
+    async fn command(&self, method: reqwest::Method, path: &str, body: Value) -> Result<Value> {
+        let mut request = self
+            .client
+            .request(method, format!("{}{path}", self.session));
+        if !body.is_null() {
+            request = request.json(&body);
+        }
+
+        let mut response: Value = request.send().await?.json().await?;
+        let value = response["value"].take();
+        if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+            return Err(anyhow!("WebDriver error: {error}: {}", value["message"]).into());
+        }
+
+        Ok(value)
+    }
+
+    async fn navigate(&self, url: &str) -> Result<()> {
+        self.command(reqwest::Method::POST, "/url", json!({ "url": url }))
+            .await?;
+        Ok(())
+    }
+
+    async fn execute(&self, script: &str) -> Result<Value> {
+        self.command(
+            reqwest::Method::POST,
+            "/execute/sync",
+            json!({ "script": script, "args": [] }),
+        )
+        .await
+    }
+
+    async fn screenshot(&self) -> Result<Vec<u8>> {
+        let value = self
+            .command(reqwest::Method::GET, "/screenshot", Value::Null)
+            .await?;
+        let png = base64::engine::general_purpose::STANDARD
+            .decode(value.as_str().unwrap_or_default())
+            .context("Failed to decode screenshot")?;
+        Ok(png)
+    }
+
+    async fn close(&self) {
+        _ = self.command(reqwest::Method::DELETE, "", Value::Null).await;
+    }
+}
+
+/// Serve the generated test pages on a random local port
+async fn serve_tests(dir: &Path) -> Result<SocketAddr> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let router = axum::Router::new().fallback_service(tower_http::services::ServeDir::new(dir));
+
+    tokio::spawn(async move {
+        if let Err(err) = axum::serve(listener, router).await {
+            tracing::error!("Test server failed: {err}");
+        }
+    });
+
+    Ok(addr)
+}
+
+/// The page every test binary is loaded into. Component tests can render into `#main` the same way a launched app
+/// would, and the harness writes its report into `#output`.
+const TEST_HTML: &str = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="utf-8">
+    <title>dx test</title>
+  </head>
+  <body>
+    <div id="main"></div>
+    <pre id="output"></pre>
+    <script type="module" src="run.js"></script>
+  </body>
+</html>
+"#;
+
+/// Generate the module that instantiates the test binary and runs all of its `#[wasm_bindgen_test]`s
+fn harness_js(args: &[String]) -> String {
+    let args = serde_json::to_string(args).expect("args should serialize");
+
+    format!(
+        r#"import * as bindings from "./test.js";
+
+// Route console output through the harness so it is attached to the test that produced it
+for (const method of ["log", "debug", "info", "warn", "error"]) {{
+  const original = console[method];
+  const capture = bindings[`__wbgtest_console_${{method}}`];
+  if (capture) {{
+    console[method] = function (...args) {{
+      original.apply(this, args);
+      capture(args);
+    }};
+  }}
+}}
+
+try {{
+  const wasm = await bindings.default();
+  const tests = Object.keys(wasm).filter((name) => name.startsWith("__wbgt_"));
+  const cx = new bindings.WasmBindgenTestContext();
+  cx.args({args});
+  window.__dx_test_done = await cx.run(tests.map((name) => wasm[name]));
+}} catch (err) {{
+  document.getElementById("output").textContent += `\nerror: ${{err}}\n${{err.stack ?? ""}}\n`;
+  window.__dx_test_done = false;
+}}
+"#
+    )
+}
+
+/// Collect the names of the tests the harness reported as failed
+fn failed_tests(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("test ")?.strip_suffix(" ... FAIL"))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_failed_tests() {
+        let output = "running 3 tests\n\
+            \n\
+            test app::renders ... ok\n\
+            test app::clicks ... FAIL\n\
+            test app::ignored ... ignored\n";
+
+        assert_eq!(failed_tests(output), vec!["app::clicks".to_string()]);
+    }
+
+    #[test]
+    fn harness_passes_args() {
+        let js = harness_js(&["counter".to_string(), "--include-ignored".to_string()]);
+        assert!(js.contains(r#"cx.args(["counter","--include-ignored"]);"#));
+    }
+}
@@ -8,6 +8,51 @@ pub(crate) struct ApplicationConfig {
 
     #[serde(default)]
     pub(crate) sub_package: Option<String>,
+
+    /// CSS transformers to run before each build and when one of their dependencies changes while serving
+    #[serde(default)]
+    pub(crate) css: Vec<CssPipelineConfig>,
+}
+
+/// A CSS transformer that generates a stylesheet from an input file
+///
+/// ```toml
+/// [[application.css]]
+/// transformer = "tailwind"
+/// input = "tailwind.css"
+/// output = "assets/tailwind.css"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CssPipelineConfig {
+    /// The program used to transform the input
+    pub(crate) transformer: CssTransformer,
+
+    /// The input stylesheet, relative to the crate root
+    pub(crate) input: PathBuf,
+
+    /// The generated stylesheet, relative to the crate root. Reference this file with `asset!()`.
+    pub(crate) output: PathBuf,
+
+    /// Override the program and any leading arguments used to run the transformer, for example
+    /// `["npx", "@tailwindcss/cli"]`. The input and output arguments are added after this command.
+    #[serde(default)]
+    pub(crate) command: Option<Vec<String>>,
+
+    /// Globs of the files tailwind scans for class names, relative to the crate root. Defaults to every rust file
+    /// in the crate.
+    #[serde(default)]
+    pub(crate) content: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CssTransformer {
+    /// Run the tailwindcss CLI
+    Tailwind,
+    /// Run the dart sass CLI
+    Sass,
+    /// Run the lightningcss CLI
+    Lightningcss,
 }
 
 pub(crate) fn asset_dir_default() -> PathBuf {
@@ -13,11 +13,74 @@ pub(crate) struct BundleConfig {
     pub(crate) short_description: Option<String>,
     pub(crate) long_description: Option<String>,
     pub(crate) external_bin: Option<Vec<String>>,
+    /// The file types the app can open. The app is listed in "Open with" for these files.
+    pub(crate) file_associations: Option<Vec<FileAssociation>>,
+    /// The URL schemes that open the app, like `myapp` for `myapp://notes/42`.
+    pub(crate) deep_link_protocols: Option<Vec<DeepLinkProtocol>>,
+    /// Why the app uses Face ID. iOS shows it when the app asks for Face ID the first time.
+    pub(crate) face_id_usage_description: Option<String>,
     pub(crate) deb: Option<DebianSettings>,
     pub(crate) macos: Option<MacOsSettings>,
     pub(crate) windows: Option<WindowsSettings>,
 }
 
+/// A file type the app can open
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct FileAssociation {
+    /// The extensions of the file type, like `["md", "markdown"]`
+    pub(crate) ext: Vec<String>,
+    /// The name of the file type. Defaults to the first extension.
+    pub(crate) name: Option<String>,
+    /// The description of the file type that is shown in Windows Explorer.
+    pub(crate) description: Option<String>,
+    /// What the app does with the files on macOS and iOS.
+    #[serde(default)]
+    pub(crate) role: BundleTypeRole,
+    /// The mime type of the files, like `text/markdown`. Used on Linux.
+    #[serde(alias = "mime-type")]
+    pub(crate) mime_type: Option<String>,
+}
+
+/// URL schemes that open the app
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DeepLinkProtocol {
+    /// The schemes without `://`, like `["myapp"]`
+    pub(crate) schemes: Vec<String>,
+    /// The name of the protocol on macOS and iOS. Defaults to the bundle identifier and the first scheme.
+    pub(crate) name: Option<String>,
+    /// What the app does with the links on macOS and iOS.
+    #[serde(default)]
+    pub(crate) role: BundleTypeRole,
+}
+
+/// The `CFBundleTypeRole` of a file association or deep link protocol
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub(crate) enum BundleTypeRole {
+    /// The app can read and edit the files
+    #[default]
+    Editor,
+    /// The app can only read the files
+    Viewer,
+    /// The app provides runtime services for the files
+    Shell,
+    /// The app generates Quick Look previews of the files
+    QLGenerator,
+    /// The app declares the file type but doesn't open it
+    None,
+}
+
+impl BundleTypeRole {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Editor => "Editor",
+            Self::Viewer => "Viewer",
+            Self::Shell => "Shell",
+            Self::QLGenerator => "QLGenerator",
+            Self::None => "None",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub(crate) struct DebianSettings {
     // OS-specific settings:
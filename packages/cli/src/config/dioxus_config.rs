@@ -3,6 +3,7 @@ use crate::Result;
 use anyhow::Context;
 use krates::{Krates, NodeId};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct DioxusConfig {
@@ -16,6 +17,18 @@ pub(crate) struct DioxusConfig {
 
     #[serde(default)]
     pub(crate) bundle: BundleConfig,
+
+    /// Public config values for each environment, read by `dioxus_config!` structs in the app
+    ///
+    /// ```toml
+    /// [env.dev]
+    /// API_URL = "http://localhost:8080/api"
+    ///
+    /// [env.release]
+    /// API_URL = "https://example.com/api"
+    /// ```
+    #[serde(default)]
+    pub(crate) env: HashMap<String, HashMap<String, toml::Value>>,
 }
 
 impl Default for DioxusConfig {
@@ -24,6 +37,7 @@ impl Default for DioxusConfig {
             application: ApplicationConfig {
                 asset_dir: asset_dir_default(),
                 sub_package: None,
+                css: vec![],
             },
             web: WebConfig {
                 app: WebAppConfig {
@@ -48,14 +62,44 @@ impl Default for DioxusConfig {
                 },
                 pre_compress: true,
                 wasm_opt: Default::default(),
+                service_worker: Default::default(),
+                manifest: Default::default(),
+                size_budget: Default::default(),
             },
             desktop: DesktopConfig::default(),
             bundle: BundleConfig::default(),
+            env: HashMap::new(),
         }
     }
 }
 
 impl DioxusConfig {
+    /// Get the public config values of an environment as strings that can be passed to the app through env vars
+    pub(crate) fn env_values(&self, environment: &str) -> Result<Vec<(String, String)>> {
+        let Some(values) = self.env.get(environment) else {
+            return Ok(Vec::new());
+        };
+
+        values
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    toml::Value::String(value) => value.clone(),
+                    toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+                        value.to_string()
+                    }
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "The config value `{key}` in `[env.{environment}]` must be a string, number or boolean"
+                        )
+                        .into())
+                    }
+                };
+                Ok((key.to_uppercase(), value))
+            })
+            .collect()
+    }
+
     pub fn load(krates: &Krates, package: NodeId) -> Result<Option<Self>> {
         // Walk up from the cargo.toml to the root of the workspace looking for Dioxus.toml
         let mut current_dir = krates[package]
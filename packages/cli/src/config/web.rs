@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct WebConfig {
@@ -25,6 +25,18 @@ pub(crate) struct WebConfig {
     /// The wasm-opt configuration
     #[serde(default)]
     pub(crate) wasm_opt: WasmOptConfig,
+
+    /// The service worker configuration
+    #[serde(default)]
+    pub(crate) service_worker: WebServiceWorkerConfig,
+
+    /// The web app manifest configuration
+    #[serde(default)]
+    pub(crate) manifest: WebManifestConfig,
+
+    /// The size budget of the bundle
+    #[serde(default)]
+    pub(crate) size_budget: WebSizeBudgetConfig,
 }
 
 impl Default for WebConfig {
@@ -37,10 +49,106 @@ impl Default for WebConfig {
             proxy: Default::default(),
             watcher: Default::default(),
             resource: Default::default(),
+            service_worker: Default::default(),
+            manifest: Default::default(),
+            size_budget: Default::default(),
+        }
+    }
+}
+
+/// The size budget of the bundle. Builds that go over the budget print a warning.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub(crate) struct WebSizeBudgetConfig {
+    /// The most the wasm of the app may weigh in kilobytes, before compression
+    pub(crate) wasm_kb: Option<u64>,
+}
+
+/// The service worker configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub(crate) struct WebServiceWorkerConfig {
+    /// Generate a service worker that caches the app for offline use in release builds [default: false]
+    #[serde(default = "false_bool")]
+    pub(crate) enabled: bool,
+
+    /// Extra urls the service worker should cache when it is installed
+    #[serde(default)]
+    pub(crate) precache: Vec<String>,
+}
+
+/// The web app manifest that lets browsers install the app as a PWA
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WebManifestConfig {
+    /// Generate a `manifest.webmanifest` and link it from the index.html [default: false]
+    #[serde(default = "false_bool")]
+    pub(crate) enabled: bool,
+
+    /// The name of the installed app [default: the title of the app]
+    pub(crate) name: Option<String>,
+
+    /// The name shown where there is little space, like the home screen [default: the name]
+    pub(crate) short_name: Option<String>,
+
+    /// A description of the app
+    pub(crate) description: Option<String>,
+
+    /// The icon of the installed app, relative to the crate root. It is resized to every size in `icon_sizes`, so it
+    /// should be a square image of at least 512x512 pixels.
+    pub(crate) icon: Option<PathBuf>,
+
+    /// The sizes the icon is generated in [default: [192, 512]]
+    #[serde(default = "default_icon_sizes")]
+    pub(crate) icon_sizes: Vec<u32>,
+
+    /// The color of the browser ui around the app, like `#1e1e1e`
+    pub(crate) theme_color: Option<String>,
+
+    /// The color of the splash screen while the app loads
+    pub(crate) background_color: Option<String>,
+
+    /// How the installed app is displayed [default: standalone]
+    #[serde(default)]
+    pub(crate) display: WebDisplayMode,
+
+    /// The url the installed app opens [default: the base path of the app]
+    pub(crate) start_url: Option<String>,
+}
+
+impl Default for WebManifestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            name: None,
+            short_name: None,
+            description: None,
+            icon: None,
+            icon_sizes: default_icon_sizes(),
+            theme_color: None,
+            background_color: None,
+            display: Default::default(),
+            start_url: None,
         }
     }
 }
 
+fn default_icon_sizes() -> Vec<u32> {
+    vec![192, 512]
+}
+
+/// How an installed web app is displayed
+#[derive(Default, Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum WebDisplayMode {
+    /// Take up the whole screen without any browser ui
+    Fullscreen,
+    /// Look like a native app in its own window
+    #[default]
+    Standalone,
+    /// Like standalone, with a minimal set of navigation controls
+    MinimalUi,
+    /// Open in a normal browser tab
+    Browser,
+}
+
 /// The wasm-opt configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub(crate) struct WasmOptConfig {
@@ -121,7 +229,35 @@ impl Default for WebAppConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct WebProxyConfig {
+    /// The url requests are forwarded to, e.g. `https://staging.example.com/api`
     pub(crate) backend: String,
+
+    /// The path on the devserver that is proxied. Defaults to the path of the backend url.
+    ///
+    /// The path is replaced with the path of the backend url, so `path = "/api"` with a backend of
+    /// `https://staging.example.com/v2` forwards `/api/users` to `https://staging.example.com/v2/users`.
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+
+    /// Headers added to every proxied request, e.g. an authorization token for the backend
+    #[serde(default)]
+    pub(crate) headers: HashMap<String, String>,
+
+    /// Rewrite the `Host` and `Origin` headers to match the backend so it doesn't reject requests from the
+    /// devserver
+    #[serde(default = "true_bool")]
+    pub(crate) change_origin: bool,
+}
+
+impl WebProxyConfig {
+    pub(crate) fn new(backend: impl Into<String>) -> Self {
+        Self {
+            backend: backend.into(),
+            path: None,
+            headers: HashMap::new(),
+            change_origin: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
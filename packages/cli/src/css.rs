@@ -0,0 +1,238 @@
+//! Run the CSS transformers configured in `Dioxus.toml` and track the files each of them depends on.
+//!
+//! While serving, editing a file in the dependency graph of a transformer only reruns that transformer. The
+//! generated stylesheet is then hot reloaded like any other asset instead of rebuilding the app.
+//!
+//! The dependency graph of a transformer contains:
+//! - The input stylesheet
+//! - Every stylesheet reachable through `@import`, `@use` or `@forward`, including sass partials
+//! - For tailwind, the files it scans for class names
+
+use crate::{CssPipelineConfig, CssTransformer, Result, TraceSrc};
+use anyhow::Context;
+use ignore::overrides::{Override, OverrideBuilder};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+use tokio::process::Command;
+
+/// Matches the targets of an import rule. Sass allows importing several files in one rule.
+static IMPORT_RULE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"@(?:import|use|forward)\s+([^;{]+)"#).unwrap());
+static QUOTED: Lazy<Regex> = Lazy::new(|| Regex::new(r#"["']([^"']+)["']"#).unwrap());
+
+/// A CSS transformer and the files it depends on
+pub(crate) struct CssPipeline {
+    config: CssPipelineConfig,
+    crate_dir: PathBuf,
+    /// The input stylesheet and every stylesheet it imports
+    dependencies: HashSet<PathBuf>,
+    /// The files tailwind scans for class names
+    content: Option<Override>,
+}
+
+impl CssPipeline {
+    pub(crate) fn new(crate_dir: &Path, config: CssPipelineConfig) -> Self {
+        let crate_dir = dunce::canonicalize(crate_dir).unwrap_or_else(|_| crate_dir.to_path_buf());
+        let content = (config.transformer == CssTransformer::Tailwind
+            && !config.content.is_empty())
+        .then(|| {
+            let mut builder = OverrideBuilder::new(&crate_dir);
+            for glob in &config.content {
+                if let Err(err) = builder.add(glob) {
+                    tracing::warn!("Invalid tailwind content glob {glob:?}: {err}");
+                }
+            }
+            builder.build().ok()
+        })
+        .flatten();
+
+        let mut pipeline = Self {
+            config,
+            crate_dir,
+            dependencies: HashSet::new(),
+            content,
+        };
+        pipeline.refresh_dependencies();
+        pipeline
+    }
+
+    /// Create the pipelines for every transformer in the config
+    pub(crate) fn from_config(crate_dir: &Path, configs: &[CssPipelineConfig]) -> Vec<Self> {
+        configs
+            .iter()
+            .map(|config| Self::new(crate_dir, config.clone()))
+            .collect()
+    }
+
+    pub(crate) fn input(&self) -> PathBuf {
+        self.crate_dir.join(&self.config.input)
+    }
+
+    pub(crate) fn output(&self) -> PathBuf {
+        self.crate_dir.join(&self.config.output)
+    }
+
+    /// Check if a change to the file should rerun this transformer
+    pub(crate) fn depends_on(&self, path: &Path) -> bool {
+        let Ok(path) = dunce::canonicalize(path) else {
+            return false;
+        };
+
+        // The transformer writes the output itself, so changes to it never rerun the transformer
+        if dunce::canonicalize(self.output()).is_ok_and(|output| output == path) {
+            return false;
+        }
+
+        if self.dependencies.contains(&path) {
+            return true;
+        }
+
+        if self.config.transformer != CssTransformer::Tailwind {
+            return false;
+        }
+
+        match &self.content {
+            Some(content) => content.matched(&path, false).is_whitelist(),
+            // Tailwind scans every rust file in the crate by default
+            None => {
+                path.starts_with(&self.crate_dir) && path.extension().is_some_and(|ext| ext == "rs")
+            }
+        }
+    }
+
+    /// Run the transformer and update the dependency graph with any new imports
+    pub(crate) async fn run(&mut self) -> Result<()> {
+        let input = self.input();
+        let output = self.output();
+
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create the output dir for {output:?}"))?;
+        }
+
+        let mut command = self.command();
+        let program = command.remove(0);
+        let mut cmd = Command::new(&program);
+        cmd.args(command).current_dir(&self.crate_dir);
+        match self.config.transformer {
+            CssTransformer::Tailwind => cmd.arg("-i").arg(&input).arg("-o").arg(&output),
+            CssTransformer::Sass => cmd.arg("--no-source-map").arg(&input).arg(&output),
+            CssTransformer::Lightningcss => cmd.arg("--bundle").arg(&input).arg("-o").arg(&output),
+        };
+
+        tracing::debug!(dx_src = ?TraceSrc::Build, "Running css transformer {cmd:?}");
+        let res = cmd
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .with_context(|| format!("Failed to run {program}. Is it installed?"))?;
+
+        // Refresh the graph even if the transformer failed so fixing a newly imported file reruns it
+        self.refresh_dependencies();
+
+        if !res.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to generate {output:?} from {input:?}:\n{}",
+                String::from_utf8_lossy(&res.stderr)
+            )
+            .into());
+        }
+
+        tracing::info!(dx_src = ?TraceSrc::Build, "Generated {}", self.config.output.display());
+
+        Ok(())
+    }
+
+    /// The program and leading arguments of the transformer
+    fn command(&self) -> Vec<String> {
+        match &self.config.command {
+            Some(command) if !command.is_empty() => command.clone(),
+            _ => match self.config.transformer {
+                CssTransformer::Tailwind => vec!["npx".into(), "tailwindcss".into()],
+                CssTransformer::Sass => vec!["sass".into()],
+                CssTransformer::Lightningcss => vec!["lightningcss".into()],
+            },
+        }
+    }
+
+    fn refresh_dependencies(&mut self) {
+        self.dependencies = stylesheet_dependencies(&self.input());
+    }
+}
+
+/// Run every pipeline, stopping at the first one that fails
+pub(crate) async fn run_css_pipelines(pipelines: &mut [CssPipeline]) -> Result<()> {
+    for pipeline in pipelines {
+        pipeline.run().await?;
+    }
+    Ok(())
+}
+
+/// Find the stylesheet and every local stylesheet it imports, directly or indirectly. Imports that can't be
+/// resolved to a file, like packages or urls, are skipped.
+pub(crate) fn stylesheet_dependencies(input: &Path) -> HashSet<PathBuf> {
+    let mut dependencies = HashSet::new();
+    let mut queue = vec![input.to_path_buf()];
+
+    while let Some(file) = queue.pop() {
+        let Ok(file) = dunce::canonicalize(&file) else {
+            continue;
+        };
+        if !dependencies.insert(file.clone()) {
+            continue;
+        }
+        let Ok(contents) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        let dir = file.parent().unwrap_or(Path::new("."));
+        queue.extend(
+            imports(&contents)
+                .iter()
+                .filter_map(|import| resolve_import(dir, import)),
+        );
+    }
+
+    dependencies
+}
+
+/// Find the targets of the import rules in a stylesheet
+fn imports(contents: &str) -> Vec<String> {
+    IMPORT_RULE
+        .captures_iter(contents)
+        .flat_map(|rule| {
+            let targets = rule.get(1).map_or("", |targets| targets.as_str());
+            QUOTED
+                .captures_iter(targets)
+                .map(|target| target[1].to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Resolve an import the way sass does, trying partials, extensions and index files
+fn resolve_import(dir: &Path, import: &str) -> Option<PathBuf> {
+    if import.contains("://") || import.starts_with("sass:") {
+        return None;
+    }
+
+    let path = dir.join(import);
+    let parent = path.parent()?;
+    let name = path.file_name()?.to_string_lossy().to_string();
+
+    let mut candidates = vec![path.clone()];
+    for ext in ["scss", "sass", "css"] {
+        candidates.push(parent.join(format!("{name}.{ext}")));
+        candidates.push(parent.join(format!("_{name}.{ext}")));
+        candidates.push(path.join(format!("_index.{ext}")));
+        candidates.push(path.join(format!("index.{ext}")));
+    }
+    candidates.push(parent.join(format!("_{name}")));
+
+    candidates.into_iter().find(|candidate| candidate.is_file())
+}
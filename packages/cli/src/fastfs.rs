@@ -9,6 +9,7 @@ use std::{
 };
 
 use brotli::enc::BrotliEncoderParams;
+use flate2::{write::GzEncoder, Compression};
 use walkdir::WalkDir;
 
 pub fn copy_asset(src: &Path, dest: &Path) -> std::io::Result<()> {
@@ -58,9 +59,9 @@ pub(crate) fn copy_dir_to(
                             err
                         );
                     }
-                    // If pre-compression isn't enabled, we should remove the old compressed file if it exists
-                } else if let Some(compressed_path) = compressed_path(&output_file_location) {
-                    _ = std::fs::remove_file(compressed_path);
+                    // If pre-compression isn't enabled, we should remove the old compressed files if they exist
+                } else {
+                    remove_compressed_files(&output_file_location);
                 }
             }
 
@@ -73,35 +74,61 @@ pub(crate) fn copy_dir_to(
     Ok(())
 }
 
+/// The extensions of the pre-compressed versions of a file. Servers pick the version that matches the
+/// accept-encoding header of the request
+const COMPRESSED_EXTENSIONS: [&str; 2] = ["br", "gz"];
+
 /// Get the path to the compressed version of a file
-fn compressed_path(path: &Path) -> Option<PathBuf> {
+fn compressed_path(path: &Path, compressed_extension: &str) -> Option<PathBuf> {
     let new_extension = match path.extension() {
         Some(ext) => {
-            if ext.to_string_lossy().to_lowercase().ends_with("br") {
+            let lowercase = ext.to_string_lossy().to_lowercase();
+            if COMPRESSED_EXTENSIONS
+                .iter()
+                .any(|compressed| lowercase.ends_with(compressed))
+            {
                 return None;
             }
             let mut ext = ext.to_os_string();
-            ext.push(".br");
+            ext.push(".");
+            ext.push(compressed_extension);
             ext
         }
-        None => OsString::from("br"),
+        None => OsString::from(compressed_extension),
     };
 
     Some(path.with_extension(new_extension))
 }
 
-/// pre-compress a file with brotli
+/// Remove the compressed versions of a file if they exist
+fn remove_compressed_files(path: &Path) {
+    for extension in COMPRESSED_EXTENSIONS {
+        if let Some(compressed_path) = compressed_path(path, extension) {
+            _ = std::fs::remove_file(compressed_path);
+        }
+    }
+}
+
+/// pre-compress a file with brotli and gzip
 pub(crate) fn pre_compress_file(path: &Path) -> std::io::Result<()> {
-    let Some(compressed_path) = compressed_path(path) else {
+    let (Some(brotli_path), Some(gzip_path)) =
+        (compressed_path(path, "br"), compressed_path(path, "gz"))
+    else {
         return Ok(());
     };
 
     let file = std::fs::File::open(path)?;
     let mut stream = std::io::BufReader::new(file);
-    let mut buffer = std::fs::File::create(compressed_path)?;
+    let mut buffer = std::fs::File::create(brotli_path)?;
     let params = BrotliEncoderParams::default();
     brotli::BrotliCompress(&mut stream, &mut buffer, &params)?;
 
+    let file = std::fs::File::open(path)?;
+    let mut stream = std::io::BufReader::new(file);
+    let mut encoder = GzEncoder::new(std::fs::File::create(gzip_path)?, Compression::best());
+    std::io::copy(&mut stream, &mut encoder)?;
+    encoder.finish()?;
+
     Ok(())
 }
 
@@ -116,9 +143,9 @@ pub(crate) fn pre_compress_folder(path: &Path, pre_compress: bool) -> std::io::R
                     tracing::error!("Failed to pre-compress file {entry_path:?}: {err}");
                 }
             }
-            // If pre-compression isn't enabled, we should remove the old compressed file if it exists
-            else if let Some(compressed_path) = compressed_path(entry_path) {
-                _ = std::fs::remove_file(compressed_path);
+            // If pre-compression isn't enabled, we should remove the old compressed files if they exist
+            else {
+                remove_compressed_files(entry_path);
             }
         }
     }
@@ -3,7 +3,7 @@ use dioxus_core::internal::{
 };
 use dioxus_core_types::HotReloadingContext;
 use dioxus_rsx::CallBody;
-use dioxus_rsx_hotreload::{ChangedRsx, HotReloadResult};
+use dioxus_rsx_hotreload::{AssetResolver, ChangedRsx, HotReloadResult};
 use std::path::PathBuf;
 use std::{collections::HashMap, path::Path};
 use syn::spanned::Spanned;
@@ -78,10 +78,13 @@ impl HotreloadFilemap {
     ///
     /// This does not do any caching on what intermediate state, like previous hotreloads, so you need
     /// to do that yourself.
+    ///
+    /// Any `asset!()` calls that were added since the last full rebuild are bundled with the asset resolver.
     pub(crate) fn update_rsx<Ctx: HotReloadingContext>(
         &mut self,
         path: &Path,
         new_contents: String,
+        assets: &AssetResolver,
     ) -> HotreloadResult {
         // Get the cached file if it exists
         let Some(cached_file) = self.map.get_mut(path) else {
@@ -127,10 +130,11 @@ impl HotreloadFilemap {
                 .join("/");
 
             // Returns a list of templates that are hotreloadable
-            let results = HotReloadResult::new::<Ctx>(
+            let results = HotReloadResult::new_with_assets::<Ctx>(
                 &old_call_body.body,
                 &new_call_body.body,
                 file_name.clone(),
+                assets,
             );
 
             // If no result is returned, we can't hotreload this file and need to keep the old file
@@ -330,6 +330,8 @@ pub struct TraceMsg {
     pub level: Level,
     pub content: TraceContent,
     pub timestamp: chrono::DateTime<chrono::Local>,
+    /// The module the message was logged from. This is only known for structured logs from the app
+    pub module: Option<String>,
 }
 
 #[derive(Clone, PartialEq)]
@@ -346,6 +348,18 @@ impl TraceMsg {
             level,
             content: TraceContent::Text(content),
             timestamp: chrono::Local::now(),
+            module: None,
+        }
+    }
+
+    /// Create a new trace message from a structured log record the app printed with `dioxus-logger`
+    pub fn record(source: TraceSrc, label: &str, record: dioxus_logger::LogRecord) -> Self {
+        Self {
+            source,
+            level: record.level,
+            content: TraceContent::Text(format!("{label}{record}")),
+            timestamp: chrono::Local::now(),
+            module: Some(record.target),
         }
     }
 
@@ -366,6 +380,7 @@ impl TraceMsg {
             timestamp: chrono::Local::now(),
             source: TraceSrc::Cargo,
             content: TraceContent::Cargo(content),
+            module: None,
         }
     }
 }
@@ -7,6 +7,7 @@ mod build;
 mod bundle_utils;
 mod cli;
 mod config;
+mod css;
 mod dioxus_crate;
 mod dx_build_info;
 mod error;
@@ -23,6 +24,7 @@ mod wasm_bindgen;
 pub(crate) use build::*;
 pub(crate) use cli::*;
 pub(crate) use config::*;
+pub(crate) use css::*;
 pub(crate) use dioxus_crate::*;
 pub(crate) use dioxus_dx_wire_format::*;
 pub(crate) use error::*;
@@ -52,6 +54,7 @@ async fn main() {
         Commands::Serve(opts) => opts.serve().await,
         Commands::Bundle(opts) => opts.bundle().await,
         Commands::Run(opts) => opts.run().await,
+        Commands::Test(opts) => opts.test().await,
     };
 
     // Provide a structured output for third party tools that can consume the output of the CLI
@@ -74,6 +74,12 @@ impl RustupShow {
         self.installed_targets
             .contains(&"wasm32-unknown-unknown".to_string())
     }
+
+    pub fn has_nightly(&self) -> bool {
+        self.installed_toolchains
+            .iter()
+            .any(|toolchain| toolchain.starts_with("nightly"))
+    }
 }
 
 #[test]
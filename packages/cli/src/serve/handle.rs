@@ -1,6 +1,7 @@
 use crate::{AppBundle, Platform, Result};
 use anyhow::Context;
-use dioxus_cli_opt::process_file_to;
+use dioxus_cli_opt::{process_css_modules, process_file_to};
+use manganis_core::{AssetOptions, CSS_MODULES_BUNDLED_PATH};
 use std::{
     net::SocketAddr,
     path::{Path, PathBuf},
@@ -85,6 +86,13 @@ impl AppHandle {
             envs.push((dioxus_cli_config::ASSET_ROOT_ENV, base_path.clone()));
         }
 
+        if self.app.build.build.threads {
+            envs.push((
+                dioxus_cli_config::CROSS_ORIGIN_ISOLATED_ENV,
+                "true".to_string(),
+            ));
+        }
+
         // Launch the server if we were given an address to start it on, and the build includes a server. After we
         // start the server, consume its stdout/stderr.
         if let (Some(addr), Some(server)) = (start_fullstack_on_address, self.app.server_exe()) {
@@ -162,12 +170,7 @@ impl AppHandle {
     pub(crate) async fn hotreload_bundled_asset(&self, changed_file: &PathBuf) -> Option<PathBuf> {
         let mut bundled_name = None;
 
-        // Use the build dir if there's no runtime asset dir as the override. For the case of ios apps,
-        // we won't actually be using the build dir.
-        let asset_dir = match self.runtime_asst_dir.as_ref() {
-            Some(dir) => dir.to_path_buf().join("assets/"),
-            None => self.app.build.asset_dir(),
-        };
+        let asset_dir = self.hotreload_asset_dir();
 
         tracing::debug!("Hotreloading asset {changed_file:?} in target {asset_dir:?}");
 
@@ -190,6 +193,18 @@ impl AppHandle {
 
         // The asset might've been renamed thanks to the manifest, let's attempt to reload that too
         if let Some(resource) = self.app.app.assets.assets.get(&changed_file).as_ref() {
+            // Css modules share one stylesheet, so we need to combine all of them again. The classes are scoped by
+            // path, so the running app keeps using the same class names.
+            if let AssetOptions::CssModule(_) = resource.options() {
+                let output_path = asset_dir.join(CSS_MODULES_BUNDLED_PATH);
+                if let Err(e) = process_css_modules(&self.app.app.assets, &output_path) {
+                    tracing::debug!("Failed to hotreload css module {e}");
+                }
+                let bundled_name = PathBuf::from(CSS_MODULES_BUNDLED_PATH);
+                self.push_asset_to_device(&output_path, &bundled_name).await;
+                return Some(bundled_name);
+            }
+
             let output_path = asset_dir.join(resource.bundled_path());
             // Remove the old asset if it exists
             _ = std::fs::remove_file(&output_path);
@@ -204,29 +219,63 @@ impl AppHandle {
             }
         }
 
-        // If the emulator is android, we need to copy the asset to the device with `adb push asset /data/local/tmp/dx/assets/filename.ext`
-        if self.app.build.build.platform() == Platform::Android {
-            if let Some(bundled_name) = bundled_name.as_ref() {
-                let target = format!("/data/local/tmp/dx/{}", bundled_name.display());
-                tracing::debug!("Pushing asset to device: {target}");
-                let res = tokio::process::Command::new("adb")
-                    .arg("push")
-                    .arg(&changed_file)
-                    .arg(target)
-                    .output()
-                    .await
-                    .context("Failed to push asset to device");
-
-                if let Err(e) = res {
-                    tracing::debug!("Failed to push asset to device: {e}");
-                }
-            }
+        if let Some(bundled_name) = bundled_name.as_ref() {
+            self.push_asset_to_device(&changed_file, bundled_name).await;
         }
 
         // Now we can return the bundled asset name to send to the hotreload engine
         bundled_name
     }
 
+    /// Bundle an asset that was referenced with a new `asset!()` call since the last build.
+    ///
+    /// The asset isn't in the manifest of the running app, so the bundled name is chosen by the hotreload engine
+    /// instead of the `asset!()` macro. The next full rebuild will bundle the asset again under its normal name.
+    pub(crate) async fn hotreload_new_asset(&self, source: &Path, bundled_name: &Path) {
+        let output_path = self.hotreload_asset_dir().join(bundled_name);
+
+        tracing::debug!("Bundling new asset {source:?} to {output_path:?}");
+
+        // The source might have changed since we last bundled it under this name
+        _ = std::fs::remove_file(&output_path);
+        if let Err(e) = process_file_to(&AssetOptions::Unknown, source, &output_path) {
+            tracing::debug!("Failed to bundle new asset {e}");
+            return;
+        }
+
+        self.push_asset_to_device(source, bundled_name).await;
+    }
+
+    /// The asset dir of the running app. Use the build dir if there's no runtime asset dir as the override. For
+    /// the case of ios apps, we won't actually be using the build dir.
+    fn hotreload_asset_dir(&self) -> PathBuf {
+        match self.runtime_asst_dir.as_ref() {
+            Some(dir) => dir.to_path_buf().join("assets/"),
+            None => self.app.build.asset_dir(),
+        }
+    }
+
+    /// If the emulator is android, we need to copy the asset to the device with `adb push asset /data/local/tmp/dx/assets/filename.ext`
+    async fn push_asset_to_device(&self, source: &Path, bundled_name: &Path) {
+        if self.app.build.build.platform() != Platform::Android {
+            return;
+        }
+
+        let target = format!("/data/local/tmp/dx/{}", bundled_name.display());
+        tracing::debug!("Pushing asset to device: {target}");
+        let res = tokio::process::Command::new("adb")
+            .arg("push")
+            .arg(source)
+            .arg(target)
+            .output()
+            .await
+            .context("Failed to push asset to device");
+
+        if let Err(e) = res {
+            tracing::debug!("Failed to push asset to device: {e}");
+        }
+    }
+
     /// Open the native app simply by running its main exe
     ///
     /// Eventually, for mac, we want to run the `.app` with `open` to fix issues with `dylib` paths,
@@ -1,4 +1,4 @@
-use crate::{BuildUpdate, Builder, Error, Platform, Result, ServeArgs, TraceController, TraceSrc};
+use crate::{BuildUpdate, Error, Platform, Result, ServeArgs, TraceController, TraceSrc};
 
 mod ansi_buffer;
 mod detect;
@@ -7,6 +7,7 @@ mod output;
 mod proxy;
 mod runner;
 mod server;
+mod session;
 mod update;
 mod watcher;
 
@@ -14,6 +15,7 @@ pub(crate) use handle::*;
 pub(crate) use output::*;
 pub(crate) use runner::*;
 pub(crate) use server::*;
+pub(crate) use session::*;
 pub(crate) use update::*;
 pub(crate) use watcher::*;
 
@@ -37,19 +39,17 @@ pub(crate) use watcher::*;
 /// - I'd love to be able to configure the CLI while it's running so we can change settings on the fly.
 /// - I want us to be able to detect a `server_fn` in the project and then upgrade from a static server
 ///   to a dynamic one on the fly.
-pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
+pub(crate) async fn serve_all(args: ServeArgs) -> Result<()> {
     // Redirect all logging the cli logger
     let mut tracer = TraceController::redirect();
 
-    // Load the krate and resolve the server args against it - this might log so do it after we turn on the tracer first
-    let krate = args.load_krate().await?;
+    // Load the krate of each app and start building them
+    let mut sessions = AppSession::start_all(&args).await?;
+    let mut screen = Output::start(&sessions[0].args)?;
 
-    // Note that starting the builder will queue up a build immediately
-    let mut builder = Builder::start(&krate, args.build_args())?;
-    let mut devserver = WebServer::start(&krate, &args)?;
-    let mut watcher = Watcher::start(&krate, &args);
-    let mut runner = AppRunner::start(&krate);
-    let mut screen = Output::start(&args)?;
+    // The app shown in the TUI and targeted by the keyboard shortcuts
+    let mut focused = 0;
+    let app_count = sessions.len();
 
     // This is our default splash screen. We might want to make this a fancier splash screen in the future
     // Also, these commands might not be the most important, but it's all we've got enabled right now
@@ -60,26 +60,61 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                 • Press `r` to rebuild the app
                 • Press `o` to open the app
                 • Press `v` to toggle verbose logging
-                • Press `/` for more commands and shortcuts
+                • Press `/` for more commands and shortcuts{}
                 Learn more at https://dioxuslabs.com/learn/0.6/getting_started
                ----------------------------------------------------------------"#,
-        krate.executable_name()
+        sessions
+            .iter()
+            .map(|session| session.name())
+            .collect::<Vec<_>>()
+            .join(", "),
+        if app_count > 1 {
+            "\n                • Press `tab` to switch between apps"
+        } else {
+            ""
+        }
     );
 
     let err: Result<(), Error> = loop {
         // Draw the state of the server to the screen
-        screen.render(&args, &krate, &builder, &devserver, &watcher);
-
-        // And then wait for any updates before redrawing
-        let msg = tokio::select! {
-            msg = builder.wait() => ServeUpdate::BuildUpdate(msg),
-            msg = watcher.wait() => msg,
-            msg = devserver.wait() => msg,
-            msg = screen.wait() => msg,
-            msg = runner.wait() => msg,
-            msg = tracer.wait() => msg,
+        let names = sessions
+            .iter()
+            .map(|session| session.name())
+            .collect::<Vec<_>>();
+        let shown = &sessions[focused];
+        screen.render(
+            &shown.args,
+            &shown.krate,
+            &shown.builder,
+            &shown.devserver,
+            &shown.watcher,
+            &names,
+            focused,
+        );
+
+        // And then wait for any updates before redrawing. Updates from the TUI apply to the focused app.
+        let apps = futures_util::future::select_all(
+            sessions
+                .iter_mut()
+                .enumerate()
+                .map(|(idx, session)| Box::pin(async move { (idx, session.wait().await) })),
+        );
+        let (idx, msg) = tokio::select! {
+            ((idx, msg), _, _) = apps => (idx, msg),
+            msg = screen.wait() => (focused, msg),
+            msg = tracer.wait() => (focused, msg),
         };
 
+        let AppSession {
+            args,
+            krate,
+            builder,
+            devserver,
+            watcher: _,
+            runner,
+            label,
+        } = &mut sessions[idx];
+
         match msg {
             ServeUpdate::FilesChanged { files } => {
                 if files.is_empty() || !args.should_hotreload() {
@@ -93,19 +128,16 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                 // and then send that update to all connected clients
                 if let Some(hr) = runner.attempt_hot_reload(files).await {
                     // Only send a hotreload message for templates and assets - otherwise we'll just get a full rebuild
-                    if hr.templates.is_empty()
-                        && hr.assets.is_empty()
-                        && hr.unknown_files.is_empty()
-                    {
-                        tracing::debug!(dx_src = ?TraceSrc::Dev, "Ignoring file change: {}", file);
+                    if hr.is_empty() {
+                        tracing::debug!(dx_src = ?TraceSrc::Dev, "{label}Ignoring file change: {}", file);
                         continue;
                     }
 
-                    tracing::info!(dx_src = ?TraceSrc::Dev, "Hotreloading: {}", file);
+                    tracing::info!(dx_src = ?TraceSrc::Dev, "{label}Hotreloading: {}", file);
 
                     devserver.send_hotreload(hr).await;
                 } else if runner.should_full_rebuild {
-                    tracing::info!(dx_src = ?TraceSrc::Dev, "Full rebuild: {}", file);
+                    tracing::info!(dx_src = ?TraceSrc::Dev, "{label}Full rebuild: {}", file);
 
                     // Kill any running executables on Windows
                     if cfg!(windows) {
@@ -124,7 +156,7 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                     devserver.start_build().await;
                 } else {
                     tracing::warn!(
-                        "Rebuild required but is currently paused - press `r` to rebuild manually"
+                        "{label}Rebuild required but is currently paused - press `r` to rebuild manually"
                     )
                 }
             }
@@ -153,7 +185,7 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                 screen.new_build_update(&update);
 
                 // And then update the websocketed clients with the new build status in case they want it
-                devserver.new_build_update(&update, &builder).await;
+                devserver.new_build_update(&update, builder).await;
 
                 // And then open the app if it's ready
                 // todo: there might be more things to do here that require coordination with other pieces of the CLI
@@ -164,7 +196,7 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                         screen.push_cargo_log(message);
                     }
                     BuildUpdate::BuildFailed { err } => {
-                        tracing::error!("Build failed: {:?}", err);
+                        tracing::error!("{label}Build failed: {:?}", err);
                     }
                     BuildUpdate::BuildReady { bundle } => {
                         let handle = runner
@@ -182,7 +214,7 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                                 devserver.send_reload_command().await;
                             }
 
-                            Err(e) => tracing::error!("Failed to open app: {}", e),
+                            Err(e) => tracing::error!("{label}Failed to open app: {}", e),
                         }
                     }
                 }
@@ -191,10 +223,10 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
             // If the process exited *cleanly*, we can exit
             ServeUpdate::ProcessExited { status, platform } => {
                 if !status.success() {
-                    tracing::error!("Application [{platform}] exited with error: {status}");
+                    tracing::error!("{label}Application [{platform}] exited with error: {status}");
                 } else {
                     tracing::info!(
-                        r#"Application [{platform}] exited gracefully.
+                        r#"{label}Application [{platform}] exited gracefully.
                - To restart the app, press `r` to rebuild or `o` to open
                - To exit the server, press `ctrl+c`"#
                     );
@@ -203,12 +235,18 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                 runner.kill(platform);
             }
 
+            // Apps that use dioxus-logger print their logs as structured records we can filter
             ServeUpdate::StdoutReceived { platform, msg } => {
-                screen.push_stdio(platform, msg, tracing::Level::INFO);
+                match dioxus_logger::LogRecord::from_dx_line(&msg) {
+                    Some(record) => screen.push_app_record(platform, &label, record),
+                    None => {
+                        screen.push_stdio(platform, format!("{label}{msg}"), tracing::Level::INFO)
+                    }
+                }
             }
 
             ServeUpdate::StderrReceived { platform, msg } => {
-                screen.push_stdio(platform, msg, tracing::Level::ERROR);
+                screen.push_stdio(platform, format!("{label}{msg}"), tracing::Level::ERROR);
             }
 
             ServeUpdate::TracingLog { log } => {
@@ -219,7 +257,7 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                 // The spacing here is important-ish: we want
                 // `Full rebuild:` to line up with
                 // `Hotreloading:` to keep the alignment during long edit sessions
-                tracing::info!("Full rebuild: triggered manually");
+                tracing::info!("{label}Full rebuild: triggered manually");
 
                 // Kill any running executables on Windows
                 if cfg!(windows) {
@@ -233,8 +271,8 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
             }
 
             ServeUpdate::OpenApp => {
-                if let Err(err) = runner.open_existing(&devserver).await {
-                    tracing::error!("Failed to open app: {err}")
+                if let Err(err) = runner.open_existing(devserver).await {
+                    tracing::error!("{label}Failed to open app: {err}")
                 }
             }
 
@@ -245,7 +283,7 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
             ServeUpdate::ToggleShouldRebuild => {
                 runner.should_full_rebuild = !runner.should_full_rebuild;
                 tracing::info!(
-                    "Automatic rebuilds are currently: {}",
+                    "{label}Automatic rebuilds are currently: {}",
                     if runner.should_full_rebuild {
                         "enabled"
                     } else {
@@ -254,6 +292,10 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                 )
             }
 
+            ServeUpdate::FocusNextApp => {
+                focused = (focused + 1) % app_count;
+            }
+
             ServeUpdate::Exit { error } => match error {
                 Some(err) => break Err(anyhow::anyhow!("{}", err).into()),
                 None => break Ok(()),
@@ -261,9 +303,11 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
         }
     };
 
-    _ = devserver.shutdown().await;
+    for session in sessions.iter_mut() {
+        _ = session.devserver.shutdown().await;
+        session.builder.abort_all();
+    }
     _ = screen.shutdown();
-    builder.abort_all();
 
     if let Err(err) = err {
         eprintln!("Exiting with error: {}", err);
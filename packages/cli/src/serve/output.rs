@@ -45,13 +45,17 @@ pub struct Output {
     // A list of all messages from build, dev, app, and more.
     more_modal_open: bool,
     interactive: bool,
-    platform: Platform,
 
     // Whether to show verbose logs or not
     // We automatically hide "debug" logs if verbose is false (only showing "info" / "warn" / "error")
     verbose: bool,
     trace: bool,
 
+    // The lowest level of the app's logs to show, and the modules to show them from
+    // These only filter the app's logs, the logs of dx itself are controlled by `verbose` and `trace`
+    app_log_level: Level,
+    app_log_filter: Vec<String>,
+
     // Pending logs
     pending_logs: VecDeque<TraceMsg>,
 
@@ -73,6 +77,10 @@ struct RenderState<'a> {
     build_engine: &'a Builder,
     server: &'a WebServer,
     watcher: &'a Watcher,
+    /// The names of all the apps being served
+    apps: &'a [&'a str],
+    /// The index of the app shown in the TUI
+    focused: usize,
 }
 
 impl Output {
@@ -93,7 +101,6 @@ impl Output {
                 env!("CARGO_PKG_VERSION"),
                 crate::dx_build_info::GIT_COMMIT_HASH_SHORT.unwrap_or("main")
             ),
-            platform: cfg.build_arguments.platform.expect("To be resolved by now"),
             events: None,
             // messages: Vec::new(),
             more_modal_open: false,
@@ -101,6 +108,8 @@ impl Output {
             throbber: RefCell::new(throbber_widgets_tui::ThrobberState::default()),
             trace: crate::logging::VERBOSITY.get().unwrap().trace,
             verbose: crate::logging::VERBOSITY.get().unwrap().verbose,
+            app_log_level: cfg.log_level.unwrap_or(Level::INFO),
+            app_log_filter: cfg.log_filter.clone(),
             tick_animation: false,
             tick_interval: {
                 let mut interval = tokio::time::interval(Duration::from_millis(TICK_RATE_MS));
@@ -210,6 +219,7 @@ impl Output {
             KeyCode::Char('r') => return Ok(Some(ServeUpdate::RequestRebuild)),
             KeyCode::Char('o') => return Ok(Some(ServeUpdate::OpenApp)),
             KeyCode::Char('p') => return Ok(Some(ServeUpdate::ToggleShouldRebuild)),
+            KeyCode::Tab => return Ok(Some(ServeUpdate::FocusNextApp)),
             KeyCode::Char('v') => {
                 self.verbose = !self.verbose;
                 tracing::info!(
@@ -221,6 +231,19 @@ impl Output {
                 self.trace = !self.trace;
                 tracing::info!("Tracing is now {}", if self.trace { "on" } else { "off" });
             }
+            KeyCode::Char('l') => {
+                self.app_log_level = match self.app_log_level {
+                    Level::ERROR => Level::WARN,
+                    Level::WARN => Level::INFO,
+                    Level::INFO => Level::DEBUG,
+                    Level::DEBUG => Level::TRACE,
+                    _ => Level::ERROR,
+                };
+                tracing::info!(
+                    "Showing app logs at {} and above",
+                    self.app_log_level.as_str().to_lowercase()
+                );
+            }
 
             KeyCode::Char('c') => {
                 stdout()
@@ -277,6 +300,16 @@ impl Output {
         self.push_log(TraceMsg::text(TraceSrc::App(platform), level, msg));
     }
 
+    /// Add a structured log record the app printed with `dioxus-logger` to the logs
+    pub fn push_app_record(
+        &mut self,
+        platform: Platform,
+        label: &str,
+        record: dioxus_logger::LogRecord,
+    ) {
+        self.push_log(TraceMsg::record(TraceSrc::App(platform), label, record));
+    }
+
     /// Push a message from the websocket to the logs
     pub fn push_ws_message(&mut self, platform: Platform, message: axum::extract::ws::Message) {
         use dioxus_devtools_types::ClientMsg;
@@ -290,8 +323,25 @@ impl Output {
         let res = serde_json::from_str::<ClientMsg>(text.as_str());
 
         // Client logs being errors aren't fatal, but we should still report them them
-        let ClientMsg::Log { level, messages } = match res {
-            Ok(msg) => msg,
+        let (level, messages) = match res {
+            Ok(ClientMsg::Log { level, messages }) => (level, messages),
+            // Panics and unhandled errors are shown in the error overlay of the app. Log them with their component
+            // stack so they aren't lost when the page reloads
+            Ok(ClientMsg::Error(report)) => {
+                let mut content = report.message;
+                if let Some(location) = report.location {
+                    content.push_str(&format!("\n  at {location}"));
+                }
+                for component in report.component_stack {
+                    content.push_str(&format!("\n  in {component}"));
+                }
+                self.push_log(TraceMsg::text(
+                    TraceSrc::App(platform),
+                    Level::ERROR,
+                    content,
+                ));
+                return;
+            }
             Err(err) => {
                 tracing::error!(dx_src = ?TraceSrc::Dev, "Error parsing message from {}: {}", platform, err);
                 return;
@@ -341,6 +391,8 @@ impl Output {
         build_engine: &Builder,
         server: &WebServer,
         watcher: &Watcher,
+        apps: &[&str],
+        focused: usize,
     ) {
         if !self.interactive {
             return;
@@ -366,6 +418,8 @@ impl Output {
                     build_engine,
                     server,
                     watcher,
+                    apps,
+                    focused,
                 },
             );
         });
@@ -389,7 +443,21 @@ impl Output {
         self.render_body_title(frame, _top, state);
     }
 
-    fn render_body_title(&self, frame: &mut Frame<'_>, area: Rect, _state: RenderState) {
+    fn render_body_title(&self, frame: &mut Frame<'_>, area: Rect, state: RenderState) {
+        // When serving several apps, list them in the title with the shown app highlighted
+        if state.apps.len() > 1 {
+            let mut tabs = vec![" ".dark_gray()];
+            for (idx, app) in state.apps.iter().enumerate() {
+                match idx == state.focused {
+                    true => tabs.push(app.light_yellow()),
+                    false => tabs.push(app.dark_gray()),
+                }
+                tabs.push(" ".dark_gray());
+            }
+            tabs.push("tab:switch ".dark_gray());
+            frame.render_widget(Line::from(tabs).left_aligned(), area);
+        }
+
         frame.render_widget(
             Line::from(vec![
                 " ".dark_gray(),
@@ -597,7 +665,12 @@ impl Output {
         frame.render_widget(
             Paragraph::new(Line::from(vec![
                 "Platform: ".gray(),
-                self.platform.expected_name().yellow(),
+                state
+                    .opts
+                    .build_arguments
+                    .platform()
+                    .expected_name()
+                    .yellow(),
                 if state.opts.build_arguments.fullstack {
                     " + fullstack".yellow()
                 } else {
@@ -610,15 +683,14 @@ impl Output {
 
         self.render_feature_list(frame, app_features, state);
 
-        // todo(jon) should we write https ?
         let address = match state.server.server_address() {
-            Some(address) => format!("http://{}", address).blue(),
+            Some(address) => format!("{}://{}", state.server.server_protocol(), address).blue(),
             None => "no server address".dark_gray(),
         };
 
         frame.render_widget_ref(
             Paragraph::new(Line::from(vec![
-                if self.platform == Platform::Web {
+                if state.opts.build_arguments.platform() == Platform::Web {
                     "Serving at: ".gray()
                 } else {
                     "ServerFns at: ".gray()
@@ -739,6 +811,36 @@ impl Output {
     /// TODO(jon): we could look into implementing scroll regions ourselves, but I think insert_before will
     /// land in a reasonable amount of time.
     #[deny(clippy::manual_saturating_arithmetic)]
+    fn is_log_visible(&self, log: &TraceMsg) -> bool {
+        // The app's logs are filtered by the level and modules picked for them
+        if let TraceSrc::App(_) = log.source {
+            // More verbose levels compare as greater
+            if log.level > self.app_log_level {
+                return false;
+            }
+
+            // Output that isn't a structured record doesn't have a module, so it is always shown
+            return match &log.module {
+                Some(module) if !self.app_log_filter.is_empty() => self
+                    .app_log_filter
+                    .iter()
+                    .any(|filter| module == filter || module.starts_with(&format!("{filter}::"))),
+                _ => true,
+            };
+        }
+
+        // Only show debug logs if verbose is enabled
+        if log.level == Level::DEBUG && !self.verbose {
+            return false;
+        }
+
+        if log.level == Level::TRACE && !self.trace {
+            return false;
+        }
+
+        true
+    }
+
     fn drain_logs(
         &mut self,
         terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
@@ -749,12 +851,7 @@ impl Output {
             return Ok(());
         };
 
-        // Only show debug logs if verbose is enabled
-        if log.level == Level::DEBUG && !self.verbose {
-            return Ok(());
-        }
-
-        if log.level == Level::TRACE && !self.trace {
+        if !self.is_log_visible(&log) {
             return Ok(());
         }
 
@@ -10,16 +10,23 @@ use axum::{
     routing::{any, MethodRouter},
     Router,
 };
+use hyper::header::{HeaderMap, HeaderName, HeaderValue, CONNECTION, HOST, ORIGIN, UPGRADE};
 use hyper::{Request, Response, Uri};
 use hyper_util::{
     client::legacy::{self, connect::HttpConnector},
-    rt::TokioExecutor,
+    rt::{TokioExecutor, TokioIo},
 };
 
 #[derive(Debug, Clone)]
 struct ProxyClient {
     inner: legacy::Client<hyper_rustls::HttpsConnector<HttpConnector>, MyBody>,
+    /// Upgrades like websockets only work over http1, so we use a separate client that never negotiates http2
+    upgrade: legacy::Client<hyper_rustls::HttpsConnector<HttpConnector>, MyBody>,
     url: Uri,
+    /// The path on the devserver that is replaced with the path of the url
+    path: Option<String>,
+    headers: HeaderMap,
+    change_origin: bool,
 }
 
 impl ProxyClient {
@@ -31,45 +38,142 @@ impl ProxyClient {
             .https_or_http()
             .enable_all_versions()
             .build();
+        let http1 = hyper_rustls::HttpsConnectorBuilder::new()
+            .with_native_roots()
+            .unwrap()
+            .https_or_http()
+            .enable_http1()
+            .build();
         Self {
             inner: legacy::Client::builder(TokioExecutor::new()).build(https),
+            upgrade: legacy::Client::builder(TokioExecutor::new()).build(http1),
             url,
+            path: None,
+            headers: HeaderMap::new(),
+            change_origin: false,
         }
     }
 
-    async fn send(&self, mut req: Request<MyBody>) -> Result<Response<hyper::body::Incoming>> {
+    fn from_config(url: Uri, path: String, proxy: &WebProxyConfig) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &proxy.headers {
+            let name = HeaderName::try_from(name)
+                .map_err(|err| Error::ProxySetup(format!("Invalid header name {name:?}: {err}")))?;
+            let value = HeaderValue::try_from(value).map_err(|err| {
+                Error::ProxySetup(format!("Invalid value for header {name}: {err}"))
+            })?;
+            headers.insert(name, value);
+        }
+
+        Ok(Self {
+            path: Some(path),
+            headers,
+            change_origin: proxy.change_origin,
+            ..Self::new(url)
+        })
+    }
+
+    /// Point the request at the backend, rewriting its path and headers
+    fn rewrite(&self, req: &mut Request<MyBody>) -> Result<()> {
         let mut uri_parts = req.uri().clone().into_parts();
         uri_parts.authority = self.url.authority().cloned();
         uri_parts.scheme = self.url.scheme().cloned();
+        if let Some(path) = &self.path {
+            let path_and_query = uri_parts
+                .path_and_query
+                .as_ref()
+                .map(|path_and_query| path_and_query.as_str())
+                .unwrap_or("/");
+            let rewritten = rewrite_path(path_and_query, path, self.url.path());
+            uri_parts.path_and_query = Some(rewritten.parse().context("Invalid rewritten path")?);
+        }
         *req.uri_mut() = Uri::from_parts(uri_parts).context("Invalid URI parts")?;
-        self.inner
+
+        if self.change_origin {
+            if let Some(authority) = self.url.authority() {
+                let host = HeaderValue::from_str(authority.as_str()).context("Invalid host")?;
+                req.headers_mut().insert(HOST, host);
+                if req.headers().contains_key(ORIGIN) {
+                    let scheme = self.url.scheme_str().unwrap_or("http");
+                    let origin = HeaderValue::from_str(&format!("{scheme}://{authority}"))
+                        .context("Invalid origin")?;
+                    req.headers_mut().insert(ORIGIN, origin);
+                }
+            }
+        }
+
+        for (name, value) in &self.headers {
+            req.headers_mut().insert(name, value.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn send(&self, mut req: Request<MyBody>) -> Result<Response<hyper::body::Incoming>> {
+        self.rewrite(&mut req)?;
+        let client = match is_upgrade_request(&req) {
+            true => &self.upgrade,
+            false => &self.inner,
+        };
+        client
             .request(req)
             .await
             .map_err(|err| crate::error::Error::Other(anyhow!(err)))
     }
 }
 
+/// Replace the proxied path prefix of the devserver with the path of the backend, keeping the rest of the path and
+/// the query
+fn rewrite_path(path_and_query: &str, path: &str, backend_path: &str) -> String {
+    let rest = path_and_query
+        .strip_prefix(path.trim_end_matches('/'))
+        .unwrap_or(path_and_query);
+    let rewritten = format!("{}{rest}", backend_path.trim_end_matches('/'));
+    match rewritten.starts_with('/') {
+        true => rewritten,
+        false => format!("/{rewritten}"),
+    }
+}
+
+/// Check if the request asks to switch protocols, like a websocket handshake
+fn is_upgrade_request<B>(req: &Request<B>) -> bool {
+    req.headers().contains_key(UPGRADE)
+        && req
+            .headers()
+            .get(CONNECTION)
+            .and_then(|connection| connection.to_str().ok())
+            .is_some_and(|connection| connection.to_ascii_lowercase().contains("upgrade"))
+}
+
 /// Add routes to the router handling the specified proxy config.
 ///
 /// We will proxy requests directed at either:
 ///
-/// - the exact path of the proxy config's backend URL, e.g. /api
+/// - the exact path of the proxy config (or the backend URL if no path is set), e.g. /api
 /// - the exact path with a trailing slash, e.g. /api/
-/// - any subpath of the backend URL, e.g. /api/foo/bar
+/// - any subpath of the path, e.g. /api/foo/bar
+///
+/// Websocket upgrades on these paths are forwarded to the backend as well.
 pub(crate) fn add_proxy(mut router: Router, proxy: &WebProxyConfig) -> Result<Router> {
     let url: Uri = proxy.backend.parse()?;
-    let path = url.path().to_string();
+    let path = proxy.path.clone().unwrap_or_else(|| url.path().to_string());
     let trimmed_path = path.trim_start_matches('/');
 
     if trimmed_path.is_empty() {
-        return Err(crate::Error::ProxySetup(format!(
-            "Proxy backend URL must have a non-empty path, e.g. {}/api instead of {}",
-            proxy.backend.trim_end_matches('/'),
-            proxy.backend
-        )));
+        return Err(match proxy.path {
+            Some(_) => crate::Error::ProxySetup(format!(
+                "Proxy path must not be empty, e.g. /api instead of {path:?}"
+            )),
+            None => crate::Error::ProxySetup(format!(
+                "Proxy backend URL must have a non-empty path, e.g. {}/api instead of {}",
+                proxy.backend.trim_end_matches('/'),
+                proxy.backend
+            )),
+        });
     }
 
-    let method_router = proxy_to(url, false, handle_proxy_error);
+    let client = ProxyClient::from_config(url, format!("/{trimmed_path}"), proxy)?;
+    let method_router = proxy_with(client, false, handle_proxy_error);
 
     // api/*path
     router = router.route(
@@ -97,8 +201,14 @@ pub(crate) fn proxy_to(
     nocache: bool,
     handle_error: fn(Error) -> Response<Body>,
 ) -> MethodRouter {
-    let client = ProxyClient::new(url.clone());
+    proxy_with(ProxyClient::new(url), nocache, handle_error)
+}
 
+fn proxy_with(
+    client: ProxyClient,
+    nocache: bool,
+    handle_error: fn(Error) -> Response<Body>,
+) -> MethodRouter {
     any(move |mut req: Request<MyBody>| async move {
         // Prevent request loops
         if req.headers().get("x-proxied-by-dioxus").is_some() {
@@ -115,40 +225,20 @@ pub(crate) fn proxy_to(
             "true".parse().expect("header value is valid"),
         );
 
-        // We have to throw a redirect for ws connections since the upgrade handler will not be called
-        // Our _dioxus handler will override this in the default case
-        if req.uri().scheme().map(|f| f.as_str()) == Some("ws")
-            || req.uri().scheme().map(|f| f.as_str()) == Some("wss")
-        {
-            let new_host = url.host().unwrap_or("localhost");
-            let proxied_uri = format!(
-                "{scheme}://{host}:{port}{path_and_query}",
-                scheme = req.uri().scheme_str().unwrap_or("ws"),
-                port = url.port().unwrap(),
-                host = new_host,
-                path_and_query = req
-                    .uri()
-                    .path_and_query()
-                    .map(|f| f.to_string())
-                    .unwrap_or_default()
-            );
-            tracing::info!(dx_src = ?TraceSrc::Dev, "Proxied websocket request {req:?} to {proxied_uri}");
-
-            return Ok(axum::response::Redirect::permanent(&proxied_uri).into_response());
-        }
-
         if nocache {
             crate::serve::insert_no_cache_headers(req.headers_mut());
         }
 
         let uri = req.uri().clone();
 
-        // retry with backoff
+        // For websockets, we take the upgrade of the incoming connection before sending the handshake to the
+        // backend. Once the backend switches protocols, we pipe the two connections together.
+        let client_upgrade = is_upgrade_request(&req).then(|| hyper::upgrade::on(&mut req));
 
         let res = client.send(req).await.map_err(handle_error);
 
         match res {
-            Ok(res) => {
+            Ok(mut res) => {
                 // log assets at a different log level
                 if uri.path().starts_with("/assets")
                     || uri.path().starts_with("/_dioxus")
@@ -160,6 +250,25 @@ pub(crate) fn proxy_to(
                     tracing::info!(dx_src = ?TraceSrc::Dev, "[{}] {}", res.status().as_u16(), uri);
                 }
 
+                if let Some(client_upgrade) = client_upgrade {
+                    if res.status() == StatusCode::SWITCHING_PROTOCOLS {
+                        let backend_upgrade = hyper::upgrade::on(&mut res);
+                        tokio::spawn(async move {
+                            match tokio::try_join!(client_upgrade, backend_upgrade) {
+                                Ok((client, backend)) => {
+                                    let mut client = TokioIo::new(client);
+                                    let mut backend = TokioIo::new(backend);
+                                    _ = tokio::io::copy_bidirectional(&mut client, &mut backend)
+                                        .await;
+                                }
+                                Err(err) => {
+                                    tracing::error!(dx_src = ?TraceSrc::Dev, "Failed to proxy websocket {uri}: {err}");
+                                }
+                            }
+                        });
+                    }
+                }
+
                 Ok(res.into_response())
             }
             Err(err) => {
@@ -189,11 +298,34 @@ mod test {
     use axum_server::{Handle, Server};
 
     async fn setup_servers(mut config: WebProxyConfig) -> String {
-        let backend_router =
-            Router::new().route(
+        let backend_router = Router::new()
+            .route(
                 "/*path",
                 any(|request: axum::extract::Request| async move {
-                    format!("backend: {}", request.uri())
+                    match request.headers().get("authorization") {
+                        Some(auth) => {
+                            format!("backend: {} {}", request.uri(), auth.to_str().unwrap())
+                        }
+                        None => format!("backend: {}", request.uri()),
+                    }
+                }),
+            )
+            .route(
+                "/api/echo",
+                any(|mut request: axum::extract::Request| async move {
+                    // Echo back anything sent over the upgraded connection
+                    let upgrade = hyper::upgrade::on(&mut request);
+                    tokio::spawn(async move {
+                        let upgraded = TokioIo::new(upgrade.await.unwrap());
+                        let (mut reader, mut writer) = tokio::io::split(upgraded);
+                        tokio::io::copy(&mut reader, &mut writer).await.unwrap();
+                    });
+                    Response::builder()
+                        .status(StatusCode::SWITCHING_PROTOCOLS)
+                        .header(CONNECTION, "upgrade")
+                        .header(UPGRADE, "echo")
+                        .body(Body::empty())
+                        .unwrap()
                 }),
             );
 
@@ -229,14 +361,12 @@ mod test {
     }
 
     async fn test_proxy_requests(path: String) {
-        let config = WebProxyConfig {
-            // Normally this would be an absolute URL including scheme/host/port,
-            // but in these tests we need to let the OS choose the port so tests
-            // don't conflict, so we'll concatenate the final address and this
-            // path together.
-            // So in day to day usage, use `http://localhost:8000/api` instead!
-            backend: path,
-        };
+        // Normally this would be an absolute URL including scheme/host/port,
+        // but in these tests we need to let the OS choose the port so tests
+        // don't conflict, so we'll concatenate the final address and this
+        // path together.
+        // So in day to day usage, use `http://localhost:8000/api` instead!
+        let config = WebProxyConfig::new(path);
 
         let server_addr = setup_servers(config).await;
 
@@ -281,11 +411,71 @@ mod test {
         test_proxy_requests("/api/".to_string()).await;
     }
 
+    #[tokio::test]
+    async fn add_proxy_rewrites_path_and_headers() {
+        let mut config = WebProxyConfig::new("/api/v2");
+        config.path = Some("/staging".to_string());
+        config
+            .headers
+            .insert("Authorization".to_string(), "Bearer token".to_string());
+
+        let server_addr = setup_servers(config).await;
+
+        assert_eq!(
+            reqwest::get(format!("http://{server_addr}/staging/users?page=2"))
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap(),
+            "backend: /api/v2/users?page=2 Bearer token"
+        );
+
+        assert_eq!(
+            reqwest::get(format!("http://{server_addr}/staging"))
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap(),
+            "backend: /api/v2 Bearer token"
+        );
+    }
+
+    #[tokio::test]
+    async fn add_proxy_websocket() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let server_addr = setup_servers(WebProxyConfig::new("/api")).await;
+
+        let mut stream = tokio::net::TcpStream::connect(&server_addr).await.unwrap();
+        stream
+            .write_all(
+                format!("GET /api/echo HTTP/1.1\r\nHost: {server_addr}\r\nConnection: Upgrade\r\nUpgrade: echo\r\n\r\n")
+                    .as_bytes(),
+            )
+            .await
+            .unwrap();
+
+        // Read the response head of the handshake
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            let mut byte = [0; 1];
+            stream.read_exact(&mut byte).await.unwrap();
+            head.push(byte[0]);
+        }
+        assert!(String::from_utf8_lossy(&head).starts_with("HTTP/1.1 101"));
+
+        // Then everything sent over the connection should be echoed back by the backend
+        stream.write_all(b"ping").await.unwrap();
+        let mut echo = [0; 4];
+        stream.read_exact(&mut echo).await.unwrap();
+        assert_eq!(&echo, b"ping");
+    }
+
     #[test]
     fn add_proxy_empty_path() {
-        let config = WebProxyConfig {
-            backend: "http://localhost:8000".to_string(),
-        };
+        let config = WebProxyConfig::new("http://localhost:8000");
         let router = super::add_proxy(Router::new(), &config);
         match router.unwrap_err() {
             crate::Error::ProxySetup(e) => {
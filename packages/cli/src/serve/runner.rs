@@ -1,16 +1,21 @@
 use super::{AppHandle, ServeUpdate, WebServer};
 use crate::{
-    AppBundle, DioxusCrate, HotreloadFilemap, HotreloadResult, Platform, Result, TraceSrc,
+    AppBundle, CssPipeline, DioxusCrate, HotreloadFilemap, HotreloadResult, Platform, Result,
+    TraceSrc,
 };
 use dioxus_core::internal::TemplateGlobalKey;
 use dioxus_devtools_types::HotReloadMsg;
 use dioxus_html::HtmlCtx;
+use dioxus_rsx_hotreload::AssetResolver;
 use futures_util::{future::OptionFuture, stream::FuturesUnordered};
 use ignore::gitignore::Gitignore;
 use std::{
+    cell::RefCell,
     collections::{HashMap, HashSet},
+    hash::Hasher,
     net::SocketAddr,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    rc::Rc,
 };
 use tokio_stream::StreamExt;
 
@@ -20,6 +25,10 @@ pub(crate) struct AppRunner {
     pub(crate) file_map: HotreloadFilemap,
     pub(crate) ignore: Gitignore,
     pub(crate) applied_hot_reload_message: HotReloadMsg,
+    /// Assets referenced by new `asset!()` calls since the last full rebuild, mapped to their bundled name
+    pub(crate) new_assets: HashMap<PathBuf, PathBuf>,
+    /// The css transformers we rerun when one of their dependencies changes
+    pub(crate) css_pipelines: Vec<CssPipeline>,
     pub(crate) builds_opened: usize,
     pub(crate) should_full_rebuild: bool,
 }
@@ -31,6 +40,11 @@ impl AppRunner {
             running: Default::default(),
             file_map: HotreloadFilemap::new(),
             applied_hot_reload_message: Default::default(),
+            new_assets: Default::default(),
+            css_pipelines: CssPipeline::from_config(
+                &krate.crate_dir(),
+                &krate.config.application.css,
+            ),
             ignore: krate.workspace_gitignore(),
             krate: krate.clone(),
             builds_opened: 0,
@@ -159,8 +173,20 @@ impl AppRunner {
 
     pub(crate) async fn attempt_hot_reload(
         &mut self,
-        modified_files: Vec<PathBuf>,
+        mut modified_files: Vec<PathBuf>,
     ) -> Option<HotReloadMsg> {
+        // Rerun the css transformers that depend on the changed files. The stylesheets they generate are
+        // hotreloaded along with the other assets, so the app doesn't need to be rebuilt
+        for pipeline in self.css_pipelines.iter_mut() {
+            if !modified_files.iter().any(|path| pipeline.depends_on(path)) {
+                continue;
+            }
+            match pipeline.run().await {
+                Ok(()) => modified_files.push(pipeline.output()),
+                Err(err) => tracing::error!(dx_src = ?TraceSrc::Build, "{err}"),
+            }
+        }
+
         // If we have any changes to the rust files, we need to update the file map
         let mut templates = vec![];
 
@@ -181,6 +207,18 @@ impl AppRunner {
                 continue;
             }
 
+            // If the asset was added with a new `asset!()` call, rebundle it under the same name
+            let new_asset = dunce::canonicalize(&path)
+                .ok()
+                .and_then(|path| Some((self.new_assets.get(&path)?.clone(), path)));
+            if let Some((bundled_name, source)) = new_asset {
+                for runner in self.running.values() {
+                    runner.hotreload_new_asset(&source, &bundled_name).await;
+                }
+                assets.push(PathBuf::from("/assets/").join(bundled_name));
+                continue;
+            }
+
             // Otherwise, it might be an asset and we should look for it in all the running apps
             for runner in self.running.values() {
                 if let Some(bundled_name) = runner.hotreload_bundled_asset(&path).await {
@@ -194,6 +232,13 @@ impl AppRunner {
         // Multiple runners might have queued the same asset, so dedup them
         assets.dedup();
 
+        // Any new `asset!()` calls in the rust files are given a bundled name while diffing and bundled afterwards
+        let resolved_assets = Rc::new(RefCell::new(self.new_assets.clone()));
+        let asset_root = match self.krate.config.web.app.base_path.as_deref() {
+            Some(base_path) => format!("/{}/assets", base_path.trim_matches('/')),
+            None => "/assets".to_string(),
+        };
+
         // Process the rust files
         for rust_file in edited_rust_files {
             // Strip the prefix before sending it to the filemap
@@ -214,7 +259,34 @@ impl AppRunner {
                 continue;
             };
 
-            match self.file_map.update_rsx::<HtmlCtx>(path, contents) {
+            // Asset paths are relative to the crate the file is in
+            let crate_dir = rust_file
+                .ancestors()
+                .find(|dir| dir.join("Cargo.toml").is_file())
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| self.krate.crate_dir());
+            let asset_resolver = AssetResolver::new({
+                let resolved_assets = resolved_assets.clone();
+                let asset_root = asset_root.clone();
+                move |asset: &str| {
+                    let source = resolve_asset_source(&crate_dir, asset)?;
+                    let mut resolved_assets = resolved_assets.borrow_mut();
+                    let bundled_name = match resolved_assets.get(&source) {
+                        Some(bundled_name) => bundled_name.clone(),
+                        None => {
+                            let bundled_name = unique_bundled_name(&source)?;
+                            resolved_assets.insert(source, bundled_name.clone());
+                            bundled_name
+                        }
+                    };
+                    Some(format!("{asset_root}/{}", bundled_name.display()))
+                }
+            });
+
+            match self
+                .file_map
+                .update_rsx::<HtmlCtx>(path, contents, &asset_resolver)
+            {
                 HotreloadResult::Rsx(new) => templates.extend(new),
 
                 // The rust file may have failed to parse, but that is most likely
@@ -227,9 +299,26 @@ impl AppRunner {
             }
         }
 
+        // Bundle the assets that weren't referenced before
+        let resolved_assets = Rc::try_unwrap(resolved_assets)
+            .map(RefCell::into_inner)
+            .unwrap_or_default();
+        let mut new_assets = Vec::new();
+        for (source, bundled_name) in resolved_assets {
+            if self.new_assets.contains_key(&source) {
+                continue;
+            }
+            for runner in self.running.values() {
+                runner.hotreload_new_asset(&source, &bundled_name).await;
+            }
+            new_assets.push(PathBuf::from("/assets/").join(&bundled_name));
+            self.new_assets.insert(source, bundled_name);
+        }
+
         let msg = HotReloadMsg {
             templates,
             assets,
+            new_assets,
             unknown_files: vec![],
         };
 
@@ -246,6 +335,7 @@ impl AppRunner {
     /// Clear the hot reload changes. This should be called any time a new build is starting
     pub(crate) fn clear_hot_reload_changes(&mut self) {
         self.applied_hot_reload_message = Default::default();
+        self.new_assets.clear();
     }
 
     /// Store the hot reload changes for any future clients that connect
@@ -260,6 +350,9 @@ impl AppRunner {
             .collect();
         let mut assets: HashSet<PathBuf> =
             std::mem::take(&mut applied.assets).into_iter().collect();
+        let mut new_assets: HashSet<PathBuf> = std::mem::take(&mut applied.new_assets)
+            .into_iter()
+            .collect();
         let mut unknown_files: HashSet<PathBuf> = std::mem::take(&mut applied.unknown_files)
             .into_iter()
             .collect();
@@ -267,9 +360,11 @@ impl AppRunner {
             templates.insert(template.key.clone(), template.clone());
         }
         assets.extend(msg.assets.iter().cloned());
+        new_assets.extend(msg.new_assets.iter().cloned());
         unknown_files.extend(msg.unknown_files.iter().cloned());
         applied.templates = templates.into_values().collect();
         applied.assets = assets.into_iter().collect();
+        applied.new_assets = new_assets.into_iter().collect();
         applied.unknown_files = unknown_files.into_iter().collect();
     }
 
@@ -334,3 +429,28 @@ impl AppRunner {
         }
     }
 }
+
+/// Resolve the path passed to `asset!()` the same way the macro does, relative to the crate root. Paths that don't
+/// exist or escape the crate are not resolved.
+fn resolve_asset_source(crate_dir: &Path, asset: &str) -> Option<PathBuf> {
+    let crate_dir = dunce::canonicalize(crate_dir).ok()?;
+    let source = dunce::canonicalize(crate_dir.join(asset.trim_start_matches('/'))).ok()?;
+    (source != crate_dir && source.starts_with(&crate_dir)).then_some(source)
+}
+
+/// Create a bundled name for a new asset in the form `{name}-{hash}.{extension}` where the hash is the hash of
+/// the contents of the asset
+fn unique_bundled_name(source: &Path) -> Option<PathBuf> {
+    let contents = std::fs::read(source).ok()?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    hasher.write(&contents);
+
+    let stem = source.file_stem()?.to_string_lossy();
+    let name = match source.extension().and_then(|ext| ext.to_str()) {
+        // Scss is compiled to css when it is bundled
+        Some("scss" | "sass") => format!("{stem}-{:016x}.css", hasher.finish()),
+        Some(ext) => format!("{stem}-{:016x}.{ext}", hasher.finish()),
+        None => format!("{stem}-{:016x}", hasher.finish()),
+    };
+    Some(PathBuf::from(name))
+}
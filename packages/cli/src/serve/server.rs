@@ -19,6 +19,10 @@ use axum::{
     routing::{get, get_service},
     Extension, Router,
 };
+use axum_server::{
+    accept::{Accept, DefaultAcceptor},
+    tls_rustls::{RustlsAcceptor, RustlsConfig},
+};
 use dioxus_devtools_types::{DevserverMsg, HotReloadMsg};
 use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use futures_util::{
@@ -30,12 +34,18 @@ use hyper::HeaderMap;
 use serde::{Deserialize, Serialize};
 use std::{
     convert::Infallible,
-    fs, io,
+    fs,
+    future::Future,
+    io,
     net::{IpAddr, SocketAddr, TcpListener},
-    path::Path,
+    path::{Path, PathBuf},
+    pin::Pin,
+    process::Stdio,
     sync::Arc,
     sync::RwLock,
 };
+use tokio::net::TcpStream;
+use tokio_util::either::Either;
 use tower_http::{
     cors::Any,
     services::fs::{ServeDir, ServeFileSystemResponseBody},
@@ -52,6 +62,7 @@ pub(crate) struct WebServer {
     devserver_ip: IpAddr,
     devserver_port: u16,
     proxied_port: Option<u16>,
+    https: bool,
     hot_reload_sockets: Vec<WebSocket>,
     build_status_sockets: Vec<WebSocket>,
     new_hot_reload_sockets: UnboundedReceiver<WebSocket>,
@@ -104,15 +115,19 @@ impl WebServer {
         })?;
 
         // And finally, start the server mainloop
-        tokio::spawn(devserver_mainloop(
-            krate.config.web.https.clone(),
-            listener,
-            router,
-        ));
+        let https = krate.config.web.https.clone();
+        let ssl_dir = krate.out_dir().join("ssl");
+        let hosts = certificate_hosts(devserver_ip);
+        tokio::spawn(async move {
+            if let Err(err) = devserver_mainloop(https, ssl_dir, hosts, listener, router).await {
+                tracing::error!(dx_src = ?TraceSrc::Dev, "Devserver stopped: {err}");
+            }
+        });
 
         Ok(Self {
             build_status,
             proxied_port,
+            https: krate.config.web.https.enabled == Some(true),
             devserver_ip,
             devserver_port,
             hot_reload_sockets: Default::default(),
@@ -330,10 +345,20 @@ impl WebServer {
             _ => self.proxied_server_address(),
         }
     }
+
+    /// The protocol of the [`Self::server_address`]. Only the devserver itself is served over https.
+    pub fn server_protocol(&self) -> &'static str {
+        match self.platform {
+            Platform::Web | Platform::Server if self.https => "https",
+            _ => "http",
+        }
+    }
 }
 
 async fn devserver_mainloop(
     https_cfg: WebHttpsConfig,
+    ssl_dir: PathBuf,
+    hosts: Vec<String>,
     listener: TcpListener,
     router: Router,
 ) -> Result<()> {
@@ -347,10 +372,14 @@ async fn devserver_mainloop(
     }
 
     // If we're using rustls, we need to get the cert/key paths and then set up rustls
-    let (cert_path, key_path) = get_rustls(&https_cfg).await?;
-    let rustls = axum_server::tls_rustls::RustlsConfig::from_pem_file(cert_path, key_path).await?;
+    let (cert_path, key_path) = get_rustls(&https_cfg, &ssl_dir, &hosts).await?;
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    let rustls = RustlsConfig::from_pem_file(cert_path, key_path).await?;
 
-    axum_server::from_tcp_rustls(listener, rustls)
+    axum_server::from_tcp(listener)
+        .acceptor(DevserverAcceptor {
+            tls: RustlsAcceptor::new(rustls),
+        })
         .serve(router.into_make_service())
         .await?;
 
@@ -470,7 +499,9 @@ fn build_serve_dir(args: &ServeArgs, cfg: &DioxusCrate) -> axum::routing::Method
         HeaderValue::from_static("same-origin"),
     );
 
-    let (coep, coop) = match args.cross_origin_policy {
+    // Browsers only share memory with web workers on cross-origin isolated pages
+    let isolated = args.cross_origin_policy || args.build_arguments.threads;
+    let (coep, coop) = match isolated {
         true => CORS_REQUIRE.clone(),
         false => CORS_UNSAFE.clone(),
     };
@@ -547,69 +578,174 @@ pub(crate) fn insert_no_cache_headers(headers: &mut HeaderMap) {
     headers.insert(EXPIRES, HeaderValue::from_static("0"));
 }
 
-async fn get_rustls(web_config: &WebHttpsConfig) -> Result<(String, String)> {
-    // If we're not using mkcert, just use the cert/key paths given to use in the config
-    if !web_config.mkcert.unwrap_or(false) {
+/// Accepts both tls and plain connections on the devserver port.
+///
+/// The browser loads the app over https, but native apps connect to the devtools websocket with a plain `ws://`
+/// url since they don't trust the local certificate. We peek at the first byte of each connection to tell a tls
+/// handshake apart from a plain http request.
+#[derive(Clone)]
+struct DevserverAcceptor {
+    tls: RustlsAcceptor<DefaultAcceptor>,
+}
+
+impl<S: Send + 'static> Accept<TcpStream, S> for DevserverAcceptor {
+    type Stream = Either<<RustlsAcceptor as Accept<TcpStream, S>>::Stream, TcpStream>;
+    type Service = S;
+    type Future = Pin<Box<dyn Future<Output = io::Result<(Self::Stream, S)>> + Send>>;
+
+    fn accept(&self, stream: TcpStream, service: S) -> Self::Future {
+        /// The first byte of every tls handshake
+        const TLS_HANDSHAKE: u8 = 0x16;
+
+        let tls = self.tls.clone();
+        Box::pin(async move {
+            let mut first = [0; 1];
+            if stream.peek(&mut first).await? == 1 && first[0] == TLS_HANDSHAKE {
+                let (stream, service) = tls.accept(stream, service).await?;
+                return Ok((Either::Left(stream), service));
+            }
+            Ok((Either::Right(stream), service))
+        })
+    }
+}
+
+/// The hosts the devserver certificate is valid for
+fn certificate_hosts(devserver_ip: IpAddr) -> Vec<String> {
+    let mut hosts = vec![
+        "localhost".to_string(),
+        "127.0.0.1".to_string(),
+        "::1".to_string(),
+    ];
+
+    // Include the address we're bound to so the app can be opened from other devices on the network
+    if !devserver_ip.is_loopback() && !devserver_ip.is_unspecified() {
+        hosts.push(devserver_ip.to_string());
+    }
+
+    hosts
+}
+
+async fn get_rustls(
+    web_config: &WebHttpsConfig,
+    ssl_dir: &Path,
+    hosts: &[String],
+) -> Result<(String, String)> {
+    // If we're not using mkcert and were given a cert/key in the config, just use those
+    if web_config.mkcert != Some(true) {
         if let (Some(key), Some(cert)) = (web_config.key_path.clone(), web_config.cert_path.clone())
         {
             return Ok((cert, key));
-        } else {
-            // missing cert or key
-            return Err("https is enabled but cert or key path is missing".into());
         }
     }
 
-    const DEFAULT_KEY_PATH: &str = "ssl/key.pem";
-    const DEFAULT_CERT_PATH: &str = "ssl/cert.pem";
-
-    // Get paths to store certs, otherwise use ssl/item.pem
+    // Otherwise we generate the certificate ourselves, storing it in the config paths or target/dx/ssl
     let key_path = web_config
         .key_path
         .clone()
-        .unwrap_or(DEFAULT_KEY_PATH.to_string());
-
+        .map(PathBuf::from)
+        .unwrap_or_else(|| ssl_dir.join("key.pem"));
     let cert_path = web_config
         .cert_path
         .clone()
-        .unwrap_or(DEFAULT_CERT_PATH.to_string());
+        .map(PathBuf::from)
+        .unwrap_or_else(|| ssl_dir.join("cert.pem"));
+
+    // Reuse the last certificate as long as it was generated for the same hosts. mkcert users expect the
+    // certificate to be regenerated every time, so we don't cache it for them.
+    let hosts_path = ssl_dir.join("hosts");
+    let hosts_list = hosts.join("\n");
+    let cached = web_config.mkcert != Some(true)
+        && key_path.exists()
+        && cert_path.exists()
+        && fs::read_to_string(&hosts_path).is_ok_and(|cached| cached == hosts_list);
+
+    if !cached {
+        fs::create_dir_all(ssl_dir)?;
+        for path in [&key_path, &cert_path] {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+        }
 
-    // Create ssl directory if using defaults
-    if key_path == DEFAULT_KEY_PATH && cert_path == DEFAULT_CERT_PATH {
-        _ = fs::create_dir("ssl");
+        generate_certificate(&key_path, &cert_path, hosts).await?;
+        _ = fs::write(&hosts_path, hosts_list);
     }
 
-    let cmd = tokio::process::Command::new("mkcert")
-        .args([
-            "-install",
-            "-key-file",
-            &key_path,
-            "-cert-file",
-            &cert_path,
-            "localhost",
-            "::1",
-            "127.0.0.1",
-        ])
-        .spawn();
-
-    match cmd {
-        Err(e) => {
-            match e.kind() {
-                io::ErrorKind::NotFound => {
-                    tracing::error!(dx_src = ?TraceSrc::Dev, "`mkcert` is not installed. See https://github.com/FiloSottile/mkcert#installation for installation instructions.")
-                }
-                e => {
-                    tracing::error!(dx_src = ?TraceSrc::Dev, "An error occurred while generating mkcert certificates: {}", e.to_string())
-                }
-            };
-            return Err("failed to generate mkcert certificates".into());
+    Ok((
+        cert_path.display().to_string(),
+        key_path.display().to_string(),
+    ))
+}
+
+/// Generate a certificate for the hosts with mkcert, which installs a local certificate authority so browsers trust
+/// the certificate. If mkcert isn't installed, we fall back to a self-signed certificate from openssl.
+async fn generate_certificate(key_path: &Path, cert_path: &Path, hosts: &[String]) -> Result<()> {
+    let mkcert = tokio::process::Command::new("mkcert")
+        .arg("-install")
+        .arg("-key-file")
+        .arg(key_path)
+        .arg("-cert-file")
+        .arg(cert_path)
+        .args(hosts)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await;
+
+    match mkcert {
+        Ok(output) if output.status.success() => return Ok(()),
+        Ok(output) => {
+            return Err(anyhow::anyhow!(
+                "Failed to generate certificates with mkcert:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            )
+            .into())
         }
-        Ok(mut cmd) => {
-            cmd.wait().await?;
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            tracing::warn!(dx_src = ?TraceSrc::Dev, "`mkcert` is not installed, so your browser will warn about the self-signed certificate. See https://github.com/FiloSottile/mkcert#installation for installation instructions.");
+        }
+        Err(err) => {
+            return Err(anyhow::anyhow!("Failed to run mkcert: {err}").into());
         }
     }
 
-    Ok((cert_path, key_path))
+    let subject_alt_names = hosts
+        .iter()
+        .map(|host| match host.parse::<IpAddr>() {
+            Ok(_) => format!("IP:{host}"),
+            Err(_) => format!("DNS:{host}"),
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let output = tokio::process::Command::new("openssl")
+        .args(["req", "-x509", "-newkey", "rsa:2048", "-nodes"])
+        .args(["-days", "825", "-subj", "/CN=localhost"])
+        .arg("-addext")
+        .arg(format!("subjectAltName={subject_alt_names}"))
+        .arg("-keyout")
+        .arg(key_path)
+        .arg("-out")
+        .arg(cert_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .context(
+            "Failed to generate a certificate. Install mkcert or openssl to serve over https.",
+        )?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to generate a certificate with openssl:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+        .into());
+    }
+
+    Ok(())
 }
+
 /// Bind a listener to any point and return it
 /// When the listener is dropped, the socket will be closed, but we'll still have a port that we
 /// can bind our proxy to.
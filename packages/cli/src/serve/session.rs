@@ -0,0 +1,74 @@
+use super::{AppRunner, ServeUpdate, Watcher, WebServer};
+use crate::{Builder, DioxusCrate, Result, ServeArgs};
+
+/// Everything needed to serve a single app: its build engine, devserver, file watcher and running processes.
+///
+/// `dx serve` usually has a single session, but `--apps` serves several packages of a workspace at once. Each app
+/// then has its own session with its own devserver port and cargo target directory so the builds don't block each
+/// other or invalidate each other's artifacts. The sessions share the TUI.
+pub(crate) struct AppSession {
+    pub(crate) args: ServeArgs,
+    pub(crate) krate: DioxusCrate,
+    pub(crate) builder: Builder,
+    pub(crate) devserver: WebServer,
+    pub(crate) watcher: Watcher,
+    pub(crate) runner: AppRunner,
+
+    /// The prefix for the logs of this app. Empty if only one app is being served.
+    pub(crate) label: String,
+}
+
+impl AppSession {
+    /// Load the crate of every app being served and start building them
+    pub(crate) async fn start_all(args: &ServeArgs) -> Result<Vec<Self>> {
+        let mut all_args = args.app_args();
+        let multiple = all_args.len() > 1;
+
+        let mut sessions = Vec::with_capacity(all_args.len());
+        for args in all_args.iter_mut() {
+            // Load the krate and resolve the server args against it - this might log so do it after we turn on the tracer first
+            let krate = args.load_krate().await?;
+
+            // Concurrent cargo builds in the same target directory wait on each other's lock and rebuild the
+            // shared dependencies whenever their features differ, so each app gets its own directory
+            let label = match multiple {
+                true => {
+                    if args.build_arguments.target_dir.is_none() {
+                        args.build_arguments.target_dir =
+                            Some(krate.out_dir().join(krate.executable_name()).join("target"));
+                    }
+                    format!("[{}] ", krate.executable_name())
+                }
+                false => String::new(),
+            };
+
+            // Note that starting the builder will queue up a build immediately
+            sessions.push(Self {
+                builder: Builder::start(&krate, args.build_args())?,
+                devserver: WebServer::start(&krate, args)?,
+                watcher: Watcher::start(&krate, args),
+                runner: AppRunner::start(&krate),
+                args: args.clone(),
+                krate,
+                label,
+            });
+        }
+
+        Ok(sessions)
+    }
+
+    /// Wait for the next update from any part of this app
+    pub(crate) async fn wait(&mut self) -> ServeUpdate {
+        tokio::select! {
+            msg = self.builder.wait() => ServeUpdate::BuildUpdate(msg),
+            msg = self.watcher.wait() => msg,
+            msg = self.devserver.wait() => msg,
+            msg = self.runner.wait() => msg,
+        }
+    }
+
+    /// The name of the app shown in the TUI
+    pub(crate) fn name(&self) -> &str {
+        self.krate.executable_name()
+    }
+}
@@ -47,6 +47,9 @@ pub(crate) enum ServeUpdate {
 
     ToggleShouldRebuild,
 
+    /// Show the next app in the TUI when serving several apps at once
+    FocusNextApp,
+
     Redraw,
 
     TracingLog {
@@ -0,0 +1,162 @@
+use std::{future::ready, rc::Rc};
+
+use dioxus_document::{Document, EvalError};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{Clipboard, ClipboardError, ClipboardFuture, ClipboardImage};
+
+/// Runs a clipboard operation and converts the outcome into something serde can read as a `Result`
+const PRELUDE: &str = r#"
+const clipboardResult = async (action) => {
+    try {
+        if (!navigator.clipboard) {
+            throw { name: "NotSupportedError", message: "navigator.clipboard is not available" };
+        }
+        return { Ok: await action() };
+    } catch (e) {
+        return { Err: { name: (e && e.name) || "Error", message: String((e && e.message) || e) } };
+    }
+};
+const clipboardEmpty = () => ({ name: "ClipboardEmpty", message: "" });
+const clipboardReadType = async (matches) => {
+    for (const item of await navigator.clipboard.read()) {
+        const type = item.types.find(matches);
+        if (type) {
+            return await item.getType(type);
+        }
+    }
+    throw clipboardEmpty();
+};
+"#;
+
+const READ_TEXT: &str = r#"
+const text = await navigator.clipboard.readText();
+if (!text) {
+    throw clipboardEmpty();
+}
+return text;
+"#;
+
+const WRITE_TEXT: &str = r#"
+await navigator.clipboard.writeText(await dioxus.recv());
+return null;
+"#;
+
+const READ_HTML: &str = r#"
+const blob = await clipboardReadType((type) => type === "text/html");
+return await blob.text();
+"#;
+
+const WRITE_HTML: &str = r#"
+const data = await dioxus.recv();
+const items = { "text/html": new Blob([data.html], { type: "text/html" }) };
+if (data.alt_text != null) {
+    items["text/plain"] = new Blob([data.alt_text], { type: "text/plain" });
+}
+await navigator.clipboard.write([new ClipboardItem(items)]);
+return null;
+"#;
+
+const READ_IMAGE: &str = r#"
+const blob = await clipboardReadType((type) => type.startsWith("image/"));
+return { mime_type: blob.type, bytes: Array.from(new Uint8Array(await blob.arrayBuffer())) };
+"#;
+
+const WRITE_IMAGE: &str = r#"
+const data = await dioxus.recv();
+const blob = new Blob([new Uint8Array(data.bytes)], { type: data.mime_type });
+await navigator.clipboard.write([new ClipboardItem({ [data.mime_type]: blob })]);
+return null;
+"#;
+
+/// A [`Clipboard`] provider that uses the [web Clipboard API](https://developer.mozilla.org/en-US/docs/Web/API/Clipboard_API)
+/// through [`Document::eval`].
+///
+/// This works with any renderer that runs a javascript context like web, desktop, mobile and liveview.
+/// Most browsers only allow access to the clipboard in a secure context and in response to user input.
+pub struct EvalClipboard {
+    document: Rc<dyn Document>,
+}
+
+impl EvalClipboard {
+    /// Create a new [`EvalClipboard`] that evaluates javascript in the given document.
+    pub fn new(document: Rc<dyn Document>) -> Self {
+        Self { document }
+    }
+
+    fn run<T: DeserializeOwned + 'static>(
+        &self,
+        script: &str,
+        data: impl Serialize,
+    ) -> ClipboardFuture<T> {
+        let eval = self.document.eval(format!(
+            "{PRELUDE}\nreturn await clipboardResult(async () => {{{script}}});"
+        ));
+        if let Err(err) = eval.send(data) {
+            return Box::pin(ready(Err(err.into())));
+        }
+        Box::pin(async move {
+            let result: Result<T, JsClipboardError> = eval.join().await?;
+            result.map_err(Into::into)
+        })
+    }
+}
+
+impl Clipboard for EvalClipboard {
+    fn read_text(&self) -> ClipboardFuture<String> {
+        self.run(READ_TEXT, ())
+    }
+
+    fn write_text(&self, text: String) -> ClipboardFuture<()> {
+        self.run(WRITE_TEXT, text)
+    }
+
+    fn read_html(&self) -> ClipboardFuture<String> {
+        self.run(READ_HTML, ())
+    }
+
+    fn write_html(&self, html: String, alt_text: Option<String>) -> ClipboardFuture<()> {
+        #[derive(Serialize)]
+        struct HtmlData {
+            html: String,
+            alt_text: Option<String>,
+        }
+
+        self.run(WRITE_HTML, HtmlData { html, alt_text })
+    }
+
+    fn read_image(&self) -> ClipboardFuture<ClipboardImage> {
+        self.run(READ_IMAGE, ())
+    }
+
+    fn write_image(&self, image: ClipboardImage) -> ClipboardFuture<()> {
+        self.run(WRITE_IMAGE, image)
+    }
+}
+
+/// A javascript exception thrown while accessing the clipboard
+#[derive(Deserialize)]
+struct JsClipboardError {
+    name: String,
+    message: String,
+}
+
+impl From<JsClipboardError> for ClipboardError {
+    fn from(err: JsClipboardError) -> Self {
+        match err.name.as_str() {
+            "NotAllowedError" | "SecurityError" => ClipboardError::PermissionDenied,
+            "NotSupportedError" => ClipboardError::Unsupported,
+            "ClipboardEmpty" => ClipboardError::Empty,
+            _ => ClipboardError::Platform(format!("{}: {}", err.name, err.message)),
+        }
+    }
+}
+
+impl From<EvalError> for ClipboardError {
+    fn from(err: EvalError) -> Self {
+        match err {
+            EvalError::Unsupported => ClipboardError::Unsupported,
+            err => ClipboardError::Platform(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use dioxus_core::prelude::{provide_context, provide_root_context};
+use std::{fmt::Display, future::Future, pin::Pin, rc::Rc};
+
+mod eval;
+mod memory;
+pub use eval::*;
+pub use memory::*;
+
+/// Get the clipboard provider for the current platform or an in-memory clipboard if the platform doesn't implement clipboard functionality.
+pub fn clipboard() -> Rc<dyn Clipboard> {
+    match dioxus_core::prelude::try_consume_context::<Rc<dyn Clipboard>>() {
+        Some(clipboard) => clipboard,
+        None => {
+            tracing::error!("Unable to find a clipboard provider in the renderer. Falling back to the in-memory clipboard provider.");
+            provide_root_context(Rc::new(MemoryClipboard::default()) as Rc<dyn Clipboard>)
+        }
+    }
+}
+
+/// Provide a clipboard context to the current component.
+pub fn provide_clipboard_context(clipboard: Rc<dyn Clipboard>) {
+    provide_context(clipboard);
+}
+
+/// The future returned by every [`Clipboard`] operation.
+pub type ClipboardFuture<T> = Pin<Box<dyn Future<Output = Result<T, ClipboardError>>>>;
+
+/// Access to the system clipboard.
+///
+/// Every operation is asynchronous because most platforms only expose the clipboard through an
+/// asynchronous API. Writing any kind of content replaces everything that was on the clipboard before.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn app() -> Element {
+///     rsx! {
+///         button {
+///             onclick: move |_| async move {
+///                 if let Err(err) = clipboard().write_text("Hello from dioxus".to_string()).await {
+///                     tracing::error!("Failed to copy: {err}");
+///                 }
+///             },
+///             "Copy"
+///         }
+///     }
+/// }
+/// ```
+pub trait Clipboard {
+    /// Read the plain text contents of the clipboard.
+    fn read_text(&self) -> ClipboardFuture<String>;
+
+    /// Replace the contents of the clipboard with plain text.
+    fn write_text(&self, text: String) -> ClipboardFuture<()>;
+
+    /// Read the HTML contents of the clipboard.
+    fn read_html(&self) -> ClipboardFuture<String>;
+
+    /// Replace the contents of the clipboard with HTML.
+    ///
+    /// `alt_text` is stored as the plain text representation for applications that can't paste HTML.
+    fn write_html(&self, html: String, alt_text: Option<String>) -> ClipboardFuture<()>;
+
+    /// Read the first image on the clipboard.
+    fn read_image(&self) -> ClipboardFuture<ClipboardImage>;
+
+    /// Replace the contents of the clipboard with an image.
+    fn write_image(&self, image: ClipboardImage) -> ClipboardFuture<()>;
+}
+
+/// An encoded image read from or written to the clipboard.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ClipboardImage {
+    /// The mime type of the encoded image, for example `image/png`.
+    pub mime_type: String,
+    /// The encoded image bytes.
+    pub bytes: Vec<u8>,
+}
+
+impl ClipboardImage {
+    /// Create a new image from a PNG encoded buffer. PNG is the only image format every platform is required to support.
+    pub fn png(bytes: Vec<u8>) -> Self {
+        Self {
+            mime_type: "image/png".to_string(),
+            bytes,
+        }
+    }
+}
+
+/// Represents an error when reading from or writing to the clipboard
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ClipboardError {
+    /// The platform does not support accessing the clipboard.
+    Unsupported,
+
+    /// The user or platform denied access to the clipboard.
+    PermissionDenied,
+
+    /// The clipboard doesn't contain content in the requested format.
+    Empty,
+
+    /// The platform returned an error while accessing the clipboard.
+    Platform(String),
+}
+
+impl Display for ClipboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClipboardError::Unsupported => write!(f, "ClipboardError::Unsupported - the clipboard is not supported on the current platform"),
+            ClipboardError::PermissionDenied => write!(f, "ClipboardError::PermissionDenied - access to the clipboard was denied"),
+            ClipboardError::Empty => write!(f, "ClipboardError::Empty - the clipboard doesn't contain content in the requested format"),
+            ClipboardError::Platform(err) => write!(f, "ClipboardError::Platform - {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
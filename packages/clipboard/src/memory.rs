@@ -0,0 +1,87 @@
+use std::{cell::RefCell, future::ready};
+
+use crate::{Clipboard, ClipboardError, ClipboardFuture, ClipboardImage};
+
+#[derive(Default)]
+struct MemoryClipboardState {
+    text: Option<String>,
+    html: Option<String>,
+    image: Option<ClipboardImage>,
+}
+
+/// A [`Clipboard`] provider that keeps the clipboard contents in memory.
+///
+/// The contents are not shared with other applications. This is used as the fallback on platforms
+/// without a system clipboard and is useful in tests.
+///
+/// ```rust
+/// use dioxus_clipboard::{Clipboard, ClipboardError, MemoryClipboard};
+///
+/// let clipboard = MemoryClipboard::default();
+/// futures::executor::block_on(async {
+///     assert_eq!(clipboard.read_text().await, Err(ClipboardError::Empty));
+///
+///     clipboard.write_html("<b>Hi</b>".to_string(), Some("Hi".to_string())).await.unwrap();
+///     assert_eq!(clipboard.read_html().await.unwrap(), "<b>Hi</b>");
+///     assert_eq!(clipboard.read_text().await.unwrap(), "Hi");
+///
+///     // Writing new content replaces everything on the clipboard
+///     clipboard.write_text("Bye".to_string()).await.unwrap();
+///     assert_eq!(clipboard.read_html().await, Err(ClipboardError::Empty));
+/// });
+/// ```
+#[derive(Default)]
+pub struct MemoryClipboard {
+    state: RefCell<MemoryClipboardState>,
+}
+
+impl MemoryClipboard {
+    fn replace(&self, state: MemoryClipboardState) -> ClipboardFuture<()> {
+        *self.state.borrow_mut() = state;
+        Box::pin(ready(Ok(())))
+    }
+
+    fn read<T: 'static>(
+        &self,
+        read: impl FnOnce(&MemoryClipboardState) -> Option<T>,
+    ) -> ClipboardFuture<T> {
+        let value = read(&self.state.borrow()).ok_or(ClipboardError::Empty);
+        Box::pin(ready(value))
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn read_text(&self) -> ClipboardFuture<String> {
+        self.read(|state| state.text.clone())
+    }
+
+    fn write_text(&self, text: String) -> ClipboardFuture<()> {
+        self.replace(MemoryClipboardState {
+            text: Some(text),
+            ..Default::default()
+        })
+    }
+
+    fn read_html(&self) -> ClipboardFuture<String> {
+        self.read(|state| state.html.clone())
+    }
+
+    fn write_html(&self, html: String, alt_text: Option<String>) -> ClipboardFuture<()> {
+        self.replace(MemoryClipboardState {
+            text: alt_text,
+            html: Some(html),
+            ..Default::default()
+        })
+    }
+
+    fn read_image(&self) -> ClipboardFuture<ClipboardImage> {
+        self.read(|state| state.image.clone())
+    }
+
+    fn write_image(&self, image: ClipboardImage) -> ClipboardFuture<()> {
+        self.replace(MemoryClipboardState {
+            image: Some(image),
+            ..Default::default()
+        })
+    }
+}
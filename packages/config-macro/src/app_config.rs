@@ -0,0 +1,218 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Fields, GenericArgument, ItemStruct, LitStr, PathArguments, Type,
+};
+
+/// A struct whose fields are read from the environment
+pub(crate) struct ConfigStruct {
+    item: ItemStruct,
+    fields: Vec<ConfigField>,
+}
+
+struct ConfigField {
+    ident: syn::Ident,
+    ty: Type,
+    secret: bool,
+}
+
+impl Parse for ConfigStruct {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut item: ItemStruct = input.parse()?;
+
+        if !item.generics.params.is_empty() {
+            return Err(syn::Error::new(
+                item.generics.span(),
+                "config structs can't be generic",
+            ));
+        }
+
+        let Fields::Named(named) = &mut item.fields else {
+            return Err(syn::Error::new(
+                item.ident.span(),
+                "config structs must have named fields",
+            ));
+        };
+
+        let mut fields = Vec::new();
+        for field in named.named.iter_mut() {
+            // Pull out the #[secret] marker and hide the field from client builds
+            let secret = field
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("secret"));
+            field.attrs.retain(|attr| !attr.path().is_ident("secret"));
+            if secret {
+                field
+                    .attrs
+                    .push(syn::parse_quote!(#[cfg(feature = "server")]));
+            }
+
+            fields.push(ConfigField {
+                ident: field.ident.clone().unwrap(),
+                ty: field.ty.clone(),
+                secret,
+            });
+        }
+
+        Ok(Self { item, fields })
+    }
+}
+
+impl ToTokens for ConfigStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let item = &self.item;
+        let name = &item.ident;
+
+        let fields = self.fields.iter().map(|field| {
+            let ident = &field.ident;
+            let key_name = ident.to_string().trim_start_matches("r#").to_uppercase();
+            let key = LitStr::new(&key_name, ident.span());
+
+            // Only public values are baked into the binary. Secrets are always read at runtime on the server
+            let (cfg, built, runtime) = match field.secret {
+                true => (
+                    quote! { #[cfg(feature = "server")] },
+                    quote! { None },
+                    quote! { true },
+                ),
+                false => {
+                    let built_key =
+                        LitStr::new(&format!("{CONFIG_ENV_PREFIX}{key_name}"), ident.span());
+                    (
+                        quote! {},
+                        quote! { option_env!(#built_key) },
+                        quote! { cfg!(feature = "server") },
+                    )
+                }
+            };
+
+            let value = match option_inner(&field.ty) {
+                Some(inner) => quote! {
+                    dioxus_cli_config::__read_config_value::<#inner>(#key, #built, #runtime)?
+                },
+                None => {
+                    let ty = &field.ty;
+                    quote! {
+                        dioxus_cli_config::__require_config_value::<#ty>(#key, #built, #runtime)?
+                    }
+                }
+            };
+
+            quote! {
+                #cfg
+                #ident: #value,
+            }
+        });
+
+        tokens.extend(quote! {
+            #item
+
+            impl dioxus_cli_config::DioxusConfig for #name {
+                fn load() -> ::std::result::Result<Self, dioxus_cli_config::ConfigError> {
+                    ::std::result::Result::Ok(Self {
+                        #(#fields)*
+                    })
+                }
+
+                fn get() -> &'static Self {
+                    static CONFIG: ::std::sync::OnceLock<#name> = ::std::sync::OnceLock::new();
+                    CONFIG.get_or_init(|| {
+                        <Self as dioxus_cli_config::DioxusConfig>::load().unwrap_or_else(|err| {
+                            panic!("Failed to load {}: {err}", stringify!(#name))
+                        })
+                    })
+                }
+            }
+        });
+    }
+}
+
+/// The prefix of the environment variables the CLI sets for public config values. This must match
+/// `dioxus_cli_config::CONFIG_ENV_PREFIX`.
+const CONFIG_ENV_PREFIX: &str = "DIOXUS_CONFIG_";
+
+/// Get `T` if the type is `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand(config: ConfigStruct) -> String {
+        config.into_token_stream().to_string()
+    }
+
+    #[test]
+    fn secrets_only_exist_on_the_server() {
+        let tokens = expand(parse_quote! {
+            pub struct AppConfig {
+                pub api_url: String,
+                #[secret]
+                pub database_url: String,
+            }
+        });
+
+        // The field and the code that reads it are both compiled out of client builds
+        assert!(
+            tokens.contains(r#"# [cfg (feature = "server")] pub database_url : String"#),
+            "{tokens}"
+        );
+        assert!(
+            tokens.contains(r#"# [cfg (feature = "server")] database_url :"#),
+            "{tokens}"
+        );
+        assert!(!tokens.contains("# [secret]"), "{tokens}");
+
+        // Public fields exist everywhere
+        assert!(tokens.contains("{ pub api_url : String ,"), "{tokens}");
+        assert!(
+            !tokens.contains(r#"# [cfg (feature = "server")] api_url"#),
+            "{tokens}"
+        );
+    }
+
+    #[test]
+    fn secrets_are_never_baked_in() {
+        let tokens = expand(parse_quote! {
+            pub struct AppConfig {
+                pub api_url: String,
+                #[secret]
+                pub database_url: Option<String>,
+            }
+        });
+
+        // Public values are baked in and only read at runtime on the server
+        assert!(
+            tokens.contains(
+                r#"__require_config_value :: < String > ("API_URL" , option_env ! ("DIOXUS_CONFIG_API_URL") , cfg ! (feature = "server"))"#
+            ),
+            "{tokens}"
+        );
+
+        // Secrets are only ever read at runtime
+        assert!(
+            tokens.contains(r#"__read_config_value :: < String > ("DATABASE_URL" , None , true)"#),
+            "{tokens}"
+        );
+        assert!(!tokens.contains("DIOXUS_CONFIG_DATABASE_URL"), "{tokens}");
+    }
+}
@@ -6,6 +6,8 @@ use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 
+mod app_config;
+
 macro_rules! define_config_macro {
     ($name:ident if $($cfg:tt)+) => {
         #[proc_macro]
@@ -35,3 +37,53 @@ define_config_macro!(mobile if feature = "mobile");
 define_config_macro!(fullstack if feature = "fullstack");
 define_config_macro!(ssr if feature = "ssr");
 define_config_macro!(liveview if feature = "liveview");
+
+/// Define a typed configuration struct that is read from the environment.
+///
+/// Every field is read from the environment variable with the uppercase name of the field. Fields can be any type
+/// that implements [`FromStr`](std::str::FromStr), and `Option` fields may be left unset.
+///
+/// - Public fields are set per environment in the Dioxus.toml. The CLI bakes the values of the environment you build
+///   for into the app, so they are available on every platform, including the web.
+/// - Fields marked with `#[secret]` only exist when the `server` feature of your crate is enabled and are only read
+///   from runtime environment variables. Using a secret in client code is a compile error.
+///
+/// On the server, runtime environment variables override the public values from the Dioxus.toml.
+///
+/// ```toml
+/// [env.dev]
+/// API_URL = "http://localhost:8080/api"
+///
+/// [env.release]
+/// API_URL = "https://example.com/api"
+/// ```
+///
+/// ```rust, ignore
+/// dioxus_config! {
+///     #[derive(Debug)]
+///     pub struct AppConfig {
+///         /// The url the client sends requests to
+///         pub api_url: String,
+///         /// How many items are shown per page
+///         pub page_size: Option<usize>,
+///         /// The connection string of the database
+///         #[secret]
+///         pub database_url: String,
+///     }
+/// }
+///
+/// fn App() -> Element {
+///     let config = use_config::<AppConfig>();
+///     rsx! { "Loading from {config.api_url}" }
+/// }
+/// ```
+///
+/// Build with `dx build --env <name>` to select the `[env.<name>]` table. It defaults to `release` for release
+/// builds and `dev` otherwise.
+#[proc_macro]
+pub fn dioxus_config(input: TokenStream) -> TokenStream {
+    match syn::parse::<app_config::ConfigStruct>(input) {
+        Ok(config) => quote! { #config }.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
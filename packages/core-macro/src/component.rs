@@ -6,13 +6,18 @@ use syn::*;
 
 pub struct ComponentBody {
     pub item_fn: ItemFn,
+    /// Extra attributes to attach to the generated props struct
+    pub props_attrs: Vec<Attribute>,
 }
 
 impl Parse for ComponentBody {
     fn parse(input: ParseStream) -> Result<Self> {
         let item_fn: ItemFn = input.parse()?;
         validate_component_fn(&item_fn)?;
-        Ok(Self { item_fn })
+        Ok(Self {
+            item_fn,
+            props_attrs: Vec::new(),
+        })
     }
 }
 
@@ -124,6 +129,7 @@ impl ComponentBody {
     /// props struct fields.
     fn props_struct(&self) -> ItemStruct {
         let ItemFn { vis, sig, .. } = &self.item_fn;
+        let props_attrs = &self.props_attrs;
         let Signature {
             inputs,
             ident,
@@ -136,6 +142,7 @@ impl ComponentBody {
 
         parse_quote! {
             #[derive(Props, Clone, PartialEq)]
+            #(#props_attrs)*
             #[allow(non_camel_case_types)]
             #vis struct #struct_ident #generics {
                 #(#struct_fields),*
@@ -214,7 +221,7 @@ impl ComponentBody {
         props_docs
     }
 
-    fn is_explicit_props_ident(&self) -> bool {
+    pub(crate) fn is_explicit_props_ident(&self) -> bool {
         if let Some(FnArg::Typed(PatType { pat, .. })) = self.item_fn.sig.inputs.first() {
             if let Pat::Ident(ident) = pat.as_ref() {
                 return ident.ident == "props";
@@ -224,7 +231,7 @@ impl ComponentBody {
         false
     }
 
-    fn has_struct_parameter_pattern(&self) -> bool {
+    pub(crate) fn has_struct_parameter_pattern(&self) -> bool {
         if let Some(FnArg::Typed(PatType { pat, .. })) = self.item_fn.sig.inputs.first() {
             if matches!(pat.as_ref(), Pat::Struct(_)) {
                 return true;
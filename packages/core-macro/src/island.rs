@@ -0,0 +1,201 @@
+use crate::component::ComponentBody;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::*;
+
+/// The arguments to the island macro like `#[island(hydrate = "visible")]`
+#[derive(Default)]
+pub struct IslandArgs {
+    hydrate: Option<Ident>,
+}
+
+impl Parse for IslandArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = Self::default();
+        while !input.is_empty() {
+            let name: Ident = input.parse()?;
+            if name != "hydrate" {
+                return Err(Error::new(
+                    name.span(),
+                    format!("Unknown island argument `{name}`. Expected `hydrate`"),
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            let value: LitStr = input.parse()?;
+            let strategy = match value.value().as_str() {
+                "load" => "Load",
+                "visible" => "Visible",
+                "idle" => "Idle",
+                "interaction" => "Interaction",
+                _ => {
+                    return Err(Error::new(
+                        value.span(),
+                        "Unknown hydration strategy. Expected one of \"load\", \"visible\", \"idle\" or \"interaction\"",
+                    ))
+                }
+            };
+            args.hydrate = Some(Ident::new(strategy, value.span()));
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// A component that is rendered as static html on the server and hydrated independently on the client
+pub struct IslandBody {
+    component: ComponentBody,
+    args: IslandArgs,
+}
+
+impl IslandBody {
+    pub fn new(args: IslandArgs, component: ComponentBody) -> Result<Self> {
+        validate_island_fn(&component)?;
+        Ok(Self { component, args })
+    }
+}
+
+impl ToTokens for IslandBody {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ComponentBody { item_fn, .. } = &self.component;
+        let fn_ident = &item_fn.sig.ident;
+        let name = fn_ident.to_string();
+        let block = &item_fn.block;
+
+        // Rebuild the props from the arguments so they can be serialized and sent to the client
+        let props = if item_fn.sig.inputs.is_empty() {
+            quote! { () }
+        } else {
+            let struct_ident = Ident::new(&format!("{fn_ident}Props"), fn_ident.span());
+            let fields = item_fn.sig.inputs.iter().map(|arg| {
+                let FnArg::Typed(pt) = arg else {
+                    unreachable!()
+                };
+                let Pat::Ident(pat) = pt.pat.as_ref() else {
+                    unreachable!()
+                };
+                let ident = &pat.ident;
+                quote! { #ident: ::std::clone::Clone::clone(&#ident) }
+            });
+            quote! { #struct_ident { #(#fields),* } }
+        };
+
+        let strategy = self
+            .args
+            .hydrate
+            .clone()
+            .unwrap_or_else(|| Ident::new("Load", fn_ident.span()));
+
+        let mut item_fn = item_fn.clone();
+        item_fn.block = parse_quote! {
+            {
+                dioxus_fullstack::islands::island(
+                    #name,
+                    dioxus_fullstack::islands::HydrationStrategy::#strategy,
+                    #props,
+                    #fn_ident,
+                    move || #block
+                )
+            }
+        };
+
+        let component = ComponentBody {
+            item_fn,
+            props_attrs: vec![
+                parse_quote! {
+                    #[derive(
+                        dioxus_fullstack::islands::__serde::Serialize,
+                        dioxus_fullstack::islands::__serde::Deserialize
+                    )]
+                },
+                parse_quote! { #[serde(crate = "dioxus_fullstack::islands::__serde")] },
+            ],
+        };
+
+        tokens.append_all(component.into_token_stream());
+    }
+}
+
+fn validate_island_fn(component: &ComponentBody) -> Result<()> {
+    let item_fn = &component.item_fn;
+
+    // The island is rendered in its own virtual dom on the client. We need to know the concrete props type to deserialize it
+    if !item_fn.sig.generics.params.is_empty() {
+        return Err(Error::new(
+            item_fn.sig.generics.span(),
+            "Generic islands are not supported".to_string(),
+        ));
+    }
+
+    if component.is_explicit_props_ident() || component.has_struct_parameter_pattern() {
+        return Err(Error::new(
+            item_fn.sig.inputs.span(),
+            "Islands must take their props as individual arguments".to_string(),
+        ));
+    }
+
+    for arg in &item_fn.sig.inputs {
+        if let FnArg::Typed(pt) = arg {
+            if !matches!(pt.pat.as_ref(), Pat::Ident(_)) {
+                return Err(Error::new(
+                    pt.pat.span(),
+                    "Island arguments must be simple identifiers".to_string(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_hydration_strategy() {
+        let args: IslandArgs = parse_quote! { hydrate = "visible" };
+        assert_eq!(args.hydrate.unwrap(), "Visible");
+        let args: IslandArgs = parse_str("").unwrap();
+        assert!(args.hydrate.is_none());
+
+        assert!(parse_str::<IslandArgs>(r#"hydrate = "never""#).is_err());
+        assert!(parse_str::<IslandArgs>(r#"strategy = "load""#).is_err());
+    }
+
+    #[test]
+    fn rejects_islands_without_serializable_props() {
+        let generic: ComponentBody = parse_quote! {
+            fn Generic<T: Clone + PartialEq + 'static>(value: T) -> Element { None }
+        };
+        assert!(IslandBody::new(IslandArgs::default(), generic).is_err());
+
+        let explicit_props: ComponentBody = parse_quote! {
+            fn Explicit(props: ExplicitProps) -> Element { None }
+        };
+        assert!(IslandBody::new(IslandArgs::default(), explicit_props).is_err());
+    }
+
+    #[test]
+    fn derives_serde_through_fullstack() {
+        let component: ComponentBody = parse_quote! {
+            fn Counter(initial: i32) -> Element { None }
+        };
+        let island = IslandBody::new(parse_quote! { hydrate = "idle" }, component).unwrap();
+        let tokens = island.into_token_stream().to_string();
+
+        assert!(tokens.contains("HydrationStrategy :: Idle"), "{tokens}");
+        assert!(
+            tokens.contains("dioxus_fullstack :: islands :: __serde :: Serialize"),
+            "{tokens}"
+        );
+        assert!(
+            tokens.contains(r#"serde (crate = "dioxus_fullstack::islands::__serde")"#),
+            "{tokens}"
+        );
+    }
+}
@@ -3,11 +3,13 @@
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 
 use component::ComponentBody;
+use island::{IslandArgs, IslandBody};
 use proc_macro::TokenStream;
 use quote::ToTokens;
 use syn::parse_macro_input;
 
 mod component;
+mod island;
 mod props;
 mod utils;
 
@@ -39,3 +41,14 @@ pub fn component(_args: TokenStream, input: TokenStream) -> TokenStream {
         .into_token_stream()
         .into()
 }
+
+#[doc = include_str!("../docs/island.md")]
+#[proc_macro_attribute]
+pub fn island(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as IslandArgs);
+    let component = parse_macro_input!(input as ComponentBody);
+    match IslandBody::new(args, component) {
+        Ok(island) => island.into_token_stream().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -748,6 +748,42 @@ mod struct_info {
             }
         }
 
+        /// Format every field for devtools. Fields that don't implement Debug are shown with the name of their type
+        fn inspect_impl(&self) -> TokenStream {
+            let fields = self.included_fields().map(|f| {
+                let name = f.name;
+                quote! {
+                    dioxus_core::prelude::InspectedValue::new(stringify!(#name), (&Inspect(&self.#name)).inspect_value())
+                }
+            });
+
+            quote! {
+                struct Inspect<'a, T>(&'a T);
+
+                trait InspectAny {
+                    fn inspect_value(&self) -> String;
+                }
+
+                impl<T> InspectAny for &Inspect<'_, T> {
+                    fn inspect_value(&self) -> String {
+                        ::std::any::type_name::<T>().to_string()
+                    }
+                }
+
+                trait InspectDebug {
+                    fn inspect_value(&self) -> String;
+                }
+
+                impl<T: ::std::fmt::Debug> InspectDebug for Inspect<'_, T> {
+                    fn inspect_value(&self) -> String {
+                        format!("{:?}", self.0)
+                    }
+                }
+
+                vec![#(#fields),*]
+            }
+        }
+
         pub fn builder_creation_impl(&self) -> Result<TokenStream, Error> {
             let StructInfo {
                 ref vis,
@@ -836,6 +872,7 @@ Finally, call `.build()` to create the instance of `{name}`.
             }
 
             let memoize = self.memoize_impl()?;
+            let inspect = self.inspect_impl();
 
             let global_fields = self
                 .extend_fields()
@@ -888,6 +925,9 @@ Finally, call `.build()` to create the instance of `{name}`.
                     fn memoize(&mut self, new: &Self) -> bool {
                         #memoize
                     }
+                    fn inspect(&self) -> ::std::vec::Vec<dioxus_core::prelude::InspectedValue> {
+                        #inspect
+                    }
                 }
             })
         }
@@ -1479,6 +1519,9 @@ Finally, call `.build()` to create the instance of `{name}`.
                         fn memoize(&mut self, new: &Self) -> bool {
                             self.inner.memoize(&new.inner)
                         }
+                        fn inspect(&self) -> ::std::vec::Vec<dioxus_core::prelude::InspectedValue> {
+                            self.inner.inspect()
+                        }
                     }
 
                     #[allow(dead_code, non_camel_case_types, missing_docs)]
@@ -2,8 +2,10 @@ pub mod bubbles;
 pub mod bundled;
 pub mod formatter;
 pub mod hr_context;
+pub mod listener;
 
 pub use bubbles::*;
 pub use bundled::*;
 pub use formatter::*;
 pub use hr_context::*;
+pub use listener::*;
@@ -0,0 +1,108 @@
+/// The parts of the name of a listener attribute. Listeners are named `on{event}`, followed by these optional
+/// suffixes in order:
+/// - `capture` for listeners that run during the capture phase
+/// - `passive` for listeners that never prevent the default action of the event
+/// - `direct` for listeners that are attached to their element instead of the delegated listener at the root
+///
+/// For example `onclickcapture`, `onscrollpassive` or `onfocusdirect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListenerName<'a> {
+    /// The name of the event without the `on` prefix
+    pub event: &'a str,
+    /// If the listener runs during the capture phase
+    pub capture: bool,
+    /// How the renderer registers the listener
+    pub options: ListenerOptions,
+}
+
+/// How renderers register a listener with the platform
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ListenerOptions {
+    /// If the listener never prevents the default action of the event
+    pub passive: bool,
+    /// If the listener is attached directly to its element instead of the delegated listener at the root of the app.
+    /// Direct listeners see events that never reach the root, like events inside shadow roots.
+    pub direct: bool,
+}
+
+impl ListenerOptions {
+    /// Combine two sets of options. The listener is passive or direct if either set asks for it
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            passive: self.passive || other.passive,
+            direct: self.direct || other.direct,
+        }
+    }
+}
+
+impl<'a> ListenerName<'a> {
+    /// Split the name of a listener attribute into its parts
+    pub fn parse(attribute: &'a str) -> Self {
+        let name = attribute.strip_prefix("on").unwrap_or(attribute);
+        let (name, direct) = match name.strip_suffix("direct") {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        let (name, passive) = match name.strip_suffix("passive") {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        let (event, capture) = match name.strip_suffix("capture") {
+            // These events end with "capture" but are not capture listeners
+            Some(_) if matches!(name, "gotpointercapture" | "lostpointercapture") => (name, false),
+            Some(event) => (event, true),
+            None => (name, false),
+        };
+        Self {
+            event,
+            capture,
+            options: ListenerOptions { passive, direct },
+        }
+    }
+
+    /// The flags renderers pass to the interpreter when they register the listener. See [`listener_flags`]
+    pub fn interpreter_flags(&self) -> u8 {
+        listener_flags(self.event, self.options)
+    }
+}
+
+/// The flags renderers pass to the interpreter when they register a listener for the event: bit 0 is set if the
+/// event bubbles, bit 1 is set if the listener is passive and bit 2 is set if the listener is direct
+pub fn listener_flags(event: &str, options: ListenerOptions) -> u8 {
+    crate::event_bubbles(event) as u8 | (options.passive as u8) << 1 | (options.direct as u8) << 2
+}
+
+#[test]
+fn parses_listener_names() {
+    let parse = ListenerName::parse;
+    let listener = |event, capture, passive, direct| ListenerName {
+        event,
+        capture,
+        options: ListenerOptions { passive, direct },
+    };
+    assert_eq!(parse("onclick"), listener("click", false, false, false));
+    assert_eq!(
+        parse("onclickcapture"),
+        listener("click", true, false, false)
+    );
+    assert_eq!(
+        parse("onscrollcapturepassive"),
+        listener("scroll", true, true, false)
+    );
+    assert_eq!(
+        parse("onfocusdirect"),
+        listener("focus", false, false, true)
+    );
+    assert_eq!(
+        parse("ontouchmovecapturepassivedirect"),
+        listener("touchmove", true, true, true)
+    );
+    assert_eq!(
+        parse("ongotpointercapture"),
+        listener("gotpointercapture", false, false, false)
+    );
+    assert_eq!(
+        parse("onlostpointercapturecapture"),
+        listener("lostpointercapture", true, false, false)
+    );
+}
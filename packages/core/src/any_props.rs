@@ -1,4 +1,7 @@
-use crate::{innerlude::CapturedPanic, ComponentFunction, Element};
+use crate::{
+    innerlude::{CapturedPanic, InspectedValue},
+    ComponentFunction, Element,
+};
 use std::{any::Any, panic::AssertUnwindSafe};
 
 pub(crate) type BoxedAnyProps = Box<dyn AnyProps>;
@@ -9,6 +12,8 @@ pub(crate) trait AnyProps: 'static {
     fn render(&self) -> Element;
     /// Make the old props equal to the new type erased props. Return if the props were equal and should be memoized.
     fn memoize(&mut self, other: &dyn Any) -> bool;
+    /// Get the debug representation of the props for devtools.
+    fn inspect(&self) -> Vec<InspectedValue>;
     /// Get the props as a type erased `dyn Any`.
     fn props(&self) -> &dyn Any;
     /// Get the props as a type erased `dyn Any`.
@@ -21,6 +26,7 @@ pub(crate) trait AnyProps: 'static {
 pub(crate) struct VProps<F: ComponentFunction<P, M>, P, M> {
     render_fn: F,
     memo: fn(&mut P, &P) -> bool,
+    inspect: fn(&P) -> Vec<InspectedValue>,
     props: P,
     name: &'static str,
     phantom: std::marker::PhantomData<M>,
@@ -31,6 +37,7 @@ impl<F: ComponentFunction<P, M>, P: Clone, M> Clone for VProps<F, P, M> {
         Self {
             render_fn: self.render_fn.clone(),
             memo: self.memo,
+            inspect: self.inspect,
             props: self.props.clone(),
             name: self.name,
             phantom: std::marker::PhantomData,
@@ -43,12 +50,14 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> VProps<
     pub fn new(
         render_fn: F,
         memo: fn(&mut P, &P) -> bool,
+        inspect: fn(&P) -> Vec<InspectedValue>,
         props: P,
         name: &'static str,
     ) -> VProps<F, P, M> {
         VProps {
             render_fn,
             memo,
+            inspect,
             props,
             name,
             phantom: std::marker::PhantomData,
@@ -66,6 +75,10 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> AnyProp
         }
     }
 
+    fn inspect(&self) -> Vec<InspectedValue> {
+        (self.inspect)(&self.props)
+    }
+
     fn props(&self) -> &dyn Any {
         &self.props
     }
@@ -94,6 +107,7 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> AnyProp
         Box::new(Self {
             render_fn: self.render_fn.clone(),
             memo: self.memo,
+            inspect: self.inspect,
             props: self.props.clone(),
             name: self.name,
             phantom: std::marker::PhantomData,
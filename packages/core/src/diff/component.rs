@@ -6,8 +6,8 @@ use std::{
 use crate::{
     any_props::AnyProps,
     innerlude::{
-        ElementRef, MountId, ScopeOrder, SuspenseBoundaryProps, SuspenseBoundaryPropsWithOwner,
-        VComponent, WriteMutations,
+        ElementRef, MountId, ProfileSpanKind, ScopeOrder, SuspenseBoundaryProps,
+        SuspenseBoundaryPropsWithOwner, VComponent, WriteMutations,
     },
     nodes::{AsVNode, VNode},
     prelude::SuspenseContext,
@@ -38,27 +38,30 @@ impl VirtualDom {
         scope: ScopeId,
         new_nodes: Element,
     ) {
-        self.runtime.clone().with_scope_on_stack(scope, || {
-            // We don't diff the nodes if the scope is suspended or has an error
-            let Ok(new_real_nodes) = &new_nodes else {
-                return;
-            };
-            let scope_state = &mut self.scopes[scope.0];
-            // Load the old and new rendered nodes
-            let old = scope_state.last_rendered_node.take().unwrap();
-
-            // If there are suspended scopes, we need to check if the scope is suspended before we diff it
-            // If it is suspended, we need to diff it but write the mutations nothing
-            // Note: It is important that we still diff the scope even if it is suspended, because the scope may render other child components which may change between renders
-            let mut render_to = to.filter(|_| self.runtime.scope_should_render(scope));
-            old.as_vnode()
-                .diff_node(new_real_nodes, self, render_to.as_deref_mut());
-
-            self.scopes[scope.0].last_rendered_node = Some(new_nodes);
-
-            if render_to.is_some() {
-                self.runtime.get_state(scope).unwrap().mount(&self.runtime);
-            }
+        let runtime = self.runtime.clone();
+        runtime.profile(ProfileSpanKind::Diff, scope, || {
+            runtime.with_scope_on_stack(scope, || {
+                // We don't diff the nodes if the scope is suspended or has an error
+                let Ok(new_real_nodes) = &new_nodes else {
+                    return;
+                };
+                let scope_state = &mut self.scopes[scope.0];
+                // Load the old and new rendered nodes
+                let old = scope_state.last_rendered_node.take().unwrap();
+
+                // If there are suspended scopes, we need to check if the scope is suspended before we diff it
+                // If it is suspended, we need to diff it but write the mutations nothing
+                // Note: It is important that we still diff the scope even if it is suspended, because the scope may render other child components which may change between renders
+                let mut render_to = to.filter(|_| self.runtime.scope_should_render(scope));
+                old.as_vnode()
+                    .diff_node(new_real_nodes, self, render_to.as_deref_mut());
+
+                self.scopes[scope.0].last_rendered_node = Some(new_nodes);
+
+                if render_to.is_some() {
+                    self.runtime.get_state(scope).unwrap().mount(&self.runtime);
+                }
+            })
         })
     }
 
@@ -73,25 +76,28 @@ impl VirtualDom {
         new_nodes: Element,
         parent: Option<ElementRef>,
     ) -> usize {
-        self.runtime.clone().with_scope_on_stack(scope, || {
-            // If there are suspended scopes, we need to check if the scope is suspended before we diff it
-            // If it is suspended, we need to diff it but write the mutations nothing
-            // Note: It is important that we still diff the scope even if it is suspended, because the scope may render other child components which may change between renders
-            let mut render_to = to.filter(|_| self.runtime.scope_should_render(scope));
-
-            // Create the node
-            let nodes = new_nodes
-                .as_vnode()
-                .create(self, parent, render_to.as_deref_mut());
-
-            // Then set the new node as the last rendered node
-            self.scopes[scope.0].last_rendered_node = Some(new_nodes);
-
-            if render_to.is_some() {
-                self.runtime.get_state(scope).unwrap().mount(&self.runtime);
-            }
-
-            nodes
+        let runtime = self.runtime.clone();
+        runtime.profile(ProfileSpanKind::Diff, scope, || {
+            runtime.with_scope_on_stack(scope, || {
+                // If there are suspended scopes, we need to check if the scope is suspended before we diff it
+                // If it is suspended, we need to diff it but write the mutations nothing
+                // Note: It is important that we still diff the scope even if it is suspended, because the scope may render other child components which may change between renders
+                let mut render_to = to.filter(|_| self.runtime.scope_should_render(scope));
+
+                // Create the node
+                let nodes = new_nodes
+                    .as_vnode()
+                    .create(self, parent, render_to.as_deref_mut());
+
+                // Then set the new node as the last rendered node
+                self.scopes[scope.0].last_rendered_node = Some(new_nodes);
+
+                if render_to.is_some() {
+                    self.runtime.get_state(scope).unwrap().mount(&self.runtime);
+                }
+
+                nodes
+            })
         })
     }
 
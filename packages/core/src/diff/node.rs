@@ -2,6 +2,7 @@ use crate::innerlude::MountId;
 use crate::{Attribute, AttributeValue, DynamicNode::*};
 use crate::{VNode, VirtualDom, WriteMutations};
 use core::iter::Peekable;
+use dioxus_core_types::ListenerName;
 
 use crate::{
     arena::ElementId,
@@ -85,6 +86,11 @@ impl VNode {
         mut to: Option<&mut impl WriteMutations>,
     ) {
         tracing::trace!("diffing dynamic node from {old_node:?} to {new_node:?}");
+        // Suspended nodes are diffed without writing mutations, so nothing moved in the renderer
+        if to.is_some() {
+            dom.runtime
+                .report_dynamic_node_transitions(old_node, new_node);
+        }
         match (old_node, new_node) {
             (Text(old), Text(new)) => {
                 // Diffing text is just a side effect, if we are diffing suspended nodes and are not outputting mutations, we can skip it
@@ -496,8 +502,10 @@ impl VNode {
 
     fn remove_attribute(&self, attribute: &Attribute, id: ElementId, to: &mut impl WriteMutations) {
         match &attribute.value {
-            AttributeValue::Listener(_) => {
-                to.remove_event_listener(&attribute.name[2..], id);
+            AttributeValue::Listener(callback) => {
+                let listener = ListenerName::parse(attribute.name);
+                let options = listener.options.union(callback.listener_options());
+                to.remove_event_listener_with_options(listener.event, id, options);
             }
             _ => {
                 to.set_attribute(
@@ -520,14 +528,17 @@ impl VNode {
         to: &mut impl WriteMutations,
     ) {
         match &attribute.value {
-            AttributeValue::Listener(_) => {
+            AttributeValue::Listener(callback) => {
                 let element_ref = ElementRef {
                     path: ElementPath { path },
                     mount,
                 };
                 let mut elements = dom.runtime.elements.borrow_mut();
                 elements[id.0] = Some(element_ref);
-                to.create_event_listener(&attribute.name[2..], id);
+                // Options can come from the suffixes of the name or from the callback itself
+                let listener = ListenerName::parse(attribute.name);
+                let options = listener.options.union(callback.listener_options());
+                to.create_event_listener_with_options(listener.event, id, options);
             }
             _ => {
                 to.set_attribute(attribute.name, attribute.namespace, &attribute.value, id);
@@ -1,6 +1,8 @@
 use crate::{
-    global_context::current_scope_id, innerlude::provide_context, use_hook, Element, IntoDynNode,
-    Properties, ScopeId, Template, TemplateAttribute, TemplateNode, VNode,
+    global_context::{current_scope_id, use_hook_with_cleanup},
+    innerlude::provide_context,
+    use_hook, Element, IntoDynNode, Properties, Runtime, ScopeId, Template, TemplateAttribute,
+    TemplateNode, VNode,
 };
 use std::{
     any::{Any, TypeId},
@@ -260,7 +262,16 @@ impl ErrorContext {
     }
 
     /// Push an error into this Error Boundary
-    pub fn insert_error(&self, error: CapturedError) {
+    pub fn insert_error(&self, mut error: CapturedError) {
+        if let std::result::Result::Ok(runtime) = Runtime::current() {
+            // Only the error boundary at the root of the app doesn't have another error boundary above it
+            error.unhandled = self
+                .id
+                .parent_scope()
+                .and_then(|parent| parent.consume_context::<ErrorContext>())
+                .is_none();
+            runtime.notify_error_listeners(&error);
+        }
         self.errors.borrow_mut().push(error);
         self.id.needs_update();
     }
@@ -269,6 +280,31 @@ impl ErrorContext {
     pub fn clear_errors(&self) {
         self.errors.borrow_mut().clear();
     }
+
+    /// Clear all errors from this Error Boundary and render the children again.
+    ///
+    /// The children that failed were unmounted when the error was shown, so they are mounted from scratch with fresh
+    /// state. If they fail again, the error will be caught by this boundary again.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// fn App() -> Element {
+    ///     rsx! {
+    ///         ErrorBoundary {
+    ///             handle_error: |errors: ErrorContext| rsx! {
+    ///                 "Failed to load the page"
+    ///                 button { onclick: move |_| errors.retry(), "Try again" }
+    ///             },
+    ///             Page {}
+    ///         }
+    ///     }
+    /// }
+    /// # fn Page() -> Element { todo!() }
+    /// ```
+    pub fn retry(&self) {
+        self.clear_errors();
+        self.id.needs_update();
+    }
 }
 
 /// Errors can have additional context added as they bubble up the render tree
@@ -334,6 +370,9 @@ pub struct CapturedError {
 
     /// Additional context that was added to the error
     context: Vec<Rc<AdditionalErrorContext>>,
+
+    /// If the error was not caught by any error boundary of the app
+    unhandled: bool,
 }
 
 impl FromStr for CapturedError {
@@ -395,6 +434,7 @@ impl<'de> serde::Deserialize<'de> for CapturedError {
             backtrace: Rc::new(Backtrace::disabled()),
             scope: ScopeId::ROOT,
             render: VNode::placeholder(),
+            unhandled: false,
         })
     }
 }
@@ -418,6 +458,7 @@ impl<E: AnyError + 'static> From<E> for CapturedError {
                 .expect("Cannot create an error boundary outside of a component's scope."),
             render: Default::default(),
             context: Default::default(),
+            unhandled: false,
         }
     }
 }
@@ -431,6 +472,7 @@ impl CapturedError {
             scope: current_scope_id().unwrap_or(ScopeId::ROOT),
             render: Default::default(),
             context: Default::default(),
+            unhandled: false,
         }
     }
 
@@ -442,6 +484,7 @@ impl CapturedError {
             scope: current_scope_id().unwrap_or(ScopeId::ROOT),
             render: Default::default(),
             context: Default::default(),
+            unhandled: false,
         }
     }
 
@@ -451,6 +494,22 @@ impl CapturedError {
         self
     }
 
+    /// The error that was thrown
+    pub fn error(&self) -> &dyn Error {
+        self.error.as_error()
+    }
+
+    /// The scope the error was thrown from
+    pub fn origin(&self) -> ScopeId {
+        self.scope
+    }
+
+    /// Check if the error was not caught by any error boundary in the app. Unhandled errors are shown by the default
+    /// error boundary at the root of the app.
+    pub fn is_unhandled(&self) -> bool {
+        self.unhandled
+    }
+
     /// Get a VNode representation of the error if the error provides one
     pub fn show(&self) -> Option<Element> {
         if self.render == VNode::placeholder() {
@@ -502,10 +561,14 @@ impl CapturedError {
 }
 
 pub(crate) fn throw_into(error: impl Into<CapturedError>, scope: ScopeId) {
-    let error = error.into();
+    let mut error = error.into();
     if let Some(cx) = scope.consume_context::<ErrorContext>() {
         cx.insert_error(error)
     } else {
+        error.unhandled = true;
+        if let std::result::Result::Ok(runtime) = Runtime::current() {
+            runtime.notify_error_listeners(&error);
+        }
         tracing::error!(
             "Tried to throw an error into an error boundary, but failed to locate a boundary: {:?}",
             error
@@ -513,6 +576,35 @@ pub(crate) fn throw_into(error: impl Into<CapturedError>, scope: ScopeId) {
     }
 }
 
+/// Listen to every error thrown in the virtual dom, even errors that are handled by an error boundary. This is useful
+/// for reporting errors to a logging or error tracking service.
+///
+/// The listener is removed when the component that called this hook is dropped. To listen to errors for the whole
+/// lifetime of the app, use [`VirtualDom::with_error_listener`](crate::VirtualDom::with_error_listener).
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn App() -> Element {
+///     use_error_listener(|error| {
+///         // Send the error to your error tracking service
+///         tracing::error!("Reporting error: {error}");
+///     });
+///
+///     rsx! { "Hello world" }
+/// }
+/// ```
+pub fn use_error_listener(listener: impl Fn(&CapturedError) + 'static) {
+    use_hook_with_cleanup(
+        || {
+            Runtime::with(|rt| rt.add_error_listener(Rc::new(listener)))
+                .unwrap_or_else(|e| panic!("{}", e))
+        },
+        |key| {
+            _ = Runtime::with(|rt| rt.remove_error_listener(key));
+        },
+    );
+}
+
 #[allow(clippy::type_complexity)]
 #[derive(Clone)]
 pub struct ErrorHandler(Rc<dyn Fn(ErrorContext) -> Element>);
@@ -1,4 +1,4 @@
-use crate::{properties::SuperFrom, runtime::RuntimeGuard, Runtime, ScopeId};
+use crate::{properties::SuperFrom, runtime::RuntimeGuard, ListenerOptions, Runtime, ScopeId};
 use generational_box::GenerationalBox;
 use std::{cell::RefCell, marker::PhantomData, panic::Location, rc::Rc};
 
@@ -28,6 +28,7 @@ pub struct Event<T: 'static + ?Sized> {
 #[derive(Clone, Copy)]
 pub(crate) struct EventMetadata {
     pub(crate) propagates: bool,
+    pub(crate) propagation_stopped: bool,
     pub(crate) prevent_default: bool,
 }
 
@@ -38,6 +39,7 @@ impl<T: ?Sized + 'static> Event<T> {
             data,
             metadata: Rc::new(RefCell::new(EventMetadata {
                 propagates,
+                propagation_stopped: false,
                 prevent_default: false,
             })),
         }
@@ -84,7 +86,7 @@ impl<T: ?Sized> Event<T> {
     /// ```
     #[deprecated = "use stop_propagation instead"]
     pub fn cancel_bubble(&self) {
-        self.metadata.borrow_mut().propagates = false;
+        self.stop_propagation();
     }
 
     /// Check if the event propagates up the tree to parent elements
@@ -92,7 +94,11 @@ impl<T: ?Sized> Event<T> {
         self.metadata.borrow().propagates
     }
 
-    /// Prevent this event from continuing to bubble up the tree to parent elements.
+    /// Stop this event from reaching any other element. Called from a capture listener, the event never reaches the
+    /// target. Called from a bubbling listener, the event doesn't bubble up the tree to parent elements.
+    ///
+    /// Every renderer stops the event the same way. Renderers that receive the event from a browser also stop the
+    /// propagation of the native event.
     ///
     /// # Example
     ///
@@ -107,7 +113,14 @@ impl<T: ?Sized> Event<T> {
     /// };
     /// ```
     pub fn stop_propagation(&self) {
-        self.metadata.borrow_mut().propagates = false;
+        let mut metadata = self.metadata.borrow_mut();
+        metadata.propagates = false;
+        metadata.propagation_stopped = true;
+    }
+
+    /// Check if a listener called [`Event::stop_propagation`] on this event
+    pub fn propagation_stopped(&self) -> bool {
+        self.metadata.borrow().propagation_stopped
     }
 
     /// Get a reference to the inner data from this event
@@ -270,6 +283,8 @@ pub struct Callback<Args = (), Ret = ()> {
     /// We double box here because we want the data to be copy (GenerationalBox) and still update in place (ExternalListenerCallback)
     /// This isn't an ideal solution for performance, but it is non-breaking and fixes the issues described in <https://github.com/DioxusLabs/dioxus/pull/2298>
     pub(super) callback: GenerationalBox<Option<ExternalListenerCallback<Args, Ret>>>,
+    /// How renderers register listeners created from this callback
+    pub(crate) listener_options: ListenerOptions,
 }
 
 impl<Args, Ret> std::fmt::Debug for Callback<Args, Ret> {
@@ -277,6 +292,7 @@ impl<Args, Ret> std::fmt::Debug for Callback<Args, Ret> {
         f.debug_struct("Callback")
             .field("origin", &self.origin)
             .field("callback", &self.callback)
+            .field("listener_options", &self.listener_options)
             .finish()
     }
 }
@@ -451,7 +467,11 @@ impl<Args: 'static, Ret: 'static> Callback<Args, Ret> {
             callback: Box::new(move |event: Args| f(event).spawn()),
             runtime: Rc::downgrade(&runtime),
         }));
-        Self { callback, origin }
+        Self {
+            callback,
+            origin,
+            listener_options: ListenerOptions::default(),
+        }
     }
 
     /// Leak a new [`Callback`] that will not be dropped unless it is manually dropped.
@@ -468,7 +488,11 @@ impl<Args: 'static, Ret: 'static> Callback<Args, Ret> {
             }),
             Location::caller(),
         );
-        Self { callback, origin }
+        Self {
+            callback,
+            origin,
+            listener_options: ListenerOptions::default(),
+        }
     }
 
     /// Leak a new reference to the [`Callback`] that will not be dropped unless the callback is dropped manually
@@ -476,6 +500,7 @@ impl<Args: 'static, Ret: 'static> Callback<Args, Ret> {
         Ok(Callback {
             callback: self.callback.leak_reference()?,
             origin: self.origin,
+            listener_options: self.listener_options,
         })
     }
 
@@ -496,6 +521,12 @@ impl<Args: 'static, Ret: 'static> Callback<Args, Ret> {
         }
     }
 
+    /// Get the options renderers use to register listeners created from this callback. Set the options with the
+    /// methods of [`EventHandlerExt`](crate::prelude::EventHandlerExt).
+    pub fn listener_options(&self) -> ListenerOptions {
+        self.listener_options
+    }
+
     /// Create a `impl FnMut + Copy` closure from the Closure type
     pub fn into_closure(self) -> impl FnMut(Args) -> Ret + Copy + 'static {
         move |args| self.call(args)
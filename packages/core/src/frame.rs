@@ -0,0 +1,227 @@
+use crate::{
+    innerlude::{spawn, ScopeId},
+    now, sleep, use_hook, Runtime, Task,
+};
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+/// The time between frames of the [`IntervalFrames`] frame source
+pub const DEFAULT_FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+/// The source of the frames [`next_frame`], [`use_raf`] and the frame callbacks are synchronized with.
+///
+/// Renderers set a frame source that follows their paint loop. On the web, frames come from `requestAnimationFrame`,
+/// so they pause while the page is hidden. Renderers without a paint loop of their own use [`IntervalFrames`].
+pub trait FrameSource {
+    /// Create a future that resolves right before the renderer paints its next frame
+    fn next_frame(&self) -> Pin<Box<dyn Future<Output = ()>>>;
+}
+
+/// A [`FrameSource`] that produces a frame every [`DEFAULT_FRAME_INTERVAL`] on the [`Clock`](crate::Clock) of the
+/// virtual dom. Tests that use a [`MockClock`](crate::MockClock) can advance the clock to produce frames.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IntervalFrames;
+
+impl FrameSource for IntervalFrames {
+    fn next_frame(&self) -> Pin<Box<dyn Future<Output = ()>>> {
+        Box::pin(sleep(DEFAULT_FRAME_INTERVAL))
+    }
+}
+
+type FrameCallback = (Option<ScopeId>, Box<dyn FnOnce()>);
+
+/// The frame that is requested from the frame source and the work that runs in it
+pub(crate) struct Frames {
+    source: RefCell<Rc<dyn FrameSource>>,
+    /// The number of frames that ran so far
+    count: Cell<u64>,
+    requested: Cell<bool>,
+    wakers: RefCell<Vec<Waker>>,
+    reads: RefCell<Vec<FrameCallback>>,
+    writes: RefCell<Vec<FrameCallback>>,
+}
+
+impl Default for Frames {
+    fn default() -> Self {
+        Self {
+            source: RefCell::new(Rc::new(IntervalFrames)),
+            count: Cell::new(0),
+            requested: Cell::new(false),
+            wakers: Default::default(),
+            reads: Default::default(),
+            writes: Default::default(),
+        }
+    }
+}
+
+impl Runtime {
+    /// Replace the source of the frames that frame callbacks run in. Renderers call this with a source that follows
+    /// their paint loop.
+    pub fn set_frame_source(&self, source: Rc<dyn FrameSource>) {
+        *self.frames.source.borrow_mut() = source;
+    }
+
+    /// Ask the frame source for the next frame if it wasn't requested already
+    fn request_frame(&self) {
+        if self.frames.requested.replace(true) {
+            return;
+        }
+        let frame = self.frames.source.borrow().next_frame();
+        self.spawn(ScopeId::ROOT, async move {
+            frame.await;
+            if let Ok(runtime) = Runtime::current() {
+                runtime.run_frame();
+            }
+        });
+    }
+
+    /// Run the layout reads and then the writes that were scheduled for this frame, and resume the futures waiting
+    /// for it. Work scheduled while the frame runs is moved to the next frame.
+    fn run_frame(self: &Rc<Self>) {
+        self.frames.requested.set(false);
+        self.frames.count.set(self.frames.count.get() + 1);
+
+        let reads = std::mem::take(&mut *self.frames.reads.borrow_mut());
+        let writes = std::mem::take(&mut *self.frames.writes.borrow_mut());
+        for (scope, callback) in reads.into_iter().chain(writes) {
+            match scope {
+                // Skip the work of components that were dropped before the frame
+                Some(scope) if self.get_state(scope).is_none() => {}
+                Some(scope) => self.on_scope(scope, callback),
+                None => callback(),
+            }
+        }
+
+        for waker in std::mem::take(&mut *self.frames.wakers.borrow_mut()) {
+            waker.wake();
+        }
+    }
+
+    fn schedule_frame_callback(&self, read: bool, callback: Box<dyn FnOnce()>) {
+        let scope = self.current_scope_id().ok();
+        let queue = match read {
+            true => &self.frames.reads,
+            false => &self.frames.writes,
+        };
+        queue.borrow_mut().push((scope, callback));
+        self.request_frame();
+    }
+}
+
+/// Run a callback in the next frame, right before the renderer paints. Use this to write to the layout of elements,
+/// like changing their size or position.
+///
+/// Callbacks scheduled with [`schedule_layout_read`] run first, so all measurements in a frame happen before all
+/// writes and the browser only computes the layout once. Callbacks that are scheduled while a frame runs run in the
+/// next frame. If the component that scheduled the callback is dropped before the frame, the callback is skipped.
+///
+/// Outside of a virtual dom, the callback runs immediately.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_core::{schedule_before_paint, schedule_layout_read};
+/// fn Accordion() -> Element {
+///     let mut expanded = use_signal(|| false);
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| {
+///                 // Runs second, even though it was scheduled first
+///                 schedule_before_paint(move || expanded.set(true));
+///                 // Runs first, before any writes in the frame
+///                 schedule_layout_read(move || tracing::info!("measure the panel here"));
+///             },
+///             "Expand"
+///         }
+///     }
+/// }
+/// ```
+pub fn schedule_before_paint(callback: impl FnOnce() + 'static) {
+    match Runtime::current() {
+        Ok(runtime) => runtime.schedule_frame_callback(false, Box::new(callback)),
+        Err(_) => callback(),
+    }
+}
+
+/// Run a callback in the next frame, before the callbacks scheduled with [`schedule_before_paint`]. Use this to
+/// read the layout of elements, like their size or position, without forcing the browser to compute the layout
+/// again after every write.
+///
+/// Outside of a virtual dom, the callback runs immediately.
+pub fn schedule_layout_read(callback: impl FnOnce() + 'static) {
+    match Runtime::current() {
+        Ok(runtime) => runtime.schedule_frame_callback(true, Box::new(callback)),
+        Err(_) => callback(),
+    }
+}
+
+/// Wait until the next frame of the renderer. The future resolves after the frame callbacks of that frame ran.
+///
+/// Outside of a virtual dom, the future resolves immediately.
+pub fn next_frame() -> NextFrame {
+    NextFrame {
+        frame: Runtime::current()
+            .ok()
+            .map(|runtime| runtime.frames.count.get()),
+    }
+}
+
+/// A future returned by [`next_frame`] that resolves in the next frame of the renderer
+pub struct NextFrame {
+    /// The number of frames that had run when the future was created
+    frame: Option<u64>,
+}
+
+impl Future for NextFrame {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let (Some(frame), Ok(runtime)) = (self.frame, Runtime::current()) else {
+            return Poll::Ready(());
+        };
+        if runtime.frames.count.get() > frame {
+            return Poll::Ready(());
+        }
+        runtime.frames.wakers.borrow_mut().push(cx.waker().clone());
+        runtime.request_frame();
+        Poll::Pending
+    }
+}
+
+/// Run a callback on every frame of the renderer while the component is mounted. The callback receives the time since
+/// the last frame.
+///
+/// The returned task can be paused and resumed to stop and restart the callback.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_core::use_raf;
+/// fn Spinner() -> Element {
+///     let mut angle = use_signal(|| 0.0);
+///     use_raf(move |delta| angle += delta.as_secs_f64() * 180.0);
+///
+///     rsx! {
+///         div { style: "transform: rotate({angle}deg)" }
+///     }
+/// }
+/// ```
+pub fn use_raf(callback: impl FnMut(Duration) + 'static) -> Task {
+    use_hook(|| {
+        let mut callback = callback;
+        spawn(async move {
+            let mut last_frame = now();
+            loop {
+                next_frame().await;
+                let frame = now();
+                callback(frame.saturating_duration_since(last_frame));
+                last_frame = frame;
+            }
+        })
+    })
+}
@@ -11,6 +11,16 @@ pub fn current_scope_id() -> Result<ScopeId, RuntimeError> {
         .ok_or(RuntimeError::new())
 }
 
+/// Get the names of the components from the component that is running up to the root of the app. This is empty
+/// outside of a component.
+///
+/// This is useful to report where a panic happened from a panic hook.
+pub fn component_stack() -> Vec<&'static str> {
+    current_scope_id()
+        .map(ScopeId::component_stack)
+        .unwrap_or_default()
+}
+
 #[doc(hidden)]
 /// Check if the virtual dom is currently inside of the body of a component
 pub fn vdom_is_rendering() -> bool {
@@ -471,3 +481,38 @@ pub fn use_hook_with_cleanup<T: Clone + 'static>(
     use_drop(move || cleanup(_value));
     value
 }
+
+/// Get an id that is unique in the virtual dom and stays the same for the lifetime of the component.
+///
+/// The id is based on the position of the component in the tree, so the server and the client create the same id
+/// while the client hydrates the html the server rendered. Use it to connect elements with attributes like `for`,
+/// `aria-describedby` or `aria-controls` instead of random ids, which cause hydration mismatches. Every call to
+/// `use_id` in a component returns a different id.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[component]
+/// fn TextField(label: String) -> Element {
+///     let id = use_id();
+///     let hint = use_id();
+///
+///     rsx! {
+///         label { r#for: "{id}", "{label}" }
+///         input { id: "{id}", aria_describedby: "{hint}" }
+///         p { id: "{hint}", "At least 8 characters" }
+///     }
+/// }
+/// ```
+pub fn use_id() -> String {
+    use_hook(|| {
+        Runtime::with_current_scope(|cx| {
+            let index = cx.ids_created.get();
+            cx.ids_created.set(index + 1);
+            match cx.tree_path.is_empty() {
+                true => format!("dx-{index}"),
+                false => format!("dx-{}-{index}", cx.tree_path),
+            }
+        })
+        .unwrap()
+    })
+}
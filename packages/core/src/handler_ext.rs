@@ -0,0 +1,244 @@
+use crate::{
+    innerlude::{now, sleep, spawn},
+    prelude::{Callback, SuperFrom},
+    ListenerOptions, SpawnIfAsync, Task,
+};
+use std::{cell::RefCell, marker::PhantomData, rc::Rc, time::Duration};
+use web_time::Instant;
+
+/// An event handler with extra options for how the listener is registered and called. Create one with the methods
+/// of [`EventHandlerExt`].
+pub struct HandlerOptions<F> {
+    handler: F,
+    listener_options: ListenerOptions,
+    rate_limit: RateLimit,
+}
+
+#[derive(Clone, Copy)]
+enum RateLimit {
+    None,
+    Throttle(Duration),
+    Debounce(Duration),
+}
+
+/// Declarative options for event handlers.
+///
+/// Events like `scroll`, `pointermove` and `touchmove` fire many times a frame. Every event is sent to the virtual
+/// dom, which is expensive on renderers that serialize events across a bridge. These options let the handler opt out
+/// of the work it doesn't need:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use std::time::Duration;
+/// fn App() -> Element {
+///     let mut position = use_signal(Default::default);
+///     let on_move = move |event: MouseEvent| position.set(event.client_coordinates());
+///
+///     rsx! {
+///         div {
+///             // The handler runs at most once every 16ms and never blocks the browser
+///             onmousemove: on_move.passive().throttle(Duration::from_millis(16)),
+///             "The mouse is at {position:?}"
+///         }
+///     }
+/// }
+/// ```
+pub trait EventHandlerExt<Args>: Sized {
+    /// Mark the listener as passive. The renderer registers passive listeners so the platform doesn't wait for the
+    /// handler before it runs the default action of the event, which keeps scrolling smooth. Calling
+    /// [`Event::prevent_default`](crate::Event::prevent_default) in a passive handler has no effect.
+    fn passive(self) -> HandlerOptions<Self>;
+
+    /// Attach the listener directly to its element instead of the listener the renderer shares at the root of the
+    /// app. Direct listeners see events that never reach the root, like events inside shadow roots or events that
+    /// custom elements stop before they bubble out. The event still propagates through the listeners of the ancestors
+    /// in the virtual dom as usual.
+    fn direct(self) -> HandlerOptions<Self>;
+
+    /// Call the handler at most once per interval. The first event runs the handler immediately and the latest event
+    /// during the interval runs it again when the interval ends.
+    fn throttle(self, interval: Duration) -> HandlerOptions<Self>;
+
+    /// Only call the handler once the events stop for the delay, with the latest event.
+    fn debounce(self, delay: Duration) -> HandlerOptions<Self>;
+}
+
+impl<Args, Ret, F: FnMut(Args) -> Ret> EventHandlerExt<Args> for F {
+    fn passive(self) -> HandlerOptions<Self> {
+        HandlerOptions::new(self).passive()
+    }
+
+    fn direct(self) -> HandlerOptions<Self> {
+        HandlerOptions::new(self).direct()
+    }
+
+    fn throttle(self, interval: Duration) -> HandlerOptions<Self> {
+        HandlerOptions::new(self).throttle(interval)
+    }
+
+    fn debounce(self, delay: Duration) -> HandlerOptions<Self> {
+        HandlerOptions::new(self).debounce(delay)
+    }
+}
+
+impl<F> HandlerOptions<F> {
+    fn new(handler: F) -> Self {
+        Self {
+            handler,
+            listener_options: ListenerOptions::default(),
+            rate_limit: RateLimit::None,
+        }
+    }
+
+    /// Mark the listener as passive. See [`EventHandlerExt::passive`]
+    pub fn passive(mut self) -> Self {
+        self.listener_options.passive = true;
+        self
+    }
+
+    /// Attach the listener directly to its element. See [`EventHandlerExt::direct`]
+    pub fn direct(mut self) -> Self {
+        self.listener_options.direct = true;
+        self
+    }
+
+    /// Call the handler at most once per interval. See [`EventHandlerExt::throttle`]
+    pub fn throttle(self, interval: Duration) -> Self {
+        Self {
+            rate_limit: RateLimit::Throttle(interval),
+            ..self
+        }
+    }
+
+    /// Only call the handler once the events stop for the delay. See [`EventHandlerExt::debounce`]
+    pub fn debounce(self, delay: Duration) -> Self {
+        Self {
+            rate_limit: RateLimit::Debounce(delay),
+            ..self
+        }
+    }
+}
+
+/// The state of a throttled or debounced handler
+struct Limiter<Args> {
+    last_call: Option<Instant>,
+    pending: Option<Args>,
+    task: Option<Task>,
+}
+
+#[doc(hidden)]
+pub struct HandlerOptionsMarker<Marker>(PhantomData<Marker>);
+
+impl<Function, Args, Spawn, Marker>
+    SuperFrom<HandlerOptions<Function>, HandlerOptionsMarker<Marker>> for Callback<Args>
+where
+    Function: FnMut(Args) -> Spawn + 'static,
+    Args: 'static,
+    Spawn: SpawnIfAsync<Marker> + 'static,
+{
+    fn super_from(input: HandlerOptions<Function>) -> Self {
+        let HandlerOptions {
+            handler,
+            listener_options,
+            rate_limit,
+        } = input;
+
+        let handler = Rc::new(RefCell::new(handler));
+        let limiter = Rc::new(RefCell::new(Limiter {
+            last_call: None,
+            pending: None,
+            task: None,
+        }));
+        // Call the handler with the latest arguments once the timer of the limiter ends
+        let call_pending = {
+            let handler = handler.clone();
+            let limiter = limiter.clone();
+            move |duration: Duration| {
+                let handler = handler.clone();
+                let limiter = limiter.clone();
+                spawn(async move {
+                    sleep(duration).await;
+                    let pending = {
+                        let mut limiter = limiter.borrow_mut();
+                        limiter.task = None;
+                        limiter.last_call = Some(now());
+                        limiter.pending.take()
+                    };
+                    if let Some(args) = pending {
+                        (handler.borrow_mut())(args).spawn();
+                    }
+                })
+            }
+        };
+
+        let mut callback = match rate_limit {
+            RateLimit::None => Callback::new(move |args| (handler.borrow_mut())(args).spawn()),
+            RateLimit::Throttle(interval) => Callback::new(move |args| {
+                let now = now();
+                let mut state = limiter.borrow_mut();
+                match state.last_call {
+                    // Keep the latest event for the call at the end of the interval
+                    Some(last_call) if now < last_call + interval => {
+                        state.pending = Some(args);
+                        if state.task.is_none() {
+                            state.task = Some(call_pending(last_call + interval - now));
+                        }
+                    }
+                    _ => {
+                        state.last_call = Some(now);
+                        drop(state);
+                        (handler.borrow_mut())(args).spawn();
+                    }
+                }
+            }),
+            RateLimit::Debounce(delay) => Callback::new(move |args| {
+                let mut state = limiter.borrow_mut();
+                if let Some(task) = state.task.take() {
+                    task.cancel();
+                }
+                state.pending = Some(args);
+                state.task = Some(call_pending(delay));
+            }),
+        };
+        callback.listener_options = listener_options;
+        callback
+    }
+}
+
+impl<Args: 'static> Callback<Args> {
+    /// Create a callback that only calls this callback once the calls stop for the delay, with the latest arguments.
+    ///
+    /// This is the same as [`EventHandlerExt::debounce`] for handlers that are already callbacks, like handlers
+    /// passed to a component. The new callback is owned by the current scope, so create it once in a hook:
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # use std::time::Duration;
+    /// #[component]
+    /// fn SearchBox(onsearch: EventHandler<String>) -> Element {
+    ///     let onsearch = use_hook(|| onsearch.debounced(Duration::from_millis(300)));
+    ///
+    ///     rsx! {
+    ///         input { oninput: move |event| onsearch(event.value()) }
+    ///     }
+    /// }
+    /// ```
+    pub fn debounced(self, delay: Duration) -> Self {
+        self.rate_limited(RateLimit::Debounce(delay))
+    }
+
+    /// Create a callback that calls this callback at most once per interval. See [`EventHandlerExt::throttle`] and
+    /// [`Callback::debounced`].
+    pub fn throttled(self, interval: Duration) -> Self {
+        self.rate_limited(RateLimit::Throttle(interval))
+    }
+
+    fn rate_limited(self, rate_limit: RateLimit) -> Self {
+        let options = HandlerOptions {
+            handler: move |args: Args| self.call(args),
+            listener_options: self.listener_options,
+            rate_limit,
+        };
+        Callback::super_from(options)
+    }
+}
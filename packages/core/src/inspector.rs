@@ -0,0 +1,183 @@
+use crate::{
+    arena::MountId, global_context::current_scope_id, innerlude::SchedulerMsg, use_hook,
+    virtual_dom::VirtualDom, ElementId, Runtime, ScopeId,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// A named value of a component that is shown in devtools, like a prop or a signal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InspectedValue {
+    /// The name of the value
+    pub name: String,
+    /// The debug representation of the value, or the name of the type if the value doesn't implement [`Debug`](std::fmt::Debug)
+    pub value: String,
+}
+
+impl InspectedValue {
+    /// Create a new [`InspectedValue`]
+    pub fn new(name: impl ToString, value: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+/// A snapshot of a component that is mounted in the virtual dom. Returned by [`Runtime::inspect_components`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectedComponent {
+    /// The scope the component is mounted to
+    pub id: ScopeId,
+    /// The name of the component function
+    pub name: &'static str,
+    /// The scope of the parent component, if this isn't the root component
+    pub parent: Option<ScopeId>,
+    /// The depth of the component in the tree
+    pub height: u32,
+    /// The number of times the component has rendered
+    pub render_count: usize,
+    /// The props the component was last rendered with
+    pub props: Vec<InspectedValue>,
+    /// The values the component registered with [`use_inspected_value`]
+    pub values: Vec<InspectedValue>,
+    /// The elements at the root of the last rendered node of the component. Renderers that keep the element ids in
+    /// the dom can use these to highlight the component.
+    pub roots: Vec<ElementId>,
+}
+
+type InspectedValueFn = Rc<RefCell<Box<dyn Fn() -> String>>>;
+
+/// The information a scope records while inspection is enabled
+#[derive(Default)]
+pub(crate) struct ScopeInspection {
+    props: Vec<InspectedValue>,
+    values: Vec<(String, InspectedValueFn)>,
+    mount: MountId,
+}
+
+/// Show a value of the current component in devtools that inspect the virtual dom.
+///
+/// The value is formatted every time the component tree is inspected, so the closure should read signals with
+/// `peek` to avoid subscribing to them.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn Counter() -> Element {
+///     let mut count = use_signal(|| 0);
+///     use_inspected_value("count", move || count.peek().to_string());
+///
+///     rsx! { button { onclick: move |_| count += 1, "{count}" } }
+/// }
+/// ```
+pub fn use_inspected_value(name: impl ToString, value: impl Fn() -> String + 'static) {
+    let mut value = Some(value);
+    let current = use_hook(|| {
+        let value: InspectedValueFn = Rc::new(RefCell::new(Box::new(value.take().unwrap())));
+        let scope = current_scope_id().unwrap_or_else(|e| panic!("{}", e));
+        Runtime::with_scope(scope, |cx| {
+            cx.inspection
+                .borrow_mut()
+                .values
+                .push((name.to_string(), value.clone()));
+        })
+        .unwrap_or_else(|e| panic!("{}", e));
+        value
+    });
+
+    // Always format with the latest closure so it can read the latest props
+    if let Some(value) = value {
+        *current.borrow_mut() = Box::new(value);
+    }
+}
+
+impl Runtime {
+    /// Start recording the props and mounted elements of components so they can be returned from
+    /// [`Runtime::inspect_components`]. Every mounted component is rerendered so the snapshot is complete after the
+    /// next render.
+    ///
+    /// Inspection is meant for devtools and has a small cost for every render, so it is disabled by default.
+    pub fn enable_inspection(&self) {
+        if self.inspecting.replace(true) {
+            return;
+        }
+        for scope in self.scope_states.borrow().iter().flatten() {
+            _ = self
+                .sender
+                .unbounded_send(SchedulerMsg::Immediate(scope.id));
+        }
+    }
+
+    /// Check if [`Runtime::enable_inspection`] has been called
+    pub fn inspection_enabled(&self) -> bool {
+        self.inspecting.get()
+    }
+
+    /// Get a snapshot of every component that is mounted in the virtual dom, ordered by their [`ScopeId`].
+    ///
+    /// Props and mounted elements are only recorded while inspection is enabled with [`Runtime::enable_inspection`].
+    pub fn inspect_components(&self) -> Vec<InspectedComponent> {
+        // Collect the values before formatting them since the closures may read the runtime
+        let scopes = self
+            .scope_states
+            .borrow()
+            .iter()
+            .flatten()
+            .map(|scope| {
+                let inspection = scope.inspection.borrow();
+                (
+                    InspectedComponent {
+                        id: scope.id,
+                        name: scope.name,
+                        parent: scope.parent_id,
+                        height: scope.height,
+                        render_count: scope.render_count.get(),
+                        props: inspection.props.clone(),
+                        values: Vec::new(),
+                        roots: Vec::new(),
+                    },
+                    inspection.values.clone(),
+                    inspection.mount,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        scopes
+            .into_iter()
+            .map(|(mut component, values, mount)| {
+                component.values = values
+                    .iter()
+                    .map(|(name, value)| InspectedValue::new(name, (value.borrow())()))
+                    .collect();
+                component.roots = mount
+                    .as_usize()
+                    .and_then(|id| self.mounts.borrow().get(id).map(|m| m.root_ids.to_vec()))
+                    .unwrap_or_default();
+                component
+            })
+            .collect()
+    }
+
+    /// Record the props of a scope that just rendered
+    pub(crate) fn record_inspected_props(&self, scope: ScopeId, props: Vec<InspectedValue>) {
+        if let Some(scope) = self.get_state(scope) {
+            scope.inspection.borrow_mut().props = props;
+        }
+    }
+}
+
+impl VirtualDom {
+    /// Record where each scope is mounted after a render if inspection is enabled
+    pub(crate) fn record_inspected_mounts(&self) {
+        if !self.runtime.inspecting.get() {
+            return;
+        }
+        for (id, scope) in self.scopes.iter() {
+            let Some(node) = scope.try_root_node() else {
+                continue;
+            };
+            if let Some(scope) = self.runtime.get_state(ScopeId(id)) {
+                scope.inspection.borrow_mut().mount = node.mount.get();
+            }
+        }
+    }
+}
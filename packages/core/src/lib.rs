@@ -10,11 +10,16 @@ mod effect;
 mod error_boundary;
 mod events;
 mod fragment;
+mod frame;
 mod generational_box;
 mod global_context;
+mod handler_ext;
+mod inspector;
 mod launch;
+mod list_transition;
 mod mutations;
 mod nodes;
+mod profiler;
 mod properties;
 mod reactive_context;
 mod render_error;
@@ -26,6 +31,7 @@ mod scope_context;
 mod scopes;
 mod suspense;
 mod tasks;
+mod time;
 mod virtual_dom;
 
 mod hotreload_utils;
@@ -54,11 +60,16 @@ pub(crate) mod innerlude {
     pub use crate::error_boundary::*;
     pub use crate::events::*;
     pub use crate::fragment::*;
+    pub use crate::frame::*;
     pub use crate::generational_box::*;
     pub use crate::global_context::*;
+    pub use crate::handler_ext::*;
+    pub use crate::inspector::*;
     pub use crate::launch::*;
+    pub use crate::list_transition::*;
     pub use crate::mutations::*;
     pub use crate::nodes::*;
+    pub use crate::profiler::*;
     pub use crate::properties::*;
     pub use crate::reactive_context::*;
     pub use crate::render_error::*;
@@ -67,6 +78,7 @@ pub(crate) mod innerlude {
     pub use crate::scopes::*;
     pub use crate::suspense::*;
     pub use crate::tasks::*;
+    pub use crate::time::*;
     pub use crate::virtual_dom::*;
 
     /// An [`Element`] is a possibly-none [`VNode`] created by calling `render` on [`ScopeId`] or [`ScopeState`].
@@ -79,12 +91,16 @@ pub(crate) mod innerlude {
 }
 
 pub use crate::innerlude::{
-    fc_to_builder, generation, schedule_update, schedule_update_any, use_hook, vdom_is_rendering,
-    AnyValue, Attribute, AttributeValue, CapturedError, Component, ComponentFunction, DynamicNode,
-    Element, ElementId, Event, Fragment, HasAttributes, IntoDynNode, LaunchConfig, MarkerWrapper,
-    Mutation, Mutations, NoOpMutations, Ok, Properties, Result, Runtime, ScopeId, ScopeState,
-    SpawnIfAsync, Task, Template, TemplateAttribute, TemplateNode, VComponent, VNode, VNodeInner,
-    VPlaceholder, VText, VirtualDom, WriteMutations,
+    component_stack, fc_to_builder, generation, next_frame, now, schedule_before_paint,
+    schedule_layout_read, schedule_update, schedule_update_any, sleep, use_hook, use_raf,
+    vdom_is_rendering, AnyValue, Attribute, AttributeValue, CapturedError, CapturedPanic, Clock,
+    Component, ComponentFunction, ComponentProfile, DynamicNode, Element, ElementId, Event,
+    Fragment, FrameSource, HasAttributes, InspectedComponent, InspectedValue, IntervalFrames,
+    IntoAttributes, IntoDynNode, LaunchConfig, ListenerOptions, MarkerWrapper, MockClock, Mutation,
+    Mutations, NextFrame, NoOpMutations, Ok, ProfileSpan, ProfileSpanKind, Properties,
+    RenderProfile, Result, Runtime, ScopeId, ScopeState, Sleep, SpawnIfAsync, SystemClock, Task,
+    Template, TemplateAttribute, TemplateNode, VComponent, VNode, VNodeInner, VPlaceholder, VText,
+    VirtualDom, WriteMutations, DEFAULT_FRAME_INTERVAL,
 };
 
 /// The purpose of this module is to alleviate imports of many common types
@@ -96,14 +112,16 @@ pub mod prelude {
         fc_to_builder, generation, has_context, needs_update, needs_update_any, parent_scope,
         provide_context, provide_error_boundary, provide_root_context, queue_effect, remove_future,
         schedule_update, schedule_update_any, spawn, spawn_forever, spawn_isomorphic, suspend,
-        throw_error, try_consume_context, use_after_render, use_before_render, use_drop, use_hook,
-        use_hook_with_cleanup, with_owner, AnyValue, Attribute, Callback, Component,
-        ComponentFunction, Context, Element, ErrorBoundary, ErrorContext, Event, EventHandler,
-        Fragment, HasAttributes, IntoAttributeValue, IntoDynNode, OptionStringFromMarker,
+        throw_error, try_consume_context, use_after_render, use_before_render, use_drop,
+        use_error_listener, use_hook, use_hook_with_cleanup, use_id, use_inspected_value,
+        use_list_transitions, use_render_count, with_owner, AnyValue, Attribute, Callback,
+        Component, ComponentFunction, Context, Element, ErrorBoundary, ErrorContext, Event,
+        EventHandler, EventHandlerExt, Fragment, HasAttributes, InspectedComponent, InspectedValue,
+        IntoAttributeValue, IntoAttributes, IntoDynNode, ListTransition, OptionStringFromMarker,
         Properties, ReactiveContext, RenderError, Runtime, RuntimeGuard, ScopeId, ScopeState,
         SuperFrom, SuperInto, SuspendedFuture, SuspenseBoundary, SuspenseBoundaryProps,
-        SuspenseContext, SuspenseExtension, Task, Template, TemplateAttribute, TemplateNode, VNode,
-        VNodeInner, VirtualDom,
+        SuspenseContext, SuspenseDeadlineExceeded, SuspenseExtension, Task, Template,
+        TemplateAttribute, TemplateNode, VNode, VNodeInner, VirtualDom,
     };
 }
 
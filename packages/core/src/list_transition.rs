@@ -0,0 +1,161 @@
+use crate::{
+    global_context::current_scope_id, innerlude::DynamicNode, nodes::VNode, use_hook, Runtime,
+};
+use rustc_hash::FxHashMap;
+use std::{cell::RefCell, rc::Rc};
+
+/// A change to a child of a keyed list that was rendered by a component that called [`use_list_transitions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListTransition {
+    /// A child with a new key was added to the list
+    Enter {
+        /// The key of the child
+        key: String,
+        /// The index of the child in the new list
+        index: usize,
+    },
+    /// A child was removed from the list
+    Leave {
+        /// The key of the child
+        key: String,
+        /// The index the child had in the old list
+        index: usize,
+    },
+    /// A child that exists in both lists has a different index in the new list
+    Move {
+        /// The key of the child
+        key: String,
+        /// The index of the child in the old list
+        from: usize,
+        /// The index of the child in the new list
+        to: usize,
+    },
+}
+
+pub(crate) type ListTransitionHandler = Rc<RefCell<Box<dyn FnMut(Vec<ListTransition>)>>>;
+
+/// Listen to children entering, leaving and moving in the keyed lists this component renders.
+///
+/// The handler is called after the render that changed the list has been applied to the renderer, so the elements
+/// are already in their new position. This makes it possible to implement FLIP style animations: measure the
+/// elements in the handler, compare them to the last measurement and animate from the old position to the new one.
+///
+/// Only lists that are rendered directly in the rsx of this component are tracked. Lists passed to a child component
+/// are reported to the child.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn App() -> Element {
+///     let mut items = use_signal(|| vec![1, 2, 3]);
+///
+///     use_list_transitions(|transitions| {
+///         for transition in transitions {
+///             match transition {
+///                 ListTransition::Enter { key, .. } => println!("{key} entered"),
+///                 ListTransition::Leave { key, .. } => println!("{key} left"),
+///                 ListTransition::Move { key, from, to } => println!("{key} moved from {from} to {to}"),
+///             }
+///         }
+///     });
+///
+///     rsx! {
+///         button { onclick: move |_| items.write().reverse(), "Reverse" }
+///         ul {
+///             for item in items() {
+///                 li { key: "{item}", id: "item-{item}", "{item}" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_list_transitions(handler: impl FnMut(Vec<ListTransition>) + 'static) {
+    let mut handler = Some(handler);
+    let current = use_hook(|| {
+        let handler: ListTransitionHandler =
+            Rc::new(RefCell::new(Box::new(handler.take().unwrap())));
+        let scope = current_scope_id().unwrap_or_else(|e| panic!("{}", e));
+        Runtime::with_scope(scope, |cx| {
+            *cx.list_transitions.borrow_mut() = Some(handler.clone());
+        })
+        .unwrap_or_else(|e| panic!("{}", e));
+        handler
+    });
+
+    // Always call the latest handler so it can read the latest props
+    if let Some(handler) = handler {
+        *current.borrow_mut() = Box::new(handler);
+    }
+}
+
+impl Runtime {
+    /// Report the changes between two versions of a list to the component that is currently being diffed
+    fn report_list_transitions(&self, old: &[VNode], new: &[VNode]) {
+        let Ok(scope) = self.current_scope_id() else {
+            return;
+        };
+        let Some(handler) = self
+            .get_state(scope)
+            .and_then(|cx| cx.list_transitions.borrow().clone())
+        else {
+            return;
+        };
+
+        let key = |node: &VNode| node.key.clone();
+        let old_keys = old
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| Some((key(node)?, index)))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut transitions = Vec::new();
+        let mut kept = rustc_hash::FxHashSet::default();
+        for (index, node) in new.iter().enumerate() {
+            let Some(key) = key(node) else {
+                continue;
+            };
+            match old_keys.get(&key) {
+                Some(&from) => {
+                    kept.insert(key.clone());
+                    if from != index {
+                        transitions.push(ListTransition::Move {
+                            key,
+                            from,
+                            to: index,
+                        });
+                    }
+                }
+                None => transitions.push(ListTransition::Enter { key, index }),
+            }
+        }
+        for (key, index) in old_keys {
+            if !kept.contains(&key) {
+                transitions.push(ListTransition::Leave { key, index });
+            }
+        }
+        if transitions.is_empty() {
+            return;
+        }
+
+        // Sort leaves by their index so the order of the transitions doesn't depend on the hash map
+        transitions.sort_by_key(|transition| match transition {
+            ListTransition::Leave { index, .. } => (1, *index),
+            _ => (0, 0),
+        });
+        self.queue_effect(scope, move || (handler.borrow_mut())(transitions));
+    }
+
+    /// Report the changes between two dynamic nodes if either of them is a keyed list
+    pub(crate) fn report_dynamic_node_transitions(&self, old: &DynamicNode, new: &DynamicNode) {
+        let is_keyed_list = |node: &DynamicNode| matches!(node, DynamicNode::Fragment(nodes) if nodes.first().is_some_and(|node| node.key.is_some()));
+        if !is_keyed_list(old) && !is_keyed_list(new) {
+            return;
+        }
+        fn nodes(node: &DynamicNode) -> &[VNode] {
+            match node {
+                DynamicNode::Fragment(nodes) => nodes,
+                _ => &[],
+            }
+        }
+        self.report_list_transitions(nodes(old), nodes(new));
+    }
+}
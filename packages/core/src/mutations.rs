@@ -1,4 +1,5 @@
 use crate::{arena::ElementId, AttributeValue, Template};
+pub use dioxus_core_types::ListenerOptions;
 
 /// Something that can handle the mutations that are generated by the diffing process and apply them to the Real DOM
 ///
@@ -106,6 +107,29 @@ pub trait WriteMutations {
     /// Id: The ID of the node to remove.
     fn remove_event_listener(&mut self, name: &'static str, id: ElementId);
 
+    /// Create a new Event Listener with options for how the platform should register it, like passive listeners that
+    /// never prevent the default action of the event.
+    ///
+    /// Renderers that support the options should override this. By default it creates a normal event listener.
+    fn create_event_listener_with_options(
+        &mut self,
+        name: &'static str,
+        id: ElementId,
+        _options: ListenerOptions,
+    ) {
+        self.create_event_listener(name, id)
+    }
+
+    /// Remove an existing Event Listener that was created with options. By default it removes a normal event listener.
+    fn remove_event_listener_with_options(
+        &mut self,
+        name: &'static str,
+        id: ElementId,
+        _options: ListenerOptions,
+    ) {
+        self.remove_event_listener(name, id)
+    }
+
     /// Remove a particular node from the DOM
     ///
     /// Id: The ID of the node to remove.
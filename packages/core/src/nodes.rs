@@ -3,7 +3,7 @@ use dioxus_core_types::DioxusFormattable;
 use crate::innerlude::VProps;
 use crate::prelude::RenderError;
 use crate::{any_props::BoxedAnyProps, innerlude::ScopeState};
-use crate::{arena::ElementId, Element, Event};
+use crate::{arena::ElementId, Element, Event, ListenerOptions};
 use crate::{
     innerlude::{ElementRef, EventHandler, MountId},
     properties::ComponentFunction,
@@ -614,6 +614,7 @@ impl VComponent {
         let props = Box::new(VProps::new(
             component,
             <P as Properties>::memoize,
+            <P as Properties>::inspect,
             props,
             fn_name,
         ));
@@ -829,16 +830,29 @@ impl AttributeValue {
     /// Create a new [`AttributeValue`] with the listener variant from a callback
     ///
     /// The callback must be confined to the lifetime of the ScopeState
-    pub fn listener<T: 'static>(mut callback: impl FnMut(Event<T>) + 'static) -> AttributeValue {
+    pub fn listener<T: 'static>(callback: impl FnMut(Event<T>) + 'static) -> AttributeValue {
+        Self::listener_with_options(ListenerOptions::default(), callback)
+    }
+
+    /// Create a new [`AttributeValue`] with the listener variant from a callback and the options renderers use to
+    /// register the listener. The options are combined with any options in the name of the attribute.
+    ///
+    /// The callback must be confined to the lifetime of the ScopeState
+    pub fn listener_with_options<T: 'static>(
+        options: ListenerOptions,
+        mut callback: impl FnMut(Event<T>) + 'static,
+    ) -> AttributeValue {
         // TODO: maybe don't use the copy-variant of EventHandler here?
         // Maybe, create an Owned variant so we are less likely to run into leaks
-        AttributeValue::Listener(EventHandler::leak(move |event: Event<dyn Any>| {
+        let mut listener = EventHandler::leak(move |event: Event<dyn Any>| {
             let data = event.data.downcast::<T>().unwrap();
             callback(Event {
                 metadata: event.metadata.clone(),
                 data,
             });
-        }))
+        });
+        listener.listener_options = options;
+        AttributeValue::Listener(listener)
     }
 
     /// Create a new [`AttributeValue`] with a value that implements [`AnyValue`]
@@ -1193,6 +1207,32 @@ where
     }
 }
 
+/// A value that can be converted into the attributes of one attribute slot. The rsx macro uses this for the `style`
+/// attribute, so a typed style can set each property as its own attribute and properties are diffed one by one.
+pub trait IntoAttributes<T = ()> {
+    /// Convert into a list of attributes for the attribute with the name, namespace and volatility
+    fn into_attributes(
+        self,
+        name: &'static str,
+        namespace: Option<&'static str>,
+        volatile: bool,
+    ) -> Box<[Attribute]>;
+}
+
+impl<T, M> IntoAttributes<M> for T
+where
+    T: IntoAttributeValue<M>,
+{
+    fn into_attributes(
+        self,
+        name: &'static str,
+        namespace: Option<&'static str>,
+        volatile: bool,
+    ) -> Box<[Attribute]> {
+        Box::new([Attribute::new(name, self, namespace, volatile)])
+    }
+}
+
 /// A trait for anything that has a dynamic list of attributes
 pub trait HasAttributes {
     /// Push an attribute onto the list of attributes
@@ -0,0 +1,291 @@
+use crate::{use_hook, Runtime, ScopeId};
+use rustc_hash::FxHashMap;
+use std::{cell::Cell, fmt::Write, rc::Rc, time::Duration};
+use web_time::Instant;
+
+/// The kind of work a [`ProfileSpan`] measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProfileSpanKind {
+    /// Running the component function
+    Render,
+    /// Diffing or creating the nodes the component returned and writing the mutations. Components created while
+    /// diffing are nested inside of this span.
+    Diff,
+    /// Running the effects queued by the component
+    Effect,
+}
+
+impl ProfileSpanKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ProfileSpanKind::Render => "render",
+            ProfileSpanKind::Diff => "diff",
+            ProfileSpanKind::Effect => "effect",
+        }
+    }
+}
+
+/// A unit of work recorded by the profiler
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileSpan {
+    /// The kind of work
+    pub kind: ProfileSpanKind,
+    /// The scope the work was done for
+    pub scope: ScopeId,
+    /// The name of the component the work was done for
+    pub name: &'static str,
+    /// When the work started, relative to the start of the profile
+    pub start: Duration,
+    /// How long the work took, including any nested spans
+    pub duration: Duration,
+    /// The index of the span this span is nested in
+    pub parent: Option<usize>,
+}
+
+/// The total time spent on each kind of work for a component. Returned by [`RenderProfile::components`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentProfile {
+    /// The name of the component
+    pub name: &'static str,
+    /// The scope the component was mounted to
+    pub scope: ScopeId,
+    /// The number of times the component rendered
+    pub renders: usize,
+    /// The total time spent running the component
+    pub render_time: Duration,
+    /// The total time spent diffing the component, not including any components created while diffing
+    pub diff_time: Duration,
+    /// The total time spent running the effects of the component
+    pub effect_time: Duration,
+}
+
+/// A trace of the work the virtual dom did while profiling. Returned by [`Runtime::stop_profiling`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderProfile {
+    /// Every span in the order they started
+    pub spans: Vec<ProfileSpan>,
+}
+
+impl RenderProfile {
+    /// The time of a span not spent in any of its nested spans
+    fn self_times(&self) -> Vec<Duration> {
+        let mut times = self
+            .spans
+            .iter()
+            .map(|span| span.duration)
+            .collect::<Vec<_>>();
+        for span in &self.spans {
+            if let Some(parent) = span.parent {
+                times[parent] = times[parent].saturating_sub(span.duration);
+            }
+        }
+        times
+    }
+
+    /// Sum up the spans of each component, ordered by the total time spent on the component from the slowest to the
+    /// fastest
+    pub fn components(&self) -> Vec<ComponentProfile> {
+        let mut components = FxHashMap::<ScopeId, ComponentProfile>::default();
+        for (span, self_time) in self.spans.iter().zip(self.self_times()) {
+            let component = components
+                .entry(span.scope)
+                .or_insert_with(|| ComponentProfile {
+                    name: span.name,
+                    scope: span.scope,
+                    renders: 0,
+                    render_time: Duration::ZERO,
+                    diff_time: Duration::ZERO,
+                    effect_time: Duration::ZERO,
+                });
+            match span.kind {
+                ProfileSpanKind::Render => {
+                    component.renders += 1;
+                    component.render_time += span.duration;
+                }
+                ProfileSpanKind::Diff => component.diff_time += self_time,
+                ProfileSpanKind::Effect => component.effect_time += span.duration,
+            }
+        }
+
+        let mut components = components.into_values().collect::<Vec<_>>();
+        components.sort_by_key(|c| {
+            (
+                std::cmp::Reverse(c.render_time + c.diff_time + c.effect_time),
+                c.scope,
+            )
+        });
+        components
+    }
+
+    /// Export the profile in the [Chrome trace event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU).
+    /// The output can be opened in `chrome://tracing`, [Perfetto](https://ui.perfetto.dev) or
+    /// [Speedscope](https://www.speedscope.app).
+    pub fn to_chrome_trace(&self) -> String {
+        let mut trace = String::from("{\"traceEvents\":[");
+        for (i, span) in self.spans.iter().enumerate() {
+            if i > 0 {
+                trace.push(',');
+            }
+            _ = write!(
+                trace,
+                "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":1,\"args\":{{\"scope\":{}}}}}",
+                escape_json(span.name),
+                span.kind.as_str(),
+                span.start.as_secs_f64() * 1_000_000.0,
+                span.duration.as_secs_f64() * 1_000_000.0,
+                span.scope.0
+            );
+        }
+        trace.push_str("]}");
+        trace
+    }
+
+    /// Export the profile as folded stacks where each line is a stack of spans and the time spent in the innermost
+    /// span in microseconds. The output can be turned into a flamegraph with
+    /// [inferno](https://github.com/jonhoo/inferno) or [flamegraph.pl](https://github.com/brendangregg/FlameGraph).
+    pub fn to_folded_stacks(&self) -> String {
+        let frame = |span: &ProfileSpan| {
+            format!("{} ({})", span.name.replace(';', ":"), span.kind.as_str())
+        };
+        let mut stacks = Vec::<String>::with_capacity(self.spans.len());
+        // Identical stacks are merged so the output stays small for components that render many times
+        let mut totals = Vec::<(String, u128)>::new();
+        let mut positions = FxHashMap::<String, usize>::default();
+        for (span, self_time) in self.spans.iter().zip(self.self_times()) {
+            let stack = match span.parent {
+                Some(parent) => format!("{};{}", stacks[parent], frame(span)),
+                None => frame(span),
+            };
+            let position = *positions.entry(stack.clone()).or_insert_with(|| {
+                totals.push((stack.clone(), 0));
+                totals.len() - 1
+            });
+            totals[position].1 += self_time.as_micros();
+            stacks.push(stack);
+        }
+
+        let mut folded = String::new();
+        for (stack, micros) in totals {
+            _ = writeln!(folded, "{stack} {micros}");
+        }
+        folded
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => _ = write!(escaped, "\\u{:04x}", c as u32),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The state of the profiler while profiling is enabled
+pub(crate) struct Profiler {
+    start: Instant,
+    spans: Vec<ProfileSpan>,
+    /// The spans that are currently running
+    stack: Vec<usize>,
+}
+
+impl Runtime {
+    /// Start recording how long each component takes to render, diff and run effects. Any profile that is already
+    /// being recorded is discarded.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// fn app() -> Element {
+    ///     rsx! { "hello world" }
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.runtime().start_profiling();
+    /// dom.rebuild_in_place();
+    /// let profile = dom.runtime().stop_profiling().unwrap();
+    /// let trace = profile.to_chrome_trace();
+    /// ```
+    pub fn start_profiling(&self) {
+        *self.profiler.borrow_mut() = Some(Profiler {
+            start: Instant::now(),
+            spans: Vec::new(),
+            stack: Vec::new(),
+        });
+    }
+
+    /// Stop profiling and return the profile that was recorded since [`Runtime::start_profiling`] was called
+    pub fn stop_profiling(&self) -> Option<RenderProfile> {
+        let profiler = self.profiler.borrow_mut().take()?;
+        Some(RenderProfile {
+            spans: profiler.spans,
+        })
+    }
+
+    /// Check if a profile is being recorded
+    pub fn is_profiling(&self) -> bool {
+        self.profiler.borrow().is_some()
+    }
+
+    /// Run a closure and record it as a span if profiling is enabled
+    pub(crate) fn profile<O>(
+        &self,
+        kind: ProfileSpanKind,
+        scope: ScopeId,
+        f: impl FnOnce() -> O,
+    ) -> O {
+        if self.profiler.borrow().is_none() {
+            return f();
+        }
+
+        let name = self.get_state(scope).map(|scope| scope.name).unwrap_or("");
+        let index = self.profiler.borrow_mut().as_mut().map(|profiler| {
+            let index = profiler.spans.len();
+            profiler.spans.push(ProfileSpan {
+                kind,
+                scope,
+                name,
+                start: profiler.start.elapsed(),
+                duration: Duration::ZERO,
+                parent: profiler.stack.last().copied(),
+            });
+            profiler.stack.push(index);
+            index
+        });
+
+        let output = f();
+
+        // Profiling may have been restarted or stopped while the closure was running
+        if let (Some(index), Some(profiler)) = (index, self.profiler.borrow_mut().as_mut()) {
+            if profiler.stack.last() == Some(&index) {
+                profiler.stack.pop();
+                let span = &mut profiler.spans[index];
+                span.duration = profiler.start.elapsed().saturating_sub(span.start);
+            }
+        }
+
+        output
+    }
+}
+
+/// Get the number of times the current component has run, including the current run. Unlike
+/// [`generation`](crate::prelude::generation), this counts renders that returned an error or suspended.
+///
+/// This is useful to check if a component reruns more often than expected.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn Counter() -> Element {
+///     let renders = use_render_count();
+///     tracing::debug!("Counter rendered {renders} times");
+///     rsx! { "Rendered {renders} times" }
+/// }
+/// ```
+pub fn use_render_count() -> usize {
+    let count = use_hook(|| Rc::new(Cell::new(0)));
+    count.set(count.get() + 1);
+    count.get()
+}
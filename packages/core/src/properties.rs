@@ -56,6 +56,12 @@ pub trait Properties: Clone + Sized + 'static {
     /// Make the old props equal to the new props. Return if the props were equal and should be memoized.
     fn memoize(&mut self, other: &Self) -> bool;
 
+    /// Get the debug representation of each prop for devtools. The derive macro implements this for every field,
+    /// falling back to the name of the type for fields that don't implement [`Debug`](std::fmt::Debug).
+    fn inspect(&self) -> Vec<InspectedValue> {
+        Vec::new()
+    }
+
     /// Create a component from the props.
     fn into_vcomponent<M: 'static>(self, render_fn: impl ComponentFunction<Self, M>) -> VComponent {
         let type_name = std::any::type_name_of_val(&render_fn);
@@ -1,5 +1,5 @@
 use crate::arena::ElementRef;
-use crate::innerlude::{DirtyTasks, Effect};
+use crate::innerlude::{Clock, DirtyTasks, Effect, Frames, Profiler, SystemClock};
 use crate::nodes::VNodeMount;
 use crate::scheduler::ScopeOrder;
 use crate::scope_context::SuspenseLocation;
@@ -9,7 +9,8 @@ use crate::{
     scopes::ScopeId,
     Task,
 };
-use crate::{AttributeValue, ElementId, Event};
+use crate::{AttributeValue, CapturedError, ElementId, Event};
+use dioxus_core_types::ListenerName;
 use slab::Slab;
 use slotmap::DefaultKey;
 use std::any::Any;
@@ -21,6 +22,8 @@ use std::{
 };
 use tracing::instrument;
 
+pub(crate) type ErrorListener = Rc<dyn Fn(&CapturedError)>;
+
 thread_local! {
     static RUNTIMES: RefCell<Vec<Rc<Runtime>>> = const { RefCell::new(vec![]) };
 }
@@ -64,6 +67,21 @@ pub struct Runtime {
     // We need to store this information on the virtual dom so that we know what nodes are mounted where when we bubble events
     // Each mount is associated with a whole rsx block. [`VirtualDom::elements`] link to a specific node in the block
     pub(crate) mounts: RefCell<Slab<VNodeMount>>,
+
+    // Listeners that are called with every error thrown in the virtual dom
+    pub(crate) error_listeners: RefCell<Slab<ErrorListener>>,
+
+    // If scopes should record their props and mounts for devtools
+    pub(crate) inspecting: Cell<bool>,
+
+    // The profile that is being recorded, if profiling is enabled
+    pub(crate) profiler: RefCell<Option<Profiler>>,
+
+    // The clock timers in the virtual dom read time from
+    clock: RefCell<Rc<dyn Clock>>,
+
+    // The next frame of the renderer and the work scheduled for it
+    pub(crate) frames: Frames,
 }
 
 impl Runtime {
@@ -85,6 +103,11 @@ impl Runtime {
             dirty_tasks: Default::default(),
             elements: RefCell::new(elements),
             mounts: Default::default(),
+            error_listeners: Default::default(),
+            inspecting: Cell::new(false),
+            profiler: RefCell::new(None),
+            clock: RefCell::new(Rc::new(SystemClock)),
+            frames: Default::default(),
         })
     }
 
@@ -116,6 +139,41 @@ impl Runtime {
         result
     }
 
+    /// Get the clock that timers in the virtual dom read time from
+    pub fn clock(&self) -> Rc<dyn Clock> {
+        self.clock.borrow().clone()
+    }
+
+    /// Replace the clock that timers in the virtual dom read time from. Timers that already started keep using the old
+    /// clock.
+    pub fn set_clock(&self, clock: Rc<dyn Clock>) {
+        *self.clock.borrow_mut() = clock;
+    }
+
+    /// Add a listener that is called with every error thrown in the virtual dom. Returns a key that can be used to remove the listener
+    pub(crate) fn add_error_listener(&self, listener: ErrorListener) -> usize {
+        self.error_listeners.borrow_mut().insert(listener)
+    }
+
+    /// Remove a listener added with [`Runtime::add_error_listener`]
+    pub(crate) fn remove_error_listener(&self, key: usize) {
+        self.error_listeners.borrow_mut().try_remove(key);
+    }
+
+    /// Call every error listener with an error that was just thrown
+    pub(crate) fn notify_error_listeners(&self, error: &CapturedError) {
+        // Listeners may add or remove other listeners, so we can't hold the borrow while calling them
+        let listeners: Vec<_> = self
+            .error_listeners
+            .borrow()
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect();
+        for listener in listeners {
+            listener(error);
+        }
+    }
+
     /// Create a scope context. This slab is synchronized with the scope slab.
     pub(crate) fn create_scope(&self, context: Scope) {
         let id = context.id;
@@ -165,6 +223,24 @@ impl Runtime {
             .ok_or(RuntimeError { _priv: () })
     }
 
+    /// Get the names of the components from a scope up to the root. Scopes that are being modified are skipped
+    /// instead of panicking.
+    pub(crate) fn component_stack(&self, scope: ScopeId) -> Vec<&'static str> {
+        let Ok(scopes) = self.scope_states.try_borrow() else {
+            return Vec::new();
+        };
+        let mut names = Vec::new();
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            let Some(scope) = scopes.get(id.0).and_then(|scope| scope.as_ref()) else {
+                break;
+            };
+            names.push(scope.name);
+            current = scope.parent_id;
+        }
+        names
+    }
+
     /// Call this function with the current scope set to the given scope
     ///
     /// Useful in a limited number of scenarios
@@ -309,6 +385,12 @@ impl Runtime {
         let elements = self.elements.borrow();
 
         if let Some(Some(parent_path)) = elements.get(element.0).copied() {
+            // Like the DOM, capture listeners run from the root down to the target before any other listener
+            self.handle_capturing_event(parent_path, name, &event);
+            if event.propagation_stopped() {
+                return;
+            }
+
             if event.propagates() {
                 self.handle_bubbling_event(parent_path, name, event);
             } else {
@@ -365,8 +447,11 @@ impl Runtime {
                 let attrs = &*el_ref.dynamic_attrs[idx];
 
                 for attr in attrs.iter() {
-                    // Remove the "on" prefix if it exists, TODO, we should remove this and settle on one
-                    if attr.name.get(2..) == Some(name) && target_path.is_descendant(this_path) {
+                    let listener = ListenerName::parse(attr.name);
+                    if !listener.capture
+                        && listener.event == name
+                        && target_path.is_descendant(this_path)
+                    {
                         listeners.push(&attr.value);
 
                         // Break if this is the exact target element.
@@ -402,6 +487,65 @@ impl Runtime {
         }
     }
 
+    /// Call the capture listeners (`on{event}capture`) of the target element and its ancestors from the top of the tree
+    /// down to the target. Capture listeners run for both bubbling and non-bubbling events.
+    #[instrument(
+        skip(self, uievent),
+        level = "trace",
+        name = "VirtualDom::handle_capturing_event"
+    )]
+    fn handle_capturing_event(&self, target: ElementRef, name: &str, uievent: &Event<dyn Any>) {
+        let mounts = self.mounts.borrow();
+
+        // Accumulate the listeners bottom to top, the same way we do for bubbling events
+        let mut listeners = vec![];
+        let mut parent = Some(target);
+        while let Some(path) = parent {
+            let Some(mount) = mounts.get(path.mount.0) else {
+                // If the node is suspended and not mounted, we can just ignore the event
+                return;
+            };
+            let el_ref = &mount.node;
+            let node_template = el_ref.template;
+            let target_path = path.path;
+
+            // The attributes in a template are ordered top to bottom, so flip them after collecting them
+            let template_start = listeners.len();
+            for (idx, this_path) in node_template.attr_paths.iter().enumerate() {
+                let attrs = &*el_ref.dynamic_attrs[idx];
+
+                for attr in attrs.iter() {
+                    let listener = ListenerName::parse(attr.name);
+                    if listener.capture
+                        && listener.event == name
+                        && target_path.is_descendant(this_path)
+                    {
+                        listeners.push(&attr.value);
+                    }
+                }
+            }
+            listeners[template_start..].reverse();
+
+            let mount = el_ref.mount.get().as_usize();
+            parent = mount.and_then(|id| mounts.get(id).and_then(|el| el.parent));
+        }
+
+        tracing::event!(
+            tracing::Level::TRACE,
+            "Calling {} capture listeners",
+            listeners.len()
+        );
+        for listener in listeners.into_iter().rev() {
+            if let AttributeValue::Listener(listener) = listener {
+                listener.call(uievent.clone());
+
+                if uievent.propagation_stopped() {
+                    return;
+                }
+            }
+        }
+    }
+
     /// Call an event listener in the simplest way possible without bubbling upwards
     #[instrument(
         skip(self, uievent),
@@ -422,9 +566,9 @@ impl Runtime {
             let attrs = &*el_ref.dynamic_attrs[idx];
 
             for attr in attrs.iter() {
-                // Remove the "on" prefix if it exists, TODO, we should remove this and settle on one
                 // Only call the listener if this is the exact target element.
-                if attr.name.get(2..) == Some(name) && target_path == this_path {
+                let listener = ListenerName::parse(attr.name);
+                if !listener.capture && listener.event == name && target_path == this_path {
                     if let AttributeValue::Listener(listener) = &attr.value {
                         listener.call(uievent.clone());
                         break;
@@ -1,4 +1,4 @@
-use crate::innerlude::{throw_error, RenderError, ScopeOrder};
+use crate::innerlude::{throw_error, ProfileSpanKind, RenderError, ScopeOrder};
 use crate::nodes::AsVNode;
 use crate::prelude::ReactiveContext;
 use crate::scope_context::SuspenseLocation;
@@ -18,18 +18,22 @@ impl VirtualDom {
         name: &'static str,
     ) -> &mut ScopeState {
         let parent_id = self.runtime.current_scope_id().ok();
-        let height = match parent_id.and_then(|id| self.runtime.get_state(id)) {
-            Some(parent) => parent.height() + 1,
-            None => 0,
-        };
         let suspense_boundary = self
             .runtime
             .current_suspense_location()
             .unwrap_or(SuspenseLocation::NotSuspended);
+        let placeholder = matches!(
+            suspense_boundary,
+            SuspenseLocation::InSuspensePlaceholder(_)
+        );
+        let (height, tree_path) = match parent_id.and_then(|id| self.runtime.get_state(id)) {
+            Some(parent) => (parent.height() + 1, parent.child_tree_path(placeholder)),
+            None => (0, String::new()),
+        };
         let entry = self.scopes.vacant_entry();
         let id = ScopeId(entry.key());
 
-        let scope_runtime = Scope::new(name, id, parent_id, height, suspense_boundary);
+        let scope_runtime = Scope::new(name, id, parent_id, height, tree_path, suspense_boundary);
         let reactive_context = ReactiveContext::new_for_scope(&scope_runtime, &self.runtime);
 
         let scope = entry.insert(ScopeState {
@@ -70,7 +74,9 @@ impl VirtualDom {
                 let span = tracing::trace_span!("render", scope = %scope.state().name);
                 span.in_scope(|| {
                     scope.reactive_context.reset_and_run_in(|| {
-                        let render_return = props.render();
+                        let render_return =
+                            self.runtime
+                                .profile(ProfileSpanKind::Render, scope_id, || props.render());
                         // After the component is run, we need to do a deep clone of the VNode. This
                         // breaks any references to mounted parts of the VNode from the component.
                         // Without this, the component could store a mounted version of the VNode
@@ -88,6 +94,12 @@ impl VirtualDom {
                 })
             };
 
+            // Record the props outside of the reactive context so formatting signals doesn't subscribe to them
+            if self.runtime.inspecting.get() {
+                self.runtime
+                    .record_inspected_props(scope_id, scope.props.inspect());
+            }
+
             let scope_state = scope.state();
 
             // Run all post-render hooks
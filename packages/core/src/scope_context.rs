@@ -1,9 +1,12 @@
 use crate::runtime::RuntimeError;
-use crate::{innerlude::SchedulerMsg, Runtime, ScopeId, Task};
 use crate::{
     innerlude::{throw_into, CapturedError},
     prelude::SuspenseContext,
 };
+use crate::{
+    innerlude::{ListTransitionHandler, SchedulerMsg, ScopeInspection},
+    Runtime, ScopeId, Task,
+};
 use generational_box::{AnyStorage, Owner};
 use rustc_hash::FxHashSet;
 use std::{
@@ -51,6 +54,14 @@ pub(crate) struct Scope {
     pub(crate) height: u32,
     pub(crate) render_count: Cell<usize>,
 
+    /// The position of this scope in the tree of components. See [`crate::prelude::use_id`]
+    pub(crate) tree_path: String,
+    /// The number of children created under this scope, outside and inside of suspense placeholders
+    pub(crate) children_created: Cell<u32>,
+    pub(crate) placeholders_created: Cell<u32>,
+    /// The number of ids created with `use_id` in this scope
+    pub(crate) ids_created: Cell<u32>,
+
     // Note: the order of the hook and context fields is important. The hooks field must be dropped before the contexts field in case a hook drop implementation tries to access a context.
     pub(crate) hooks: RefCell<Vec<Box<dyn Any>>>,
     pub(crate) hook_index: Cell<usize>,
@@ -59,6 +70,12 @@ pub(crate) struct Scope {
     pub(crate) before_render: RefCell<Vec<Box<dyn FnMut()>>>,
     pub(crate) after_render: RefCell<Vec<Box<dyn FnMut()>>>,
 
+    /// The handler for changes to the keyed lists this scope renders (if any)
+    pub(crate) list_transitions: RefCell<Option<ListTransitionHandler>>,
+
+    /// The props, values and mount of this scope recorded for devtools
+    pub(crate) inspection: RefCell<ScopeInspection>,
+
     /// The suspense boundary that this scope is currently in (if any)
     suspense_boundary: SuspenseLocation,
 
@@ -71,6 +88,7 @@ impl Scope {
         id: ScopeId,
         parent_id: Option<ScopeId>,
         height: u32,
+        tree_path: String,
         suspense_boundary: SuspenseLocation,
     ) -> Self {
         Self {
@@ -79,12 +97,18 @@ impl Scope {
             parent_id,
             height,
             render_count: Cell::new(0),
+            tree_path,
+            children_created: Cell::new(0),
+            placeholders_created: Cell::new(0),
+            ids_created: Cell::new(0),
             shared_contexts: RefCell::new(vec![]),
             spawned_tasks: RefCell::new(FxHashSet::default()),
             hooks: RefCell::new(vec![]),
             hook_index: Cell::new(0),
             before_render: RefCell::new(vec![]),
             after_render: RefCell::new(vec![]),
+            list_transitions: RefCell::new(None),
+            inspection: RefCell::new(ScopeInspection::default()),
             status: RefCell::new(ScopeStatus::Unmounted {
                 effects_queued: Vec::new(),
             }),
@@ -96,6 +120,23 @@ impl Scope {
         self.parent_id
     }
 
+    /// Get the path of a new child of this scope. Children are numbered in the order they are created.
+    ///
+    /// Placeholders of suspense boundaries are numbered separately because the server may render a placeholder that the
+    /// client never renders while it hydrates.
+    pub(crate) fn child_tree_path(&self, placeholder: bool) -> String {
+        let (counter, prefix) = match placeholder {
+            true => (&self.placeholders_created, "p"),
+            false => (&self.children_created, ""),
+        };
+        let index = counter.get();
+        counter.set(index + 1);
+        match self.tree_path.is_empty() {
+            true => format!("{prefix}{index}"),
+            false => format!("{}-{prefix}{index}", self.tree_path),
+        }
+    }
+
     fn sender(&self) -> futures_channel::mpsc::UnboundedSender<SchedulerMsg> {
         Runtime::with(|rt| rt.sender.clone()).unwrap_or_else(|e| panic!("{}", e))
     }
@@ -565,6 +606,12 @@ impl ScopeId {
         false
     }
 
+    /// Get the names of the components from this scope up to the root of the app. This doesn't panic, so it can be
+    /// called from a panic hook while the scope is rendering.
+    pub fn component_stack(self) -> Vec<&'static str> {
+        Runtime::with(|rt| rt.component_stack(self)).unwrap_or_default()
+    }
+
     /// Mark the current scope as dirty, causing it to re-render
     pub fn needs_update(self) {
         Runtime::with_scope(self, |cx| cx.needs_update()).unwrap();
@@ -1,9 +1,16 @@
 use crate::{innerlude::*, scope_context::SuspenseLocation};
+use std::time::Duration;
 
 /// Properties for the [`SuspenseBoundary()`] component.
 #[allow(non_camel_case_types)]
 pub struct SuspenseBoundaryProps {
     fallback: Callback<SuspenseContext, Element>,
+    /// The fallback to render once the boundary has been suspended for longer than the `timeout`
+    slow_fallback: Option<Callback<SuspenseContext, Element>>,
+    /// How long the boundary can be suspended before the `slow_fallback` is rendered
+    timeout: Option<Duration>,
+    /// How long the boundary can be suspended before a [`SuspenseDeadlineExceeded`] error is thrown to the nearest error boundary
+    deadline: Option<Duration>,
     /// The children of the suspense boundary
     children: Element,
 }
@@ -12,6 +19,9 @@ impl Clone for SuspenseBoundaryProps {
     fn clone(&self) -> Self {
         Self {
             fallback: self.fallback,
+            slow_fallback: self.slow_fallback,
+            timeout: self.timeout,
+            deadline: self.deadline,
             children: self.children.clone(),
         }
     }
@@ -20,7 +30,7 @@ impl Clone for SuspenseBoundaryProps {
 impl SuspenseBoundaryProps {
     /**
     Create a builder for building `SuspenseBoundaryProps`.
-    On the builder, call `.fallback(...)`, `.children(...)`(optional) to set the values of the fields.
+    On the builder, call `.fallback(...)`, `.slow_fallback(...)`(optional), `.timeout(...)`(optional), `.deadline(...)`(optional), `.children(...)`(optional) to set the values of the fields.
     Finally, call `.build()` to create the instance of `SuspenseBoundaryProps`.
                         */
     #[allow(dead_code, clippy::type_complexity)]
@@ -28,6 +38,7 @@ impl SuspenseBoundaryProps {
         SuspenseBoundaryPropsBuilder {
             owner: Owner::default(),
             fields: ((), ()),
+            timers: SuspenseTimers::default(),
             _phantom: ::core::default::Default::default(),
         }
     }
@@ -38,8 +49,41 @@ impl SuspenseBoundaryProps {
 pub struct SuspenseBoundaryPropsBuilder<TypedBuilderFields> {
     owner: Owner,
     fields: TypedBuilderFields,
+    timers: SuspenseTimers,
     _phantom: (),
 }
+
+/// The optional timing fields of the suspense boundary. These don't change the type of the builder
+#[derive(Default)]
+struct SuspenseTimers {
+    slow_fallback: Option<Callback<SuspenseContext, Element>>,
+    timeout: Option<Duration>,
+    deadline: Option<Duration>,
+}
+
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<TypedBuilderFields> SuspenseBoundaryPropsBuilder<TypedBuilderFields> {
+    #[allow(clippy::type_complexity)]
+    pub fn slow_fallback<__Marker>(
+        mut self,
+        slow_fallback: impl SuperInto<Callback<SuspenseContext, Element>, __Marker>,
+    ) -> Self {
+        self.timers.slow_fallback = Some(with_owner(self.owner.clone(), move || {
+            SuperInto::super_into(slow_fallback)
+        }));
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timers.timeout = Some(timeout);
+        self
+    }
+
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.timers.deadline = Some(deadline);
+        self
+    }
+}
 impl Properties for SuspenseBoundaryProps
 where
     Self: Clone,
@@ -51,6 +95,11 @@ where
     fn memoize(&mut self, new: &Self) -> bool {
         let equal = self == new;
         self.fallback.__point_to(&new.fallback);
+        if let (Some(slow_fallback), Some(new_slow_fallback)) =
+            (&mut self.slow_fallback, &new.slow_fallback)
+        {
+            slow_fallback.__point_to(new_slow_fallback);
+        }
         if !equal {
             let new_clone = new.clone();
             self.children = new_clone.children;
@@ -87,6 +136,7 @@ impl<__children> SuspenseBoundaryPropsBuilder<((), __children)> {
         SuspenseBoundaryPropsBuilder {
             owner: self.owner,
             fields: (fallback, children),
+            timers: self.timers,
             _phantom: self._phantom,
         }
     }
@@ -118,6 +168,7 @@ impl<__fallback> SuspenseBoundaryPropsBuilder<(__fallback, ())> {
         SuspenseBoundaryPropsBuilder {
             owner: self.owner,
             fields: (fallback, children),
+            timers: self.timers,
             _phantom: self._phantom,
         }
     }
@@ -204,8 +255,19 @@ impl<__children: SuspenseBoundaryPropsBuilder_Optional<Element>>
         let (fallback, children) = self.fields;
         let fallback = fallback.0;
         let children = SuspenseBoundaryPropsBuilder_Optional::into_value(children, VNode::empty);
+        let SuspenseTimers {
+            slow_fallback,
+            timeout,
+            deadline,
+        } = self.timers;
         SuspenseBoundaryPropsWithOwner {
-            inner: SuspenseBoundaryProps { fallback, children },
+            inner: SuspenseBoundaryProps {
+                fallback,
+                slow_fallback,
+                timeout,
+                deadline,
+                children,
+            },
             owner: self.owner,
         }
     }
@@ -215,7 +277,11 @@ impl<__children: SuspenseBoundaryPropsBuilder_Optional<Element>>
 impl ::core::cmp::PartialEq for SuspenseBoundaryProps {
     #[inline]
     fn eq(&self, other: &SuspenseBoundaryProps) -> bool {
-        self.fallback == other.fallback && self.children == other.children
+        self.fallback == other.fallback
+            && self.slow_fallback == other.slow_fallback
+            && self.timeout == other.timeout
+            && self.deadline == other.deadline
+            && self.children == other.children
     }
 }
 
@@ -241,6 +307,32 @@ impl ::core::cmp::PartialEq for SuspenseBoundaryProps {
 ///     }
 /// }
 /// ```
+///
+/// # Timeouts
+///
+/// If the boundary is suspended for longer than the `timeout`, the `slow_fallback` is rendered instead of the `fallback`.
+/// If the boundary is suspended for longer than the `deadline`, a [`SuspenseDeadlineExceeded`] error is thrown to the
+/// nearest error boundary.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use std::time::Duration;
+/// # fn Article() -> Element { rsx! { "Article" } }
+/// fn App() -> Element {
+///     rsx! {
+///         ErrorBoundary {
+///             handle_error: |_| rsx! { "The article failed to load" },
+///             SuspenseBoundary {
+///                 fallback: |_| rsx! { "Loading..." },
+///                 slow_fallback: |_| rsx! { "Still loading, is your network slow?" },
+///                 timeout: Duration::from_secs(3),
+///                 deadline: Duration::from_secs(30),
+///                 Article {}
+///             }
+///         }
+///     }
+/// }
+/// ```
 #[allow(non_snake_case)]
 pub fn SuspenseBoundary(mut __props: SuspenseBoundaryProps) -> Element {
     unreachable!("SuspenseBoundary should not be called directly")
@@ -261,6 +353,16 @@ pub use SuspenseBoundary_completions::Component::SuspenseBoundary;
 
 /// Suspense has a custom diffing algorithm that diffs the suspended nodes in the background without rendering them
 impl SuspenseBoundaryProps {
+    /// Render the slow fallback if the boundary has been suspended for longer than the timeout, otherwise render the fallback
+    fn render_fallback(&self, suspense_context: SuspenseContext) -> Element {
+        match self.slow_fallback {
+            Some(slow_fallback) if suspense_context.is_slow() => {
+                slow_fallback.call(suspense_context)
+            }
+            _ => self.fallback.call(suspense_context),
+        }
+    }
+
     /// Try to downcast [`AnyProps`] to [`SuspenseBoundaryProps`]
     pub(crate) fn downcast_from_props(props: &mut dyn AnyProps) -> Option<&mut Self> {
         let inner: Option<&mut SuspenseBoundaryPropsWithOwner> = props.props_mut().downcast_mut();
@@ -338,7 +440,8 @@ impl SuspenseBoundaryProps {
                             )
                             .unwrap();
                         suspense_context.set_suspended_nodes(children.into());
-                        let suspense_placeholder = props.fallback.call(suspense_context);
+                        suspense_context.start_timers(props.timeout, props.deadline);
+                        let suspense_placeholder = props.render_fallback(suspense_context);
                         let nodes_created = suspense_placeholder.as_vnode().create(dom, parent, to);
                         (suspense_placeholder, nodes_created)
                     });
@@ -393,6 +496,7 @@ impl SuspenseBoundaryProps {
                 .unwrap()
                 .clone();
             suspense_context.inner.suspended_tasks.borrow_mut().clear();
+            suspense_context.stop_timers();
 
             // Get the parent of the suspense boundary to later create children with the right parent
             let currently_rendered = scope_state.last_rendered_node.as_ref().unwrap().clone();
@@ -453,9 +557,7 @@ impl SuspenseBoundaryProps {
 
             let last_rendered_node = scope.last_rendered_node.as_ref().unwrap().clone();
 
-            let Self {
-                fallback, children, ..
-            } = myself;
+            let children = myself.children.clone();
 
             let suspense_context = scope.state().suspense_boundary().unwrap().clone();
             let suspended_nodes = suspense_context.suspended_nodes();
@@ -470,7 +572,7 @@ impl SuspenseBoundaryProps {
                     let new_placeholder =
                         suspense_context.in_suspense_placeholder(&dom.runtime(), || {
                             let old_placeholder = last_rendered_node;
-                            let new_placeholder = fallback.call(suspense_context.clone());
+                            let new_placeholder = myself.render_fallback(suspense_context.clone());
 
                             old_placeholder.as_vnode().diff_node(
                                 new_placeholder.as_vnode(),
@@ -514,7 +616,8 @@ impl SuspenseBoundaryProps {
                     let old_children = last_rendered_node.as_vnode();
                     let new_children: VNode = children.into();
 
-                    let new_placeholder = fallback.call(suspense_context.clone());
+                    suspense_context.start_timers(myself.timeout, myself.deadline);
+                    let new_placeholder = myself.render_fallback(suspense_context.clone());
 
                     // Move the children to the background
                     let mount = old_children.mount.get();
@@ -550,6 +653,7 @@ impl SuspenseBoundaryProps {
                 (Some(_), false) => {
                     // Take the suspended nodes out of the suspense boundary so the children know that the boundary is not suspended while diffing
                     let old_suspended_nodes = suspense_context.take_suspended_nodes().unwrap();
+                    suspense_context.stop_timers();
                     let old_placeholder = last_rendered_node;
                     let new_children = children;
 
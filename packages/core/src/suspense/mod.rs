@@ -29,8 +29,9 @@ pub use component::*;
 use crate::innerlude::*;
 use std::{
     cell::{Cell, Ref, RefCell},
-    fmt::Debug,
+    fmt::{Debug, Display},
     rc::Rc,
+    time::Duration,
 };
 
 /// A task that has been suspended which may have an optional loading placeholder
@@ -102,6 +103,8 @@ impl SuspenseContext {
                 id: Cell::new(ScopeId::ROOT),
                 suspended_nodes: Default::default(),
                 frozen: Default::default(),
+                slow: Default::default(),
+                timer: Default::default(),
             }),
         }
     }
@@ -175,6 +178,61 @@ impl SuspenseContext {
         })
     }
 
+    /// Check if the boundary has been suspended for longer than the `timeout` of the [`SuspenseBoundary`]. While the
+    /// boundary is slow, the `slow_fallback` is rendered instead of the `fallback`.
+    pub fn is_slow(&self) -> bool {
+        self.inner.slow.get()
+    }
+
+    /// Start the timers for the timeout and deadline of the boundary when it becomes suspended
+    pub(crate) fn start_timers(&self, timeout: Option<Duration>, deadline: Option<Duration>) {
+        if timeout.is_none() && deadline.is_none() {
+            return;
+        }
+        let std::result::Result::Ok(runtime) = Runtime::current() else {
+            return;
+        };
+        let Some(scope) = runtime.get_state(self.inner.id.get()) else {
+            return;
+        };
+        if let Some(task) = self.inner.timer.take() {
+            task.cancel();
+        }
+
+        let context = self.clone();
+        // Start the timers now instead of when the task is first polled so they measure the time since the boundary suspended
+        let slow = timeout
+            .filter(|timeout| deadline.is_none_or(|deadline| *timeout < deadline))
+            .map(crate::sleep);
+        let deadline = deadline.map(|deadline| (deadline, crate::sleep(deadline)));
+        // The timers need to run on the server as well so they can show the slow fallback and escalate to an error
+        // boundary while streaming
+        let task = scope.spawn_isomorphic(async move {
+            if let Some(slow) = slow {
+                slow.await;
+                context.inner.slow.set(true);
+                context.inner.id.get().needs_update();
+            }
+            if let Some((deadline, delay)) = deadline {
+                delay.await;
+                context
+                    .inner
+                    .id
+                    .get()
+                    .throw_error(SuspenseDeadlineExceeded { deadline });
+            }
+        });
+        self.inner.timer.set(Some(task));
+    }
+
+    /// Stop the timers for the boundary after it resolves
+    pub(crate) fn stop_timers(&self) {
+        if let Some(task) = self.inner.timer.take() {
+            task.cancel();
+        }
+        self.inner.slow.set(false);
+    }
+
     /// Get the first suspended task with a loading placeholder
     pub fn suspense_placeholder(&self) -> Option<Element> {
         self.inner
@@ -195,8 +253,32 @@ pub struct SuspenseBoundaryInner {
     suspended_nodes: RefCell<Option<VNode>>,
     /// On the server, you can only resolve a suspense boundary once. This is used to track if the suspense boundary has been resolved and if it should be frozen
     frozen: Cell<bool>,
+    /// If the boundary has been suspended for longer than its timeout
+    slow: Cell<bool>,
+    /// The task that tracks the timeout and deadline of the boundary while it is suspended
+    timer: Cell<Option<Task>>,
+}
+
+/// The error thrown from a [`SuspenseBoundary`] that was suspended for longer than its `deadline`. The error is caught
+/// by the nearest error boundary above the suspense boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SuspenseDeadlineExceeded {
+    /// The deadline of the suspense boundary
+    pub deadline: Duration,
 }
 
+impl Display for SuspenseDeadlineExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Suspense boundary did not resolve before its deadline of {:?}",
+            self.deadline
+        )
+    }
+}
+
+impl std::error::Error for SuspenseDeadlineExceeded {}
+
 /// Provides context methods to [`Result<T, RenderError>`] to show loading indicators for suspended results
 ///
 /// This trait is sealed and cannot be implemented outside of dioxus-core
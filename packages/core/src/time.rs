@@ -0,0 +1,224 @@
+use crate::Runtime;
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    fmt,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+use web_time::Instant;
+
+/// The source of time for the timers in the virtual dom.
+///
+/// Suspense timeouts, debounced writes, route transitions and any future that awaits [`sleep`] read time from the
+/// clock of the [`VirtualDom`](crate::VirtualDom) they run in. The default [`SystemClock`] uses real time. Tests can
+/// swap it for a [`MockClock`] with [`VirtualDom::with_clock`](crate::VirtualDom::with_clock) to control time.
+pub trait Clock {
+    /// The current time
+    fn now(&self) -> Instant;
+
+    /// Create a future that resolves once the clock reaches the deadline
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Future<Output = ()>>>;
+}
+
+/// A [`Clock`] that follows real time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Future<Output = ()>>> {
+        let duration = deadline.saturating_duration_since(Instant::now());
+        Box::pin(futures_timer::Delay::new(duration))
+    }
+}
+
+/// A [`Clock`] that only moves forward when you advance it.
+///
+/// Clones of a mock clock share the same time, so you can keep a clone in your test after passing one to the
+/// [`VirtualDom`](crate::VirtualDom):
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_core::MockClock;
+/// # use std::time::Duration;
+/// let clock = MockClock::new();
+/// let dom = VirtualDom::new(|| rsx! {}).with_clock(clock.clone());
+///
+/// // Resolve every sleep that ends in the next second
+/// clock.advance(Duration::from_secs(1));
+/// ```
+#[derive(Clone)]
+pub struct MockClock {
+    inner: Rc<MockClockInner>,
+}
+
+struct MockClockInner {
+    start: Instant,
+    elapsed: Cell<Duration>,
+    // The wakers of pending sleeps, ordered by their deadline and then by when they were created
+    timers: RefCell<BTreeMap<(Instant, u64), Waker>>,
+    next_timer: Cell<u64>,
+}
+
+impl MockClock {
+    /// Create a new mock clock that starts at the current time
+    pub fn new() -> Self {
+        Self {
+            inner: Rc::new(MockClockInner {
+                start: Instant::now(),
+                elapsed: Cell::new(Duration::ZERO),
+                timers: RefCell::new(BTreeMap::new()),
+                next_timer: Cell::new(0),
+            }),
+        }
+    }
+
+    /// How far the clock has been advanced since it was created
+    pub fn elapsed(&self) -> Duration {
+        self.inner.elapsed.get()
+    }
+
+    /// Move the clock forward and wake every sleep whose deadline has passed.
+    ///
+    /// The sleeps are only woken. The futures that await them run the next time the virtual dom polls its tasks.
+    pub fn advance(&self, duration: Duration) {
+        self.inner.elapsed.set(self.inner.elapsed.get() + duration);
+
+        let now = self.now();
+        let ready = {
+            let mut timers = self.inner.timers.borrow_mut();
+            let pending = timers.split_off(&(now, u64::MAX));
+            std::mem::replace(&mut *timers, pending)
+        };
+        for waker in ready.into_values() {
+            waker.wake();
+        }
+    }
+
+    /// The earliest deadline of the sleeps that are still pending
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.inner
+            .timers
+            .borrow()
+            .keys()
+            .next()
+            .map(|(deadline, _)| *deadline)
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for MockClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockClock")
+            .field("elapsed", &self.elapsed())
+            .field("pending_timers", &self.inner.timers.borrow().len())
+            .finish()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.inner.start + self.inner.elapsed.get()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Future<Output = ()>>> {
+        let id = self.inner.next_timer.get();
+        self.inner.next_timer.set(id + 1);
+        Box::pin(MockSleep {
+            clock: self.clone(),
+            key: (deadline, id),
+        })
+    }
+}
+
+struct MockSleep {
+    clock: MockClock,
+    key: (Instant, u64),
+}
+
+impl Future for MockSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.clock.now() >= self.key.0 {
+            return Poll::Ready(());
+        }
+        self.clock
+            .inner
+            .timers
+            .borrow_mut()
+            .insert(self.key, cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for MockSleep {
+    fn drop(&mut self) {
+        self.clock.inner.timers.borrow_mut().remove(&self.key);
+    }
+}
+
+/// A future returned by [`sleep`] that resolves once the clock of the virtual dom has advanced by the duration
+pub struct Sleep {
+    inner: Pin<Box<dyn Future<Output = ()>>>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.inner.as_mut().poll(cx)
+    }
+}
+
+/// Wait for a duration on the clock of the current virtual dom.
+///
+/// The time is measured from when `sleep` is called, not when the future is first polled. Outside of a virtual dom,
+/// this uses the [`SystemClock`].
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use std::time::Duration;
+/// fn Toast() -> Element {
+///     let mut visible = use_signal(|| true);
+///     use_future(move || async move {
+///         dioxus_core::sleep(Duration::from_secs(3)).await;
+///         visible.set(false);
+///     });
+///
+///     rsx! {
+///         if visible() {
+///             "Saved!"
+///         }
+///     }
+/// }
+/// ```
+pub fn sleep(duration: Duration) -> Sleep {
+    let clock = current_clock();
+    Sleep {
+        inner: clock.sleep_until(clock.now() + duration),
+    }
+}
+
+/// Get the current time on the clock of the current virtual dom. Outside of a virtual dom, this is the real time.
+pub fn now() -> Instant {
+    current_clock().now()
+}
+
+fn current_clock() -> Rc<dyn Clock> {
+    Runtime::current()
+        .map(|runtime| runtime.clock())
+        .unwrap_or_else(|_| Rc::new(SystemClock))
+}
@@ -7,10 +7,13 @@ use crate::properties::RootProps;
 use crate::root_wrapper::RootScopeWrapper;
 use crate::{
     arena::ElementId,
-    innerlude::{NoOpMutations, SchedulerMsg, ScopeOrder, ScopeState, VProps, WriteMutations},
+    innerlude::{
+        Clock, NoOpMutations, ProfileSpanKind, SchedulerMsg, ScopeOrder, ScopeState, VProps,
+        WriteMutations,
+    },
     runtime::{Runtime, RuntimeGuard},
     scopes::ScopeId,
-    ComponentFunction, Element, Mutations,
+    CapturedError, ComponentFunction, Element, Mutations,
 };
 use crate::{Task, VComponent};
 use futures_util::StreamExt;
@@ -295,7 +298,7 @@ impl VirtualDom {
         root_props: P,
     ) -> Self {
         let render_fn = root.id();
-        let props = VProps::new(root, |_, _| true, root_props, "Root");
+        let props = VProps::new(root, |_, _| true, |_| Vec::new(), root_props, "Root");
         Self::new_with_component(VComponent {
             name: "root",
             render_fn,
@@ -326,6 +329,7 @@ impl VirtualDom {
         let root = VProps::new(
             RootScopeWrapper,
             |_, _| true,
+            |_| Vec::new(),
             RootProps(root),
             "RootWrapper",
         );
@@ -363,6 +367,23 @@ impl VirtualDom {
         self
     }
 
+    /// Build the virtualdom with a listener that is called with every error thrown in the app, even errors that are
+    /// handled by an error boundary
+    ///
+    /// This is useful for reporting errors to a logging or error tracking service
+    pub fn with_error_listener(self, listener: impl Fn(&CapturedError) + 'static) -> Self {
+        self.runtime.add_error_listener(Rc::new(listener));
+        self
+    }
+
+    /// Build the virtualdom with a clock that timers read time from instead of the [`SystemClock`](crate::SystemClock)
+    ///
+    /// This is useful for testing timeouts and debounces with a [`MockClock`](crate::MockClock) instead of waiting in real time
+    pub fn with_clock(self, clock: impl Clock + 'static) -> Self {
+        self.runtime.set_clock(Rc::new(clock));
+        self
+    }
+
     /// Provide a context to the root scope
     pub fn provide_root_context<T: Clone + 'static>(&self, context: T) {
         self.base_scope().state().provide_context(context);
@@ -511,7 +532,8 @@ impl VirtualDom {
 
             // At this point, we have finished running all tasks that are pending and we haven't found any scopes to rerun. This means it is safe to run our lowest priority work: effects
             while let Some(effect) = self.pop_effect() {
-                effect.run();
+                self.runtime
+                    .profile(ProfileSpanKind::Effect, effect.order.id, || effect.run());
                 // Check if any new scopes are queued for rerun
                 self.queue_events();
                 if self.has_dirty_scopes() {
@@ -573,6 +595,8 @@ impl VirtualDom {
         let m = self.create_scope(Some(to), ScopeId::ROOT, new_nodes, None);
 
         to.append_children(ElementId(0), m);
+
+        self.record_inspected_mounts();
     }
 
     /// Render whatever the VirtualDom has ready as fast as possible without requiring an executor to progress
@@ -604,6 +628,7 @@ impl VirtualDom {
         }
 
         self.runtime.finish_render();
+        self.record_inspected_mounts();
     }
 
     /// [`Self::render_immediate`] to a vector of mutations for testing purposes
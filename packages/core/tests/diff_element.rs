@@ -206,3 +206,38 @@ fn diff_empty() {
         ]
     )
 }
+
+#[test]
+fn typed_style_diffs_each_property() {
+    use dioxus::html::css::*;
+
+    fn app() -> Element {
+        let style = match generation() % 2 {
+            0 => Style::new().display(Display::Flex).gap(px(8)),
+            _ => Style::new().display(Display::Flex).width(percent(50)),
+        };
+        rsx!(div { style })
+    }
+
+    let mut vdom = VirtualDom::new(app);
+    vdom.rebuild(&mut NoOpMutations);
+
+    vdom.mark_dirty(ScopeId::APP);
+    assert_eq!(
+        vdom.render_immediate_to_vec().edits,
+        [
+            SetAttribute {
+                name: "gap",
+                value: AttributeValue::None,
+                id: ElementId(1),
+                ns: Some("style")
+            },
+            SetAttribute {
+                name: "width",
+                value: AttributeValue::Text("50%".to_string()),
+                id: ElementId(1),
+                ns: Some("style")
+            },
+        ]
+    );
+}
@@ -445,3 +445,42 @@ fn old_middle_empty_right_pivot() {
         ]
     );
 }
+
+#[test]
+fn keyed_diffing_reports_transitions() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let transitions = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new_with_props(
+        |transitions: Rc<RefCell<Vec<ListTransition>>>| {
+            use_list_transitions(move |new| transitions.borrow_mut().extend(new));
+
+            let order: &[_] = match generation() % 2 {
+                0 => &[0, 1, 2],
+                1 => &[2, 0, 3],
+                _ => unreachable!(),
+            };
+
+            rsx!({ order.iter().map(|i| rsx!(div { key: "{i}" })) })
+        },
+        transitions.clone(),
+    );
+
+    dom.rebuild_in_place();
+    assert!(transitions.borrow().is_empty());
+
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    // The transitions are reported in an effect after the mutations are applied
+    assert!(transitions.borrow().is_empty());
+    dom.process_events();
+    assert_eq!(
+        *transitions.borrow(),
+        [
+            ListTransition::Move { key: "2".to_string(), from: 2, to: 0 },
+            ListTransition::Move { key: "0".to_string(), from: 0, to: 1 },
+            ListTransition::Enter { key: "3".to_string(), index: 2 },
+            ListTransition::Leave { key: "1".to_string(), index: 1 },
+        ]
+    );
+}
@@ -30,3 +30,112 @@ fn ThrowChild() -> Element {
 
     rsx! { div {} }
 }
+
+#[test]
+fn retry_remounts_children() {
+    thread_local! {
+        static RENDERS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+        static BOUNDARY: std::cell::RefCell<Option<ErrorContext>> = const { std::cell::RefCell::new(None) };
+    }
+
+    fn app() -> Element {
+        rsx! {
+            ErrorBoundary {
+                handle_error: |errors: ErrorContext| {
+                    BOUNDARY.with(|b| *b.borrow_mut() = Some(errors));
+                    rsx! { "error" }
+                },
+                FailsOnce {}
+            }
+        }
+    }
+
+    fn FailsOnce() -> Element {
+        let renders = RENDERS.with(|r| {
+            r.set(r.get() + 1);
+            r.get()
+        });
+        if renders == 1 {
+            Err(std::io::Error::other("first render"))?;
+        }
+        rsx! { "ok" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "error");
+
+    let boundary = BOUNDARY.with(|b| b.borrow_mut().take()).unwrap();
+    dom.in_runtime(|| boundary.retry());
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(RENDERS.with(|r| r.get()), 2);
+    assert_eq!(dioxus_ssr::render(&dom), "ok");
+}
+
+#[test]
+fn error_listeners_see_handled_errors() {
+    let errors = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new(app).with_error_listener({
+        let errors = errors.clone();
+        move |error| errors.borrow_mut().push(error.to_string())
+    });
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    let errors = errors.borrow();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("asd"));
+}
+
+#[test]
+fn errors_outside_of_boundaries_are_unhandled() {
+    fn app() -> Element {
+        rsx! {
+            ErrorBoundary {
+                handle_error: |_| rsx! { "handled" },
+                Thrower { message: "caught by the boundary" }
+            }
+            Thrower { message: "reaches the root" }
+        }
+    }
+
+    #[component]
+    fn Thrower(message: String) -> Element {
+        Err(std::io::Error::other(message))?;
+        rsx! { div {} }
+    }
+
+    let errors = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new(app).with_error_listener({
+        let errors = errors.clone();
+        move |error: &dioxus_core::CapturedError| {
+            let stack: Vec<_> = error
+                .origin()
+                .component_stack()
+                .into_iter()
+                .map(|name| name.rsplit("::").next().unwrap())
+                .collect();
+            errors
+                .borrow_mut()
+                .push((error.to_string(), error.is_unhandled(), stack))
+        }
+    });
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    let errors = errors.borrow();
+    assert_eq!(errors.len(), 2);
+    let inside = errors
+        .iter()
+        .find(|(error, ..)| error.contains("caught by the boundary"))
+        .unwrap();
+    assert!(!inside.1);
+    assert_eq!(inside.2[..2], ["Thrower", "ErrorBoundary"]);
+    let outside = errors
+        .iter()
+        .find(|(error, ..)| error.contains("reaches the root"))
+        .unwrap();
+    assert!(outside.1);
+    assert_eq!(outside.2[0], "Thrower");
+    assert_eq!(outside.2.len(), inside.2.len() - 1);
+}
@@ -0,0 +1,180 @@
+//! Passive, throttled and debounced event handlers
+
+use dioxus::prelude::*;
+use dioxus_core::{ElementId, MockClock, Mutation, NoOpMutations};
+use dioxus_ssr::testing::settle;
+use std::{any::Any, cell::RefCell, rc::Rc, time::Duration};
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+fn click(dom: &VirtualDom, id: usize) {
+    let event = Event::new(
+        Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())) as Rc<dyn Any>,
+        true,
+    );
+    dom.runtime().handle_event("click", event, ElementId(id));
+}
+
+fn app() -> Element {
+    let log = use_context::<Log>();
+    let throttled = {
+        let log = log.clone();
+        move |_: MouseEvent| log.borrow_mut().push("throttled")
+    };
+    let debounced = move |_: MouseEvent| log.borrow_mut().push("debounced");
+
+    rsx! {
+        div { onclick: throttled.passive().throttle(Duration::from_millis(100)) }
+        div { onclick: debounced.debounce(Duration::from_millis(100)) }
+    }
+}
+
+#[test]
+fn passive_listeners_use_the_event_name() {
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+
+    let mut dom = VirtualDom::new(app).with_clock(MockClock::new());
+    dom.provide_root_context(Log::default());
+    let mutations = dom.rebuild_to_vec();
+
+    let listeners = mutations
+        .edits
+        .iter()
+        .filter(|edit| matches!(edit, Mutation::NewEventListener { name, .. } if name == "click"))
+        .count();
+    assert_eq!(listeners, 2);
+}
+
+#[test]
+fn listener_options_are_kept_in_the_attribute_name() {
+    fn app() -> Element {
+        rsx! {
+            div {
+                onclick: (|_| {}).direct(),
+                onscroll: (|_| {}).passive().direct(),
+                onfocuscapture: (|_| {}).passive(),
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+    let scope = dom.get_scope(ScopeId::APP).unwrap();
+    let names: Vec<_> = scope
+        .root_node()
+        .dynamic_attrs
+        .iter()
+        .flatten()
+        .map(|attr| attr.name)
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "onclickdirect",
+            "onscrollpassivedirect",
+            "onfocuscapturepassive"
+        ]
+    );
+}
+
+#[test]
+fn throttled_handlers_run_at_most_once_per_interval() {
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+
+    let log = Log::default();
+    let clock = MockClock::new();
+    let mut dom = VirtualDom::new(app).with_clock(clock.clone());
+    dom.provide_root_context(log.clone());
+    dom.rebuild(&mut NoOpMutations);
+
+    // The first event runs immediately and the rest wait for the end of the interval
+    for _ in 0..3 {
+        click(&dom, 1);
+    }
+    settle(&mut dom, &mut NoOpMutations);
+    assert_eq!(*log.borrow(), ["throttled"]);
+
+    clock.advance(Duration::from_millis(100));
+    settle(&mut dom, &mut NoOpMutations);
+    assert_eq!(*log.borrow(), ["throttled", "throttled"]);
+
+    clock.advance(Duration::from_millis(100));
+    settle(&mut dom, &mut NoOpMutations);
+    assert_eq!(log.borrow().len(), 2);
+}
+
+#[test]
+fn debounced_handlers_wait_for_events_to_stop() {
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+
+    let log = Log::default();
+    let clock = MockClock::new();
+    let mut dom = VirtualDom::new(app).with_clock(clock.clone());
+    dom.provide_root_context(log.clone());
+    dom.rebuild(&mut NoOpMutations);
+
+    for _ in 0..3 {
+        click(&dom, 2);
+        settle(&mut dom, &mut NoOpMutations);
+        clock.advance(Duration::from_millis(50));
+        settle(&mut dom, &mut NoOpMutations);
+    }
+    assert!(log.borrow().is_empty());
+
+    clock.advance(Duration::from_millis(50));
+    settle(&mut dom, &mut NoOpMutations);
+    assert_eq!(*log.borrow(), ["debounced"]);
+}
+
+#[test]
+fn custom_event_listeners_are_direct() {
+    fn app() -> Element {
+        rsx! {
+            design-select { "onitem-selected": custom_event(|_| {}) }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+    let scope = dom.get_scope(ScopeId::APP).unwrap();
+    let root = scope.root_node();
+    let attr = root.dynamic_attrs.iter().flatten().next().unwrap();
+    assert_eq!(attr.name, "onitem-selected");
+    let dioxus_core::AttributeValue::Listener(listener) = &attr.value else {
+        panic!("expected a listener");
+    };
+    assert!(listener.listener_options().direct);
+}
+
+#[test]
+fn debounced_callbacks_wait_for_calls_to_stop() {
+    thread_local! {
+        static CALLBACK: std::cell::Cell<Option<Callback<i32>>> = const { std::cell::Cell::new(None) };
+    }
+
+    fn app() -> Element {
+        let log = use_context::<Rc<RefCell<Vec<i32>>>>();
+        let onchange = use_callback(move |value: i32| log.borrow_mut().push(value));
+        CALLBACK.set(Some(use_hook(|| {
+            onchange.debounced(Duration::from_millis(100))
+        })));
+        rsx! {}
+    }
+
+    let log = Rc::new(RefCell::new(Vec::<i32>::new()));
+    let clock = MockClock::new();
+    let mut dom = VirtualDom::new(app).with_clock(clock.clone());
+    dom.provide_root_context(log.clone());
+    dom.rebuild(&mut NoOpMutations);
+
+    let debounced = CALLBACK.get().unwrap();
+    for value in 1..=3 {
+        debounced(value);
+    }
+    settle(&mut dom, &mut NoOpMutations);
+    assert!(log.borrow().is_empty());
+
+    clock.advance(Duration::from_millis(100));
+    settle(&mut dom, &mut NoOpMutations);
+    assert_eq!(*log.borrow(), [3]);
+}
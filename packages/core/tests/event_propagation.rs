@@ -77,3 +77,81 @@ fn problematic_child() -> Element {
             } }
     }
 }
+
+thread_local! {
+    static CALLS: std::cell::RefCell<Vec<&'static str>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+fn record(call: &'static str) {
+    CALLS.with(|calls| calls.borrow_mut().push(call));
+}
+
+fn take_calls() -> Vec<&'static str> {
+    CALLS.with(|calls| std::mem::take(&mut *calls.borrow_mut()))
+}
+
+#[test]
+fn capture_listeners_run_before_bubbling_listeners() {
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+
+    let mut dom = VirtualDom::new(capture_app);
+    let mutations = dom.rebuild_to_vec();
+
+    // Renderers only see the event name without the capture suffix
+    assert!(mutations.edits.iter().any(|edit| matches!(
+        edit,
+        dioxus_core::Mutation::NewEventListener { name, .. } if name == "click"
+    )));
+    assert!(!mutations.edits.iter().any(|edit| matches!(
+        edit,
+        dioxus_core::Mutation::NewEventListener { name, .. } if name.ends_with("capture")
+    )));
+
+    let click = || {
+        Event::new(
+            Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())) as Rc<dyn Any>,
+            true,
+        )
+    };
+
+    dom.runtime().handle_event("click", click(), ElementId(3));
+    assert_eq!(
+        take_calls(),
+        [
+            "outer capture",
+            "inner capture",
+            "button",
+            "inner bubble",
+            "outer bubble"
+        ]
+    );
+
+    // Stopping the event while capturing keeps it from reaching the target
+    let event = click();
+    dom.runtime()
+        .handle_event("click", event.clone(), ElementId(3));
+    assert!(event.propagation_stopped());
+    assert_eq!(take_calls(), ["outer capture", "inner capture"]);
+}
+
+fn capture_app() -> Element {
+    let mut clicks = use_signal(|| 0);
+
+    rsx! {
+        div {
+            onclickcapture: move |_| record("outer capture"),
+            onclick: move |_| record("outer bubble"),
+            div {
+                onclickcapture: move |evt| {
+                    record("inner capture");
+                    if clicks() > 0 {
+                        evt.stop_propagation();
+                    }
+                    clicks += 1;
+                },
+                onclick: move |_| record("inner bubble"),
+                button { onclick: move |_| record("button") }
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Frame callbacks run in the next frame of the frame source, with layout reads before writes
+
+use dioxus::prelude::*;
+use dioxus_core::{
+    next_frame, schedule_before_paint, schedule_layout_read, MockClock, NoOpMutations,
+    DEFAULT_FRAME_INTERVAL,
+};
+use dioxus_ssr::testing::settle;
+use std::{cell::RefCell, rc::Rc};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+fn advance_one_frame(dom: &mut VirtualDom, clock: &MockClock) {
+    clock.advance(DEFAULT_FRAME_INTERVAL);
+    settle(dom, &mut NoOpMutations);
+}
+
+#[test]
+fn reads_run_before_writes() {
+    fn app() -> Element {
+        let log = use_context::<Log>();
+        use_hook(|| {
+            let write_log = log.clone();
+            schedule_before_paint(move || {
+                write_log.borrow_mut().push("write".to_string());
+                // Work scheduled while the frame runs waits for the next frame
+                let write_log = write_log.clone();
+                schedule_layout_read(move || write_log.borrow_mut().push("next read".to_string()));
+            });
+            let read_log = log.clone();
+            schedule_layout_read(move || read_log.borrow_mut().push("read".to_string()));
+            spawn(async move {
+                next_frame().await;
+                log.borrow_mut().push("task".to_string());
+            });
+        });
+        rsx! {}
+    }
+
+    let log = Log::default();
+    let clock = MockClock::new();
+    let mut dom = VirtualDom::new(app).with_clock(clock.clone());
+    dom.provide_root_context(log.clone());
+    dom.rebuild(&mut NoOpMutations);
+    settle(&mut dom, &mut NoOpMutations);
+    assert!(log.borrow().is_empty());
+
+    advance_one_frame(&mut dom, &clock);
+    assert_eq!(*log.borrow(), ["read", "write", "task"]);
+
+    advance_one_frame(&mut dom, &clock);
+    assert_eq!(*log.borrow(), ["read", "write", "task", "next read"]);
+}
+
+#[test]
+fn raf_runs_every_frame_until_dropped() {
+    fn app() -> Element {
+        let mut show = use_signal(|| true);
+        let log = use_context::<Log>();
+        use_hook(|| {
+            spawn(async move {
+                for _ in 0..3 {
+                    next_frame().await;
+                }
+                show.set(false);
+            })
+        });
+        rsx! {
+            if show() {
+                Child { log }
+            }
+        }
+    }
+
+    #[component]
+    fn Child(log: Log) -> Element {
+        dioxus_core::use_raf(move |delta| log.borrow_mut().push(format!("{delta:?}")));
+        rsx! {}
+    }
+
+    let log = Log::default();
+    let clock = MockClock::new();
+    let mut dom = VirtualDom::new(app).with_clock(clock.clone());
+    dom.provide_root_context(log.clone());
+    dom.rebuild(&mut NoOpMutations);
+    settle(&mut dom, &mut NoOpMutations);
+
+    for _ in 0..5 {
+        advance_one_frame(&mut dom, &clock);
+    }
+    // The child is removed in the third frame, and the callback stops with it
+    let frame = format!("{DEFAULT_FRAME_INTERVAL:?}");
+    assert_eq!(*log.borrow(), [frame.clone(), frame]);
+}
@@ -0,0 +1,65 @@
+//! Tests for inspecting the components in a virtual dom.
+use dioxus::prelude::*;
+use dioxus_core::{ElementId, NoOpMutations};
+
+#[test]
+fn inspect_components() {
+    fn app() -> Element {
+        let mut count = use_signal(|| 0);
+        use_inspected_value("count", move || count.peek().to_string());
+        use_hook(move || count += 1);
+
+        rsx! {
+            div {
+                Child { name: "ada", count }
+            }
+        }
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct NotDebug;
+
+    #[component]
+    fn Child(
+        name: &'static str,
+        count: ReadOnlySignal<i32>,
+        #[props(default)] hidden: Option<NotDebug>,
+    ) -> Element {
+        _ = hidden;
+        rsx! { "{name}: {count}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    // Props are only recorded after inspection is enabled
+    let runtime = dom.runtime();
+    assert!(runtime
+        .inspect_components()
+        .iter()
+        .all(|component| component.props.is_empty() && component.roots.is_empty()));
+
+    runtime.enable_inspection();
+    dom.render_immediate(&mut NoOpMutations);
+
+    let components = runtime.inspect_components();
+    let app = components.iter().find(|c| c.id == ScopeId::APP).unwrap();
+    assert_eq!(app.values, [InspectedValue::new("count", "1")]);
+    assert_eq!(app.roots, [ElementId(1)]);
+
+    let child = components
+        .iter()
+        .find(|c| c.name.ends_with("Child"))
+        .unwrap();
+    assert_eq!(child.parent, Some(ScopeId::APP));
+    assert_eq!(child.height, app.height + 1);
+    assert_eq!(child.render_count, 2);
+    assert_eq!(
+        child.props,
+        [
+            InspectedValue::new("name", "\"ada\""),
+            InspectedValue::new("count", "1"),
+            InspectedValue::new("hidden", std::any::type_name::<Option<NotDebug>>()),
+        ]
+    );
+}
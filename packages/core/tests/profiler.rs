@@ -0,0 +1,105 @@
+//! Tests for profiling the work the virtual dom does.
+use dioxus::prelude::*;
+use dioxus_core::{NoOpMutations, ProfileSpanKind, RenderProfile};
+
+fn app() -> Element {
+    let renders = use_render_count();
+    use_hook(|| queue_effect(|| {}));
+    rsx! {
+        div { "{renders}" }
+        Child {}
+    }
+}
+
+#[component]
+fn Child() -> Element {
+    rsx! { "child" }
+}
+
+fn profile_rebuild() -> RenderProfile {
+    let mut dom = VirtualDom::new(app);
+    let runtime = dom.runtime();
+    runtime.start_profiling();
+    dom.rebuild_in_place();
+    for _ in 0..2 {
+        dom.mark_dirty(ScopeId::APP);
+        dom.render_immediate(&mut NoOpMutations);
+    }
+    dom.process_events();
+    let profile = runtime.stop_profiling().unwrap();
+    assert!(!runtime.is_profiling());
+    profile
+}
+
+#[test]
+fn records_nested_spans() {
+    let profile = profile_rebuild();
+    let spans = &profile.spans;
+    let find = |kind: ProfileSpanKind, name: &str| {
+        spans
+            .iter()
+            .position(|span| span.kind == kind && span.name.ends_with(name))
+            .unwrap()
+    };
+
+    // The child is rendered and created while the app is diffed
+    let app_diff = find(ProfileSpanKind::Diff, "root");
+    let child_render = find(ProfileSpanKind::Render, "Child");
+    let child_diff = find(ProfileSpanKind::Diff, "Child");
+    assert_eq!(spans[child_render].parent, Some(app_diff));
+    assert_eq!(spans[child_diff].parent, Some(app_diff));
+    assert!(spans[app_diff].duration >= spans[child_render].duration);
+
+    let effect = find(ProfileSpanKind::Effect, "root");
+    assert_eq!(spans[effect].scope, ScopeId::APP);
+    assert_eq!(spans[effect].parent, None);
+
+    let components = profile.components();
+    let app = components.iter().find(|c| c.scope == ScopeId::APP).unwrap();
+    assert_eq!(app.renders, 3);
+    let child = components
+        .iter()
+        .find(|c| c.name.ends_with("Child"))
+        .unwrap();
+    assert_eq!(child.renders, 1);
+}
+
+#[test]
+fn exports_traces() {
+    let profile = profile_rebuild();
+
+    let trace = profile.to_chrome_trace();
+    assert!(trace.starts_with("{\"traceEvents\":["));
+    assert_eq!(trace.matches("\"ph\":\"X\"").count(), profile.spans.len());
+    assert!(trace.contains("\"cat\":\"effect\""));
+
+    let folded = profile.to_folded_stacks();
+    let stacks: Vec<_> = folded
+        .lines()
+        .map(|line| line.rsplit_once(' ').unwrap().0)
+        .collect();
+    assert!(stacks
+        .iter()
+        .any(|stack| stack.contains("root (diff);") && stack.ends_with("Child (render)")));
+    // The app rerenders twice at the top level but the stack is only written once
+    let app_rerenders = stacks
+        .iter()
+        .filter(|stack| **stack == "root (render)")
+        .count();
+    assert_eq!(app_rerenders, 1);
+}
+
+#[test]
+fn render_count_counts_every_run() {
+    let mut dom = VirtualDom::new(|| {
+        let renders = use_render_count();
+        assert_eq!(renders, generation() + 1);
+        rsx! { "{renders}" }
+    });
+    dom.rebuild_in_place();
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId::APP);
+        dom.render_immediate(&mut NoOpMutations);
+    }
+    assert_eq!(dioxus_ssr::render(&dom), "4");
+}
@@ -930,3 +930,101 @@ fn nested_suspense_resolves_client() {
             )
         });
 }
+
+#[test]
+fn suspense_timeout_shows_slow_fallback_then_escalates() {
+    fn app() -> Element {
+        rsx! {
+            ErrorBoundary {
+                handle_error: |errors: ErrorContext| {
+                    let timed_out = errors
+                        .errors()
+                        .iter()
+                        .any(|error| error.to_string().contains("deadline"));
+                    rsx! { if timed_out { "timed out" } else { "other error" } }
+                },
+                SuspenseBoundary {
+                    fallback: |_| rsx! { "loading" },
+                    slow_fallback: |_| rsx! { "still loading" },
+                    timeout: std::time::Duration::from_millis(10),
+                    deadline: std::time::Duration::from_millis(50),
+                    NeverResolves {}
+                }
+            }
+        }
+    }
+
+    #[component]
+    fn NeverResolves() -> Element {
+        use_resource(std::future::pending::<()>).suspend()?;
+        rsx! { "resolved" }
+    }
+
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(app);
+            dom.rebuild(&mut dioxus_core::NoOpMutations);
+            assert_eq!(dioxus_ssr::render(&dom), "loading");
+
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            dom.wait_for_work().await;
+            dom.render_immediate(&mut dioxus_core::NoOpMutations);
+            assert_eq!(dioxus_ssr::render(&dom), "still loading");
+
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            dom.wait_for_work().await;
+            dom.render_immediate(&mut dioxus_core::NoOpMutations);
+            assert_eq!(dioxus_ssr::render(&dom), "timed out");
+        });
+}
+
+#[test]
+fn suspense_timeout_follows_mock_clock() {
+    fn app() -> Element {
+        rsx! {
+            ErrorBoundary {
+                handle_error: |_| rsx! { "timed out" },
+                SuspenseBoundary {
+                    fallback: |_| rsx! { "loading" },
+                    slow_fallback: |_| rsx! { "still loading" },
+                    timeout: std::time::Duration::from_secs(10),
+                    deadline: std::time::Duration::from_secs(60),
+                    NeverResolves {}
+                }
+            }
+        }
+    }
+
+    #[component]
+    fn NeverResolves() -> Element {
+        use_resource(std::future::pending::<()>).suspend()?;
+        rsx! { "resolved" }
+    }
+
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let clock = dioxus_core::MockClock::new();
+            let mut dom = VirtualDom::new(app).with_clock(clock.clone());
+            dom.rebuild(&mut dioxus_core::NoOpMutations);
+            assert_eq!(dioxus_ssr::render(&dom), "loading");
+
+            clock.advance(std::time::Duration::from_secs(10));
+            dom.wait_for_work().await;
+            dom.render_immediate(&mut dioxus_core::NoOpMutations);
+            assert_eq!(dioxus_ssr::render(&dom), "still loading");
+
+            clock.advance(std::time::Duration::from_secs(49));
+            dom.render_immediate(&mut dioxus_core::NoOpMutations);
+            assert_eq!(dioxus_ssr::render(&dom), "still loading");
+
+            clock.advance(std::time::Duration::from_secs(1));
+            dom.wait_for_work().await;
+            dom.render_immediate(&mut dioxus_core::NoOpMutations);
+            assert_eq!(dioxus_ssr::render(&dom), "timed out");
+        });
+}
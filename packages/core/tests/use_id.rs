@@ -0,0 +1,68 @@
+//! Ids from use_id are stable and match between the server and the client
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use std::cell::Cell;
+
+thread_local! {
+    static ITEMS: Cell<Option<Signal<usize>>> = const { Cell::new(None) };
+}
+
+fn app() -> Element {
+    let id = use_id();
+    let items = use_signal(|| 2);
+    ITEMS.set(Some(items));
+
+    rsx! {
+        label { r#for: "{id}", "Name" }
+        input { id: "{id}" }
+        for _ in 0..items() {
+            Field {}
+        }
+    }
+}
+
+#[component]
+fn Field() -> Element {
+    let input = use_id();
+    let hint = use_id();
+    rsx! {
+        input { id: "{input}", aria_describedby: "{hint}" }
+    }
+}
+
+#[test]
+fn ids_are_the_same_on_every_render_of_the_app() {
+    let render = || {
+        let mut dom = VirtualDom::new(app);
+        dom.rebuild_in_place();
+        dioxus_ssr::render(&dom)
+    };
+
+    let server = render();
+    assert_eq!(server, render());
+    assert_eq!(
+        server,
+        "<label for=\"dx-0-0-0-0\">Name</label><input id=\"dx-0-0-0-0\"/>\
+        <input id=\"dx-0-0-0-0-0\" aria-describedby=\"dx-0-0-0-0-1\"/>\
+        <input id=\"dx-0-0-0-1-0\" aria-describedby=\"dx-0-0-0-1-1\"/>"
+    );
+}
+
+#[test]
+fn ids_stay_the_same_for_the_lifetime_of_the_component() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    // Adding a field keeps the ids of the existing fields and gives the new field new ids
+    let mut items = ITEMS.get().unwrap();
+    dom.in_runtime(|| items.set(3));
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<label for=\"dx-0-0-0-0\">Name</label><input id=\"dx-0-0-0-0\"/>\
+        <input id=\"dx-0-0-0-0-0\" aria-describedby=\"dx-0-0-0-0-1\"/>\
+        <input id=\"dx-0-0-0-1-0\" aria-describedby=\"dx-0-0-0-1-1\"/>\
+        <input id=\"dx-0-0-0-2-0\" aria-describedby=\"dx-0-0-0-2-1\"/>"
+    );
+}
@@ -0,0 +1,107 @@
+use crate::{
+    report::{component_name, now},
+    Breadcrumb,
+};
+use dioxus_logger::{LogRecord, LogSink};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, PoisonError,
+    },
+};
+
+/// The number of breadcrumbs kept by default
+pub(crate) const DEFAULT_MAX_BREADCRUMBS: usize = 100;
+
+pub(crate) static MAX_BREADCRUMBS: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_BREADCRUMBS);
+
+static BREADCRUMBS: Mutex<VecDeque<Breadcrumb>> = Mutex::new(VecDeque::new());
+
+/// Record a breadcrumb that is sent with the next crash report. The breadcrumb is tagged with the component that is
+/// running. Only the most recent breadcrumbs are kept.
+///
+/// ```rust
+/// dioxus_crash_reporter::add_breadcrumb("navigation", "Opened the settings");
+/// ```
+pub fn add_breadcrumb(category: impl Into<String>, message: impl Into<String>) {
+    push(Breadcrumb {
+        timestamp: now(),
+        level: "info".to_string(),
+        category: category.into(),
+        message: message.into(),
+        component: dioxus_core::component_stack()
+            .first()
+            .map(|name| component_name(name)),
+    });
+}
+
+/// Get the breadcrumbs that were recorded, oldest first
+pub fn breadcrumbs() -> Vec<Breadcrumb> {
+    BREADCRUMBS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .cloned()
+        .collect()
+}
+
+/// Remove every breadcrumb that was recorded
+pub fn clear_breadcrumbs() {
+    BREADCRUMBS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clear();
+}
+
+/// Get the breadcrumbs from a panic hook. The panic may have happened while the breadcrumbs were locked on this
+/// thread, so this doesn't wait for the lock.
+pub(crate) fn try_breadcrumbs() -> Vec<Breadcrumb> {
+    match BREADCRUMBS.try_lock() {
+        Ok(breadcrumbs) => breadcrumbs.iter().cloned().collect(),
+        Err(std::sync::TryLockError::Poisoned(breadcrumbs)) => {
+            breadcrumbs.into_inner().iter().cloned().collect()
+        }
+        Err(std::sync::TryLockError::WouldBlock) => Vec::new(),
+    }
+}
+
+fn push(breadcrumb: Breadcrumb) {
+    let max = MAX_BREADCRUMBS.load(Ordering::Relaxed);
+    let mut breadcrumbs = BREADCRUMBS.lock().unwrap_or_else(PoisonError::into_inner);
+    breadcrumbs.push_back(breadcrumb);
+    while breadcrumbs.len() > max {
+        breadcrumbs.pop_front();
+    }
+}
+
+/// A [`LogSink`] that records every log as a breadcrumb. Logs written in the scope of
+/// [`use_logger`](dioxus_logger::use_logger) keep the component they were tagged with.
+///
+/// ```rust,no_run
+/// use dioxus_crash_reporter::BreadcrumbSink;
+/// use dioxus_logger::{tracing::Level, LoggerBuilder};
+///
+/// LoggerBuilder::new(Level::INFO)
+///     .sink(BreadcrumbSink)
+///     .init()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BreadcrumbSink;
+
+impl LogSink for BreadcrumbSink {
+    fn log(&self, record: &LogRecord) {
+        let mut message = record.message.clone();
+        for (key, value) in &record.fields {
+            message.push_str(&format!(" {key}={value}"));
+        }
+        push(Breadcrumb {
+            timestamp: now(),
+            level: record.level.as_str().to_lowercase(),
+            category: record.target.clone(),
+            message,
+            component: record.component.clone(),
+        });
+    }
+}
@@ -0,0 +1,34 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+mod breadcrumbs;
+mod report;
+mod reporter;
+mod transport;
+
+#[cfg(all(
+    feature = "minidump",
+    any(target_os = "windows", target_os = "macos", target_os = "linux")
+))]
+mod minidump;
+
+pub use breadcrumbs::{add_breadcrumb, breadcrumbs, clear_breadcrumbs, BreadcrumbSink};
+pub use report::{Breadcrumb, CrashKind, CrashReport};
+pub use reporter::CrashReporter;
+pub use transport::*;
+
+/// An error that occurred while installing the crash reporter
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Starting the process that captures minidumps failed
+    #[error("Failed to start the crash monitor: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The crash handler that captures minidumps could not be attached
+    #[error("Failed to capture minidumps: {0}")]
+    Minidump(String),
+}
+
+/// The result type of the crash reporter
+pub type Result<T, E = Error> = std::result::Result<T, E>;
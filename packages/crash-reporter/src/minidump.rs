@@ -0,0 +1,102 @@
+//! Capture native crashes as minidumps. A crashed process can't reliably inspect itself, so the app starts a copy of
+//! itself as a monitor that writes the minidump when the app crashes and sends the report.
+
+use crate::{report::now, CrashKind, CrashReporter, CrashTransport, Error, Result};
+use minidumper::{LoopAction, MinidumpBinary, ServerHandler};
+use std::{
+    fs::File,
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
+
+/// The environment variable that holds the name of the socket in the monitor process
+const MONITOR_ENV: &str = "DIOXUS_CRASH_MONITOR";
+
+pub(crate) fn start(dir: PathBuf, reporter: &CrashReporter) -> Result<()> {
+    // This process is the monitor. It waits for a crash of the app and exits with it.
+    if let Ok(name) = std::env::var(MONITOR_ENV) {
+        let monitor = Monitor {
+            dir,
+            transport: reporter.transport.clone(),
+            release: reporter.release.clone(),
+        };
+        let result = minidumper::Server::with_name(name.as_str())
+            .and_then(|mut server| server.run(Box::new(monitor), &AtomicBool::new(false), None));
+        std::process::exit(result.is_err() as i32);
+    }
+
+    let name = format!("dioxus-crash-{}", std::process::id());
+    let monitor = Command::new(std::env::current_exe()?)
+        .env(MONITOR_ENV, &name)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Give the monitor some time to start listening
+    let mut client = None;
+    for _ in 0..50 {
+        if let Ok(connected) = minidumper::Client::with_name(name.as_str()) {
+            client = Some(connected);
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    let client =
+        client.ok_or_else(|| Error::Minidump("the monitor process didn't start".into()))?;
+
+    let handler = crash_handler::CrashHandler::attach(unsafe {
+        crash_handler::make_crash_event(move |context: &crash_handler::CrashContext| {
+            crash_handler::CrashEventResult::Handled(client.request_dump(context).is_ok())
+        })
+    })
+    .map_err(|err| Error::Minidump(err.to_string()))?;
+
+    // Linux only lets the monitor read the memory of the app if the app allows it
+    #[cfg(target_os = "linux")]
+    handler.set_ptracer(Some(monitor.id()));
+    #[cfg(not(target_os = "linux"))]
+    drop(monitor);
+
+    // The handler is detached when it is dropped. Crashes are captured until the app exits.
+    std::mem::forget(handler);
+
+    Ok(())
+}
+
+struct Monitor {
+    dir: PathBuf,
+    transport: Arc<dyn CrashTransport>,
+    release: Option<String>,
+}
+
+impl ServerHandler for Monitor {
+    fn create_minidump_file(&self) -> std::io::Result<(File, PathBuf)> {
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("{}.dmp", now()));
+        Ok((File::create(&path)?, path))
+    }
+
+    fn on_minidump_created(&self, result: Result<MinidumpBinary, minidumper::Error>) -> LoopAction {
+        let mut report = crate::CrashReport::new(CrashKind::Native, "The app crashed");
+        report.release = self.release.clone();
+        match result {
+            Ok(binary) => {
+                report.minidump = binary.contents.or_else(|| std::fs::read(&binary.path).ok());
+            }
+            Err(err) => report.message = format!("The app crashed, but the minidump failed: {err}"),
+        }
+        self.transport.send(&report);
+
+        LoopAction::Exit
+    }
+
+    fn on_message(&self, _kind: u32, _buffer: Vec<u8>) {}
+
+    // The app exited without crashing
+    fn on_client_disconnected(&self, _num_clients: usize) -> LoopAction {
+        LoopAction::Exit
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+/// A crash of the app, with everything that was known about the app when it crashed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// What kind of crash this is
+    pub kind: CrashKind,
+    /// The panic message, or a description of the native crash
+    pub message: String,
+    /// The file, line and column the panic happened at
+    pub location: Option<String>,
+    /// The names of the components from the one that was running when the app crashed up to the root of the app
+    pub component_stack: Vec<String>,
+    /// The breadcrumbs that were recorded before the crash, oldest first
+    pub breadcrumbs: Vec<Breadcrumb>,
+    /// The platform the app was running on, like `web`, `linux` or `android`
+    pub platform: String,
+    /// The release of the app set with [`CrashReporter::release`](crate::CrashReporter::release)
+    pub release: Option<String>,
+    /// When the app crashed, in milliseconds since the unix epoch
+    pub timestamp: u64,
+    /// The minidump of a native crash. Minidumps are encoded as base64 in json
+    #[serde(
+        default,
+        with = "base64_bytes",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub minidump: Option<Vec<u8>>,
+}
+
+impl CrashReport {
+    /// Create a report of a crash that happened just now
+    pub fn new(kind: CrashKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            location: None,
+            component_stack: Vec::new(),
+            breadcrumbs: Vec::new(),
+            platform: platform().to_string(),
+            release: None,
+            timestamp: now(),
+            minidump: None,
+        }
+    }
+}
+
+/// The kind of a [`CrashReport`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum CrashKind {
+    /// A rust panic
+    Panic,
+    /// A native crash like a segfault or an abort, captured as a minidump
+    Native,
+}
+
+/// Something that happened in the app before it crashed. Record breadcrumbs with [`add_breadcrumb`](crate::add_breadcrumb)
+/// or from logs with [`BreadcrumbSink`](crate::BreadcrumbSink).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Breadcrumb {
+    /// When the breadcrumb was recorded, in milliseconds since the unix epoch
+    pub timestamp: u64,
+    /// The level of the breadcrumb, like `info` or `error`
+    pub level: String,
+    /// What the breadcrumb is about, like `navigation` or the module of a log
+    pub category: String,
+    /// A description of what happened
+    pub message: String,
+    /// The component the breadcrumb was recorded in
+    pub component: Option<String>,
+}
+
+/// Strip the module path from the name of a component
+pub(crate) fn component_name(name: &str) -> String {
+    name.rsplit("::").next().unwrap_or(name).to_string()
+}
+
+pub(crate) fn platform() -> &'static str {
+    if cfg!(target_arch = "wasm32") {
+        "web"
+    } else {
+        std::env::consts::OS
+    }
+}
+
+/// The current time in milliseconds since the unix epoch
+pub(crate) fn now() -> u64 {
+    // The standard library doesn't have a clock on the web
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now() as u64
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default()
+    }
+}
+
+mod base64_bytes {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        bytes: &Option<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match bytes {
+            Some(bytes) => serializer.serialize_str(&STANDARD.encode(bytes)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|encoded| STANDARD.decode(encoded).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    breadcrumbs::{try_breadcrumbs, DEFAULT_MAX_BREADCRUMBS, MAX_BREADCRUMBS},
+    report::component_name,
+    CrashKind, CrashReport, CrashTransport, Result,
+};
+use std::{
+    cell::Cell,
+    sync::{atomic::Ordering, Arc},
+};
+
+/// Captures crashes of the app and sends them to a [`CrashTransport`].
+///
+/// Panics are captured on every platform with the component that panicked and the breadcrumbs that were recorded
+/// before the panic. With the `minidump` feature, native crashes on desktop are captured as minidumps.
+///
+/// ```rust,no_run
+/// use dioxus_crash_reporter::{CrashReport, CrashReporter};
+///
+/// fn main() {
+///     CrashReporter::new(|report: &CrashReport| {
+///         // Send the report to your crash reporting service
+///     })
+///     .release(env!("CARGO_PKG_VERSION"))
+///     .install()
+///     .expect("failed to install the crash reporter");
+///
+///     // dioxus::launch(app);
+/// }
+/// ```
+pub struct CrashReporter {
+    pub(crate) transport: Arc<dyn CrashTransport>,
+    pub(crate) release: Option<String>,
+    max_breadcrumbs: usize,
+    #[cfg(all(
+        feature = "minidump",
+        any(target_os = "windows", target_os = "macos", target_os = "linux")
+    ))]
+    minidumps: Option<std::path::PathBuf>,
+}
+
+impl CrashReporter {
+    /// Create a crash reporter that sends reports to the transport
+    pub fn new(transport: impl CrashTransport) -> Self {
+        Self {
+            transport: Arc::new(transport),
+            release: None,
+            max_breadcrumbs: DEFAULT_MAX_BREADCRUMBS,
+            #[cfg(all(
+                feature = "minidump",
+                any(target_os = "windows", target_os = "macos", target_os = "linux")
+            ))]
+            minidumps: None,
+        }
+    }
+
+    /// Set the release of the app that is sent with every report, usually `env!("CARGO_PKG_VERSION")`
+    pub fn release(mut self, release: impl Into<String>) -> Self {
+        self.release = Some(release.into());
+        self
+    }
+
+    /// Set the number of breadcrumbs that are kept and sent with reports. Defaults to 100.
+    pub fn max_breadcrumbs(mut self, count: usize) -> Self {
+        self.max_breadcrumbs = count;
+        self
+    }
+
+    /// Capture native crashes as minidumps that are written to the directory and sent with the report.
+    ///
+    /// The minidump is written by a copy of the app that runs in a separate process, since a crashed process can't
+    /// reliably inspect itself. [`CrashReporter::install`] starts that process, so it must be called at the start
+    /// of `main` before the app does anything else.
+    #[cfg(all(
+        feature = "minidump",
+        any(target_os = "windows", target_os = "macos", target_os = "linux")
+    ))]
+    pub fn minidumps(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.minidumps = Some(dir.into());
+        self
+    }
+
+    /// Start capturing crashes. The panic hook that was installed before keeps running, so panics are still logged.
+    pub fn install(self) -> Result<()> {
+        MAX_BREADCRUMBS.store(self.max_breadcrumbs, Ordering::Relaxed);
+
+        #[cfg(all(
+            feature = "minidump",
+            any(target_os = "windows", target_os = "macos", target_os = "linux")
+        ))]
+        if let Some(dir) = &self.minidumps {
+            crate::minidump::start(dir.clone(), &self)?;
+        }
+
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            previous(info);
+
+            thread_local! {
+                static REPORTING: Cell<bool> = const { Cell::new(false) };
+            }
+            // A panic in the transport would otherwise be reported again until the stack overflows
+            if REPORTING.with(|reporting| reporting.replace(true)) {
+                return;
+            }
+
+            let payload = info.payload();
+            let message = match (
+                payload.downcast_ref::<&str>(),
+                payload.downcast_ref::<String>(),
+            ) {
+                (Some(message), _) => message.to_string(),
+                (_, Some(message)) => message.clone(),
+                _ => "Box<dyn Any>".to_string(),
+            };
+            let mut report = self.report(CrashKind::Panic, message);
+            report.location = info.location().map(|location| location.to_string());
+            // The panic hook runs before the stack unwinds, so the component that panicked is still running
+            report.component_stack = dioxus_core::component_stack()
+                .into_iter()
+                .map(component_name)
+                .collect();
+            self.transport.send(&report);
+
+            REPORTING.with(|reporting| reporting.set(false));
+        }));
+
+        Ok(())
+    }
+
+    /// Create a report with the breadcrumbs and release of the app
+    pub(crate) fn report(&self, kind: CrashKind, message: String) -> CrashReport {
+        let mut report = CrashReport::new(kind, message);
+        report.release = self.release.clone();
+        report.breadcrumbs = try_breadcrumbs();
+        report
+    }
+}
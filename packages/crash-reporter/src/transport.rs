@@ -0,0 +1,108 @@
+use crate::CrashReport;
+
+/// Sends crash reports to a crash reporting service. Implement it to send reports to Sentry or any other service, or
+/// use [`HttpTransport`] with an endpoint like the one of `dioxus-fullstack`.
+///
+/// Reports of panics are sent from the panic hook while the app is crashing, so transports should send the report
+/// before they return instead of spawning a task on the async runtime.
+///
+/// Closures that take a [`CrashReport`] are transports:
+///
+/// ```rust
+/// use dioxus_crash_reporter::{CrashReport, CrashReporter};
+///
+/// let reporter = CrashReporter::new(|report: &CrashReport| {
+///     eprintln!("The app crashed: {}", report.message);
+/// });
+/// ```
+pub trait CrashTransport: Send + Sync + 'static {
+    /// Send a report to the service
+    fn send(&self, report: &CrashReport);
+}
+
+impl<F: Fn(&CrashReport) + Send + Sync + 'static> CrashTransport for F {
+    fn send(&self, report: &CrashReport) {
+        self(report)
+    }
+}
+
+/// Posts crash reports as json to an http endpoint.
+///
+/// On the web, reports are sent with a `keepalive` request so they are delivered even if the page is closed. On
+/// other platforms, reports are sent before the panic hook returns.
+#[cfg(feature = "http")]
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    endpoint: String,
+    headers: Vec<(String, String)>,
+}
+
+#[cfg(feature = "http")]
+impl HttpTransport {
+    /// Create a transport that posts reports to the endpoint
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Add a header to every request, like the api key of the service
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+#[cfg(feature = "http")]
+impl CrashTransport for HttpTransport {
+    #[cfg(target_arch = "wasm32")]
+    fn send(&self, report: &CrashReport) {
+        let Ok(body) = serde_json::to_string(report) else {
+            return;
+        };
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let Ok(headers) = web_sys::Headers::new() else {
+            return;
+        };
+        _ = headers.append("Content-Type", "application/json");
+        for (name, value) in &self.headers {
+            _ = headers.append(name, value);
+        }
+
+        let init = web_sys::RequestInit::new();
+        init.set_method("POST");
+        init.set_headers(&headers);
+        init.set_body(&body.into());
+        // Keep the request alive if the page is closed after the crash
+        _ = js_sys::Reflect::set(&init, &"keepalive".into(), &true.into());
+        _ = window.fetch_with_str_and_init(&self.endpoint, &init);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn send(&self, report: &CrashReport) {
+        let transport = self.clone();
+        let report = report.clone();
+        // The blocking client panics if it is used on a thread of an async runtime, which is where most panics of
+        // servers happen. Send the report from a new thread and wait for it instead.
+        let sent = std::thread::spawn(move || {
+            let mut request = reqwest::blocking::Client::new()
+                .post(&transport.endpoint)
+                .timeout(std::time::Duration::from_secs(10))
+                .json(&report);
+            for (name, value) in &transport.headers {
+                request = request.header(name, value);
+            }
+            request
+                .send()
+                .and_then(|response| response.error_for_status())
+        })
+        .join();
+
+        if let Ok(Err(err)) = sent {
+            eprintln!("Failed to send the crash report: {err}");
+        }
+    }
+}
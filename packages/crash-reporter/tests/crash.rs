@@ -0,0 +1,77 @@
+use dioxus::prelude::*;
+use dioxus_crash_reporter::{
+    add_breadcrumb, breadcrumbs, clear_breadcrumbs, BreadcrumbSink, CrashKind, CrashReport,
+    CrashReporter,
+};
+use dioxus_logger::{tracing, LoggerBuilder};
+use std::sync::{Arc, Mutex};
+
+// The panic hook and the breadcrumbs are global, so everything runs in one test
+#[test]
+fn panics_are_reported_with_breadcrumbs() {
+    fn app() -> Element {
+        rsx! { Settings {} }
+    }
+
+    #[component]
+    fn Settings() -> Element {
+        add_breadcrumb("navigation", "Opened the settings");
+        dioxus_logger::use_logger().in_scope(|| tracing::info!(theme = "dark", "Loaded"));
+        panic!("the settings are corrupted");
+    }
+
+    let reports = Arc::new(Mutex::new(Vec::<CrashReport>::new()));
+    let sent = reports.clone();
+    CrashReporter::new(move |report: &CrashReport| sent.lock().unwrap().push(report.clone()))
+        .release("1.2.3")
+        .max_breadcrumbs(2)
+        .install()
+        .unwrap();
+
+    let logger = LoggerBuilder::new(tracing::Level::INFO)
+        .sink(BreadcrumbSink)
+        .build();
+    tracing::subscriber::with_default(logger, || {
+        add_breadcrumb("startup", "Dropped once there are more than two");
+        let mut dom = VirtualDom::new(app);
+        dom.rebuild_in_place();
+    });
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    let report = &reports[0];
+    assert_eq!(report.kind, CrashKind::Panic);
+    assert_eq!(report.message, "the settings are corrupted");
+    assert!(report.location.as_deref().unwrap().contains("crash.rs"));
+    assert_eq!(report.release.as_deref(), Some("1.2.3"));
+    assert_eq!(
+        report.component_stack.first().map(String::as_str),
+        Some("Settings")
+    );
+
+    let crumbs: Vec<_> = report
+        .breadcrumbs
+        .iter()
+        .map(|crumb| {
+            (
+                crumb.category.as_str(),
+                crumb.message.as_str(),
+                crumb.component.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        crumbs,
+        [
+            ("navigation", "Opened the settings", Some("Settings")),
+            ("crash", "Loaded theme=dark", Some("Settings")),
+        ]
+    );
+
+    clear_breadcrumbs();
+    assert!(breadcrumbs().is_empty());
+
+    // Reports survive the round trip through the json the http transport sends
+    let json = serde_json::to_string(report).unwrap();
+    assert_eq!(&serde_json::from_str::<CrashReport>(&json).unwrap(), report);
+}
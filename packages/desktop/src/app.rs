@@ -1,18 +1,23 @@
 use crate::{
     config::{Config, WindowCloseBehaviour},
+    deep_link::{deep_link_route, is_deep_link, startup_url},
     event_handlers::WindowEventHandlers,
     file_upload::{DesktopFileUploadForm, FileDialogRequest, NativeFileEngine},
     ipc::{IpcMessage, UserWindowEvent},
+    open_with::command_line_files,
     query::QueryResult,
     shortcut::ShortcutRegistry,
     webview::WebviewInstance,
+    window_manager::WindowRegistry,
 };
 use dioxus_core::{ElementId, VirtualDom};
+use dioxus_history::History;
 use dioxus_html::PlatformEventData;
 use std::{
     any::Any,
     cell::{Cell, RefCell},
     collections::HashMap,
+    path::PathBuf,
     rc::Rc,
     sync::Arc,
 };
@@ -22,6 +27,7 @@ use tao::{
     event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopProxy, EventLoopWindowTarget},
     window::{Window, WindowId},
 };
+use url::Url;
 
 /// The single top-level object that manages all the running windows, assets, shortcuts, etc
 pub(crate) struct App {
@@ -37,6 +43,8 @@ pub(crate) struct App {
     pub(crate) webviews: HashMap<WindowId, WebviewInstance>,
     pub(crate) float_all: bool,
     pub(crate) show_devtools: bool,
+    pub(crate) first_window_loaded: bool,
+    pub(crate) deep_link_navigation: bool,
 
     /// This single blob of state is shared between all the windows so they have access to the runtime state
     ///
@@ -51,6 +59,9 @@ pub(crate) struct SharedContext {
     pub(crate) shortcut_manager: ShortcutRegistry,
     pub(crate) proxy: EventLoopProxy<UserWindowEvent>,
     pub(crate) target: EventLoopWindowTarget<UserWindowEvent>,
+    pub(crate) windows: WindowRegistry,
+    pub(crate) launch_files: RefCell<Vec<PathBuf>>,
+    pub(crate) launch_url: RefCell<Option<String>>,
 }
 
 impl App {
@@ -68,6 +79,8 @@ impl App {
             unmounted_dom: Cell::new(Some(virtual_dom)),
             float_all: false,
             show_devtools: false,
+            first_window_loaded: false,
+            deep_link_navigation: cfg.deep_link_navigation,
             cfg: Cell::new(Some(cfg)),
             shared: Rc::new(SharedContext {
                 event_handlers: WindowEventHandlers::default(),
@@ -75,6 +88,9 @@ impl App {
                 shortcut_manager: ShortcutRegistry::new(),
                 proxy: event_loop.create_proxy(),
                 target: event_loop.clone(),
+                windows: WindowRegistry::default(),
+                launch_files: RefCell::new(command_line_files()),
+                launch_url: RefCell::new(startup_url()),
             }),
         };
 
@@ -97,6 +113,10 @@ impl App {
         #[cfg(all(feature = "devtools", debug_assertions))]
         app.connect_hotreload();
 
+        // Show panics in the error overlay - but only in debug mode
+        #[cfg(all(feature = "devtools", debug_assertions))]
+        app.report_panics();
+
         #[cfg(debug_assertions)]
         #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
         app.connect_preserve_window_state_handler();
@@ -144,7 +164,9 @@ impl App {
     }
     #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
     pub fn handle_tray_menu_event(&mut self, event: tray_icon::menu::MenuEvent) {
-        _ = event;
+        if event.id().0 == crate::trayicon::QUIT_ITEM_ID {
+            self.control_flow = ControlFlow::Exit;
+        }
     }
 
     #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
@@ -176,6 +198,17 @@ impl App {
         }
     }
 
+    #[cfg(all(feature = "devtools", debug_assertions))]
+    pub fn report_panics(&self) {
+        // The panic hook can run on any thread, so the proxy needs to be shared behind a lock
+        let proxy = std::sync::Mutex::new(self.shared.proxy.clone());
+        dioxus_devtools::report_panics(move |report| {
+            if let Ok(proxy) = proxy.lock() {
+                _ = proxy.send_event(UserWindowEvent::ErrorReport(report));
+            }
+        });
+    }
+
     pub fn handle_new_window(&mut self) {
         for handler in self.shared.pending_webviews.borrow_mut().drain(..) {
             let id = handler.desktop_context.window.id();
@@ -211,6 +244,12 @@ impl App {
             CloseWindow => {
                 self.webviews.remove(&id);
             }
+
+            WindowHides => {
+                if let Some(webview) = self.webviews.get(&id) {
+                    webview.desktop_context.window.set_visible(false);
+                }
+            }
         }
     }
 
@@ -281,6 +320,14 @@ impl App {
     pub fn handle_initialize_msg(&mut self, id: WindowId) {
         let view = self.webviews.get_mut(&id).unwrap();
 
+        // Start the first window at the route of the deep link that launched the app
+        if !self.first_window_loaded && self.deep_link_navigation {
+            let launch_url = self.shared.launch_url.borrow();
+            if let Some(route) = launch_url.as_deref().and_then(deep_link_route) {
+                view.history.replace(route);
+            }
+        }
+
         view.edits
             .wry_queue
             .with_mutation_state_mut(|f| view.dom.rebuild(f));
@@ -291,9 +338,42 @@ impl App {
             .window
             .set_visible(self.is_visible_before_start);
 
+        self.first_window_loaded = true;
+
         _ = self.shared.proxy.send_event(UserWindowEvent::Poll(id));
     }
 
+    /// Files and deep links that are opened before the first window loaded are part of the launch since there are no
+    /// handlers to receive them yet
+    pub fn handle_opened(&mut self, urls: &[Url]) {
+        let files = urls.iter().filter_map(|url| url.to_file_path().ok());
+        let mut links = urls.iter().filter(|url| is_deep_link(url));
+
+        if !self.first_window_loaded {
+            self.shared.launch_files.borrow_mut().extend(files);
+            if let Some(link) = links.next_back() {
+                *self.shared.launch_url.borrow_mut() = Some(link.to_string());
+            }
+            return;
+        }
+
+        if !self.deep_link_navigation {
+            return;
+        }
+
+        // Open the link in the focused window, or any window if the app is in the background
+        let webview = self
+            .webviews
+            .values()
+            .find(|webview| webview.desktop_context.window.is_focused())
+            .or_else(|| self.webviews.values().next());
+        if let Some(webview) = webview {
+            for route in links.filter_map(|link| deep_link_route(link.as_str())) {
+                webview.history.navigate(route);
+            }
+        }
+    }
+
     /// Todo: maybe we should poll the virtualdom asking if it has any final actions to apply before closing the webview
     ///
     /// Technically you can handle this with the use_window_event hook
@@ -304,6 +384,12 @@ impl App {
         }
     }
 
+    pub fn handle_window_control_msg(&mut self, msg: IpcMessage, id: WindowId) {
+        if let Some(view) = self.webviews.get(&id) {
+            crate::titlebar::handle_window_control(&view.desktop_context.window, msg.params());
+        }
+    }
+
     pub fn handle_query_msg(&mut self, msg: IpcMessage, id: WindowId) {
         let Ok(result) = serde_json::from_value::<QueryResult>(msg.params()) else {
             return;
@@ -345,6 +431,14 @@ impl App {
         }
     }
 
+    #[cfg(all(feature = "devtools", debug_assertions))]
+    pub fn handle_error_report(&mut self, report: dioxus_devtools::ErrorReport) {
+        let script = dioxus_devtools::show_error_overlay_script(&report);
+        for webview in self.webviews.values() {
+            _ = webview.desktop_context.webview.evaluate_script(&script);
+        }
+    }
+
     pub fn handle_file_dialog_msg(&mut self, msg: IpcMessage, window: WindowId) {
         let Ok(file_dialog) = serde_json::from_value::<FileDialogRequest>(msg.params()) else {
             return;
@@ -1,6 +1,7 @@
 use dioxus_core::LaunchConfig;
 use std::borrow::Cow;
 use std::path::PathBuf;
+use std::rc::Rc;
 use tao::event_loop::{EventLoop, EventLoopWindowTarget};
 use tao::window::{Icon, WindowBuilder};
 use wry::http::{Request as HttpRequest, Response as HttpResponse};
@@ -8,6 +9,11 @@ use wry::RequestAsyncResponder;
 
 use crate::ipc::UserWindowEvent;
 use crate::menubar::{default_menu_bar, DioxusMenu};
+use crate::requests::{
+    DownloadFinished, DownloadFinishedHandler, DownloadHandler, DownloadRequest, NewWindowAction,
+    NewWindowHandler, PermissionDecision, PermissionHandler, PermissionRequest,
+};
+use crate::streaming::{range_response, StreamSource};
 
 type CustomEventHandler = Box<
     dyn 'static
@@ -27,6 +33,9 @@ pub enum WindowCloseBehaviour {
     LastWindowHides,
     /// Closing the last window will close it but the app will keep running so that new windows can be opened
     CloseWindow,
+    /// Closing any window will hide it instead, and the app keeps running with all windows hidden. Pair this with a
+    /// [`Tray`](crate::trayicon::Tray) so the user can show the windows again.
+    WindowHides,
 }
 
 /// The state of the menu builder. We need to keep track of if the state is default
@@ -50,6 +59,7 @@ pub struct Config {
     pub(crate) event_loop: Option<EventLoop<UserWindowEvent>>,
     pub(crate) window: WindowBuilder,
     pub(crate) as_child_window: bool,
+    pub(crate) custom_titlebar: bool,
     pub(crate) menu: MenuBuilderState,
     pub(crate) protocols: Vec<WryProtocol>,
     pub(crate) asynchronous_protocols: Vec<AsyncWryProtocol>,
@@ -62,6 +72,11 @@ pub struct Config {
     pub(crate) root_name: String,
     pub(crate) background_color: Option<(u8, u8, u8, u8)>,
     pub(crate) last_window_close_behavior: WindowCloseBehaviour,
+    pub(crate) deep_link_navigation: bool,
+    pub(crate) permission_handler: Option<PermissionHandler>,
+    pub(crate) download_handler: Option<DownloadHandler>,
+    pub(crate) download_finished_handler: Option<DownloadFinishedHandler>,
+    pub(crate) new_window_handler: Option<NewWindowHandler>,
     pub(crate) custom_event_handler: Option<CustomEventHandler>,
 }
 
@@ -94,6 +109,7 @@ impl Config {
         Self {
             window,
             as_child_window: false,
+            custom_titlebar: false,
             event_loop: None,
             menu: MenuBuilderState::Unset,
             protocols: Vec::new(),
@@ -107,6 +123,11 @@ impl Config {
             root_name: "main".to_string(),
             background_color: None,
             last_window_close_behavior: WindowCloseBehaviour::LastWindowExitsApp,
+            deep_link_navigation: true,
+            permission_handler: None,
+            download_handler: None,
+            download_finished_handler: None,
+            new_window_handler: None,
             custom_event_handler: None,
         }
     }
@@ -154,6 +175,21 @@ impl Config {
         self
     }
 
+    /// Replace the native titlebar with one rendered by the app, built from a
+    /// [`DragRegion`](crate::titlebar::DragRegion) and [`WindowControls`](crate::titlebar::WindowControls).
+    ///
+    /// On macOS the titlebar becomes transparent and the content fills the whole window, but the native close,
+    /// minimize and maximize buttons stay in the top left corner. Leave some space for them in your titlebar. On other
+    /// platforms the window has no decorations and the app draws its own buttons.
+    pub fn with_custom_titlebar(mut self) -> Self {
+        self.custom_titlebar = true;
+        // The menu bar is part of the native titlebar on windows and linux
+        if cfg!(not(target_os = "macos")) && matches!(self.menu, MenuBuilderState::Unset) {
+            self.menu = MenuBuilderState::Set(None);
+        }
+        self
+    }
+
     /// Set the window as child
     pub fn with_as_child_window(mut self) -> Self {
         self.as_child_window = true;
@@ -166,6 +202,74 @@ impl Config {
         self
     }
 
+    /// Set whether deep links navigate the router. This is on by default: the app starts at the route of the
+    /// [`launch_url`](crate::launch_url), and deep links the app receives while it runs open their route in the
+    /// focused window. Turn it off to handle deep links yourself with [`use_deep_link`](crate::use_deep_link).
+    pub fn with_deep_link_navigation(mut self, navigate: bool) -> Self {
+        self.deep_link_navigation = navigate;
+        self
+    }
+
+    /// Decide on the permissions the page asks for, like access to the camera or the microphone.
+    ///
+    /// ```rust, no_run
+    /// use dioxus::desktop::{Config, PermissionDecision, PermissionKind};
+    ///
+    /// let config = Config::new().with_permission_handler(|request| match request.kind {
+    ///     PermissionKind::Camera | PermissionKind::Microphone => PermissionDecision::Allow,
+    ///     _ => PermissionDecision::Deny,
+    /// });
+    /// ```
+    ///
+    /// The handler is called on Windows and Linux. WKWebView on macOS and iOS grants access to the camera and the
+    /// microphone without asking the app.
+    pub fn with_permission_handler(
+        mut self,
+        handler: impl Fn(&PermissionRequest) -> PermissionDecision + 'static,
+    ) -> Self {
+        self.permission_handler = Some(Rc::new(handler));
+        self
+    }
+
+    /// Decide on the downloads the page starts. Return `false` to cancel the download, or change the
+    /// [`destination`](DownloadRequest::destination) to save the file somewhere else.
+    ///
+    /// ```rust, no_run
+    /// use dioxus::desktop::Config;
+    ///
+    /// let config = Config::new().with_download_handler(|download| {
+    ///     let name = download.destination.file_name().unwrap_or_default().to_owned();
+    ///     download.destination = dirs::download_dir().unwrap().join("my-app").join(name);
+    ///     true
+    /// });
+    /// ```
+    pub fn with_download_handler(
+        mut self,
+        handler: impl FnMut(&mut DownloadRequest) -> bool + 'static,
+    ) -> Self {
+        self.download_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Run a handler when a download finishes, successfully or not
+    pub fn with_download_finished_handler(
+        mut self,
+        handler: impl Fn(DownloadFinished) + 'static,
+    ) -> Self {
+        self.download_finished_handler = Some(Rc::new(handler));
+        self
+    }
+
+    /// Decide what happens when the page tries to open a new window with the URL, like a link with
+    /// `target="_blank"` or `window.open`.
+    pub fn with_new_window_handler(
+        mut self,
+        handler: impl Fn(&str) -> NewWindowAction + 'static,
+    ) -> Self {
+        self.new_window_handler = Some(Box::new(handler));
+        self
+    }
+
     /// Sets a custom callback to run whenever the event pool receives an event.
     pub fn with_custom_event_handler(
         mut self,
@@ -220,6 +324,53 @@ impl Config {
         self
     }
 
+    /// Set a custom protocol that streams bytes to the webview with support for range requests. This lets `video` and
+    /// `audio` elements play and seek through large files without reading them into memory, like files from local or
+    /// encrypted storage.
+    ///
+    /// The handler picks the [`StreamSource`] of a request, or returns `None` to answer with a `404`. The bytes are
+    /// read on a background thread.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus_desktop::{Config, StreamSource};
+    /// let cfg = Config::new().with_streaming_protocol("media", |request| {
+    ///     let path = request.uri().path().trim_start_matches('/');
+    ///     StreamSource::open(std::path::Path::new("videos").join(path)).ok()
+    /// });
+    /// ```
+    ///
+    /// The protocol is available at `media://localhost/` on macOS and Linux, and at `http://media.localhost/` on
+    /// Windows and Android.
+    pub fn with_streaming_protocol<F>(self, name: impl ToString, handler: F) -> Self
+    where
+        F: Fn(&HttpRequest<Vec<u8>>) -> Option<StreamSource> + 'static,
+    {
+        self.with_asynchronous_custom_protocol(name, move |request, responder| {
+            let Some(source) = handler(&request) else {
+                responder.respond(
+                    HttpResponse::builder()
+                        .status(404)
+                        .body(b"Not Found".to_vec())
+                        .unwrap(),
+                );
+                return;
+            };
+
+            std::thread::spawn(move || match range_response(&request, source) {
+                Ok(response) => responder.respond(response),
+                Err(err) => {
+                    tracing::error!("Failed to stream {}: {err}", request.uri());
+                    responder.respond(
+                        HttpResponse::builder()
+                            .status(500)
+                            .body(err.to_string().into_bytes())
+                            .unwrap(),
+                    );
+                }
+            });
+        })
+    }
+
     /// Set a custom icon for this application
     pub fn with_icon(mut self, icon: Icon) -> Self {
         self.window.window.window_icon = Some(icon);
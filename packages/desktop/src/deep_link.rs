@@ -0,0 +1,114 @@
+//! Deep links that open the app with a custom URL scheme like `myapp://notes/42`
+
+use crate::{use_wry_event_handler, window};
+use tao::event::Event;
+use url::Url;
+
+/// The deep link the app was launched with, if it was opened with a URL of one of its schemes. Register the schemes
+/// with `deep_link_protocols` in the `[bundle]` section of `Dioxus.toml`.
+///
+/// The router starts at the route of the link unless [`Config::with_deep_link_navigation`](crate::Config::with_deep_link_navigation)
+/// is turned off. Use [`deep_link_route`] to get the route yourself.
+///
+/// ## Panics
+///
+/// This function will panic if it is called outside of the context of a Dioxus App.
+pub fn launch_url() -> Option<String> {
+    window().shared.launch_url.borrow().clone()
+}
+
+/// Run a handler with the URL of every deep link the app receives while it is running. The deep link the app was
+/// launched with is in [`launch_url`].
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::desktop::{launch_url, use_deep_link};
+///
+/// fn App() -> Element {
+///     let mut last_link = use_signal(launch_url);
+///     use_deep_link(move |url| last_link.set(Some(url)));
+///
+///     rsx! { "Last link: {last_link:?}" }
+/// }
+/// ```
+///
+/// Deep links are delivered while the app runs on macOS and iOS. Windows, Linux and Android start a new instance of
+/// the app for every link.
+pub fn use_deep_link(mut handler: impl FnMut(String) + 'static) {
+    use_wry_event_handler(move |event, _| {
+        if let Event::Opened { urls } = event {
+            for url in urls.iter().filter(|url| is_deep_link(url)) {
+                handler(url.to_string());
+            }
+        }
+    });
+}
+
+/// Get the route of the router a deep link points to. The host and path of the link become the path of the route,
+/// so `myapp://notes/42?edit=true` opens `/notes/42?edit=true`.
+///
+/// Returns `None` if the text is not a URL.
+pub fn deep_link_route(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+
+    let mut route = String::new();
+    if let Some(host) = url.host_str().filter(|host| !host.is_empty()) {
+        route.push('/');
+        route.push_str(host);
+    }
+    if !url.path().starts_with('/') {
+        route.push('/');
+    }
+    route.push_str(url.path());
+    if let Some(query) = url.query() {
+        route.push('?');
+        route.push_str(query);
+    }
+    if let Some(fragment) = url.fragment() {
+        route.push('#');
+        route.push_str(fragment);
+    }
+
+    Some(route)
+}
+
+/// Links to files and websites are not deep links of the app
+pub(crate) fn is_deep_link(url: &Url) -> bool {
+    !matches!(url.scheme(), "file" | "http" | "https")
+}
+
+/// The deep link in the arguments of the app. Windows and Linux pass the link as an argument when they launch the app
+/// for it, and Android stores it in the intent that started the activity.
+pub(crate) fn startup_url() -> Option<String> {
+    #[cfg(target_os = "android")]
+    if let Some(url) = android_intent_url() {
+        return Some(url);
+    }
+
+    std::env::args()
+        .skip(1)
+        .filter_map(|arg| Url::parse(&arg).ok())
+        .find(is_deep_link)
+        .map(|url| url.to_string())
+}
+
+#[cfg(target_os = "android")]
+fn android_intent_url() -> Option<String> {
+    let url = dioxus_android::with_env(|env, activity| {
+        let intent = env
+            .call_method(activity, "getIntent", "()Landroid/content/Intent;", &[])?
+            .l()?;
+        if intent.is_null() {
+            return Ok(None);
+        }
+        let data = env
+            .call_method(intent, "getDataString", "()Ljava/lang/String;", &[])?
+            .l()?;
+        if data.is_null() {
+            return Ok(None);
+        }
+        let url: String = env.get_string(&data.into())?.into();
+        Ok(Some(url))
+    });
+    url.ok().flatten()
+}
@@ -5,13 +5,10 @@ use crate::{
     ipc::UserWindowEvent,
     query::QueryEngine,
     shortcut::{HotKey, ShortcutHandle, ShortcutRegistryError},
-    webview::WebviewInstance,
+    window_manager::open_window,
     AssetRequest, Config, WryEventHandler,
 };
-use dioxus_core::{
-    prelude::{Callback, ScopeId},
-    VirtualDom,
-};
+use dioxus_core::{prelude::Callback, VirtualDom};
 use std::rc::{Rc, Weak};
 use tao::{
     event::Event,
@@ -107,22 +104,7 @@ impl DesktopService {
     ///
     /// Be careful to not create a cycle of windows, or you might leak memory.
     pub fn new_window(&self, dom: VirtualDom, cfg: Config) -> WeakDesktopContext {
-        let window = WebviewInstance::new(cfg, dom, self.shared.clone());
-
-        let cx = window.dom.in_runtime(|| {
-            ScopeId::ROOT
-                .consume_context::<Rc<DesktopService>>()
-                .unwrap()
-        });
-
-        self.shared
-            .proxy
-            .send_event(UserWindowEvent::NewWindow)
-            .unwrap();
-
-        self.shared.pending_webviews.borrow_mut().push(window);
-
-        Rc::downgrade(&cx)
+        Rc::downgrade(&open_window(&self.shared, dom, cfg))
     }
 
     /// trigger the drag-window event
@@ -152,6 +134,15 @@ impl DesktopService {
             .send_event(UserWindowEvent::CloseWindow(self.id()));
     }
 
+    /// Close this window as if the user clicked the native close button. Unlike [`DesktopService::close`], this
+    /// respects the [`WindowCloseBehaviour`](crate::WindowCloseBehaviour) of the app.
+    pub fn request_close(&self) {
+        let _ = self
+            .shared
+            .proxy
+            .send_event(UserWindowEvent::RequestClose(self.id()));
+    }
+
     /// Close a particular window, given its ID
     pub fn close_window(&self, id: WindowId) {
         let _ = self
@@ -0,0 +1,225 @@
+//! Native dialogs to pick files to open and where to save files
+
+use crate::window;
+use std::path::{Path, PathBuf};
+
+/// A group of file extensions that a dialog shows, like `("Images", ["png", "jpg"])`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFilter {
+    /// The name of the filter that is shown in the dialog
+    pub name: String,
+    /// The extensions of the files without the leading `.`
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// Create a new filter for the extensions
+    pub fn new(name: impl Into<String>, extensions: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            extensions: extensions
+                .iter()
+                .map(|ext| ext.trim_start_matches('.').to_string())
+                .collect(),
+        }
+    }
+}
+
+/// A native dialog to pick one or more files or directories to open. The dialog is modal to the current window.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::desktop::dialog::OpenDialog;
+/// use std::path::PathBuf;
+///
+/// fn App() -> Element {
+///     let mut images = use_signal(Vec::<PathBuf>::new);
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| async move {
+///                 let picked = OpenDialog::new()
+///                     .title("Add images")
+///                     .filter("Images", &["png", "jpg", "webp"])
+///                     .multiple(true)
+///                     .show()
+///                     .await;
+///                 images.extend(picked);
+///             },
+///             "Add images"
+///         }
+///         for image in images.iter() {
+///             p { "{image.display()}" }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OpenDialog {
+    title: Option<String>,
+    directory: Option<PathBuf>,
+    filters: Vec<FileFilter>,
+    multiple: bool,
+    pick_directories: bool,
+}
+
+impl OpenDialog {
+    /// Create a dialog that picks a single file
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the title of the dialog
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the directory the dialog starts in
+    pub fn starting_directory(mut self, directory: impl AsRef<Path>) -> Self {
+        self.directory = Some(directory.as_ref().to_path_buf());
+        self
+    }
+
+    /// Only show files with these extensions. Filters are ignored when picking directories.
+    pub fn filter(self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.with_filter(FileFilter::new(name, extensions))
+    }
+
+    /// Add a [`FileFilter`] to the dialog
+    pub fn with_filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Allow picking more than one file or directory
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Pick directories instead of files
+    pub fn directories(mut self, directories: bool) -> Self {
+        self.pick_directories = directories;
+        self
+    }
+
+    /// Show the dialog and wait for the user to pick. Returns no paths if the dialog was cancelled.
+    pub async fn show(self) -> Vec<PathBuf> {
+        let mut dialog = rfd::AsyncFileDialog::new().set_parent(&window().window);
+        if let Some(title) = self.title {
+            dialog = dialog.set_title(title);
+        }
+        if let Some(directory) = self.directory {
+            dialog = dialog.set_directory(directory);
+        }
+
+        let picked = match (self.pick_directories, self.multiple) {
+            (true, true) => dialog.pick_folders().await.unwrap_or_default(),
+            (true, false) => dialog.pick_folder().await.into_iter().collect(),
+            (false, multiple) => {
+                for filter in self.filters {
+                    dialog = dialog.add_filter(filter.name, &filter.extensions);
+                }
+                match multiple {
+                    true => dialog.pick_files().await.unwrap_or_default(),
+                    false => dialog.pick_file().await.into_iter().collect(),
+                }
+            }
+        };
+
+        picked
+            .into_iter()
+            .map(|handle| handle.path().to_path_buf())
+            .collect()
+    }
+}
+
+/// A native dialog to pick where to save a file. The dialog is modal to the current window.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::desktop::dialog::SaveDialog;
+///
+/// fn App() -> Element {
+///     rsx! {
+///         button {
+///             onclick: move |_| async move {
+///                 let path = SaveDialog::new()
+///                     .file_name("notes.md")
+///                     .filter("Markdown", &["md"])
+///                     .show()
+///                     .await;
+///                 if let Some(path) = path {
+///                     std::fs::write(path, "# Notes").unwrap();
+///                 }
+///             },
+///             "Save"
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SaveDialog {
+    title: Option<String>,
+    directory: Option<PathBuf>,
+    file_name: Option<String>,
+    filters: Vec<FileFilter>,
+}
+
+impl SaveDialog {
+    /// Create a new save dialog
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the title of the dialog
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the directory the dialog starts in
+    pub fn starting_directory(mut self, directory: impl AsRef<Path>) -> Self {
+        self.directory = Some(directory.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set the file name the dialog suggests
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Only show files with these extensions. Some platforms also add the first extension to the name the user
+    /// typed if it doesn't have one.
+    pub fn filter(self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.with_filter(FileFilter::new(name, extensions))
+    }
+
+    /// Add a [`FileFilter`] to the dialog
+    pub fn with_filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Show the dialog and wait for the user to pick a path. Returns `None` if the dialog was cancelled.
+    pub async fn show(self) -> Option<PathBuf> {
+        let mut dialog = rfd::AsyncFileDialog::new().set_parent(&window().window);
+        if let Some(title) = self.title {
+            dialog = dialog.set_title(title);
+        }
+        if let Some(directory) = self.directory {
+            dialog = dialog.set_directory(directory);
+        }
+        if let Some(file_name) = self.file_name {
+            dialog = dialog.set_file_name(file_name);
+        }
+        for filter in self.filters {
+            dialog = dialog.add_filter(filter.name, &filter.extensions);
+        }
+
+        let handle = dialog.save_file().await?;
+        Some(handle.path().to_path_buf())
+    }
+}
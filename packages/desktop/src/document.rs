@@ -1,8 +1,9 @@
 use crate::{query::Query, DesktopContext, WeakDesktopContext};
 use dioxus_core::prelude::queue_effect;
 use dioxus_document::{
-    create_element_in_head, Document, Eval, EvalError, Evaluator, LinkProps, MetaProps,
-    ScriptProps, StyleProps,
+    create_element_in_head, insert_element_in_head, remove_element_in_head, Document, Eval,
+    EvalError, Evaluator, HeadElement, HeadElementId, LinkProps, MetaProps, ScriptProps,
+    StyleProps,
 };
 
 use generational_box::{AnyStorage, GenerationalBox, UnsyncStorage};
@@ -78,6 +79,19 @@ impl Document for DesktopDocument {
             myself.eval(create_element_in_head("link", &props.attributes(), None));
         });
     }
+
+    /// Insert an element a head component rendered into the head
+    fn insert_head_element(&self, element: HeadElement) {
+        let myself = self.clone();
+        queue_effect(move || {
+            myself.eval(insert_element_in_head(&element));
+        });
+    }
+
+    /// Remove an element a head component rendered from the head
+    fn remove_head_element(&self, id: HeadElementId) {
+        self.eval(remove_element_in_head(id));
+    }
 }
 
 /// Represents a desktop-target's JavaScript evaluator.
@@ -124,6 +124,33 @@ impl RenderedElementBacking for DesktopElement {
             }
         })
     }
+
+    fn set_focus_trap(
+        &self,
+        trap: bool,
+    ) -> std::pin::Pin<Box<dyn futures_util::Future<Output = dioxus_html::MountedResult<()>>>> {
+        let script = format!(
+            "return window.interpreter.setFocusTrap({}, {});",
+            self.id.0, trap
+        );
+
+        let fut = self
+            .query
+            .new_query::<bool>(&script, self.webview.clone())
+            .resolve();
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(true) => Ok(()),
+                Ok(false) => MountedResult::Err(dioxus_html::MountedError::OperationFailed(
+                    Box::new(DesktopQueryError::FailedToQuery),
+                )),
+                Err(err) => {
+                    MountedResult::Err(dioxus_html::MountedError::OperationFailed(Box::new(err)))
+                }
+            }
+        })
+    }
 }
 
 #[derive(Debug)]
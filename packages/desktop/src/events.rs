@@ -33,6 +33,14 @@ impl HtmlEventConverter for SerializedHtmlEventConverter {
             .into()
     }
 
+    fn convert_custom_data(&self, event: &PlatformEventData) -> CustomEventData {
+        event
+            .downcast::<SerializedCustomEventData>()
+            .cloned()
+            .unwrap()
+            .into()
+    }
+
     fn convert_drag_data(&self, event: &PlatformEventData) -> DragData {
         // Attempt a simple serialized data conversion
         if let Some(_data) = event.downcast::<SerializedDragData>() {
@@ -3,7 +3,10 @@
 use std::any::Any;
 
 #[cfg(feature = "tokio_runtime")]
-use tokio::{fs::File, io::AsyncReadExt};
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
 
 use dioxus_html::{
     geometry::{ClientPoint, Coordinates, ElementPoint, PagePoint, ScreenPoint},
@@ -12,7 +15,7 @@ use dioxus_html::{
         InteractionElementOffset, InteractionLocation, ModifiersInteraction, PointerInteraction,
     },
     prelude::{SerializedMouseData, SerializedPointInteraction},
-    FileEngine, HasDragData, HasFileData, HasFormData, HasMouseData,
+    DataTransfer, FileEngine, HasDragData, HasFileData, HasFormData, HasMouseData,
 };
 
 use serde::Deserialize;
@@ -167,20 +170,34 @@ impl HasFormData for DesktopFileUploadForm {
 #[derive(Default, Clone)]
 pub struct NativeFileHover {
     event: Rc<RefCell<Option<DragDropEvent>>>,
+    // Only enter and drop events carry the paths of the files, so remember them for the over events in between
+    paths: Rc<RefCell<Vec<PathBuf>>>,
 }
 impl NativeFileHover {
     pub fn set(&self, event: DragDropEvent) {
+        match &event {
+            DragDropEvent::Enter { paths, .. } | DragDropEvent::Drop { paths, .. } => {
+                *self.paths.borrow_mut() = paths.clone();
+            }
+            _ => {}
+        }
         self.event.borrow_mut().replace(event);
     }
 
     pub fn current(&self) -> Option<DragDropEvent> {
         self.event.borrow_mut().clone()
     }
+
+    /// The paths of the files that are being dragged over the window or were dropped last
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.paths.borrow().clone()
+    }
 }
 
 #[derive(Clone)]
 pub(crate) struct DesktopFileDragEvent {
     pub mouse: SerializedPointInteraction,
+    pub data_transfer: DataTransfer,
     pub files: Arc<NativeFileEngine>,
 }
 
@@ -194,6 +211,10 @@ impl HasDragData for DesktopFileDragEvent {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn data_transfer(&self) -> DataTransfer {
+        self.data_transfer.clone()
+    }
 }
 
 impl HasMouseData for DesktopFileDragEvent {
@@ -305,6 +326,26 @@ impl FileEngine for NativeFileEngine {
         }
     }
 
+    async fn read_file_range(&self, file: &str, start: u64, end: u64) -> Option<Vec<u8>> {
+        #[cfg(feature = "tokio_runtime")]
+        {
+            let mut file = File::open(file).await.ok()?;
+            file.seek(std::io::SeekFrom::Start(start)).await.ok()?;
+
+            let mut contents = Vec::new();
+            file.take(end.saturating_sub(start))
+                .read_to_end(&mut contents)
+                .await
+                .ok()?;
+
+            Some(contents)
+        }
+        #[cfg(not(feature = "tokio_runtime"))]
+        {
+            None
+        }
+    }
+
     async fn get_native_file(&self, file: &str) -> Option<Box<dyn Any>> {
         #[cfg(feature = "tokio_runtime")]
         {
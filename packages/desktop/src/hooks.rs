@@ -88,6 +88,31 @@ pub fn use_tray_icon_event_handler(
     })
 }
 
+/// Register an event handler that runs when the user clicks a tray icon or an item of its
+/// [`TrayMenu`](crate::trayicon::TrayMenu). This receives the events of every tray icon of the app.
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(target_os = "windows", target_os = "linux", target_os = "macos")))
+)]
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub fn use_tray_event(
+    mut handler: impl FnMut(crate::trayicon::TrayEvent) + 'static,
+) -> WryEventHandler {
+    use crate::trayicon::TrayEvent;
+
+    use_wry_event_handler(move |event, _| match event {
+        Event::UserEvent(UserWindowEvent::TrayIconEvent(event)) => {
+            if let Some(event) = TrayEvent::from_icon_event(event) {
+                handler(event);
+            }
+        }
+        Event::UserEvent(UserWindowEvent::TrayMenuEvent(event)) => {
+            handler(TrayEvent::Menu(event.id().0.clone()));
+        }
+        _ => {}
+    })
+}
+
 /// Provide a callback to handle asset loading yourself.
 ///
 /// The callback takes a path as requested by the web view, and it should return `Some(response)`
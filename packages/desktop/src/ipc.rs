@@ -30,6 +30,10 @@ pub enum UserWindowEvent {
     #[cfg(all(feature = "devtools", debug_assertions))]
     HotReloadEvent(dioxus_devtools::DevserverMsg),
 
+    /// Show a panic or an error no error boundary handled in the error overlay of every window
+    #[cfg(all(feature = "devtools", debug_assertions))]
+    ErrorReport(dioxus_devtools::ErrorReport),
+
     // Windows-only drag-n-drop fix events.
     WindowsDragDrop(WindowId),
     WindowsDragOver(WindowId, i32, i32),
@@ -41,6 +45,9 @@ pub enum UserWindowEvent {
     /// Close a given window (could be any window!)
     CloseWindow(WindowId),
 
+    /// Close a window as if the user clicked its native close button
+    RequestClose(WindowId),
+
     /// Gracefully shutdown the entire app
     Shutdown,
 }
@@ -62,6 +69,7 @@ pub enum IpcMethod<'a> {
     Query,
     BrowserOpen,
     Initialize,
+    WindowControl,
     Other(&'a str),
 }
 
@@ -73,6 +81,7 @@ impl IpcMessage {
             "query" => IpcMethod::Query,
             "browser_open" => IpcMethod::BrowserOpen,
             "initialize" => IpcMethod::Initialize,
+            "window_control" => IpcMethod::WindowControl,
             _ => IpcMethod::Other(&self.method),
         }
     }
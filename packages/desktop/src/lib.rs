@@ -8,6 +8,7 @@ mod android_sync_lock;
 mod app;
 mod assets;
 mod config;
+mod deep_link;
 mod desktop_context;
 mod document;
 mod edits;
@@ -18,11 +19,16 @@ mod file_upload;
 mod hooks;
 mod ipc;
 mod menubar;
+mod open_with;
 mod protocol;
 mod query;
+mod requests;
 mod shortcut;
+mod storage;
+mod streaming;
 mod waker;
 mod webview;
+mod window_manager;
 
 // mobile shortcut is only supported on mobile platforms
 #[cfg(any(target_os = "ios", target_os = "android"))]
@@ -41,6 +47,21 @@ pub use wry;
 #[cfg(not(any(target_os = "ios", target_os = "android")))]
 pub use muda;
 
+// Custom titlebars
+pub mod titlebar;
+
+// Native file dialogs
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub mod dialog;
+
 // Tray icon
 #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
 pub mod trayicon;
@@ -48,8 +69,19 @@ pub mod trayicon;
 // Public exports
 pub use assets::AssetRequest;
 pub use config::{Config, WindowCloseBehaviour};
+pub use deep_link::{deep_link_route, launch_url, use_deep_link};
 pub use desktop_context::{window, DesktopContext, DesktopService, WeakDesktopContext};
 pub use event_handlers::WryEventHandler;
 pub use hooks::*;
+pub use open_with::{launch_files, use_open_files};
+pub use requests::{
+    DownloadFinished, DownloadRequest, NewWindowAction, PermissionDecision, PermissionKind,
+    PermissionRequest,
+};
 pub use shortcut::{ShortcutHandle, ShortcutRegistryError};
+pub use streaming::{range_response, StreamSource};
+pub use window_manager::{
+    use_shared_signal, use_window_manager, use_window_message, window_manager, WindowHandle,
+    WindowManager,
+};
 pub use wry::RequestAsyncResponder;
@@ -0,0 +1,63 @@
+//! Files the operating system asks the app to open
+
+use crate::{use_wry_event_handler, window};
+use std::path::PathBuf;
+use tao::event::Event;
+
+/// The files the app was launched with, either from the command line or by opening them with the app in the file
+/// manager. Register the extensions the app can open with `file_associations` in the `[bundle]` section of
+/// `Dioxus.toml`.
+///
+/// On Windows and Linux the files are the arguments of the app that are existing files. On macOS and iOS they are the
+/// files the system asked the app to open before the first window loaded.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::desktop::{launch_files, use_open_files};
+///
+/// fn App() -> Element {
+///     let mut documents = use_signal(launch_files);
+///     use_open_files(move |files| documents.extend(files));
+///
+///     rsx! {
+///         for document in documents.iter() {
+///             p { "{document.display()}" }
+///         }
+///     }
+/// }
+/// ```
+///
+/// ## Panics
+///
+/// This function will panic if it is called outside of the context of a Dioxus App.
+pub fn launch_files() -> Vec<PathBuf> {
+    window().shared.launch_files.borrow().clone()
+}
+
+/// Run a handler when the system asks the running app to open files, like when the user opens a file with the app in
+/// Finder. The files the app was launched with are in [`launch_files`].
+///
+/// This is only supported on macOS and iOS. Windows and Linux start a new instance of the app for every file that is
+/// opened.
+pub fn use_open_files(mut handler: impl FnMut(Vec<PathBuf>) + 'static) {
+    use_wry_event_handler(move |event, _| {
+        if let Event::Opened { urls } = event {
+            let files: Vec<_> = urls
+                .iter()
+                .filter_map(|url| url.to_file_path().ok())
+                .collect();
+            if !files.is_empty() {
+                handler(files);
+            }
+        }
+    });
+}
+
+/// The arguments of the app that are paths to existing files
+pub(crate) fn command_line_files() -> Vec<PathBuf> {
+    std::env::args_os()
+        .skip(1)
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+        .collect()
+}
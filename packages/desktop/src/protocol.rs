@@ -236,7 +236,7 @@ fn get_asset_root() -> PathBuf {
 }
 
 /// Get the mime type from a path-like string
-fn get_mime_from_path(asset: &Path) -> Result<&'static str> {
+pub(crate) fn get_mime_from_path(asset: &Path) -> Result<&'static str> {
     if asset.extension().is_some_and(|ext| ext == "svg") {
         return Ok("image/svg+xml");
     }
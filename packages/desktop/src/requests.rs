@@ -0,0 +1,213 @@
+//! Requests of the page that the app decides on: permissions, downloads and new windows
+
+use std::{path::PathBuf, rc::Rc};
+
+/// A kind of access the page asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PermissionKind {
+    /// Capture video from the camera
+    Camera,
+    /// Capture audio from the microphone
+    Microphone,
+    /// Read the position of the device
+    Geolocation,
+    /// Show notifications
+    Notifications,
+    /// Read the clipboard
+    ClipboardRead,
+    /// A permission that doesn't have a variant yet
+    Other,
+}
+
+/// A request of the page to access something. Decide on it with
+/// [`Config::with_permission_handler`](crate::Config::with_permission_handler).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionRequest {
+    /// What the page wants to access
+    pub kind: PermissionKind,
+    /// The URL of the page that asks for the permission
+    pub origin: String,
+}
+
+/// The answer to a [`PermissionRequest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PermissionDecision {
+    /// Grant the permission
+    Allow,
+    /// Refuse the permission
+    Deny,
+    /// Let the webview decide, which usually asks the user
+    #[default]
+    Default,
+}
+
+/// A download the page started. Change the [`destination`](Self::destination) to save the file somewhere else.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadRequest {
+    /// The URL the file is downloaded from
+    pub url: String,
+    /// Where the file will be saved. It must be an absolute path.
+    pub destination: PathBuf,
+}
+
+/// A download that finished, successfully or not
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadFinished {
+    /// The URL the file was downloaded from
+    pub url: String,
+    /// Where the file was saved. This is always `None` on macOS.
+    pub path: Option<PathBuf>,
+    /// If the file was downloaded successfully
+    pub success: bool,
+}
+
+/// What to do when the page tries to open a new window, like a link with `target="_blank"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NewWindowAction {
+    /// Let the webview open the window
+    Allow,
+    /// Don't open the window
+    Deny,
+    /// Open the URL in the default browser of the user instead
+    OpenInBrowser,
+}
+
+pub(crate) type PermissionHandler = Rc<dyn Fn(&PermissionRequest) -> PermissionDecision>;
+pub(crate) type DownloadHandler = Box<dyn FnMut(&mut DownloadRequest) -> bool>;
+pub(crate) type DownloadFinishedHandler = Rc<dyn Fn(DownloadFinished)>;
+pub(crate) type NewWindowHandler = Box<dyn Fn(&str) -> NewWindowAction>;
+
+/// Ask the handler about every kind of a request. The request is only allowed if every kind is allowed.
+#[allow(dead_code)] // Unused on platforms without a permission hook
+fn decide(
+    handler: &PermissionHandler,
+    origin: String,
+    kinds: impl IntoIterator<Item = PermissionKind>,
+) -> PermissionDecision {
+    let mut decision = PermissionDecision::Allow;
+    for kind in kinds {
+        match handler(&PermissionRequest {
+            kind,
+            origin: origin.clone(),
+        }) {
+            PermissionDecision::Deny => return PermissionDecision::Deny,
+            PermissionDecision::Default => decision = PermissionDecision::Default,
+            PermissionDecision::Allow => {}
+        }
+    }
+    decision
+}
+
+/// Route the permission requests of the webview to the handler.
+///
+/// wry doesn't expose permission requests, so we hook into the native webview. WKWebView on macOS and iOS grants
+/// camera and microphone access itself and the other platforms don't have a hook, so the handler isn't called there.
+#[cfg(target_os = "windows")]
+pub(crate) fn attach_permission_handler(webview: &wry::WebView, handler: PermissionHandler) {
+    use webview2_com::{Microsoft::Web::WebView2::Win32::*, *};
+    use windows::{core::PWSTR, Win32::System::WinRT::EventRegistrationToken};
+    use wry::WebViewExtWindows;
+
+    let Ok(core) = (unsafe { webview.controller().CoreWebView2() }) else {
+        return;
+    };
+
+    let mut token = EventRegistrationToken::default();
+    let result = unsafe {
+        core.add_PermissionRequested(
+            &PermissionRequestedEventHandler::create(Box::new(move |_, args| {
+                let Some(args) = args else { return Ok(()) };
+
+                let mut kind = COREWEBVIEW2_PERMISSION_KIND::default();
+                args.PermissionKind(&mut kind)?;
+                let kind = match kind {
+                    COREWEBVIEW2_PERMISSION_KIND_CAMERA => PermissionKind::Camera,
+                    COREWEBVIEW2_PERMISSION_KIND_MICROPHONE => PermissionKind::Microphone,
+                    COREWEBVIEW2_PERMISSION_KIND_GEOLOCATION => PermissionKind::Geolocation,
+                    COREWEBVIEW2_PERMISSION_KIND_NOTIFICATIONS => PermissionKind::Notifications,
+                    COREWEBVIEW2_PERMISSION_KIND_CLIPBOARD_READ => PermissionKind::ClipboardRead,
+                    _ => PermissionKind::Other,
+                };
+                let origin = {
+                    let mut uri = PWSTR::null();
+                    args.Uri(&mut uri)?;
+                    take_pwstr(uri)
+                };
+
+                let state = match decide(&handler, origin, [kind]) {
+                    PermissionDecision::Allow => COREWEBVIEW2_PERMISSION_STATE_ALLOW,
+                    PermissionDecision::Deny => COREWEBVIEW2_PERMISSION_STATE_DENY,
+                    PermissionDecision::Default => COREWEBVIEW2_PERMISSION_STATE_DEFAULT,
+                };
+                args.SetState(state)?;
+                Ok(())
+            })),
+            &mut token,
+        )
+    };
+
+    if let Err(err) = result {
+        tracing::error!("Failed to listen for permission requests: {err}");
+    }
+}
+
+/// Route the permission requests of the webview to the handler.
+///
+/// wry doesn't expose permission requests, so we hook into the native webview. WKWebView on macOS and iOS grants
+/// camera and microphone access itself and the other platforms don't have a hook, so the handler isn't called there.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub(crate) fn attach_permission_handler(webview: &wry::WebView, handler: PermissionHandler) {
+    use webkit2gtk::{
+        glib::{Cast, ObjectExt},
+        GeolocationPermissionRequest, NotificationPermissionRequest, PermissionRequestExt,
+        UserMediaPermissionRequest, UserMediaPermissionRequestExt, WebViewExt,
+    };
+    use wry::WebViewExtUnix;
+
+    webview
+        .webview()
+        .connect_permission_request(move |webview, request| {
+            let kinds = if let Some(media) = request.downcast_ref::<UserMediaPermissionRequest>() {
+                let mut kinds = Vec::new();
+                if media.is_for_video_device() {
+                    kinds.push(PermissionKind::Camera);
+                }
+                if media.is_for_audio_device() {
+                    kinds.push(PermissionKind::Microphone);
+                }
+                kinds
+            } else if request.is::<GeolocationPermissionRequest>() {
+                vec![PermissionKind::Geolocation]
+            } else if request.is::<NotificationPermissionRequest>() {
+                vec![PermissionKind::Notifications]
+            } else {
+                vec![PermissionKind::Other]
+            };
+            let origin = webview.uri().map(|uri| uri.to_string()).unwrap_or_default();
+
+            match decide(&handler, origin, kinds) {
+                PermissionDecision::Allow => request.allow(),
+                PermissionDecision::Deny => request.deny(),
+                // Let the default handler of webkit decide
+                PermissionDecision::Default => return false,
+            }
+            true
+        });
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+pub(crate) fn attach_permission_handler(_webview: &wry::WebView, _handler: PermissionHandler) {}
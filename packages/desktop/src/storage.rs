@@ -0,0 +1,69 @@
+use dioxus_signals::{MemoryStorage, PlatformStorage, StorageBackend};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+
+/// A storage backend that saves every value in a json file
+struct FileStorage {
+    path: PathBuf,
+    values: RefCell<HashMap<String, String>>,
+}
+
+impl FileStorage {
+    fn new(path: PathBuf) -> Self {
+        let values = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            values: RefCell::new(values),
+        }
+    }
+
+    fn save(&self) {
+        let result = serde_json::to_string(&*self.values.borrow())
+            .map_err(std::io::Error::other)
+            .and_then(|contents| {
+                if let Some(parent) = self.path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&self.path, contents)
+            });
+        if let Err(err) = result {
+            tracing::error!("Failed to save storage to {}: {err}", self.path.display());
+        }
+    }
+}
+
+impl StorageBackend for FileStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: String) {
+        self.values.borrow_mut().insert(key.to_string(), value);
+        self.save();
+    }
+
+    fn remove(&self, key: &str) {
+        self.values.borrow_mut().remove(key);
+        self.save();
+    }
+}
+
+/// Save local storage in the data directory of the app. Session storage is kept in memory.
+///
+/// If the data directory isn't set in the config, the local data directory of the platform is used.
+pub(crate) fn platform_storage(data_dir: Option<PathBuf>) -> PlatformStorage {
+    let data_dir = data_dir.or_else(|| {
+        let app_name = std::env::current_exe().ok()?.file_stem()?.to_owned();
+        Some(dirs::data_local_dir()?.join(app_name))
+    });
+    let local: Rc<dyn StorageBackend> = match data_dir {
+        Some(dir) => Rc::new(FileStorage::new(dir.join("storage.json"))),
+        None => Rc::new(MemoryStorage::default()),
+    };
+    PlatformStorage {
+        local,
+        session: Rc::new(MemoryStorage::default()),
+    }
+}
@@ -0,0 +1,312 @@
+//! Custom protocols that stream bytes to the webview with support for range requests
+
+use crate::protocol::get_mime_from_path;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+};
+use wry::http::{header, Request as HttpRequest, Response as HttpResponse, StatusCode};
+
+/// The most bytes we send in one response. Media elements ask for the rest of the file with an open ended range, so
+/// capping the response keeps us from reading a whole video into memory.
+const MAX_CHUNK_SIZE: u64 = 1024 * 1024;
+
+trait ReadSeek: Read + Seek + Send {}
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// Bytes to stream to the webview from a custom protocol registered with
+/// [`Config::with_streaming_protocol`](crate::Config::with_streaming_protocol).
+///
+/// The source can be any reader that can seek, like a [`File`](std::fs::File), a [`Cursor`](std::io::Cursor) or a
+/// reader that decrypts a file while it is read. Only the bytes of the requested range are read.
+pub struct StreamSource {
+    reader: Box<dyn ReadSeek>,
+    content_type: String,
+}
+
+impl StreamSource {
+    /// Stream the bytes of a reader with a content type like `video/mp4`
+    pub fn new(reader: impl Read + Seek + Send + 'static, content_type: impl Into<String>) -> Self {
+        Self {
+            reader: Box::new(reader),
+            content_type: content_type.into(),
+        }
+    }
+
+    /// Stream a file. The content type is guessed from the contents and extension of the file.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)?;
+        let content_type = get_mime_from_path(path).unwrap_or("application/octet-stream");
+        Ok(Self::new(file, content_type))
+    }
+}
+
+impl std::fmt::Debug for StreamSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamSource")
+            .field("content_type", &self.content_type)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Answer a request with the parts of the source the `Range` header asks for.
+///
+/// Requests with one range get a `206 Partial Content` response with at most one MiB of the source, and requests with
+/// several ranges get a `multipart/byteranges` response with at most one MiB of each range. Requests for ranges outside
+/// of the source get a `416 Range Not Satisfiable` response.
+///
+/// Some webviews, like WebKitGTK on Linux, never send a `Range` header. Requests without one get the whole source if it
+/// fits in one MiB, and a `206 Partial Content` response with the first MiB otherwise, so a large source is never read
+/// into memory at once.
+pub fn range_response(
+    request: &HttpRequest<Vec<u8>>,
+    mut source: StreamSource,
+) -> io::Result<HttpResponse<Vec<u8>>> {
+    let total = source.reader.seek(SeekFrom::End(0))?;
+
+    let ranges = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|range| range.to_str().ok())
+        .and_then(|range| parse_ranges(range, total));
+
+    let builder = HttpResponse::builder()
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+
+    let response = match ranges {
+        None if total <= MAX_CHUNK_SIZE => {
+            let body = source.read(0, total)?;
+            builder
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, &source.content_type)
+                .header(header::CONTENT_LENGTH, body.len())
+                .body(body)
+        }
+        None => partial_response(builder, &mut source, (0, total - 1), total)?,
+        Some(Err(())) => builder
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{total}"))
+            .body(Vec::new()),
+        Some(Ok(ranges)) if ranges.len() == 1 => {
+            partial_response(builder, &mut source, ranges[0], total)?
+        }
+        Some(Ok(ranges)) => {
+            // The boundary only needs to be unlikely to show up in the source
+            let boundary = format!("{:016x}", RandomState::new().build_hasher().finish());
+            let mut body = Vec::new();
+            for range in ranges {
+                let (start, end) = cap(range);
+                body.extend_from_slice(
+                    format!(
+                        "\r\n--{boundary}\r\n{}: {}\r\n{}: bytes {start}-{end}/{total}\r\n\r\n",
+                        header::CONTENT_TYPE,
+                        source.content_type,
+                        header::CONTENT_RANGE,
+                    )
+                    .as_bytes(),
+                );
+                body.extend(source.read(start, end + 1 - start)?);
+            }
+            body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+            builder
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(
+                    header::CONTENT_TYPE,
+                    format!("multipart/byteranges; boundary={boundary}"),
+                )
+                .header(header::CONTENT_LENGTH, body.len())
+                .body(body)
+        }
+    };
+
+    response.map_err(io::Error::other)
+}
+
+/// Answer with one range of the source, capped to [`MAX_CHUNK_SIZE`]
+fn partial_response(
+    builder: wry::http::response::Builder,
+    source: &mut StreamSource,
+    range: (u64, u64),
+    total: u64,
+) -> io::Result<Result<HttpResponse<Vec<u8>>, wry::http::Error>> {
+    let (start, end) = cap(range);
+    let body = source.read(start, end + 1 - start)?;
+    Ok(builder
+        .status(StatusCode::PARTIAL_CONTENT)
+        .header(header::CONTENT_TYPE, &source.content_type)
+        .header(
+            header::CONTENT_RANGE,
+            format!("bytes {start}-{end}/{total}"),
+        )
+        .header(header::CONTENT_LENGTH, body.len())
+        .body(body))
+}
+
+fn cap((start, end): (u64, u64)) -> (u64, u64) {
+    (start, end.min(start + MAX_CHUNK_SIZE - 1))
+}
+
+impl StreamSource {
+    fn read(&mut self, start: u64, len: u64) -> io::Result<Vec<u8>> {
+        let mut body = Vec::with_capacity(len as usize);
+        self.reader.seek(SeekFrom::Start(start))?;
+        (&mut self.reader).take(len).read_to_end(&mut body)?;
+        Ok(body)
+    }
+}
+
+/// Parse the ranges of a `Range: bytes=...` header into inclusive ranges of bytes in the source.
+///
+/// Ranges that start past the end of the source are skipped. Returns `None` if the header is invalid and should be
+/// ignored, and `Some(Err(()))` if none of the ranges are in the source.
+fn parse_ranges(header: &str, total: u64) -> Option<Result<Vec<(u64, u64)>, ()>> {
+    let specs = header.trim().strip_prefix("bytes=")?;
+
+    let mut ranges = Vec::new();
+    for spec in specs.split(',') {
+        let (start, end) = spec.trim().split_once('-')?;
+        let range = match (start.trim(), end.trim()) {
+            // The last n bytes
+            ("", suffix) => {
+                let suffix: u64 = suffix.parse().ok()?;
+                (suffix > 0 && total > 0).then(|| (total.saturating_sub(suffix), total - 1))
+            }
+            (start, end) => {
+                let start: u64 = start.parse().ok()?;
+                let end = match end {
+                    // Everything after the start
+                    "" => u64::MAX,
+                    end => end.parse().ok()?,
+                };
+                if end < start {
+                    return None;
+                }
+                (start < total).then(|| (start, end.min(total - 1)))
+            }
+        };
+        ranges.extend(range);
+    }
+
+    match ranges.is_empty() {
+        true => Some(Err(())),
+        false => Some(Ok(ranges)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(parse_ranges("bytes=0-499", 1000), Some(Ok(vec![(0, 499)])));
+        // The end is clamped to the source
+        assert_eq!(
+            parse_ranges("bytes=500-2000", 1000),
+            Some(Ok(vec![(500, 999)]))
+        );
+        assert_eq!(
+            parse_ranges("bytes=0-9, 20-29", 1000),
+            Some(Ok(vec![(0, 9), (20, 29)]))
+        );
+    }
+
+    #[test]
+    fn parses_suffix_ranges() {
+        assert_eq!(parse_ranges("bytes=-100", 1000), Some(Ok(vec![(900, 999)])));
+        // A suffix longer than the source is the whole source
+        assert_eq!(parse_ranges("bytes=-5000", 1000), Some(Ok(vec![(0, 999)])));
+        assert_eq!(parse_ranges("bytes=-0", 1000), Some(Err(())));
+    }
+
+    #[test]
+    fn parses_open_ended_ranges() {
+        assert_eq!(parse_ranges("bytes=100-", 1000), Some(Ok(vec![(100, 999)])));
+        assert_eq!(parse_ranges("bytes=1000-", 1000), Some(Err(())));
+    }
+
+    #[test]
+    fn ignores_invalid_ranges() {
+        // A range that ends before it starts makes the whole header invalid
+        assert_eq!(parse_ranges("bytes=500-100", 1000), None);
+        assert_eq!(parse_ranges("bytes=0-9, 500-100", 1000), None);
+        assert_eq!(parse_ranges("bytes=abc", 1000), None);
+        assert_eq!(parse_ranges("items=0-9", 1000), None);
+    }
+
+    #[test]
+    fn nothing_is_in_an_empty_source() {
+        assert_eq!(parse_ranges("bytes=0-9", 0), Some(Err(())));
+        assert_eq!(parse_ranges("bytes=-10", 0), Some(Err(())));
+        assert_eq!(parse_ranges("bytes=0-", 0), Some(Err(())));
+    }
+
+    fn respond(range: Option<&str>, len: usize) -> HttpResponse<Vec<u8>> {
+        let mut request = HttpRequest::builder().uri("media://video.mp4");
+        if let Some(range) = range {
+            request = request.header(header::RANGE, range);
+        }
+        let source = (0..len).map(|byte| byte as u8).collect::<Vec<_>>();
+        let source = StreamSource::new(Cursor::new(source), "video/mp4");
+        range_response(&request.body(Vec::new()).unwrap(), source).unwrap()
+    }
+
+    #[test]
+    fn ranges_outside_of_the_source_are_not_satisfiable() {
+        let response = respond(Some("bytes=2000-"), 1000);
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes */1000");
+        assert!(response.body().is_empty());
+    }
+
+    #[test]
+    fn responds_with_the_requested_range() {
+        let response = respond(Some("bytes=10-19"), 1000);
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            response.headers()[header::CONTENT_RANGE],
+            "bytes 10-19/1000"
+        );
+        assert_eq!(*response.body(), (10..20).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn large_sources_are_sent_in_chunks_without_a_range() {
+        let small = respond(None, 1000);
+        assert_eq!(small.status(), StatusCode::OK);
+        assert_eq!(small.body().len(), 1000);
+
+        let len = MAX_CHUNK_SIZE as usize * 3;
+        let large = respond(None, len);
+        assert_eq!(large.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            large.headers()[header::CONTENT_RANGE],
+            format!("bytes 0-{}/{len}", MAX_CHUNK_SIZE - 1)
+        );
+        assert_eq!(large.body().len() as u64, MAX_CHUNK_SIZE);
+    }
+
+    #[test]
+    fn several_ranges_are_sent_as_multipart() {
+        let response = respond(Some("bytes=0-1, 10-11"), 1000);
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        let content_type = response.headers()[header::CONTENT_TYPE].to_str().unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/byteranges; boundary=")
+            .unwrap();
+        let mut expected = Vec::new();
+        for (start, end) in [(0u8, 1u8), (10, 11)] {
+            expected.extend_from_slice(
+                format!("\r\n--{boundary}\r\ncontent-type: video/mp4\r\ncontent-range: bytes {start}-{end}/1000\r\n\r\n").as_bytes(),
+            );
+            expected.extend([start, end]);
+        }
+        expected.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        assert_eq!(*response.body(), expected);
+    }
+}
@@ -0,0 +1,274 @@
+//! Custom titlebars for windows without native decorations
+
+use crate::{use_window, use_wry_event_handler, window};
+use dioxus_core::prelude::*;
+use dioxus_core_macro::*;
+use dioxus_hooks::use_signal;
+use dioxus_html as dioxus_elements;
+use dioxus_signals::*;
+use serde::Deserialize;
+use tao::{event::Event, window::Window};
+
+/// Turns presses on `[data-dioxus-drag-region]` elements into window drags and reports hovers on the maximize control
+pub(crate) const TITLEBAR_SCRIPT: &str = r#"
+(() => {
+    const post = (action) => window.ipc.postMessage(JSON.stringify({ method: "window_control", params: { action } }));
+    const ignored = "button, input, select, textarea, a, [data-dioxus-no-drag]";
+    let snapTarget = null;
+    let snapTimer = null;
+    const hover = (target) => {
+        if (target === snapTarget) return;
+        clearTimeout(snapTimer);
+        snapTarget = target;
+        // Windows opens the snap layouts after hovering the maximize button for a moment
+        if (target) snapTimer = setTimeout(() => post("snap_layout"), 600);
+    };
+    document.addEventListener("mouseover", (e) => {
+        hover(e.target.closest ? e.target.closest('[data-dioxus-window-control="maximize"]') : null);
+    });
+    document.addEventListener("mousedown", (e) => {
+        hover(null);
+        if (e.button !== 0 || !e.target.closest) return;
+        const region = e.target.closest("[data-dioxus-drag-region]");
+        if (!region) return;
+        const control = e.target.closest(ignored);
+        if (control && region.contains(control)) return;
+        post(e.detail === 2 ? "toggle_maximize" : "drag");
+    });
+})();
+"#;
+
+/// A button of the window controls
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowControl {
+    /// Minimize the window
+    Minimize,
+    /// Maximize the window, or restore it if it is maximized
+    Maximize,
+    /// Close the window. This respects the [`WindowCloseBehaviour`](crate::WindowCloseBehaviour) of the app.
+    Close,
+}
+
+impl WindowControl {
+    fn as_str(&self) -> &'static str {
+        match self {
+            WindowControl::Minimize => "minimize",
+            WindowControl::Maximize => "maximize",
+            WindowControl::Close => "close",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            WindowControl::Minimize => "Minimize",
+            WindowControl::Maximize => "Maximize",
+            WindowControl::Close => "Close",
+        }
+    }
+
+    /// Run the action of the control on the current window
+    pub fn apply(&self) {
+        let window = window();
+        match self {
+            WindowControl::Minimize => window.set_minimized(true),
+            WindowControl::Maximize => window.toggle_maximized(),
+            WindowControl::Close => window.request_close(),
+        }
+    }
+}
+
+/// An area that moves the window when it is dragged and maximizes it when it is double clicked. Buttons, inputs and
+/// links inside of the region stay interactive.
+///
+/// Any element becomes a drag region with the `data-dioxus-drag-region` attribute, and elements inside of a region
+/// opt out with `data-dioxus-no-drag`.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::desktop::titlebar::{DragRegion, WindowControls};
+///
+/// fn TitleBar() -> Element {
+///     rsx! {
+///         DragRegion { class: "titlebar",
+///             span { "My App" }
+///             WindowControls { class: "controls" }
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn DragRegion(
+    /// Attributes of the region element
+    #[props(extends = GlobalAttributes)]
+    attributes: Vec<Attribute>,
+    /// The content of the titlebar
+    children: Element,
+) -> Element {
+    rsx! {
+        div { "data-dioxus-drag-region": true, ..attributes, {children} }
+    }
+}
+
+/// A button that minimizes, maximizes or closes the window. Shows a default icon if it has no children.
+///
+/// Style the buttons with the `[data-dioxus-window-control]` attribute selector. The maximize button has
+/// `data-maximized` while the window is maximized.
+#[component]
+pub fn WindowControlButton(
+    /// What the button does
+    control: WindowControl,
+    /// Attributes of the button element
+    #[props(extends = button, extends = GlobalAttributes)]
+    attributes: Vec<Attribute>,
+    /// A custom icon for the button
+    children: Element,
+) -> Element {
+    let window = use_window();
+    let mut maximized = use_signal(|| window.is_maximized());
+    use_wry_event_handler(move |event, _| {
+        if let Event::WindowEvent {
+            event: tao::event::WindowEvent::Resized(_),
+            window_id,
+            ..
+        } = event
+        {
+            if *window_id == window.id() {
+                maximized.set(window.is_maximized());
+            }
+        }
+    });
+
+    let icon = match children {
+        Ok(node) if node == VNode::placeholder() => default_icon(control, maximized()),
+        children => children,
+    };
+
+    rsx! {
+        button {
+            r#type: "button",
+            aria_label: control.label(),
+            "data-dioxus-window-control": control.as_str(),
+            "data-maximized": control == WindowControl::Maximize && maximized(),
+            onclick: move |_| control.apply(),
+            ..attributes,
+            {icon}
+        }
+    }
+}
+
+/// The minimize, maximize and close buttons in the order of the platform.
+///
+/// The buttons are only shown if the window doesn't have native ones. On macOS,
+/// [`Config::with_custom_titlebar`](crate::Config::with_custom_titlebar) keeps the native buttons over the content of
+/// the window, so this renders nothing there.
+#[component]
+pub fn WindowControls(
+    /// Attributes of the element around the buttons
+    #[props(extends = GlobalAttributes)]
+    attributes: Vec<Attribute>,
+) -> Element {
+    let window = use_window();
+    if window.is_decorated() {
+        return VNode::empty();
+    }
+
+    let controls = if cfg!(target_os = "macos") {
+        [
+            WindowControl::Close,
+            WindowControl::Minimize,
+            WindowControl::Maximize,
+        ]
+    } else {
+        [
+            WindowControl::Minimize,
+            WindowControl::Maximize,
+            WindowControl::Close,
+        ]
+    };
+
+    rsx! {
+        div { ..attributes,
+            for control in controls {
+                WindowControlButton { key: "{control.as_str()}", control }
+            }
+        }
+    }
+}
+
+fn default_icon(control: WindowControl, maximized: bool) -> Element {
+    let path = match control {
+        WindowControl::Minimize => "M0 5h10",
+        WindowControl::Maximize if maximized => "M2.5 2.5V0.5h7v7h-2M0.5 2.5h7v7h-7z",
+        WindowControl::Maximize => "M0.5 0.5h9v9h-9z",
+        WindowControl::Close => "M0 0l10 10M10 0L0 10",
+    };
+
+    rsx! {
+        svg {
+            width: "10",
+            height: "10",
+            view_box: "0 0 10 10",
+            fill: "none",
+            stroke: "currentColor",
+            path { d: path }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct WindowControlRequest {
+    action: String,
+}
+
+/// Handle a message from the titlebar script
+pub(crate) fn handle_window_control(window: &Window, params: serde_json::Value) {
+    let Ok(request) = serde_json::from_value::<WindowControlRequest>(params) else {
+        return;
+    };
+
+    match request.action.as_str() {
+        "drag" if window.fullscreen().is_none() => _ = window.drag_window(),
+        "toggle_maximize" if window.is_resizable() => window.set_maximized(!window.is_maximized()),
+        "snap_layout" if window.is_resizable() && window.is_focused() => show_snap_layouts(),
+        _ => {}
+    }
+}
+
+/// Windows 11 opens the snap layouts of the focused window with Win+Z. Windows without a native titlebar don't get
+/// them when hovering the maximize button, so we press the shortcut for them.
+#[cfg(target_os = "windows")]
+fn show_snap_layouts() {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+        SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, VK_LWIN, VK_Z,
+    };
+
+    let key = |key, flags| INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: key,
+                wScan: 0,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    };
+    let inputs = [
+        key(VK_LWIN, 0),
+        key(VK_Z, 0),
+        key(VK_Z, KEYEVENTF_KEYUP),
+        key(VK_LWIN, KEYEVENTF_KEYUP),
+    ];
+
+    unsafe {
+        SendInput(
+            inputs.len() as u32,
+            inputs.as_ptr(),
+            std::mem::size_of::<INPUT>() as i32,
+        );
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn show_snap_layouts() {}
@@ -4,6 +4,7 @@ use dioxus_core::{
     prelude::{provide_context, try_consume_context},
     use_hook,
 };
+use tray_icon::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
 
 #[cfg(not(any(target_os = "ios", target_os = "android")))]
 pub use tray_icon::*;
@@ -34,25 +35,25 @@ pub fn init_tray_icon(menu: DioxusTrayMenu, icon: Option<DioxusTrayIcon>) -> Dio
         let builder = tray_icon::TrayIconBuilder::new()
             .with_menu(Box::new(menu))
             .with_menu_on_left_click(false)
-            .with_icon(match icon {
-                Some(value) => value,
-                None => tray_icon::Icon::from_rgba(
-                    include_bytes!("./assets/default_icon.bin").to_vec(),
-                    460,
-                    460,
-                )
-                .expect("image parse failed"),
-            });
+            .with_icon(icon.unwrap_or_else(default_icon));
 
         provide_context(builder.build().expect("tray icon builder failed"))
     }
 }
 
+fn default_icon() -> DioxusTrayIcon {
+    tray_icon::Icon::from_rgba(
+        include_bytes!("./assets/default_icon.bin").to_vec(),
+        460,
+        460,
+    )
+    .expect("image parse failed")
+}
+
 /// Returns a default tray icon menu
 pub fn default_tray_icon() -> DioxusTrayMenu {
     #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
     {
-        use tray_icon::menu::{Menu, PredefinedMenuItem};
         let tray_menu = Menu::new();
         tray_menu
             .append_items(&[&PredefinedMenuItem::quit(None)])
@@ -66,3 +67,291 @@ pub fn default_tray_icon() -> DioxusTrayMenu {
 pub fn use_tray_icon() -> Option<tray_icon::TrayIcon> {
     use_hook(try_consume_context)
 }
+
+/// The id of the quit item in a [`TrayMenu`]. The app exits when it is clicked.
+pub(crate) const QUIT_ITEM_ID: &str = "dioxus-tray-quit";
+
+/// A tray icon, shown in the system tray on Windows and Linux and in the menu bar on macOS. Create it with
+/// [`use_tray`].
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::desktop::trayicon::{use_tray, Tray, TrayEvent, TrayMenu};
+/// use dioxus::desktop::{use_tray_event, window};
+///
+/// fn App() -> Element {
+///     use_tray(|| {
+///         Tray::new()
+///             .tooltip("Pomodoro")
+///             .menu(TrayMenu::new().item("start", "Start timer").separator().quit("Quit"))
+///     });
+///
+///     use_tray_event(|event| {
+///         if let TrayEvent::Menu(id) = event {
+///             if id == "start" {
+///                 window().set_visible(true);
+///             }
+///         }
+///     });
+///
+///     rsx! { "25:00" }
+/// }
+/// ```
+#[derive(Default)]
+pub struct Tray {
+    icon: Option<DioxusTrayIcon>,
+    tooltip: Option<String>,
+    title: Option<String>,
+    menu: Option<TrayMenu>,
+    menu_on_left_click: bool,
+}
+
+impl Tray {
+    /// Create a tray icon with the default dioxus icon and no menu
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the icon shown in the tray
+    pub fn icon(mut self, icon: DioxusTrayIcon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Set the text shown when hovering the icon
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Set the text shown next to the icon. Only supported on macOS and Linux.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the menu that opens when the icon is right clicked
+    pub fn menu(mut self, menu: TrayMenu) -> Self {
+        self.menu = Some(menu);
+        self
+    }
+
+    /// Open the menu on left clicks too. By default a left click shows and focuses the windows of the app instead.
+    pub fn menu_on_left_click(mut self, enable: bool) -> Self {
+        self.menu_on_left_click = enable;
+        self
+    }
+
+    /// Add the icon to the tray. It is removed when the returned [`TrayIcon`] and all of its clones are dropped.
+    pub fn build(self) -> tray_icon::Result<DioxusTray> {
+        let mut builder = TrayIconBuilder::new()
+            .with_menu_on_left_click(self.menu_on_left_click)
+            .with_icon(self.icon.unwrap_or_else(default_icon))
+            // Linux only shows the icon if it has a menu, so give it an empty one
+            .with_menu(Box::new(self.menu.unwrap_or_default().build()));
+        if let Some(tooltip) = self.tooltip {
+            builder = builder.with_tooltip(tooltip);
+        }
+        if let Some(title) = self.title {
+            builder = builder.with_title(title);
+        }
+        builder.build()
+    }
+}
+
+/// The menu of a [`Tray`]. Clicks on the items are delivered to [`use_tray_event`](crate::use_tray_event) as
+/// [`TrayEvent::Menu`] with the id of the item.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrayMenu {
+    items: Vec<TrayMenuItem>,
+}
+
+/// An entry in a [`TrayMenu`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum TrayMenuItem {
+    /// A clickable item
+    Item {
+        /// The id reported when the item is clicked
+        id: String,
+        /// The text of the item
+        label: String,
+        /// If the item can be clicked
+        enabled: bool,
+    },
+    /// An item with a check mark that toggles when it is clicked
+    Check {
+        /// The id reported when the item is clicked
+        id: String,
+        /// The text of the item
+        label: String,
+        /// If the check mark is shown
+        checked: bool,
+        /// If the item can be clicked
+        enabled: bool,
+    },
+    /// A nested menu
+    Submenu {
+        /// The text of the item that opens the menu
+        label: String,
+        /// The items of the nested menu
+        menu: TrayMenu,
+    },
+    /// A line between items
+    Separator,
+    /// An item that exits the app when it is clicked
+    Quit {
+        /// The text of the item
+        label: String,
+    },
+}
+
+impl TrayMenu {
+    /// Create an empty menu
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a clickable item
+    pub fn item(self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.push(TrayMenuItem::Item {
+            id: id.into(),
+            label: label.into(),
+            enabled: true,
+        })
+    }
+
+    /// Add an item with a check mark
+    pub fn check(self, id: impl Into<String>, label: impl Into<String>, checked: bool) -> Self {
+        self.push(TrayMenuItem::Check {
+            id: id.into(),
+            label: label.into(),
+            checked,
+            enabled: true,
+        })
+    }
+
+    /// Add a nested menu
+    pub fn submenu(self, label: impl Into<String>, menu: TrayMenu) -> Self {
+        self.push(TrayMenuItem::Submenu {
+            label: label.into(),
+            menu,
+        })
+    }
+
+    /// Add a line between items
+    pub fn separator(self) -> Self {
+        self.push(TrayMenuItem::Separator)
+    }
+
+    /// Add an item that exits the app
+    pub fn quit(self, label: impl Into<String>) -> Self {
+        self.push(TrayMenuItem::Quit {
+            label: label.into(),
+        })
+    }
+
+    /// Add any item
+    pub fn push(mut self, item: TrayMenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// The items of the menu
+    pub fn items(&self) -> &[TrayMenuItem] {
+        &self.items
+    }
+
+    /// Create the native menu. Pass it to [`TrayIcon::set_menu`] to replace the menu of an existing tray icon.
+    pub fn build(&self) -> DioxusTrayMenu {
+        let menu = Menu::new();
+        for item in &self.items {
+            _ = menu.append(item.build().as_ref());
+        }
+        menu
+    }
+}
+
+impl TrayMenuItem {
+    fn build(&self) -> Box<dyn tray_icon::menu::IsMenuItem> {
+        match self {
+            TrayMenuItem::Item { id, label, enabled } => {
+                Box::new(MenuItem::with_id(id.as_str(), label, *enabled, None))
+            }
+            TrayMenuItem::Check {
+                id,
+                label,
+                checked,
+                enabled,
+            } => Box::new(CheckMenuItem::with_id(
+                id.as_str(),
+                label,
+                *enabled,
+                *checked,
+                None,
+            )),
+            TrayMenuItem::Submenu { label, menu } => {
+                let submenu = Submenu::new(label, true);
+                for item in &menu.items {
+                    _ = submenu.append(item.build().as_ref());
+                }
+                Box::new(submenu)
+            }
+            TrayMenuItem::Separator => Box::new(PredefinedMenuItem::separator()),
+            // The predefined quit item doesn't work on linux, so the event loop handles this id instead
+            TrayMenuItem::Quit { label } => {
+                Box::new(MenuItem::with_id(QUIT_ITEM_ID, label, true, None))
+            }
+        }
+    }
+}
+
+/// Something the user did with a tray icon
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum TrayEvent {
+    /// The icon was clicked
+    Click {
+        /// The button that was released
+        button: MouseButton,
+        /// Where the icon was clicked, in physical pixels
+        position: (f64, f64),
+    },
+    /// The icon was double clicked. Only reported on Windows.
+    DoubleClick {
+        /// The button that was clicked
+        button: MouseButton,
+    },
+    /// The item of a [`TrayMenu`] with this id was clicked
+    Menu(String),
+}
+
+impl TrayEvent {
+    pub(crate) fn from_icon_event(event: &TrayIconEvent) -> Option<Self> {
+        match event {
+            TrayIconEvent::Click {
+                button,
+                button_state: MouseButtonState::Up,
+                position,
+                ..
+            } => Some(TrayEvent::Click {
+                button: *button,
+                position: (position.x, position.y),
+            }),
+            TrayIconEvent::DoubleClick { button, .. } => {
+                Some(TrayEvent::DoubleClick { button: *button })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Add a tray icon while the component is mounted. The tray is created once and removed when the component is
+/// dropped. Children can get it with [`use_tray_icon`].
+///
+/// ## Panics
+///
+/// Panics if the platform fails to create the tray icon.
+pub fn use_tray(tray: impl FnOnce() -> Tray) -> DioxusTray {
+    use_hook(|| provide_context(tray().build().expect("tray icon builder failed")))
+}
@@ -8,16 +8,18 @@ use crate::{
     file_upload::{NativeFileEngine, NativeFileHover},
     ipc::UserWindowEvent,
     protocol,
+    requests::{DownloadFinished, DownloadRequest, NewWindowAction},
     waker::tao_waker,
     Config, DesktopContext, DesktopService,
 };
 use crate::{document::DesktopDocument, WeakDesktopContext};
 use base64::prelude::BASE64_STANDARD;
+use dioxus_clipboard::{Clipboard, EvalClipboard};
 use dioxus_core::{Runtime, ScopeId, VirtualDom};
 use dioxus_document::Document;
 use dioxus_history::{History, MemoryHistory};
 use dioxus_hooks::to_owned;
-use dioxus_html::{HasFileData, HtmlEvent, PlatformEventData};
+use dioxus_html::{HasDragData, HasFileData, HtmlEvent, PlatformEventData};
 use futures_util::{pin_mut, FutureExt};
 use std::cell::OnceCell;
 use std::sync::Arc;
@@ -92,7 +94,7 @@ impl WebviewEdits {
                     String::from_utf8(request.body().to_vec()),
                     request
                 );
-                SynchronousEventResponse::new(false)
+                SynchronousEventResponse::new(false, false)
             }
         };
 
@@ -129,14 +131,10 @@ impl WebviewEdits {
             dioxus_html::EventData::Drag(ref drag) => {
                 // we want to override this with a native file engine, provided by the most recent drag event
                 if drag.files().is_some() {
-                    let file_event = recent_file.current().unwrap();
-                    let paths = match file_event {
-                        wry::DragDropEvent::Enter { paths, .. } => paths,
-                        wry::DragDropEvent::Drop { paths, .. } => paths,
-                        _ => vec![],
-                    };
+                    let paths = recent_file.paths();
                     Rc::new(PlatformEventData::new(Box::new(DesktopFileDragEvent {
                         mouse: drag.mouse.clone(),
+                        data_transfer: drag.data_transfer(),
                         files: Arc::new(NativeFileEngine::new(paths)),
                     })))
                 } else {
@@ -150,7 +148,7 @@ impl WebviewEdits {
         self.runtime.handle_event(&name, event.clone(), element);
 
         // Get the response from the event
-        SynchronousEventResponse::new(!event.default_action_enabled())
+        SynchronousEventResponse::new(!event.default_action_enabled(), event.propagation_stopped())
     }
 }
 
@@ -159,6 +157,7 @@ pub(crate) struct WebviewInstance {
     pub edits: WebviewEdits,
     pub desktop_context: DesktopContext,
     pub waker: Waker,
+    pub history: Rc<MemoryHistory>,
 
     // Wry assumes the webcontext is alive for the lifetime of the webview.
     // We need to keep the webcontext alive, otherwise the webview will crash
@@ -175,9 +174,18 @@ pub(crate) struct WebviewInstance {
 impl WebviewInstance {
     pub(crate) fn new(
         mut cfg: Config,
-        dom: VirtualDom,
+        mut dom: VirtualDom,
         shared: Rc<SharedContext>,
     ) -> WebviewInstance {
+        // Show errors that no error boundary handled in the error overlay
+        #[cfg(all(feature = "devtools", debug_assertions))]
+        {
+            let proxy = shared.proxy.clone();
+            dom = dioxus_devtools::report_unhandled_errors(dom, move |report| {
+                _ = proxy.send_event(UserWindowEvent::ErrorReport(report));
+            });
+        }
+
         let mut window = cfg.window.clone();
 
         // tao makes small windows for some reason, make them bigger on desktop
@@ -203,6 +211,29 @@ impl WebviewInstance {
             ));
         }
 
+        if cfg.custom_titlebar {
+            #[cfg(target_os = "macos")]
+            {
+                use tao::platform::macos::WindowBuilderExtMacOS;
+                window = window
+                    .with_titlebar_transparent(true)
+                    .with_title_hidden(true)
+                    .with_fullsize_content_view(true);
+            }
+
+            #[cfg(not(target_os = "macos"))]
+            {
+                window = window.with_decorations(false);
+            }
+
+            // Keep the shadow and resize borders of the window
+            #[cfg(target_os = "windows")]
+            {
+                use tao::platform::windows::WindowBuilderExtWindows;
+                window = window.with_undecorated_shadow(true);
+            }
+        }
+
         let window = window.build(&shared.target).unwrap();
 
         // https://developer.apple.com/documentation/appkit/nswindowcollectionbehavior/nswindowcollectionbehaviormanaged
@@ -373,6 +404,35 @@ impl WebviewInstance {
             webview = webview.with_asynchronous_custom_protocol(name, handler);
         }
 
+        if let Some(mut handler) = cfg.download_handler.take() {
+            webview = webview.with_download_started_handler(move |url, destination| {
+                let mut download = DownloadRequest {
+                    url,
+                    destination: destination.clone(),
+                };
+                let allow = handler(&mut download);
+                *destination = download.destination;
+                allow
+            });
+        }
+
+        if let Some(handler) = cfg.download_finished_handler.take() {
+            webview = webview.with_download_completed_handler(move |url, path, success| {
+                handler(DownloadFinished { url, path, success })
+            });
+        }
+
+        if let Some(handler) = cfg.new_window_handler.take() {
+            webview = webview.with_new_window_req_handler(move |url| match handler(&url) {
+                NewWindowAction::Allow => true,
+                NewWindowAction::Deny => false,
+                NewWindowAction::OpenInBrowser => {
+                    _ = webbrowser::open(&url);
+                    false
+                }
+            });
+        }
+
         const INITIALIZATION_SCRIPT: &str = r#"
         if (document.addEventListener) {
             document.addEventListener('contextmenu', function(e) {
@@ -385,6 +445,8 @@ impl WebviewInstance {
         }
         "#;
 
+        webview = webview.with_initialization_script(crate::titlebar::TITLEBAR_SCRIPT);
+
         if cfg.disable_context_menu {
             // in release mode, we don't want to show the dev tool or reload menus
             webview = webview.with_initialization_script(INITIALIZATION_SCRIPT)
@@ -395,6 +457,10 @@ impl WebviewInstance {
 
         let webview = webview.build().unwrap();
 
+        if let Some(handler) = cfg.permission_handler.take() {
+            crate::requests::attach_permission_handler(&webview, handler);
+        }
+
         let menu = if cfg!(not(any(target_os = "android", target_os = "ios"))) {
             let menu_option = cfg.menu.into();
             if let Some(menu) = &menu_option {
@@ -415,12 +481,18 @@ impl WebviewInstance {
 
         // Provide the desktop context to the virtual dom and edit handler
         edits.set_desktop_context(Rc::downgrade(&desktop_context));
+        shared.windows.register(&desktop_context, &mut dom);
         let provider: Rc<dyn Document> = Rc::new(DesktopDocument::new(desktop_context.clone()));
-        let history_provider: Rc<dyn History> = Rc::new(MemoryHistory::default());
+        let clipboard_provider: Rc<dyn Clipboard> = Rc::new(EvalClipboard::new(provider.clone()));
+        let history = Rc::new(MemoryHistory::default());
+        let history_provider: Rc<dyn History> = history.clone();
+        let storage = crate::storage::platform_storage(cfg.data_dir.clone());
         dom.in_runtime(|| {
             ScopeId::ROOT.provide_context(desktop_context.clone());
             ScopeId::ROOT.provide_context(provider);
+            ScopeId::ROOT.provide_context(clipboard_provider);
             ScopeId::ROOT.provide_context(history_provider);
+            ScopeId::ROOT.provide_context(storage);
         });
 
         WebviewInstance {
@@ -428,6 +500,7 @@ impl WebviewInstance {
             edits,
             waker: tao_waker(shared.proxy.clone(), desktop_context.window.id()),
             desktop_context,
+            history,
             _menu: menu,
             _web_context: web_context,
         }
@@ -484,12 +557,17 @@ impl WebviewInstance {
 pub struct SynchronousEventResponse {
     #[serde(rename = "preventDefault")]
     prevent_default: bool,
+    #[serde(rename = "stopPropagation")]
+    stop_propagation: bool,
 }
 
 impl SynchronousEventResponse {
     /// Create a new SynchronousEventResponse
     #[allow(unused)]
-    pub fn new(prevent_default: bool) -> Self {
-        Self { prevent_default }
+    pub fn new(prevent_default: bool, stop_propagation: bool) -> Self {
+        Self {
+            prevent_default,
+            stop_propagation,
+        }
     }
 }
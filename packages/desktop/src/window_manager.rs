@@ -0,0 +1,341 @@
+use crate::{
+    app::SharedContext, ipc::UserWindowEvent, webview::WebviewInstance, window, Config,
+    DesktopContext, WeakDesktopContext,
+};
+use dioxus_core::{
+    prelude::{current_scope_id, use_hook_with_cleanup, ComponentFunction, ScopeId},
+    use_hook, Runtime, VirtualDom,
+};
+use dioxus_signals::Signal;
+use rustc_hash::FxHashMap;
+use slab::Slab;
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    rc::Rc,
+};
+use tao::window::WindowId;
+
+/// Get a handle to the windows of the app
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::desktop::{use_window_manager, Config};
+///
+/// fn App() -> Element {
+///     let windows = use_window_manager();
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| {
+///                 windows.open(Editor, EditorProps { file: "notes.md".into() }, Config::new());
+///             },
+///             "Open notes"
+///         }
+///         "{windows.windows().len()} windows open"
+///     }
+/// }
+///
+/// #[component]
+/// fn Editor(file: String) -> Element {
+///     rsx! { "Editing {file}" }
+/// }
+/// ```
+pub fn use_window_manager() -> WindowManager {
+    use_hook(window_manager)
+}
+
+/// Get a handle to the windows of the app without using a hook
+///
+/// ## Panics
+///
+/// This function will panic if it is called outside of the context of a Dioxus App.
+pub fn window_manager() -> WindowManager {
+    WindowManager {
+        shared: window().shared.clone(),
+    }
+}
+
+/// Opens, closes and lists the windows of the app and passes messages between them. Get it with
+/// [`use_window_manager`].
+///
+/// Every window runs its own [`VirtualDom`], so contexts and signals aren't shared between windows by default. Use
+/// [`WindowManager::provide_shared_context`] and [`use_shared_signal`] to share state, and [`WindowHandle::send`]
+/// or [`WindowManager::broadcast`] with [`use_window_message`] to send messages between windows.
+#[derive(Clone)]
+pub struct WindowManager {
+    shared: Rc<SharedContext>,
+}
+
+impl WindowManager {
+    /// Open a new window that renders the component with the props
+    pub fn open<P: Clone + 'static, M: 'static>(
+        &self,
+        component: impl ComponentFunction<P, M>,
+        props: P,
+        cfg: Config,
+    ) -> WindowHandle {
+        self.open_dom(VirtualDom::new_with_props(component, props), cfg)
+    }
+
+    /// Open a new window that renders the virtual dom
+    pub fn open_dom(&self, dom: VirtualDom, cfg: Config) -> WindowHandle {
+        let context = open_window(&self.shared, dom, cfg);
+        WindowHandle::new(&context)
+    }
+
+    /// The windows that are open, in the order they were opened
+    pub fn windows(&self) -> Vec<WindowHandle> {
+        let mut windows = self.shared.windows.windows.borrow_mut();
+        windows.retain(|window| window.strong_count() > 0);
+        let handles = windows
+            .iter()
+            .filter_map(|window| window.upgrade())
+            .map(|context| WindowHandle::new(&context))
+            .collect();
+        handles
+    }
+
+    /// Get the window with this id if it is still open
+    pub fn get(&self, id: WindowId) -> Option<WindowHandle> {
+        self.windows().into_iter().find(|window| window.id() == id)
+    }
+
+    /// Close the window with this id
+    pub fn close(&self, id: WindowId) {
+        _ = self
+            .shared
+            .proxy
+            .send_event(UserWindowEvent::CloseWindow(id));
+    }
+
+    /// Provide a context to the root of every window opened after this call, in addition to the contexts the new
+    /// window provides itself. The value is cloned into each window.
+    pub fn provide_shared_context<T: Clone + 'static>(&self, value: T) {
+        self.shared
+            .windows
+            .contexts
+            .borrow_mut()
+            .push(Rc::new(move || Box::new(value.clone()) as Box<dyn Any>));
+    }
+
+    /// Send a message to every window that listens for messages of this type with [`use_window_message`]
+    pub fn broadcast<T: Clone + 'static>(&self, message: T) {
+        self.shared.windows.send(None, message);
+    }
+}
+
+/// A handle to a window opened with the [`WindowManager`]. The handle doesn't keep the window open.
+#[derive(Clone)]
+pub struct WindowHandle {
+    id: WindowId,
+    context: WeakDesktopContext,
+}
+
+impl WindowHandle {
+    fn new(context: &DesktopContext) -> Self {
+        Self {
+            id: context.window.id(),
+            context: Rc::downgrade(context),
+        }
+    }
+
+    /// The id of the window
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    /// Get the [`DesktopContext`] of the window if it is still open
+    pub fn desktop(&self) -> Option<DesktopContext> {
+        self.context.upgrade()
+    }
+
+    /// Check if the window is still open
+    pub fn is_open(&self) -> bool {
+        self.context.strong_count() > 0
+    }
+
+    /// Show the window and bring it to the front
+    pub fn focus(&self) {
+        if let Some(context) = self.desktop() {
+            context.window.set_visible(true);
+            context.window.set_focus();
+        }
+    }
+
+    /// Close the window
+    pub fn close(&self) {
+        if let Some(context) = self.desktop() {
+            context.close();
+        }
+    }
+
+    /// Send a message to the window. It is delivered to the handlers of the window that listen for messages of this
+    /// type with [`use_window_message`].
+    pub fn send<T: Clone + 'static>(&self, message: T) {
+        if let Some(context) = self.desktop() {
+            context.shared.windows.send(Some(self.id), message);
+        }
+    }
+}
+
+impl PartialEq for WindowHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl std::fmt::Debug for WindowHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WindowHandle")
+            .field("id", &self.id)
+            .field("open", &self.is_open())
+            .finish()
+    }
+}
+
+/// Run a handler when another window sends this window a message of type `T` with [`WindowHandle::send`] or
+/// [`WindowManager::broadcast`]. The handler is removed when the component is dropped.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::desktop::{use_window_message, window_manager};
+///
+/// #[derive(Clone)]
+/// struct ThemeChanged(&'static str);
+///
+/// fn Settings() -> Element {
+///     rsx! {
+///         button {
+///             onclick: move |_| window_manager().broadcast(ThemeChanged("dark")),
+///             "Dark mode"
+///         }
+///     }
+/// }
+///
+/// fn Editor() -> Element {
+///     let mut theme = use_signal(|| "light");
+///     use_window_message(move |ThemeChanged(new_theme)| theme.set(new_theme));
+///
+///     rsx! { div { class: "{theme}" } }
+/// }
+/// ```
+pub fn use_window_message<T: Clone + 'static>(mut handler: impl FnMut(T) + 'static) {
+    let runtime = Runtime::current().unwrap();
+    let scope_id = current_scope_id().unwrap();
+    let handler = RefCell::new(move |message: &dyn Any| {
+        if let Some(message) = message.downcast_ref::<T>() {
+            runtime.on_scope(scope_id, || handler(message.clone()))
+        }
+    });
+
+    use_hook_with_cleanup(
+        move || {
+            let context = window();
+            let key = context
+                .shared
+                .windows
+                .listeners
+                .borrow_mut()
+                .insert(MessageListener {
+                    window: context.window.id(),
+                    message: TypeId::of::<T>(),
+                    handler: Rc::new(move |message| (*handler.borrow_mut())(message)),
+                });
+            (context.shared.clone(), key)
+        },
+        |(shared, key)| {
+            let mut listeners = shared.windows.listeners.borrow_mut();
+            if listeners.contains(key) {
+                listeners.remove(key);
+            }
+        },
+    );
+}
+
+/// Get a signal that is shared between all windows of the app. Every window that calls this with the same type `T`
+/// gets the same signal, and writing to it reruns the components that read it in every window.
+///
+/// The signal is created with `init` the first time it is used and lives until the app exits.
+#[track_caller]
+pub fn use_shared_signal<T: 'static>(init: impl FnOnce() -> T) -> Signal<T> {
+    let caller = std::panic::Location::caller();
+    use_hook(move || {
+        let shared = window().shared.clone();
+        let mut signals = shared.windows.signals.borrow_mut();
+        let signal = signals.entry(TypeId::of::<T>()).or_insert_with(|| {
+            let signal: Signal<T> = Signal::leak_with_caller(init(), caller);
+            Box::new(signal)
+        });
+        let signal = *signal.downcast_ref::<Signal<T>>().unwrap();
+        signal
+    })
+}
+
+/// Create the webview for the virtual dom and queue it up to be added to the event loop
+pub(crate) fn open_window(
+    shared: &Rc<SharedContext>,
+    dom: VirtualDom,
+    cfg: Config,
+) -> DesktopContext {
+    let window = WebviewInstance::new(cfg, dom, shared.clone());
+
+    let cx = window.dom.in_runtime(|| {
+        ScopeId::ROOT
+            .consume_context::<Rc<crate::DesktopService>>()
+            .unwrap()
+    });
+
+    shared.proxy.send_event(UserWindowEvent::NewWindow).unwrap();
+
+    shared.pending_webviews.borrow_mut().push(window);
+
+    cx
+}
+
+type ContextFactory = Rc<dyn Fn() -> Box<dyn Any>>;
+type MessageHandler = Rc<dyn Fn(&dyn Any)>;
+
+/// The state the window manager shares between all windows
+#[derive(Default)]
+pub(crate) struct WindowRegistry {
+    windows: RefCell<Vec<WeakDesktopContext>>,
+    contexts: RefCell<Vec<ContextFactory>>,
+    signals: RefCell<FxHashMap<TypeId, Box<dyn Any>>>,
+    listeners: RefCell<Slab<MessageListener>>,
+}
+
+struct MessageListener {
+    window: WindowId,
+    message: TypeId,
+    handler: MessageHandler,
+}
+
+impl WindowRegistry {
+    /// Track a new window and provide the shared contexts to it
+    pub(crate) fn register(&self, context: &DesktopContext, dom: &mut VirtualDom) {
+        self.windows.borrow_mut().push(Rc::downgrade(context));
+        for context in self.contexts.borrow().iter() {
+            dom.insert_any_root_context(context());
+        }
+    }
+
+    /// Deliver a message to the listeners of the window, or all windows if there is no target
+    fn send<T: 'static>(&self, target: Option<WindowId>, message: T) {
+        // Collect the handlers first so they can add or remove listeners while they run
+        let handlers: Vec<_> = self
+            .listeners
+            .borrow()
+            .iter()
+            .filter(|(_, listener)| {
+                listener.message == TypeId::of::<T>()
+                    && (target.is_none() || target == Some(listener.window))
+            })
+            .map(|(_, listener)| listener.handler.clone())
+            .collect();
+
+        for handler in handlers {
+            handler(&message);
+        }
+    }
+}
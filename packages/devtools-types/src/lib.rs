@@ -1,4 +1,4 @@
-use dioxus_core::internal::HotReloadTemplateWithLocation;
+use dioxus_core::{internal::HotReloadTemplateWithLocation, CapturedError};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -31,17 +31,59 @@ pub enum ClientMsg {
         level: String,
         messages: Vec<String>,
     },
+
+    /// The app panicked or threw an error that no error boundary handled
+    Error(ErrorReport),
+}
+
+/// A panic or unhandled error in the app. The error overlay shows it over the app during development.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ErrorReport {
+    /// The panic message or the error that was thrown
+    pub message: String,
+
+    /// The file, line and column of the panic if it is known
+    pub location: Option<String>,
+
+    /// The names of the components from the component that failed up to the root of the app
+    pub component_stack: Vec<String>,
+}
+
+impl ErrorReport {
+    /// Create a report for an error thrown in the virtual dom
+    pub fn from_error(error: &CapturedError) -> Self {
+        Self {
+            message: error.error().to_string(),
+            location: None,
+            component_stack: Self::component_names(error.origin().component_stack()),
+        }
+    }
+
+    /// Strip the module path from the names of components
+    pub fn component_names(stack: Vec<&'static str>) -> Vec<String> {
+        stack
+            .into_iter()
+            .map(|name| name.rsplit("::").next().unwrap_or(name).to_string())
+            .collect()
+    }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
 pub struct HotReloadMsg {
     pub templates: Vec<HotReloadTemplateWithLocation>,
     pub assets: Vec<PathBuf>,
+    /// Assets that were referenced with a new `asset!()` call and bundled without a full rebuild. The templates
+    /// in this message may point to these assets
+    #[serde(default)]
+    pub new_assets: Vec<PathBuf>,
     pub unknown_files: Vec<PathBuf>,
 }
 
 impl HotReloadMsg {
     pub fn is_empty(&self) -> bool {
-        self.templates.is_empty() && self.assets.is_empty() && self.unknown_files.is_empty()
+        self.templates.is_empty()
+            && self.assets.is_empty()
+            && self.new_assets.is_empty()
+            && self.unknown_files.is_empty()
     }
 }
@@ -3,6 +3,9 @@ pub use dioxus_devtools_types::*;
 use dioxus_signals::{GlobalKey, Writable};
 use warnings::Warning;
 
+mod overlay;
+pub use overlay::*;
+
 /// Applies template and literal changes to the VirtualDom
 ///
 /// Assets need to be handled by the renderer.
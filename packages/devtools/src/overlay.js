@@ -0,0 +1,119 @@
+// The overlay that shows panics and unhandled errors over the app during development. The overlay renders into a
+// shadow root so the styles of the app don't leak into it.
+(function () {
+  if (window.__dxErrorOverlay) {
+    return;
+  }
+
+  const STYLE = `
+    :host { all: initial; }
+    .backdrop {
+      position: fixed; inset: 0; z-index: 2147483647; overflow: auto;
+      background: rgba(0, 0, 0, 0.66); font-family: ui-monospace, Menlo, Consolas, monospace;
+    }
+    .panel {
+      box-sizing: border-box; max-width: 960px; margin: 40px auto; padding: 24px 32px;
+      background: #181818; color: #e8e8e8; border-top: 6px solid #ff5555; border-radius: 6px;
+      box-shadow: 0 12px 40px rgba(0, 0, 0, 0.5);
+    }
+    header { display: flex; align-items: center; gap: 12px; }
+    h1 { flex: 1; margin: 0; font-size: 18px; color: #ff5555; }
+    button {
+      font: inherit; font-size: 13px; padding: 6px 14px; border-radius: 4px; cursor: pointer;
+      border: 1px solid #555; background: #2a2a2a; color: #e8e8e8;
+    }
+    button.primary { background: #ff5555; border-color: #ff5555; color: #181818; }
+    .report { margin-top: 24px; }
+    .message { margin: 0; white-space: pre-wrap; word-break: break-word; font-size: 15px; color: #ffb8b8; }
+    .location { margin-top: 8px; font-size: 13px; color: #8be9fd; }
+    .stack { margin: 12px 0 0; padding: 0; list-style: none; font-size: 13px; color: #aaa; }
+    .stack li::before { content: "in "; color: #666; }
+    footer { margin-top: 24px; font-size: 12px; color: #777; }
+  `;
+
+  let host = null;
+  let reports = null;
+  let title = null;
+  let count = 0;
+
+  function mount() {
+    if (host && host.isConnected) {
+      return;
+    }
+    host = document.createElement("div");
+    host.id = "__dx-error-overlay";
+    const shadow = host.attachShadow({ mode: "open" });
+
+    const style = document.createElement("style");
+    style.textContent = STYLE;
+
+    const backdrop = document.createElement("div");
+    backdrop.className = "backdrop";
+    const panel = document.createElement("div");
+    panel.className = "panel";
+
+    const header = document.createElement("header");
+    title = document.createElement("h1");
+    const reload = document.createElement("button");
+    reload.className = "primary";
+    reload.textContent = "Reload";
+    reload.onclick = () => window.location.reload();
+    const dismiss = document.createElement("button");
+    dismiss.textContent = "Dismiss";
+    dismiss.onclick = () => window.__dxErrorOverlay.hide();
+    header.append(title, reload, dismiss);
+
+    reports = document.createElement("div");
+    const footer = document.createElement("footer");
+    footer.textContent =
+      "This overlay is only shown in debug builds. Fix the error and save to reload the app.";
+
+    panel.append(header, reports, footer);
+    backdrop.append(panel);
+    shadow.append(style, backdrop);
+    (document.body || document.documentElement).append(host);
+    count = 0;
+  }
+
+  window.__dxErrorOverlay = {
+    show(report) {
+      mount();
+      count += 1;
+      title.textContent = count === 1 ? "The app crashed" : `The app crashed (${count} errors)`;
+
+      const section = document.createElement("section");
+      section.className = "report";
+      const message = document.createElement("pre");
+      message.className = "message";
+      message.textContent = report.message;
+      section.append(message);
+
+      if (report.location) {
+        const location = document.createElement("div");
+        location.className = "location";
+        location.textContent = report.location;
+        section.append(location);
+      }
+
+      if (report.component_stack.length > 0) {
+        const stack = document.createElement("ul");
+        stack.className = "stack";
+        for (const name of report.component_stack) {
+          const item = document.createElement("li");
+          item.textContent = name;
+          stack.append(item);
+        }
+        section.append(stack);
+      }
+
+      reports.append(section);
+    },
+
+    hide() {
+      if (host) {
+        host.remove();
+      }
+      host = null;
+    },
+  };
+})();
@@ -0,0 +1,52 @@
+//! Report panics and errors that no error boundary handled to the error overlay the renderers show over the app
+//! during development.
+
+use crate::ErrorReport;
+use dioxus_core::{CapturedPanic, VirtualDom};
+
+const OVERLAY_JS: &str = include_str!("./overlay.js");
+
+/// The javascript that shows a report in the error overlay. Run it in the browser or webview the app renders into.
+/// The overlay lists every report until the page is reloaded or the overlay is dismissed.
+pub fn show_error_overlay_script(report: &ErrorReport) -> String {
+    let report = serde_json::to_string(report).unwrap_or_default();
+    format!("{OVERLAY_JS}\nwindow.__dxErrorOverlay.show({report});")
+}
+
+/// Install a panic hook that calls a function with a report of every panic, including panics in components, event
+/// handlers and futures. The panic hook that was installed before runs first, so panics are still logged.
+pub fn report_panics(on_panic: impl Fn(ErrorReport) + Send + Sync + 'static) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        previous(info);
+
+        let payload = info.payload();
+        let message = match (
+            payload.downcast_ref::<&str>(),
+            payload.downcast_ref::<String>(),
+        ) {
+            (Some(message), _) => message.to_string(),
+            (_, Some(message)) => message.clone(),
+            _ => "Box<dyn Any>".to_string(),
+        };
+        on_panic(ErrorReport {
+            message,
+            location: info.location().map(|location| location.to_string()),
+            // The panic hook runs before the stack unwinds, so the component that panicked is still running
+            component_stack: ErrorReport::component_names(dioxus_core::component_stack()),
+        });
+    }));
+}
+
+/// Call a function with a report of every error that reaches the error boundary at the root of the app. Panics in
+/// components are skipped since [`report_panics`] already reports them.
+pub fn report_unhandled_errors(
+    dom: VirtualDom,
+    on_error: impl Fn(ErrorReport) + 'static,
+) -> VirtualDom {
+    dom.with_error_listener(move |error| {
+        if error.is_unhandled() && error.downcast::<CapturedPanic>().is_none() {
+            on_error(ErrorReport::from_error(error));
+        }
+    })
+}
@@ -22,6 +22,9 @@ pub use dioxus_document as document;
 #[cfg(feature = "html")]
 pub use dioxus_history as history;
 
+#[cfg(feature = "html")]
+pub use dioxus_clipboard as clipboard;
+
 #[cfg(feature = "macro")]
 pub use dioxus_rsx as rsx;
 
@@ -36,6 +39,10 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "html")))]
     pub use dioxus_history::{history, History};
 
+    #[cfg(feature = "html")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "html")))]
+    pub use dioxus_clipboard::{clipboard, Clipboard};
+
     #[cfg(feature = "hooks")]
     pub use crate::hooks::*;
 
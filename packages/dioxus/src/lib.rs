@@ -10,6 +10,7 @@
 //! - `hooks`: (default) re-exports `dioxus-hooks`
 //! - `hot-reload`: (default) enables hot rsx reloading in all renderers that support it
 //! - `router`: exports the [router](https://dioxuslabs.com/learn/0.6/router) and enables any router features for the current platform
+//! - `inspector`: exports the [`Inspector`](inspector::Inspector) devtools panel that shows the component tree of the app
 //! - `third-party-renderer`: Just disables warnings about no active platform when no renderers are enabled
 //! - `logger`: Enable the default tracing subscriber for Dioxus apps
 //!
@@ -58,6 +59,10 @@ pub use dioxus_document as document;
 #[cfg_attr(docsrs, doc(cfg(feature = "document")))]
 pub use dioxus_history as history;
 
+#[cfg(feature = "document")]
+#[cfg_attr(docsrs, doc(cfg(feature = "document")))]
+pub use dioxus_clipboard as clipboard;
+
 #[cfg(feature = "html")]
 #[cfg_attr(docsrs, doc(cfg(feature = "html")))]
 pub use dioxus_html as html;
@@ -83,6 +88,10 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "document")))]
     pub use dioxus_history::{history, History};
 
+    #[cfg(feature = "document")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "document")))]
+    pub use dioxus_clipboard::{clipboard, Clipboard};
+
     #[cfg(feature = "launch")]
     #[cfg_attr(docsrs, doc(cfg(feature = "launch")))]
     pub use crate::launch::*;
@@ -106,6 +115,10 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "launch")))]
     pub use dioxus_config_macro::*;
 
+    #[cfg(feature = "cli-config")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cli-config")))]
+    pub use dioxus_cli_config::{self, use_config, DioxusConfig};
+
     #[cfg(feature = "html")]
     #[cfg_attr(docsrs, doc(cfg(feature = "html")))]
     pub use dioxus_html as dioxus_elements;
@@ -127,6 +140,14 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "fullstack")))]
     pub use dioxus_fullstack::prelude::*;
 
+    #[cfg(feature = "fullstack")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fullstack")))]
+    pub use dioxus_fullstack;
+
+    #[cfg(all(feature = "fullstack", feature = "macro"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "fullstack", feature = "macro"))))]
+    pub use dioxus_core_macro::island;
+
     #[cfg(feature = "router")]
     #[cfg_attr(docsrs, doc(cfg(feature = "router")))]
     pub use dioxus_router;
@@ -148,6 +169,10 @@ pub use dioxus_web as web;
 #[cfg_attr(docsrs, doc(cfg(feature = "router")))]
 pub use dioxus_router as router;
 
+#[cfg(feature = "inspector")]
+#[cfg_attr(docsrs, doc(cfg(feature = "inspector")))]
+pub use dioxus_inspector as inspector;
+
 #[cfg(feature = "fullstack")]
 #[cfg_attr(docsrs, doc(cfg(feature = "fullstack")))]
 pub use dioxus_fullstack as fullstack;
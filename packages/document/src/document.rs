@@ -48,6 +48,33 @@ pub fn create_element_in_head(
     format!(r#"{helpers};window.createElementInHead({tag}, {attributes}, {children});"#)
 }
 
+/// Insert an element into the head with javascript through the [`Document::eval`] method. The element is tagged with
+/// its id so [`remove_element_in_head`] can remove it later.
+///
+/// This can be used to implement [`Document::insert_head_element`] for most [`Document`] implementations.
+pub fn insert_element_in_head(element: &HeadElement) -> String {
+    let helpers = include_str!("./js/head.js");
+    let id = format_string_for_js(&element.id.to_string());
+    let tag = format_string_for_js(element.tag);
+    let attributes = format_attributes(&element.attributes);
+    let children = element
+        .contents
+        .as_deref()
+        .map(format_string_for_js)
+        .unwrap_or("null".to_string());
+    let priority = element.priority;
+    format!(
+        r#"{helpers};window.insertElementInHead({id}, {tag}, {attributes}, {children}, {priority});"#
+    )
+}
+
+/// Remove an element [`insert_element_in_head`] inserted with javascript through the [`Document::eval`] method
+pub fn remove_element_in_head(id: HeadElementId) -> String {
+    let helpers = include_str!("./js/head.js");
+    let id = format_string_for_js(&id.to_string());
+    format!(r#"{helpers};window.removeElementInHead({id});"#)
+}
+
 /// A provider for document-related functionality.
 ///
 /// Provides things like a history API, a title, a way to run JS, and some other basics/essentials used
@@ -106,7 +133,22 @@ pub trait Document: 'static {
         self.create_head_element("link", &attributes, None);
     }
 
-    /// Check if we should create a new head component at all. If it returns false, the head component will be skipped.
+    /// Insert an element a head component rendered into the head. Elements with a higher priority should be placed
+    /// before elements with a lower priority.
+    ///
+    /// Renderers that run javascript should insert the element inside an effect so it is not inserted while the
+    /// component is suspended.
+    fn insert_head_element(&self, element: HeadElement) {
+        self.eval(insert_element_in_head(&element));
+    }
+
+    /// Remove an element from the head after the head component that rendered it was dropped
+    fn remove_head_element(&self, id: HeadElementId) {
+        self.eval(remove_element_in_head(id));
+    }
+
+    /// Check if we should create a new head component at all. If it returns false, the element the head component
+    /// renders is already in the head and will not be inserted again.
     ///
     /// This runs once per head component and is used to hydrate head components in fullstack.
     fn create_head_component(&self) -> bool {
@@ -149,4 +191,6 @@ impl Document for NoOpDocument {
     fn create_script(&self, _: ScriptProps) {}
     fn create_style(&self, _: StyleProps) {}
     fn create_link(&self, _: LinkProps) {}
+    fn insert_head_element(&self, _: HeadElement) {}
+    fn remove_head_element(&self, _: HeadElementId) {}
 }
@@ -0,0 +1,373 @@
+//! The elements head components render into the head, and the registry that decides which of them are shown.
+//!
+//! Head components register an element when they are created and unregister it when they are dropped. Elements with
+//! the same key replace each other: the element with the highest priority is shown, and the most recently rendered
+//! element wins ties. When the shown element is dropped, the next one takes its place.
+
+use std::fmt::Display;
+
+use super::*;
+use crate::Document;
+use dioxus_core::AttributeValue;
+
+/// The id of an element a head component rendered into the head.
+///
+/// Renderers write the id into the `data-dioxus-head` attribute of the element so it can be removed later. Ids are
+/// assigned in render order, so the server and a hydrating client agree on them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HeadElementId(u64);
+
+impl Display for HeadElementId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An element rendered into the head by one of the head components
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeadElement {
+    /// The id of the element
+    pub id: HeadElementId,
+    /// The tag of the element, like `meta` or `link`
+    pub tag: &'static str,
+    /// The attributes of the element
+    pub attributes: Vec<(&'static str, String)>,
+    /// The text inside the element
+    pub contents: Option<String>,
+    /// Elements with a higher priority are placed before elements with a lower priority
+    pub priority: i32,
+}
+
+/// Boolean attributes are present when they are true and left out when they are false
+const BOOLEAN_ATTRIBUTES: &[&str] = &["async", "defer", "disabled", "nomodule"];
+
+impl HeadElement {
+    pub(crate) fn new(
+        tag: &'static str,
+        mut attributes: Vec<(&'static str, String)>,
+        additional_attributes: &[Attribute],
+        contents: Option<String>,
+        priority: Option<i32>,
+    ) -> Self {
+        for attribute in additional_attributes {
+            let value = match &attribute.value {
+                AttributeValue::Text(value) => value.clone(),
+                AttributeValue::Float(value) => value.to_string(),
+                AttributeValue::Int(value) => value.to_string(),
+                AttributeValue::Bool(value) => value.to_string(),
+                _ => continue,
+            };
+            attributes.push((attribute.name, value));
+        }
+        attributes.retain(|(name, value)| !(BOOLEAN_ATTRIBUTES.contains(name) && value == "false"));
+
+        Self {
+            id: HeadElementId(0),
+            tag,
+            attributes,
+            contents,
+            priority: priority.unwrap_or_default(),
+        }
+    }
+
+    /// Render the element to html. This is used to write the head during server side rendering.
+    pub fn to_html(&self) -> String {
+        let mut html = format!(r#"<{} data-dioxus-head="{}""#, self.tag, self.id);
+        if self.priority != 0 {
+            html.push_str(&format!(r#" data-dioxus-priority="{}""#, self.priority));
+        }
+        for (name, value) in &self.attributes {
+            let name = name.to_ascii_lowercase();
+            match BOOLEAN_ATTRIBUTES.contains(&name.as_str()) {
+                true => html.push_str(&format!(" {name}")),
+                false => html.push_str(&format!(r#" {name}="{}""#, escape(value))),
+            }
+        }
+        html.push('>');
+
+        // Meta and link tags are void elements
+        if !matches!(self.tag, "meta" | "link") {
+            match (self.tag, &self.contents) {
+                // The contents of scripts and styles are not parsed as html
+                ("script" | "style", Some(contents)) => html.push_str(contents),
+                (_, Some(contents)) => html.push_str(&escape(contents)),
+                (_, None) => {}
+            }
+            html.push_str(&format!("</{}>", self.tag));
+        }
+        html
+    }
+
+    /// Check if the element is a script the browser runs. Scripts with another type, like json-ld, only hold data.
+    fn is_executable_script(&self) -> bool {
+        if self.tag != "script" {
+            return false;
+        }
+        let r#type = self
+            .attributes
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("type"))
+            .map(|(_, value)| value.trim().to_ascii_lowercase());
+        match r#type.as_deref() {
+            None | Some("" | "module") => true,
+            Some(r#type) => r#type.contains("javascript") || r#type.contains("ecmascript"),
+        }
+    }
+
+    /// Check if two elements would look the same in the head
+    fn same_as(&self, other: &Self) -> bool {
+        self.tag == other.tag
+            && self.attributes == other.attributes
+            && self.contents == other.contents
+            && self.priority == other.priority
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+struct Entry {
+    element: HeadElement,
+    key: Option<String>,
+    /// The id of the element in the head that shows this entry, if it is shown
+    shown: Option<HeadElementId>,
+    /// The component was dropped, but the element stays in the head. A script that ran can't be unloaded, and
+    /// adding it again would run it again.
+    orphaned: bool,
+}
+
+enum Operation {
+    Insert(HeadElement),
+    Remove(HeadElementId),
+    SetTitle(String),
+}
+
+#[derive(Default)]
+struct Registry {
+    next_id: u64,
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    fn show(entry: &mut Entry, skip_insert: bool, operations: &mut Vec<Operation>) {
+        entry.shown = Some(entry.element.id);
+        if entry.element.tag == "title" {
+            operations.push(Operation::SetTitle(
+                entry.element.contents.clone().unwrap_or_default(),
+            ));
+        } else if !skip_insert {
+            operations.push(Operation::Insert(entry.element.clone()));
+        }
+    }
+
+    fn hide(entry: &mut Entry, operations: &mut Vec<Operation>) {
+        if let Some(id) = entry.shown.take() {
+            if entry.element.tag != "title" {
+                operations.push(Operation::Remove(id));
+            }
+        }
+    }
+
+    /// Show the next entry instead of the previous one. Identical elements are left in the head.
+    fn replace(
+        previous: &mut Entry,
+        next: &mut Entry,
+        skip_insert: bool,
+        operations: &mut Vec<Operation>,
+    ) {
+        if previous.element.same_as(&next.element) {
+            next.shown = previous.shown.take();
+        } else {
+            Self::hide(previous, operations);
+            Self::show(next, skip_insert, operations);
+        }
+    }
+
+    fn register(
+        &mut self,
+        key: Option<String>,
+        mut element: HeadElement,
+        hydrated: bool,
+        operations: &mut Vec<Operation>,
+    ) -> HeadElementId {
+        let id = HeadElementId(self.next_id);
+        self.next_id += 1;
+        element.id = id;
+
+        let mut entry = Entry {
+            element,
+            key,
+            shown: None,
+            orphaned: false,
+        };
+
+        let shown = entry.key.as_ref().and_then(|key| {
+            self.entries
+                .iter()
+                .position(|other| other.key.as_ref() == Some(key) && other.shown.is_some())
+        });
+        match shown {
+            // An element with a higher priority is already shown
+            Some(index) if self.entries[index].element.priority > entry.element.priority => {}
+            Some(index) => {
+                Self::replace(&mut self.entries[index], &mut entry, hydrated, operations);
+                if self.entries[index].orphaned {
+                    self.entries.remove(index);
+                }
+            }
+            // If the server rendered the element, it is already in the head
+            None => Self::show(&mut entry, hydrated, operations),
+        }
+
+        self.entries.push(entry);
+        id
+    }
+
+    fn unregister(&mut self, id: HeadElementId, operations: &mut Vec<Operation>) {
+        let Some(index) = self.entries.iter().position(|entry| entry.element.id == id) else {
+            return;
+        };
+
+        let mut entry = self.entries.remove(index);
+        if entry.shown.is_none() {
+            return;
+        }
+
+        // Keyed scripts stay around so a script with the same key can take their place without running again
+        if entry.element.is_executable_script() {
+            if entry.key.is_some() {
+                entry.orphaned = true;
+                self.entries.push(entry);
+            }
+            return;
+        }
+
+        // Show the element with the highest priority that was rendered last instead
+        let next = entry.key.as_ref().and_then(|key| {
+            self.entries
+                .iter_mut()
+                .filter(|other| other.key.as_ref() == Some(key))
+                .max_by_key(|other| other.element.priority)
+        });
+        match next {
+            Some(next) => Self::replace(&mut entry, next, false, operations),
+            None => Self::hide(&mut entry, operations),
+        }
+    }
+
+    fn update_contents(
+        &mut self,
+        id: HeadElementId,
+        contents: String,
+        operations: &mut Vec<Operation>,
+    ) {
+        let Some(entry) = self.entries.iter_mut().find(|entry| entry.element.id == id) else {
+            return;
+        };
+        entry.element.contents = Some(contents);
+        if entry.shown.is_some() {
+            Self::hide(entry, operations);
+            Self::show(entry, false, operations);
+        }
+    }
+}
+
+/// The elements every head component in the app rendered
+#[derive(Default, Clone)]
+struct HeadRegistry(Rc<RefCell<Registry>>);
+
+impl HeadRegistry {
+    fn apply(document: &dyn Document, operations: Vec<Operation>) {
+        for operation in operations {
+            match operation {
+                Operation::Insert(element) => document.insert_head_element(element),
+                Operation::Remove(id) => document.remove_head_element(id),
+                Operation::SetTitle(title) => document.set_title(title),
+            }
+        }
+    }
+}
+
+/// A handle to an element a component rendered into the head
+#[derive(Clone)]
+pub(crate) struct HeadElementHandle {
+    registry: HeadRegistry,
+    document: Rc<dyn Document>,
+    id: HeadElementId,
+}
+
+impl HeadElementHandle {
+    /// Change the text inside the element
+    pub(crate) fn update_contents(&self, contents: String) {
+        let mut operations = Vec::new();
+        self.registry
+            .0
+            .borrow_mut()
+            .update_contents(self.id, contents, &mut operations);
+        HeadRegistry::apply(&*self.document, operations);
+    }
+}
+
+/// Render an element into the head while the component is mounted. Elements with the same key replace each other.
+pub(crate) fn use_head_element(
+    key: Option<String>,
+    element: impl FnOnce() -> HeadElement,
+) -> HeadElementHandle {
+    let mut handles = use_head_elements(|| vec![(key, element())]);
+    handles.remove(0)
+}
+
+/// Render several elements into the head while the component is mounted. The elements are created on the first
+/// render, so a component can render a different number of elements depending on its props.
+pub(crate) fn use_head_elements(
+    elements: impl FnOnce() -> Vec<(Option<String>, HeadElement)>,
+) -> Vec<HeadElementHandle> {
+    let handles = use_hook(|| {
+        let document = crate::document();
+        let registry = get_or_insert_root_context::<HeadRegistry>();
+        // On a hydrating client, the server already wrote the elements into the head
+        let hydrated = !document.create_head_component();
+
+        let mut operations = Vec::new();
+        let ids: Vec<_> = elements()
+            .into_iter()
+            .map(|(key, element)| {
+                registry
+                    .0
+                    .borrow_mut()
+                    .register(key, element, hydrated, &mut operations)
+            })
+            .collect();
+        HeadRegistry::apply(&*document, operations);
+
+        ids.into_iter()
+            .map(|id| HeadElementHandle {
+                registry: registry.clone(),
+                document: document.clone(),
+                id,
+            })
+            .collect::<Vec<_>>()
+    });
+
+    use_drop({
+        let handles = handles.clone();
+        move || {
+            for handle in handles {
+                let mut operations = Vec::new();
+                handle
+                    .registry
+                    .0
+                    .borrow_mut()
+                    .unregister(handle.id, &mut operations);
+                HeadRegistry::apply(&*handle.document, operations);
+            }
+        }
+    });
+
+    handles
+}
@@ -0,0 +1,83 @@
+use super::*;
+use serde::Serialize;
+
+/// Props for the [`JsonLd`] component
+#[derive(Clone, Props, PartialEq)]
+pub struct JsonLdProps<T: Serialize + Clone + PartialEq + 'static> {
+    /// The structured data. It is serialized with serde, so it can be your own type or a [`serde_json::Value`]
+    pub data: T,
+    /// Structured data with the same key replaces each other. By default, every component renders its own script
+    pub head_key: Option<String>,
+    /// If several components share a key, the one with the highest priority is used. Defaults to 0
+    pub priority: Option<i32>,
+}
+
+/// Render [structured data](https://json-ld.org/) into a `<script type="application/ld+json">` tag in the head of the
+/// page. Search engines read it to show rich results for the page.
+///
+/// Unlike the [`Script`] component, the structured data is updated when `data` changes and is removed from the head
+/// when the component is dropped.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[derive(serde::Serialize, Clone, PartialEq)]
+/// struct Article {
+///     #[serde(rename = "@context")]
+///     context: &'static str,
+///     #[serde(rename = "@type")]
+///     kind: &'static str,
+///     headline: String,
+/// }
+///
+/// fn Post() -> Element {
+///     rsx! {
+///         document::JsonLd {
+///             data: Article {
+///                 context: "https://schema.org",
+///                 kind: "Article",
+///                 headline: "Why cats sleep so much".to_string(),
+///             },
+///         }
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[doc(alias = "structured data")]
+pub fn JsonLd<T: Serialize + Clone + PartialEq + 'static>(props: JsonLdProps<T>) -> Element {
+    let json = match serde_json::to_string(&props.data) {
+        Ok(json) => escape_json(&json),
+        Err(err) => {
+            tracing::error!("Error while rendering JsonLd: failed to serialize the data: {err}");
+            return VNode::empty();
+        }
+    };
+
+    let key = props.head_key.map(|key| format!("script#{key}"));
+    let script = use_head_element(key, || {
+        HeadElement::new(
+            "script",
+            vec![("type", "application/ld+json".to_string())],
+            &[],
+            Some(json.clone()),
+            props.priority,
+        )
+    });
+    let last_json = use_hook(|| Rc::new(RefCell::new(json.clone())));
+
+    // If the data changes, update the script
+    let mut last_json = last_json.borrow_mut();
+    if json != *last_json {
+        script.update_contents(json.clone());
+        *last_json = json;
+    }
+
+    VNode::empty()
+}
+
+/// Escape the characters that could end the script tag early or start a comment. `<` can only appear inside strings
+/// in json, where the unicode escape means the same thing.
+fn escape_json(json: &str) -> String {
+    json.replace('<', "\\u003c")
+}
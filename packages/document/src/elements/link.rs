@@ -1,5 +1,4 @@
 use super::*;
-use crate::document;
 use dioxus_html as dioxus_elements;
 
 #[non_exhaustive]
@@ -20,6 +19,11 @@ pub struct LinkProps {
     pub integrity: Option<String>,
     pub r#type: Option<String>,
     pub blocking: Option<String>,
+    /// Links with the same key replace each other. Defaults to the href of the link
+    pub head_key: Option<String>,
+    /// If several links share a key, the one with the highest priority is used. Links with a higher priority are also
+    /// placed earlier in the head. Defaults to 0
+    pub priority: Option<i32>,
     #[props(extends = link, extends = GlobalAttributes)]
     pub additional_attributes: Vec<Attribute>,
 }
@@ -72,6 +76,15 @@ impl LinkProps {
         }
         attributes
     }
+
+    /// The key links are deduplicated by
+    fn dedupe_key(&self) -> Option<String> {
+        match (&self.head_key, &self.href) {
+            (Some(key), _) => Some(format!("link#{key}")),
+            (None, Some(href)) => Some(format!("link:{href}")),
+            (None, None) => None,
+        }
+    }
 }
 
 /// Render a [`link`](crate::elements::link) tag into the head of the page.
@@ -94,6 +107,9 @@ impl LinkProps {
 /// }
 /// ```
 ///
+/// Links with the same href are only added once. The link is removed from the head when every component that rendered
+/// it is dropped.
+///
 /// <div class="warning">
 ///
 /// Any updates to the props after the first render will not be reflected in the head.
@@ -104,30 +120,15 @@ impl LinkProps {
 pub fn Link(props: LinkProps) -> Element {
     use_update_warning(&props, "Link {}");
 
-    use_hook(|| {
-        let document = document();
-        let mut insert_link = document.create_head_component();
-        if let Some(href) = &props.href {
-            if !should_insert_link(href) {
-                insert_link = false;
-            }
-        }
-
-        if !insert_link {
-            return;
-        }
-
-        document.create_link(props);
+    use_head_element(props.dedupe_key(), || {
+        HeadElement::new(
+            "link",
+            props.attributes(),
+            &props.additional_attributes,
+            None,
+            props.priority,
+        )
     });
 
     VNode::empty()
 }
-
-#[derive(Default, Clone)]
-struct LinkContext(DeduplicationContext);
-
-fn should_insert_link(href: &str) -> bool {
-    get_or_insert_root_context::<LinkContext>()
-        .0
-        .should_insert(href)
-}
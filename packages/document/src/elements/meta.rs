@@ -1,5 +1,4 @@
 use super::*;
-use crate::document;
 use dioxus_html as dioxus_elements;
 
 #[non_exhaustive]
@@ -11,6 +10,11 @@ pub struct MetaProps {
     pub charset: Option<String>,
     pub http_equiv: Option<String>,
     pub content: Option<String>,
+    /// Meta tags with the same key replace each other. Defaults to the name, property, http-equiv or charset of the tag
+    pub head_key: Option<String>,
+    /// If several meta tags share a key, the one with the highest priority is used. Tags with a higher priority are
+    /// also placed earlier in the head. Defaults to 0
+    pub priority: Option<i32>,
     #[props(extends = meta, extends = GlobalAttributes)]
     pub additional_attributes: Vec<Attribute>,
 }
@@ -36,6 +40,26 @@ impl MetaProps {
         }
         attributes
     }
+
+    /// The key meta tags are deduplicated by
+    fn dedupe_key(&self) -> Option<String> {
+        if let Some(key) = &self.head_key {
+            return Some(format!("meta#{key}"));
+        }
+        if let Some(name) = &self.name {
+            return Some(format!("meta:name:{name}"));
+        }
+        if let Some(property) = &self.property {
+            return Some(format!("meta:property:{property}"));
+        }
+        if let Some(http_equiv) = &self.http_equiv {
+            return Some(format!(
+                "meta:http-equiv:{}",
+                http_equiv.to_ascii_lowercase()
+            ));
+        }
+        self.charset.as_ref().map(|_| "meta:charset".to_string())
+    }
 }
 
 /// Render a [`meta`](crate::elements::meta) tag into the head of the page.
@@ -56,6 +80,9 @@ impl MetaProps {
 /// }
 /// ```
 ///
+/// Meta tags with the same name, property, http-equiv or charset replace each other, so a page can override the
+/// description of the layout around it. The tag is removed from the head when the component is dropped.
+///
 /// <div class="warning">
 ///
 /// Any updates to the props after the first render will not be reflected in the head.
@@ -66,9 +93,14 @@ impl MetaProps {
 pub fn Meta(props: MetaProps) -> Element {
     use_update_warning(&props, "Meta {}");
 
-    use_hook(|| {
-        let document = document();
-        document.create_meta(props);
+    use_head_element(props.dedupe_key(), || {
+        HeadElement::new(
+            "meta",
+            props.attributes(),
+            &props.additional_attributes,
+            None,
+            props.priority,
+        )
     });
 
     VNode::empty()
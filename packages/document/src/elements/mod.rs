@@ -5,6 +5,10 @@ use std::{cell::RefCell, collections::HashSet, rc::Rc};
 use dioxus_core::{prelude::*, DynamicNode};
 use dioxus_core_macro::*;
 
+mod head;
+pub use head::*;
+mod json_ld;
+pub use json_ld::*;
 mod link;
 pub use link::*;
 mod stylesheet;
@@ -13,6 +17,8 @@ mod meta;
 pub use meta::*;
 mod script;
 pub use script::*;
+mod social;
+pub use social::*;
 mod style;
 pub use style::*;
 mod title;
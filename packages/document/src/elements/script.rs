@@ -1,5 +1,4 @@
 use super::*;
-use crate::document;
 use dioxus_html as dioxus_elements;
 
 #[non_exhaustive]
@@ -17,6 +16,11 @@ pub struct ScriptProps {
     pub nonce: Option<String>,
     pub referrerpolicy: Option<String>,
     pub r#type: Option<String>,
+    /// Scripts with the same key replace each other. Defaults to the src of the script
+    pub head_key: Option<String>,
+    /// If several scripts share a key, the one with the highest priority is used. Scripts with a higher priority are
+    /// also placed earlier in the head. Defaults to 0
+    pub priority: Option<i32>,
     #[props(extends = script, extends = GlobalAttributes)]
     pub additional_attributes: Vec<Attribute>,
 }
@@ -58,6 +62,15 @@ impl ScriptProps {
     pub fn script_contents(&self) -> Result<String, ExtractSingleTextNodeError<'_>> {
         extract_single_text_node(&self.children)
     }
+
+    /// The key scripts are deduplicated by
+    fn dedupe_key(&self) -> Option<String> {
+        match (&self.head_key, &self.src) {
+            (Some(key), _) => Some(format!("script#{key}")),
+            (None, Some(src)) => Some(format!("script:{src}")),
+            (None, None) => None,
+        }
+    }
 }
 
 /// Render a [`script`](crate::elements::script) tag into the head of the page.
@@ -66,7 +79,8 @@ impl ScriptProps {
 /// If present, the children of the script component must be a single static or formatted string. If there are more children or the children contain components, conditionals, loops, or fragments, the script will not be added.
 ///
 ///
-/// Any scripts you add will be deduplicated by their `src` attribute (if present).
+/// Any scripts you add will be deduplicated by their `src` attribute (if present). Scripts stay in the head after the
+/// component is dropped since a script that ran can't be unloaded.
 ///
 /// # Example
 /// ```rust, no_run
@@ -90,36 +104,21 @@ impl ScriptProps {
 pub fn Script(props: ScriptProps) -> Element {
     use_update_warning(&props, "Script {}");
 
-    use_hook(|| {
-        let document = document();
-        let mut insert_script = document.create_head_component();
-        if let Some(src) = &props.src {
-            if !should_insert_script(src) {
-                insert_script = false;
-            }
-        }
-
-        if !insert_script {
-            return;
-        }
-
+    use_head_element(props.dedupe_key(), || {
         // Make sure the props are in a valid form - they must either have a source or children
-        if let (None, Err(err)) = (&props.src, props.script_contents()) {
+        let contents = props.script_contents();
+        if let (None, Err(err)) = (&props.src, &contents) {
             // If the script has neither contents nor src, log an error
             err.log("Script")
         }
-
-        document.create_script(props);
+        HeadElement::new(
+            "script",
+            props.attributes(),
+            &props.additional_attributes,
+            contents.ok(),
+            props.priority,
+        )
     });
 
     VNode::empty()
 }
-
-#[derive(Default, Clone)]
-struct ScriptContext(DeduplicationContext);
-
-fn should_insert_script(src: &str) -> bool {
-    get_or_insert_root_context::<ScriptContext>()
-        .0
-        .should_insert(src)
-}
@@ -0,0 +1,246 @@
+//! Components for the metadata social media sites and chat apps read to show a preview of a link to the page.
+//!
+//! Each property renders its own meta tag, so a page can override single properties of the layout around it.
+
+use super::*;
+
+/// Render a meta tag for each property that is set. Tags are deduplicated like the [`Meta`] component would.
+fn use_meta_tags(
+    attribute: &'static str,
+    properties: impl IntoIterator<Item = (&'static str, Option<String>)>,
+    priority: Option<i32>,
+) {
+    use_head_elements(|| {
+        properties
+            .into_iter()
+            .filter_map(|(property, content)| {
+                let content = content?;
+                let key = format!("meta:{attribute}:{property}");
+                let attributes = vec![(attribute, property.to_string()), ("content", content)];
+                let element = HeadElement::new("meta", attributes, &[], None, priority);
+                Some((Some(key), element))
+            })
+            .collect()
+    });
+}
+
+/// Props for the [`OpenGraph`] component
+#[non_exhaustive]
+#[derive(Clone, Props, PartialEq)]
+pub struct OpenGraphProps {
+    /// The title of the page without the name of the site. Renders `og:title`
+    pub title: Option<String>,
+    /// A one or two sentence description of the page. Renders `og:description`
+    pub description: Option<String>,
+    /// The canonical url of the page. Renders `og:url`
+    pub url: Option<String>,
+    /// The name of the site the page is part of. Renders `og:site_name`
+    pub site_name: Option<String>,
+    /// The type of the page, like `website` or `article`. Renders `og:type`
+    pub r#type: Option<String>,
+    /// The locale of the page, like `en_US`. Renders `og:locale`
+    pub locale: Option<String>,
+    /// If another component renders the same property, the one with the highest priority is used. Defaults to 0
+    pub priority: Option<i32>,
+}
+
+/// Render the [Open Graph](https://ogp.me/) metadata of the page into the head. Use [`OgImage`] to add the image
+/// of the preview.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn Post() -> Element {
+///     rsx! {
+///         document::OpenGraph {
+///             title: "Why cats sleep so much",
+///             description: "A deep dive into the sleeping habits of cats",
+///             url: "https://example.com/posts/cats",
+///             r#type: "article",
+///         }
+///     }
+/// }
+/// ```
+///
+/// <div class="warning">
+///
+/// Any updates to the props after the first render will not be reflected in the head.
+///
+/// </div>
+#[component]
+pub fn OpenGraph(props: OpenGraphProps) -> Element {
+    use_update_warning(&props, "OpenGraph {}");
+
+    let priority = props.priority;
+    let properties = [
+        ("og:title", props.title),
+        ("og:description", props.description),
+        ("og:url", props.url),
+        ("og:site_name", props.site_name),
+        ("og:type", props.r#type),
+        ("og:locale", props.locale),
+    ];
+
+    use_meta_tags("property", properties, priority);
+
+    VNode::empty()
+}
+
+/// Props for the [`OgImage`] component
+#[non_exhaustive]
+#[derive(Clone, Props, PartialEq)]
+pub struct OgImageProps {
+    /// The absolute url of the image. Renders `og:image`
+    #[props(into)]
+    pub url: String,
+    /// The https url of the image if `url` uses http. Renders `og:image:secure_url`
+    pub secure_url: Option<String>,
+    /// The MIME type of the image, like `image/png`. Renders `og:image:type`
+    pub r#type: Option<String>,
+    /// The width of the image in pixels. Renders `og:image:width`
+    pub width: Option<u32>,
+    /// The height of the image in pixels. Renders `og:image:height`
+    pub height: Option<u32>,
+    /// A description of the image for people who can't see it. Renders `og:image:alt`
+    pub alt: Option<String>,
+    /// If another component renders the same property, the one with the highest priority is used. Defaults to 0
+    pub priority: Option<i32>,
+}
+
+/// Render the image of the [Open Graph](https://ogp.me/) preview of the page into the head.
+///
+/// Only one image is rendered. An image rendered by a page replaces the image of the layout around it.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn Post() -> Element {
+///     rsx! {
+///         document::OgImage {
+///             url: "https://example.com/cats.png",
+///             width: 1200,
+///             height: 630,
+///             alt: "A cat sleeping in the sun",
+///         }
+///     }
+/// }
+/// ```
+///
+/// <div class="warning">
+///
+/// Any updates to the props after the first render will not be reflected in the head.
+///
+/// </div>
+#[component]
+pub fn OgImage(props: OgImageProps) -> Element {
+    use_update_warning(&props, "OgImage {}");
+
+    let priority = props.priority;
+    let properties = [
+        ("og:image", Some(props.url)),
+        ("og:image:secure_url", props.secure_url),
+        ("og:image:type", props.r#type),
+        ("og:image:width", props.width.map(|width| width.to_string())),
+        (
+            "og:image:height",
+            props.height.map(|height| height.to_string()),
+        ),
+        ("og:image:alt", props.alt),
+    ];
+
+    use_meta_tags("property", properties, priority);
+
+    VNode::empty()
+}
+
+/// The layout of the preview of the page on X (formerly Twitter)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TwitterCardType {
+    /// A small square image next to the title and description
+    #[default]
+    Summary,
+    /// A large image above the title and description
+    SummaryLargeImage,
+    /// A link to download a mobile app
+    App,
+    /// A video or audio player
+    Player,
+}
+
+impl TwitterCardType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TwitterCardType::Summary => "summary",
+            TwitterCardType::SummaryLargeImage => "summary_large_image",
+            TwitterCardType::App => "app",
+            TwitterCardType::Player => "player",
+        }
+    }
+}
+
+/// Props for the [`TwitterCard`] component
+#[non_exhaustive]
+#[derive(Clone, Props, PartialEq)]
+pub struct TwitterCardProps {
+    /// The layout of the card. Renders `twitter:card`
+    #[props(default)]
+    pub card: TwitterCardType,
+    /// The @username of the site. Renders `twitter:site`
+    pub site: Option<String>,
+    /// The @username of the author of the page. Renders `twitter:creator`
+    pub creator: Option<String>,
+    /// The title of the card. Falls back to `og:title` if it is not set. Renders `twitter:title`
+    pub title: Option<String>,
+    /// The description of the card. Falls back to `og:description` if it is not set. Renders `twitter:description`
+    pub description: Option<String>,
+    /// The absolute url of the image of the card. Falls back to `og:image` if it is not set. Renders `twitter:image`
+    pub image: Option<String>,
+    /// A description of the image for people who can't see it. Renders `twitter:image:alt`
+    pub image_alt: Option<String>,
+    /// If another component renders the same property, the one with the highest priority is used. Defaults to 0
+    pub priority: Option<i32>,
+}
+
+/// Render the metadata of the [card](https://developer.x.com/en/docs/x-for-websites/cards/overview/markup) X
+/// (formerly Twitter) shows for links to the page into the head.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn Post() -> Element {
+///     rsx! {
+///         document::TwitterCard {
+///             card: document::TwitterCardType::SummaryLargeImage,
+///             site: "@dioxuslabs",
+///         }
+///     }
+/// }
+/// ```
+///
+/// <div class="warning">
+///
+/// Any updates to the props after the first render will not be reflected in the head.
+///
+/// </div>
+#[component]
+pub fn TwitterCard(props: TwitterCardProps) -> Element {
+    use_update_warning(&props, "TwitterCard {}");
+
+    let priority = props.priority;
+    let names = [
+        ("twitter:card", Some(props.card.as_str().to_string())),
+        ("twitter:site", props.site),
+        ("twitter:creator", props.creator),
+        ("twitter:title", props.title),
+        ("twitter:description", props.description),
+        ("twitter:image", props.image),
+        ("twitter:image:alt", props.image_alt),
+    ];
+
+    use_meta_tags("name", names, priority);
+
+    VNode::empty()
+}
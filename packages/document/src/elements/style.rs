@@ -105,6 +105,8 @@ pub fn Style(props: StyleProps) -> Element {
                     hreflang: None,
                     integrity: None,
                     blocking: None,
+                    head_key: None,
+                    priority: None,
                 });
             }
             // The style has neither contents nor src, log an error
@@ -1,16 +1,16 @@
-use crate::document;
-
 use super::*;
 
 #[derive(Clone, Props, PartialEq)]
 pub struct TitleProps {
     /// The contents of the title tag. The children must be a single text node.
     children: Element,
+    /// If several titles are rendered, the one with the highest priority is used. Defaults to 0
+    priority: Option<i32>,
 }
 
 /// Render the title of the page. On web renderers, this will set the [title](crate::elements::title) in the head. On desktop, it will set the window title.
 ///
-/// Unlike most head components, the Title can be modified after the first render. Only the latest update to the title will be reflected if multiple title components are rendered.
+/// Unlike most head components, the Title can be modified after the first render. If multiple title components are rendered, the one with the highest priority that was rendered last is used. When it is dropped, the title goes back to the previous one.
 ///
 ///
 /// The children of the title component must be a single static or formatted string. If there are more children or the children contain components, conditionals, loops, or fragments, the title will not be updated.
@@ -39,17 +39,15 @@ pub fn Title(props: TitleProps) -> Element {
     };
 
     // Update the title as it changes. NOTE: We don't use use_effect here because we need this to run on the server
-    let document = use_hook(document);
-    let last_text = use_hook(|| {
-        // Set the title initially
-        document.set_title(text.clone());
-        Rc::new(RefCell::new(text.clone()))
+    let title = use_head_element(Some("title".to_string()), || {
+        HeadElement::new("title", Vec::new(), &[], Some(text.clone()), props.priority)
     });
+    let last_text = use_hook(|| Rc::new(RefCell::new(text.clone())));
 
     // If the text changes, update the title
     let mut last_text = last_text.borrow_mut();
     if text != *last_text {
-        document.set_title(text.clone());
+        title.update_contents(text.clone());
         *last_text = text;
     }
 
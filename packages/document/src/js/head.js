@@ -1 +1 @@
-var createElementInHead=function(tag,attributes,children){const element=document.createElement(tag);for(let[key,value]of attributes)element.setAttribute(key,value);if(children)element.appendChild(document.createTextNode(children));document.head.appendChild(element)};window.createElementInHead=createElementInHead;
+var createElementInHead=function(tag,attributes,children){const element=document.createElement(tag);for(let[key,value]of attributes)element.setAttribute(key,value);if(children)element.appendChild(document.createTextNode(children));document.head.appendChild(element)},headPriority=function(element){return Number(element.getAttribute("data-dioxus-priority")??0)},insertElementInHead=function(id,tag,attributes,children,priority){const element=document.createElement(tag);for(let[key,value]of attributes)element.setAttribute(key,value);if(element.setAttribute("data-dioxus-head",id),priority!==0)element.setAttribute("data-dioxus-priority",String(priority));if(children)element.appendChild(document.createTextNode(children));const before=Array.from(document.head.querySelectorAll("[data-dioxus-head]")).find((other)=>headPriority(other)<priority);document.head.insertBefore(element,before??null)},removeElementInHead=function(id){document.head.querySelector(`[data-dioxus-head="${id}"]`)?.remove()};window.createElementInHead=createElementInHead;window.insertElementInHead=insertElementInHead;window.removeElementInHead=removeElementInHead;
@@ -15,5 +15,43 @@ function createElementInHead(
   document.head.appendChild(element);
 }
 
+function headPriority(element: Element): number {
+  return Number(element.getAttribute("data-dioxus-priority") ?? 0);
+}
+
+// Insert an element that can be removed later. Elements are kept after anything that was already in the head, and
+// elements with a higher priority come first.
+function insertElementInHead(
+  id: string,
+  tag: string,
+  attributes: [string, string][],
+  children: string | null,
+  priority: number
+): void {
+  const element = document.createElement(tag);
+  for (const [key, value] of attributes) {
+    element.setAttribute(key, value);
+  }
+  element.setAttribute("data-dioxus-head", id);
+  if (priority !== 0) {
+    element.setAttribute("data-dioxus-priority", String(priority));
+  }
+  if (children) {
+    element.appendChild(document.createTextNode(children));
+  }
+  const before = Array.from(
+    document.head.querySelectorAll("[data-dioxus-head]")
+  ).find((other) => headPriority(other) < priority);
+  document.head.insertBefore(element, before ?? null);
+}
+
+function removeElementInHead(id: string): void {
+  document.head.querySelector(`[data-dioxus-head="${id}"]`)?.remove();
+}
+
 // @ts-ignore
 window.createElementInHead = createElementInHead;
+// @ts-ignore
+window.insertElementInHead = insertElementInHead;
+// @ts-ignore
+window.removeElementInHead = removeElementInHead;
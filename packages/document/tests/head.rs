@@ -0,0 +1,233 @@
+use dioxus::prelude::*;
+use dioxus_document::{Document, Eval, HeadElement, HeadElementId, NoOpDocument};
+use std::{cell::RefCell, rc::Rc};
+
+/// A document that records what the head components did
+#[derive(Default)]
+struct RecordingDocument {
+    head: RefCell<Vec<HeadElement>>,
+    title: RefCell<Option<String>>,
+}
+
+impl RecordingDocument {
+    fn head(&self) -> Vec<String> {
+        self.head
+            .borrow()
+            .iter()
+            .map(|element| element.to_html())
+            .collect()
+    }
+}
+
+impl Document for RecordingDocument {
+    fn eval(&self, js: String) -> Eval {
+        NoOpDocument.eval(js)
+    }
+
+    fn set_title(&self, title: String) {
+        *self.title.borrow_mut() = Some(title);
+    }
+
+    fn insert_head_element(&self, element: HeadElement) {
+        self.head.borrow_mut().push(element);
+    }
+
+    fn remove_head_element(&self, id: HeadElementId) {
+        self.head.borrow_mut().retain(|element| element.id != id);
+    }
+}
+
+/// Render the app with a document that records the head
+fn render(app: fn() -> Element) -> (VirtualDom, Rc<RecordingDocument>) {
+    let document = Rc::new(RecordingDocument::default());
+    let mut dom = VirtualDom::new(app);
+    dom.provide_root_context(document.clone() as Rc<dyn Document>);
+    dom.rebuild_in_place();
+    (dom, document)
+}
+
+/// Show or hide the part of the app behind the `Signal<bool>` context
+fn toggle(dom: &mut VirtualDom) {
+    dom.in_runtime(|| {
+        let mut shown = ScopeId::APP.consume_context::<Signal<bool>>().unwrap();
+        shown.toggle();
+    });
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+}
+
+#[test]
+fn keyed_meta_is_replaced_and_restored() {
+    fn app() -> Element {
+        let shown = use_context_provider(|| Signal::new(true));
+        rsx! {
+            document::Meta { name: "description", content: "layout" }
+            if shown() {
+                document::Meta { name: "description", content: "page" }
+            }
+        }
+    }
+
+    let (mut dom, document) = render(app);
+    assert_eq!(
+        document.head(),
+        [r#"<meta data-dioxus-head="1" name="description" content="page">"#]
+    );
+
+    // Dropping the page brings back the description of the layout
+    toggle(&mut dom);
+    assert_eq!(
+        document.head(),
+        [r#"<meta data-dioxus-head="0" name="description" content="layout">"#]
+    );
+}
+
+#[test]
+fn higher_priority_wins() {
+    fn app() -> Element {
+        rsx! {
+            document::Meta { name: "robots", content: "noindex", priority: 1 }
+            document::Meta { name: "robots", content: "index" }
+            document::Meta { head_key: "canonical", property: "og:url", content: "/a" }
+            document::Meta { head_key: "canonical", property: "og:url", content: "/b" }
+        }
+    }
+
+    let (_dom, document) = render(app);
+    assert_eq!(
+        document.head(),
+        [
+            r#"<meta data-dioxus-head="0" data-dioxus-priority="1" name="robots" content="noindex">"#,
+            r#"<meta data-dioxus-head="3" property="og:url" content="/b">"#,
+        ]
+    );
+}
+
+#[test]
+fn links_are_removed_with_the_last_component() {
+    fn app() -> Element {
+        let shown = use_context_provider(|| Signal::new(true));
+        rsx! {
+            document::Stylesheet { href: "/style.css" }
+            if shown() {
+                document::Stylesheet { href: "/style.css" }
+                document::Link { rel: "preconnect", href: "https://example.com" }
+            }
+        }
+    }
+
+    let (mut dom, document) = render(app);
+    assert_eq!(document.head().len(), 2);
+
+    toggle(&mut dom);
+    assert_eq!(
+        document.head(),
+        [r#"<link data-dioxus-head="0" rel="stylesheet" href="/style.css" type="text/css">"#]
+    );
+}
+
+#[test]
+fn scripts_stay_after_unmount() {
+    fn app() -> Element {
+        let shown = use_context_provider(|| Signal::new(true));
+        rsx! {
+            if shown() {
+                document::Script { src: "/analytics.js", defer: true }
+            }
+        }
+    }
+
+    let (mut dom, document) = render(app);
+    let script = [r#"<script data-dioxus-head="0" defer src="/analytics.js"></script>"#];
+    assert_eq!(document.head(), script);
+
+    // The script already ran, so it stays and isn't inserted again when the component comes back
+    toggle(&mut dom);
+    toggle(&mut dom);
+    assert_eq!(document.head(), script);
+}
+
+#[test]
+fn title_goes_back_to_the_previous_one() {
+    fn app() -> Element {
+        let shown = use_context_provider(|| Signal::new(true));
+        rsx! {
+            document::Title { "My App" }
+            if shown() {
+                document::Title { "Settings | My App" }
+            }
+        }
+    }
+
+    let (mut dom, document) = render(app);
+    assert_eq!(
+        document.title.borrow().as_deref(),
+        Some("Settings | My App")
+    );
+
+    toggle(&mut dom);
+    assert_eq!(document.title.borrow().as_deref(), Some("My App"));
+}
+
+#[test]
+fn social_tags_override_meta_tags() {
+    fn app() -> Element {
+        rsx! {
+            document::Meta { property: "og:title", content: "My App" }
+            document::OpenGraph { title: "Settings", r#type: "website" }
+            document::OgImage { url: "https://example.com/cat.png", width: 1200 }
+            document::TwitterCard { card: document::TwitterCardType::SummaryLargeImage }
+        }
+    }
+
+    let (_dom, document) = render(app);
+    assert_eq!(
+        document.head(),
+        [
+            r#"<meta data-dioxus-head="1" property="og:title" content="Settings">"#,
+            r#"<meta data-dioxus-head="2" property="og:type" content="website">"#,
+            r#"<meta data-dioxus-head="3" property="og:image" content="https://example.com/cat.png">"#,
+            r#"<meta data-dioxus-head="4" property="og:image:width" content="1200">"#,
+            r#"<meta data-dioxus-head="5" name="twitter:card" content="summary_large_image">"#,
+        ]
+    );
+}
+
+#[test]
+fn json_ld_is_escaped_updated_and_removed() {
+    #[derive(serde::Serialize, Clone, PartialEq)]
+    struct Article {
+        headline: String,
+    }
+
+    fn app() -> Element {
+        let shown = use_context_provider(|| Signal::new(true));
+        let headline = use_context_provider(|| Signal::new("</script>".to_string()));
+        rsx! {
+            if shown() {
+                document::JsonLd { data: Article { headline: headline() } }
+            }
+        }
+    }
+
+    let (mut dom, document) = render(app);
+    assert_eq!(
+        document.head(),
+        [
+            r#"<script data-dioxus-head="0" type="application/ld+json">{"headline":"\u003c/script>"}</script>"#
+        ]
+    );
+
+    dom.in_runtime(|| {
+        let mut headline = ScopeId::APP.consume_context::<Signal<String>>().unwrap();
+        headline.set("Cats".to_string());
+    });
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(
+        document.head(),
+        [r#"<script data-dioxus-head="0" type="application/ld+json">{"headline":"Cats"}</script>"#]
+    );
+
+    // Unlike scripts that ran, structured data is removed with the component
+    toggle(&mut dom);
+    assert!(document.head().is_empty());
+}
@@ -0,0 +1,167 @@
+//! Authentication: sessions stored on the server, a [`User`] extractor for server functions and the state of the
+//! current user on the client.
+//!
+//! The server keeps a session for every browser behind an http only cookie. Add the [`SessionLayer`] to the axum router
+//! of the server, check the credentials in a server function and [`login`] the user into the session. Server functions
+//! and server rendering read the user back with [`User`] or [`current_user`]. On the client, [`use_auth`] holds the
+//! current user. It starts with the user the server rendered the page for and is updated after the login or logout
+//! server function returns.
+//!
+//! ```rust, ignore
+//! #[derive(Clone, Serialize, Deserialize)]
+//! struct Account {
+//!     name: String,
+//! }
+//!
+//! #[server]
+//! async fn sign_in(name: String, password: String) -> Result<Account, ServerFnError> {
+//!     let account = db::check_password(&name, &password).await?;
+//!     login(&account)?;
+//!     Ok(account)
+//! }
+//!
+//! #[server]
+//! async fn sign_out() -> Result<(), ServerFnError> {
+//!     logout()?;
+//!     Ok(())
+//! }
+//!
+//! #[server]
+//! async fn messages() -> Result<Vec<String>, ServerFnError> {
+//!     let User(account): User<Account> = extract().await?;
+//!     Ok(db::messages_for(&account.name).await?)
+//! }
+//!
+//! fn Header() -> Element {
+//!     let mut auth = use_auth::<Account>();
+//!
+//!     rsx! {
+//!         if let Some(account) = auth.user() {
+//!             "Signed in as {account.name}"
+//!             button {
+//!                 onclick: move |_| async move {
+//!                     if sign_out().await.is_ok() {
+//!                         auth.set_user(None);
+//!                     }
+//!                 },
+//!                 "Sign out"
+//!             }
+//!         } else {
+//!             button {
+//!                 onclick: move |_| async move {
+//!                     if let Ok(account) = sign_in("ferris".into(), "hunter2".into()).await {
+//!                         auth.set_user(Some(account));
+//!                     }
+//!                 },
+//!                 "Sign in"
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use dioxus_lib::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Display;
+
+#[cfg(feature = "server")]
+mod server;
+#[cfg(feature = "server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "server")))]
+pub use server::*;
+
+/// A user that can be logged into a session. The user is serialized into the session on the server and into the
+/// html of server rendered pages.
+pub trait AuthUser: Clone + Serialize + DeserializeOwned + Send + Sync + 'static {}
+
+impl<T: Clone + Serialize + DeserializeOwned + Send + Sync + 'static> AuthUser for T {}
+
+/// An error from reading or changing the session
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum AuthError {
+    /// No user is logged into the session
+    Unauthorized,
+    /// The request didn't go through the `SessionLayer`
+    MissingSessionLayer,
+    /// A value couldn't be serialized into the session
+    Serialization(String),
+    /// The session store failed
+    Store(String),
+}
+
+impl Display for AuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthError::Unauthorized => {
+                write!(f, "AuthError::Unauthorized - no user is logged in")
+            }
+            AuthError::MissingSessionLayer => write!(
+                f,
+                "AuthError::MissingSessionLayer - add the SessionLayer to the router of the server"
+            ),
+            AuthError::Serialization(err) => write!(f, "AuthError::Serialization - {err}"),
+            AuthError::Store(err) => write!(f, "AuthError::Store - {err}"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// The user that is logged in on the client. Get it with [`use_auth`].
+pub struct Auth<U: 'static> {
+    user: Signal<Option<U>>,
+}
+
+impl<U> Clone for Auth<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U> Copy for Auth<U> {}
+
+impl<U: AuthUser> Auth<U> {
+    /// Get the user that is logged in. Reading this subscribes the component to changes.
+    pub fn user(&self) -> Option<U> {
+        (self.user)()
+    }
+
+    /// Check if a user is logged in. Reading this subscribes the component to changes.
+    pub fn is_logged_in(&self) -> bool {
+        self.user.read().is_some()
+    }
+
+    /// Update the user after a login or logout server function returned. The session on the server is changed by
+    /// [`login`] and [`logout`], so this only changes what the client shows.
+    pub fn set_user(&mut self, user: Option<U>) {
+        self.user.set(user);
+    }
+}
+
+/// Get the user that is logged in, shared by every component of the app.
+///
+/// On the server, the user is read from the session of the request and serialized into the page. The client starts
+/// with the same user when it hydrates, so the page doesn't flash the logged out state. Apps without server rendering
+/// start logged out until [`Auth::set_user`] is called.
+#[track_caller]
+pub fn use_auth<U: AuthUser>() -> Auth<U> {
+    let location = std::panic::Location::caller();
+    use_hook(|| {
+        if let Some(auth) = try_consume_context::<Auth<U>>() {
+            return auth;
+        }
+        let user = crate::hooks::server_cached::server_cached(user_on_server::<U>, location);
+        provide_root_context(Auth {
+            user: Signal::new_in_scope(user, ScopeId::ROOT),
+        })
+    })
+}
+
+fn user_on_server<U: AuthUser>() -> Option<U> {
+    #[cfg(feature = "server")]
+    return current_user();
+
+    #[cfg(not(feature = "server"))]
+    None
+}
@@ -0,0 +1,495 @@
+use super::{AuthError, AuthUser};
+use crate::prelude::{server_context, DioxusServerContext, FromServerContext};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use http::{header, HeaderMap, HeaderValue, Request, Response};
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, SystemTime},
+};
+
+/// The key [`login`] stores the user under in the session
+const USER_KEY: &str = "dioxus_user";
+
+/// The values stored in a session
+pub type SessionData = HashMap<String, serde_json::Value>;
+
+/// Where the [`SessionLayer`] keeps the data of the sessions between requests.
+///
+/// [`MemoryStore`] keeps the sessions in memory, so they are lost when the server restarts and can't be shared
+/// between several servers. Implement this trait to keep them in a database instead.
+#[async_trait::async_trait]
+pub trait SessionStore: Send + Sync + 'static {
+    /// Load the data of a session. Returns `None` if the session doesn't exist or expired.
+    async fn load(&self, id: &str) -> Result<Option<SessionData>, AuthError>;
+
+    /// Save the data of a session until it expires
+    async fn save(
+        &self,
+        id: &str,
+        data: &SessionData,
+        expires: SystemTime,
+    ) -> Result<(), AuthError>;
+
+    /// Delete a session
+    async fn delete(&self, id: &str) -> Result<(), AuthError>;
+}
+
+/// A [`SessionStore`] that keeps the sessions in the memory of the server
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    sessions: Arc<Mutex<HashMap<String, (SessionData, SystemTime)>>>,
+}
+
+#[async_trait::async_trait]
+impl SessionStore for MemoryStore {
+    async fn load(&self, id: &str) -> Result<Option<SessionData>, AuthError> {
+        let mut sessions = self.sessions.lock();
+        let now = SystemTime::now();
+        // Drop the expired sessions while the lock is held anyway
+        sessions.retain(|_, (_, expires)| *expires > now);
+        Ok(sessions.get(id).map(|(data, _)| data.clone()))
+    }
+
+    async fn save(
+        &self,
+        id: &str,
+        data: &SessionData,
+        expires: SystemTime,
+    ) -> Result<(), AuthError> {
+        self.sessions
+            .lock()
+            .insert(id.to_string(), (data.clone(), expires));
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), AuthError> {
+        self.sessions.lock().remove(id);
+        Ok(())
+    }
+}
+
+struct SessionState {
+    /// The id of the session if it was loaded from the store
+    id: Option<String>,
+    data: SessionData,
+    changed: bool,
+    renew: bool,
+    destroyed: bool,
+}
+
+/// The session of the current request. Read it in server functions with [`extract`](crate::prelude::extract).
+///
+/// Changes are saved to the [`SessionStore`] when the response is sent, and the cookie of the session is only set if
+/// the session has data.
+#[derive(Clone)]
+pub struct Session {
+    state: Arc<Mutex<SessionState>>,
+}
+
+impl Session {
+    fn new(id: Option<String>, data: SessionData) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(SessionState {
+                id,
+                data,
+                changed: false,
+                renew: false,
+                destroyed: false,
+            })),
+        }
+    }
+
+    /// The id of the session if the browser sent a cookie for a session in the store
+    pub(crate) fn id(&self) -> Option<String> {
+        self.state.lock().id.clone()
+    }
+
+    /// Get a value from the session
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let state = self.state.lock();
+        serde_json::from_value(state.data.get(key)?.clone()).ok()
+    }
+
+    /// Insert a value into the session
+    pub fn insert(&self, key: impl Into<String>, value: impl Serialize) -> Result<(), AuthError> {
+        let value =
+            serde_json::to_value(value).map_err(|err| AuthError::Serialization(err.to_string()))?;
+        let mut state = self.state.lock();
+        state.data.insert(key.into(), value);
+        state.changed = true;
+        Ok(())
+    }
+
+    /// Remove a value from the session
+    pub fn remove(&self, key: &str) {
+        let mut state = self.state.lock();
+        if state.data.remove(key).is_some() {
+            state.changed = true;
+        }
+    }
+
+    /// Move the session to a new id when the response is sent. Renew the session whenever the user it belongs to
+    /// changes, so an id that leaked before the login can't be used to act as the user.
+    pub fn renew(&self) {
+        self.state.lock().renew = true;
+    }
+
+    /// Delete the session and its cookie when the response is sent
+    pub fn destroy(&self) {
+        let mut state = self.state.lock();
+        state.data.clear();
+        state.destroyed = true;
+    }
+
+    /// Save the changes to the store and create the cookie to send back, if it changed
+    async fn commit(&self, layer: &SessionLayer) -> Result<Option<HeaderValue>, AuthError> {
+        let (id, data, destroyed, renew) = {
+            let state = self.state.lock();
+            if !state.destroyed && !state.changed && !state.renew {
+                return Ok(None);
+            }
+            (
+                state.id.clone(),
+                state.data.clone(),
+                state.destroyed,
+                state.renew,
+            )
+        };
+
+        if destroyed || data.is_empty() {
+            let Some(id) = id else {
+                return Ok(None);
+            };
+            layer.store.delete(&id).await?;
+            return Ok(Some(layer.cookie("", Duration::ZERO)));
+        }
+
+        let id = match id {
+            Some(id) if !renew => id,
+            old => {
+                if let Some(old) = old {
+                    layer.store.delete(&old).await?;
+                }
+                new_session_id()
+            }
+        };
+        let expires = SystemTime::now() + layer.max_age;
+        layer.store.save(&id, &data, expires).await?;
+        Ok(Some(layer.cookie(&id, layer.max_age)))
+    }
+}
+
+#[async_trait::async_trait]
+impl FromServerContext for Session {
+    type Rejection = AuthError;
+
+    async fn from_request(req: &DioxusServerContext) -> Result<Self, Self::Rejection> {
+        session_of(req)
+    }
+}
+
+/// The user that is logged into the session of the request. Extracting it fails with [`AuthError::Unauthorized`] if
+/// nobody is logged in, so it guards server functions that need a user.
+///
+/// ```rust, ignore
+/// #[server]
+/// async fn delete_account() -> Result<(), ServerFnError> {
+///     let User(account): User<Account> = extract().await?;
+///     db::delete_account(account.id).await?;
+///     logout()?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct User<U>(pub U);
+
+#[async_trait::async_trait]
+impl<U: AuthUser> FromServerContext for User<U> {
+    type Rejection = AuthError;
+
+    async fn from_request(req: &DioxusServerContext) -> Result<Self, Self::Rejection> {
+        let session = session_of(req)?;
+        session
+            .get(USER_KEY)
+            .map(User)
+            .ok_or(AuthError::Unauthorized)
+    }
+}
+
+/// Log a user into the session of the current request. Call this after the credentials were checked.
+///
+/// The session moves to a new id and the response sets the new cookie, which works for server functions called from
+/// the client and for requests rendered on the server.
+pub fn login<U: AuthUser>(user: &U) -> Result<(), AuthError> {
+    let session = session_of(&server_context())?;
+    session.renew();
+    session.insert(USER_KEY, user)
+}
+
+/// Log the user out by deleting the session of the current request and its cookie
+pub fn logout() -> Result<(), AuthError> {
+    session_of(&server_context())?.destroy();
+    Ok(())
+}
+
+/// Get the user that is logged into the session of the current request, if there is one
+pub fn current_user<U: AuthUser>() -> Option<U> {
+    session_of(&server_context()).ok()?.get(USER_KEY)
+}
+
+fn session_of(context: &DioxusServerContext) -> Result<Session, AuthError> {
+    context
+        .request_parts()
+        .extensions
+        .get::<Session>()
+        .cloned()
+        .ok_or(AuthError::MissingSessionLayer)
+}
+
+fn new_session_id() -> String {
+    URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>())
+}
+
+/// A tower layer that loads the session of every request from a [`SessionStore`] and saves it when the response is
+/// sent.
+///
+/// ```rust, ignore
+/// let router = axum::Router::new()
+///     .serve_dioxus_application(ServeConfig::new().unwrap(), App)
+///     .layer(SessionLayer::new(MemoryStore::default()));
+/// ```
+#[derive(Clone)]
+pub struct SessionLayer {
+    store: Arc<dyn SessionStore>,
+    cookie_name: String,
+    secure: bool,
+    max_age: Duration,
+}
+
+impl SessionLayer {
+    /// Create a layer that keeps sessions in the store for 30 days
+    pub fn new(store: impl SessionStore) -> Self {
+        Self {
+            store: Arc::new(store),
+            cookie_name: "dioxus_session".to_string(),
+            // Safari doesn't keep secure cookies for http://localhost
+            secure: !cfg!(debug_assertions),
+            max_age: Duration::from_secs(30 * 24 * 60 * 60),
+        }
+    }
+
+    /// Set the name of the cookie that holds the id of the session. Defaults to `dioxus_session`.
+    pub fn with_cookie_name(mut self, name: impl Into<String>) -> Self {
+        self.cookie_name = name.into();
+        self
+    }
+
+    /// Only send the cookie over https. Defaults to true in release builds.
+    pub fn with_secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Set how long a session lasts after it last changed
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// The `Set-Cookie` header for the session. The cookie is hidden from javascript and not sent with requests that
+    /// other sites start, except for navigating to the app.
+    fn cookie(&self, id: &str, max_age: Duration) -> HeaderValue {
+        let secure = if self.secure { "; Secure" } else { "" };
+        let cookie = format!(
+            "{}={id}; Path=/; Max-Age={}; HttpOnly; SameSite=Lax{secure}",
+            self.cookie_name,
+            max_age.as_secs()
+        );
+        HeaderValue::from_str(&cookie).expect("session cookies only contain ascii")
+    }
+
+    fn session_id(&self, headers: &HeaderMap) -> Option<String> {
+        headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(name, _)| *name == self.cookie_name)
+            .map(|(_, id)| id.to_string())
+    }
+}
+
+impl<S> tower_layer::Layer<S> for SessionLayer {
+    type Service = SessionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SessionService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service [`SessionLayer`] wraps other services in
+#[derive(Clone)]
+pub struct SessionService<S> {
+    inner: S,
+    layer: SessionLayer,
+}
+
+impl<S, ReqBody, ResBody> tower::Service<Request<ReqBody>> for SessionService<S>
+where
+    S: tower::Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    ReqBody: Send + 'static,
+    ResBody: Send,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        // The clone may not be ready, so call the service that was polled and keep the clone
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+
+        Box::pin(async move {
+            let mut session = Session::new(None, SessionData::new());
+            if let Some(id) = layer.session_id(request.headers()) {
+                match layer.store.load(&id).await {
+                    Ok(Some(data)) => session = Session::new(Some(id), data),
+                    Ok(None) => {}
+                    Err(err) => tracing::error!("Failed to load the session: {err}"),
+                }
+            }
+            request.extensions_mut().insert(session.clone());
+
+            let mut response = inner.call(request).await?;
+            match session.commit(&layer).await {
+                Ok(Some(cookie)) => {
+                    response.headers_mut().append(header::SET_COOKIE, cookie);
+                }
+                Ok(None) => {}
+                Err(err) => tracing::error!("Failed to save the session: {err}"),
+            }
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::{Service, ServiceExt};
+
+    /// A service that logs in the user in the `login` header and responds with the user of the session
+    async fn handler(request: Request<()>) -> Result<Response<String>, std::convert::Infallible> {
+        let session = request.extensions().get::<Session>().unwrap().clone();
+        if let Some(user) = request.headers().get("login") {
+            session.renew();
+            session
+                .insert(USER_KEY, user.to_str().unwrap().to_string())
+                .unwrap();
+        }
+        if request.headers().contains_key("logout") {
+            session.destroy();
+        }
+        let user: Option<String> = session.get(USER_KEY);
+        Ok(Response::new(user.unwrap_or_default()))
+    }
+
+    async fn send<S>(service: &mut S, headers: &[(&str, &str)]) -> Response<String>
+    where
+        S: Service<Request<()>, Response = Response<String>>,
+        S::Error: std::fmt::Debug,
+    {
+        let mut request = Request::builder();
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        service
+            .ready()
+            .await
+            .unwrap()
+            .call(request.body(()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    fn service(
+    ) -> impl Service<Request<()>, Response = Response<String>, Error = std::convert::Infallible>
+    {
+        let layer = SessionLayer::new(MemoryStore::default()).with_secure(true);
+        tower_layer::Layer::layer(&layer, tower::service_fn(handler))
+    }
+
+    fn session_cookie(response: &Response<String>) -> String {
+        let cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+        cookie.split(';').next().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn sessions_without_data_have_no_cookie() {
+        let mut service = service();
+        let response = send(&mut service, &[]).await;
+        assert!(!response.headers().contains_key(header::SET_COOKIE));
+    }
+
+    #[tokio::test]
+    async fn login_sets_a_cookie_that_restores_the_user() {
+        let mut service = service();
+        let response = send(&mut service, &[("login", "ferris")]).await;
+        let set_cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+        assert!(set_cookie.starts_with("dioxus_session="));
+        assert!(set_cookie.contains("HttpOnly"));
+        assert!(set_cookie.contains("SameSite=Lax"));
+        assert!(set_cookie.contains("Secure"));
+
+        let cookie = session_cookie(&response);
+        let response = send(&mut service, &[("cookie", &cookie)]).await;
+        assert_eq!(response.body(), "ferris");
+        // The session didn't change, so the cookie isn't sent again
+        assert!(!response.headers().contains_key(header::SET_COOKIE));
+    }
+
+    #[tokio::test]
+    async fn login_moves_the_session_to_a_new_id() {
+        let mut service = service();
+        let first = session_cookie(&send(&mut service, &[("login", "ferris")]).await);
+        let second =
+            session_cookie(&send(&mut service, &[("cookie", &first), ("login", "crab")]).await);
+        assert_ne!(first, second);
+
+        // The old id no longer works
+        let response = send(&mut service, &[("cookie", &first)]).await;
+        assert_eq!(response.body(), "");
+        let response = send(&mut service, &[("cookie", &second)]).await;
+        assert_eq!(response.body(), "crab");
+    }
+
+    #[tokio::test]
+    async fn logout_deletes_the_session_and_the_cookie() {
+        let mut service = service();
+        let cookie = session_cookie(&send(&mut service, &[("login", "ferris")]).await);
+
+        let response = send(&mut service, &[("cookie", &cookie), ("logout", "")]).await;
+        let set_cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+        assert!(set_cookie.starts_with("dioxus_session=;"));
+        assert!(set_cookie.contains("Max-Age=0"));
+
+        let response = send(&mut service, &[("cookie", &cookie)]).await;
+        assert_eq!(response.body(), "");
+    }
+}
@@ -0,0 +1,84 @@
+//! Receive the crash reports of apps.
+//!
+//! Apps send reports with the `HttpTransport` of `dioxus-crash-reporter`. Mount [`crash_reports`] on the router of the
+//! server to receive them:
+//!
+//! ```rust, ignore
+//! let router = axum::Router::new()
+//!     .route(
+//!         "/api/crash",
+//!         crash_reports(|report: CrashReport| async move {
+//!             tracing::error!("{} crashed on {}: {}", report.release.unwrap_or_default(), report.platform, report.message);
+//!             db::crash_reports().insert(report).await;
+//!         }),
+//!     )
+//!     .serve_dioxus_application(ServeConfig::new().unwrap(), app);
+//! ```
+//!
+//! ```rust, ignore
+//! CrashReporter::new(HttpTransport::new("https://example.com/api/crash")).install()?;
+//! ```
+
+use axum::{extract::DefaultBodyLimit, http::StatusCode, routing::MethodRouter, Json};
+use std::future::Future;
+
+pub use dioxus_crash_reporter::{Breadcrumb, CrashKind, CrashReport};
+
+/// The largest report the endpoint accepts. Reports of native crashes include a minidump, which can be several
+/// megabytes.
+pub const MAX_CRASH_REPORT_SIZE: usize = 32 * 1024 * 1024;
+
+/// A `POST` route that calls a function with every crash report it receives. Reports that are not valid json are
+/// rejected with `422 Unprocessable Entity`.
+pub fn crash_reports<S, F, Fut>(on_report: F) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+    F: Fn(CrashReport) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    axum::routing::post(move |Json(report): Json<CrashReport>| async move {
+        on_report(report).await;
+        StatusCode::NO_CONTENT
+    })
+    .layer(DefaultBodyLimit::max(MAX_CRASH_REPORT_SIZE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn receives_reports() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let router = axum::Router::new().route(
+            "/api/crash",
+            crash_reports({
+                let received = received.clone();
+                move |report: CrashReport| {
+                    received.lock().unwrap().push(report);
+                    async {}
+                }
+            }),
+        );
+
+        let mut report = CrashReport::new(CrashKind::Native, "segfault");
+        report.minidump = Some(vec![0x4d, 0x44, 0x4d, 0x50]);
+        let request = Request::post("/api/crash")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&report).unwrap()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(*received.lock().unwrap(), [report]);
+
+        let request = Request::post("/api/crash")
+            .header("content-type", "application/json")
+            .body(Body::from("not a report"))
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert!(response.status().is_client_error());
+    }
+}
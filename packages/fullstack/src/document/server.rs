@@ -1,6 +1,6 @@
 //! On the server, we collect any elements that should be rendered into the head in the first frame of SSR.
-//! After the first frame, we have already sent down the head, so we can't modify it in place. The web client
-//! will hydrate the head with the correct contents once it loads.
+//! After the first frame, we have already sent down the head, so we can't modify it in place. Changes to the head
+//! while streaming are sent down as scripts that apply them before the suspense boundary that made them hydrates.
 
 use std::cell::RefCell;
 
@@ -18,6 +18,9 @@ struct ServerDocumentInner {
     meta: Vec<Element>,
     link: Vec<Element>,
     script: Vec<Element>,
+    elements: Vec<HeadElement>,
+    /// Javascript that applies the changes to the head made after the head was sent
+    late: String,
 }
 
 /// A Document provider that collects all contents injected into the head for SSR rendering.
@@ -44,28 +47,47 @@ impl ServerDocument {
 
         RENDERER.write().render_element_to(to, element)?;
 
+        // Elements with a higher priority come first. The sort is stable so elements keep their render order otherwise
+        let mut elements: Vec<_> = myself.elements.iter().collect();
+        elements.sort_by_key(|element| std::cmp::Reverse(element.priority));
+        for element in elements {
+            to.write_str(&element.to_html())?;
+        }
+
         Ok(())
     }
 
     pub(crate) fn start_streaming(&self) {
-        self.0.borrow_mut().streaming = true;
+        let mut myself = self.0.borrow_mut();
+        myself.streaming = true;
+        // Everything so far was just rendered into the head
+        myself.late.clear();
+    }
+
+    /// Take a script that applies the changes to the head made since the last call, if there are any
+    pub(crate) fn take_late_head_script(&self) -> Option<String> {
+        let late = std::mem::take(&mut self.0.borrow_mut().late);
+        (!late.is_empty()).then(|| format!("<script>{late}</script>"))
     }
 
-    pub(crate) fn warn_if_streaming(&self) {
-        if self.0.borrow().streaming {
-            tracing::warn!("Attempted to insert content into the head after the initial streaming frame. Inserting content into the head only works during the initial render of SSR outside before resolving any suspense boundaries.");
+    fn add_late(&self, js: String) {
+        let mut myself = self.0.borrow_mut();
+        if myself.streaming {
+            // The script is written into the html, so it can't contain a closing script tag
+            myself.late.push_str(&js.replace("</", "<\\/"));
         }
     }
 
     /// Write the head element into the serialized context for hydration
-    /// We write true if the head element was written to the DOM during server side rendering
+    /// We write true if the head element was written to the DOM during server side rendering. Elements created
+    /// while streaming are inserted by a script before their suspense boundary hydrates, so this is always true.
     #[track_caller]
     pub(crate) fn serialize_for_hydration(&self) {
         // We only serialize the head elements if the web document feature is enabled
         #[cfg(feature = "document")]
         {
             let serialize = crate::html_storage::serialize_context();
-            serialize.push(&!self.0.borrow().streaming, std::panic::Location::caller());
+            serialize.push(&true, std::panic::Location::caller());
         }
     }
 }
@@ -76,7 +98,8 @@ impl Document for ServerDocument {
     }
 
     fn set_title(&self, title: String) {
-        self.warn_if_streaming();
+        let js_title = serde_json::to_string(&title).unwrap_or_default();
+        self.add_late(format!("document.title = {js_title};"));
         self.0.borrow_mut().title = Some(title);
     }
 
@@ -146,8 +169,20 @@ impl Document for ServerDocument {
         })
     }
 
+    fn insert_head_element(&self, element: HeadElement) {
+        self.add_late(insert_element_in_head(&element));
+        self.0.borrow_mut().elements.push(element);
+    }
+
+    fn remove_head_element(&self, id: HeadElementId) {
+        self.add_late(remove_element_in_head(id));
+        self.0
+            .borrow_mut()
+            .elements
+            .retain(|element| element.id != id);
+    }
+
     fn create_head_component(&self) -> bool {
-        self.warn_if_streaming();
         self.serialize_for_hydration();
         true
     }
@@ -45,6 +45,16 @@ impl Document for FullstackWebDocument {
         WebDocument.create_link(props);
     }
 
+    /// Insert an element a head component rendered into the head
+    fn insert_head_element(&self, element: HeadElement) {
+        WebDocument.insert_head_element(element);
+    }
+
+    /// Remove an element a head component rendered from the head
+    fn remove_head_element(&self, id: HeadElementId) {
+        WebDocument.remove_head_element(id);
+    }
+
     fn create_head_component(&self) -> bool {
         !head_element_written_on_server()
     }
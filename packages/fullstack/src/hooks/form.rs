@@ -0,0 +1,823 @@
+//! Form state and validation with [`use_form`], [`Form`] and [`Field`].
+//!
+//! A form tracks the value of each field, whether it was changed (dirty) or visited (touched) and the error from its
+//! validators. Validators run when a field changes or loses focus and for every field when the form is submitted. The
+//! submit handler only runs if every field is valid.
+//!
+//! The submit handler usually calls a server function. If the server function returns
+//! [`ServerFnError<FieldErrors>`](ServerFnError), the errors are mapped back to the fields they belong to.
+
+use dioxus_lib::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::ServerFnError;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    str::FromStr,
+};
+
+/// The value of every field in a form by name
+pub type FormValues = HashMap<String, String>;
+
+type SyncValidator = Rc<dyn Fn(&str) -> Result<(), String>>;
+type AsyncValidator = Rc<dyn Fn(String) -> Pin<Box<dyn Future<Output = Result<(), String>>>>>;
+type SubmitHandler =
+    Rc<dyn Fn(FormValues) -> Pin<Box<dyn Future<Output = Result<(), SubmitError>>>>>;
+
+#[derive(Default)]
+struct Validators {
+    sync: Vec<SyncValidator>,
+    async_: Vec<AsyncValidator>,
+}
+
+#[derive(Default)]
+struct FieldState {
+    value: String,
+    initial: String,
+    touched: bool,
+    validating: bool,
+    error: Option<String>,
+}
+
+/// Validation errors for individual fields of a form.
+///
+/// Return these from a server function as [`ServerFnError<FieldErrors>`](ServerFnError) and the form that submitted it
+/// shows each error next to its field.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldErrors {
+    fields: HashMap<String, String>,
+}
+
+impl FieldErrors {
+    /// Create an empty set of errors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an error for a field and return the errors
+    pub fn with(mut self, field: impl ToString, error: impl ToString) -> Self {
+        self.add(field, error);
+        self
+    }
+
+    /// Add an error for a field. If the field already has an error, it is replaced.
+    pub fn add(&mut self, field: impl ToString, error: impl ToString) {
+        self.fields.insert(field.to_string(), error.to_string());
+    }
+
+    /// Get the error for a field
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.fields.get(field).map(|error| error.as_str())
+    }
+
+    /// Check if there are no errors
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Iterate over the field names and their errors
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields
+            .iter()
+            .map(|(field, error)| (field.as_str(), error.as_str()))
+    }
+}
+
+// Server functions send custom errors as strings
+impl Display for FieldErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(&self.fields).map_err(|_| std::fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl FromStr for FieldErrors {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            fields: serde_json::from_str(s)?,
+        })
+    }
+}
+
+/// The reason a form submission failed
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubmitError {
+    /// Some fields are invalid. The errors are shown on their fields.
+    Fields(FieldErrors),
+    /// The submission failed for a reason that doesn't belong to a field
+    Form(String),
+}
+
+impl From<FieldErrors> for SubmitError {
+    fn from(errors: FieldErrors) -> Self {
+        Self::Fields(errors)
+    }
+}
+
+impl From<ServerFnError<FieldErrors>> for SubmitError {
+    fn from(error: ServerFnError<FieldErrors>) -> Self {
+        match error {
+            ServerFnError::WrappedServerError(errors) => Self::Fields(errors),
+            error => Self::Form(error.to_string()),
+        }
+    }
+}
+
+impl From<ServerFnError> for SubmitError {
+    fn from(error: ServerFnError) -> Self {
+        Self::Form(error.to_string())
+    }
+}
+
+/// A form returned from [`use_form`]
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseForm {
+    scope: ScopeId,
+    fields: CopyValue<HashMap<String, Signal<FieldState>>>,
+    validators: CopyValue<HashMap<String, Validators>>,
+    validation_tasks: CopyValue<HashMap<String, Task>>,
+    on_submit: CopyValue<Option<SubmitHandler>>,
+    submitting: Signal<bool>,
+    form_error: Signal<Option<String>>,
+}
+
+impl UseForm {
+    /// Add a field with an initial value. If the field already exists, this does nothing.
+    pub fn register(&self, name: &str, initial: impl ToString) {
+        if self.fields.peek().contains_key(name) {
+            return;
+        }
+        let initial = initial.to_string();
+        let field = Signal::new_in_scope(
+            FieldState {
+                value: initial.clone(),
+                initial,
+                ..Default::default()
+            },
+            self.scope,
+        );
+        self.fields
+            .write_unchecked()
+            .insert(name.to_string(), field);
+    }
+
+    /// Add a validator that runs every time the field changes. Return an error message if the value is invalid.
+    pub fn add_validator(
+        &self,
+        name: &str,
+        validator: impl Fn(&str) -> Result<(), String> + 'static,
+    ) {
+        self.validators
+            .write_unchecked()
+            .entry(name.to_string())
+            .or_default()
+            .sync
+            .push(Rc::new(validator));
+    }
+
+    /// Add a validator that runs in the background after the other validators of the field pass. If the field changes
+    /// while the validator is running, the old validation is cancelled.
+    pub fn add_async_validator<F>(&self, name: &str, validator: impl Fn(String) -> F + 'static)
+    where
+        F: Future<Output = Result<(), String>> + 'static,
+    {
+        self.validators
+            .write_unchecked()
+            .entry(name.to_string())
+            .or_default()
+            .async_
+            .push(Rc::new(move |value| Box::pin(validator(value))));
+    }
+
+    /// Set the function that runs when the form is submitted and every field is valid.
+    ///
+    /// Errors returned from the handler are mapped back to the form. [`FieldErrors`] are shown on their fields and any
+    /// other error is available from [`UseForm::form_error`].
+    pub fn on_submit<F, E>(&self, handler: impl Fn(FormValues) -> F + 'static)
+    where
+        F: Future<Output = Result<(), E>> + 'static,
+        E: Into<SubmitError>,
+    {
+        let handler: SubmitHandler = Rc::new(move |values| {
+            let future = handler(values);
+            Box::pin(async move { future.await.map_err(Into::into) })
+        });
+        self.on_submit.write_unchecked().replace(handler);
+    }
+
+    /// Get the value of a field. Reading this subscribes to the field.
+    pub fn value(&self, name: &str) -> String {
+        self.read_field(name, |field| field.value.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the value of every field. Reading this subscribes to every field.
+    pub fn values(&self) -> FormValues {
+        self.fields
+            .peek()
+            .iter()
+            .map(|(name, field)| (name.clone(), field.read().value.clone()))
+            .collect()
+    }
+
+    /// Change the value of a field and validate it
+    pub fn set_value(&self, name: &str, value: impl ToString) {
+        self.register(name, "");
+        if let Some(mut field) = self.field(name) {
+            field.write().value = value.to_string();
+        }
+        self.validate_field(name);
+    }
+
+    /// Mark a field as visited and validate it. [`Field`] does this when the input loses focus.
+    pub fn touch(&self, name: &str) {
+        self.register(name, "");
+        if let Some(mut field) = self.field(name) {
+            if !field.peek().touched {
+                field.write().touched = true;
+            }
+        }
+        self.validate_field(name);
+    }
+
+    /// Check if the value of a field is different from its initial value
+    pub fn is_dirty(&self, name: &str) -> bool {
+        self.read_field(name, |field| field.value != field.initial)
+            .unwrap_or_default()
+    }
+
+    /// Check if the value of any field is different from its initial value
+    pub fn is_form_dirty(&self) -> bool {
+        self.fields
+            .peek()
+            .values()
+            .any(|field| field.read().value != field.read().initial)
+    }
+
+    /// Check if a field was visited or the form was submitted
+    pub fn is_touched(&self, name: &str) -> bool {
+        self.read_field(name, |field| field.touched)
+            .unwrap_or_default()
+    }
+
+    /// Check if the async validators of a field are running
+    pub fn is_validating(&self, name: &str) -> bool {
+        self.read_field(name, |field| field.validating)
+            .unwrap_or_default()
+    }
+
+    /// Get the error of a field. Errors are only shown once the field was touched so the user isn't warned about fields
+    /// they haven't filled out yet.
+    pub fn error(&self, name: &str) -> Option<String> {
+        self.read_field(name, |field| {
+            field.touched.then(|| field.error.clone()).flatten()
+        })
+        .flatten()
+    }
+
+    /// Set the error of a field and mark it as touched so the error is shown
+    pub fn set_error(&self, name: &str, error: Option<String>) {
+        self.register(name, "");
+        if let Some(mut field) = self.field(name) {
+            let mut field = field.write();
+            field.touched = true;
+            field.error = error;
+        }
+    }
+
+    /// Check if every field passed its validators. This includes fields that weren't touched yet.
+    pub fn is_valid(&self) -> bool {
+        self.fields
+            .peek()
+            .values()
+            .all(|field| field.read().error.is_none())
+    }
+
+    /// Get the error from the last submission that doesn't belong to a field
+    pub fn form_error(&self) -> Option<String> {
+        self.form_error.read().clone()
+    }
+
+    /// Check if the submit handler is running
+    pub fn is_submitting(&self) -> bool {
+        (self.submitting)()
+    }
+
+    /// Set every field back to its initial value and clear all errors
+    pub fn reset(&self) {
+        for (_, task) in self.validation_tasks.write_unchecked().drain() {
+            task.cancel();
+        }
+        for field in self.fields.peek().values() {
+            let mut field = *field;
+            let mut field = field.write();
+            field.value = field.initial.clone();
+            field.touched = false;
+            field.validating = false;
+            field.error = None;
+        }
+        let mut form_error = self.form_error;
+        form_error.set(None);
+    }
+
+    /// Validate every field and run the submit handler if they are all valid.
+    ///
+    /// The submission is owned by the component that called [`use_form`] and is cancelled if it is dropped.
+    pub fn submit(&self) {
+        let form = *self;
+        self.scope.spawn(async move {
+            if *form.submitting.peek() {
+                return;
+            }
+            form.run_submit().await;
+        })
+    }
+
+    async fn run_submit(self) {
+        let mut form_error = self.form_error;
+        form_error.set(None);
+
+        // Validate every field and show all of the errors
+        let names: Vec<String> = self.fields.peek().keys().cloned().collect();
+        let mut valid = true;
+        for name in &names {
+            if let Some(task) = self.validation_tasks.write_unchecked().remove(name) {
+                task.cancel();
+            }
+            let Some(mut field) = self.field(name) else {
+                continue;
+            };
+            let value = field.peek().value.clone();
+            let error = self.run_validators(name, value).await;
+            valid &= error.is_none();
+            let mut field = field.write();
+            field.touched = true;
+            field.validating = false;
+            field.error = error;
+        }
+        if !valid {
+            return;
+        }
+
+        let Some(handler) = self.on_submit.peek().clone() else {
+            return;
+        };
+        let mut submitting = self.submitting;
+        submitting.set(true);
+        let result = handler(self.values_untracked()).await;
+        submitting.set(false);
+
+        match result {
+            Ok(()) => {
+                // The submitted values are the new starting point of the form
+                for field in self.fields.peek().values() {
+                    let mut field = *field;
+                    let mut field = field.write();
+                    field.initial = field.value.clone();
+                }
+            }
+            Err(SubmitError::Fields(errors)) => {
+                for (name, error) in errors.iter() {
+                    self.set_error(name, Some(error.to_string()));
+                }
+            }
+            Err(SubmitError::Form(error)) => form_error.set(Some(error)),
+        }
+    }
+
+    fn validate_field(&self, name: &str) {
+        if let Some(task) = self.validation_tasks.write_unchecked().remove(name) {
+            task.cancel();
+        }
+        let Some(mut field) = self.field(name) else {
+            return;
+        };
+        let value = field.peek().value.clone();
+
+        // Show the errors from synchronous validators right away
+        let sync_error = self.run_sync_validators(name, &value);
+        let has_async = self
+            .validators
+            .peek()
+            .get(name)
+            .is_some_and(|validators| !validators.async_.is_empty());
+        if sync_error.is_some() || !has_async {
+            let mut field = field.write();
+            field.validating = false;
+            field.error = sync_error;
+            return;
+        }
+
+        field.write().validating = true;
+        let form = *self;
+        let name = name.to_string();
+        let task = self.scope.push_future({
+            let name = name.clone();
+            async move {
+                let error = form.run_async_validators(&name, value).await;
+                form.validation_tasks.write_unchecked().remove(&name);
+                let mut field = field.write();
+                field.validating = false;
+                field.error = error;
+            }
+        });
+        if let Some(task) = task {
+            self.validation_tasks.write_unchecked().insert(name, task);
+        }
+    }
+
+    async fn run_validators(&self, name: &str, value: String) -> Option<String> {
+        match self.run_sync_validators(name, &value) {
+            Some(error) => Some(error),
+            None => self.run_async_validators(name, value).await,
+        }
+    }
+
+    fn run_sync_validators(&self, name: &str, value: &str) -> Option<String> {
+        let validators = self.validators.peek();
+        let validators = validators.get(name)?;
+        validators
+            .sync
+            .iter()
+            .find_map(|validator| validator(value).err())
+    }
+
+    async fn run_async_validators(&self, name: &str, value: String) -> Option<String> {
+        let validators = match self.validators.peek().get(name) {
+            Some(validators) => validators.async_.clone(),
+            None => return None,
+        };
+        for validator in validators {
+            if let Err(error) = validator(value.clone()).await {
+                return Some(error);
+            }
+        }
+        None
+    }
+
+    fn field(&self, name: &str) -> Option<Signal<FieldState>> {
+        self.fields.peek().get(name).copied()
+    }
+
+    fn read_field<O>(&self, name: &str, f: impl FnOnce(&FieldState) -> O) -> Option<O> {
+        Some(f(&self.field(name)?.read()))
+    }
+
+    fn values_untracked(&self) -> FormValues {
+        self.fields
+            .peek()
+            .iter()
+            .map(|(name, field)| (name.clone(), field.peek().value.clone()))
+            .collect()
+    }
+}
+
+/// Create a form that tracks the value, dirty and touched state and validation errors of each field.
+///
+/// The closure runs once when the form is created. Use it to add validators with [`UseForm::add_validator`] and
+/// [`UseForm::add_async_validator`] and set the submit handler with [`UseForm::on_submit`]. Render the form with the
+/// [`Form`] and [`Field`] components or read and write the fields yourself.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[server]
+/// async fn sign_up(email: String) -> Result<(), ServerFnError<FieldErrors>> {
+///     if email == "taken@example.com" {
+///         return Err(FieldErrors::new().with("email", "That email is already in use").into());
+///     }
+///     Ok(())
+/// }
+///
+/// fn SignUp() -> Element {
+///     let form = use_form(|form| {
+///         form.add_validator("email", |email| match email.contains('@') {
+///             true => Ok(()),
+///             false => Err("Enter an email address".to_string()),
+///         });
+///         form.on_submit(|values| async move { sign_up(values["email"].clone()).await });
+///     });
+///
+///     rsx! {
+///         Form { form,
+///             Field { name: "email", r#type: "email" }
+///             if let Some(error) = form.error("email") {
+///                 p { "{error}" }
+///             }
+///             button { disabled: form.is_submitting(), "Sign up" }
+///         }
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_form(init: impl FnOnce(UseForm)) -> UseForm {
+    use_hook(|| {
+        let form = UseForm {
+            scope: current_scope_id().expect("use_form must be called in a component"),
+            fields: CopyValue::new(HashMap::new()),
+            validators: CopyValue::new(HashMap::new()),
+            validation_tasks: CopyValue::new(HashMap::new()),
+            on_submit: CopyValue::new(None),
+            submitting: Signal::new(false),
+            form_error: Signal::new(None),
+        };
+        init(form);
+        form
+    })
+}
+
+/// A `form` element that submits a [`UseForm`] instead of reloading the page.
+///
+/// [`Field`]s inside the form use it automatically.
+#[component]
+pub fn Form(
+    /// The form to submit
+    form: UseForm,
+    /// Attributes to pass to the form element
+    #[props(extends = GlobalAttributes, extends = form)]
+    attributes: Vec<Attribute>,
+    /// The contents of the form
+    children: Element,
+) -> Element {
+    use_context_provider(|| form);
+    rsx! {
+        form {
+            onsubmit: move |event| {
+                event.prevent_default();
+                form.submit();
+            },
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// An `input` element bound to a field of the surrounding [`Form`].
+///
+/// The field is validated every time it changes and marked as touched when it loses focus.
+#[component]
+pub fn Field(
+    /// The name of the field in the form
+    name: String,
+    /// The value of the field before the user changes it
+    #[props(default)]
+    initial: String,
+    /// Attributes to pass to the input element
+    #[props(extends = GlobalAttributes, extends = input)]
+    attributes: Vec<Attribute>,
+) -> Element {
+    let form = use_context::<UseForm>();
+    use_hook(|| form.register(&name, initial));
+
+    let value = form.value(&name);
+    let invalid = form.error(&name).is_some();
+    let on_input = name.clone();
+    let on_blur = name.clone();
+
+    rsx! {
+        input {
+            name: "{name}",
+            value: "{value}",
+            "aria-invalid": "{invalid}",
+            oninput: move |event| form.set_value(&on_input, event.value()),
+            onblur: move |_| form.touch(&on_blur),
+            ..attributes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_channel::oneshot;
+    use std::cell::RefCell;
+
+    /// The submissions and async validations that are waiting for the test to resolve them
+    #[derive(Clone, Default)]
+    struct Pending {
+        submissions: Rc<RefCell<Vec<(FormValues, oneshot::Sender<Result<(), SubmitError>>)>>>,
+        validations: Rc<RefCell<Vec<oneshot::Sender<Result<(), String>>>>>,
+    }
+
+    impl PartialEq for Pending {
+        fn eq(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.submissions, &other.submissions)
+        }
+    }
+
+    fn app(pending: Pending) -> Element {
+        let form = use_form(|form| {
+            form.register("email", "");
+            form.register("name", "Ferris");
+            form.add_validator("email", |email| match email.contains('@') {
+                true => Ok(()),
+                false => Err("Enter an email address".to_string()),
+            });
+            let validations = pending.validations.clone();
+            form.add_async_validator("name", move |_| {
+                let (sender, receiver) = oneshot::channel();
+                validations.borrow_mut().push(sender);
+                async move { receiver.await.unwrap_or(Ok(())) }
+            });
+            let submissions = pending.submissions.clone();
+            form.on_submit(move |values| {
+                let (sender, receiver) = oneshot::channel();
+                submissions.borrow_mut().push((values, sender));
+                async move { receiver.await.unwrap() }
+            });
+        });
+        use_context_provider(|| form);
+        rsx! {}
+    }
+
+    fn form(dom: &VirtualDom) -> UseForm {
+        dom.in_runtime(|| ScopeId::APP.consume_context::<UseForm>().unwrap())
+    }
+
+    fn check<O>(dom: &VirtualDom, f: impl FnOnce(UseForm) -> O) -> O {
+        dom.in_runtime(|| f(form(dom)))
+    }
+
+    /// Run the tasks of the form until they wait for the test again
+    fn settle(dom: &mut VirtualDom) {
+        dom.process_events();
+        dom.render_immediate_to_vec();
+    }
+
+    fn submit(dom: &mut VirtualDom) {
+        check(dom, |form| form.submit());
+        settle(dom);
+    }
+
+    fn respond(dom: &mut VirtualDom, pending: &Pending, result: Result<(), SubmitError>) {
+        let (_, sender) = pending.submissions.borrow_mut().remove(0);
+        sender.send(result).unwrap();
+        settle(dom);
+    }
+
+    fn start() -> (VirtualDom, Pending) {
+        let pending = Pending::default();
+        let mut dom = VirtualDom::new_with_props(app, pending.clone());
+        dom.rebuild_in_place();
+        (dom, pending)
+    }
+
+    #[test]
+    fn errors_are_shown_once_the_field_is_touched() {
+        let (dom, _) = start();
+
+        check(&dom, |form| form.set_value("email", "ferris"));
+        check(&dom, |form| {
+            assert!(form.is_dirty("email"));
+            assert!(form.is_form_dirty());
+            assert!(!form.is_valid());
+            // The user is still typing, so the error is hidden
+            assert!(!form.is_touched("email"));
+            assert_eq!(form.error("email"), None);
+        });
+
+        check(&dom, |form| form.touch("email"));
+        assert_eq!(
+            check(&dom, |form| form.error("email")),
+            Some("Enter an email address".to_string())
+        );
+
+        check(&dom, |form| form.set_value("email", "ferris@rust.dev"));
+        check(&dom, |form| {
+            assert_eq!(form.error("email"), None);
+            assert!(form.is_valid());
+        });
+
+        check(&dom, |form| form.reset());
+        check(&dom, |form| {
+            assert_eq!(form.value("email"), "");
+            assert!(!form.is_form_dirty());
+            assert!(!form.is_touched("email"));
+        });
+    }
+
+    #[test]
+    fn async_validators_only_keep_the_latest_value() {
+        let (mut dom, pending) = start();
+
+        check(&dom, |form| form.set_value("name", "a"));
+        settle(&mut dom);
+        assert!(check(&dom, |form| form.is_validating("name")));
+
+        // The new value cancels the validation of the old value
+        check(&dom, |form| form.set_value("name", "ab"));
+        settle(&mut dom);
+        assert_eq!(pending.validations.borrow().len(), 2);
+        assert!(pending.validations.borrow()[0].is_canceled());
+
+        let sender = pending.validations.borrow_mut().pop().unwrap();
+        sender.send(Err("Taken".to_string())).unwrap();
+        settle(&mut dom);
+        check(&dom, |form| {
+            assert!(!form.is_validating("name"));
+            assert!(!form.is_valid());
+        });
+    }
+
+    #[test]
+    fn submit_validates_every_field_first() {
+        let (mut dom, pending) = start();
+
+        // The email was never touched, but submitting shows its error and skips the handler
+        submit(&mut dom);
+        for validation in pending.validations.borrow_mut().drain(..) {
+            validation.send(Ok(())).unwrap();
+        }
+        settle(&mut dom);
+        assert!(pending.submissions.borrow().is_empty());
+        check(&dom, |form| {
+            assert!(form.is_touched("email"));
+            assert_eq!(
+                form.error("email"),
+                Some("Enter an email address".to_string())
+            );
+        });
+    }
+
+    #[test]
+    fn submit_errors_are_mapped_back_to_the_form() {
+        let (mut dom, pending) = start();
+        check(&dom, |form| form.set_value("email", "ferris@rust.dev"));
+
+        // Server side validation errors are shown on their fields
+        submit(&mut dom);
+        // The async validator of the name runs before the handler
+        pending
+            .validations
+            .borrow_mut()
+            .remove(0)
+            .send(Ok(()))
+            .unwrap();
+        settle(&mut dom);
+        assert_eq!(
+            pending.submissions.borrow()[0].0,
+            FormValues::from([
+                ("email".to_string(), "ferris@rust.dev".to_string()),
+                ("name".to_string(), "Ferris".to_string()),
+            ])
+        );
+        assert!(check(&dom, |form| form.is_submitting()));
+        let error: ServerFnError<FieldErrors> =
+            ServerFnError::WrappedServerError(FieldErrors::new().with("email", "Already in use"));
+        respond(&mut dom, &pending, Err(error.into()));
+        check(&dom, |form| {
+            assert!(!form.is_submitting());
+            assert_eq!(form.error("email"), Some("Already in use".to_string()));
+            assert!(form.is_form_dirty());
+        });
+
+        // Other errors belong to the whole form
+        check(&dom, |form| form.set_value("email", "crab@rust.dev"));
+        submit(&mut dom);
+        pending
+            .validations
+            .borrow_mut()
+            .remove(0)
+            .send(Ok(()))
+            .unwrap();
+        settle(&mut dom);
+        respond(
+            &mut dom,
+            &pending,
+            Err(SubmitError::Form("Offline".to_string())),
+        );
+        assert_eq!(
+            check(&dom, |form| form.form_error()),
+            Some("Offline".to_string())
+        );
+
+        // A successful submission clears the form error and makes the values the new initial values
+        submit(&mut dom);
+        pending
+            .validations
+            .borrow_mut()
+            .remove(0)
+            .send(Ok(()))
+            .unwrap();
+        settle(&mut dom);
+        respond(&mut dom, &pending, Ok(()));
+        check(&dom, |form| {
+            assert_eq!(form.form_error(), None);
+            assert!(!form.is_form_dirty());
+        });
+    }
+
+    #[test]
+    fn field_errors_round_trip_through_server_functions() {
+        let errors = FieldErrors::new()
+            .with("email", "Already in use")
+            .with("name", "Too long");
+        let parsed: FieldErrors = errors.to_string().parse().unwrap();
+        assert_eq!(parsed, errors);
+        assert_eq!(parsed.get("name"), Some("Too long"));
+    }
+}
@@ -1,2 +1,4 @@
+pub mod form;
+pub mod query;
 pub mod server_cached;
 pub mod server_future;
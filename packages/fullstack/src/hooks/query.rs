@@ -0,0 +1,677 @@
+//! Cached data fetching with [`use_query`] and [`use_mutation`].
+//!
+//! Queries are cached in a [`QueryClient`] by key. Every component that uses the same key shares the same data and
+//! the query is only fetched once. When a key is invalidated, the query is fetched again and every component that
+//! reads it is updated. In the browser, active queries are also fetched again when the window regains focus or the
+//! network reconnects.
+//!
+//! [`use_server_query`] also fetches the query while the page is rendered on the server and sends the data to the
+//! client with the html, so the client doesn't fetch it again while it hydrates.
+
+use dioxus_lib::prelude::*;
+use dioxus_lib::warnings::Warning;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{any::Any, collections::HashMap, future::Future, rc::Rc};
+
+type Refetch = Rc<dyn Fn()>;
+
+struct QueryEntry {
+    // A `Signal<Option<T>>` owned by the root scope
+    data: Box<dyn Any>,
+    fetching: Signal<bool>,
+    // The query should be fetched again the next time a component uses it
+    stale: bool,
+    // The query was invalidated while it was being fetched, so it is fetched again once the current fetch finishes
+    refetch_pending: bool,
+    // The task that is fetching the query
+    task: Option<Task>,
+    observers: Vec<(usize, Refetch)>,
+}
+
+/// The cache every [`use_query`] and [`use_mutation`] in the app share.
+///
+/// The client is created at the root of the app the first time it is used. You can get it with [`use_query_client`]
+/// to invalidate queries or change their cached data.
+#[derive(Clone, Copy)]
+pub struct QueryClient {
+    entries: CopyValue<HashMap<String, QueryEntry>>,
+    next_observer: CopyValue<usize>,
+    pending_mutations: Signal<usize>,
+}
+
+impl QueryClient {
+    fn new() -> Self {
+        let client = Self {
+            entries: CopyValue::new_in_scope(HashMap::new(), ScopeId::ROOT),
+            next_observer: CopyValue::new_in_scope(0, ScopeId::ROOT),
+            pending_mutations: Signal::new_in_scope(0, ScopeId::ROOT),
+        };
+        #[cfg(feature = "web")]
+        web::refetch_on_focus_and_reconnect(client);
+        client
+    }
+
+    /// Get the cached data for a query without subscribing to it
+    pub fn get_query_data<T: Clone + 'static>(&self, key: &str) -> Option<T> {
+        self.data::<T>(key)?.peek().clone()
+    }
+
+    /// Replace the cached data for a query. Every component that reads the query is updated.
+    ///
+    /// This is useful for optimistic updates. Save the old data with [`QueryClient::get_query_data`] before you
+    /// change it so you can restore it if the mutation fails.
+    pub fn set_query_data<T: 'static>(&self, key: &str, value: T) {
+        self.update_query_data::<T>(key, |data| *data = Some(value));
+    }
+
+    /// Change the cached data for a query in place. Every component that reads the query is updated.
+    pub fn update_query_data<T: 'static>(&self, key: &str, f: impl FnOnce(&mut Option<T>)) {
+        let (mut data, _) = self.entry::<T>(key);
+        f(&mut data.write());
+    }
+
+    /// Mark the query with this key as stale and fetch it again if any component is using it
+    pub fn invalidate(&self, key: &str) {
+        self.invalidate_matching(|entry| entry == key);
+    }
+
+    /// Mark every query with a key that starts with the prefix as stale and fetch the ones that are in use again
+    pub fn invalidate_prefix(&self, prefix: &str) {
+        self.invalidate_matching(|key| key.starts_with(prefix));
+    }
+
+    /// Fetch every query that is in use again
+    pub fn refetch_active(&self) {
+        self.invalidate_matching(|_| true);
+    }
+
+    /// Get the number of mutations that are running. Reading this subscribes to it.
+    pub fn pending_mutations(&self) -> usize {
+        (self.pending_mutations)()
+    }
+
+    fn invalidate_matching(&self, matches: impl Fn(&str) -> bool) {
+        let refetches: Vec<Refetch> = self
+            .entries
+            .write_unchecked()
+            .iter_mut()
+            .filter(|(key, _)| matches(key))
+            .filter_map(|(_, entry)| {
+                entry.stale = true;
+                // The running fetch may have started before the change, so fetch again once it finishes
+                if *entry.fetching.peek() {
+                    entry.refetch_pending = true;
+                    return None;
+                }
+                entry.observers.first().map(|(_, refetch)| refetch.clone())
+            })
+            .collect();
+        for refetch in refetches {
+            refetch();
+        }
+    }
+
+    fn data<T: 'static>(&self, key: &str) -> Option<Signal<Option<T>>> {
+        let entries = self.entries.read_unchecked();
+        let entry = entries.get(key)?;
+        Some(Self::downcast(key, entry))
+    }
+
+    fn entry<T: 'static>(&self, key: &str) -> (Signal<Option<T>>, Signal<bool>) {
+        let mut entries = self.entries.write_unchecked();
+        let entry = entries
+            .entry(key.to_string())
+            .or_insert_with(|| QueryEntry {
+                data: Box::new(Signal::new_in_scope(None::<T>, ScopeId::ROOT)),
+                fetching: Signal::new_in_scope(false, ScopeId::ROOT),
+                stale: true,
+                refetch_pending: false,
+                task: None,
+                observers: Vec::new(),
+            });
+        (Self::downcast(key, entry), entry.fetching)
+    }
+
+    fn downcast<T: 'static>(key: &str, entry: &QueryEntry) -> Signal<Option<T>> {
+        *entry.data.downcast_ref::<Signal<Option<T>>>().unwrap_or_else(|| {
+            panic!(
+                "The query {key:?} was used with two different types. Each key must always return the same type."
+            )
+        })
+    }
+
+    fn observe(&self, key: &str, id: usize, refetch: Refetch) {
+        let stale = {
+            let mut entries = self.entries.write_unchecked();
+            let entry = entries.get_mut(key).expect("the entry was just created");
+            entry.observers.push((id, refetch.clone()));
+            entry.stale
+        };
+        if stale {
+            refetch();
+        }
+    }
+
+    fn unobserve(&self, key: &str, id: usize) {
+        if let Some(entry) = self.entries.write_unchecked().get_mut(key) {
+            entry.observers.retain(|(observer, _)| *observer != id);
+        }
+    }
+
+    fn start_fetch(&self, key: &str, task: Task) {
+        if let Some(entry) = self.entries.write_unchecked().get_mut(key) {
+            entry.task = Some(task);
+        }
+    }
+
+    /// Mark the query as fresh unless it was invalidated during the fetch. Returns true if it should be fetched again
+    fn finish_fetch(&self, key: &str) -> bool {
+        let mut entries = self.entries.write_unchecked();
+        let Some(entry) = entries.get_mut(key) else {
+            return false;
+        };
+        entry.task = None;
+        entry.stale = std::mem::take(&mut entry.refetch_pending);
+        entry.stale
+    }
+
+    /// The task that is fetching the query, if it is being fetched
+    fn fetch_task(&self, key: &str) -> Option<Task> {
+        self.entries.read_unchecked().get(key)?.task
+    }
+
+    /// Fill the cache with data that was fetched on the server so the query isn't fetched again
+    #[cfg(feature = "web")]
+    fn hydrate<T: 'static>(&self, key: &str, value: T) {
+        let (mut data, _) = self.entry::<T>(key);
+        dioxus_lib::signals::warnings::signal_write_in_component_body::allow(|| {
+            data.set(Some(value))
+        });
+        if let Some(entry) = self.entries.write_unchecked().get_mut(key) {
+            entry.stale = false;
+        }
+    }
+}
+
+/// Get the [`QueryClient`] for the app. The client is created at the root of the app the first time it is used.
+pub fn use_query_client() -> QueryClient {
+    use_root_context(QueryClient::new)
+}
+
+/// A cached query returned from [`use_query`]
+pub struct Query<T: 'static> {
+    data: Signal<Option<T>>,
+    fetching: Signal<bool>,
+    refetch: Callback<()>,
+}
+
+impl<T: 'static> Query<T> {
+    /// Get the data of the query. The data is `None` until the query is fetched for the first time.
+    ///
+    /// After the first fetch, the data is kept while the query is fetched again.
+    pub fn data(&self) -> ReadOnlySignal<Option<T>> {
+        self.data.into()
+    }
+
+    /// Check if the query is being fetched
+    pub fn is_fetching(&self) -> bool {
+        (self.fetching)()
+    }
+
+    /// Invalidate the query and fetch it again
+    pub fn refetch(&self) {
+        self.refetch.call(());
+    }
+}
+
+impl<T> Clone for Query<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Query<T> {}
+
+/// Sets the fetching state back to false even if the fetch is cancelled because the component was dropped
+struct FetchGuard {
+    fetching: Signal<bool>,
+}
+
+impl Drop for FetchGuard {
+    fn drop(&mut self) {
+        if let Ok(mut fetching) = self.fetching.try_write() {
+            *fetching = false;
+        }
+    }
+}
+
+/// Fetch data and cache it by key.
+///
+/// Every component that uses the same key shares the cached data and the query is only fetched once. The query is
+/// fetched again when it is invalidated with [`QueryClient::invalidate`]. In the browser, it is also fetched again when
+/// the window regains focus or the network reconnects.
+///
+/// The key may change between renders. Each key must always return the same type.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[server]
+/// async fn get_todos() -> Result<Vec<String>, ServerFnError> {
+///     Ok(vec!["Write docs".to_string()])
+/// }
+///
+/// #[server]
+/// async fn add_todo(todo: String) -> Result<(), ServerFnError> {
+///     Ok(())
+/// }
+///
+/// fn Todos() -> Element {
+///     let client = use_query_client();
+///     let todos = use_query("todos", get_todos);
+///     let add = use_mutation(move |todo: String| async move {
+///         let result = add_todo(todo).await;
+///         client.invalidate("todos");
+///         result
+///     });
+///
+///     rsx! {
+///         button { onclick: move |_| _ = add.mutate("Ship it".to_string()), "Add todo" }
+///         match &*todos.data().read() {
+///             Some(Ok(todos)) => rsx! {
+///                 for todo in todos {
+///                     p { "{todo}" }
+///                 }
+///             },
+///             Some(Err(err)) => rsx! { "Failed to load todos: {err}" },
+///             None => rsx! { "Loading..." },
+///         }
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_query<T, F>(key: impl ToString, fetcher: impl FnMut() -> F + 'static) -> Query<T>
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
+    let client = use_query_client();
+    let key = key.to_string();
+    let (data, fetching) = client.entry::<T>(&key);
+
+    let mut fetcher = fetcher;
+    let fetcher = use_callback(move |_| fetcher());
+    let scope = current_scope_id().expect("use_query must be called in a component");
+    let observer = use_hook(|| {
+        let mut next = client.next_observer;
+        let id = *next.peek();
+        next.set(id + 1);
+        id
+    });
+
+    // Register with the entry for the current key. If the key changed, stop observing the old key
+    let mut current_key = use_hook(|| CopyValue::new(None::<String>));
+    if current_key.peek().as_deref() != Some(key.as_str()) {
+        if let Some(old) = current_key.write().replace(key.clone()) {
+            client.unobserve(&old, observer);
+        }
+
+        let key = key.clone();
+        let refetch: Refetch = Rc::new(move || {
+            let mut data = data;
+            let mut fetching = fetching;
+            // Only fetch each query once at a time
+            if *fetching.peek() {
+                return;
+            }
+            // The first fetch starts while the component that uses the query renders
+            dioxus_lib::signals::warnings::signal_write_in_component_body::allow(|| {
+                fetching.set(true)
+            });
+            let future = scope.in_runtime(|| fetcher.call(()));
+            let task = scope.push_future({
+                let key = key.clone();
+                async move {
+                    let guard = FetchGuard { fetching };
+                    let value = future.await;
+                    data.set(Some(value));
+                    drop(guard);
+                    if client.finish_fetch(&key) {
+                        client.invalidate(&key);
+                    }
+                }
+            });
+            if let Some(task) = task {
+                client.start_fetch(&key, task);
+            }
+        });
+        client.observe(current_key.peek().as_deref().unwrap(), observer, refetch);
+    }
+
+    use_drop(move || {
+        if let Some(key) = current_key.peek().as_deref() {
+            client.unobserve(key, observer);
+        }
+    });
+
+    let refetch = use_callback(move |_| {
+        if let Some(key) = current_key.peek().as_deref() {
+            client.invalidate(key);
+        }
+    });
+
+    Query {
+        data,
+        fetching,
+        refetch,
+    }
+}
+
+/// Fetch data and cache it by key like [`use_query`], and render the data on the server.
+///
+/// On the server, the component suspends until the query is fetched for the first time and the data is serialized
+/// into the page. The client fills the cache with that data instead of fetching the query again while it hydrates.
+/// Like [`use_server_future`](crate::prelude::use_server_future), bubble the suspense up with `?`.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[server]
+/// async fn get_todos() -> Result<Vec<String>, ServerFnError> {
+///     Ok(vec!["Write docs".to_string()])
+/// }
+///
+/// fn Todos() -> Element {
+///     let todos = use_server_query("todos", get_todos)?;
+///
+///     rsx! {
+///         if let Some(Ok(todos)) = &*todos.data().read() {
+///             for todo in todos {
+///                 p { "{todo}" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_server_query<T, F>(
+    key: impl ToString,
+    fetcher: impl FnMut() -> F + 'static,
+) -> Result<Query<T>, RenderError>
+where
+    T: Serialize + DeserializeOwned + 'static,
+    F: Future<Output = T> + 'static,
+{
+    let client = use_query_client();
+    let key = key.to_string();
+
+    // Reserve a slot for the data on the first render so the client takes the data in the same order
+    #[cfg(feature = "server")]
+    let serialize_context = crate::html_storage::use_serialize_context();
+    #[cfg(feature = "server")]
+    let server_storage_entry = use_hook(|| serialize_context.create_entry());
+    #[cfg(feature = "server")]
+    let caller = std::panic::Location::caller();
+
+    // If the server fetched the query, the client starts with its data
+    #[cfg(feature = "web")]
+    use_hook(|| {
+        if let Ok(Some(value)) = dioxus_web::take_server_data::<T>() {
+            client.hydrate(&key, value);
+        }
+    });
+
+    let query = use_query(key.clone(), fetcher);
+
+    // Serialize the data into the page once the first fetch finishes
+    #[cfg(feature = "server")]
+    {
+        let mut serialized = use_hook(|| CopyValue::new(false));
+        if !*serialized.peek() {
+            if let Some(value) = &*query.data.peek() {
+                serialize_context.insert(server_storage_entry, value, caller);
+                serialized.set(true);
+            }
+        }
+    }
+
+    // Suspend until the query has data
+    if query.data.peek().is_none() {
+        if let Some(task) = client.fetch_task(&key) {
+            return Err(suspend(task).unwrap_err());
+        }
+    }
+
+    Ok(query)
+}
+
+/// The state of a [`Mutation`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum MutationState<T> {
+    /// The mutation has not been started
+    Idle,
+    /// The mutation is running
+    Pending,
+    /// The mutation finished with this value
+    Done(T),
+}
+
+/// A mutation returned from [`use_mutation`]
+pub struct Mutation<A: 'static, T: 'static> {
+    state: Signal<MutationState<T>>,
+    mutate: Callback<A, Task>,
+}
+
+impl<A: 'static, T: 'static> Mutation<A, T> {
+    /// Start the mutation with an argument. If the component is dropped before the mutation finishes, it is cancelled.
+    pub fn mutate(&self, argument: A) -> Task {
+        self.mutate.call(argument)
+    }
+
+    /// Get the state of the latest mutation
+    pub fn state(&self) -> ReadOnlySignal<MutationState<T>> {
+        self.state.into()
+    }
+
+    /// Check if the mutation is running
+    pub fn is_pending(&self) -> bool {
+        matches!(*self.state.read(), MutationState::Pending)
+    }
+}
+
+impl<A, T> Clone for Mutation<A, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, T> Copy for Mutation<A, T> {}
+
+/// Decrements the number of pending mutations even if the mutation is cancelled
+struct MutationGuard {
+    client: QueryClient,
+}
+
+impl Drop for MutationGuard {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = self.client.pending_mutations.try_write() {
+            *pending -= 1;
+        }
+    }
+}
+
+/// Create a mutation that changes data on the server.
+///
+/// Mutations run when you call [`Mutation::mutate`]. Invalidate the queries the mutation changes with
+/// [`QueryClient::invalidate`] or update them optimistically with [`QueryClient::set_query_data`].
+///
+/// Any component can observe the number of running mutations with [`QueryClient::pending_mutations`].
+#[track_caller]
+pub fn use_mutation<A, T, F>(mut mutator: impl FnMut(A) -> F + 'static) -> Mutation<A, T>
+where
+    A: 'static,
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
+    let client = use_query_client();
+    let mut state = use_signal(|| MutationState::Idle);
+    let mutate = use_callback(move |argument: A| {
+        state.set(MutationState::Pending);
+        let mut pending = client.pending_mutations;
+        *pending.write() += 1;
+        let future = mutator(argument);
+        spawn(async move {
+            let _guard = MutationGuard { client };
+            let value = future.await;
+            state.set(MutationState::Done(value));
+        })
+    });
+
+    Mutation { state, mutate }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::QueryClient;
+    use dioxus_lib::prelude::{Runtime, RuntimeGuard};
+    use web_sys::wasm_bindgen::{closure::Closure, JsCast};
+
+    /// Fetch every active query again when the window regains focus or the network reconnects
+    pub(super) fn refetch_on_focus_and_reconnect(client: QueryClient) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let Ok(runtime) = Runtime::current() else {
+            return;
+        };
+        let callback = Closure::<dyn FnMut()>::new(move || {
+            let _guard = RuntimeGuard::new(runtime.clone());
+            client.refetch_active();
+        });
+        for event in ["focus", "online"] {
+            _ = window.add_event_listener_with_callback(event, callback.as_ref().unchecked_ref());
+        }
+        callback.forget();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_channel::oneshot;
+    use std::cell::RefCell;
+
+    /// The fetches that were started and are waiting for the test to resolve them
+    #[derive(Clone, Default)]
+    struct Fetches(Rc<RefCell<Vec<oneshot::Sender<i32>>>>);
+
+    impl PartialEq for Fetches {
+        fn eq(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.0, &other.0)
+        }
+    }
+
+    fn app(fetches: Fetches) -> Element {
+        rsx! {
+            Count { fetches: fetches.clone() }
+            Count { fetches }
+        }
+    }
+
+    #[component]
+    fn Count(fetches: Fetches) -> Element {
+        let count = use_query("count", move || {
+            let (sender, receiver) = oneshot::channel();
+            fetches.0.borrow_mut().push(sender);
+            async move { receiver.await.unwrap() }
+        });
+        rsx! { "{count.data():?}" }
+    }
+
+    fn client(dom: &VirtualDom) -> QueryClient {
+        dom.in_runtime(|| ScopeId::ROOT.consume_context::<QueryClient>().unwrap())
+    }
+
+    fn cached(dom: &VirtualDom) -> Option<i32> {
+        dom.in_runtime(|| client(dom).get_query_data::<i32>("count"))
+    }
+
+    /// Resolve the oldest fetch that is still running and render the new data
+    async fn resolve(dom: &mut VirtualDom, fetches: &Fetches, value: i32) {
+        let sender = fetches.0.borrow_mut().remove(0);
+        sender.send(value).unwrap();
+        dom.wait_for_work().await;
+        dom.render_immediate_to_vec();
+    }
+
+    #[tokio::test]
+    async fn components_share_one_fetch() {
+        let fetches = Fetches::default();
+        let mut dom = VirtualDom::new_with_props(app, fetches.clone());
+        dom.rebuild_in_place();
+
+        // Both components use the same key, so the query is only fetched once
+        assert_eq!(fetches.0.borrow().len(), 1);
+        assert_eq!(cached(&dom), None);
+
+        resolve(&mut dom, &fetches, 1).await;
+        assert_eq!(cached(&dom), Some(1));
+        assert!(fetches.0.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn invalidating_fetches_the_query_again() {
+        let fetches = Fetches::default();
+        let mut dom = VirtualDom::new_with_props(app, fetches.clone());
+        dom.rebuild_in_place();
+        resolve(&mut dom, &fetches, 1).await;
+
+        // Invalidating a fresh query fetches it again and keeps the old data until the new data arrives
+        dom.in_runtime(|| client(&dom).invalidate("count"));
+        assert_eq!(fetches.0.borrow().len(), 1);
+        assert_eq!(cached(&dom), Some(1));
+
+        resolve(&mut dom, &fetches, 2).await;
+        assert_eq!(cached(&dom), Some(2));
+
+        // Keys that don't match aren't fetched again
+        dom.in_runtime(|| client(&dom).invalidate("other"));
+        dom.in_runtime(|| client(&dom).invalidate_prefix("cou"));
+        assert_eq!(fetches.0.borrow().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn invalidating_during_a_fetch_fetches_once_more() {
+        let fetches = Fetches::default();
+        let mut dom = VirtualDom::new_with_props(app, fetches.clone());
+        dom.rebuild_in_place();
+
+        // The invalidations during the first fetch don't start more fetches
+        dom.in_runtime(|| client(&dom).invalidate("count"));
+        dom.in_runtime(|| client(&dom).invalidate("count"));
+        assert_eq!(fetches.0.borrow().len(), 1);
+
+        // The first fetch may be outdated, so the query is fetched again once it finishes
+        resolve(&mut dom, &fetches, 1).await;
+        assert_eq!(cached(&dom), Some(1));
+        assert_eq!(fetches.0.borrow().len(), 1);
+
+        resolve(&mut dom, &fetches, 2).await;
+        assert_eq!(cached(&dom), Some(2));
+        assert!(fetches.0.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_query_data_updates_the_cache() {
+        let fetches = Fetches::default();
+        let mut dom = VirtualDom::new_with_props(app, fetches.clone());
+        dom.rebuild_in_place();
+        resolve(&mut dom, &fetches, 1).await;
+
+        // Optimistic updates change the data without fetching the query
+        dom.in_runtime(|| {
+            client(&dom)
+                .update_query_data::<i32>("count", |data| *data = data.map(|count| count + 1))
+        });
+        assert_eq!(cached(&dom), Some(2));
+        assert!(fetches.0.borrow().is_empty());
+    }
+}
@@ -72,12 +72,18 @@ impl super::HTMLData {
     }
 
     #[cfg(feature = "server")]
-    /// Encode data as base64. This is intended to be used in the server to send data to the client.
-    pub(crate) fn serialized(&self) -> SerializedHydrationData {
+    /// Encode data as a base64 hydration payload. This is intended to be used in the server to send data to the client.
+    /// The payload is compressed if it is large and includes a checksum the client verifies before it hydrates.
+    pub(crate) fn serialized(
+        &self,
+        compression: dioxus_interpreter_js::HydrationCompression,
+    ) -> SerializedHydrationData {
         let mut serialized = Vec::new();
         ciborium::into_writer(&self.data, &mut serialized).unwrap();
+        let payload =
+            dioxus_interpreter_js::encode_hydration_payload_with(&serialized, compression);
 
-        let data = base64::engine::general_purpose::STANDARD.encode(serialized);
+        let data = base64::engine::general_purpose::STANDARD.encode(payload);
 
         let format_js_list_of_strings = |list: &[Option<String>]| {
             let body = list
@@ -105,7 +111,7 @@ impl super::HTMLData {
 /// Data that was serialized on the server for hydration on the client. This includes
 /// extra information about the types and sources of the serialized data in debug mode
 pub(crate) struct SerializedHydrationData {
-    /// The base64 encoded serialized data
+    /// The base64 encoded hydration payload
     pub data: String,
     /// A list of the types of each serialized data
     #[cfg(debug_assertions)]
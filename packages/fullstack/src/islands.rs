@@ -0,0 +1,369 @@
+//! Islands let you ship interactivity for only part of a page.
+//!
+//! Components marked with `#[island]` are rendered in their own virtual dom on the server and written into the
+//! page inside a `<div data-island>` mount point. The mount point holds the serialized props and hydration data
+//! for the island. Everything outside of an island is static html that is never hydrated.
+//!
+//! On the client, `launch_islands` finds every island mount point on the page and hydrates it with the
+//! component registered under the same name in an `IslandRegistry`.
+//!
+//! Each island can defer hydration with a [`HydrationStrategy`] like `#[island(hydrate = "visible")]`. The
+//! island's virtual dom is not created until the trigger fires, which keeps long content pages interactive sooner.
+
+use dioxus_lib::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+// The `#[island]` macro derives the serde traits through this path so apps don't need to depend on serde
+#[doc(hidden)]
+pub use serde as __serde;
+
+/// The css selector that matches the mount point of every island on the page
+pub const ISLAND_SELECTOR: &str = "[data-island]";
+
+/// When the client should hydrate an island
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HydrationStrategy {
+    /// Hydrate the island as soon as the page loads. This is the default.
+    #[default]
+    Load,
+    /// Hydrate the island the first time it scrolls into the viewport
+    Visible,
+    /// Hydrate the island once the browser is idle
+    Idle,
+    /// Hydrate the island the first time the user hovers, focuses or touches it. The event that triggers
+    /// hydration is not replayed into the island.
+    Interaction,
+}
+
+impl HydrationStrategy {
+    /// The value of the `data-island-hydrate` attribute for this strategy
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HydrationStrategy::Load => "load",
+            HydrationStrategy::Visible => "visible",
+            HydrationStrategy::Idle => "idle",
+            HydrationStrategy::Interaction => "interaction",
+        }
+    }
+
+    /// Parse a strategy from the value of the `data-island-hydrate` attribute
+    pub fn from_attribute(value: &str) -> Option<Self> {
+        match value {
+            "load" => Some(HydrationStrategy::Load),
+            "visible" => Some(HydrationStrategy::Visible),
+            "idle" => Some(HydrationStrategy::Idle),
+            "interaction" => Some(HydrationStrategy::Interaction),
+            _ => None,
+        }
+    }
+}
+
+/// Marker context provided to the root of every island virtual dom. Islands rendered inside of another island are
+/// rendered inline as part of their parent.
+#[cfg(any(feature = "server", feature = "web"))]
+#[derive(Clone, Copy)]
+struct InsideIsland;
+
+/// Render an island. This is called by the code generated by the `#[island]` macro and should not be called directly.
+#[doc(hidden)]
+pub fn island<P, M>(
+    name: &'static str,
+    strategy: HydrationStrategy,
+    props: P,
+    component: impl ComponentFunction<P, M> + Send,
+    body: impl FnOnce() -> Element,
+) -> Element
+where
+    P: Properties + Serialize + DeserializeOwned,
+    M: 'static,
+{
+    #[cfg(feature = "server")]
+    if try_consume_context::<InsideIsland>().is_none() {
+        return server::render_island(name, strategy, props, component);
+    }
+
+    #[cfg(not(feature = "server"))]
+    let _ = (name, strategy, props, component);
+
+    body()
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use super::*;
+    use base64::Engine;
+
+    /// Render the island in its own virtual dom so the hydration ids start at zero like they will on the client.
+    ///
+    /// The island is rendered in a task of its own once all of its suspense has resolved, so the page gets the content
+    /// and hydration data of its server futures instead of their fallbacks. The component around the island is
+    /// suspended until then.
+    pub(super) fn render_island<P, M>(
+        name: &'static str,
+        strategy: HydrationStrategy,
+        props: P,
+        component: impl ComponentFunction<P, M> + Send,
+    ) -> Element
+    where
+        P: Properties + Serialize + DeserializeOwned,
+        M: 'static,
+    {
+        let props = use_hook(|| {
+            let mut serialized_props = Vec::new();
+            if let Err(err) = ciborium::into_writer(&props, &mut serialized_props) {
+                tracing::error!("Failed to serialize the props for island {name}: {err}");
+            }
+            serialized_props
+        });
+        let serialized_props = base64::engine::general_purpose::STANDARD.encode(&props);
+        let server_context = crate::server_context::server_context();
+
+        let island = use_resource(move || {
+            let props = props.clone();
+            let component = component.clone();
+            let server_context = server_context.clone();
+            async move {
+                // Islands don't share the runtime of the page, so they can't run in one of its tasks
+                let rendered = crate::render::spawn_platform(move || async move {
+                    let props: P = ciborium::from_reader(props.as_slice())
+                        .inspect_err(|err| {
+                            tracing::error!(
+                                "Failed to deserialize the props for island {name}: {err}"
+                            )
+                        })
+                        .ok()?;
+                    let mut dom = VirtualDom::new_with_props(component, props);
+                    dom.provide_root_context(InsideIsland);
+                    crate::server_context::with_server_context(server_context.clone(), || {
+                        dom.rebuild_in_place()
+                    });
+                    crate::server_context::ProvideServerContext::new(
+                        dom.wait_for_suspense(),
+                        server_context,
+                    )
+                    .await;
+
+                    let html = dioxus_ssr::pre_render(&dom);
+                    let data = crate::render::serialize_server_data(
+                        &dom,
+                        ScopeId::ROOT,
+                        crate::HydrationCompression::default(),
+                    )
+                    .data;
+                    Some((html, data))
+                });
+                rendered.await.ok().flatten()
+            }
+        })
+        .suspend()?;
+
+        let Some((html, data)) = island.cloned() else {
+            return VNode::empty();
+        };
+        rsx! {
+            div {
+                style: "display: contents",
+                "data-island": name,
+                "data-island-hydrate": strategy.as_str(),
+                "data-island-props": serialized_props,
+                "data-island-data": data,
+                dangerous_inner_html: html,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+pub use web::*;
+
+#[cfg(feature = "web")]
+mod web {
+    use super::*;
+    use base64::Engine;
+    use std::{cell::RefCell, collections::HashMap, rc::Rc};
+    use web_sys::js_sys;
+    use web_sys::wasm_bindgen::{closure::Closure, JsCast};
+
+    type IslandConstructor = Rc<dyn Fn(&[u8]) -> Option<VirtualDom>>;
+
+    /// A list of the islands the client knows how to hydrate
+    #[derive(Default)]
+    pub struct IslandRegistry {
+        islands: HashMap<&'static str, IslandConstructor>,
+    }
+
+    impl IslandRegistry {
+        /// Create a new empty registry
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Register an island component. The name must match the name of the function marked with `#[island]`.
+        pub fn register<P, M>(
+            mut self,
+            name: &'static str,
+            component: impl ComponentFunction<P, M>,
+        ) -> Self
+        where
+            P: Properties + Serialize + DeserializeOwned,
+            M: 'static,
+        {
+            self.islands.insert(
+                name,
+                Rc::new(move |props| {
+                    let props: P = match ciborium::from_reader(std::io::Cursor::new(props)) {
+                        Ok(props) => props,
+                        Err(err) => {
+                            tracing::error!(
+                                "Failed to deserialize the props for island {name}: {err}"
+                            );
+                            return None;
+                        }
+                    };
+                    let dom = VirtualDom::new_with_props(component.clone(), props);
+                    dom.provide_root_context(InsideIsland);
+                    Some(dom)
+                }),
+            );
+            self
+        }
+    }
+
+    /// Hydrate every island on the page with the components in the registry.
+    ///
+    /// Each island gets its own virtual dom mounted to its `<div data-island>` element. Islands that are not in the
+    /// registry are left as static html. Islands with a deferred [`HydrationStrategy`] are hydrated once their trigger fires.
+    pub fn launch_islands(registry: IslandRegistry) {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .expect("islands can only be launched in the browser");
+        let Ok(elements) = document.query_selector_all(ISLAND_SELECTOR) else {
+            return;
+        };
+
+        for i in 0..elements.length() {
+            let Some(element) = elements
+                .get(i)
+                .and_then(|node| node.dyn_into::<web_sys::Element>().ok())
+            else {
+                continue;
+            };
+            let Some(name) = element.get_attribute("data-island") else {
+                continue;
+            };
+            let Some(constructor) = registry.islands.get(name.as_str()).cloned() else {
+                tracing::warn!("Island {name} was rendered on the server but it is not registered on the client. It will not be hydrated.");
+                continue;
+            };
+            let strategy = element
+                .get_attribute("data-island-hydrate")
+                .and_then(|value| HydrationStrategy::from_attribute(&value))
+                .unwrap_or_default();
+
+            let hydrate = {
+                let element = element.clone();
+                move || hydrate_island(element, constructor)
+            };
+            schedule_hydration(&element, strategy, hydrate);
+        }
+    }
+
+    /// Create the virtual dom for an island and hydrate it
+    fn hydrate_island(element: web_sys::Element, constructor: IslandConstructor) {
+        let decode = |attribute: &str| {
+            element
+                .get_attribute(attribute)
+                .and_then(|value| base64::engine::general_purpose::STANDARD.decode(value).ok())
+                .unwrap_or_default()
+        };
+        let props = decode("data-island-props");
+        let data = decode("data-island-data");
+
+        let Some(dom) = constructor(&props) else {
+            return;
+        };
+        let cfg = dioxus_web::Config::new()
+            .rootelement(element)
+            .hydrate(true)
+            .hydration_data(data);
+        wasm_bindgen_futures::spawn_local(async move {
+            dioxus_web::run(dom, cfg).await;
+        });
+    }
+
+    /// Run `hydrate` once the trigger for the strategy fires
+    fn schedule_hydration(
+        element: &web_sys::Element,
+        strategy: HydrationStrategy,
+        hydrate: impl FnOnce() + 'static,
+    ) {
+        // Every trigger may fire more than once, but the island should only be hydrated the first time
+        let hydrate = RefCell::new(Some(hydrate));
+        let run_once: Rc<dyn Fn()> = Rc::new(move || {
+            if let Some(hydrate) = hydrate.borrow_mut().take() {
+                hydrate();
+            }
+        });
+
+        match strategy {
+            HydrationStrategy::Load => run_once(),
+            HydrationStrategy::Visible => {
+                let callback =
+                    Closure::<dyn FnMut(js_sys::Array, web_sys::IntersectionObserver)>::new({
+                        let run_once = run_once.clone();
+                        move |entries: js_sys::Array, observer: web_sys::IntersectionObserver| {
+                            let visible = entries.iter().any(|entry| {
+                                entry
+                                    .unchecked_into::<web_sys::IntersectionObserverEntry>()
+                                    .is_intersecting()
+                            });
+                            if visible {
+                                observer.disconnect();
+                                run_once();
+                            }
+                        }
+                    });
+                let Ok(observer) =
+                    web_sys::IntersectionObserver::new(callback.as_ref().unchecked_ref())
+                else {
+                    // Browsers without IntersectionObserver hydrate immediately
+                    run_once();
+                    return;
+                };
+                // The island root uses `display: contents` so it has no box of its own. Observe the children instead
+                let children = element.children();
+                if children.length() == 0 {
+                    observer.observe(element);
+                }
+                for i in 0..children.length() {
+                    if let Some(child) = children.item(i) {
+                        observer.observe(&child);
+                    }
+                }
+                callback.forget();
+            }
+            HydrationStrategy::Idle => {
+                let callback = Closure::once_into_js(move || run_once());
+                let window = web_sys::window().unwrap();
+                // Safari doesn't support requestIdleCallback, fall back to a timeout
+                if window
+                    .request_idle_callback(callback.unchecked_ref())
+                    .is_err()
+                {
+                    _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                        callback.unchecked_ref(),
+                        200,
+                    );
+                }
+            }
+            HydrationStrategy::Interaction => {
+                let callback = Closure::<dyn FnMut()>::new(move || run_once());
+                for event in ["pointerover", "focusin", "touchstart"] {
+                    _ = element
+                        .add_event_listener_with_callback(event, callback.as_ref().unchecked_ref());
+                }
+                callback.forget();
+            }
+        }
+    }
+}
@@ -14,7 +14,21 @@ pub mod server;
 
 mod hooks;
 
+#[cfg(feature = "auth")]
+#[cfg_attr(docsrs, doc(cfg(feature = "auth")))]
+pub mod auth;
+#[cfg(feature = "crash-reports")]
+#[cfg_attr(docsrs, doc(cfg(feature = "crash-reports")))]
+pub mod crash_reports;
 pub mod document;
+pub mod islands;
+#[cfg(feature = "server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "server")))]
+pub mod limits;
+pub mod push;
+#[cfg(feature = "axum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub mod rate_limit;
 #[cfg(feature = "server")]
 mod render;
 #[cfg(feature = "server")]
@@ -28,10 +42,22 @@ pub use serve_config::*;
 #[cfg(feature = "server")]
 mod server_context;
 
+// Shared state and peer connections are used on the client and the server, so these modules aren't gated
+pub mod rtc;
+pub mod sync;
+
 /// A prelude of commonly used items in dioxus-fullstack.
 pub mod prelude {
     use crate::hooks;
-    pub use hooks::{server_cached::use_server_cached, server_future::use_server_future};
+    pub use hooks::{
+        form::{use_form, Field, FieldErrors, Form, FormValues, SubmitError, UseForm},
+        query::{
+            use_mutation, use_query, use_query_client, use_server_query, Mutation, MutationState,
+            Query, QueryClient,
+        },
+        server_cached::use_server_cached,
+        server_future::use_server_future,
+    };
 
     #[cfg(feature = "axum")]
     #[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
@@ -0,0 +1,252 @@
+//! Limits for the requests of server functions.
+//!
+//! Every server function reads at most [`DEFAULT_BODY_LIMIT`] bytes of the request body and runs as long as it needs
+//! to. Change the defaults for every server function with [`ServerFnLimits`] when the server functions are registered:
+//!
+//! ```rust, ignore
+//! let router = axum::Router::new()
+//!     .register_server_functions_with_limits(
+//!         Default::default(),
+//!         ServerFnLimits::new()
+//!             .with_body_limit(512 * 1024)
+//!             .with_timeout(Duration::from_secs(10)),
+//!     );
+//! ```
+//!
+//! Or override them for one server function with the `#[body_limit]` and `#[timeout]` attributes:
+//!
+//! ```rust, ignore
+//! #[server]
+//! #[body_limit("50MB")]
+//! #[timeout("5m")]
+//! async fn upload_video(video: Vec<u8>) -> Result<(), ServerFnError> {
+//!     storage::save(video).await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Requests with a larger body are rejected with `413 Payload Too Large`, even if the body is streamed without a
+//! length, and server functions that run too long are cancelled with `408 Request Timeout`. The client reads both back
+//! from the [`ServerFnError`] with [`LimitExceeded::from_error`].
+
+use http::{Response, StatusCode};
+use parking_lot::Mutex;
+use server_fn::{
+    error::{NoCustomError, ServerFnErrorSerde},
+    ServerFnError,
+};
+use std::{cell::Cell, collections::HashMap, sync::OnceLock, time::Duration};
+
+/// The number of bytes server functions read from the request body by default. This is the same as the default limit
+/// of axum extractors.
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+const PAYLOAD_TOO_LARGE: &str = "payload too large, the limit is ";
+const TIMED_OUT: &str = "timed out after ";
+
+/// The limits of the requests to server functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerFnLimits {
+    body_limit: Option<usize>,
+    timeout: Option<Duration>,
+}
+
+impl Default for ServerFnLimits {
+    fn default() -> Self {
+        Self {
+            body_limit: Some(DEFAULT_BODY_LIMIT),
+            timeout: None,
+        }
+    }
+}
+
+impl ServerFnLimits {
+    /// Create the default limits: a body of up to [`DEFAULT_BODY_LIMIT`] bytes and no timeout
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of bytes in the request body
+    pub fn with_body_limit(mut self, bytes: usize) -> Self {
+        self.body_limit = Some(bytes);
+        self
+    }
+
+    /// Read request bodies of any size
+    pub fn without_body_limit(mut self) -> Self {
+        self.body_limit = None;
+        self
+    }
+
+    /// Cancel server functions that run longer than the timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The maximum number of bytes in the request body
+    pub fn body_limit(&self) -> Option<usize> {
+        self.body_limit
+    }
+
+    /// How long server functions can run
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Apply the overrides of the attributes of a server function
+    pub(crate) fn for_server_fn(mut self, path: &str) -> Self {
+        if let Some(overrides) = overrides().lock().get(path) {
+            if let Some(body_limit) = overrides.body_limit {
+                self.body_limit = Some(body_limit);
+            }
+            if let Some(timeout) = overrides.timeout {
+                self.timeout = Some(timeout);
+            }
+        }
+        self
+    }
+}
+
+#[derive(Default)]
+struct Overrides {
+    body_limit: Option<usize>,
+    timeout: Option<Duration>,
+}
+
+fn overrides() -> &'static Mutex<HashMap<&'static str, Overrides>> {
+    static OVERRIDES: OnceLock<Mutex<HashMap<&'static str, Overrides>>> = OnceLock::new();
+    OVERRIDES.get_or_init(Default::default)
+}
+
+thread_local! {
+    /// The path of the server function whose middleware is being built while it is registered
+    static REGISTERING: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Build the middleware of a server function to collect the limits its attributes set
+pub(crate) fn discover(path: &'static str, build_service: impl FnOnce()) {
+    REGISTERING.with(|current| current.set(Some(path)));
+    build_service();
+    REGISTERING.with(|current| current.set(None));
+}
+
+/// Get the path of the server function whose middleware is being built
+pub(crate) fn registering_server_fn() -> Option<&'static str> {
+    REGISTERING.with(|current| current.get())
+}
+
+fn record(set: impl FnOnce(&mut Overrides)) {
+    if let Some(path) = registering_server_fn() {
+        set(overrides().lock().entry(path).or_default());
+    }
+}
+
+/// Override the body limit of a server function. Add it with the `#[body_limit("10MB")]` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimit(usize);
+
+impl BodyLimit {
+    /// Allow request bodies of up to this many bytes
+    pub fn new(bytes: usize) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<S> tower_layer::Layer<S> for BodyLimit {
+    type Service = S;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        // The limit is enforced by the handler before the body is read, so the layer only records it
+        record(|overrides| overrides.body_limit = Some(self.0));
+        inner
+    }
+}
+
+/// Override the timeout of a server function. Add it with the `#[timeout("30s")]` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout(Duration);
+
+impl Timeout {
+    /// Cancel the server function if it runs longer than the timeout
+    pub fn new(timeout: Duration) -> Self {
+        Self(timeout)
+    }
+}
+
+impl<S> tower_layer::Layer<S> for Timeout {
+    type Service = S;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        record(|overrides| overrides.timeout = Some(self.0));
+        inner
+    }
+}
+
+/// A request that was rejected because it went over the limits of the server function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// The request body was larger than the body limit
+    PayloadTooLarge {
+        /// The maximum number of bytes in the request body
+        limit: usize,
+    },
+    /// The server function ran longer than the timeout
+    TimedOut {
+        /// How long the server function ran before it was cancelled
+        after: Duration,
+    },
+}
+
+impl LimitExceeded {
+    /// Check if a server function failed because the request went over its limits
+    pub fn from_error<E>(error: &ServerFnError<E>) -> Option<Self> {
+        let ServerFnError::ServerError(message) = error else {
+            return None;
+        };
+        if let Some(limit) = message.strip_prefix(PAYLOAD_TOO_LARGE) {
+            let limit = limit.strip_suffix(" bytes")?.parse().ok()?;
+            return Some(Self::PayloadTooLarge { limit });
+        }
+        let millis = message.strip_prefix(TIMED_OUT)?.strip_suffix("ms")?;
+        Some(Self::TimedOut {
+            after: Duration::from_millis(millis.parse().ok()?),
+        })
+    }
+
+    pub(crate) fn into_response<B: From<String>>(self) -> Response<B> {
+        let (status, message) = match self {
+            LimitExceeded::PayloadTooLarge { limit } => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("{PAYLOAD_TOO_LARGE}{limit} bytes"),
+            ),
+            LimitExceeded::TimedOut { after } => (
+                StatusCode::REQUEST_TIMEOUT,
+                format!("{TIMED_OUT}{}ms", after.as_millis()),
+            ),
+        };
+        let error = ServerFnError::<NoCustomError>::ServerError(message);
+        let mut response = Response::new(B::from(error.ser().unwrap_or_default()));
+        *response.status_mut() = status;
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_decodes_rejections() {
+        for rejection in [
+            LimitExceeded::PayloadTooLarge { limit: 1024 },
+            LimitExceeded::TimedOut {
+                after: Duration::from_millis(1500),
+            },
+        ] {
+            let response = rejection.into_response::<String>();
+            let error = ServerFnError::<NoCustomError>::de(response.body());
+            assert_eq!(LimitExceeded::from_error(&error), Some(rejection));
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Web push: subscribe the browser to push messages and send them from the server.
+//!
+//! The browser subscribes with the public [VAPID](https://datatracker.ietf.org/doc/html/rfc8292) key of the server and
+//! hands the [`PushSubscription`] to the server, usually through a server function. The server stores the
+//! subscription and sends messages to it with [`WebPush`] when the `web-push` feature is enabled. Push messages are
+//! delivered to the service worker even when the app is closed, so the app needs one: `dx build --service-worker`
+//! generates a worker that shows [`PushNotification`] payloads as notifications.
+//!
+//! ```rust, ignore
+//! #[server]
+//! async fn save_subscription(subscription: PushSubscription) -> Result<(), ServerFnError> {
+//!     db::subscriptions().insert(subscription).await?;
+//!     Ok(())
+//! }
+//!
+//! fn EnablePush() -> Element {
+//!     rsx! {
+//!         button {
+//!             onclick: move |_| async move {
+//!                 if let Ok(subscription) = subscribe_push(VAPID_PUBLIC_KEY).await {
+//!                     _ = save_subscription(subscription).await;
+//!                 }
+//!             },
+//!             "Notify me about new messages"
+//!         }
+//!     }
+//! }
+//! ```
+
+use dioxus_lib::document::{document, EvalError};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt::Display;
+
+#[cfg(feature = "web-push")]
+mod web_push;
+#[cfg(feature = "web-push")]
+#[cfg_attr(docsrs, doc(cfg(feature = "web-push")))]
+pub use web_push::*;
+
+/// Finds the service worker and converts the outcome into something serde can read as a `Result`
+const PRELUDE: &str = r#"
+const pushError = (e) => ({ name: (e && e.name) || "Error", message: String((e && e.message) || e) });
+const pushRegistration = async () => {
+    if (!("serviceWorker" in navigator) || typeof PushManager === "undefined") {
+        throw { name: "NotSupportedError", message: "push messages are not supported" };
+    }
+    if (!(await navigator.serviceWorker.getRegistration())) {
+        throw { name: "NoServiceWorker", message: "the app didn't register a service worker" };
+    }
+    return await navigator.serviceWorker.ready;
+};
+const pushResult = async (action) => {
+    try {
+        return { Ok: await action() };
+    } catch (e) {
+        return { Err: pushError(e) };
+    }
+};
+"#;
+
+const SUBSCRIBE: &str = r#"
+const key = await dioxus.recv();
+const registration = await pushRegistration();
+const decoded = atob(key.replace(/-/g, "+").replace(/_/g, "/") + "=".repeat((4 - (key.length % 4)) % 4));
+const subscription = await registration.pushManager.subscribe({
+    userVisibleOnly: true,
+    applicationServerKey: Uint8Array.from(decoded, (c) => c.charCodeAt(0)),
+});
+return subscription.toJSON();
+"#;
+
+const SUBSCRIPTION: &str = r#"
+const registration = await pushRegistration();
+const subscription = await registration.pushManager.getSubscription();
+return subscription && subscription.toJSON();
+"#;
+
+const UNSUBSCRIBE: &str = r#"
+const registration = await pushRegistration();
+const subscription = await registration.pushManager.getSubscription();
+return subscription ? await subscription.unsubscribe() : false;
+"#;
+
+/// A browser subscribed to push messages. Send this to the server to push messages to the browser.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushSubscription {
+    /// The url of the push service to send messages to
+    pub endpoint: String,
+    /// When the subscription expires, in milliseconds since the unix epoch
+    #[serde(default)]
+    pub expiration_time: Option<u64>,
+    /// The keys messages are encrypted with
+    pub keys: PushSubscriptionKeys,
+}
+
+/// The keys of a [`PushSubscription`], encoded as url safe base64
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PushSubscriptionKeys {
+    /// The public key of the browser
+    pub p256dh: String,
+    /// The secret shared with the browser
+    pub auth: String,
+}
+
+/// A notification in the format the service worker `dx` generates shows. Send it with [`PushMessage::notification`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PushNotification {
+    /// The title of the notification
+    pub title: String,
+    /// The text below the title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// The url of an image shown next to the text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Notifications with the same tag replace each other instead of stacking up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// The page to open when the notification is clicked. Defaults to the root of the app.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl PushNotification {
+    /// Create a notification with a title
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the text below the title
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Set the url of an image shown next to the text
+    pub fn icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Replace the notification with the same tag instead of showing another one
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Open this page when the notification is clicked
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+}
+
+/// An error while subscribing to or sending push messages
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum PushError {
+    /// The platform doesn't support push messages
+    Unsupported,
+    /// The user denied the app permission to show notifications
+    PermissionDenied,
+    /// The app didn't register a service worker to receive the messages
+    NoServiceWorker,
+    /// A key or subscription is malformed
+    InvalidKey(String),
+    /// The subscription expired or the user unsubscribed. Delete the subscription instead of retrying.
+    SubscriptionGone,
+    /// The push service rejected the message
+    PushService {
+        /// The http status the push service responded with
+        status: u16,
+        /// The body of the response
+        message: String,
+    },
+    /// The platform returned an error
+    Platform(String),
+}
+
+impl Display for PushError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PushError::Unsupported => write!(
+                f,
+                "PushError::Unsupported - push messages are not supported on the current platform"
+            ),
+            PushError::PermissionDenied => write!(
+                f,
+                "PushError::PermissionDenied - permission to show notifications was denied"
+            ),
+            PushError::NoServiceWorker => write!(
+                f,
+                "PushError::NoServiceWorker - the app didn't register a service worker"
+            ),
+            PushError::InvalidKey(err) => write!(f, "PushError::InvalidKey - {err}"),
+            PushError::SubscriptionGone => write!(
+                f,
+                "PushError::SubscriptionGone - the subscription expired or was cancelled"
+            ),
+            PushError::PushService { status, message } => write!(
+                f,
+                "PushError::PushService - the push service responded with {status}: {message}"
+            ),
+            PushError::Platform(err) => write!(f, "PushError::Platform - {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PushError {}
+
+/// Subscribe the browser to push messages from the server with this public VAPID key, encoded as url safe base64.
+/// If the browser is already subscribed with the same key, this returns the existing subscription.
+///
+/// Subscribing asks the user for permission to show notifications if they haven't answered yet. Call it from an event
+/// handler: some browsers only show the prompt in response to user input.
+pub async fn subscribe_push(application_server_key: &str) -> Result<PushSubscription, PushError> {
+    run(SUBSCRIBE, application_server_key).await
+}
+
+/// Get the push subscription of the browser if it is subscribed
+pub async fn push_subscription() -> Result<Option<PushSubscription>, PushError> {
+    run(SUBSCRIPTION, ()).await
+}
+
+/// Unsubscribe the browser from push messages. Returns `false` if it wasn't subscribed.
+pub async fn unsubscribe_push() -> Result<bool, PushError> {
+    run(UNSUBSCRIBE, ()).await
+}
+
+async fn run<T: DeserializeOwned>(script: &str, data: impl Serialize) -> Result<T, PushError> {
+    let eval = document().eval(format!(
+        "{PRELUDE}\nreturn await pushResult(async () => {{{script}}});"
+    ));
+    eval.send(data)?;
+    let result: Result<T, JsPushError> = eval.join().await?;
+    result.map_err(Into::into)
+}
+
+/// A javascript exception thrown while subscribing
+#[derive(Deserialize)]
+struct JsPushError {
+    name: String,
+    message: String,
+}
+
+impl From<JsPushError> for PushError {
+    fn from(err: JsPushError) -> Self {
+        match err.name.as_str() {
+            "NotAllowedError" => PushError::PermissionDenied,
+            "NotSupportedError" => PushError::Unsupported,
+            "NoServiceWorker" => PushError::NoServiceWorker,
+            "InvalidAccessError" | "InvalidCharacterError" => PushError::InvalidKey(err.message),
+            _ => PushError::Platform(format!("{}: {}", err.name, err.message)),
+        }
+    }
+}
+
+impl From<EvalError> for PushError {
+    fn from(err: EvalError) -> Self {
+        match err {
+            EvalError::Unsupported => PushError::Unsupported,
+            err => PushError::Platform(err.to_string()),
+        }
+    }
+}
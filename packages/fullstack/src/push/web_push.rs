@@ -0,0 +1,476 @@
+use super::{PushError, PushNotification, PushSubscription};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ring::{
+    aead, agreement, hkdf,
+    rand::{SecureRandom, SystemRandom},
+    signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The size of the records the payload is encrypted in. Messages are sent in a single record.
+const RECORD_SIZE: u32 = 4096;
+
+/// Push services accept payloads up to 4096 bytes after encryption, which adds 103 bytes
+const MAX_PAYLOAD_SIZE: usize = 3993;
+
+/// How long the signature of a request stays valid. Push services reject signatures valid for more than a day.
+const VAPID_EXPIRATION: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// The [VAPID](https://datatracker.ietf.org/doc/html/rfc8292) key pair the server signs push messages with.
+///
+/// Generate the keys once and store them with the other secrets of the server. The browser subscribes with the
+/// [`VapidKeys::public_key`], and push services only accept messages signed with the same key.
+pub struct VapidKeys {
+    key_pair: EcdsaKeyPair,
+    private_key: Vec<u8>,
+}
+
+impl VapidKeys {
+    /// Generate a new key pair
+    pub fn generate() -> Result<Self, PushError> {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+            .map_err(|_| PushError::Platform("failed to generate the VAPID keys".to_string()))?;
+        // ring wraps the 32 byte private key and the public key in a fixed PKCS#8 template
+        let pkcs8 = pkcs8.as_ref();
+        let private_key = &pkcs8[36..68];
+        let public_key = &pkcs8[pkcs8.len() - 65..];
+        Self::from_bytes(private_key, public_key)
+    }
+
+    /// Load the keys from the private and public key encoded as url safe base64, like the keys
+    /// [`VapidKeys::private_key`] and [`VapidKeys::public_key`] return or the keys other web push libraries generate
+    pub fn from_base64(private_key: &str, public_key: &str) -> Result<Self, PushError> {
+        Self::from_bytes(&decode(private_key)?, &decode(public_key)?)
+    }
+
+    fn from_bytes(private_key: &[u8], public_key: &[u8]) -> Result<Self, PushError> {
+        let key_pair = EcdsaKeyPair::from_private_key_and_public_key(
+            &ECDSA_P256_SHA256_FIXED_SIGNING,
+            private_key,
+            public_key,
+            &SystemRandom::new(),
+        )
+        .map_err(|err| PushError::InvalidKey(format!("invalid VAPID keys: {err}")))?;
+        Ok(Self {
+            key_pair,
+            private_key: private_key.to_vec(),
+        })
+    }
+
+    /// Get the private key encoded as url safe base64. Keep this secret.
+    pub fn private_key(&self) -> String {
+        URL_SAFE_NO_PAD.encode(&self.private_key)
+    }
+
+    /// Get the public key encoded as url safe base64. Pass this to [`subscribe_push`](super::subscribe_push) in the
+    /// browser.
+    pub fn public_key(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.key_pair.public_key())
+    }
+
+    /// Create the value of the `Authorization` header for a push service
+    fn authorization(&self, audience: &str, subject: &str) -> Result<String, PushError> {
+        let expires = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            + VAPID_EXPIRATION;
+        let header = URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(
+            serde_json::json!({ "aud": audience, "exp": expires.as_secs(), "sub": subject })
+                .to_string(),
+        );
+        let message = format!("{header}.{claims}");
+        let signature = self
+            .key_pair
+            .sign(&SystemRandom::new(), message.as_bytes())
+            .map_err(|_| PushError::Platform("failed to sign the VAPID token".to_string()))?;
+        Ok(format!(
+            "vapid t={message}.{}, k={}",
+            URL_SAFE_NO_PAD.encode(signature),
+            self.public_key()
+        ))
+    }
+}
+
+/// How urgently the push service should deliver a message. Push services may hold back messages with a low urgency
+/// while the device is on battery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Urgency {
+    /// Deliver the message when the device is on power and wifi
+    VeryLow,
+    /// Deliver the message when the device is on power or wifi
+    Low,
+    /// Deliver the message unless the battery is low
+    #[default]
+    Normal,
+    /// Deliver the message right away
+    High,
+}
+
+impl Urgency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Urgency::VeryLow => "very-low",
+            Urgency::Low => "low",
+            Urgency::Normal => "normal",
+            Urgency::High => "high",
+        }
+    }
+}
+
+/// A message to push to a subscribed browser
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushMessage {
+    payload: Vec<u8>,
+    ttl: Duration,
+    urgency: Urgency,
+    topic: Option<String>,
+}
+
+impl PushMessage {
+    /// Create a message with a payload that the service worker receives in its `push` event
+    pub fn new(payload: impl Into<Vec<u8>>) -> Self {
+        Self {
+            payload: payload.into(),
+            ttl: Duration::from_secs(24 * 60 * 60),
+            urgency: Urgency::Normal,
+            topic: None,
+        }
+    }
+
+    /// Create a message that the service worker `dx` generates shows as a notification
+    pub fn notification(notification: &PushNotification) -> Self {
+        Self::new(serde_json::to_vec(notification).expect("notifications are always valid json"))
+    }
+
+    /// Set how long the push service keeps the message while the device is offline. Defaults to a day.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set how urgently the push service should deliver the message
+    pub fn urgency(mut self, urgency: Urgency) -> Self {
+        self.urgency = urgency;
+        self
+    }
+
+    /// Replace the undelivered message with the same topic instead of delivering both. The topic can be up to 32 url
+    /// safe base64 characters.
+    pub fn topic(mut self, topic: impl Into<String>) -> Self {
+        self.topic = Some(topic.into());
+        self
+    }
+}
+
+/// Sends push messages to subscribed browsers.
+///
+/// ```rust, no_run
+/// # use dioxus_fullstack::push::*;
+/// # async fn send(subscription: PushSubscription) -> Result<(), PushError> {
+/// let keys = VapidKeys::from_base64(
+///     &std::env::var("VAPID_PRIVATE_KEY").unwrap(),
+///     &std::env::var("VAPID_PUBLIC_KEY").unwrap(),
+/// )?;
+/// let push = WebPush::new(keys, "mailto:admin@example.com");
+///
+/// let notification = PushNotification::new("New message").body("Ferris: are we there yet?").url("/chat");
+/// match push.send(&subscription, &PushMessage::notification(&notification)).await {
+///     // The browser unsubscribed, so the subscription can be deleted
+///     Err(PushError::SubscriptionGone) => {}
+///     result => result?,
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct WebPush {
+    keys: VapidKeys,
+    subject: String,
+    client: reqwest::Client,
+}
+
+impl WebPush {
+    /// Create a sender that signs messages with the keys. The subject is a `mailto:` or `https:` url the push service
+    /// can use to contact the operator of the server.
+    pub fn new(keys: VapidKeys, subject: impl Into<String>) -> Self {
+        Self {
+            keys,
+            subject: subject.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Get the keys messages are signed with
+    pub fn keys(&self) -> &VapidKeys {
+        &self.keys
+    }
+
+    /// Encrypt and sign a message without sending it. Use this to send messages with your own http client.
+    pub fn request(
+        &self,
+        subscription: &PushSubscription,
+        message: &PushMessage,
+    ) -> Result<http::Request<Vec<u8>>, PushError> {
+        let endpoint: http::Uri = subscription.endpoint.parse().map_err(|err| {
+            PushError::InvalidKey(format!("invalid subscription endpoint: {err}"))
+        })?;
+        let (Some(scheme), Some(authority)) = (endpoint.scheme(), endpoint.authority()) else {
+            return Err(PushError::InvalidKey(
+                "the subscription endpoint is not an absolute url".to_string(),
+            ));
+        };
+        let audience = format!("{scheme}://{authority}");
+
+        let body = encrypt(subscription, &message.payload)?;
+        let mut request = http::Request::post(endpoint)
+            .header(http::header::CONTENT_ENCODING, "aes128gcm")
+            .header(http::header::CONTENT_TYPE, "application/octet-stream")
+            .header("TTL", message.ttl.as_secs())
+            .header("Urgency", message.urgency.as_str())
+            .header(
+                http::header::AUTHORIZATION,
+                self.keys.authorization(&audience, &self.subject)?,
+            );
+        if let Some(topic) = &message.topic {
+            request = request.header("Topic", topic);
+        }
+        request
+            .body(body)
+            .map_err(|err| PushError::InvalidKey(err.to_string()))
+    }
+
+    /// Send a message to a subscribed browser.
+    ///
+    /// Returns [`PushError::SubscriptionGone`] if the browser unsubscribed or the subscription expired. Delete the
+    /// subscription when this happens instead of sending to it again.
+    pub async fn send(
+        &self,
+        subscription: &PushSubscription,
+        message: &PushMessage,
+    ) -> Result<(), PushError> {
+        let request = self.request(subscription, message)?;
+        let request = reqwest::Request::try_from(request)
+            .map_err(|err| PushError::Platform(err.to_string()))?;
+        let response = self
+            .client
+            .execute(request)
+            .await
+            .map_err(|err| PushError::Platform(err.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        match status.as_u16() {
+            404 | 410 => Err(PushError::SubscriptionGone),
+            status => Err(PushError::PushService {
+                status,
+                message: response.text().await.unwrap_or_default(),
+            }),
+        }
+    }
+}
+
+/// Encrypt the payload for the browser with the `aes128gcm` content encoding from
+/// [RFC 8291](https://datatracker.ietf.org/doc/html/rfc8291)
+fn encrypt(subscription: &PushSubscription, payload: &[u8]) -> Result<Vec<u8>, PushError> {
+    if payload.len() > MAX_PAYLOAD_SIZE {
+        return Err(PushError::PushService {
+            status: 413,
+            message: format!(
+                "the payload is {} bytes, but push services only accept up to {MAX_PAYLOAD_SIZE} bytes",
+                payload.len()
+            ),
+        });
+    }
+    let browser_key = decode(&subscription.keys.p256dh)?;
+    let auth_secret = decode(&subscription.keys.auth)?;
+    let crypto_error = |_| PushError::Platform("failed to encrypt the push message".to_string());
+
+    let rng = SystemRandom::new();
+    let server_private_key = agreement::EphemeralPrivateKey::generate(&agreement::ECDH_P256, &rng)
+        .map_err(crypto_error)?;
+    let server_key = server_private_key
+        .compute_public_key()
+        .map_err(crypto_error)?;
+    let server_key = server_key.as_ref();
+    let mut salt = [0; 16];
+    rng.fill(&mut salt).map_err(crypto_error)?;
+
+    let shared_secret = agreement::agree_ephemeral(
+        server_private_key,
+        &agreement::UnparsedPublicKey::new(&agreement::ECDH_P256, &browser_key),
+        |secret| secret.to_vec(),
+    )
+    .map_err(|_| PushError::InvalidKey("invalid subscription key".to_string()))?;
+
+    // Mix the shared secret with the auth secret of the browser, then derive the key and nonce from the salt
+    let key_info = [b"WebPush: info\0".as_slice(), &browser_key, server_key].concat();
+    let ikm = hkdf_expand(&auth_secret, &shared_secret, &key_info, 32)?;
+    let key = hkdf_expand(&salt, &ikm, b"Content-Encoding: aes128gcm\0", 16)?;
+    let nonce = hkdf_expand(&salt, &ikm, b"Content-Encoding: nonce\0", 12)?;
+
+    // The payload is followed by the delimiter of the last record
+    let mut record = [payload, &[2]].concat();
+    let key = aead::LessSafeKey::new(
+        aead::UnboundKey::new(&aead::AES_128_GCM, &key).map_err(crypto_error)?,
+    );
+    let nonce = aead::Nonce::try_assume_unique_for_key(&nonce).map_err(crypto_error)?;
+    key.seal_in_place_append_tag(nonce, aead::Aad::empty(), &mut record)
+        .map_err(crypto_error)?;
+
+    let mut body = Vec::with_capacity(16 + 4 + 1 + server_key.len() + record.len());
+    body.extend_from_slice(&salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(server_key.len() as u8);
+    body.extend_from_slice(server_key);
+    body.extend_from_slice(&record);
+    Ok(body)
+}
+
+/// Run HKDF with SHA-256 and return `len` bytes of output
+fn hkdf_expand(salt: &[u8], secret: &[u8], info: &[u8], len: usize) -> Result<Vec<u8>, PushError> {
+    struct Len(usize);
+
+    impl hkdf::KeyType for Len {
+        fn len(&self) -> usize {
+            self.0
+        }
+    }
+
+    let mut out = vec![0; len];
+    hkdf::Salt::new(hkdf::HKDF_SHA256, salt)
+        .extract(secret)
+        .expand(&[info], Len(len))
+        .and_then(|okm| okm.fill(&mut out))
+        .map_err(|_| PushError::Platform("failed to derive the push message keys".to_string()))?;
+    Ok(out)
+}
+
+fn decode(key: &str) -> Result<Vec<u8>, PushError> {
+    // Browsers and key generators disagree on padding, so accept both
+    URL_SAFE_NO_PAD
+        .decode(key.trim_end_matches('='))
+        .map_err(|err| PushError::InvalidKey(format!("invalid base64 key: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::push::PushSubscriptionKeys;
+    use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_FIXED};
+
+    #[test]
+    fn generated_keys_round_trip() {
+        let keys = VapidKeys::generate().unwrap();
+        let loaded = VapidKeys::from_base64(&keys.private_key(), &keys.public_key()).unwrap();
+        assert_eq!(loaded.public_key(), keys.public_key());
+        assert_eq!(decode(&keys.public_key()).unwrap().len(), 65);
+    }
+
+    #[test]
+    fn browsers_can_decrypt_messages() {
+        let rng = SystemRandom::new();
+        let browser_private_key =
+            agreement::EphemeralPrivateKey::generate(&agreement::ECDH_P256, &rng).unwrap();
+        let browser_key = browser_private_key.compute_public_key().unwrap();
+        let mut auth = [0; 16];
+        rng.fill(&mut auth).unwrap();
+        let subscription = PushSubscription {
+            endpoint: "https://push.example.com/send/abc?x=1".to_string(),
+            expiration_time: None,
+            keys: PushSubscriptionKeys {
+                p256dh: URL_SAFE_NO_PAD.encode(browser_key.as_ref()),
+                auth: URL_SAFE_NO_PAD.encode(auth),
+            },
+        };
+
+        let keys = VapidKeys::generate().unwrap();
+        let public_key = keys.public_key();
+        let push = WebPush::new(keys, "mailto:admin@example.com");
+        let notification = PushNotification::new("Hello").body("from the server");
+        let request = push
+            .request(
+                &subscription,
+                &PushMessage::notification(&notification).urgency(Urgency::High),
+            )
+            .unwrap();
+
+        assert_eq!(request.headers()["Content-Encoding"], "aes128gcm");
+        assert_eq!(request.headers()["Urgency"], "high");
+        assert_eq!(request.headers()["TTL"], "86400");
+
+        // The token is signed by the VAPID key for the origin of the push service
+        let authorization = request.headers()["Authorization"].to_str().unwrap();
+        let (token, key) = authorization
+            .strip_prefix("vapid t=")
+            .unwrap()
+            .split_once(", k=")
+            .unwrap();
+        assert_eq!(key, public_key);
+        let (message, signature) = token.rsplit_once('.').unwrap();
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, decode(key).unwrap())
+            .verify(message.as_bytes(), &decode(signature).unwrap())
+            .unwrap();
+        let claims: serde_json::Value =
+            serde_json::from_slice(&decode(message.split_once('.').unwrap().1).unwrap()).unwrap();
+        assert_eq!(claims["aud"], "https://push.example.com");
+        assert_eq!(claims["sub"], "mailto:admin@example.com");
+
+        // Decrypt the body like a browser would
+        let body = request.body();
+        let salt = &body[..16];
+        assert_eq!(body[16..20], RECORD_SIZE.to_be_bytes());
+        let key_len = body[20] as usize;
+        let server_key = &body[21..21 + key_len];
+        let mut record = body[21 + key_len..].to_vec();
+
+        let shared_secret = agreement::agree_ephemeral(
+            browser_private_key,
+            &agreement::UnparsedPublicKey::new(&agreement::ECDH_P256, server_key),
+            |secret| secret.to_vec(),
+        )
+        .unwrap();
+        let key_info = [
+            b"WebPush: info\0".as_slice(),
+            browser_key.as_ref(),
+            server_key,
+        ]
+        .concat();
+        let ikm = hkdf_expand(&auth, &shared_secret, &key_info, 32).unwrap();
+        let key = hkdf_expand(salt, &ikm, b"Content-Encoding: aes128gcm\0", 16).unwrap();
+        let nonce = hkdf_expand(salt, &ikm, b"Content-Encoding: nonce\0", 12).unwrap();
+        let key = aead::LessSafeKey::new(aead::UnboundKey::new(&aead::AES_128_GCM, &key).unwrap());
+        let plaintext = key
+            .open_in_place(
+                aead::Nonce::try_assume_unique_for_key(&nonce).unwrap(),
+                aead::Aad::empty(),
+                &mut record,
+            )
+            .unwrap();
+
+        let (delimiter, payload) = plaintext.split_last().unwrap();
+        assert_eq!(*delimiter, 2);
+        let received: PushNotification = serde_json::from_slice(payload).unwrap();
+        assert_eq!(received, notification);
+    }
+
+    #[test]
+    fn rejects_oversized_payloads() {
+        let subscription = PushSubscription {
+            endpoint: "https://push.example.com/send/abc".to_string(),
+            expiration_time: None,
+            keys: PushSubscriptionKeys {
+                p256dh: String::new(),
+                auth: String::new(),
+            },
+        };
+        let push = WebPush::new(VapidKeys::generate().unwrap(), "mailto:admin@example.com");
+        let message = PushMessage::new(vec![0; MAX_PAYLOAD_SIZE + 1]);
+        assert!(matches!(
+            push.request(&subscription, &message),
+            Err(PushError::PushService { status: 413, .. })
+        ));
+    }
+}
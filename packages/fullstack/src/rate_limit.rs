@@ -0,0 +1,515 @@
+//! Rate limits for server functions.
+//!
+//! Limit how often each client can call a server function with the `#[limit]` attribute. Requests over the limit are
+//! rejected with a `429 Too Many Requests` response before the server function runs, and the client gets a
+//! [`ServerFnError`] that [`RateLimited::from_error`] turns back into the time to wait.
+//!
+//! ```rust, ignore
+//! #[server]
+//! #[limit(5 per "60s")]
+//! async fn send_login_code(email: String) -> Result<(), ServerFnError> {
+//!     mail::send_code(&email).await?;
+//!     Ok(())
+//! }
+//!
+//! if let Err(err) = send_login_code(email).await {
+//!     if let Some(limited) = RateLimited::from_error(&err) {
+//!         println!("Try again in {} seconds", limited.retry_after.as_secs());
+//!     }
+//! }
+//! ```
+//!
+//! Clients are told apart by their IP address by default, or by their session with `#[limit(5 per "60s", by = session)]`
+//! when the `auth` feature is enabled. The address comes from the connection the request arrived on. Behind a reverse
+//! proxy, list the proxies with [`set_trusted_proxies`] so the address is read from the `X-Forwarded-For` header they
+//! add instead. Requests from clients without an address aren't limited, because they can't be told apart. Calls are counted in the [`MemoryStore`] unless another store is set with
+//! [`set_rate_limit_store`], like the [`RedisStore`] of the `rate-limit-redis` feature that shares the counts between
+//! several servers.
+//!
+//! The limits of every server function are listed by [`server_fn_rate_limits`] once the server functions are
+//! registered.
+
+use http::{header, HeaderMap, HeaderValue, Request, Response, StatusCode};
+use parking_lot::Mutex;
+use server_fn::{
+    error::{NoCustomError, ServerFnErrorSerde},
+    ServerFnError,
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    pin::Pin,
+    sync::{Arc, OnceLock},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::ServiceExt;
+
+/// The message the error of rejected requests starts with. The seconds to wait follow it.
+const RATE_LIMITED: &str = "rate limited, retry after ";
+
+/// What requests are counted together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RateLimitKey {
+    /// Count the requests from each IP address. The address is read from the connect info of axum. If the request came
+    /// through one of the [trusted proxies](set_trusted_proxies), it is the last address in the `X-Forwarded-For`
+    /// header that isn't a trusted proxy.
+    #[default]
+    Ip,
+    /// Count the requests from each session of the `SessionLayer`. Requests without a session are counted by IP
+    /// address.
+    Session,
+}
+
+/// The rate limit of a server function. Add it with the `#[limit]` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RateLimit {
+    requests: u64,
+    window: Duration,
+    key: RateLimitKey,
+}
+
+impl RateLimit {
+    /// Allow this many requests from each client in every window of time
+    pub fn new(requests: u64, window: Duration) -> Self {
+        Self {
+            requests,
+            window,
+            key: RateLimitKey::Ip,
+        }
+    }
+
+    /// Set what requests are counted together
+    pub fn by(mut self, key: RateLimitKey) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// The number of requests allowed in every window
+    pub fn requests(&self) -> u64 {
+        self.requests
+    }
+
+    /// The length of the window the requests are counted in
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// What requests are counted together
+    pub fn key(&self) -> RateLimitKey {
+        self.key
+    }
+
+    /// The client the request counts toward, or `None` if the client can't be identified
+    fn client(&self, request: &Request<impl Sized>, trusted_proxies: &[IpAddr]) -> Option<String> {
+        #[cfg(feature = "auth")]
+        if self.key == RateLimitKey::Session {
+            let session = request.extensions().get::<crate::auth::Session>();
+            if let Some(id) = session.and_then(|session| session.id()) {
+                return Some(format!("session:{id}"));
+            }
+        }
+
+        let peer = request
+            .extensions()
+            .get::<axum::extract::ConnectInfo<SocketAddr>>()?
+            .0
+            .ip();
+        let address = match trusted_proxies.contains(&peer) {
+            true => forwarded_for(request.headers(), trusted_proxies)?,
+            false => peer,
+        };
+        Some(format!("ip:{address}"))
+    }
+}
+
+/// Find the address of the client in the `X-Forwarded-For` headers of a request from a trusted proxy.
+///
+/// Every proxy appends the address it received the request from, so the entries are read from the right. Everything
+/// left of the first address that isn't a trusted proxy was sent by the client and can't be trusted.
+fn forwarded_for(headers: &HeaderMap, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let mut hops = Vec::new();
+    for header in headers.get_all("x-forwarded-for") {
+        hops.extend(header.to_str().ok()?.split(','));
+    }
+    for hop in hops.into_iter().rev() {
+        let address: IpAddr = hop.trim().parse().ok()?;
+        if !trusted_proxies.contains(&address) {
+            return Some(address);
+        }
+    }
+    None
+}
+
+fn trusted_proxies() -> &'static OnceLock<Vec<IpAddr>> {
+    static TRUSTED_PROXIES: OnceLock<Vec<IpAddr>> = OnceLock::new();
+    &TRUSTED_PROXIES
+}
+
+/// Trust the `X-Forwarded-For` header of requests from these reverse proxies when rate limits look up the address of
+/// the client. Call this before the server starts.
+///
+/// Without trusted proxies, the header is ignored and every client behind a proxy shares the limit of the proxy.
+pub fn set_trusted_proxies(proxies: impl IntoIterator<Item = IpAddr>) {
+    if trusted_proxies()
+        .set(proxies.into_iter().collect())
+        .is_err()
+    {
+        tracing::error!("The trusted proxies were already set or used");
+    }
+}
+
+/// A request that was rejected because the client made too many requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimited {
+    /// How long until the client can make requests again
+    pub retry_after: Duration,
+}
+
+impl RateLimited {
+    /// Check if a server function failed because of its rate limit
+    pub fn from_error<E>(error: &ServerFnError<E>) -> Option<Self> {
+        let ServerFnError::ServerError(message) = error else {
+            return None;
+        };
+        let seconds = message.strip_prefix(RATE_LIMITED)?.strip_suffix('s')?;
+        Some(Self {
+            retry_after: Duration::from_secs(seconds.parse().ok()?),
+        })
+    }
+
+    fn into_response<B: From<String>>(self) -> Response<B> {
+        // Round up so the client doesn't retry before the window ends
+        let seconds = self.retry_after.as_secs() + u64::from(self.retry_after.subsec_nanos() > 0);
+        let error =
+            ServerFnError::<NoCustomError>::ServerError(format!("{RATE_LIMITED}{seconds}s"));
+        let mut response = Response::new(B::from(error.ser().unwrap_or_default()));
+        *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+        response
+    }
+}
+
+/// Where the requests of each client are counted
+#[async_trait::async_trait]
+pub trait RateLimitStore: Send + Sync + 'static {
+    /// Count a request toward the key. Returns the number of requests in the current window, including this one, and
+    /// how long until the window ends.
+    async fn hit(&self, key: &str, window: Duration) -> Result<(u64, Duration), String>;
+}
+
+/// A [`RateLimitStore`] that counts requests in the memory of the server
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    windows: Arc<Mutex<HashMap<String, (u64, Instant)>>>,
+}
+
+#[async_trait::async_trait]
+impl RateLimitStore for MemoryStore {
+    async fn hit(&self, key: &str, window: Duration) -> Result<(u64, Duration), String> {
+        let mut windows = self.windows.lock();
+        let now = Instant::now();
+        // Forget the windows that ended while the lock is held anyway
+        windows.retain(|_, (_, ends)| *ends > now);
+        let (count, ends) = windows.entry(key.to_string()).or_insert((0, now + window));
+        *count += 1;
+        Ok((*count, *ends - now))
+    }
+}
+
+/// A [`RateLimitStore`] that counts requests in Redis, so every server behind a load balancer shares the counts
+#[cfg(feature = "rate-limit-redis")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rate-limit-redis")))]
+#[derive(Clone)]
+pub struct RedisStore {
+    connection: redis::aio::MultiplexedConnection,
+}
+
+#[cfg(feature = "rate-limit-redis")]
+impl RedisStore {
+    /// Connect to Redis, like `redis://127.0.0.1/`
+    pub async fn connect(url: &str) -> redis::RedisResult<Self> {
+        let client = redis::Client::open(url)?;
+        Ok(Self {
+            connection: client.get_multiplexed_tokio_connection().await?,
+        })
+    }
+}
+
+#[cfg(feature = "rate-limit-redis")]
+#[async_trait::async_trait]
+impl RateLimitStore for RedisStore {
+    async fn hit(&self, key: &str, window: Duration) -> Result<(u64, Duration), String> {
+        let key = format!("dioxus-rate-limit:{key}");
+        let mut connection = self.connection.clone();
+        // Start the window with the first request and read how long it has left in the same round trip
+        let (count, remaining): (u64, i64) = redis::pipe()
+            .atomic()
+            .cmd("SET")
+            .arg(&key)
+            .arg(0)
+            .arg("PX")
+            .arg(window.as_millis() as u64)
+            .arg("NX")
+            .ignore()
+            .incr(&key, 1)
+            .pttl(&key)
+            .query_async(&mut connection)
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok((count, Duration::from_millis(remaining.max(0) as u64)))
+    }
+}
+
+fn store() -> &'static OnceLock<Arc<dyn RateLimitStore>> {
+    static STORE: OnceLock<Arc<dyn RateLimitStore>> = OnceLock::new();
+    &STORE
+}
+
+/// Count requests in a different store than the [`MemoryStore`]. Call this before the server starts.
+pub fn set_rate_limit_store(rate_limit_store: impl RateLimitStore) {
+    if store().set(Arc::new(rate_limit_store)).is_err() {
+        tracing::error!("The rate limit store was already set or used");
+    }
+}
+
+fn registry() -> &'static Mutex<Vec<(&'static str, RateLimit)>> {
+    static REGISTRY: OnceLock<Mutex<Vec<(&'static str, RateLimit)>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Get the rate limit of every registered server function by path
+pub fn server_fn_rate_limits() -> Vec<(&'static str, RateLimit)> {
+    registry().lock().clone()
+}
+
+impl<S> tower_layer::Layer<S> for RateLimit {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        if let Some(path) = crate::limits::registering_server_fn() {
+            let mut registry = registry().lock();
+            if !registry.iter().any(|(registered, _)| *registered == path) {
+                tracing::trace!("Rate limiting {path} to {self:?}");
+                registry.push((path, *self));
+            }
+        }
+
+        RateLimitService {
+            inner: Arc::new(tokio::sync::Mutex::new(inner)),
+            limit: *self,
+        }
+    }
+}
+
+/// The service [`RateLimit`] wraps server functions in
+pub struct RateLimitService<S> {
+    // The services of server functions can't be cloned, so the service is shared with the futures that call it
+    inner: Arc<tokio::sync::Mutex<S>>,
+    limit: RateLimit,
+}
+
+impl<S> Clone for RateLimitService<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            limit: self.limit,
+        }
+    }
+}
+
+impl<S, ReqBody, ResBody> tower::Service<Request<ReqBody>> for RateLimitService<S>
+where
+    S: tower::Service<Request<ReqBody>, Response = Response<ResBody>> + Send + 'static,
+    S::Future: Send,
+    ReqBody: Send + 'static,
+    ResBody: From<String>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // The inner service is polled when the request is under the limit
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let inner = self.inner.clone();
+        let limit = self.limit;
+        let trusted_proxies = trusted_proxies().get_or_init(Vec::new);
+        let client = limit.client(&request, trusted_proxies);
+
+        Box::pin(async move {
+            match client {
+                Some(client) => {
+                    let key = format!("{}|{client}", request.uri().path());
+                    let store = store()
+                        .get_or_init(|| Arc::new(MemoryStore::default()))
+                        .clone();
+                    match store.hit(&key, limit.window).await {
+                        Ok((count, retry_after)) if count > limit.requests => {
+                            return Ok(RateLimited { retry_after }.into_response());
+                        }
+                        Ok(_) => {}
+                        // Don't take the app down with the store
+                        Err(err) => tracing::error!("Failed to check the rate limit: {err}"),
+                    }
+                }
+                // Counting unidentified clients together would let one of them lock out all of the others
+                None => {
+                    static WARNED: std::sync::Once = std::sync::Once::new();
+                    WARNED.call_once(|| {
+                        tracing::warn!("Rate limits are skipped for requests without a client address. Serve the app with the connect info of axum, and list any reverse proxies with set_trusted_proxies.");
+                    });
+                }
+            }
+
+            // Only hold the lock while the request is handed to the inner service
+            let future = {
+                let mut inner = inner.lock().await;
+                inner.ready().await?.call(request)
+            };
+            future.await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::Service;
+
+    /// A request from the address that is forwarded by each proxy in the chain
+    fn request(peer: Option<&str>, forwarded_for: &[&str]) -> Request<()> {
+        let mut request = Request::builder().uri("/api/limited");
+        for hops in forwarded_for {
+            request = request.header("x-forwarded-for", *hops);
+        }
+        let mut request = request.body(()).unwrap();
+        if let Some(peer) = peer {
+            let address = SocketAddr::new(peer.parse().unwrap(), 443);
+            request
+                .extensions_mut()
+                .insert(axum::extract::ConnectInfo(address));
+        }
+        request
+    }
+
+    async fn call<S>(service: &mut S, request: Request<()>) -> Response<String>
+    where
+        S: Service<Request<()>, Response = Response<String>, Error = std::convert::Infallible>,
+    {
+        service.ready().await.unwrap().call(request).await.unwrap()
+    }
+
+    fn limited_service(
+        requests: u64,
+    ) -> impl Service<Request<()>, Response = Response<String>, Error = std::convert::Infallible>
+    {
+        let inner =
+            tower::service_fn(|_: Request<()>| async { Ok(Response::new("ok".to_string())) });
+        tower_layer::Layer::layer(&RateLimit::new(requests, Duration::from_secs(60)), inner)
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_over_the_limit() {
+        let mut service = limited_service(2);
+
+        let from = |peer| request(Some(peer), &[]);
+        assert_eq!(
+            call(&mut service, from("10.0.0.1")).await.status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(&mut service, from("10.0.0.1")).await.status(),
+            StatusCode::OK
+        );
+
+        let rejected = call(&mut service, from("10.0.0.1")).await;
+        assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(rejected.headers()[header::RETRY_AFTER], "60");
+
+        // The client decodes the body into the time to wait
+        let error = ServerFnError::<NoCustomError>::de(rejected.body());
+        assert_eq!(
+            RateLimited::from_error(&error),
+            Some(RateLimited {
+                retry_after: Duration::from_secs(60)
+            })
+        );
+
+        // Other clients have their own count
+        assert_eq!(
+            call(&mut service, from("10.0.0.2")).await.status(),
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn unidentified_clients_are_not_counted_together() {
+        let mut service = limited_service(1);
+        for _ in 0..3 {
+            let response = call(&mut service, request(None, &["10.0.0.1"])).await;
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+
+    #[test]
+    fn clients_can_not_choose_their_address() {
+        let limit = RateLimit::new(1, Duration::from_secs(60));
+        let proxy: IpAddr = "127.0.0.1".parse().unwrap();
+
+        // The header is ignored unless the request came from a trusted proxy
+        let spoofed = request(Some("203.0.113.7"), &["198.51.100.1"]);
+        assert_eq!(
+            limit.client(&spoofed, &[]).as_deref(),
+            Some("ip:203.0.113.7")
+        );
+        assert_eq!(
+            limit.client(&spoofed, &[proxy]).as_deref(),
+            Some("ip:203.0.113.7")
+        );
+
+        // Behind proxies, the client is the last hop that isn't a trusted proxy. The hops left of it came from the client.
+        let proxies = [proxy, "10.0.0.2".parse().unwrap()];
+        let forwarded = request(
+            Some("127.0.0.1"),
+            &["198.51.100.1, 203.0.113.7", "10.0.0.2"],
+        );
+        assert_eq!(
+            limit.client(&forwarded, &proxies).as_deref(),
+            Some("ip:203.0.113.7")
+        );
+    }
+
+    #[test]
+    fn requests_without_an_address_are_unidentified() {
+        let limit = RateLimit::new(1, Duration::from_secs(60));
+        let proxy: IpAddr = "127.0.0.1".parse().unwrap();
+
+        assert_eq!(limit.client(&request(None, &["198.51.100.1"]), &[]), None);
+        // A trusted proxy that didn't say who it forwarded the request for
+        assert_eq!(
+            limit.client(&request(Some("127.0.0.1"), &[]), &[proxy]),
+            None
+        );
+        assert_eq!(
+            limit.client(&request(Some("127.0.0.1"), &["127.0.0.1"]), &[proxy]),
+            None
+        );
+        assert_eq!(
+            limit.client(&request(Some("127.0.0.1"), &["unknown"]), &[proxy]),
+            None
+        );
+    }
+
+    #[test]
+    fn other_errors_are_not_rate_limits() {
+        let error = ServerFnError::<NoCustomError>::ServerError("database is down".into());
+        assert_eq!(RateLimited::from_error(&error), None);
+    }
+}
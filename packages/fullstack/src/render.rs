@@ -16,7 +16,7 @@ use std::sync::RwLock;
 use std::{collections::HashMap, future::Future};
 use tokio::task::JoinHandle;
 
-use crate::{prelude::*, StreamingMode};
+use crate::{prelude::*, HydrationCompression, StreamingMode};
 use dioxus_lib::prelude::*;
 
 /// A suspense boundary that is pending with a placeholder in the client
@@ -26,7 +26,7 @@ struct PendingSuspenseBoundary {
 }
 
 /// Spawn a task in the background. If wasm is enabled, this will use the single threaded tokio runtime
-fn spawn_platform<Fut>(f: impl FnOnce() -> Fut + Send + 'static) -> JoinHandle<Fut::Output>
+pub(crate) fn spawn_platform<Fut>(f: impl FnOnce() -> Fut + Send + 'static) -> JoinHandle<Fut::Output>
 where
     Fut: Future + 'static,
     Fut::Output: Send + 'static,
@@ -168,6 +168,7 @@ impl SsrRendererPool {
 
         let myself = self.clone();
         let streaming_mode = cfg.streaming_mode;
+        let hydration_compression = cfg.hydration_compression;
 
         let join_handle = spawn_platform(move || async move {
             let mut virtual_dom = virtual_dom_factory();
@@ -247,6 +248,11 @@ impl SsrRendererPool {
                 )
                 .await;
 
+                // Apply any changes the resolved nodes made to the head before they hydrate
+                if let Some(head) = document.take_late_head_script() {
+                    stream.render(head);
+                }
+
                 // Just rerender the resolved nodes
                 for scope in resolved_suspense_nodes {
                     let pending_suspense_boundary = {
@@ -261,7 +267,8 @@ impl SsrRendererPool {
                             renderer.reset_hydration();
                             renderer.render_scope(into, &virtual_dom, scope)
                         };
-                        let resolved_data = serialize_server_data(&virtual_dom, scope);
+                        let resolved_data =
+                            serialize_server_data(&virtual_dom, scope, hydration_compression);
                         if let Err(err) = stream.replace_placeholder(
                             pending_suspense_boundary.mount,
                             render_suspense,
@@ -410,14 +417,18 @@ fn start_capturing_errors(suspense_scope: ScopeId) {
     suspense_scope.in_runtime(provide_error_boundary);
 }
 
-fn serialize_server_data(virtual_dom: &VirtualDom, scope: ScopeId) -> SerializedHydrationData {
+pub(crate) fn serialize_server_data(
+    virtual_dom: &VirtualDom,
+    scope: ScopeId,
+    compression: HydrationCompression,
+) -> SerializedHydrationData {
     // After we replace the placeholder in the dom with javascript, we need to send down the resolved data so that the client can hydrate the node
     // Extract any data we serialized for hydration (from server futures)
     let html_data =
         crate::html_storage::HTMLData::extract_from_suspense_boundary(virtual_dom, scope);
 
     // serialize the server state into a base64 string
-    html_data.serialized()
+    html_data.serialized(compression)
 }
 
 /// State used in server side rendering. This utilizes a pool of [`dioxus_ssr::Renderer`]s to cache static templates between renders.
@@ -527,11 +538,16 @@ impl FullstackHTMLTemplate {
         to: &mut R,
         virtual_dom: &VirtualDom,
     ) -> Result<(), dioxus_isrg::IncrementalRendererError> {
-        let ServeConfig { index, .. } = &self.cfg;
+        let ServeConfig {
+            index,
+            hydration_compression,
+            ..
+        } = &self.cfg;
 
         // Collect the initial server data from the root node. For most apps, no use_server_futures will be resolved initially, so this will be full on `None`s.
         // Sending down those Nones are still important to tell the client not to run the use_server_futures that are already running on the backend
-        let resolved_data = serialize_server_data(virtual_dom, ScopeId::ROOT);
+        let resolved_data =
+            serialize_server_data(virtual_dom, ScopeId::ROOT, *hydration_compression);
         // We always send down the data required to hydrate components on the client
         let raw_data = resolved_data.data;
         write!(
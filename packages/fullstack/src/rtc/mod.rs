@@ -0,0 +1,652 @@
+//! Peer to peer data channels between the clients of an app with WebRTC.
+//!
+//! Clients that open a [`use_data_channel`] with the same room connect to each other directly. The server only relays
+//! the messages the clients need to find each other through built in server functions, so the data itself never goes
+//! through the server. That makes data channels a good fit for cursors, games and file transfers between users.
+//!
+//! ```rust, ignore
+//! #[derive(Clone, Serialize, Deserialize)]
+//! struct Cursor {
+//!     x: f64,
+//!     y: f64,
+//! }
+//!
+//! fn Canvas() -> Element {
+//!     let channel = use_data_channel::<Cursor>("canvas");
+//!     let mut cursors = use_signal(HashMap::<u64, Cursor>::new);
+//!
+//!     use_future(move || async move {
+//!         let mut messages = channel.messages();
+//!         while let Some((peer, cursor)) = messages.next().await {
+//!             cursors.write().insert(peer, cursor);
+//!         }
+//!     });
+//!
+//!     rsx! {
+//!         div {
+//!             onmousemove: move |event| {
+//!                 let point = event.client_coordinates();
+//!                 channel.send(Cursor { x: point.x, y: point.y });
+//!             },
+//!             for (peer, cursor) in cursors() {
+//!                 div { key: "{peer}", class: "cursor", left: "{cursor.x}px", top: "{cursor.y}px" }
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Data channels use the WebRTC api of the browser, so they work on the web and in desktop webviews that support
+//! WebRTC. The server keeps the rooms in memory, so every client of a room must reach the same server.
+//!
+//! There is no native backend for desktop webviews without WebRTC. A backend on webrtc-rs is out of scope until a
+//! version of the `webrtc` crate that builds with the dependencies of this workspace is available.
+
+use dioxus_lib::document::{document, Eval, EvalError};
+use dioxus_lib::prelude::*;
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::{
+    future::{select, Either},
+    pin_mut, FutureExt, Sink, StreamExt,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use server_fn::ServerFnError;
+use std::{
+    fmt::Display,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+#[cfg(feature = "server")]
+mod server;
+
+/// Keeps a peer connection with a data channel for every other client in the room. The signaling messages of the
+/// connections are relayed by the app through the server.
+const SCRIPT: &str = r#"
+const config = await dioxus.recv();
+const peers = new Map();
+const signal = (peer, signal) => dioxus.send({ type: "signal", peer, signal });
+const close = (peer) => {
+    const connection = peers.get(peer);
+    if (!connection) return;
+    peers.delete(peer);
+    connection.pc.close();
+    dioxus.send({ type: "close", peer });
+};
+const open = (peer, initiator) => {
+    const pc = new RTCPeerConnection({ iceServers: config.ice_servers });
+    // Both sides create the same channel, so it doesn't need to be announced
+    const channel = pc.createDataChannel("dioxus", { negotiated: true, id: 0 });
+    pc.onicecandidate = (event) => {
+        if (event.candidate) signal(peer, { candidate: event.candidate.toJSON() });
+    };
+    pc.onconnectionstatechange = () => {
+        if (pc.connectionState === "failed" || pc.connectionState === "closed") close(peer);
+    };
+    channel.onopen = () => dioxus.send({ type: "open", peer });
+    channel.onclose = () => close(peer);
+    channel.onmessage = (event) => dioxus.send({ type: "message", peer, data: event.data });
+    const connection = { pc, channel };
+    peers.set(peer, connection);
+    if (initiator) {
+        pc.createOffer()
+            .then((offer) => pc.setLocalDescription(offer))
+            .then(() => signal(peer, { description: pc.localDescription.toJSON() }));
+    }
+    return connection;
+};
+while (true) {
+    const message = await dioxus.recv();
+    if (message === null) {
+        for (const peer of [...peers.keys()]) close(peer);
+        return;
+    }
+    switch (message.type) {
+        case "connect":
+            open(message.peer, true);
+            break;
+        case "signal": {
+            const { pc } = peers.get(message.peer) || open(message.peer, false);
+            const { description, candidate } = message.signal;
+            try {
+                if (description) {
+                    await pc.setRemoteDescription(description);
+                    if (description.type === "offer") {
+                        await pc.setLocalDescription(await pc.createAnswer());
+                        signal(message.peer, { description: pc.localDescription.toJSON() });
+                    }
+                } else if (candidate) {
+                    await pc.addIceCandidate(candidate);
+                }
+            } catch (e) {
+                console.error("Failed to connect to peer", message.peer, e);
+            }
+            break;
+        }
+        case "leave":
+            close(message.peer);
+            break;
+        case "send":
+            for (const [peer, { channel }] of peers) {
+                if ((message.peer === null || message.peer === peer) && channel.readyState === "open") {
+                    channel.send(message.data);
+                }
+            }
+            break;
+    }
+}
+"#;
+
+/// A STUN or TURN server that helps clients behind a NAT connect to each other
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IceServer {
+    urls: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    credential: Option<String>,
+}
+
+impl IceServer {
+    /// A server at a url like `stun:stun.example.com:3478`
+    pub fn new(url: impl ToString) -> Self {
+        Self {
+            urls: vec![url.to_string()],
+            username: None,
+            credential: None,
+        }
+    }
+
+    /// Log into a TURN server
+    pub fn credentials(mut self, username: impl ToString, credential: impl ToString) -> Self {
+        self.username = Some(username.to_string());
+        self.credential = Some(credential.to_string());
+        self
+    }
+}
+
+/// The room of a data channel and how its clients connect
+#[derive(Debug, Clone, PartialEq)]
+pub struct RtcOptions {
+    room: String,
+    ice_servers: Vec<IceServer>,
+}
+
+impl RtcOptions {
+    /// Connect to the clients in a room
+    pub fn new(room: impl ToString) -> Self {
+        Self {
+            room: room.to_string(),
+            ice_servers: Vec::new(),
+        }
+    }
+
+    /// Add a STUN or TURN server. Without one, only clients on the same network can connect to each other.
+    pub fn ice_server(mut self, server: IceServer) -> Self {
+        self.ice_servers.push(server);
+        self
+    }
+}
+
+/// An error while sending a message over a data channel
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum RtcError {
+    /// The message couldn't be serialized
+    Serialization(String),
+    /// The component that owns the channel was dropped
+    Closed,
+}
+
+impl Display for RtcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RtcError::Serialization(err) => {
+                write!(f, "RtcError::Serialization - {err}")
+            }
+            RtcError::Closed => write!(f, "RtcError::Closed - the data channel was closed"),
+        }
+    }
+}
+
+impl std::error::Error for RtcError {}
+
+/// A typed data channel to every other client in a room. Created with [`use_data_channel`].
+///
+/// Messages are sent to every connected peer with [`DataChannel::send`] or through the channel as a [`Sink`], and
+/// received with [`DataChannel::messages`].
+pub struct DataChannel<T: 'static> {
+    id: Signal<Option<u64>>,
+    peers: Signal<Vec<u64>>,
+    outgoing: CopyValue<UnboundedSender<Outgoing>>,
+    listeners: CopyValue<Vec<UnboundedSender<(u64, Value)>>>,
+    message: PhantomData<fn(T) -> T>,
+}
+
+impl<T> Clone for DataChannel<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DataChannel<T> {}
+
+impl<T: Serialize + DeserializeOwned + 'static> DataChannel<T> {
+    /// The id the server gave this client, or `None` until it joined the room
+    pub fn id(&self) -> Option<u64> {
+        (self.id)()
+    }
+
+    /// The peers the channel is open to
+    pub fn peers(&self) -> Vec<u64> {
+        self.peers.cloned()
+    }
+
+    /// Send a message to every connected peer
+    pub fn send(&self, message: T) {
+        _ = self.try_send(None, message);
+    }
+
+    /// Send a message to one peer
+    pub fn send_to(&self, peer: u64, message: T) {
+        _ = self.try_send(Some(peer), message);
+    }
+
+    fn try_send(&self, peer: Option<u64>, message: T) -> Result<(), RtcError> {
+        let data = serde_json::to_string(&message)
+            .map_err(|err| RtcError::Serialization(err.to_string()))?;
+        self.outgoing
+            .read()
+            .unbounded_send(Outgoing { peer, data })
+            .map_err(|_| RtcError::Closed)
+    }
+
+    /// Get a stream of the messages from the peers and who sent them. Every stream gets every message that arrives
+    /// after it was created.
+    pub fn messages(&self) -> impl futures_util::Stream<Item = (u64, T)> {
+        let (sender, receiver): (_, UnboundedReceiver<(u64, Value)>) = unbounded();
+        let mut listeners = self.listeners;
+        listeners.write().push(sender);
+        receiver.filter_map(|(peer, message)| async move {
+            Some((peer, serde_json::from_value(message).ok()?))
+        })
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> Sink<T> for DataChannel<T> {
+    type Error = RtcError;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, message: T) -> Result<(), Self::Error> {
+        self.try_send(None, message)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Open a data channel to every other client in the room
+#[track_caller]
+pub fn use_data_channel<T>(room: impl ToString) -> DataChannel<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    use_data_channel_with(RtcOptions::new(room))
+}
+
+/// Open a data channel to every other client in the room with [`RtcOptions`] for the ICE servers
+#[track_caller]
+pub fn use_data_channel_with<T>(options: RtcOptions) -> DataChannel<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    use_hook(|| {
+        let (outgoing, messages) = unbounded();
+        let channel = DataChannel {
+            id: Signal::new(None),
+            peers: Signal::new(Vec::new()),
+            outgoing: CopyValue::new(outgoing),
+            listeners: CopyValue::new(Vec::new()),
+            message: PhantomData,
+        };
+        spawn(async move {
+            if let Err(err) = run(options, channel, messages).await {
+                tracing::error!("Data channel failed: {err}");
+            }
+        });
+        channel
+    })
+}
+
+/// A message the app sends to one peer or every peer
+#[derive(Serialize)]
+struct Outgoing {
+    peer: Option<u64>,
+    data: String,
+}
+
+/// A message from the script
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ScriptEvent {
+    Signal { peer: u64, signal: Value },
+    Open { peer: u64 },
+    Close { peer: u64 },
+    Message { peer: u64, data: String },
+}
+
+/// A message for the script
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ScriptCommand {
+    Connect { peer: u64 },
+    Signal { peer: u64, signal: Value },
+    Leave { peer: u64 },
+    Send(Outgoing),
+}
+
+/// Join the room, then relay signaling messages between the script and the server and app messages to the script
+async fn run<T>(
+    options: RtcOptions,
+    mut channel: DataChannel<T>,
+    mut messages: UnboundedReceiver<Outgoing>,
+) -> Result<(), ServerFnError> {
+    let mut script = Script(document().eval(SCRIPT.to_string()));
+    #[derive(Serialize)]
+    struct Config {
+        ice_servers: Vec<IceServer>,
+    }
+    let config = Config {
+        ice_servers: options.ice_servers,
+    };
+    // The server can't connect to peers while it renders the page
+    if script.0.send(config).is_err() {
+        return Ok(());
+    }
+
+    let room = options.room;
+    let joined = rtc_join(room.clone()).await?;
+    channel.id.set(Some(joined.peer));
+    // The newest client in the room connects to everyone that was there before it
+    for peer in joined.peers {
+        _ = script.0.send(ScriptCommand::Connect { peer });
+    }
+
+    let poll = |room: String| rtc_poll(room, joined.peer, joined.secret).fuse();
+    let mut events = Box::pin(poll(room.clone()));
+    loop {
+        let next = {
+            let from_script = script.0.recv::<ScriptEvent>();
+            pin_mut!(from_script);
+            let local = select(from_script, messages.next());
+            match select(local, &mut events).await {
+                Either::Left((Either::Left((event, _)), _)) => Next::Script(event),
+                Either::Left((Either::Right((message, _)), _)) => Next::App(message),
+                Either::Right((events, _)) => Next::Server(events),
+            }
+        };
+
+        match next {
+            Next::Script(Ok(event)) => match event {
+                ScriptEvent::Signal { peer, signal } => {
+                    // Signals are relayed in order because the description must arrive before the candidates
+                    rtc_signal(room.clone(), joined.peer, joined.secret, peer, signal).await?;
+                }
+                ScriptEvent::Open { peer } => channel.peers.write().push(peer),
+                ScriptEvent::Close { peer } => channel.peers.write().retain(|id| *id != peer),
+                ScriptEvent::Message { peer, data } => {
+                    let Ok(message) = serde_json::from_str::<Value>(&data) else {
+                        continue;
+                    };
+                    channel.listeners.write().retain(|listener| {
+                        listener.unbounded_send((peer, message.clone())).is_ok()
+                    });
+                }
+            },
+            Next::Script(Err(EvalError::Serialization(_))) => {}
+            Next::Script(Err(_)) | Next::App(None) => return Ok(()),
+            Next::App(Some(message)) => _ = script.0.send(ScriptCommand::Send(message)),
+            Next::Server(result) => {
+                for event in result? {
+                    match event {
+                        RoomEvent::Signal { from, signal } => {
+                            _ = script.0.send(ScriptCommand::Signal { peer: from, signal });
+                        }
+                        RoomEvent::Left { peer } => {
+                            _ = script.0.send(ScriptCommand::Leave { peer });
+                        }
+                        // The new client sends the offer
+                        RoomEvent::Joined { .. } => {}
+                    }
+                }
+                events = Box::pin(poll(room.clone()));
+            }
+        }
+    }
+}
+
+enum Next {
+    Script(Result<ScriptEvent, EvalError>),
+    App(Option<Outgoing>),
+    Server(Result<Vec<RoomEvent>, ServerFnError>),
+}
+
+/// Closes every peer connection when the task that reads from the script is dropped with its component
+struct Script(Eval);
+
+impl Drop for Script {
+    fn drop(&mut self) {
+        _ = self.0.send(Value::Null);
+    }
+}
+
+/// The id of a client in a room and the peers that were already there
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinedRoom {
+    /// The id of the client
+    pub peer: u64,
+    /// The secret the client proves it is the peer with
+    pub secret: u64,
+    /// The other clients in the room
+    pub peers: Vec<u64>,
+}
+
+/// Something that happened in a room
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RoomEvent {
+    /// A client joined the room
+    Joined {
+        /// The id of the client
+        peer: u64,
+    },
+    /// A client left the room or stopped polling it
+    Left {
+        /// The id of the client
+        peer: u64,
+    },
+    /// A peer sent a signaling message to this client
+    Signal {
+        /// The id of the peer
+        from: u64,
+        /// The session description or ICE candidate
+        signal: Value,
+    },
+}
+
+pub use signaling::*;
+
+/// The signaling server functions.
+///
+/// These are implemented by hand instead of with `#[server]`. The macro picks the client and request types from its
+/// own features, which are unified separately from the `server_fn` features of this crate and don't always match them.
+mod signaling {
+    use super::{JoinedRoom, RoomEvent};
+    use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+    use server_fn::{
+        codec::{Json, PostUrl},
+        error::NoCustomError,
+        ServerFn, ServerFnError,
+    };
+
+    #[cfg(any(feature = "desktop", feature = "mobile"))]
+    type Client = server_fn::client::reqwest::ReqwestClient;
+    #[cfg(not(any(feature = "desktop", feature = "mobile")))]
+    type Client = server_fn::client::browser::BrowserClient;
+
+    #[cfg(feature = "axum")]
+    type ServerRequest = server_fn::axum_export::http::Request<server_fn::axum_export::body::Body>;
+    #[cfg(not(feature = "axum"))]
+    type ServerRequest = server_fn::request::BrowserMockReq;
+
+    #[cfg(feature = "axum")]
+    type ServerResponse =
+        server_fn::axum_export::http::Response<server_fn::axum_export::body::Body>;
+    #[cfg(not(feature = "axum"))]
+    type ServerResponse = server_fn::response::BrowserMockRes;
+
+    /// Run a server function in place on the server, or call the server from the client
+    async fn call<F: ServerFn<Error = NoCustomError>>(args: F) -> Result<F::Output, ServerFnError> {
+        #[cfg(feature = "server")]
+        return args.run_body().await;
+        #[cfg(not(feature = "server"))]
+        return args.run_on_client().await;
+    }
+
+    /// Implement [`ServerFn`] for the arguments of a signaling function and register it with the server
+    macro_rules! signaling_fn {
+        ($args:ident, $path:literal, $output:ty, |$this:ident| $body:expr) => {
+            impl ServerFn for $args {
+                const PATH: &'static str = $path;
+
+                type Client = Client;
+                type ServerRequest = ServerRequest;
+                type ServerResponse = ServerResponse;
+                type Output = $output;
+                type InputEncoding = PostUrl;
+                type OutputEncoding = Json;
+                type Error = NoCustomError;
+
+                #[cfg(feature = "server")]
+                #[allow(clippy::manual_async_fn)]
+                fn run_body(
+                    self,
+                ) -> impl std::future::Future<Output = Result<$output, ServerFnError>> + Send {
+                    let $this = self;
+                    async move { $body }
+                }
+
+                #[cfg(not(feature = "server"))]
+                async fn run_body(self) -> Result<$output, ServerFnError> {
+                    unreachable!()
+                }
+            }
+
+            #[cfg(feature = "server")]
+            server_fn::inventory::submit! {
+                server_fn::ServerFnTraitObj::new(
+                    $args::PATH,
+                    <PostUrl as server_fn::codec::Encoding>::METHOD,
+                    |req| Box::pin($args::run_on_server(req)),
+                    $args::middlewares,
+                )
+            }
+        };
+    }
+
+    /// The arguments of [`rtc_join`]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct RtcJoin {
+        /// The room to join
+        pub room: String,
+    }
+
+    signaling_fn!(RtcJoin, "/api/rtc_join", JoinedRoom, |args| {
+        Ok(super::server::join(&args.room))
+    });
+
+    /// Join a room. [`use_data_channel`](super::use_data_channel) joins with this server function.
+    pub async fn rtc_join(room: String) -> Result<JoinedRoom, ServerFnError> {
+        call(RtcJoin { room }).await
+    }
+
+    /// The arguments of [`rtc_poll`]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct RtcPoll {
+        /// The room the client joined
+        pub room: String,
+        /// The id of the client
+        pub peer: u64,
+        /// The secret of the client
+        pub secret: u64,
+    }
+
+    signaling_fn!(RtcPoll, "/api/rtc_poll", Vec<RoomEvent>, |args| {
+        super::server::poll(&args.room, args.peer, args.secret).await
+    });
+
+    /// Wait for the next events of a room. [`use_data_channel`](super::use_data_channel) polls this server function.
+    pub async fn rtc_poll(
+        room: String,
+        peer: u64,
+        secret: u64,
+    ) -> Result<Vec<RoomEvent>, ServerFnError> {
+        call(RtcPoll { room, peer, secret }).await
+    }
+
+    /// The arguments of [`rtc_signal`]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct RtcSignal {
+        /// The room the client joined
+        pub room: String,
+        /// The id of the client
+        pub peer: u64,
+        /// The secret of the client
+        pub secret: u64,
+        /// The peer the message is for
+        pub to: u64,
+        /// The session description or ICE candidate
+        pub signal: Value,
+    }
+
+    signaling_fn!(RtcSignal, "/api/rtc_signal", (), |args| {
+        let RtcSignal {
+            room,
+            peer,
+            secret,
+            to,
+            signal,
+        } = args;
+        super::server::signal(&room, peer, secret, to, signal)
+    });
+
+    /// Send a signaling message to a peer in the room. [`use_data_channel`](super::use_data_channel) signals with this
+    /// server function.
+    pub async fn rtc_signal(
+        room: String,
+        peer: u64,
+        secret: u64,
+        to: u64,
+        signal: Value,
+    ) -> Result<(), ServerFnError> {
+        call(RtcSignal {
+            room,
+            peer,
+            secret,
+            to,
+            signal,
+        })
+        .await
+    }
+}
@@ -0,0 +1,195 @@
+use super::{JoinedRoom, RoomEvent};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde_json::Value;
+use server_fn::ServerFnError;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Notify;
+
+/// How long a poll waits for events before it returns without any
+const POLL_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Clients that stop polling for this long have left the room
+const PEER_TIMEOUT: Duration = Duration::from_secs(45);
+
+static ROOMS: Lazy<Mutex<HashMap<String, Room>>> = Lazy::new(Default::default);
+
+#[derive(Default)]
+struct Room {
+    peers: HashMap<u64, Peer>,
+}
+
+struct Peer {
+    secret: u64,
+    /// The events the client has not polled yet
+    events: Vec<RoomEvent>,
+    notify: Arc<Notify>,
+    /// When the client last started or finished a poll. Polls time out before the client does.
+    last_seen: Instant,
+}
+
+impl Room {
+    fn broadcast(&mut self, event: RoomEvent) {
+        for peer in self.peers.values_mut() {
+            peer.events.push(event.clone());
+            peer.notify.notify_one();
+        }
+    }
+
+    /// Remove the clients that stopped polling and tell everyone else they left
+    fn remove_stale_peers(&mut self) {
+        let stale: Vec<u64> = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| peer.last_seen.elapsed() > PEER_TIMEOUT)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in stale {
+            self.peers.remove(&id);
+            self.broadcast(RoomEvent::Left { peer: id });
+        }
+    }
+
+    fn authenticate(&mut self, peer: u64, secret: u64) -> Result<&mut Peer, ServerFnError> {
+        match self.peers.get_mut(&peer) {
+            Some(state) if state.secret == secret => Ok(state),
+            _ => Err(not_in_room()),
+        }
+    }
+}
+
+fn not_in_room() -> ServerFnError {
+    ServerFnError::ServerError("the client is not in the room".to_string())
+}
+
+pub(super) fn join(room: &str) -> JoinedRoom {
+    let mut rooms = ROOMS.lock();
+    // Clean up every room while the lock is held anyway, so rooms everyone left don't pile up
+    for state in rooms.values_mut() {
+        state.remove_stale_peers();
+    }
+    rooms.retain(|_, state| !state.peers.is_empty());
+    let state = rooms.entry(room.to_string()).or_default();
+
+    let mut peer = rand::random::<u64>();
+    while state.peers.contains_key(&peer) {
+        peer = rand::random();
+    }
+    let peers = state.peers.keys().copied().collect();
+    state.broadcast(RoomEvent::Joined { peer });
+    let secret = rand::random();
+    state.peers.insert(
+        peer,
+        Peer {
+            secret,
+            events: Vec::new(),
+            notify: Arc::new(Notify::new()),
+            last_seen: Instant::now(),
+        },
+    );
+    JoinedRoom {
+        peer,
+        secret,
+        peers,
+    }
+}
+
+pub(super) async fn poll(
+    room: &str,
+    peer: u64,
+    secret: u64,
+) -> Result<Vec<RoomEvent>, ServerFnError> {
+    let notify = {
+        let mut rooms = ROOMS.lock();
+        let Some(state) = rooms.get_mut(room) else {
+            return Err(not_in_room());
+        };
+        state.remove_stale_peers();
+        let peer = state.authenticate(peer, secret)?;
+        peer.last_seen = Instant::now();
+        peer.notify.clone()
+    };
+    // Events that arrived before the wait started are kept as a permit by the notify
+    _ = tokio::time::timeout(POLL_TIMEOUT, notify.notified()).await;
+
+    let mut rooms = ROOMS.lock();
+    let Some(state) = rooms.get_mut(room) else {
+        return Err(not_in_room());
+    };
+    let peer = state.authenticate(peer, secret)?;
+    peer.last_seen = Instant::now();
+    Ok(std::mem::take(&mut peer.events))
+}
+
+pub(super) fn signal(
+    room: &str,
+    peer: u64,
+    secret: u64,
+    to: u64,
+    signal: Value,
+) -> Result<(), ServerFnError> {
+    let mut rooms = ROOMS.lock();
+    let Some(state) = rooms.get_mut(room) else {
+        return Err(not_in_room());
+    };
+    state.authenticate(peer, secret)?;
+    if let Some(to) = state.peers.get_mut(&to) {
+        to.events.push(RoomEvent::Signal { from: peer, signal });
+        to.notify.notify_one();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn peers_find_each_other_through_the_room() {
+        let first = join("signaling");
+        let second = join("signaling");
+        assert_eq!(second.peers, [first.peer]);
+
+        // The client that was there first is told about the new client and gets its offer
+        let offer = json!({ "description": { "type": "offer", "sdp": "" } });
+        signal(
+            "signaling",
+            second.peer,
+            second.secret,
+            first.peer,
+            offer.clone(),
+        )
+        .unwrap();
+        let events = poll("signaling", first.peer, first.secret).await.unwrap();
+        assert_eq!(
+            events,
+            [
+                RoomEvent::Joined { peer: second.peer },
+                RoomEvent::Signal {
+                    from: second.peer,
+                    signal: offer
+                }
+            ]
+        );
+
+        // Clients can't read the events of other clients
+        assert!(poll("signaling", first.peer, second.secret).await.is_err());
+        assert!(signal("signaling", first.peer, 0, second.peer, json!({})).is_err());
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn signaling_functions_are_registered() {
+        let paths: Vec<_> = server_fn::axum::server_fn_paths()
+            .map(|(path, _)| path)
+            .collect();
+        for path in ["/api/rtc_join", "/api/rtc_poll", "/api/rtc_signal"] {
+            assert!(paths.contains(&path), "{path} is not registered");
+        }
+    }
+}
@@ -5,12 +5,14 @@ use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
 
+pub use dioxus_interpreter_js::HydrationCompression;
 use dioxus_lib::prelude::dioxus_core::LaunchConfig;
 
+use crate::limits::ServerFnLimits;
 use crate::server::ContextProviders;
 
 /// A ServeConfig is used to configure how to serve a Dioxus application. It contains information about how to serve static assets, and what content to render with [`dioxus-ssr`].
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct ServeConfigBuilder {
     pub(crate) root_id: Option<&'static str>,
     pub(crate) index_html: Option<String>,
@@ -18,10 +20,19 @@ pub struct ServeConfigBuilder {
     pub(crate) incremental: Option<dioxus_isrg::IncrementalRendererConfig>,
     pub(crate) context_providers: ContextProviders,
     pub(crate) streaming_mode: StreamingMode,
+    pub(crate) server_fn_limits: ServerFnLimits,
+    pub(crate) hydration_compression: HydrationCompression,
+    pub(crate) cross_origin_isolated: bool,
 }
 
 impl LaunchConfig for ServeConfigBuilder {}
 
+impl Default for ServeConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ServeConfigBuilder {
     /// Create a new ServeConfigBuilder with incremental static generation disabled and the default index.html settings
     pub fn new() -> Self {
@@ -32,6 +43,9 @@ impl ServeConfigBuilder {
             incremental: None,
             context_providers: Default::default(),
             streaming_mode: StreamingMode::default(),
+            server_fn_limits: ServerFnLimits::default(),
+            hydration_compression: HydrationCompression::default(),
+            cross_origin_isolated: dioxus_cli_config::cross_origin_isolated(),
         }
     }
 
@@ -177,6 +191,56 @@ impl ServeConfigBuilder {
         self
     }
 
+    /// Set the default body limit and timeout of server functions. Server functions can override them with the
+    /// `#[body_limit]` and `#[timeout]` attributes.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # use std::time::Duration;
+    /// # fn app() -> Element { todo!() }
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_cfg(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder().server_fn_limits(
+    ///             dioxus::fullstack::limits::ServerFnLimits::new().with_timeout(Duration::from_secs(30)),
+    ///         )
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn server_fn_limits(mut self, limits: ServerFnLimits) -> Self {
+        self.server_fn_limits = limits;
+        self
+    }
+
+    /// Set how the data the server sends to hydrate the page is compressed. Large payloads are compressed with
+    /// deflate by default. [`HydrationCompression::Brotli`] produces smaller payloads for data heavy pages, but requires
+    /// the `hydration-brotli` feature on both the server and the client.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_cfg(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder()
+    ///             .hydration_compression(dioxus::fullstack::HydrationCompression::None)
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn hydration_compression(mut self, compression: HydrationCompression) -> Self {
+        self.hydration_compression = compression;
+        self
+    }
+
+    /// Send the `Cross-Origin-Opener-Policy` and `Cross-Origin-Embedder-Policy` headers that make the page cross-origin
+    /// isolated. Browsers only share memory with web workers on isolated pages, so apps built with `dx build --threads`
+    /// need them. This is enabled by default for apps built with `--threads`.
+    ///
+    /// Every resource the page loads from another origin has to opt in with CORS or a `Cross-Origin-Resource-Policy`
+    /// header once the page is isolated.
+    pub fn cross_origin_isolated(mut self, isolated: bool) -> Self {
+        self.cross_origin_isolated = isolated;
+        self
+    }
+
     /// Build the ServeConfig. This may fail if the index.html file is not found.
     pub fn build(self) -> Result<ServeConfig, UnableToLoadIndex> {
         // The CLI always bundles static assets into the exe/public directory
@@ -201,6 +265,9 @@ impl ServeConfigBuilder {
             incremental: self.incremental,
             context_providers: self.context_providers,
             streaming_mode: self.streaming_mode,
+            server_fn_limits: self.server_fn_limits,
+            hydration_compression: self.hydration_compression,
+            cross_origin_isolated: self.cross_origin_isolated,
         })
     }
 }
@@ -318,6 +385,9 @@ pub struct ServeConfig {
     pub(crate) incremental: Option<dioxus_isrg::IncrementalRendererConfig>,
     pub(crate) context_providers: ContextProviders,
     pub(crate) streaming_mode: StreamingMode,
+    pub(crate) server_fn_limits: ServerFnLimits,
+    pub(crate) hydration_compression: HydrationCompression,
+    pub(crate) cross_origin_isolated: bool,
 }
 
 impl LaunchConfig for ServeConfig {}
@@ -100,7 +100,8 @@ pub fn launch(
                 None => router = dioxus_router,
             }
 
-            let router = router.into_make_service();
+            // The connect info gives rate limits the address of each client
+            let router = router.into_make_service_with_connect_info::<std::net::SocketAddr>();
             let listener = tokio::net::TcpListener::bind(address).await.unwrap();
 
             axum::serve(listener, router).await.unwrap();
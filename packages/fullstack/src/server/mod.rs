@@ -71,8 +71,12 @@ use axum::{
 use dioxus_lib::prelude::{Element, VirtualDom};
 use http::header::*;
 
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
+use crate::limits::{LimitExceeded, ServerFnLimits};
 use crate::prelude::*;
 
 /// A extension trait with utilities for integrating Dioxus with your Axum router.
@@ -119,7 +123,43 @@ pub trait DioxusRouterExt<S> {
     ///     axum::serve(listener, router).await.unwrap();
     /// }
     /// ```
-    fn register_server_functions_with_context(self, context_providers: ContextProviders) -> Self;
+    fn register_server_functions_with_context(self, context_providers: ContextProviders) -> Self
+    where
+        Self: Sized,
+    {
+        self.register_server_functions_with_limits(context_providers, ServerFnLimits::default())
+    }
+
+    /// Registers server functions with some additional context and the default [`ServerFnLimits`] of their requests.
+    /// Server functions can override the limits with the `#[body_limit]` and `#[timeout]` attributes.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use dioxus_lib::prelude::*;
+    /// # use dioxus_fullstack::prelude::*;
+    /// # use dioxus_fullstack::limits::ServerFnLimits;
+    /// # use std::time::Duration;
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let addr = dioxus::cli_config::fullstack_address_or_localhost();
+    ///     let router = axum::Router::new()
+    ///         // Accept bodies of up to 10MB and cancel server functions after 30 seconds
+    ///         .register_server_functions_with_limits(
+    ///             Default::default(),
+    ///             ServerFnLimits::new()
+    ///                 .with_body_limit(10 * 1024 * 1024)
+    ///                 .with_timeout(Duration::from_secs(30)),
+    ///         )
+    ///         .into_make_service();
+    ///     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    ///     axum::serve(listener, router).await.unwrap();
+    /// }
+    /// ```
+    fn register_server_functions_with_limits(
+        self,
+        context_providers: ContextProviders,
+        limits: ServerFnLimits,
+    ) -> Self;
 
     /// Serves the static WASM for your Dioxus application (except the generated index.html).
     ///
@@ -179,16 +219,21 @@ impl<S> DioxusRouterExt<S> for Router<S>
 where
     S: Send + Sync + Clone + 'static,
 {
-    fn register_server_functions_with_context(
+    fn register_server_functions_with_limits(
         mut self,
         context_providers: ContextProviders,
+        limits: ServerFnLimits,
     ) -> Self {
         use http::method::Method;
 
         for (path, method) in server_fn::axum::server_fn_paths() {
             tracing::trace!("Registering server function: {} {}", method, path);
+            crate::limits::discover(path, || {
+                server_fn::axum::get_server_fn_service(path);
+            });
+            let limits = limits.for_server_fn(path);
             let context_providers = context_providers.clone();
-            let handler = move |req| handle_server_fns_inner(path, context_providers, req);
+            let handler = move |req| handle_server_fns_inner(path, context_providers, limits, req);
             self = match method {
                 Method::GET => self.route(path, get(handler)),
                 Method::POST => self.route(path, post(handler)),
@@ -200,48 +245,14 @@ where
         self
     }
 
-    fn serve_static_assets(mut self) -> Self {
-        use tower_http::services::{ServeDir, ServeFile};
-
+    fn serve_static_assets(self) -> Self {
         let public_path = crate::public_path();
 
         if !public_path.exists() {
             return self;
         }
 
-        // Serve all files in public folder except index.html
-        let dir = std::fs::read_dir(&public_path).unwrap_or_else(|e| {
-            panic!(
-                "Couldn't read public directory at {:?}: {}",
-                &public_path, e
-            )
-        });
-
-        for entry in dir.flatten() {
-            let path = entry.path();
-            if path.ends_with("index.html") {
-                continue;
-            }
-            let route = path
-                .strip_prefix(&public_path)
-                .unwrap()
-                .iter()
-                .map(|segment| {
-                    segment.to_str().unwrap_or_else(|| {
-                        panic!("Failed to convert path segment {:?} to string", segment)
-                    })
-                })
-                .collect::<Vec<_>>()
-                .join("/");
-            let route = format!("/{}", route);
-            if path.is_dir() {
-                self = self.nest_service(&route, ServeDir::new(path).precompressed_br());
-            } else {
-                self = self.nest_service(&route, ServeFile::new(path).precompressed_br());
-            }
-        }
-
-        self
+        self.merge(public_assets(&public_path))
     }
 
     fn serve_dioxus_application<Cfg, Error>(self, cfg: Cfg, app: fn() -> Element) -> Self
@@ -254,13 +265,21 @@ where
             .as_ref()
             .map(|cfg| cfg.context_providers.clone())
             .unwrap_or_default();
+        let limits = cfg
+            .as_ref()
+            .map(|cfg| cfg.server_fn_limits)
+            .unwrap_or_default();
+        let cross_origin_isolated = cfg.as_ref().map_or_else(
+            |_| dioxus_cli_config::cross_origin_isolated(),
+            |cfg| cfg.cross_origin_isolated,
+        );
 
         // Add server functions and render index.html
         let server = self
             .serve_static_assets()
-            .register_server_functions_with_context(context_providers);
+            .register_server_functions_with_limits(context_providers, limits);
 
-        match cfg {
+        let server = match cfg {
             Ok(cfg) => {
                 let ssr_state = SSRState::new(&cfg);
                 server.fallback(
@@ -272,8 +291,136 @@ where
                 tracing::trace!("Failed to create render handler. This is expected if you are only using fullstack for desktop/mobile server functions: {}", err);
                 server
             }
+        };
+
+        match cross_origin_isolated {
+            true => server.layer(axum::middleware::map_response(isolate_cross_origin)),
+            false => server,
+        }
+    }
+}
+
+/// Serve all files in the public folder except index.html
+fn public_assets<S: Clone + Send + Sync + 'static>(public_path: &std::path::Path) -> Router<S> {
+    use tower_http::services::{ServeDir, ServeFile};
+
+    let mut assets = Router::new();
+
+    let dir = std::fs::read_dir(public_path).unwrap_or_else(|e| {
+        panic!(
+            "Couldn't read public directory at {:?}: {}",
+            &public_path, e
+        )
+    });
+
+    for entry in dir.flatten() {
+        let path = entry.path();
+        if path.ends_with("index.html") {
+            continue;
+        }
+        let route = path
+            .strip_prefix(public_path)
+            .unwrap()
+            .iter()
+            .map(|segment| {
+                segment.to_str().unwrap_or_else(|| {
+                    panic!("Failed to convert path segment {:?} to string", segment)
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let route = format!("/{}", route);
+        // Serve the brotli or gzip versions the CLI creates in release builds if the client accepts them
+        if path.is_dir() {
+            assets = assets.nest_service(
+                &route,
+                ServeDir::new(path).precompressed_br().precompressed_gzip(),
+            );
+        } else {
+            assets = assets.nest_service(
+                &route,
+                ServeFile::new(path).precompressed_br().precompressed_gzip(),
+            );
+        }
+    }
+
+    assets.layer(axum::middleware::from_fn(cache_hashed_assets))
+}
+
+/// Make the page cross-origin isolated so the browser lets it share memory with web workers
+async fn isolate_cross_origin(mut response: Response<Body>) -> Response<Body> {
+    let headers = response.headers_mut();
+    headers.insert(
+        HeaderName::from_static("cross-origin-opener-policy"),
+        HeaderValue::from_static("same-origin"),
+    );
+    headers.insert(
+        HeaderName::from_static("cross-origin-embedder-policy"),
+        HeaderValue::from_static("require-corp"),
+    );
+    response
+}
+
+/// Let clients cache assets with a hash in their name forever. The hash manganis adds to the name of bundled assets
+/// changes whenever their contents do, so it doubles as a strong ETag.
+async fn cache_hashed_assets(
+    request: Request<Body>,
+    next: axum::middleware::Next,
+) -> Response<Body> {
+    let Some(hash) = request
+        .uri()
+        .path()
+        .split('/')
+        .find_map(manganis_core::bundled_path_hash)
+        .map(str::to_string)
+    else {
+        return next.run(request).await;
+    };
+    let if_none_match = request.headers().get(IF_NONE_MATCH).cloned();
+
+    let mut response = next.run(request).await;
+    if !response.status().is_success() {
+        return response;
+    }
+
+    // Each encoding of the asset is a different representation, so it needs a different strong ETag
+    let etag = match response.headers().get(CONTENT_ENCODING) {
+        Some(encoding) => format!("\"{hash}-{}\"", encoding.to_str().unwrap_or_default()),
+        None => format!("\"{hash}\""),
+    };
+    // The encoding is picked from the Accept-Encoding header, so caches need to keep each encoding separately
+    if !response.headers().contains_key(VARY) {
+        response
+            .headers_mut()
+            .insert(VARY, HeaderValue::from_static("accept-encoding"));
+    }
+    let not_modified = if_none_match
+        .as_ref()
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+        });
+    if not_modified {
+        let mut not_modified = Response::new(Body::empty());
+        *not_modified.status_mut() = StatusCode::NOT_MODIFIED;
+        if let Some(vary) = response.headers().get(VARY) {
+            not_modified.headers_mut().insert(VARY, vary.clone());
         }
+        response = not_modified;
+    }
+
+    let headers = response.headers_mut();
+    if let Ok(etag) = HeaderValue::from_str(&etag) {
+        headers.insert(ETAG, etag);
     }
+    headers.insert(
+        CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=31536000, immutable"),
+    );
+    response
 }
 
 fn apply_request_parts_to_response<B>(
@@ -444,6 +591,7 @@ fn report_err<E: std::fmt::Display>(e: E) -> Response<axum::body::Body> {
 async fn handle_server_fns_inner(
     path: &str,
     additional_context: ContextProviders,
+    limits: ServerFnLimits,
     req: Request<Body>,
 ) -> impl IntoResponse {
     use server_fn::middleware::Service;
@@ -452,6 +600,32 @@ async fn handle_server_fns_inner(
 
     let future = move || async move {
         let (parts, body) = req.into_parts();
+
+        // Reject bodies that are too large up front if they have a length, and stop reading them once they go over the
+        // limit if they are streamed
+        let body_too_large = Arc::new(AtomicBool::new(false));
+        let body = match limits.body_limit() {
+            Some(limit) => {
+                let length = parts
+                    .headers
+                    .get(CONTENT_LENGTH)
+                    .and_then(|length| length.to_str().ok()?.parse::<usize>().ok());
+                if length.is_some_and(|length| length > limit) {
+                    return LimitExceeded::PayloadTooLarge { limit }.into_response();
+                }
+                let body_too_large = body_too_large.clone();
+                Body::new(http_body_util::BodyExt::map_err(
+                    http_body_util::Limited::new(body, limit),
+                    move |err| {
+                        if err.is::<http_body_util::LengthLimitError>() {
+                            body_too_large.store(true, Ordering::Relaxed);
+                        }
+                        err
+                    },
+                ))
+            }
+            None => body,
+        };
         let req = Request::from_parts(parts.clone(), body);
 
         if let Some(mut service) =
@@ -473,7 +647,22 @@ async fn handle_server_fns_inner(
 
             // actually run the server fn (which may use the server context)
             let fut = with_server_context(server_context.clone(), || service.run(req));
-            let mut res = ProvideServerContext::new(fut, server_context.clone()).await;
+            let fut = ProvideServerContext::new(fut, server_context.clone());
+            let mut res = match limits.timeout() {
+                #[cfg(not(target_arch = "wasm32"))]
+                Some(timeout) => match tokio::time::timeout(timeout, fut).await {
+                    Ok(res) => res,
+                    Err(_) => return LimitExceeded::TimedOut { after: timeout }.into_response(),
+                },
+                _ => fut.await,
+            };
+
+            // The server function failed to read the body because it was too large
+            if let Some(limit) = limits.body_limit() {
+                if body_too_large.load(Ordering::Relaxed) {
+                    return LimitExceeded::PayloadTooLarge { limit }.into_response();
+                }
+            }
 
             // it it accepts text/html (i.e., is a plain form post) and doesn't already have a
             // Location set, then redirect to Referer
@@ -535,3 +724,108 @@ async fn handle_server_fns_inner(
         future().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::ServiceExt;
+
+    const ASSET: &str = "/assets/style-0123456789abcdef.css";
+
+    /// A public folder with a hashed asset, the compressed versions the CLI creates and an asset without a hash
+    fn public_folder(name: &str) -> std::path::PathBuf {
+        let public =
+            std::env::temp_dir().join(format!("dioxus-fullstack-{name}-{}", std::process::id()));
+        let assets = public.join("assets");
+        std::fs::create_dir_all(&assets).unwrap();
+        std::fs::write(assets.join("style-0123456789abcdef.css"), "identity").unwrap();
+        std::fs::write(assets.join("style-0123456789abcdef.css.br"), "brotli").unwrap();
+        std::fs::write(assets.join("style-0123456789abcdef.css.gz"), "gzip").unwrap();
+        std::fs::write(public.join("robots.txt"), "User-agent: *").unwrap();
+        public
+    }
+
+    async fn get(
+        public: &std::path::Path,
+        path: &str,
+        headers: &[(HeaderName, &str)],
+    ) -> Response<Body> {
+        let mut request = Request::builder().uri(path);
+        for (name, value) in headers {
+            request = request.header(name, *value);
+        }
+        public_assets::<()>(public)
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn text(response: Response<Body>) -> String {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn serves_the_encoding_the_client_accepts() {
+        let public = public_folder("encoding");
+
+        let response = get(&public, ASSET, &[(ACCEPT_ENCODING, "gzip, br")]).await;
+        assert_eq!(response.headers()[CONTENT_ENCODING], "br");
+        assert_eq!(response.headers()[ETAG], "\"0123456789abcdef-br\"");
+        assert_eq!(text(response).await, "brotli");
+
+        let response = get(&public, ASSET, &[(ACCEPT_ENCODING, "gzip")]).await;
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[ETAG], "\"0123456789abcdef-gzip\"");
+        assert_eq!(text(response).await, "gzip");
+
+        let response = get(&public, ASSET, &[]).await;
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(response.headers()[ETAG], "\"0123456789abcdef\"");
+        assert_eq!(
+            response.headers()[CACHE_CONTROL],
+            "public, max-age=31536000, immutable"
+        );
+        assert_eq!(text(response).await, "identity");
+
+        _ = std::fs::remove_dir_all(public);
+    }
+
+    #[tokio::test]
+    async fn matching_etags_are_not_modified() {
+        let public = public_folder("etag");
+
+        let response = get(
+            &public,
+            ASSET,
+            &[
+                (ACCEPT_ENCODING, "br"),
+                (IF_NONE_MATCH, "\"other\", W/\"0123456789abcdef-br\""),
+            ],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[ETAG], "\"0123456789abcdef-br\"");
+        assert_eq!(response.headers()[VARY], "accept-encoding");
+        assert_eq!(text(response).await, "");
+
+        // The ETag of another encoding doesn't match
+        let response = get(
+            &public,
+            ASSET,
+            &[(IF_NONE_MATCH, "\"0123456789abcdef-br\"")],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(text(response).await, "identity");
+
+        // Files without a hash in their name may change, so they don't get an ETag
+        let response = get(&public, "/robots.txt", &[(IF_NONE_MATCH, "*")]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(ETAG).is_none());
+
+        _ = std::fs::remove_dir_all(public);
+    }
+}
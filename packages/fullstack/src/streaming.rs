@@ -25,6 +25,10 @@
 //!     window.dx_hydrate(2, "suspenseboundarydata");
 //! </script>
 //! ```
+//!
+//! The data for the initial page and for each resolved suspense boundary is sent in its own hydration payload, so the
+//! client can hydrate each boundary as soon as its chunk arrives. Payloads are compressed if they are large and include
+//! a checksum the client verifies before it hydrates. See [`dioxus_interpreter_js::encode_hydration_payload`].
 
 use futures_channel::mpsc::Sender;
 
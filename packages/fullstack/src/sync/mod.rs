@@ -0,0 +1,391 @@
+//! Signals that are kept in sync between every client connected to the server, and the presence of those clients.
+//!
+//! The server is the authority over the value of each channel. It keeps the values in a [`SyncHub`] and clients talk to
+//! it over a websocket: mount [`synced_signals`] on the router of the server at [`DEFAULT_SYNC_ENDPOINT`]. When a
+//! client writes to a [`use_synced_signal`], the new value is sent to the server, which can accept, change or reject it
+//! with [`SyncHub::on_update`] and sends the result to every client on the channel. [`use_presence`] shares a small
+//! state like a name or a cursor position with the other clients on a channel.
+//!
+//! ```rust, ignore
+//! // On the server
+//! let hub = SyncHub::new().on_update("todos", |update: Update<Vec<String>>| {
+//!     // Merge the lists if another client changed them first
+//!     match (update.conflict, update.current) {
+//!         (true, Some(mut current)) => {
+//!             current.extend(update.proposed.into_iter().filter(|todo| !current.contains(todo)));
+//!             Some(current)
+//!         }
+//!         _ => Some(update.proposed),
+//!     }
+//! });
+//! let router = axum::Router::new()
+//!     .route(DEFAULT_SYNC_ENDPOINT, synced_signals(hub))
+//!     .serve_dioxus_application(ServeConfig::new().unwrap(), app);
+//!
+//! // In the app
+//! #[derive(Clone, Serialize, Deserialize)]
+//! struct Cursor {
+//!     name: String,
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! fn Todos() -> Element {
+//!     let mut todos = use_synced_signal("todos", Vec::<String>::new);
+//!     let presence = use_presence("todos", || Cursor { name: "ferris".into(), x: 0, y: 0 });
+//!
+//!     rsx! {
+//!         div { "{presence.peers().len()} other people are editing" }
+//!         for todo in todos() {
+//!             li { "{todo}" }
+//!         }
+//!         button { onclick: move |_| todos.write().push("New todo".into()), "Add" }
+//!     }
+//! }
+//! ```
+//!
+//! Synced signals start with their initial value on the server and while the page hydrates, then switch to the value
+//! of the server once the socket connects. If the channel doesn't have a value yet, the first client sets it to its
+//! initial value.
+
+use dioxus_lib::document::{document, Eval, EvalError};
+use dioxus_lib::prelude::*;
+use futures_channel::mpsc::{unbounded, UnboundedSender};
+use futures_util::{
+    future::{select, Either},
+    pin_mut, StreamExt,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{cell::RefCell, rc::Rc};
+
+#[cfg(feature = "axum")]
+mod server;
+#[cfg(feature = "axum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub use server::*;
+
+/// The path clients connect to unless [`SyncOptions::endpoint`] is set
+pub const DEFAULT_SYNC_ENDPOINT: &str = "/api/sync";
+
+/// Opens a socket that reconnects until the app closes it. The messages that set up the channel are sent again on every
+/// new socket.
+const SCRIPT: &str = r#"
+const url = new URL(await dioxus.recv(), window.location.href);
+url.protocol = url.protocol === "https:" ? "wss:" : "ws:";
+const restore = {};
+let pending = null;
+let closed = false;
+let socket;
+const connect = () => {
+    socket = new WebSocket(url);
+    socket.onopen = () => {
+        for (const message of [restore.join, restore.presence, pending]) {
+            if (message) socket.send(message);
+        }
+        pending = null;
+    };
+    socket.onmessage = (event) => dioxus.send(JSON.parse(event.data));
+    socket.onclose = () => {
+        if (!closed) setTimeout(connect, 1000);
+    };
+};
+connect();
+while (true) {
+    const message = await dioxus.recv();
+    if (message === null) {
+        closed = true;
+        socket.close();
+        return;
+    }
+    const text = JSON.stringify(message);
+    if (message.type === "join" || message.type === "presence") {
+        restore[message.type] = text;
+    }
+    if (socket.readyState === WebSocket.OPEN) {
+        socket.send(text);
+    } else if (message.type === "update") {
+        pending = text;
+    }
+}
+"#;
+
+/// A message from a client to the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ClientMessage {
+    /// The first message of every socket
+    Join { channel: String },
+    /// Set the value of the channel. `version` is the version of the value the client changed.
+    Update { version: u64, value: Value },
+    /// Share the state of the client with the other clients
+    Presence { state: Value },
+}
+
+/// A message from the server to a client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ServerMessage {
+    /// The id of the client on the channel
+    Welcome { peer: u64 },
+    /// The value of the channel. Version 0 means the channel doesn't have a value yet.
+    Value { version: u64, value: Value },
+    /// The clients on the channel that shared their state
+    Peers { peers: Vec<Peer<Value>> },
+}
+
+/// A client connected to a channel and the state it shares with [`use_presence`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Peer<P> {
+    /// The id the server gave the client. It changes when the client reconnects.
+    pub id: u64,
+    /// The state the client shares
+    pub state: P,
+}
+
+/// The channel of a synced signal and where the server is
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncOptions {
+    channel: String,
+    endpoint: String,
+}
+
+impl SyncOptions {
+    /// Sync with the clients on a channel
+    pub fn new(channel: impl ToString) -> Self {
+        Self {
+            channel: channel.to_string(),
+            endpoint: DEFAULT_SYNC_ENDPOINT.to_string(),
+        }
+    }
+
+    /// Set the path [`synced_signals`] is mounted at. Defaults to [`DEFAULT_SYNC_ENDPOINT`].
+    pub fn endpoint(mut self, endpoint: impl ToString) -> Self {
+        self.endpoint = endpoint.to_string();
+        self
+    }
+}
+
+/// Create a signal that is kept in sync with every client on the channel.
+///
+/// Writes are sent to the server, which decides the value of the channel and sends it to every client. See the
+/// [module docs](self) for how to set up the server.
+///
+/// ```rust, ignore
+/// fn Counter() -> Element {
+///     let mut count = use_synced_signal("count", || 0);
+///     rsx! { button { onclick: move |_| count += 1, "Clicked {count} times by everyone" } }
+/// }
+/// ```
+#[track_caller]
+#[must_use]
+pub fn use_synced_signal<T>(channel: impl ToString, init: impl FnOnce() -> T) -> Signal<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    use_synced_signal_with(SyncOptions::new(channel), init)
+}
+
+/// Create a signal that is kept in sync with every client on the channel with [`SyncOptions`] for the endpoint
+#[track_caller]
+#[must_use]
+pub fn use_synced_signal_with<T>(options: SyncOptions, init: impl FnOnce() -> T) -> Signal<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let caller = std::panic::Location::caller();
+    let (signal, synced, sender) = use_hook(|| {
+        let mut signal = Signal::<T>::new_with_caller(init(), caller);
+        // The version and value the server sent last
+        let synced: Rc<RefCell<Option<(u64, Value)>>> = Default::default();
+        let sender = connect(&options, {
+            let synced = synced.clone();
+            move |message| {
+                let ServerMessage::Value { version, value } = message else {
+                    return None;
+                };
+                let local = serde_json::to_value(&*signal.peek()).ok()?;
+                if version == 0 {
+                    // Nobody set the channel yet, so it starts with our value
+                    *synced.borrow_mut() = Some((0, Value::Null));
+                    return Some(ClientMessage::Update {
+                        version: 0,
+                        value: local,
+                    });
+                }
+                *synced.borrow_mut() = Some((version, value.clone()));
+                if local != value {
+                    match serde_json::from_value(value) {
+                        Ok(value) => signal.set(value),
+                        Err(err) => tracing::error!("Failed to read synced signal: {err}"),
+                    }
+                }
+                None
+            }
+        });
+        (signal, synced, sender)
+    });
+
+    use_effect(move || {
+        let Ok(value) = serde_json::to_value(&*signal.read()) else {
+            return;
+        };
+        // Changes made before the server sent its value are replaced by it
+        let synced = synced.borrow();
+        let Some((version, current)) = &*synced else {
+            return;
+        };
+        if *current != value {
+            _ = sender.unbounded_send(ClientMessage::Update {
+                version: *version,
+                value,
+            });
+        }
+    });
+
+    signal
+}
+
+/// The clients on a channel and the state they share. Created with [`use_presence`].
+pub struct Presence<P: 'static> {
+    id: Signal<Option<u64>>,
+    peers: Signal<Vec<Peer<P>>>,
+    sender: CopyValue<UnboundedSender<ClientMessage>>,
+}
+
+impl<P> Clone for Presence<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for Presence<P> {}
+
+impl<P: Serialize + Clone + 'static> Presence<P> {
+    /// The id the server gave this client, or `None` until it connects
+    pub fn id(&self) -> Option<u64> {
+        (self.id)()
+    }
+
+    /// The other clients on the channel and their state
+    pub fn peers(&self) -> Vec<Peer<P>> {
+        let id = self.id();
+        self.peers
+            .read()
+            .iter()
+            .filter(|peer| Some(peer.id) != id)
+            .cloned()
+            .collect()
+    }
+
+    /// Share a new state with the other clients
+    pub fn set(&self, state: P) {
+        if let Ok(state) = serde_json::to_value(state) {
+            _ = self
+                .sender
+                .read()
+                .unbounded_send(ClientMessage::Presence { state });
+        }
+    }
+}
+
+/// Track the clients on a channel and share a state with them, like the name of the user or the position of their
+/// cursor
+#[track_caller]
+pub fn use_presence<P>(channel: impl ToString, state: impl FnOnce() -> P) -> Presence<P>
+where
+    P: Serialize + DeserializeOwned + Clone + 'static,
+{
+    use_presence_with(SyncOptions::new(channel), state)
+}
+
+/// Track the clients on a channel with [`SyncOptions`] for the endpoint
+#[track_caller]
+pub fn use_presence_with<P>(options: SyncOptions, state: impl FnOnce() -> P) -> Presence<P>
+where
+    P: Serialize + DeserializeOwned + Clone + 'static,
+{
+    use_hook(|| {
+        let mut id = Signal::new(None);
+        let mut peers = Signal::new(Vec::new());
+        let sender = connect(&options, move |message| {
+            match message {
+                ServerMessage::Welcome { peer } => id.set(Some(peer)),
+                ServerMessage::Peers { peers: all } => peers.set(
+                    all.into_iter()
+                        .filter_map(|peer| {
+                            Some(Peer {
+                                id: peer.id,
+                                state: serde_json::from_value(peer.state).ok()?,
+                            })
+                        })
+                        .collect(),
+                ),
+                ServerMessage::Value { .. } => {}
+            }
+            None
+        });
+        let presence = Presence {
+            id,
+            peers,
+            sender: CopyValue::new(sender),
+        };
+        presence.set(state());
+        presence
+    })
+}
+
+/// Connect to a channel. Messages for the server are sent through the returned sender and `on_message` can answer the
+/// messages of the server. The socket closes when the component is dropped.
+fn connect(
+    options: &SyncOptions,
+    mut on_message: impl FnMut(ServerMessage) -> Option<ClientMessage> + 'static,
+) -> UnboundedSender<ClientMessage> {
+    let (sender, mut outgoing) = unbounded();
+    _ = sender.unbounded_send(ClientMessage::Join {
+        channel: options.channel.clone(),
+    });
+    let url = format!(
+        "{}{}",
+        server_fn::client::get_server_url(),
+        options.endpoint
+    );
+
+    spawn(async move {
+        let mut socket = Socket(document().eval(SCRIPT.to_string()));
+        // The server can't open sockets while it renders the page
+        if socket.0.send(url).is_err() {
+            return;
+        }
+        loop {
+            let next = {
+                let incoming = socket.0.recv::<ServerMessage>();
+                pin_mut!(incoming);
+                match select(incoming, outgoing.next()).await {
+                    Either::Left((message, _)) => Either::Left(message),
+                    Either::Right((message, _)) => Either::Right(message),
+                }
+            };
+            let reply = match next {
+                Either::Left(Ok(message)) => on_message(message),
+                Either::Left(Err(EvalError::Serialization(_))) => None,
+                Either::Left(Err(_)) | Either::Right(None) => return,
+                Either::Right(Some(message)) => Some(message),
+            };
+            if let Some(reply) = reply {
+                _ = socket.0.send(reply);
+            }
+        }
+    });
+
+    sender
+}
+
+/// Closes the socket when the task that reads from it is dropped with its component
+struct Socket(Eval);
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        _ = self.0.send(Value::Null);
+    }
+}
@@ -0,0 +1,435 @@
+use super::{ClientMessage, Peer, ServerMessage};
+use axum::{
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    routing::MethodRouter,
+};
+use futures_util::{
+    future::{select, Either},
+    pin_mut,
+};
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// How many changes a slow client can fall behind before it is sent the whole state of the channel instead
+const CHANNEL_CAPACITY: usize = 64;
+
+/// An update a client made to the value of a channel, passed to the callback of [`SyncHub::on_update`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update<T> {
+    /// The id of the client that made the update
+    pub peer: u64,
+    /// The value of the channel on the server, or `None` if the channel doesn't have a value yet
+    pub current: Option<T>,
+    /// The value the client wants to set
+    pub proposed: T,
+    /// If another client changed the value after this client last saw it. The proposed value is based on an older value
+    /// than [`Update::current`].
+    pub conflict: bool,
+}
+
+type UpdateHandler = Arc<dyn Fn(Update<Value>) -> Option<Value> + Send + Sync>;
+
+/// The values of the channels of [`use_synced_signal`](super::use_synced_signal) and the clients connected to them.
+///
+/// The hub only keeps the values in memory. Read them with [`SyncHub::get`] to save them somewhere else.
+#[derive(Clone, Default)]
+pub struct SyncHub {
+    channels: Arc<Mutex<HashMap<String, Channel>>>,
+    handlers: Arc<Mutex<HashMap<String, UpdateHandler>>>,
+    next_peer: Arc<AtomicU64>,
+}
+
+struct Channel {
+    value: Option<Value>,
+    /// Incremented every time the value changes. Version 0 is the channel without a value.
+    version: u64,
+    /// The connected clients and the state they share, if any
+    peers: BTreeMap<u64, Option<Value>>,
+    events: broadcast::Sender<ServerMessage>,
+}
+
+impl Default for Channel {
+    fn default() -> Self {
+        Self {
+            value: None,
+            version: 0,
+            peers: BTreeMap::new(),
+            events: broadcast::channel(CHANNEL_CAPACITY).0,
+        }
+    }
+}
+
+impl Channel {
+    fn value_message(&self) -> ServerMessage {
+        ServerMessage::Value {
+            version: self.version,
+            value: self.value.clone().unwrap_or_default(),
+        }
+    }
+
+    fn peers_message(&self) -> ServerMessage {
+        ServerMessage::Peers {
+            peers: self
+                .peers
+                .iter()
+                .filter_map(|(id, state)| {
+                    Some(Peer {
+                        id: *id,
+                        state: state.clone()?,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    fn set(&mut self, value: Value) {
+        self.value = Some(value);
+        self.version += 1;
+        _ = self.events.send(self.value_message());
+    }
+}
+
+impl SyncHub {
+    /// Create a hub without any values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decide what happens when a client updates a channel. The callback returns the new value of the channel, or
+    /// `None` to reject the update and send the client the current value again.
+    ///
+    /// Without a callback, every update is accepted and the last update wins. The callback runs while the channel is
+    /// locked, so it must not use the hub.
+    pub fn on_update<T: Serialize + DeserializeOwned + 'static>(
+        self,
+        channel: impl ToString,
+        on_update: impl Fn(Update<T>) -> Option<T> + Send + Sync + 'static,
+    ) -> Self {
+        let handler: UpdateHandler = Arc::new(move |update: Update<Value>| {
+            let update = Update {
+                peer: update.peer,
+                current: update
+                    .current
+                    .and_then(|current| serde_json::from_value(current).ok()),
+                proposed: serde_json::from_value(update.proposed).ok()?,
+                conflict: update.conflict,
+            };
+            serde_json::to_value(on_update(update)?).ok()
+        });
+        self.handlers.lock().insert(channel.to_string(), handler);
+        self
+    }
+
+    /// Get the value of a channel
+    pub fn get<T: DeserializeOwned>(&self, channel: &str) -> Option<T> {
+        let channels = self.channels.lock();
+        let value = channels.get(channel)?.value.clone()?;
+        serde_json::from_value(value).ok()
+    }
+
+    /// Set the value of a channel and send it to every client on the channel
+    pub fn set<T: Serialize>(&self, channel: &str, value: T) -> Result<(), serde_json::Error> {
+        let value = serde_json::to_value(value)?;
+        self.channels
+            .lock()
+            .entry(channel.to_string())
+            .or_default()
+            .set(value);
+        Ok(())
+    }
+
+    /// Get the clients on a channel that share their state with [`use_presence`](super::use_presence)
+    pub fn peers<P: DeserializeOwned>(&self, channel: &str) -> Vec<Peer<P>> {
+        let channels = self.channels.lock();
+        let Some(channel) = channels.get(channel) else {
+            return Vec::new();
+        };
+        channel
+            .peers
+            .iter()
+            .filter_map(|(id, state)| {
+                Some(Peer {
+                    id: *id,
+                    state: serde_json::from_value(state.clone()?).ok()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Add a client to a channel. Returns the messages the client starts with.
+    fn join(&self, channel: &str) -> (Member, Vec<ServerMessage>) {
+        let peer = self.next_peer.fetch_add(1, Ordering::Relaxed);
+        let mut channels = self.channels.lock();
+        let state = channels.entry(channel.to_string()).or_default();
+        state.peers.insert(peer, None);
+        let member = Member {
+            hub: self.clone(),
+            channel: channel.to_string(),
+            peer,
+            events: state.events.subscribe(),
+        };
+        let messages = vec![
+            ServerMessage::Welcome { peer },
+            state.value_message(),
+            state.peers_message(),
+        ];
+        (member, messages)
+    }
+
+    /// Handle a message from a client. Returns a message for only that client if there is one.
+    fn handle(&self, channel: &str, peer: u64, message: ClientMessage) -> Option<ServerMessage> {
+        let handler = self.handlers.lock().get(channel).cloned();
+        let mut channels = self.channels.lock();
+        let state = channels.get_mut(channel)?;
+        match message {
+            ClientMessage::Join { .. } => None,
+            ClientMessage::Update { version, value } => {
+                let update = Update {
+                    peer,
+                    current: state.value.clone(),
+                    proposed: value,
+                    conflict: version != state.version,
+                };
+                let value = match handler {
+                    Some(handler) => handler(update),
+                    None => Some(update.proposed),
+                };
+                match value {
+                    Some(value) => {
+                        state.set(value);
+                        None
+                    }
+                    // Roll the client back to the value everyone else has
+                    None => Some(state.value_message()),
+                }
+            }
+            ClientMessage::Presence { state: shared } => {
+                state.peers.insert(peer, Some(shared));
+                _ = state.events.send(state.peers_message());
+                None
+            }
+        }
+    }
+
+    /// Remove a client from a channel
+    fn leave(&self, channel: &str, peer: u64) {
+        let mut channels = self.channels.lock();
+        let Some(state) = channels.get_mut(channel) else {
+            return;
+        };
+        // Clients without a shared state are not in the list of peers, so the others don't need to know
+        if let Some(Some(_)) = state.peers.remove(&peer) {
+            _ = state.events.send(state.peers_message());
+        }
+    }
+
+    /// The whole state of a channel for a client that fell behind
+    fn catch_up(&self, channel: &str) -> Vec<ServerMessage> {
+        let channels = self.channels.lock();
+        match channels.get(channel) {
+            Some(state) => vec![state.value_message(), state.peers_message()],
+            None => Vec::new(),
+        }
+    }
+}
+
+/// A client connected to a channel. It leaves the channel when it is dropped.
+struct Member {
+    hub: SyncHub,
+    channel: String,
+    peer: u64,
+    events: broadcast::Receiver<ServerMessage>,
+}
+
+impl Drop for Member {
+    fn drop(&mut self) {
+        self.hub.leave(&self.channel, self.peer);
+    }
+}
+
+/// A websocket route that keeps the [`use_synced_signal`](super::use_synced_signal) and
+/// [`use_presence`](super::use_presence) hooks of the clients in sync with the hub
+pub fn synced_signals<S>(hub: SyncHub) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    axum::routing::get(move |upgrade: WebSocketUpgrade| async move {
+        upgrade.on_upgrade(move |socket| serve_socket(hub, socket))
+    })
+}
+
+async fn serve_socket(hub: SyncHub, mut socket: WebSocket) {
+    // Every socket starts by joining a channel
+    let channel = loop {
+        match socket.recv().await {
+            Some(Ok(Message::Text(text))) => {
+                if let Ok(ClientMessage::Join { channel }) = serde_json::from_str(&text) {
+                    break channel;
+                }
+            }
+            Some(Ok(_)) => {}
+            _ => return,
+        }
+    };
+
+    let (mut member, messages) = hub.join(&channel);
+    if send(&mut socket, messages).await.is_err() {
+        return;
+    }
+
+    loop {
+        let next = {
+            let incoming = socket.recv();
+            let event = member.events.recv();
+            pin_mut!(incoming, event);
+            match select(incoming, event).await {
+                Either::Left((message, _)) => Either::Left(message),
+                Either::Right((event, _)) => Either::Right(event),
+            }
+        };
+        let messages = match next {
+            Either::Left(Some(Ok(Message::Text(text)))) => {
+                let Ok(message) = serde_json::from_str(&text) else {
+                    continue;
+                };
+                hub.handle(&channel, member.peer, message)
+                    .into_iter()
+                    .collect()
+            }
+            Either::Left(Some(Ok(Message::Close(_))) | Some(Err(_)) | None) => return,
+            Either::Left(Some(Ok(_))) => continue,
+            Either::Right(Ok(event)) => vec![event],
+            Either::Right(Err(RecvError::Lagged(_))) => hub.catch_up(&channel),
+            Either::Right(Err(RecvError::Closed)) => return,
+        };
+        if send(&mut socket, messages).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn send(socket: &mut WebSocket, messages: Vec<ServerMessage>) -> Result<(), axum::Error> {
+    for message in messages {
+        let text = serde_json::to_string(&message).unwrap();
+        socket.send(Message::Text(text)).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn clients_share_the_value_of_a_channel() {
+        let hub = SyncHub::new();
+        let (mut first, messages) = hub.join("count");
+        assert_eq!(
+            messages,
+            [
+                ServerMessage::Welcome { peer: 0 },
+                ServerMessage::Value {
+                    version: 0,
+                    value: Value::Null
+                },
+                ServerMessage::Peers { peers: vec![] },
+            ]
+        );
+
+        // The first client sets the empty channel to its initial value
+        let update = ClientMessage::Update {
+            version: 0,
+            value: json!(1),
+        };
+        assert_eq!(hub.handle("count", first.peer, update), None);
+        let changed = ServerMessage::Value {
+            version: 1,
+            value: json!(1),
+        };
+        assert_eq!(first.events.try_recv().unwrap(), changed);
+
+        // Clients that join later start with the value of the server
+        let (_second, messages) = hub.join("count");
+        assert_eq!(messages[1], changed);
+        assert_eq!(hub.get::<i32>("count"), Some(1));
+    }
+
+    #[test]
+    fn the_server_resolves_conflicts() {
+        let hub = SyncHub::new().on_update("items", |update: Update<Vec<String>>| {
+            if update.proposed.is_empty() {
+                return None;
+            }
+            match (update.conflict, update.current) {
+                (true, Some(mut current)) => {
+                    current.extend(update.proposed);
+                    Some(current)
+                }
+                _ => Some(update.proposed),
+            }
+        });
+        hub.set("items", vec!["a"]).unwrap();
+        let (member, _) = hub.join("items");
+
+        // Another client changed the list after this client last saw it, so the lists are merged
+        let update = ClientMessage::Update {
+            version: 0,
+            value: json!(["b"]),
+        };
+        assert_eq!(hub.handle("items", member.peer, update), None);
+        assert_eq!(hub.get::<Vec<String>>("items").unwrap(), ["a", "b"]);
+
+        // Rejected updates roll the client back
+        let update = ClientMessage::Update {
+            version: 2,
+            value: json!([]),
+        };
+        assert_eq!(
+            hub.handle("items", member.peer, update),
+            Some(ServerMessage::Value {
+                version: 2,
+                value: json!(["a", "b"])
+            })
+        );
+    }
+
+    #[test]
+    fn peers_share_their_presence() {
+        let hub = SyncHub::new();
+        let (mut watcher, _) = hub.join("doc");
+        let (editor, _) = hub.join("doc");
+
+        let presence = ClientMessage::Presence {
+            state: json!("ferris"),
+        };
+        hub.handle("doc", editor.peer, presence);
+        let peers = vec![Peer {
+            id: editor.peer,
+            state: json!("ferris"),
+        }];
+        assert_eq!(
+            watcher.events.try_recv().unwrap(),
+            ServerMessage::Peers {
+                peers: peers.clone()
+            }
+        );
+        assert_eq!(hub.peers::<String>("doc")[0].state, "ferris");
+
+        // Everyone is told when a peer leaves
+        drop(editor);
+        assert_eq!(
+            watcher.events.try_recv().unwrap(),
+            ServerMessage::Peers { peers: vec![] }
+        );
+    }
+}
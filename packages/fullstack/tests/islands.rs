@@ -0,0 +1,98 @@
+//! Islands are rendered into their own mount point with the props and hydration data the client needs
+#![cfg(feature = "server")]
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+
+#[island(hydrate = "visible")]
+fn Counter(initial: i32) -> Element {
+    rsx! {
+        button { "Count: {initial}" }
+    }
+}
+
+#[island]
+fn Panel(label: String) -> Element {
+    rsx! {
+        p { "{label}" }
+        Counter { initial: 2 }
+    }
+}
+
+fn app() -> Element {
+    rsx! {
+        h1 { "Static heading" }
+        Counter { initial: 5 }
+        Panel { label: "Panel" }
+    }
+}
+
+#[tokio::test]
+async fn islands_render_mount_points() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.wait_for_suspense().await;
+    let html = dioxus_ssr::render(&dom);
+
+    // The content outside of islands is plain html
+    assert!(html.contains("<h1>Static heading</h1>"), "{html}");
+
+    // Each top level island gets a mount point with its strategy, props and hydration data
+    assert_eq!(html.matches("data-island=").count(), 2, "{html}");
+    assert!(html.contains(r#"data-island="Counter""#), "{html}");
+    assert!(html.contains(r#"data-island="Panel""#), "{html}");
+    assert!(html.contains(r#"data-island-hydrate="visible""#), "{html}");
+    assert!(html.contains(r#"data-island-hydrate="load""#), "{html}");
+    assert!(html.contains("data-island-props="), "{html}");
+    assert!(html.contains("data-island-data="), "{html}");
+
+    // Every island is its own virtual dom, so the hydration ids of each island start at zero
+    assert!(
+        html.contains(r#"<button data-node-hydration="0"><!--node-id1-->Count: 5"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<p data-node-hydration="0"><!--node-id1-->Panel"#),
+        "{html}"
+    );
+
+    // Islands inside of another island are rendered inline as part of their parent
+    assert!(
+        html.contains(r#"<button data-node-hydration="2"><!--node-id3-->Count: 2"#),
+        "{html}"
+    );
+}
+
+#[island]
+fn Greeting() -> Element {
+    let name = use_server_future(|| async {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        "ferris".to_string()
+    })?;
+    rsx! {
+        p { "Hello {name().unwrap_or_default()}" }
+    }
+}
+
+fn suspended_app() -> Element {
+    rsx! {
+        Greeting {}
+    }
+}
+
+#[tokio::test]
+async fn islands_wait_for_their_server_futures() {
+    let mut dom = VirtualDom::new(suspended_app);
+    dom.rebuild_in_place();
+    dom.wait_for_suspense().await;
+    let html = dioxus_ssr::render(&dom);
+
+    // The island is rendered with the value of its server future instead of the fallback
+    assert!(html.contains("Hello ferris"), "{html}");
+    let data = html
+        .split(r#"data-island-data=""#)
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap();
+    assert!(!data.is_empty(), "{html}");
+}
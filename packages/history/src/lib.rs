@@ -20,6 +20,25 @@ pub fn provide_history_context(history: Rc<dyn History>) {
     provide_context(history);
 }
 
+/// The position a page was scrolled to in pixels
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScrollPosition {
+    /// The horizontal scroll offset
+    pub x: f64,
+    /// The vertical scroll offset
+    pub y: f64,
+}
+
+/// An entry in the navigation history of a [`History`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryEntry {
+    /// The route of the entry, without the prefix
+    pub route: String,
+    /// The state that was stored with the entry with [`History::push_with_state`] or
+    /// [`History::replace_with_state`]
+    pub state: Option<String>,
+}
+
 pub trait History {
     /// Get the path of the current URL.
     ///
@@ -253,6 +272,107 @@ pub trait History {
     #[allow(unused_variables)]
     fn updater(&self, callback: Arc<dyn Fn() + Send + Sync>) {}
 
+    /// Remember the scroll position of the current page.
+    ///
+    /// The router calls this before it navigates away from a page so the position can be restored
+    /// with [`History::scroll_position`] when the user navigates back to it. If a
+    /// [`HistoryProvider`] cannot store scroll positions, it should do nothing.
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// history.set_scroll_position(ScrollPosition { x: 0.0, y: 200.0 });
+    /// history.push("/other".to_string());
+    /// assert_eq!(history.scroll_position(), None);
+    ///
+    /// history.go_back();
+    /// assert_eq!(history.scroll_position(), Some(ScrollPosition { x: 0.0, y: 200.0 }));
+    /// ```
+    #[allow(unused_variables)]
+    fn set_scroll_position(&self, position: ScrollPosition) {}
+
+    /// Get the scroll position that was saved for the current page with
+    /// [`History::set_scroll_position`].
+    fn scroll_position(&self) -> Option<ScrollPosition> {
+        None
+    }
+
+    /// Go to another page and store `state` with the new history entry.
+    ///
+    /// The state is a serialized payload, like JSON, that stays with the entry until it is replaced.
+    /// It can be read with [`History::current_state`] when the user comes back to the entry. If a
+    /// [`HistoryProvider`] cannot store state, it should push the route without the state.
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// history.push_with_state("/search".to_string(), r#"{"filter":"open"}"#.to_string());
+    /// history.push("/other".to_string());
+    /// assert_eq!(history.current_state(), None);
+    ///
+    /// history.go_back();
+    /// assert_eq!(history.current_state().as_deref(), Some(r#"{"filter":"open"}"#));
+    /// ```
+    #[allow(unused_variables)]
+    fn push_with_state(&self, route: String, state: String) {
+        self.push(route)
+    }
+
+    /// Replace the current page with another one and store `state` with the entry.
+    ///
+    /// Replacing the current route with itself updates the state of the current entry. If a
+    /// [`HistoryProvider`] cannot store state, it should replace the route without the state.
+    #[allow(unused_variables)]
+    fn replace_with_state(&self, path: String, state: String) {
+        self.replace(path)
+    }
+
+    /// Get the state stored with the current entry with [`History::push_with_state`] or
+    /// [`History::replace_with_state`].
+    fn current_state(&self) -> Option<String> {
+        None
+    }
+
+    /// Get the number of entries in the navigation history, including the current entry and the
+    /// entries the user can go forward to.
+    ///
+    /// If a [`HistoryProvider`] cannot know this, it should return `1`.
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// history.push("/other".to_string());
+    /// history.go_back();
+    /// assert_eq!(history.length(), 2);
+    /// ```
+    fn length(&self) -> usize {
+        1
+    }
+
+    /// Get every entry in the navigation history from oldest to newest.
+    ///
+    /// Platforms like the browser don't allow inspecting their history. Their [`HistoryProvider`]s
+    /// return [`None`].
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// history.push("/other".to_string());
+    ///
+    /// let entries = history.entries().unwrap();
+    /// assert_eq!(entries[0].route, "/");
+    /// assert_eq!(entries[1].route, "/other");
+    /// assert_eq!(history.current_index(), Some(1));
+    /// ```
+    fn entries(&self) -> Option<Vec<HistoryEntry>> {
+        None
+    }
+
+    /// Get the index of the current entry in [`History::entries`].
+    fn current_index(&self) -> Option<usize> {
+        None
+    }
+
     /// Whether the router should include the legacy prevent default attribute instead of the new
     /// prevent default method. This should only be used by liveview.
     fn include_prevent_default(&self) -> bool {
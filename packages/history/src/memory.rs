@@ -1,17 +1,55 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, sync::Arc};
 
-use crate::History;
+use crate::{History, HistoryEntry, ScrollPosition};
+
+struct MemoryEntry {
+    route: String,
+    state: Option<String>,
+    /// The scroll position saved for the entry
+    scroll: Option<ScrollPosition>,
+}
+
+impl MemoryEntry {
+    fn new(route: String, state: Option<String>) -> Self {
+        Self {
+            route,
+            state,
+            scroll: None,
+        }
+    }
+}
 
 struct MemoryHistoryState {
-    current: String,
-    history: Vec<String>,
-    future: Vec<String>,
+    current: MemoryEntry,
+    history: Vec<MemoryEntry>,
+    future: Vec<MemoryEntry>,
+}
+
+impl MemoryHistoryState {
+    fn push(&mut self, new: String, state: Option<String>) {
+        // don't push the same route twice
+        if self.current.route == new {
+            if state.is_some() {
+                self.current.state = state;
+            }
+            return;
+        }
+        let old = std::mem::replace(&mut self.current, MemoryEntry::new(new, state));
+        self.history.push(old);
+        self.future.clear();
+    }
+
+    fn replace(&mut self, path: String, state: Option<String>) {
+        self.current.route = path;
+        self.current.state = state;
+    }
 }
 
 /// A [`History`] provider that stores all navigation information in memory.
 pub struct MemoryHistory {
     state: RefCell<MemoryHistoryState>,
     base_path: Option<String>,
+    updater: RefCell<Option<Arc<dyn Fn() + Send + Sync>>>,
 }
 
 impl Default for MemoryHistory {
@@ -45,13 +83,14 @@ impl MemoryHistory {
     pub fn with_initial_path(path: impl ToString) -> Self {
         Self {
             state: MemoryHistoryState{
-                current: path.to_string().parse().unwrap_or_else(|err| {
+                current: MemoryEntry::new(path.to_string().parse().unwrap_or_else(|err| {
                     panic!("index route does not exist:\n{err}\n use MemoryHistory::with_initial_path to set a custom path")
-                }),
+                }), None),
                 history: Vec::new(),
                 future: Vec::new(),
             }.into(),
             base_path: None,
+            updater: RefCell::new(None),
         }
     }
 
@@ -68,6 +107,25 @@ impl MemoryHistory {
         self.base_path = Some(prefix.to_string());
         self
     }
+
+    /// Go to a route from outside of the router, like a deep link the app received. Unlike [`History::push`], this
+    /// tells the router that the route changed so it renders the new route.
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// history.navigate("/notes/42".to_string());
+    ///
+    /// assert_eq!(history.current_route(), "/notes/42");
+    /// assert!(history.can_go_back());
+    /// ```
+    pub fn navigate(&self, route: String) {
+        self.push(route);
+        let updater = self.updater.borrow().clone();
+        if let Some(updater) = updater {
+            updater();
+        }
+    }
 }
 
 impl History for MemoryHistory {
@@ -76,7 +134,11 @@ impl History for MemoryHistory {
     }
 
     fn current_route(&self) -> String {
-        self.state.borrow().current.clone()
+        self.state.borrow().current.route.clone()
+    }
+
+    fn updater(&self, callback: Arc<dyn Fn() + Send + Sync>) {
+        *self.updater.borrow_mut() = Some(callback);
     }
 
     fn can_go_back(&self) -> bool {
@@ -104,18 +166,55 @@ impl History for MemoryHistory {
     }
 
     fn push(&self, new: String) {
-        let mut write = self.state.borrow_mut();
-        // don't push the same route twice
-        if write.current == new {
-            return;
-        }
-        let old = std::mem::replace(&mut write.current, new);
-        write.history.push(old);
-        write.future.clear();
+        self.state.borrow_mut().push(new, None);
     }
 
     fn replace(&self, path: String) {
-        let mut write = self.state.borrow_mut();
-        write.current = path;
+        self.state.borrow_mut().replace(path, None);
+    }
+
+    fn push_with_state(&self, route: String, state: String) {
+        self.state.borrow_mut().push(route, Some(state));
+    }
+
+    fn replace_with_state(&self, path: String, state: String) {
+        self.state.borrow_mut().replace(path, Some(state));
+    }
+
+    fn current_state(&self) -> Option<String> {
+        self.state.borrow().current.state.clone()
+    }
+
+    fn length(&self) -> usize {
+        let state = self.state.borrow();
+        state.history.len() + 1 + state.future.len()
+    }
+
+    fn entries(&self) -> Option<Vec<HistoryEntry>> {
+        let state = self.state.borrow();
+        let entries = state
+            .history
+            .iter()
+            .chain(std::iter::once(&state.current))
+            // The future is stored with the next entry last
+            .chain(state.future.iter().rev())
+            .map(|entry| HistoryEntry {
+                route: entry.route.clone(),
+                state: entry.state.clone(),
+            })
+            .collect();
+        Some(entries)
+    }
+
+    fn current_index(&self) -> Option<usize> {
+        Some(self.state.borrow().history.len())
+    }
+
+    fn set_scroll_position(&self, position: ScrollPosition) {
+        self.state.borrow_mut().current.scroll = Some(position);
+    }
+
+    fn scroll_position(&self) -> Option<ScrollPosition> {
+        self.state.borrow().current.scroll
     }
 }
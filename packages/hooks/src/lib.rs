@@ -93,5 +93,11 @@ pub use use_hook_did_run::*;
 mod use_signal;
 pub use use_signal::*;
 
+mod use_persistent_signal;
+pub use use_persistent_signal::*;
+
 mod use_set_compare;
 pub use use_set_compare::*;
+
+mod use_animated_value;
+pub use use_animated_value::*;
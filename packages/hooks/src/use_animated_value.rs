@@ -0,0 +1,658 @@
+use crate::{use_effect, use_memo, use_signal};
+use dioxus_core::{next_frame, now, prelude::*};
+use dioxus_signals::{CopyValue, ReadOnlySignal, Readable, Signal, Writable};
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::StreamExt;
+use std::{fmt, time::Duration};
+
+/// The longest step a spring is simulated with. Longer frames are split into several steps to keep the simulation
+/// stable.
+const MAX_SPRING_STEP: f64 = 0.001;
+
+/// A value that can be animated. The value is treated as a vector: animations add, subtract and scale values to move
+/// them towards the target.
+///
+/// It is implemented for floats, and for tuples and arrays of animatable values:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn Dot() -> Element {
+///     let mut corner = use_signal(|| false);
+///     let (x, y) = use_spring(
+///         move || if corner() { (200.0, 100.0) } else { (0.0, 0.0) },
+///         Spring::default(),
+///     )();
+///
+///     rsx! {
+///         div {
+///             style: "transform: translate({x}px, {y}px)",
+///             onclick: move |_| corner.toggle(),
+///         }
+///     }
+/// }
+/// ```
+pub trait Animatable: Clone + PartialEq + 'static {
+    /// The value with every component set to zero
+    fn zero() -> Self;
+
+    /// Add two values component by component
+    fn add(&self, other: &Self) -> Self;
+
+    /// Subtract a value component by component
+    fn sub(&self, other: &Self) -> Self;
+
+    /// Multiply every component by the factor
+    fn scale(&self, factor: f64) -> Self;
+
+    /// The distance of the value from zero. Animations stop once the distance to the target is below their precision.
+    fn magnitude(&self) -> f64;
+}
+
+impl Animatable for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self - other
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        self * factor
+    }
+
+    fn magnitude(&self) -> f64 {
+        self.abs()
+    }
+}
+
+impl Animatable for f32 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self - other
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        (*self as f64 * factor) as f32
+    }
+
+    fn magnitude(&self) -> f64 {
+        self.abs() as f64
+    }
+}
+
+impl<A: Animatable, B: Animatable> Animatable for (A, B) {
+    fn zero() -> Self {
+        (A::zero(), B::zero())
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        (self.0.add(&other.0), self.1.add(&other.1))
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        (self.0.sub(&other.0), self.1.sub(&other.1))
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        (self.0.scale(factor), self.1.scale(factor))
+    }
+
+    fn magnitude(&self) -> f64 {
+        self.0.magnitude().hypot(self.1.magnitude())
+    }
+}
+
+impl<A: Animatable, B: Animatable, C: Animatable> Animatable for (A, B, C) {
+    fn zero() -> Self {
+        (A::zero(), B::zero(), C::zero())
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        (
+            self.0.add(&other.0),
+            self.1.add(&other.1),
+            self.2.add(&other.2),
+        )
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        (
+            self.0.sub(&other.0),
+            self.1.sub(&other.1),
+            self.2.sub(&other.2),
+        )
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        (
+            self.0.scale(factor),
+            self.1.scale(factor),
+            self.2.scale(factor),
+        )
+    }
+
+    fn magnitude(&self) -> f64 {
+        [self.0.magnitude(), self.1.magnitude(), self.2.magnitude()]
+            .iter()
+            .map(|magnitude| magnitude * magnitude)
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+impl<T: Animatable, const N: usize> Animatable for [T; N] {
+    fn zero() -> Self {
+        std::array::from_fn(|_| T::zero())
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        std::array::from_fn(|i| self[i].add(&other[i]))
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        std::array::from_fn(|i| self[i].sub(&other[i]))
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        std::array::from_fn(|i| self[i].scale(factor))
+    }
+
+    fn magnitude(&self) -> f64 {
+        self.iter()
+            .map(|value| value.magnitude() * value.magnitude())
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+/// A physically based animation that moves the value like a mass on a spring. Springs keep their velocity when the
+/// target changes in the middle of an animation, so interrupted animations stay smooth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    /// How strongly the spring pulls the value towards the target
+    pub stiffness: f64,
+    /// How strongly the spring resists motion. Low damping makes the value overshoot and bounce.
+    pub damping: f64,
+    /// The mass on the spring. Heavier masses are slower to start and stop.
+    pub mass: f64,
+    /// The spring stops once both the distance to the target and the velocity are below the precision
+    pub precision: f64,
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self {
+            stiffness: 170.0,
+            damping: 26.0,
+            mass: 1.0,
+            precision: 0.01,
+        }
+    }
+}
+
+impl Spring {
+    /// A slow spring that doesn't overshoot
+    pub fn gentle() -> Self {
+        Self {
+            stiffness: 120.0,
+            damping: 14.0,
+            ..Default::default()
+        }
+    }
+
+    /// A spring that overshoots and bounces around the target
+    pub fn wobbly() -> Self {
+        Self {
+            stiffness: 180.0,
+            damping: 12.0,
+            ..Default::default()
+        }
+    }
+
+    /// A fast spring that barely overshoots
+    pub fn stiff() -> Self {
+        Self {
+            stiffness: 210.0,
+            damping: 20.0,
+            ..Default::default()
+        }
+    }
+
+    /// Set how strongly the spring pulls the value towards the target
+    pub fn stiffness(mut self, stiffness: f64) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    /// Set how strongly the spring resists motion
+    pub fn damping(mut self, damping: f64) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Set the mass on the spring
+    pub fn mass(mut self, mass: f64) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Set the distance to the target and velocity the spring stops at
+    pub fn precision(mut self, precision: f64) -> Self {
+        self.precision = precision;
+        self
+    }
+}
+
+/// How a [`Tween`] moves from the start to the end over its duration
+#[derive(Clone, Copy, Default)]
+pub enum Easing {
+    /// Move at a constant speed
+    Linear,
+    /// Start slowly and speed up
+    EaseIn,
+    /// Start fast and slow down
+    EaseOut,
+    /// Start slowly, speed up and slow down again
+    #[default]
+    EaseInOut,
+    /// A custom easing function that maps the progress from 0 to 1 to the eased progress
+    Custom(fn(f64) -> f64),
+}
+
+impl Easing {
+    /// Ease the progress of the animation, from 0 at the start to 1 at the end
+    pub fn ease(&self, progress: f64) -> f64 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+            Easing::Custom(ease) => ease(t),
+        }
+    }
+}
+
+impl fmt::Debug for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Easing::Linear => write!(f, "Linear"),
+            Easing::EaseIn => write!(f, "EaseIn"),
+            Easing::EaseOut => write!(f, "EaseOut"),
+            Easing::EaseInOut => write!(f, "EaseInOut"),
+            Easing::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// An animation that moves the value to the target over a fixed duration
+#[derive(Debug, Clone, Copy)]
+pub struct Tween {
+    /// How long the animation takes
+    pub duration: Duration,
+    /// How the value moves over the duration
+    pub easing: Easing,
+}
+
+impl Tween {
+    /// Create a tween that takes the duration and eases in and out
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Easing::default(),
+        }
+    }
+
+    /// Set how the value moves over the duration
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// How an [`AnimatedValue`] moves to a new target
+#[derive(Debug, Clone, Copy)]
+pub enum Animation {
+    /// Move like a mass on a spring
+    Spring(Spring),
+    /// Move over a fixed duration
+    Tween(Tween),
+}
+
+impl From<Spring> for Animation {
+    fn from(spring: Spring) -> Self {
+        Animation::Spring(spring)
+    }
+}
+
+impl From<Tween> for Animation {
+    fn from(tween: Tween) -> Self {
+        Animation::Tween(tween)
+    }
+}
+
+enum Command<T> {
+    AnimateTo(T, Animation),
+    Jump(T),
+    Stop,
+}
+
+/// The state of a running animation
+struct Motion<T> {
+    current: T,
+    velocity: T,
+    target: T,
+    animation: Animation,
+    /// The value and the time the current tween started at
+    tween_start: (T, Duration),
+}
+
+impl<T: Animatable> Motion<T> {
+    /// Move the value forward by the time since the last frame. Returns false once the animation reached the target.
+    fn step(&mut self, elapsed: Duration, dt: Duration) -> bool {
+        let previous = self.current.clone();
+        match self.animation {
+            Animation::Spring(spring) => {
+                // Simulate the spring in small steps so stiff springs don't explode on slow frames
+                let dt = dt.as_secs_f64().min(0.25);
+                let steps = (dt / MAX_SPRING_STEP).ceil().max(1.0);
+                let step = dt / steps;
+                for _ in 0..steps as usize {
+                    let displacement = self.current.sub(&self.target);
+                    let force = displacement
+                        .scale(-spring.stiffness)
+                        .sub(&self.velocity.scale(spring.damping));
+                    self.velocity = self.velocity.add(&force.scale(step / spring.mass));
+                    self.current = self.current.add(&self.velocity.scale(step));
+                }
+
+                let at_rest = self.current.sub(&self.target).magnitude() < spring.precision
+                    && self.velocity.magnitude() < spring.precision;
+                if at_rest {
+                    self.current = self.target.clone();
+                    self.velocity = T::zero();
+                }
+                !at_rest
+            }
+            Animation::Tween(tween) => {
+                let (start, started) = &self.tween_start;
+                let progress = match tween.duration.is_zero() {
+                    true => 1.0,
+                    false => {
+                        (elapsed.saturating_sub(*started)).as_secs_f64()
+                            / tween.duration.as_secs_f64()
+                    }
+                };
+                let eased = tween.easing.ease(progress);
+                self.current = start.add(&self.target.sub(start).scale(eased));
+
+                // Keep track of the velocity so a spring that interrupts the tween continues smoothly
+                let dt = dt.as_secs_f64();
+                if dt > 0.0 {
+                    self.velocity = self.current.sub(&previous).scale(1.0 / dt);
+                }
+                if progress >= 1.0 {
+                    self.current = self.target.clone();
+                    self.velocity = T::zero();
+                }
+                progress < 1.0
+            }
+        }
+    }
+}
+
+/// A value that animates towards a target. Create it with [`use_animated_value`].
+///
+/// The current value is stored in a signal, so components that read it rerender on every frame of the animation.
+pub struct AnimatedValue<T: 'static> {
+    value: Signal<T>,
+    animating: Signal<bool>,
+    commands: CopyValue<UnboundedSender<Command<T>>>,
+}
+
+impl<T> Clone for AnimatedValue<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for AnimatedValue<T> {}
+
+impl<T: Animatable> AnimatedValue<T> {
+    /// Animate from the current value to the target. If the value is already animating, the animation continues from
+    /// where it is now with the new target and animation.
+    pub fn animate_to(&self, target: T, animation: impl Into<Animation>) {
+        self.send(Command::AnimateTo(target, animation.into()));
+    }
+
+    /// Jump to a value without animating. This stops the running animation.
+    pub fn set(&self, value: T) {
+        self.send(Command::Jump(value));
+    }
+
+    /// Stop the running animation at the current value
+    pub fn stop(&self) {
+        self.send(Command::Stop);
+    }
+
+    /// Read the current value and subscribe to its changes
+    pub fn get(&self) -> T {
+        self.value.cloned()
+    }
+
+    /// Read the current value without subscribing to its changes
+    pub fn peek(&self) -> T {
+        self.value.peek().clone()
+    }
+
+    /// Check if the value is moving and subscribe to changes
+    pub fn is_animating(&self) -> bool {
+        (self.animating)()
+    }
+
+    /// Get a read only signal with the current value
+    pub fn value(&self) -> ReadOnlySignal<T> {
+        self.value.into()
+    }
+
+    fn send(&self, command: Command<T>) {
+        _ = self.commands.read().unbounded_send(command);
+    }
+}
+
+/// Create a value that can be animated to new targets with [`AnimatedValue::animate_to`].
+///
+/// The animation runs once per frame of the renderer in a task owned by the component. When the component is
+/// unmounted, the animation stops. On the web, frames come from `requestAnimationFrame`, so animations also pause
+/// while the page is hidden.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use std::time::Duration;
+/// fn Drawer() -> Element {
+///     let offset = use_animated_value(|| -300.0);
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| offset.animate_to(0.0, Tween::new(Duration::from_millis(250))),
+///             "Open"
+///         }
+///         button {
+///             onclick: move |_| offset.animate_to(-300.0, Spring::stiff()),
+///             "Close"
+///         }
+///         nav { style: "transform: translateX({offset.get()}px)" }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+pub fn use_animated_value<T: Animatable>(initial: impl FnOnce() -> T) -> AnimatedValue<T> {
+    let value = use_signal(initial);
+    let animating = use_signal(|| false);
+
+    use_hook(|| {
+        let (tx, rx) = futures_channel::mpsc::unbounded();
+        spawn(animate(value, animating, rx));
+        AnimatedValue {
+            value,
+            animating,
+            commands: CopyValue::new(tx),
+        }
+    })
+}
+
+/// Run the animations of a value until the component is dropped
+async fn animate<T: Animatable>(
+    mut value: Signal<T>,
+    mut animating: Signal<bool>,
+    mut commands: UnboundedReceiver<Command<T>>,
+) {
+    let start = now();
+    let elapsed = || now().saturating_duration_since(start);
+    let mut motion: Option<Motion<T>> = None;
+
+    loop {
+        // Wait for a new target while the value is at rest
+        if motion.is_none() {
+            let Some(command) = commands.next().await else {
+                return;
+            };
+            apply(&mut motion, &mut value, command, elapsed());
+        }
+
+        // Then move the value one frame at a time until it reaches the target
+        let mut last_frame = elapsed();
+        if !*animating.peek() {
+            animating.set(true);
+        }
+        while motion.is_some() {
+            next_frame().await;
+            while let Ok(Some(command)) = commands.try_next() {
+                apply(&mut motion, &mut value, command, elapsed());
+            }
+
+            let Some(running) = motion.as_mut() else {
+                break;
+            };
+            let now = elapsed();
+            let moving = running.step(now, now.saturating_sub(last_frame));
+            last_frame = now;
+            value.set(running.current.clone());
+            if !moving {
+                motion = None;
+            }
+        }
+        animating.set(false);
+    }
+}
+
+fn apply<T: Animatable>(
+    motion: &mut Option<Motion<T>>,
+    value: &mut Signal<T>,
+    command: Command<T>,
+    elapsed: Duration,
+) {
+    match command {
+        Command::AnimateTo(target, animation) => {
+            let (current, velocity) = match motion.take() {
+                Some(motion) => (motion.current, motion.velocity),
+                None => (value.peek().clone(), T::zero()),
+            };
+            // There is nothing to animate if the value is already resting at the target
+            if current == target && velocity == T::zero() {
+                return;
+            }
+            *motion = Some(Motion {
+                tween_start: (current.clone(), elapsed),
+                current,
+                velocity,
+                target,
+                animation,
+            });
+        }
+        Command::Jump(target) => {
+            *motion = None;
+            value.set(target);
+        }
+        Command::Stop => *motion = None,
+    }
+}
+
+/// Animate towards a target with a spring. The target is reactive: when a signal the closure reads changes, the value
+/// springs to the new target from wherever it is, keeping its current velocity.
+///
+/// The spring is read when the component is first rendered.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn Toggle() -> Element {
+///     let mut on = use_signal(|| false);
+///     let x = use_spring(move || if on() { 20.0 } else { 0.0 }, Spring::wobbly());
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| on.toggle(),
+///             div { style: "transform: translateX({x}px)" }
+///         }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+pub fn use_spring<T: Animatable>(
+    target: impl FnMut() -> T + 'static,
+    spring: Spring,
+) -> ReadOnlySignal<T> {
+    use_animation(target, spring.into())
+}
+
+/// Animate towards a target over a fixed duration. The target is reactive: when a signal the closure reads changes,
+/// the value moves to the new target from wherever it is.
+///
+/// The tween is read when the component is first rendered.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use std::time::Duration;
+/// fn FadeIn() -> Element {
+///     let mut visible = use_signal(|| false);
+///     let opacity = use_tween(
+///         move || if visible() { 1.0 } else { 0.0 },
+///         Tween::new(Duration::from_millis(300)).easing(Easing::EaseOut),
+///     );
+///
+///     rsx! {
+///         div { onmounted: move |_| visible.set(true), opacity: "{opacity}", "Hello!" }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+pub fn use_tween<T: Animatable>(
+    target: impl FnMut() -> T + 'static,
+    tween: Tween,
+) -> ReadOnlySignal<T> {
+    use_animation(target, tween.into())
+}
+
+fn use_animation<T: Animatable>(
+    target: impl FnMut() -> T + 'static,
+    animation: Animation,
+) -> ReadOnlySignal<T> {
+    let target = use_memo(target);
+    let animated = use_animated_value(|| target.peek().clone());
+    let animation = use_hook(|| animation);
+    use_effect(move || animated.animate_to(target(), animation));
+    animated.value()
+}
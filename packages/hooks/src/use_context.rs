@@ -1,7 +1,9 @@
+use crate::use_memo;
 use dioxus_core::{
     prelude::{consume_context, provide_context, try_consume_context},
     use_hook,
 };
+use dioxus_signals::{Memo, Readable, Signal};
 
 /// Consume some context in the tree, providing a sharable handle to the value
 ///
@@ -65,3 +67,39 @@ pub fn use_context<T: 'static + Clone>() -> T {
 pub fn use_context_provider<T: 'static + Clone>(f: impl FnOnce() -> T) -> T {
     use_hook(|| provide_context(f()))
 }
+
+/// Subscribe to part of a `Signal<T>` that was provided as context.
+///
+/// The selector runs whenever the signal in the context changes, but the component only reruns if the selected value
+/// changes (`PartialEq` returns false). This keeps components that only need a small part of a large app state from
+/// rerunning every time an unrelated part of the state changes.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Clone, Default)]
+/// struct AppState {
+///     user: String,
+///     notifications: Vec<String>,
+/// }
+///
+/// fn App() -> Element {
+///     use_context_provider(|| Signal::new(AppState::default()));
+///     rsx! { UserName {} }
+/// }
+///
+/// #[component]
+/// fn UserName() -> Element {
+///     // This component doesn't rerun when a notification is added
+///     let user = use_context_selector(|state: &AppState| state.user.clone());
+///     rsx! { "{user}" }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[must_use]
+#[track_caller]
+pub fn use_context_selector<T: 'static, O: PartialEq + 'static>(
+    mut selector: impl FnMut(&T) -> O + 'static,
+) -> Memo<O> {
+    let state = use_context::<Signal<T>>();
+    use_memo(move || selector(&state.read()))
+}
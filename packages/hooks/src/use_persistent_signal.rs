@@ -0,0 +1,196 @@
+use crate::use_effect;
+use dioxus_core::prelude::*;
+use dioxus_signals::{PlatformStorage, Readable, Signal, StorageBackend, StorageKind};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+type Migration = Box<dyn Fn(u32, Value) -> Option<Value>>;
+
+/// Options for [`use_persistent_signal_with`]
+pub struct PersistOptions {
+    key: String,
+    kind: StorageKind,
+    version: u32,
+    migrate: Option<Migration>,
+    debounce: Duration,
+}
+
+impl PersistOptions {
+    /// Create options that save the value under the key in local storage
+    pub fn new(key: impl ToString) -> Self {
+        Self {
+            key: key.to_string(),
+            kind: StorageKind::Local,
+            version: 0,
+            migrate: None,
+            debounce: Duration::from_millis(100),
+        }
+    }
+
+    /// Keep the value for the current session only. On the web, this uses session storage.
+    pub fn session(mut self) -> Self {
+        self.kind = StorageKind::Session;
+        self
+    }
+
+    /// Set the version of the saved value. Change the version when the type of the value changes.
+    ///
+    /// Values saved with a different version are passed to the migration. If there is no migration, they are discarded.
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Convert a value saved with an older version into the current version. The migration receives the version the
+    /// value was saved with and the saved json. Return `None` to discard the saved value.
+    pub fn migrate(mut self, migrate: impl Fn(u32, Value) -> Option<Value> + 'static) -> Self {
+        self.migrate = Some(Box::new(migrate));
+        self
+    }
+
+    /// Wait until the value hasn't changed for this long before saving it. Defaults to 100ms.
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    fn load<T: DeserializeOwned>(&self, storage: &dyn StorageBackend) -> Option<T> {
+        let saved: Value = serde_json::from_str(&storage.get(&self.key)?).ok()?;
+        let version = saved.get("version")?.as_u64()? as u32;
+        let mut value = saved.get("value")?.clone();
+        if version != self.version {
+            value = (self.migrate.as_ref()?)(version, value)?;
+        }
+        match serde_json::from_value(value) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                tracing::warn!("Failed to load the persisted value for {}: {err}", self.key);
+                None
+            }
+        }
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Option<String> {
+        let saved = serde_json::json!({ "version": self.version, "value": value });
+        match serde_json::to_string(&saved) {
+            Ok(saved) => Some(saved),
+            Err(err) => {
+                tracing::error!("Failed to persist the value for {}: {err}", self.key);
+                None
+            }
+        }
+    }
+}
+
+struct PersistState {
+    options: PersistOptions,
+    storage: Rc<dyn StorageBackend>,
+    // The debounced write that hasn't been saved yet
+    pending: RefCell<Option<(Task, String)>>,
+}
+
+impl PersistState {
+    fn flush(&self) {
+        let pending = self.pending.borrow_mut().take();
+        if let Some((_, value)) = pending {
+            self.storage.set(&self.options.key, value);
+        }
+    }
+}
+
+/// Create a signal that is saved between sessions.
+///
+/// The value is serialized to json and saved in the storage the renderer provides: local storage on the web and a file in
+/// the data directory of the app on desktop and mobile. If the renderer doesn't provide storage, the value is only kept in
+/// memory. Writes are debounced, so changing the value many times in a row only saves it once.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn App() -> Element {
+///     let mut count = use_persistent_signal("count", || 0);
+///     rsx! { button { onclick: move |_| count += 1, "Clicked {count} times" } }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[track_caller]
+#[must_use]
+pub fn use_persistent_signal<T>(key: impl ToString, init: impl FnOnce() -> T) -> Signal<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    use_persistent_signal_with(PersistOptions::new(key), init)
+}
+
+/// Create a signal that is saved between sessions with [`PersistOptions`] for the storage, versioning and debouncing.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Settings {
+///     theme: String,
+///     font_size: u32,
+/// }
+///
+/// fn App() -> Element {
+///     let settings = use_persistent_signal_with(
+///         PersistOptions::new("settings")
+///             .version(1)
+///             // Version 0 only saved the theme
+///             .migrate(|_, theme| Some(serde_json::json!({ "theme": theme, "font_size": 16 }))),
+///         || Settings { theme: "light".to_string(), font_size: 16 },
+///     );
+///     rsx! { "Theme: {settings.read().theme}" }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[track_caller]
+#[must_use]
+pub fn use_persistent_signal_with<T>(options: PersistOptions, init: impl FnOnce() -> T) -> Signal<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let caller = std::panic::Location::caller();
+    let (signal, state) = use_hook(|| {
+        let storage = PlatformStorage::current().backend(options.kind);
+        let value = options.load(&*storage).unwrap_or_else(init);
+        let state = Rc::new(PersistState {
+            options,
+            storage,
+            pending: RefCell::new(None),
+        });
+        (Signal::<T>::new_with_caller(value, caller), state)
+    });
+
+    use_effect({
+        let state = state.clone();
+        move || {
+            let Some(value) = state.options.serialize(&*signal.read()) else {
+                return;
+            };
+            if state.options.debounce.is_zero() {
+                state.storage.set(&state.options.key, value);
+                return;
+            }
+
+            let previous = state.pending.borrow_mut().take();
+            if let Some((task, _)) = previous {
+                task.cancel();
+            }
+            let wait = dioxus_core::sleep(state.options.debounce);
+            let task = spawn({
+                let state = state.clone();
+                async move {
+                    wait.await;
+                    state.flush();
+                }
+            });
+            *state.pending.borrow_mut() = Some((task, value));
+        }
+    });
+
+    // Save the last value right away if the component is dropped before the debounce finishes
+    use_drop(move || state.flush());
+
+    signal
+}
@@ -1,6 +1,6 @@
 #![allow(missing_docs)]
 
-use crate::{use_callback, use_signal};
+use crate::{use_callback, use_signal, Dependency};
 use dioxus_core::prelude::*;
 use dioxus_signals::*;
 use futures_util::{future, pin_mut, FutureExt, StreamExt};
@@ -21,6 +21,94 @@ where
 {
     let location = std::panic::Location::caller();
 
+    // Every signal the closure or the future reads is a dependency of the resource
+    use_resource_inner(
+        location,
+        move |rc: &ReactiveContext| rc.reset_and_run_in(&mut future),
+        true,
+    )
+}
+
+/// Create a resource that only reruns when its explicit dependencies change. The dependencies are compared with the
+/// ones of the last render, just like [`use_reactive`](crate::use_reactive()). Unlike [`use_resource`], reading signals
+/// in the future does not subscribe the resource to them.
+///
+/// The future of the previous dependencies is cancelled when the dependencies change, so a slow response to an old
+/// request can never overwrite the value of a newer one.
+///
+/// ## Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[component]
+/// fn Issues(owner: String, repo: String) -> Element {
+///     let mut page = use_signal(|| 1);
+///     let issues = use_resource_with_deps((&owner, &repo, &page()), |(owner, repo, page)| async move {
+///         reqwest::get(format!("https://api.github.com/repos/{owner}/{repo}/issues?page={page}"))
+///             .await?
+///             .text()
+///             .await
+///     });
+///
+///     rsx! {
+///         button { onclick: move |_| page += 1, "Next page" }
+///         match &*issues.read_unchecked() {
+///             Some(Ok(issues)) => rsx! { "{issues}" },
+///             Some(Err(err)) => rsx! { "Error: {err}" },
+///             None => rsx! { "Loading..." },
+///         }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[must_use = "Consider using `cx.spawn` to run a future without reading its value"]
+#[track_caller]
+pub fn use_resource_with_deps<D, T, F>(
+    dependencies: D,
+    mut future: impl FnMut(D::Out) -> F + 'static,
+) -> Resource<T>
+where
+    D: Dependency,
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
+    let location = std::panic::Location::caller();
+
+    let mut first_run = false;
+    let mut last_dependencies = use_signal(|| {
+        first_run = true;
+        dependencies.out()
+    });
+    if !first_run && dependencies.changed(&*last_dependencies.peek()) {
+        use ::warnings::Warning;
+        // Like use_reactive, we write to a signal during rendering to bridge the props and the reactive world
+        dioxus_signals::warnings::signal_read_and_write_in_reactive_scope::allow(|| {
+            dioxus_signals::warnings::signal_write_in_component_body::allow(|| {
+                last_dependencies.set(dependencies.out())
+            })
+        });
+    }
+
+    use_resource_inner(
+        location,
+        move |rc: &ReactiveContext| {
+            // Only subscribe to the dependencies. Signals the future reads are not dependencies
+            let dependencies = rc.reset_and_run_in(|| last_dependencies.cloned());
+            future(dependencies)
+        },
+        false,
+    )
+}
+
+fn use_resource_inner<T, F>(
+    location: &'static std::panic::Location<'static>,
+    mut create_future: impl FnMut(&ReactiveContext) -> F + 'static,
+    track_polls: bool,
+) -> Resource<T>
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
     let mut value = use_signal(|| None);
     let mut state = use_signal(|| UseResourceState::Pending);
     let (rc, changed) = use_hook(|| {
@@ -29,8 +117,13 @@ where
     });
 
     let cb = use_callback(move |_| {
+        // The new future is running until it finishes, even if the old one was ready
+        if *state.peek() != UseResourceState::Pending {
+            state.set(UseResourceState::Pending);
+        }
+
         // Create the user's task
-        let fut = rc.reset_and_run_in(&mut future);
+        let fut = create_future(&rc);
 
         // Spawn a wrapper task that polls the inner future and watch its dependencies
         spawn(async move {
@@ -41,10 +134,14 @@ where
             // Run each poll in the context of the reactive scope
             // This ensures the scope is properly subscribed to the future's dependencies
             let res = future::poll_fn(|cx| {
-                rc.run_in(|| {
+                let mut poll = || {
                     tracing::trace_span!("polling resource", location = %location)
                         .in_scope(|| fut.poll_unpin(cx))
-                })
+                };
+                match track_polls {
+                    true => rc.run_in(poll),
+                    false => poll(),
+                }
             })
             .await;
 
@@ -149,7 +246,7 @@ pub enum UseResourceState {
 impl<T> Resource<T> {
     /// Restart the resource's future.
     ///
-    /// This will cancel the current future and start a new one.
+    /// This will cancel the current future and start a new one. This is the same as [`Resource::refetch`].
     ///
     /// ## Example
     /// ```rust, no_run
@@ -173,11 +270,48 @@ impl<T> Resource<T> {
     /// }
     /// ```
     pub fn restart(&mut self) {
+        self.refetch();
+    }
+
+    /// Run the resource's future again without waiting for its dependencies to change.
+    ///
+    /// The future that is running is cancelled. The current value is kept until the new future finishes, and
+    /// [`Resource::is_stale`] returns `true` until then.
+    ///
+    /// ## Example
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// fn App() -> Element {
+    ///     let mut notifications = use_resource(move || async move {
+    ///         reqwest::get("https://example.com/api/notifications").await?.text().await
+    ///     });
+    ///
+    ///     rsx! {
+    ///         button {
+    ///             onclick: move |_| notifications.refetch(),
+    ///             disabled: notifications.is_stale(),
+    ///             "Refresh"
+    ///         }
+    ///         if let Some(Ok(notifications)) = &*notifications.read_unchecked() {
+    ///             "{notifications}"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn refetch(&mut self) {
         self.task.write().cancel();
         let new_task = self.callback.call(());
         self.task.set(new_task);
     }
 
+    /// Is the value of the resource out of date? This is `true` while the future is running again after it already
+    /// produced a value, because the dependencies changed or the resource was refetched.
+    ///
+    /// Reading this subscribes to the state of the resource.
+    pub fn is_stale(&self) -> bool {
+        *self.state.read() == UseResourceState::Pending && self.value.peek().is_some()
+    }
+
     /// Forcefully cancel the resource's future.
     ///
     /// ## Example
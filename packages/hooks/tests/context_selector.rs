@@ -0,0 +1,45 @@
+use std::cell::Cell;
+
+use dioxus::prelude::*;
+
+#[derive(Clone, Default)]
+struct AppState {
+    user: String,
+    notifications: usize,
+}
+
+thread_local! {
+    static STATE: Cell<Option<Signal<AppState>>> = const { Cell::new(None) };
+    static USER_RENDERS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[test]
+fn selector_only_reruns_when_the_selection_changes() {
+    fn app() -> Element {
+        let state = use_context_provider(|| Signal::new(AppState::default()));
+        STATE.with(|cell| cell.set(Some(state)));
+        rsx! { User {} }
+    }
+
+    #[component]
+    fn User() -> Element {
+        let user = use_context_selector(|state: &AppState| state.user.clone());
+        USER_RENDERS.with(|renders| renders.set(renders.get() + 1));
+        rsx! { "{user}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let mut state = STATE.with(|cell| cell.get()).unwrap();
+    assert_eq!(USER_RENDERS.with(|renders| renders.get()), 1);
+
+    // Changing an unrelated part of the state doesn't rerun the component
+    dom.in_runtime(|| state.write().notifications += 1);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(USER_RENDERS.with(|renders| renders.get()), 1);
+
+    // Changing the selected part of the state does
+    dom.in_runtime(|| state.write().user = "Alice".to_string());
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(USER_RENDERS.with(|renders| renders.get()), 2);
+}
@@ -0,0 +1,98 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use dioxus::prelude::*;
+use dioxus_signals::{MemoryStorage, PlatformStorage, StorageBackend};
+
+thread_local! {
+    static STORAGE: RefCell<Option<Rc<MemoryStorage>>> = const { RefCell::new(None) };
+}
+
+fn storage() -> Rc<MemoryStorage> {
+    STORAGE.with_borrow_mut(|storage| storage.get_or_insert_with(Default::default).clone())
+}
+
+fn provide_storage() {
+    use_hook(|| {
+        let local = storage();
+        provide_root_context(PlatformStorage {
+            local,
+            session: Rc::new(MemoryStorage::default()),
+        })
+    });
+}
+
+#[test]
+fn persistent_signal_saves_and_loads() {
+    fn app() -> Element {
+        provide_storage();
+        let mut count =
+            use_persistent_signal_with(PersistOptions::new("count").debounce(Duration::ZERO), || 0);
+        use_hook(|| count += 5);
+        rsx! { "{count}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.process_events();
+    assert_eq!(
+        storage().get("count").as_deref(),
+        Some(r#"{"value":5,"version":0}"#)
+    );
+    drop(dom);
+
+    // The next session starts with the saved value
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.process_events();
+    assert_eq!(
+        storage().get("count").as_deref(),
+        Some(r#"{"value":10,"version":0}"#)
+    );
+}
+
+#[test]
+fn persistent_signal_migrates_old_versions() {
+    fn app() -> Element {
+        provide_storage();
+        let name = use_persistent_signal_with(
+            PersistOptions::new("name")
+                .version(1)
+                .migrate(|version, value| {
+                    assert_eq!(version, 0);
+                    Some(serde_json::json!([value, ""]))
+                }),
+            || ("".to_string(), "".to_string()),
+        );
+        assert_eq!(*name.peek(), ("Ada".to_string(), "".to_string()));
+        rsx! {}
+    }
+
+    storage().set("name", r#"{"value":"Ada","version":0}"#.to_string());
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+}
+
+#[tokio::test]
+async fn persistent_signal_flushes_on_drop() {
+    fn app() -> Element {
+        provide_storage();
+        let mut text = use_persistent_signal_with(
+            PersistOptions::new("text").debounce(Duration::from_secs(60)),
+            String::new,
+        );
+        use_hook(|| text.set("draft".to_string()));
+        rsx! {}
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.process_events();
+    assert_eq!(storage().get("text"), None);
+
+    // The debounced write is saved when the component is dropped
+    drop(dom);
+    assert_eq!(
+        storage().get("text").as_deref(),
+        Some(r#"{"value":"draft","version":0}"#)
+    );
+}
@@ -0,0 +1,104 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+
+/// Run the virtual dom for a while, rendering every time there is work
+async fn run_for(dom: &mut VirtualDom, duration: Duration) {
+    _ = tokio::time::timeout(duration, async {
+        loop {
+            dom.wait_for_work().await;
+            dom.render_immediate(&mut NoOpMutations);
+        }
+    })
+    .await;
+}
+
+type Handles = (Signal<i32>, Signal<i32>, Resource<(i32, i32)>);
+
+#[derive(Default)]
+struct State {
+    runs: Cell<usize>,
+    handles: Cell<Option<Handles>>,
+}
+
+#[tokio::test]
+async fn explicit_dependencies_and_refetch() {
+    let state = Rc::new(State::default());
+    let mut dom = VirtualDom::new_with_props(
+        |state: Rc<State>| {
+            let key = use_signal(|| 0);
+            let other = use_signal(|| 0);
+            let resource = use_resource_with_deps((&key(),), {
+                let state = state.clone();
+                move |(key,)| {
+                    state.runs.set(state.runs.get() + 1);
+                    async move { (key, other()) }
+                }
+            });
+            state.handles.set(Some((key, other, resource)));
+            rsx! {}
+        },
+        state.clone(),
+    );
+    dom.rebuild_in_place();
+    run_for(&mut dom, Duration::from_millis(50)).await;
+
+    let (mut key, mut other, mut resource) = state.handles.get().unwrap();
+    assert_eq!(state.runs.get(), 1);
+    dom.in_runtime(|| assert_eq!(resource.cloned(), Some((0, 0))));
+
+    // Signals the future reads are not dependencies
+    dom.in_runtime(|| other.set(5));
+    run_for(&mut dom, Duration::from_millis(50)).await;
+    assert_eq!(state.runs.get(), 1);
+    dom.in_runtime(|| assert_eq!(resource.cloned(), Some((0, 0))));
+
+    // Changing a dependency reruns the future
+    dom.in_runtime(|| key.set(1));
+    run_for(&mut dom, Duration::from_millis(50)).await;
+    assert_eq!(state.runs.get(), 2);
+    dom.in_runtime(|| assert_eq!(resource.cloned(), Some((1, 5))));
+
+    // Refetching keeps the old value, which is stale until the new one is ready
+    dom.in_runtime(|| {
+        resource.refetch();
+        assert!(resource.is_stale());
+        assert_eq!(resource.cloned(), Some((1, 5)));
+    });
+    run_for(&mut dom, Duration::from_millis(50)).await;
+    assert_eq!(state.runs.get(), 3);
+    dom.in_runtime(|| assert!(!resource.is_stale()));
+}
+
+#[tokio::test]
+async fn changing_dependencies_cancels_the_previous_future() {
+    thread_local! {
+        static FINISHED: RefCell<Vec<i32>> = const { RefCell::new(Vec::new()) };
+        static KEY: Cell<Option<Signal<i32>>> = const { Cell::new(None) };
+    }
+
+    let mut dom = VirtualDom::new(|| {
+        let key = use_signal(|| 0);
+        KEY.set(Some(key));
+        let resource = use_resource_with_deps((&key(),), |(key,)| async move {
+            // The first request is slower than the second one
+            if key == 0 {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            FINISHED.with_borrow_mut(|finished| finished.push(key));
+            key
+        });
+        rsx! { "{resource:?}" }
+    });
+    dom.rebuild_in_place();
+
+    let mut key = KEY.get().unwrap();
+    dom.in_runtime(|| key.set(1));
+    run_for(&mut dom, Duration::from_millis(200)).await;
+
+    FINISHED.with_borrow(|finished| assert_eq!(finished, &[1]));
+}
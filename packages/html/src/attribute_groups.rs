@@ -228,6 +228,9 @@ mod_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/autocapitalize>
     autocapitalize;
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/autocorrect>
+    autocorrect;
+
 
     /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/autofocus>
     autofocus;
@@ -300,6 +303,9 @@ mod_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/id>
     id;
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/inert>
+    inert;
+
     /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/inputmode>
     inputmode;
 
@@ -354,6 +360,12 @@ mod_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/translate>
     translate;
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/virtualkeyboardpolicy>
+    virtualkeyboardpolicy;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/writingsuggestions>
+    writingsuggestions;
+
 
     /// dangerous_inner_html is Dioxus's replacement for using innerHTML in the browser DOM. In general, setting
     /// HTML from code is risky because it’s easy to inadvertently expose your users to a cross-site scripting (XSS)
@@ -1594,6 +1606,9 @@ mod_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-current>
     aria_current: "aria-current";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-description>
+    aria_description: "aria-description";
+
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-details>
     aria_details: "aria-details";
 
@@ -1615,6 +1630,12 @@ mod_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-roledescription>
     aria_roledescription: "aria-roledescription";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-braillelabel>
+    aria_braillelabel: "aria-braillelabel";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-brailleroledescription>
+    aria_brailleroledescription: "aria-brailleroledescription";
+
 // Widget Attributes
 
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-autocomplete>
@@ -1705,6 +1726,9 @@ mod_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-colindex>
     aria_colindex: "aria-colindex";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-colindextext>
+    aria_colindextext: "aria-colindextext";
+
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-colspan>
     aria_colspan: "aria-colspan";
 
@@ -1735,6 +1759,9 @@ mod_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-rowindex>
     aria_rowindex: "aria-rowindex";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-rowindextext>
+    aria_rowindextext: "aria-rowindextext";
+
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-rowspan>
     aria_rowspan: "aria-rowspan";
 
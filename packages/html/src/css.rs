@@ -0,0 +1,500 @@
+//! Typed inline styles.
+//!
+//! [`Style`] builds the value of the `style` attribute from typed properties, so property names and units are
+//! checked at compile time:
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! use dioxus::html::css::*;
+//!
+//! fn Toolbar() -> Element {
+//!     rsx! {
+//!         div {
+//!             style: Style::new().display(Display::Flex).gap(px(8)).padding(rem(1)),
+//!             "Toolbar"
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Each property is rendered as its own attribute in the `style` namespace. When the style changes, the renderer only
+//! sets the properties that changed and removes the properties that are no longer set.
+
+use dioxus_core::{Attribute, AttributeValue, IntoAttributes};
+use std::fmt::{self, Formatter};
+
+/// A typed inline style. Properties are set with the builder methods, and setting a property again replaces it.
+///
+/// Properties without a typed method can be set with [`Style::property`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    /// The properties of the style sorted by name
+    properties: Vec<(&'static str, String)>,
+}
+
+impl Style {
+    /// Create a style without any properties
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a css property by name. The name is the kebab case name of the property, like `grid-template-columns`.
+    ///
+    /// ```rust
+    /// # use dioxus_html::css::*;
+    /// let style = Style::new().property("grid-template-columns", "1fr 2fr");
+    /// assert_eq!(style.to_string(), "grid-template-columns:1fr 2fr;");
+    /// ```
+    pub fn property(mut self, name: &'static str, value: impl ToString) -> Self {
+        let value = value.to_string();
+        match self
+            .properties
+            .binary_search_by(|(other, _)| other.cmp(&name))
+        {
+            Ok(index) => self.properties[index].1 = value,
+            Err(index) => self.properties.insert(index, (name, value)),
+        }
+        self
+    }
+
+    /// Get the value of a css property if it is set
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(other, _)| *other == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Remove a css property if it is set
+    pub fn remove(mut self, name: &str) -> Self {
+        self.properties.retain(|(other, _)| *other != name);
+        self
+    }
+
+    /// Iterate over the properties of the style sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.properties
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+    }
+
+    /// Check if the style has no properties
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.properties {
+            write!(f, "{name}:{value};")?;
+        }
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+pub struct StyleMarker;
+
+impl IntoAttributes<StyleMarker> for Style {
+    fn into_attributes(
+        self,
+        _name: &'static str,
+        _namespace: Option<&'static str>,
+        volatile: bool,
+    ) -> Box<[Attribute]> {
+        // Renderers set attributes in the style namespace with `style.setProperty`
+        self.properties
+            .into_iter()
+            .map(|(name, value)| {
+                Attribute::new(name, AttributeValue::Text(value), Some("style"), volatile)
+            })
+            .collect()
+    }
+}
+
+/// A css length like `8px`, `1.5rem` or `50%`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Length {
+    /// `auto`
+    Auto,
+    /// Pixels
+    Px(f64),
+    /// Relative to the font size of the element
+    Em(f64),
+    /// Relative to the font size of the root element
+    Rem(f64),
+    /// Relative to the size of the parent element
+    Percent(f64),
+    /// Relative to the width of the viewport
+    Vw(f64),
+    /// Relative to the height of the viewport
+    Vh(f64),
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Length::Auto => write!(f, "auto"),
+            Length::Px(value) => write!(f, "{value}px"),
+            Length::Em(value) => write!(f, "{value}em"),
+            Length::Rem(value) => write!(f, "{value}rem"),
+            Length::Percent(value) => write!(f, "{value}%"),
+            Length::Vw(value) => write!(f, "{value}vw"),
+            Length::Vh(value) => write!(f, "{value}vh"),
+        }
+    }
+}
+
+/// A length in pixels
+pub fn px(value: impl Into<f64>) -> Length {
+    Length::Px(value.into())
+}
+
+/// A length relative to the font size of the element
+pub fn em(value: impl Into<f64>) -> Length {
+    Length::Em(value.into())
+}
+
+/// A length relative to the font size of the root element
+pub fn rem(value: impl Into<f64>) -> Length {
+    Length::Rem(value.into())
+}
+
+/// A length relative to the size of the parent element
+pub fn percent(value: impl Into<f64>) -> Length {
+    Length::Percent(value.into())
+}
+
+/// A length relative to the width of the viewport
+pub fn vw(value: impl Into<f64>) -> Length {
+    Length::Vw(value.into())
+}
+
+/// A length relative to the height of the viewport
+pub fn vh(value: impl Into<f64>) -> Length {
+    Length::Vh(value.into())
+}
+
+/// A css color
+#[derive(Clone, Debug, PartialEq)]
+pub enum Color {
+    /// A color from red, green and blue channels and an alpha between 0 and 1
+    Rgba(u8, u8, u8, f32),
+    /// Any other css color, like `red`, `#ff0000` or `var(--accent)`
+    Other(String),
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Rgba(r, g, b, a) if *a >= 1.0 => write!(f, "rgb({r} {g} {b})"),
+            Color::Rgba(r, g, b, a) => write!(f, "rgb({r} {g} {b} / {a})"),
+            Color::Other(color) => write!(f, "{color}"),
+        }
+    }
+}
+
+impl From<&str> for Color {
+    fn from(color: &str) -> Self {
+        Color::Other(color.to_string())
+    }
+}
+
+impl From<String> for Color {
+    fn from(color: String) -> Self {
+        Color::Other(color)
+    }
+}
+
+/// An opaque color from red, green and blue channels
+pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgba(r, g, b, 1.0)
+}
+
+/// A color from red, green and blue channels and an alpha between 0 and 1
+pub fn rgba(r: u8, g: u8, b: u8, a: f32) -> Color {
+    Color::Rgba(r, g, b, a)
+}
+
+macro_rules! keywords {
+    (
+        $(
+            $(#[$attr:meta])*
+            $name:ident {
+                $(
+                    $(#[$variant_attr:meta])*
+                    $variant:ident => $value:literal,
+                )*
+            }
+        )*
+    ) => {
+        $(
+            $(#[$attr])*
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            pub enum $name {
+                $(
+                    $(#[$variant_attr])*
+                    #[doc = concat!("`", $value, "`")]
+                    $variant,
+                )*
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    f.write_str(match self {
+                        $($name::$variant => $value,)*
+                    })
+                }
+            }
+        )*
+    };
+}
+
+keywords! {
+    /// The value of the `display` property
+    Display {
+        None => "none",
+        Block => "block",
+        Inline => "inline",
+        InlineBlock => "inline-block",
+        Flex => "flex",
+        InlineFlex => "inline-flex",
+        Grid => "grid",
+        InlineGrid => "inline-grid",
+        Contents => "contents",
+    }
+
+    /// The value of the `position` property
+    Position {
+        Static => "static",
+        Relative => "relative",
+        Absolute => "absolute",
+        Fixed => "fixed",
+        Sticky => "sticky",
+    }
+
+    /// The value of the `flex-direction` property
+    FlexDirection {
+        Row => "row",
+        RowReverse => "row-reverse",
+        Column => "column",
+        ColumnReverse => "column-reverse",
+    }
+
+    /// The value of the `flex-wrap` property
+    FlexWrap {
+        NoWrap => "nowrap",
+        Wrap => "wrap",
+        WrapReverse => "wrap-reverse",
+    }
+
+    /// The value of the `align-items` and `align-self` properties
+    AlignItems {
+        Normal => "normal",
+        Stretch => "stretch",
+        Center => "center",
+        Start => "start",
+        End => "end",
+        FlexStart => "flex-start",
+        FlexEnd => "flex-end",
+        Baseline => "baseline",
+    }
+
+    /// The value of the `justify-content` property
+    JustifyContent {
+        Normal => "normal",
+        Center => "center",
+        Start => "start",
+        End => "end",
+        FlexStart => "flex-start",
+        FlexEnd => "flex-end",
+        SpaceBetween => "space-between",
+        SpaceAround => "space-around",
+        SpaceEvenly => "space-evenly",
+        Stretch => "stretch",
+    }
+
+    /// The value of the `overflow` properties
+    Overflow {
+        Visible => "visible",
+        Hidden => "hidden",
+        Clip => "clip",
+        Scroll => "scroll",
+        Auto => "auto",
+    }
+
+    /// The value of the `visibility` property
+    Visibility {
+        Visible => "visible",
+        Hidden => "hidden",
+        Collapse => "collapse",
+    }
+
+    /// The value of the `text-align` property
+    TextAlign {
+        Start => "start",
+        End => "end",
+        Left => "left",
+        Right => "right",
+        Center => "center",
+        Justify => "justify",
+    }
+
+    /// The value of the `cursor` property
+    Cursor {
+        Auto => "auto",
+        Default => "default",
+        Pointer => "pointer",
+        Text => "text",
+        Move => "move",
+        Wait => "wait",
+        NotAllowed => "not-allowed",
+        Grab => "grab",
+        Grabbing => "grabbing",
+    }
+
+    /// The value of the `box-sizing` property
+    BoxSizing {
+        ContentBox => "content-box",
+        BorderBox => "border-box",
+    }
+}
+
+macro_rules! properties {
+    ($($(#[$attr:meta])* $method:ident: $ty:ty => $name:literal;)*) => {
+        impl Style {
+            $(
+                $(#[$attr])*
+                #[doc = concat!("\n\nSets the `", $name, "` property")]
+                pub fn $method(self, value: impl Into<$ty>) -> Self {
+                    self.property($name, value.into())
+                }
+            )*
+        }
+    };
+}
+
+properties! {
+    /// How the element is laid out
+    display: Display => "display";
+    /// How the element is positioned
+    position: Position => "position";
+    /// The distance from the top of the containing block
+    top: Length => "top";
+    /// The distance from the right of the containing block
+    right: Length => "right";
+    /// The distance from the bottom of the containing block
+    bottom: Length => "bottom";
+    /// The distance from the left of the containing block
+    left: Length => "left";
+    /// The stack order of a positioned element
+    z_index: i32 => "z-index";
+
+    /// The direction flex items are placed in
+    flex_direction: FlexDirection => "flex-direction";
+    /// Whether flex items wrap onto multiple lines
+    flex_wrap: FlexWrap => "flex-wrap";
+    /// How much a flex item grows relative to the other items
+    flex_grow: f32 => "flex-grow";
+    /// How much a flex item shrinks relative to the other items
+    flex_shrink: f32 => "flex-shrink";
+    /// The initial size of a flex item
+    flex_basis: Length => "flex-basis";
+    /// How items are aligned on the cross axis
+    align_items: AlignItems => "align-items";
+    /// How this item is aligned on the cross axis
+    align_self: AlignItems => "align-self";
+    /// How items are distributed on the main axis
+    justify_content: JustifyContent => "justify-content";
+    /// The gap between rows and columns
+    gap: Length => "gap";
+    /// The gap between rows
+    row_gap: Length => "row-gap";
+    /// The gap between columns
+    column_gap: Length => "column-gap";
+
+    /// The width of the element
+    width: Length => "width";
+    /// The height of the element
+    height: Length => "height";
+    /// The minimum width of the element
+    min_width: Length => "min-width";
+    /// The minimum height of the element
+    min_height: Length => "min-height";
+    /// The maximum width of the element
+    max_width: Length => "max-width";
+    /// The maximum height of the element
+    max_height: Length => "max-height";
+    /// Whether the width and height include the padding and border
+    box_sizing: BoxSizing => "box-sizing";
+
+    /// The margin on every side
+    margin: Length => "margin";
+    /// The top margin
+    margin_top: Length => "margin-top";
+    /// The right margin
+    margin_right: Length => "margin-right";
+    /// The bottom margin
+    margin_bottom: Length => "margin-bottom";
+    /// The left margin
+    margin_left: Length => "margin-left";
+    /// The padding on every side
+    padding: Length => "padding";
+    /// The top padding
+    padding_top: Length => "padding-top";
+    /// The right padding
+    padding_right: Length => "padding-right";
+    /// The bottom padding
+    padding_bottom: Length => "padding-bottom";
+    /// The left padding
+    padding_left: Length => "padding-left";
+
+    /// What happens to content that is too big for the element
+    overflow: Overflow => "overflow";
+    /// What happens to content that is too wide for the element
+    overflow_x: Overflow => "overflow-x";
+    /// What happens to content that is too tall for the element
+    overflow_y: Overflow => "overflow-y";
+    /// Whether the element is visible
+    visibility: Visibility => "visibility";
+    /// How transparent the element is, from 0 to 1
+    opacity: f32 => "opacity";
+    /// The mouse cursor over the element
+    cursor: Cursor => "cursor";
+
+    /// The color of the text
+    color: Color => "color";
+    /// The background color
+    background_color: Color => "background-color";
+    /// The color of the border
+    border_color: Color => "border-color";
+    /// The width of the border
+    border_width: Length => "border-width";
+    /// The radius of the corners
+    border_radius: Length => "border-radius";
+
+    /// The size of the font
+    font_size: Length => "font-size";
+    /// The weight of the font, like 400 for normal or 700 for bold
+    font_weight: u16 => "font-weight";
+    /// The horizontal alignment of the text
+    text_align: TextAlign => "text-align";
+}
+
+#[test]
+fn properties_are_sorted_and_replaced() {
+    let style = Style::new()
+        .gap(px(8))
+        .display(Display::Flex)
+        .color(rgb(255, 0, 0))
+        .gap(rem(0.5))
+        .width(percent(50))
+        .opacity(0.5);
+    assert_eq!(
+        style.to_string(),
+        "color:rgb(255 0 0);display:flex;gap:0.5rem;opacity:0.5;width:50%;"
+    );
+    assert_eq!(style.get("gap"), Some("0.5rem"));
+    assert_eq!(style.remove("gap").get("gap"), None);
+}
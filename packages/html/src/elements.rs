@@ -1103,7 +1103,7 @@ builder_constructors! {
         usemap: String DEFAULT, // FIXME should be a fragment starting with '#'
         width: usize DEFAULT,
         referrerpolicy: String DEFAULT,
-        // sizes: SpacedList<String>, // FIXME it's not really just a string
+        sizes: String DEFAULT, // FIXME it's not really just a string
     };
 
     /// Build a
@@ -1211,6 +1211,11 @@ builder_constructors! {
     source None {
         src: Uri DEFAULT,
         r#type: Mime "type",
+        srcset: String DEFAULT,
+        sizes: String DEFAULT,
+        media: String DEFAULT,
+        width: usize DEFAULT,
+        height: usize DEFAULT,
     };
 
 
@@ -1479,7 +1484,12 @@ builder_constructors! {
         /// - `week`
 
         r#type: InputType "type",
-        // value: String,
+
+        /// The current value of the input
+        ///
+        /// Setting the value makes the input controlled. On the web, if the app handles an edit with `oninput` or
+        /// `onchange` and doesn't change the value in response, the edit is reverted. Use `initial_value` instead to
+        /// let the browser manage the value.
         value: String volatile,
         initial_value: String DEFAULT,
     };
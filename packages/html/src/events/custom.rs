@@ -0,0 +1,157 @@
+use dioxus_core::{AttributeValue, Event, ListenerOptions};
+
+pub type CustomEvent = Event<CustomEventData>;
+
+/// The data of a [`CustomEvent`](https://developer.mozilla.org/en-US/docs/Web/API/CustomEvent) dispatched by a custom
+/// element or any other event that doesn't have its own event data type.
+pub struct CustomEventData {
+    inner: Box<dyn HasCustomEventData>,
+}
+
+impl<E: HasCustomEventData> From<E> for CustomEventData {
+    fn from(e: E) -> Self {
+        Self { inner: Box::new(e) }
+    }
+}
+
+impl std::fmt::Debug for CustomEventData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomEventData")
+            .field("detail", &self.detail_json())
+            .finish()
+    }
+}
+
+impl PartialEq for CustomEventData {
+    fn eq(&self, other: &Self) -> bool {
+        self.detail_json() == other.detail_json()
+    }
+}
+
+impl CustomEventData {
+    /// Create a new CustomEventData
+    pub fn new(inner: impl HasCustomEventData + 'static) -> Self {
+        Self {
+            inner: Box::new(inner),
+        }
+    }
+
+    /// The `detail` of the event as JSON. Returns `None` if the event has no detail or the detail can't be
+    /// converted to JSON.
+    pub fn detail_json(&self) -> Option<String> {
+        self.inner.detail_json()
+    }
+
+    /// Deserialize the `detail` of the event into a typed value. Events without a detail deserialize from `null`, so
+    /// use an `Option` for details that may be missing.
+    #[cfg(feature = "serialize")]
+    pub fn detail<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        match self.detail_json() {
+            Some(json) => serde_json::from_str(&json),
+            None => serde_json::from_str("null"),
+        }
+    }
+
+    /// Downcast this event to a concrete event type
+    #[inline(always)]
+    pub fn downcast<T: 'static>(&self) -> Option<&T> {
+        self.inner.as_any().downcast_ref::<T>()
+    }
+}
+
+#[cfg(feature = "serialize")]
+/// A serialized version of CustomEventData
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct SerializedCustomEventData {
+    #[serde(default)]
+    pub detail: Option<serde_json::Value>,
+}
+
+#[cfg(feature = "serialize")]
+impl From<&CustomEventData> for SerializedCustomEventData {
+    fn from(data: &CustomEventData) -> Self {
+        Self {
+            detail: data
+                .detail_json()
+                .and_then(|json| serde_json::from_str(&json).ok()),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl HasCustomEventData for SerializedCustomEventData {
+    fn detail_json(&self) -> Option<String> {
+        self.detail.as_ref().map(|detail| detail.to_string())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl serde::Serialize for CustomEventData {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedCustomEventData::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> serde::Deserialize<'de> for CustomEventData {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = SerializedCustomEventData::deserialize(deserializer)?;
+        Ok(Self {
+            inner: Box::new(data),
+        })
+    }
+}
+
+pub trait HasCustomEventData: std::any::Any {
+    /// The `detail` of the event as JSON
+    fn detail_json(&self) -> Option<String>;
+
+    /// return self as Any
+    fn as_any(&self) -> &dyn std::any::Any;
+}
+
+/// Listen to an event that doesn't have a typed listener, like the custom events of a web component. Use it with
+/// the name of the event after `on` as the attribute name:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # #[derive(serde::Deserialize)]
+/// # struct Item { id: u32 }
+/// let mut selected = use_signal(|| None);
+///
+/// rsx! {
+///     design-select {
+///         // Listen to the `item-selected` custom event and read the typed detail
+///         "onitem-selected": custom_event(move |event: CustomEvent| {
+///             selected.set(event.detail::<Item>().ok().map(|item| item.id));
+///         }),
+///     }
+/// };
+/// ```
+///
+/// Most custom events don't bubble, so the listener is attached directly to the element instead of the listener
+/// Dioxus shares at the root of the app.
+#[inline]
+pub fn custom_event<__Marker>(
+    mut _f: impl ::dioxus_core::prelude::SuperInto<
+        ::dioxus_core::prelude::EventHandler<CustomEvent>,
+        __Marker,
+    >,
+) -> AttributeValue {
+    // Run super into with an owner that is moved into the listener closure like the typed listeners
+    let owner = <::generational_box::UnsyncStorage as ::generational_box::AnyStorage>::owner();
+    let event_handler = ::dioxus_core::prelude::with_owner(owner.clone(), || _f.super_into());
+    let options = event_handler.listener_options().union(ListenerOptions {
+        direct: true,
+        ..Default::default()
+    });
+    AttributeValue::listener_with_options(options, move |e: Event<crate::PlatformEventData>| {
+        // Force the owner to be moved into the event handler
+        _ = &owner;
+        event_handler.call(e.map(|e| e.into()));
+    })
+}
@@ -30,6 +30,7 @@ impl std::fmt::Debug for DragData {
             .field("modifiers", &self.modifiers())
             .field("held_buttons", &self.held_buttons())
             .field("trigger_button", &self.trigger_button())
+            .field("data_transfer", &self.data_transfer())
             .finish()
     }
 }
@@ -40,6 +41,7 @@ impl PartialEq for DragData {
             && self.modifiers() == other.modifiers()
             && self.held_buttons() == other.held_buttons()
             && self.trigger_button() == other.trigger_button()
+            && self.data_transfer() == other.data_transfer()
     }
 }
 
@@ -51,6 +53,11 @@ impl DragData {
         }
     }
 
+    /// Get the data that is being dragged
+    pub fn data_transfer(&self) -> DataTransfer {
+        self.inner.data_transfer()
+    }
+
     /// Downcast this event data to a specific type
     #[inline(always)]
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
@@ -58,6 +65,62 @@ impl DragData {
     }
 }
 
+/// The data that is being dragged in a [`DragEvent`].
+///
+/// Files that are dragged are listed as items with the kind `"file"`. Read their contents with
+/// [`HasFileData::files`](crate::HasFileData::files).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataTransfer {
+    /// The effect the drop target selected: `"none"`, `"copy"`, `"link"` or `"move"`
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub drop_effect: String,
+
+    /// The effects the source of the drag allows like `"copy"`, `"copyMove"` or `"all"`
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub effect_allowed: String,
+
+    /// The items that are being dragged
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub items: Vec<DataTransferItem>,
+}
+
+impl DataTransfer {
+    /// Get the string data for a MIME type like `"text/plain"` or `"text/uri-list"`
+    pub fn get_data(&self, mime_type: &str) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|item| item.kind == "string" && item.mime_type == mime_type)
+            .and_then(|item| item.data.as_deref())
+    }
+
+    /// Get the MIME types of the items that are being dragged
+    pub fn types(&self) -> impl Iterator<Item = &str> {
+        self.items.iter().map(|item| item.mime_type.as_str())
+    }
+
+    /// Check if any files are being dragged
+    pub fn has_files(&self) -> bool {
+        self.items.iter().any(|item| item.kind == "file")
+    }
+}
+
+/// An item that is being dragged in a [`DataTransfer`]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataTransferItem {
+    /// The kind of the item: `"string"` or `"file"`
+    pub kind: String,
+
+    /// The MIME type of the item like `"text/plain"` or `"image/png"`
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub mime_type: String,
+
+    /// The data of a string item. Browsers only expose the data during `dragstart` and `drop` events.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub data: Option<String>,
+}
+
 impl crate::HasFileData for DragData {
     fn files(&self) -> Option<std::sync::Arc<dyn crate::file_data::FileEngine>> {
         self.inner.files()
@@ -113,6 +176,9 @@ pub struct SerializedDragData {
 
     #[serde(default)]
     files: Option<crate::file_data::SerializedFileEngine>,
+
+    #[serde(default)]
+    data_transfer: DataTransfer,
 }
 
 #[cfg(feature = "serialize")]
@@ -121,6 +187,7 @@ impl SerializedDragData {
         Self {
             mouse: crate::point_interaction::SerializedPointInteraction::from(drag),
             files: None,
+            data_transfer: drag.data_transfer(),
         }
     }
 }
@@ -130,6 +197,10 @@ impl HasDragData for SerializedDragData {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn data_transfer(&self) -> DataTransfer {
+        self.data_transfer.clone()
+    }
 }
 
 #[cfg(feature = "serialize")]
@@ -213,6 +284,11 @@ impl<'de> serde::Deserialize<'de> for DragData {
 pub trait HasDragData: HasMouseData + crate::HasFileData {
     /// return self as Any
     fn as_any(&self) -> &dyn std::any::Any;
+
+    /// The data that is being dragged
+    fn data_transfer(&self) -> DataTransfer {
+        DataTransfer::default()
+    }
 }
 
 impl_event! {
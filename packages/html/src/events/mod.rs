@@ -31,8 +31,10 @@ macro_rules! impl_event {
                 // Instead we run super into with an owner that is moved into the listener closure so it will be dropped when the closure is dropped.
                 let owner = <::generational_box::UnsyncStorage as ::generational_box::AnyStorage>::owner();
                 let event_handler = ::dioxus_core::prelude::with_owner(owner.clone(), || _f.super_into());
+                // The listener options are kept as suffixes of the name that the renderer uses to register the listener
+                let name = impl_event!(@options event_handler, impl_event!(@name $name $($js_name)?));
                 ::dioxus_core::Attribute::new(
-                    impl_event!(@name $name $($js_name)?),
+                    name,
                     ::dioxus_core::AttributeValue::listener(move |e: ::dioxus_core::Event<crate::PlatformEventData>| {
                         // Force the owner to be moved into the event handler
                         _ = &owner;
@@ -60,9 +62,58 @@ macro_rules! impl_event {
                     super::$name(event_handler)
                 }
             }
+
+            ::paste::paste! {
+                #[doc = concat!("Capture phase version of [`", stringify!($name), "`]. Capture listeners run from the root of the tree down to the target before any bubbling listener, so a parent can see or stop the event before its children. Call [`Event::stop_propagation`](::dioxus_core::Event::stop_propagation) to keep the event from reaching the target.")]
+                ///
+                #[doc = concat!("For events that don't bubble, capture listeners only run when an element inside the listener also listens to `", stringify!($name), "`.")]
+                ///
+                $( #[$attr] )*
+                #[inline]
+                pub fn [<$name capture>]<__Marker>(mut _f: impl ::dioxus_core::prelude::SuperInto<::dioxus_core::prelude::EventHandler<::dioxus_core::Event<$data>>, __Marker>) -> ::dioxus_core::Attribute {
+                    let owner = <::generational_box::UnsyncStorage as ::generational_box::AnyStorage>::owner();
+                    let event_handler = ::dioxus_core::prelude::with_owner(owner.clone(), || _f.super_into());
+                    let name = impl_event!(@options event_handler, concat!(impl_event!(@name $name $($js_name)?), "capture"));
+                    ::dioxus_core::Attribute::new(
+                        name,
+                        ::dioxus_core::AttributeValue::listener(move |e: ::dioxus_core::Event<crate::PlatformEventData>| {
+                            // Force the owner to be moved into the event handler
+                            _ = &owner;
+                            event_handler.call(e.map(|e| e.into()));
+                        }),
+                        None,
+                        false,
+                    ).into()
+                }
+
+                #[doc(hidden)]
+                $( #[$attr] )*
+                pub mod [<$name capture>] {
+                    use super::*;
+
+                    $( #[$attr] )*
+                    pub fn call_with_explicit_closure<
+                        __Marker,
+                        Return: ::dioxus_core::SpawnIfAsync<__Marker> + 'static,
+                    >(
+                        event_handler: impl FnMut(::dioxus_core::Event<$data>) -> Return + 'static,
+                    ) -> ::dioxus_core::Attribute {
+                        #[allow(deprecated)]
+                        super::[<$name capture>](event_handler)
+                    }
+                }
+            }
         )*
     };
 
+    (@options $event_handler:ident, $name:expr) => {
+        match $event_handler.listener_options() {
+            ::dioxus_core::ListenerOptions { passive: false, direct: false } => $name,
+            ::dioxus_core::ListenerOptions { passive: true, direct: false } => concat!($name, "passive"),
+            ::dioxus_core::ListenerOptions { passive: false, direct: true } => concat!($name, "direct"),
+            ::dioxus_core::ListenerOptions { passive: true, direct: true } => concat!($name, "passivedirect"),
+        }
+    };
     (@name $name:ident $js_name:literal) => {
         $js_name
     };
@@ -122,6 +173,8 @@ pub trait HtmlEventConverter: Send + Sync {
     fn convert_clipboard_data(&self, event: &PlatformEventData) -> ClipboardData;
     /// Convert a general event to a composition data event
     fn convert_composition_data(&self, event: &PlatformEventData) -> CompositionData;
+    /// Convert a general event to a custom event data event
+    fn convert_custom_data(&self, event: &PlatformEventData) -> CustomEventData;
     /// Convert a general event to a drag data event
     fn convert_drag_data(&self, event: &PlatformEventData) -> DragData;
     /// Convert a general event to a focus data event
@@ -176,6 +229,12 @@ impl From<&PlatformEventData> for CompositionData {
     }
 }
 
+impl From<&PlatformEventData> for CustomEventData {
+    fn from(val: &PlatformEventData) -> Self {
+        with_event_converter(|c| c.convert_custom_data(val))
+    }
+}
+
 impl From<&PlatformEventData> for DragData {
     fn from(val: &PlatformEventData) -> Self {
         with_event_converter(|c| c.convert_drag_data(val))
@@ -281,6 +340,7 @@ impl From<&PlatformEventData> for WheelData {
 mod animation;
 mod clipboard;
 mod composition;
+mod custom;
 mod drag;
 mod focus;
 mod form;
@@ -302,6 +362,7 @@ mod wheel;
 pub use animation::*;
 pub use clipboard::*;
 pub use composition::*;
+pub use custom::*;
 pub use drag::*;
 pub use focus::*;
 pub use form::*;
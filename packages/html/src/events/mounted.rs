@@ -43,6 +43,11 @@ pub trait RenderedElementBacking: std::any::Any {
     fn set_focus(&self, _focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
         Box::pin(async { Err(MountedError::NotSupported) })
     }
+
+    /// Trap keyboard focus inside of the element or release the trap and restore focus to the element that was focused before
+    fn set_focus_trap(&self, _trap: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        Box::pin(async { Err(MountedError::NotSupported) })
+    }
 }
 
 impl RenderedElementBacking for () {
@@ -126,6 +131,25 @@ impl MountedData {
         self.inner.set_focus(focus)
     }
 
+    /// Move the focus to the element
+    pub fn focus(&self) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        self.inner.set_focus(true)
+    }
+
+    /// Remove the focus from the element
+    pub fn blur(&self) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        self.inner.set_focus(false)
+    }
+
+    /// Keep keyboard focus inside of the element, for example while a dialog is open.
+    ///
+    /// While the trap is active, tabbing past the last focusable child wraps around to the first one and focus that
+    /// leaves the element is moved back into it. Releasing the trap restores focus to the element that was focused
+    /// before the trap was created. The [`FocusTrap`](crate::FocusTrap) component manages the trap for you.
+    pub fn set_focus_trap(&self, trap: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        self.inner.set_focus_trap(trap)
+    }
+
     /// Downcast this event to a concrete event type
     #[inline(always)]
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
@@ -56,4 +56,81 @@ pub trait FileEngine {
 
     // returns a file in platform's native representation
     async fn get_native_file(&self, file: &str) -> Option<Box<dyn std::any::Any>>;
+
+    // read the bytes from start up to end of a file. Platforms that can seek in files should override this
+    async fn read_file_range(&self, file: &str, start: u64, end: u64) -> Option<Vec<u8>> {
+        let bytes = self.read_file(file).await?;
+        let end = (end as usize).min(bytes.len());
+        let start = (start as usize).min(end);
+        Some(bytes[start..end].to_vec())
+    }
+}
+
+/// Reads a file from a [`FileEngine`] in chunks so large files don't need to be loaded into memory at once.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_html::{FileChunks, HasFileData};
+/// fn app() -> Element {
+///     rsx! {
+///         div {
+///             ondrop: move |event| async move {
+///                 let Some(engine) = event.files() else { return };
+///                 for file in engine.files() {
+///                     let mut chunks = FileChunks::new(engine.clone(), &file, 64 * 1024);
+///                     while let Some(chunk) = chunks.next_chunk().await {
+///                         println!("read {} bytes of {file}", chunk.len());
+///                     }
+///                 }
+///             },
+///         }
+///     }
+/// }
+/// ```
+pub struct FileChunks {
+    engine: std::sync::Arc<dyn FileEngine>,
+    file: String,
+    chunk_size: u64,
+    offset: u64,
+    size: Option<u64>,
+}
+
+impl FileChunks {
+    /// Create a reader for a file that returns chunks of at most `chunk_size` bytes
+    pub fn new(
+        engine: std::sync::Arc<dyn FileEngine>,
+        file: impl ToString,
+        chunk_size: u64,
+    ) -> Self {
+        Self {
+            engine,
+            file: file.to_string(),
+            chunk_size: chunk_size.max(1),
+            offset: 0,
+            size: None,
+        }
+    }
+
+    /// Read the next chunk of the file. Returns `None` once the whole file was read or if the file can't be read.
+    pub async fn next_chunk(&mut self) -> Option<Vec<u8>> {
+        let size = match self.size {
+            Some(size) => size,
+            None => *self.size.insert(self.engine.file_size(&self.file).await?),
+        };
+        if self.offset >= size {
+            return None;
+        }
+        let end = (self.offset + self.chunk_size).min(size);
+        let chunk = self
+            .engine
+            .read_file_range(&self.file, self.offset, end)
+            .await?;
+        self.offset = end;
+        Some(chunk)
+    }
+
+    /// The number of bytes that were read so far
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
 }
@@ -0,0 +1,184 @@
+//! Utilities for managing keyboard focus like focus traps for dialogs.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate as dioxus_elements;
+use crate::{FocusEvent, MountedData};
+use dioxus_core::prelude::*;
+use dioxus_core_macro::*;
+
+/// Props for the [`FocusTrap`] component
+#[derive(Clone, Props, PartialEq)]
+pub struct FocusTrapProps {
+    /// If the trap is currently holding focus. Defaults to `true`.
+    #[props(default = true)]
+    pub active: bool,
+
+    /// Extra attributes to set on the container element
+    #[props(extends = GlobalAttributes)]
+    pub attributes: Vec<Attribute>,
+
+    /// The children to trap focus in
+    pub children: Element,
+}
+
+/// Keep keyboard focus inside of the children while the trap is active.
+///
+/// When the trap activates, focus moves to the first focusable child. Tabbing past the last child wraps around to the
+/// first one. When the trap is deactivated or removed from the page, focus returns to the element that was focused
+/// before the trap activated.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn App() -> Element {
+///     let mut open = use_signal(|| false);
+///     rsx! {
+///         button { onclick: move |_| open.set(true), "Open dialog" }
+///         if open() {
+///             FocusTrap {
+///                 role: "dialog",
+///                 input { placeholder: "Name" }
+///                 button { onclick: move |_| open.set(false), "Close" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn FocusTrap(props: FocusTrapProps) -> Element {
+    let container = use_hook(|| Rc::new(RefCell::new(None::<(Rc<MountedData>, bool)>)));
+
+    // Sync the trap with the active prop after the container is mounted
+    if let Some((mounted, trapped)) = &mut *container.borrow_mut() {
+        if *trapped != props.active {
+            *trapped = props.active;
+            let set_trap = mounted.set_focus_trap(props.active);
+            spawn(async move {
+                if let Err(err) = set_trap.await {
+                    tracing::error!("Failed to update focus trap: {err}");
+                }
+            });
+        }
+    }
+
+    use_drop({
+        let container = container.clone();
+        move || {
+            if let Some((mounted, true)) = container.borrow_mut().take() {
+                // The element is being removed so nothing else will poll the result. Releasing the trap happens eagerly
+                _ = mounted.set_focus_trap(false);
+            }
+        }
+    });
+
+    let active = props.active;
+    let onmounted = move |event: Event<MountedData>| {
+        let mounted = event.data();
+        if active {
+            let set_trap = mounted.set_focus_trap(true);
+            spawn(async move {
+                if let Err(err) = set_trap.await {
+                    tracing::error!("Failed to trap focus: {err}");
+                }
+            });
+        }
+        *container.borrow_mut() = Some((mounted, active));
+    };
+
+    static TEMPLATE: Template = Template {
+        roots: &[TemplateNode::Element {
+            tag: "div",
+            namespace: None,
+            attrs: &[
+                TemplateAttribute::Dynamic { id: 0usize },
+                TemplateAttribute::Dynamic { id: 1usize },
+            ],
+            children: &[TemplateNode::Dynamic { id: 0usize }],
+        }],
+        node_paths: &[&[0u8, 0u8]],
+        attr_paths: &[&[0u8], &[0u8]],
+    };
+    Ok(VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([props.children.into_dyn_node()]),
+        Box::new([
+            Box::new([crate::events::onmounted(onmounted)]),
+            props.attributes.into_boxed_slice(),
+        ]),
+    ))
+}
+
+/// Track if focus is anywhere inside of an element, like the css `:focus-within` selector.
+///
+/// Attach the handlers from [`UseFocusWithin::onfocusin`] and [`UseFocusWithin::onfocusout`] to the element you want
+/// to track.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn App() -> Element {
+///     let focus = use_focus_within();
+///     rsx! {
+///         div {
+///             class: if focus.is_focused() { "search active" } else { "search" },
+///             onfocusin: focus.onfocusin(),
+///             onfocusout: focus.onfocusout(),
+///             input { placeholder: "Search" }
+///             button { "Go" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_focus_within() -> UseFocusWithin {
+    use_hook(|| UseFocusWithin {
+        focused: Rc::new(Cell::new(false)),
+        update: schedule_update(),
+    })
+}
+
+/// The state returned by [`use_focus_within`]
+#[derive(Clone)]
+pub struct UseFocusWithin {
+    focused: Rc<Cell<bool>>,
+    update: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl PartialEq for UseFocusWithin {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.focused, &other.focused)
+    }
+}
+
+impl UseFocusWithin {
+    /// Check if the element or any of its children currently have focus. The component that called
+    /// [`use_focus_within`] reruns when this changes.
+    pub fn is_focused(&self) -> bool {
+        self.focused.get()
+    }
+
+    /// The handler for the `onfocusin` event of the tracked element
+    pub fn onfocusin(&self) -> impl FnMut(FocusEvent) {
+        let state = self.clone();
+        move |_| state.set(true)
+    }
+
+    /// The handler for the `onfocusout` event of the tracked element
+    pub fn onfocusout(&self) -> impl FnMut(FocusEvent) {
+        let state = self.clone();
+        move |_| state.set(false)
+    }
+
+    fn set(&self, focused: bool) {
+        if self.focused.replace(focused) != focused {
+            (self.update)();
+        }
+    }
+}
@@ -16,6 +16,7 @@
 //!
 //! Currently, we don't validate for structures, but do validate attributes.
 
+pub mod css;
 pub mod elements;
 #[cfg(feature = "hot-reload-context")]
 pub use elements::HtmlCtx;
@@ -23,11 +24,15 @@ pub use elements::HtmlCtx;
 pub use elements::{map_html_attribute_to_rsx, map_html_element_to_rsx};
 pub mod events;
 pub(crate) mod file_data;
+mod focus;
 pub use file_data::*;
 mod attribute_groups;
 pub mod geometry;
 pub mod input_data;
 pub mod point_interaction;
+mod portal;
+pub mod property;
+mod raw_html;
 mod render_template;
 
 #[cfg(feature = "serialize")]
@@ -39,6 +44,10 @@ pub use transit::*;
 pub use attribute_groups::*;
 pub use elements::*;
 pub use events::*;
+pub use focus::*;
+pub use portal::*;
+pub use property::*;
+pub use raw_html::*;
 pub use render_template::*;
 
 pub mod extensions {
@@ -50,6 +59,11 @@ pub mod prelude {
     pub use crate::attribute_groups::{GlobalAttributesExtension, SvgAttributesExtension};
     pub use crate::elements::extensions::*;
     pub use crate::events::*;
+    pub use crate::focus::{use_focus_within, FocusTrap, FocusTrapProps, UseFocusWithin};
     pub use crate::point_interaction::*;
+    pub use crate::portal::{Portal, PortalProps};
+    #[cfg(feature = "serialize")]
+    pub use crate::property::property;
+    pub use crate::raw_html::{HtmlSanitizer, RawHtml, RawHtmlProps, SanitizePolicy, Sanitizer};
     pub use keyboard_types::{self, Code, Key, Location, Modifiers};
 }
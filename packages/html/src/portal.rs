@@ -0,0 +1,131 @@
+//! Portals render their children into a different part of the page while keeping them in the component tree.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::MountedData;
+use dioxus_core::prelude::*;
+use dioxus_core_macro::*;
+
+/// A renderer that can move the container of a [`Portal`] somewhere else in the page.
+///
+/// Renderers that support portals provide an `Rc<dyn PortalHost>` as a root context. If no host is provided, the
+/// children of the portal are rendered in place.
+pub trait PortalHost {
+    /// Move the portal container into the element that matches the `target` css selector
+    fn mount(&self, container: &MountedData, target: &str);
+
+    /// Clean up the portal container after the portal is removed from the component tree
+    fn unmount(&self, container: &MountedData);
+}
+
+/// Props for the [`Portal`] component
+#[derive(Clone, Props, PartialEq)]
+pub struct PortalProps {
+    /// A css selector for the element the children are rendered into. Defaults to the body of the document.
+    #[props(into, default = "body".to_string())]
+    pub target: String,
+
+    /// The children to render in the target element
+    pub children: Element,
+}
+
+/// Render the children into a different element in the page, like the body for modals and tooltips.
+///
+/// The children are still part of the component tree where the portal is rendered. They can read context from the
+/// components above the portal and events bubble up through the components above the portal, not the elements
+/// above the target.
+///
+/// On the server and in renderers that don't support portals, the children are rendered in place.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn App() -> Element {
+///     let mut open = use_signal(|| false);
+///     rsx! {
+///         button { onclick: move |_| open.set(true), "Open modal" }
+///         if open() {
+///             Portal {
+///                 div { class: "modal", onclick: move |_| open.set(false), "Click to close" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn Portal(props: PortalProps) -> Element {
+    let host = use_hook(try_consume_context::<Rc<dyn PortalHost>>);
+    let container = use_hook(|| Rc::new(RefCell::new(None::<(Rc<MountedData>, String)>)));
+
+    // If the target changes after the container is mounted, move the container to the new target
+    if let (Some(host), Some((mounted, target))) = (&host, &mut *container.borrow_mut()) {
+        if *target != props.target {
+            host.mount(mounted, &props.target);
+            *target = props.target.clone();
+        }
+    }
+
+    use_drop({
+        let host = host.clone();
+        let container = container.clone();
+        move || {
+            if let (Some(host), Some((mounted, _))) = (host, container.borrow_mut().take()) {
+                host.unmount(&mounted);
+            }
+        }
+    });
+
+    let target = props.target.clone();
+    let onmounted = move |event: Event<MountedData>| {
+        let Some(host) = &host else {
+            return;
+        };
+        let mounted = event.data();
+        host.mount(&mounted, &target);
+        *container.borrow_mut() = Some((mounted, target.clone()));
+    };
+
+    // The virtual dom inserts siblings of the portal next to its first and last root. The placeholders keep those
+    // roots in place while the container is moved to the target
+    static TEMPLATE: Template = Template {
+        roots: &[
+            TemplateNode::Dynamic { id: 0usize },
+            TemplateNode::Element {
+                tag: "div",
+                namespace: None,
+                attrs: &[
+                    TemplateAttribute::Static {
+                        name: "style",
+                        namespace: None,
+                        value: "display: contents",
+                    },
+                    TemplateAttribute::Dynamic { id: 0usize },
+                    TemplateAttribute::Dynamic { id: 1usize },
+                ],
+                children: &[TemplateNode::Dynamic { id: 1usize }],
+            },
+            TemplateNode::Dynamic { id: 2usize },
+        ],
+        node_paths: &[&[0u8], &[1u8, 0u8], &[2u8]],
+        attr_paths: &[&[1u8], &[1u8]],
+    };
+    Ok(VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([
+            None::<VNode>.into_dyn_node(),
+            props.children.into_dyn_node(),
+            None::<VNode>.into_dyn_node(),
+        ]),
+        Box::new([
+            Box::new([Attribute::new(
+                "data-dioxus-portal",
+                props.target,
+                None,
+                false,
+            )]),
+            Box::new([crate::events::onmounted(onmounted)]),
+        ]),
+    ))
+}
@@ -0,0 +1,51 @@
+//! Set DOM properties instead of attributes on an element, like the rich data inputs of web components.
+
+#[cfg(feature = "serialize")]
+use dioxus_core::AttributeValue;
+
+/// A value that renderers set as a property of the element instead of an attribute. Create one with [`property`].
+///
+/// The value is kept as JSON so every renderer can rebuild it on the other side of the interpreter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementProperty {
+    json: String,
+}
+
+impl ElementProperty {
+    /// Create a property from a value that is already encoded as JSON
+    pub fn from_json(json: impl Into<String>) -> Self {
+        Self { json: json.into() }
+    }
+
+    /// The value of the property as JSON
+    pub fn json(&self) -> &str {
+        &self.json
+    }
+}
+
+/// Set a DOM property instead of an attribute. Attributes can only be strings, so web components usually take
+/// objects and arrays through properties:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// let options = vec!["Small", "Medium", "Large"];
+///
+/// rsx! {
+///     design-select {
+///         // Sets `element.options = ["Small", "Medium", "Large"]`
+///         "options": property(&options),
+///     }
+/// };
+/// ```
+///
+/// The property is set again when the serialized value changes. Server side rendering skips properties.
+#[cfg(feature = "serialize")]
+pub fn property(value: impl serde::Serialize) -> AttributeValue {
+    match serde_json::to_string(&value) {
+        Ok(json) => AttributeValue::any_value(ElementProperty::from_json(json)),
+        Err(err) => {
+            tracing::error!("Failed to serialize element property: {err}");
+            AttributeValue::None
+        }
+    }
+}
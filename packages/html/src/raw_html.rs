@@ -0,0 +1,625 @@
+//! Render html strings from a CMS or markdown renderer after running them through a sanitizer.
+
+use std::{collections::HashSet, rc::Rc};
+
+use crate as dioxus_elements;
+use dioxus_core::prelude::*;
+use dioxus_core_macro::*;
+
+/// Cleans up html before it is rendered with [`RawHtml`].
+///
+/// [`SanitizePolicy`] is the default sanitizer. Any `Fn(&str) -> String` closure is also a sanitizer, which lets you
+/// plug in another sanitizer crate.
+pub trait HtmlSanitizer {
+    /// Return the html that is safe to render
+    fn sanitize(&self, html: &str) -> String;
+}
+
+impl<F: Fn(&str) -> String> HtmlSanitizer for F {
+    fn sanitize(&self, html: &str) -> String {
+        self(html)
+    }
+}
+
+/// A shared [`HtmlSanitizer`] that can be passed to [`RawHtml`] or provided as context to every [`RawHtml`] below
+/// the component that provides it.
+#[derive(Clone)]
+pub struct Sanitizer(Rc<dyn HtmlSanitizer>);
+
+impl Sanitizer {
+    /// Create a sanitizer
+    pub fn new(sanitizer: impl HtmlSanitizer + 'static) -> Self {
+        Self(Rc::new(sanitizer))
+    }
+
+    /// A sanitizer that renders the html as is. Only use it for html your app generated itself.
+    pub fn trusted() -> Self {
+        Self::new(|html: &str| html.to_string())
+    }
+
+    /// Run the sanitizer
+    pub fn sanitize(&self, html: &str) -> String {
+        self.0.sanitize(html)
+    }
+}
+
+impl Default for Sanitizer {
+    fn default() -> Self {
+        Self::new(SanitizePolicy::default())
+    }
+}
+
+impl<S: HtmlSanitizer + 'static> From<S> for Sanitizer {
+    fn from(sanitizer: S) -> Self {
+        Self::new(sanitizer)
+    }
+}
+
+impl PartialEq for Sanitizer {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl std::fmt::Debug for Sanitizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sanitizer").finish()
+    }
+}
+
+/// Elements that are removed with everything inside of them
+const DROPPED_ELEMENTS: &[&str] = &[
+    "script", "style", "iframe", "object", "embed", "noscript", "template", "textarea", "title",
+    "svg", "math",
+];
+
+/// Elements that never have children or a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Attributes that hold a url
+const URL_ATTRIBUTES: &[&str] = &["href", "src", "cite"];
+
+/// An allowlist of the tags, attributes and url schemes that are kept in sanitized html.
+///
+/// The default policy keeps the formatting that markdown and rich text editors produce: text formatting, headings,
+/// lists, tables, links and images. Tags that are not allowed are removed but their text is kept, except for
+/// elements like `script` and `style` that are always removed with their contents. Event handler attributes like
+/// `onclick` and `style` attributes are removed unless you allow them.
+///
+/// ```rust
+/// # use dioxus_html::SanitizePolicy;
+/// let policy = SanitizePolicy::new().allow_tags(["video"]).allow_attributes(["controls"]);
+/// assert_eq!(
+///     policy.sanitize(r#"<video controls src="/intro.mp4" onplay="track()"></video>"#),
+///     r#"<video controls src="/intro.mp4"></video>"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SanitizePolicy {
+    tags: HashSet<String>,
+    attributes: HashSet<String>,
+    url_schemes: HashSet<String>,
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        Self {
+            tags: [
+                "a",
+                "abbr",
+                "b",
+                "blockquote",
+                "br",
+                "caption",
+                "code",
+                "dd",
+                "del",
+                "details",
+                "div",
+                "dl",
+                "dt",
+                "em",
+                "figcaption",
+                "figure",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "hr",
+                "i",
+                "img",
+                "ins",
+                "kbd",
+                "li",
+                "mark",
+                "ol",
+                "p",
+                "pre",
+                "q",
+                "s",
+                "small",
+                "span",
+                "strong",
+                "sub",
+                "summary",
+                "sup",
+                "table",
+                "tbody",
+                "td",
+                "tfoot",
+                "th",
+                "thead",
+                "tr",
+                "u",
+                "ul",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            attributes: [
+                "alt", "cite", "class", "colspan", "datetime", "dir", "height", "href", "id",
+                "lang", "open", "rel", "rowspan", "src", "start", "title", "width",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            url_schemes: ["http", "https", "mailto"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl SanitizePolicy {
+    /// Create the default policy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep these tags in addition to the tags the policy already allows
+    pub fn allow_tags<S: Into<String>>(mut self, tags: impl IntoIterator<Item = S>) -> Self {
+        self.tags
+            .extend(tags.into_iter().map(|tag| tag.into().to_ascii_lowercase()));
+        self
+    }
+
+    /// Keep these attributes on every allowed tag in addition to the attributes the policy already allows
+    pub fn allow_attributes<S: Into<String>>(
+        mut self,
+        attributes: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.attributes.extend(
+            attributes
+                .into_iter()
+                .map(|attribute| attribute.into().to_ascii_lowercase()),
+        );
+        self
+    }
+
+    /// Keep urls with these schemes in addition to `http`, `https` and `mailto`. Relative urls are always kept.
+    pub fn allow_url_schemes<S: Into<String>>(
+        mut self,
+        schemes: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.url_schemes.extend(
+            schemes
+                .into_iter()
+                .map(|scheme| scheme.into().to_ascii_lowercase()),
+        );
+        self
+    }
+
+    /// Remove everything the policy doesn't allow from the html. Tags that are left open are closed so the html can't
+    /// change the elements around it.
+    pub fn sanitize(&self, html: &str) -> String {
+        let mut out = String::with_capacity(html.len());
+        let mut open: Vec<String> = Vec::new();
+        let mut rest = html;
+
+        while let Some(start) = rest.find('<') {
+            push_text(&mut out, &rest[..start]);
+            rest = &rest[start..];
+
+            // Comments, doctypes and processing instructions are dropped
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+                continue;
+            }
+            if rest.starts_with("<!") || rest.starts_with("<?") {
+                rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+                continue;
+            }
+
+            // A `<` that doesn't start a tag is just text
+            let Some(tag) = Tag::parse(rest) else {
+                out.push_str("&lt;");
+                rest = &rest[1..];
+                continue;
+            };
+            rest = &rest[tag.len..];
+
+            if tag.closing {
+                // Close the matching open tag and everything that was left open inside of it
+                if let Some(index) = open.iter().rposition(|name| *name == tag.name) {
+                    for name in open.drain(index..).rev() {
+                        push_closing_tag(&mut out, &name);
+                    }
+                }
+                continue;
+            }
+
+            if DROPPED_ELEMENTS.contains(&tag.name.as_str()) {
+                rest = skip_element(rest, &tag.name);
+                continue;
+            }
+
+            if !self.tags.contains(&tag.name) {
+                continue;
+            }
+
+            out.push('<');
+            out.push_str(&tag.name);
+            for (name, value) in &tag.attributes {
+                if !self.attributes.contains(name) {
+                    continue;
+                }
+                match value {
+                    Some(value) => {
+                        if URL_ATTRIBUTES.contains(&name.as_str()) && !self.allows_url(value) {
+                            continue;
+                        }
+                        out.push(' ');
+                        out.push_str(name);
+                        out.push_str("=\"");
+                        push_attribute_value(&mut out, value);
+                        out.push('"');
+                    }
+                    None => {
+                        out.push(' ');
+                        out.push_str(name);
+                    }
+                }
+            }
+            out.push('>');
+
+            if !VOID_ELEMENTS.contains(&tag.name.as_str()) {
+                if tag.self_closing {
+                    push_closing_tag(&mut out, &tag.name);
+                } else {
+                    open.push(tag.name);
+                }
+            }
+        }
+
+        push_text(&mut out, rest);
+        for name in open.into_iter().rev() {
+            push_closing_tag(&mut out, &name);
+        }
+
+        out
+    }
+
+    fn allows_url(&self, url: &str) -> bool {
+        // Browsers decode character references and ignore whitespace in urls, so `jav&#x61;script:` is still a
+        // javascript url
+        let url: String = decode_numeric_references(url)
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match url.find([':', '/', '?', '#', '&']) {
+            Some(index) => match &url[index..index + 1] {
+                ":" => self.url_schemes.contains(&url[..index]),
+                // Named character references like `&colon;` could hide the scheme
+                "&" => false,
+                // Relative urls
+                _ => true,
+            },
+            None => true,
+        }
+    }
+}
+
+impl HtmlSanitizer for SanitizePolicy {
+    fn sanitize(&self, html: &str) -> String {
+        SanitizePolicy::sanitize(self, html)
+    }
+}
+
+/// An opening or closing tag in the html
+struct Tag {
+    name: String,
+    closing: bool,
+    self_closing: bool,
+    attributes: Vec<(String, Option<String>)>,
+    /// The length of the tag in the html
+    len: usize,
+}
+
+impl Tag {
+    /// Parse the tag at the start of the html. Returns `None` if the html doesn't start with a complete tag.
+    fn parse(html: &str) -> Option<Self> {
+        let bytes = html.as_bytes();
+        let is_space = |byte: u8| matches!(byte, b' ' | b'\t' | b'\n' | b'\r' | b'\x0c');
+        let closing = bytes.get(1) == Some(&b'/');
+        let mut i = if closing { 2 } else { 1 };
+
+        let name_start = i;
+        if !bytes.get(i)?.is_ascii_alphabetic() {
+            return None;
+        }
+        while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'-') {
+            i += 1;
+        }
+        let name = html[name_start..i].to_ascii_lowercase();
+
+        let mut attributes = Vec::new();
+        let mut self_closing = false;
+        loop {
+            while i < bytes.len() && is_space(bytes[i]) {
+                i += 1;
+            }
+            match bytes.get(i)? {
+                b'>' => {
+                    i += 1;
+                    break;
+                }
+                b'/' => {
+                    i += 1;
+                    if bytes.get(i) == Some(&b'>') {
+                        self_closing = true;
+                        i += 1;
+                        break;
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            let attribute_start = i;
+            while i < bytes.len() && !is_space(bytes[i]) && !matches!(bytes[i], b'=' | b'>' | b'/')
+            {
+                i += 1;
+            }
+            if attribute_start == i {
+                // A stray `=`
+                i += 1;
+                continue;
+            }
+            let attribute = html[attribute_start..i].to_ascii_lowercase();
+
+            while i < bytes.len() && is_space(bytes[i]) {
+                i += 1;
+            }
+            let mut value = None;
+            if bytes.get(i) == Some(&b'=') {
+                i += 1;
+                while i < bytes.len() && is_space(bytes[i]) {
+                    i += 1;
+                }
+                match bytes.get(i)? {
+                    quote @ (b'"' | b'\'') => {
+                        let quote = *quote;
+                        let value_start = i + 1;
+                        let value_len = bytes[value_start..].iter().position(|b| *b == quote)?;
+                        value = Some(html[value_start..value_start + value_len].to_string());
+                        i = value_start + value_len + 1;
+                    }
+                    _ => {
+                        let value_start = i;
+                        while i < bytes.len() && !is_space(bytes[i]) && bytes[i] != b'>' {
+                            i += 1;
+                        }
+                        value = Some(html[value_start..i].to_string());
+                    }
+                }
+            }
+            attributes.push((attribute, value));
+        }
+
+        Some(Self {
+            name,
+            closing,
+            self_closing,
+            attributes,
+            len: i,
+        })
+    }
+}
+
+/// Skip everything up to and including the closing tag of the element
+fn skip_element<'a>(html: &'a str, name: &str) -> &'a str {
+    // Lowercasing ascii keeps the byte offsets the same
+    let lowercase = html.to_ascii_lowercase();
+    let closing = format!("</{name}");
+    let mut offset = 0;
+    while let Some(index) = lowercase[offset..].find(&closing) {
+        let end = offset + index + closing.len();
+        match lowercase.as_bytes().get(end) {
+            Some(byte) if byte.is_ascii_alphanumeric() || *byte == b'-' => offset = end,
+            _ => {
+                return html[end..]
+                    .find('>')
+                    .map_or("", |close| &html[end + close + 1..])
+            }
+        }
+    }
+    ""
+}
+
+fn push_text(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn push_attribute_value(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn push_closing_tag(out: &mut String, name: &str) {
+    out.push_str("</");
+    out.push_str(name);
+    out.push('>');
+}
+
+/// Decode `&#106;` and `&#x6A;` character references. The semicolon is optional like it is in browsers.
+fn decode_numeric_references(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("&#") {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+        let (radix, digits) = match rest.strip_prefix(['x', 'X']) {
+            Some(hex) => (16, hex),
+            None => (10, rest),
+        };
+        let len = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        match u32::from_str_radix(&digits[..len], radix)
+            .ok()
+            .and_then(char::from_u32)
+        {
+            Some(c) => {
+                out.push(c);
+                rest = &digits[len..];
+                rest = rest.strip_prefix(';').unwrap_or(rest);
+            }
+            None => out.push_str("&#"),
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Props for the [`RawHtml`] component
+#[derive(Clone, Props, PartialEq)]
+pub struct RawHtmlProps {
+    /// The html to render
+    #[props(into)]
+    pub html: String,
+
+    /// The sanitizer the html is cleaned up with. Defaults to the [`Sanitizer`] provided as context or the default
+    /// [`SanitizePolicy`].
+    pub sanitizer: Option<Sanitizer>,
+
+    /// Extra attributes to set on the container element
+    #[props(extends = GlobalAttributes)]
+    pub attributes: Vec<Attribute>,
+}
+
+/// Render an html string inside of a `div` after cleaning it up with a sanitizer.
+///
+/// The html is sanitized the same way on the server and the client, so the server rendered html hydrates without
+/// mismatches. Every renderer sets the sanitized html as the inner html of the container.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[component]
+/// fn Article(body: String) -> Element {
+///     rsx! {
+///         // Scripts, event handlers and javascript urls in the body are removed
+///         RawHtml { class: "article", html: body }
+///         // Allow embedded videos in the comments
+///         RawHtml {
+///             html: "<video src=\"/intro.mp4\" controls></video>",
+///             sanitizer: Sanitizer::new(
+///                 SanitizePolicy::new().allow_tags(["video"]).allow_attributes(["controls"]),
+///             ),
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn RawHtml(props: RawHtmlProps) -> Element {
+    let fallback = use_hook(|| try_consume_context::<Sanitizer>().unwrap_or_default());
+    let sanitizer = props.sanitizer.as_ref().unwrap_or(&fallback);
+    let html = sanitizer.sanitize(&props.html);
+
+    static TEMPLATE: Template = Template {
+        roots: &[TemplateNode::Element {
+            tag: "div",
+            namespace: None,
+            attrs: &[
+                TemplateAttribute::Dynamic { id: 0usize },
+                TemplateAttribute::Dynamic { id: 1usize },
+            ],
+            children: &[],
+        }],
+        node_paths: &[],
+        attr_paths: &[&[0u8], &[0u8]],
+    };
+    Ok(VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([]),
+        Box::new([
+            Box::new([Attribute::new("dangerous_inner_html", html, None, false)]),
+            props.attributes.into_boxed_slice(),
+        ]),
+    ))
+}
+
+#[test]
+fn scripts_and_event_handlers_are_removed() {
+    let policy = SanitizePolicy::new();
+    assert_eq!(
+        policy.sanitize(r#"<p onclick="steal()">Hi<script>alert("</p>")</script> there</p>"#),
+        "<p>Hi there</p>"
+    );
+    assert_eq!(
+        policy.sanitize("<STYLE>p { color: red }</style ><b>bold</b>"),
+        "<b>bold</b>"
+    );
+}
+
+#[test]
+fn unsafe_urls_are_removed() {
+    let policy = SanitizePolicy::new();
+    assert_eq!(
+        policy.sanitize(
+            r#"<a href="javascript:alert(1)">a</a><a href=" JaVa&#x73;cript:x">b</a><a href="java&colon;x">c</a><a href='/docs?a=1&b="2"'>d</a>"#
+        ),
+        r#"<a>a</a><a>b</a><a>c</a><a href="/docs?a=1&b=&quot;2&quot;">d</a>"#
+    );
+    assert_eq!(
+        policy
+            .allow_url_schemes(["tel"])
+            .sanitize(r#"<a href="tel:123">call</a>"#),
+        r#"<a href="tel:123">call</a>"#
+    );
+}
+
+#[test]
+fn tags_are_balanced() {
+    let policy = SanitizePolicy::new();
+    assert_eq!(
+        policy.sanitize("<div><custom-card>text</custom-card><b>bold</div></i> 1 < 2<br/>"),
+        "<div>text<b>bold</b></div> 1 &lt; 2<br>"
+    );
+    assert_eq!(
+        policy
+            .allow_tags(["custom-card"])
+            .sanitize("<custom-card/>"),
+        "<custom-card></custom-card>"
+    );
+}
@@ -132,12 +132,8 @@ fn deserialize_raw(name: &str, data: &serde_json::Value) -> Result<EventData, se
         // Mounted
         "mounted" => Mounted,
 
-        // OtherData => "abort" | "afterprint" | "beforeprint" | "beforeunload" | "hashchange" | "languagechange" | "message" | "offline" | "online" | "pagehide" | "pageshow" | "popstate" | "rejectionhandled" | "storage" | "unhandledrejection" | "unload" | "userproximity" | "vrdisplayactivate" | "vrdisplayblur" | "vrdisplayconnect" | "vrdisplaydeactivate" | "vrdisplaydisconnect" | "vrdisplayfocus" | "vrdisplaypointerrestricted" | "vrdisplaypointerunrestricted" | "vrdisplaypresentchange";
-        other => {
-            return Err(serde::de::Error::custom(format!(
-                "Unknown event type: {other}"
-            )))
-        }
+        // Any other event, like the custom events of web components, only carries the detail of the event
+        _ => Custom(de(data)?),
     };
 
     Ok(data)
@@ -173,6 +169,7 @@ pub enum EventData {
     Transition(SerializedTransitionData),
     Toggle(SerializedToggleData),
     Image(SerializedImageData),
+    Custom(SerializedCustomEventData),
     Mounted,
 }
 
@@ -232,6 +229,9 @@ impl EventData {
             EventData::Image(data) => {
                 Rc::new(PlatformEventData::new(Box::new(data))) as Rc<dyn Any>
             }
+            EventData::Custom(data) => {
+                Rc::new(PlatformEventData::new(Box::new(data))) as Rc<dyn Any>
+            }
             EventData::Mounted => {
                 Rc::new(PlatformEventData::new(Box::new(MountedData::new(())))) as Rc<dyn Any>
             }
@@ -279,6 +279,36 @@ fn test_back_and_forth() {
     assert_eq!(data, p);
 }
 
+#[test]
+fn unknown_events_are_custom_events() {
+    let o = r#"
+{
+  "element": 1,
+  "name": "item-selected",
+  "bubbles": false,
+  "data": {
+    "detail": { "id": 3 }
+  }
+}
+    "#;
+
+    let p: HtmlEvent = serde_json::from_str(o).unwrap();
+    let EventData::Custom(data) = p.data else {
+        panic!("expected a custom event, got {:?}", p.data);
+    };
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Item {
+        id: u32,
+    }
+    let data = CustomEventData::new(data);
+    assert_eq!(data.detail::<Item>().unwrap(), Item { id: 3 });
+    assert!(CustomEventData::new(SerializedCustomEventData::default())
+        .detail::<Option<Item>>()
+        .unwrap()
+        .is_none());
+}
+
 /// A trait for converting from a serialized event to a concrete event type.
 pub struct SerializedHtmlEventConverter;
 
@@ -307,6 +337,14 @@ impl HtmlEventConverter for SerializedHtmlEventConverter {
             .into()
     }
 
+    fn convert_custom_data(&self, event: &PlatformEventData) -> CustomEventData {
+        event
+            .downcast::<SerializedCustomEventData>()
+            .cloned()
+            .unwrap()
+            .into()
+    }
+
     fn convert_drag_data(&self, event: &PlatformEventData) -> DragData {
         event
             .downcast::<SerializedDragData>()
@@ -0,0 +1,176 @@
+use crate::{DateLength, TimeLength};
+use fixed_decimal::FixedDecimal;
+use icu_calendar::{Date, DateTime, Iso};
+use icu_datetime::{options::length, DateFormatter, DateTimeFormatter};
+use icu_decimal::{options::FixedDecimalFormatterOptions, FixedDecimalFormatter};
+use icu_experimental::dimension::provider::currency::{
+    CurrencyEssentialsV1Marker, PatternSelection, PlaceholderValue,
+};
+use icu_experimental::relativetime::{
+    options::Numeric, RelativeTimeFormatter, RelativeTimeFormatterOptions,
+};
+use icu_locid::Locale;
+use icu_provider::{DataLocale, DataPayload, DataProvider, DataRequest};
+use tinystr::TinyAsciiStr;
+use writeable::Writeable;
+
+/// The message for errors ICU4X can't return for its compiled data, which falls back to the root locale for any
+/// locale it doesn't have data for
+const COMPILED_DATA: &str = "the compiled ICU4X data has a fallback for every locale";
+
+/// Formats dates, numbers, currencies and relative times for a locale.
+///
+/// The formatting data is compiled into the app instead of coming from the `Intl` APIs of the browser or the operating
+/// system, so the server and every client format the same value to the same string.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Formatter {
+    locale: Locale,
+}
+
+impl Formatter {
+    /// Create a formatter for a locale
+    pub fn new(locale: Locale) -> Self {
+        Self { locale }
+    }
+
+    /// Get the locale the formatter formats for
+    pub fn locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    fn data_locale(&self) -> DataLocale {
+        (&self.locale).into()
+    }
+
+    /// Format a number with the digits and grouping of the locale, like `12,345.6` in `en` or `12.345,6` in `de`.
+    ///
+    /// Floats can be turned into a [`FixedDecimal`] with [`FixedDecimal::try_from_f64`].
+    pub fn number(&self, value: impl Into<FixedDecimal>) -> String {
+        self.decimal_formatter().format_to_string(&value.into())
+    }
+
+    /// Format an amount of a currency with the currency symbol of the locale, like `€1,999.00` in `en` or `1.999,00 €`
+    /// in `de`. The amount keeps the fraction digits it has, so pad it to the digits of the currency with
+    /// [`FixedDecimal::padded_end`].
+    ///
+    /// # Panics
+    ///
+    /// This panics if the currency is not a three letter ISO 4217 code
+    pub fn currency(&self, value: impl Into<FixedDecimal>, currency: &str) -> String {
+        let code = TinyAsciiStr::<3>::from_str(currency)
+            .ok()
+            .filter(|code| code.is_ascii_alphabetic())
+            .unwrap_or_else(|| panic!("{currency:?} is not an ISO 4217 currency code"))
+            .to_ascii_uppercase();
+
+        let essentials: DataPayload<CurrencyEssentialsV1Marker> = icu_experimental::provider::Baked
+            .load(DataRequest {
+                locale: &self.data_locale(),
+                metadata: Default::default(),
+            })
+            .and_then(|response| response.take_payload())
+            .expect(COMPILED_DATA);
+        let essentials = essentials.get();
+
+        let config = essentials
+            .pattern_config_map
+            .get_copied(&code.to_unvalidated())
+            .unwrap_or(essentials.default_pattern_config);
+        let symbol = match config.short_placeholder_value {
+            Some(PlaceholderValue::Index(index)) => essentials
+                .placeholders
+                .get(index.into())
+                .unwrap_or(code.as_str()),
+            Some(PlaceholderValue::ISO) | None => code.as_str(),
+        };
+        let pattern = match config.short_pattern_selection {
+            PatternSelection::Standard => essentials.standard_pattern.as_ref(),
+            PatternSelection::StandardAlphaNextToNumber => {
+                essentials.standard_alpha_next_to_number_pattern.as_ref()
+            }
+        }
+        .expect(COMPILED_DATA);
+
+        // The currency data only has the pattern, so the amount is formatted like any other number of the locale
+        let decimal_formatter = self.decimal_formatter();
+        let value = value.into();
+        let formatted = pattern
+            .interpolate((decimal_formatter.format(&value), symbol))
+            .write_to_string()
+            .into_owned();
+        formatted
+    }
+
+    /// Format a date with the calendar of the locale, like `Jan 21, 2020` in `en` with [`DateLength::Medium`]
+    pub fn date(&self, date: &Date<Iso>, length: DateLength) -> String {
+        DateFormatter::try_new_with_length(&self.data_locale(), length)
+            .expect(COMPILED_DATA)
+            .format_to_string(&date.to_any())
+            .expect("the formatter converts dates to the calendar of the locale")
+    }
+
+    /// Format a date and a time with the calendar of the locale, like `Jan 21, 2020, 8:25 AM` in `en` with
+    /// [`DateLength::Medium`] and [`TimeLength::Short`]
+    pub fn date_time(
+        &self,
+        date_time: &DateTime<Iso>,
+        date_length: DateLength,
+        time_length: TimeLength,
+    ) -> String {
+        let options = length::Bag::from_date_time_style(date_length, time_length);
+        DateTimeFormatter::try_new(&self.data_locale(), options.into())
+            .expect(COMPILED_DATA)
+            .format_to_string(&date_time.to_any())
+            .expect("the formatter converts dates to the calendar of the locale")
+    }
+
+    /// Format an amount of time relative to now, like `in 3 days` or `2 hours ago` in `en`. Negative values are in
+    /// the past. Some values have a special name, like `yesterday` for `-1` [`TimeUnit::Day`].
+    pub fn relative_time(&self, value: i64, unit: TimeUnit) -> String {
+        let locale = &self.data_locale();
+        let options = RelativeTimeFormatterOptions {
+            numeric: Numeric::Auto,
+        };
+        let formatter = match unit {
+            TimeUnit::Second => RelativeTimeFormatter::try_new_long_second(locale, options),
+            TimeUnit::Minute => RelativeTimeFormatter::try_new_long_minute(locale, options),
+            TimeUnit::Hour => RelativeTimeFormatter::try_new_long_hour(locale, options),
+            TimeUnit::Day => RelativeTimeFormatter::try_new_long_day(locale, options),
+            TimeUnit::Week => RelativeTimeFormatter::try_new_long_week(locale, options),
+            TimeUnit::Month => RelativeTimeFormatter::try_new_long_month(locale, options),
+            TimeUnit::Quarter => RelativeTimeFormatter::try_new_long_quarter(locale, options),
+            TimeUnit::Year => RelativeTimeFormatter::try_new_long_year(locale, options),
+        }
+        .expect(COMPILED_DATA);
+        formatter
+            .format(FixedDecimal::from(value))
+            .write_to_string()
+            .into_owned()
+    }
+
+    fn decimal_formatter(&self) -> FixedDecimalFormatter {
+        FixedDecimalFormatter::try_new(&self.data_locale(), FixedDecimalFormatterOptions::default())
+            .expect(COMPILED_DATA)
+    }
+}
+
+/// The unit of a relative time passed to [`Formatter::relative_time`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimeUnit {
+    /// Seconds
+    Second,
+    /// Minutes
+    Minute,
+    /// Hours
+    Hour,
+    /// Days
+    Day,
+    /// Weeks
+    Week,
+    /// Months
+    Month,
+    /// Quarters of a year
+    Quarter,
+    /// Years
+    Year,
+}
@@ -0,0 +1,68 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+use dioxus_lib::prelude::*;
+
+mod format;
+pub use format::*;
+
+pub use fixed_decimal::{FixedDecimal, FloatPrecision};
+pub use icu_calendar::{Date, DateTime, Iso};
+pub use icu_datetime::options::length::{Date as DateLength, Time as TimeLength};
+pub use icu_locid::{locale, Locale};
+
+/// The locale every component below [`use_init_locale`] formats with
+#[derive(Clone, Copy)]
+struct LocaleContext(Signal<Locale>);
+
+/// Provide the locale of the app to this component and every component below it. Returns a signal you can write to
+/// change the locale at runtime, which rerenders every component that formats with it.
+///
+/// The server and the client must start with the same locale to hydrate without mismatches. Pick it from something
+/// both of them see, like the route or a cookie, instead of the `Accept-Language` header on the server.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_i18n::*;
+///
+/// fn App() -> Element {
+///     let mut locale = use_init_locale(|| locale!("en-US"));
+///
+///     rsx! {
+///         button { onclick: move |_| locale.set(locale!("de-DE")), "Deutsch" }
+///         Price {}
+///     }
+/// }
+///
+/// fn Price() -> Element {
+///     let format = use_formatter();
+///     rsx! { "{format.currency(1999, \"EUR\")}" }
+/// }
+/// ```
+pub fn use_init_locale(init: impl FnOnce() -> Locale) -> Signal<Locale> {
+    use_context_provider(|| LocaleContext(Signal::new(init()))).0
+}
+
+/// Get the locale provided by the closest [`use_init_locale`]. Reading the signal subscribes the component to changes
+/// of the locale.
+///
+/// If no component provided a locale, this provides `en` in the root context.
+pub fn use_locale() -> Signal<Locale> {
+    use_hook(|| match try_consume_context::<LocaleContext>() {
+        Some(LocaleContext(locale)) => locale,
+        None => {
+            provide_root_context(LocaleContext(Signal::new_in_scope(
+                locale!("en"),
+                ScopeId::ROOT,
+            )))
+            .0
+        }
+    })
+}
+
+/// Get a [`Formatter`] for the locale from [`use_locale`]. The component rerenders with a new formatter when the
+/// locale changes.
+pub fn use_formatter() -> Formatter {
+    let locale = use_locale();
+    Formatter::new(locale())
+}
@@ -0,0 +1,110 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_i18n::*;
+use dioxus_ssr::testing::ComponentHarness;
+
+fn cents(cents: i64) -> FixedDecimal {
+    FixedDecimal::from(cents).multiplied_pow10(-2)
+}
+
+#[test]
+fn numbers_are_grouped_for_the_locale() {
+    let en = Formatter::new(locale!("en-US"));
+    let de = Formatter::new(locale!("de-DE"));
+
+    assert_eq!(en.number(1234567), "1,234,567");
+    assert_eq!(de.number(1234567), "1.234.567");
+    assert_eq!(en.number(cents(-123450)), "-1,234.50");
+    assert_eq!(de.number(cents(-123450)), "-1.234,50");
+
+    let float = FixedDecimal::try_from_f64(0.25, FloatPrecision::Floating).unwrap();
+    assert_eq!(de.number(float), "0,25");
+}
+
+#[test]
+fn currencies_use_the_symbol_and_digits_of_the_locale() {
+    let en = Formatter::new(locale!("en-US"));
+    let de = Formatter::new(locale!("de-DE"));
+
+    assert_eq!(en.currency(cents(199900), "EUR"), "€1,999.00");
+    assert_eq!(de.currency(cents(199900), "EUR"), "1.999,00\u{a0}€");
+    assert_eq!(en.currency(cents(500), "usd"), "$5.00");
+    assert_eq!(de.currency(5, "JPY"), "5\u{a0}¥");
+}
+
+#[test]
+#[should_panic = "is not an ISO 4217 currency code"]
+fn currencies_must_be_iso_codes() {
+    Formatter::new(locale!("en")).currency(5, "€");
+}
+
+#[test]
+fn dates_use_the_calendar_of_the_locale() {
+    let date = Date::try_new_iso_date(2024, 3, 14).unwrap();
+    let date_time = DateTime::try_new_iso_datetime(2024, 3, 14, 15, 9, 26).unwrap();
+
+    let en = Formatter::new(locale!("en-US"));
+    assert_eq!(en.date(&date, DateLength::Medium), "Mar 14, 2024");
+    assert_eq!(
+        en.date_time(&date_time, DateLength::Short, TimeLength::Short),
+        "3/14/24, 3:09\u{202f}PM"
+    );
+
+    let de = Formatter::new(locale!("de-DE"));
+    assert_eq!(de.date(&date, DateLength::Medium), "14.03.2024");
+    assert_eq!(
+        de.date_time(&date_time, DateLength::Short, TimeLength::Short),
+        "14.03.24, 15:09"
+    );
+
+    // Thai dates are in the Buddhist calendar
+    let th = Formatter::new(locale!("th"));
+    assert!(th.date(&date, DateLength::Medium).ends_with("2567"));
+}
+
+#[test]
+fn relative_times_name_the_unit_and_direction() {
+    let en = Formatter::new(locale!("en"));
+    assert_eq!(en.relative_time(3, TimeUnit::Hour), "in 3 hours");
+    assert_eq!(en.relative_time(-2, TimeUnit::Week), "2 weeks ago");
+    assert_eq!(en.relative_time(-1, TimeUnit::Day), "yesterday");
+    assert_eq!(en.relative_time(0, TimeUnit::Year), "this year");
+
+    let de = Formatter::new(locale!("de"));
+    assert_eq!(de.relative_time(3, TimeUnit::Hour), "in 3 Stunden");
+    assert_eq!(de.relative_time(-1, TimeUnit::Day), "gestern");
+}
+
+#[test]
+fn components_format_with_the_locale_context() {
+    fn App() -> Element {
+        let mut locale = use_init_locale(|| locale!("en-US"));
+        rsx! {
+            button { id: "german", onclick: move |_| locale.set(locale!("de-DE")) }
+            Price {}
+        }
+    }
+
+    fn Price() -> Element {
+        let format = use_formatter();
+        rsx! { "{format.currency(cents(199900), \"EUR\")}" }
+    }
+
+    let mut harness = ComponentHarness::new(App);
+    assert!(harness.render().contains("€1,999.00"));
+
+    harness.find_by_attribute("id", "german").click();
+    assert!(harness.render().contains("1.999,00\u{a0}€"));
+}
+
+#[test]
+fn apps_without_a_locale_format_in_english() {
+    fn App() -> Element {
+        let format = use_formatter();
+        rsx! { "{format.number(1234567)}" }
+    }
+
+    let mut harness = ComponentHarness::new(App);
+    assert_eq!(harness.render(), "1,234,567");
+}
@@ -0,0 +1,263 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+use std::{fmt::Debug, rc::Rc};
+
+use dioxus_lib::document::Document;
+use dioxus_lib::prelude::*;
+
+mod tree;
+pub use tree::*;
+
+/// How often the panel refreshes the component tree while it is open, in milliseconds
+const REFRESH_INTERVAL: u32 = 1000;
+
+/// The id of the element that highlights the selected component
+const HIGHLIGHT_ID: &str = "dioxus-inspector-highlight";
+
+const TOGGLE_STYLE: &str = "position: fixed; right: 12px; bottom: 12px; z-index: 2147483647; padding: 6px 10px; border: none; border-radius: 6px; background: #1f2937; color: white; font: 12px monospace; cursor: pointer;";
+const PANEL_STYLE: &str = "position: fixed; top: 0; right: 0; bottom: 0; width: 360px; z-index: 2147483647; display: flex; flex-direction: column; background: #111827; color: #e5e7eb; font: 12px monospace; box-shadow: -2px 0 8px rgba(0, 0, 0, 0.3);";
+const HEADER_STYLE: &str = "display: flex; justify-content: space-between; align-items: center; padding: 8px; border-bottom: 1px solid #374151;";
+const TREE_STYLE: &str = "flex: 1; overflow: auto; padding: 4px 0;";
+const DETAILS_STYLE: &str = "flex: 1; overflow: auto; padding: 8px; border-top: 1px solid #374151;";
+const BUTTON_STYLE: &str =
+    "border: none; background: transparent; color: inherit; font: inherit; cursor: pointer;";
+
+/// The properties for an [`Inspector`].
+#[derive(Props, Clone, PartialEq)]
+pub struct InspectorProps {
+    /// If the panel is open when the inspector is first rendered. Defaults to false.
+    #[props(default)]
+    pub open: bool,
+}
+
+/// A devtools panel that shows the component tree of the app.
+///
+/// The panel shows every mounted component with the number of times it has rendered. Selecting a component shows its
+/// props and the values it registered with [`use_inspect`] or [`use_inspected_value`], and highlights the elements
+/// it rendered. The inspector and its children are not shown in the tree.
+///
+/// Rendering the inspector enables [`Runtime::enable_inspection`] which adds a small cost to every render, so it
+/// should only be rendered in debug builds.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_inspector::Inspector;
+///
+/// fn App() -> Element {
+///     rsx! {
+///         if cfg!(debug_assertions) {
+///             Inspector {}
+///         }
+///         h1 { "My app" }
+///     }
+/// }
+/// ```
+#[component]
+pub fn Inspector(props: InspectorProps) -> Element {
+    let mut open = use_signal(|| props.open);
+    let mut selected = use_signal(|| None::<ScopeId>);
+    // Incremented to read a new snapshot of the component tree
+    let mut refresh = use_signal(|| 0usize);
+    let document = use_hook(try_consume_context::<Rc<dyn Document>>);
+
+    let me = use_hook(|| {
+        if let Ok(runtime) = Runtime::current() {
+            runtime.enable_inspection();
+        }
+        current_scope_id().ok()
+    });
+
+    // Values and render counts can change without rerendering the inspector, so refresh the panel while it is open
+    use_hook(|| {
+        let Some(document) = document.clone() else {
+            return;
+        };
+        let mut eval = document.eval(format!(
+            "while (true) {{
+                await new Promise(resolve => setTimeout(resolve, {REFRESH_INTERVAL}));
+                dioxus.send(true);
+            }}"
+        ));
+        spawn(async move {
+            while eval.recv::<bool>().await.is_ok() {
+                if *open.peek() {
+                    refresh += 1;
+                }
+            }
+        });
+    });
+
+    refresh.read();
+    let tree = match Runtime::current() {
+        Ok(runtime) if open() => component_tree(runtime.inspect_components(), me),
+        _ => Vec::new(),
+    };
+    let details = selected().and_then(|id| tree.iter().find(|node| node.component.id == id));
+
+    let roots = match (open(), details) {
+        (true, Some(node)) => node.component.roots.iter().map(|id| id.0).collect(),
+        _ => Vec::new(),
+    };
+    use_effect(use_reactive!(|roots| {
+        if let Some(document) = &document {
+            highlight(document, &roots);
+        }
+    }));
+
+    if !open() {
+        return rsx! {
+            button { style: TOGGLE_STYLE, onclick: move |_| open.set(true), "Inspect" }
+        };
+    }
+
+    rsx! {
+        div { style: PANEL_STYLE,
+            div { style: HEADER_STYLE,
+                strong { "Components" }
+                div {
+                    button {
+                        style: BUTTON_STYLE,
+                        title: "Refresh",
+                        onclick: move |_| refresh += 1,
+                        "⟳"
+                    }
+                    button {
+                        style: BUTTON_STYLE,
+                        title: "Close",
+                        onclick: move |_| open.set(false),
+                        "✕"
+                    }
+                }
+            }
+            div { style: TREE_STYLE,
+                for node in tree.iter() {
+                    TreeRow {
+                        key: "{node.component.id:?}",
+                        name: short_name(node.component.name),
+                        depth: node.depth,
+                        render_count: node.component.render_count,
+                        selected: selected() == Some(node.component.id),
+                        onselect: {
+                            let id = node.component.id;
+                            move |_| selected.set((selected() != Some(id)).then_some(id))
+                        },
+                    }
+                }
+            }
+            if let Some(node) = details {
+                div { style: DETAILS_STYLE,
+                    strong { "{short_name(node.component.name)}" }
+                    p { "Rendered {node.component.render_count} times" }
+                    ValueTable { title: "Props", values: node.component.props.clone() }
+                    ValueTable { title: "Values", values: node.component.values.clone() }
+                }
+            }
+        }
+    }
+}
+
+/// Show a readable value like a signal or memo of the current component in the [`Inspector`].
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_inspector::use_inspect;
+///
+/// fn Counter() -> Element {
+///     let mut count = use_signal(|| 0);
+///     use_inspect("count", count);
+///
+///     rsx! { button { onclick: move |_| count += 1, "{count}" } }
+/// }
+/// ```
+pub fn use_inspect<R>(name: &str, value: R)
+where
+    R: Readable + 'static,
+    R::Target: Debug,
+{
+    use_inspected_value(name, move || format!("{:?}", &*value.peek()));
+}
+
+#[component]
+fn TreeRow(
+    name: String,
+    depth: usize,
+    render_count: usize,
+    selected: bool,
+    onselect: EventHandler,
+) -> Element {
+    let background = if selected { "#374151" } else { "transparent" };
+    let indent = 8 + depth * 12;
+    rsx! {
+        div {
+            style: "display: flex; justify-content: space-between; padding: 2px 8px 2px {indent}px; cursor: pointer; background: {background};",
+            onclick: move |_| onselect(()),
+            span { "{name}" }
+            span { style: "color: #9ca3af;", title: "Renders", "{render_count}" }
+        }
+    }
+}
+
+#[component]
+fn ValueTable(title: String, values: Vec<InspectedValue>) -> Element {
+    if values.is_empty() {
+        return VNode::empty();
+    }
+
+    rsx! {
+        p { style: "margin: 8px 0 4px; color: #9ca3af;", "{title}" }
+        for value in values {
+            div { style: "padding: 2px 0; overflow-wrap: anywhere;",
+                span { style: "color: #93c5fd;", "{value.name}" }
+                ": {value.value}"
+            }
+        }
+    }
+}
+
+/// Remove the module path from the name of a component function
+fn short_name(name: &str) -> String {
+    let base = name.split('<').next().unwrap_or(name);
+    let short = base.rsplit("::").next().unwrap_or(base);
+    format!("{short}{}", &name[base.len()..])
+}
+
+/// Move the highlight over the elements with the given ids, or hide it if none of them are in the document
+fn highlight(document: &Rc<dyn Document>, roots: &[usize]) {
+    _ = document.eval(format!(
+        r#"let highlight = document.getElementById("{HIGHLIGHT_ID}");
+        if (!highlight) {{
+            highlight = document.createElement("div");
+            highlight.id = "{HIGHLIGHT_ID}";
+            highlight.style.cssText = "position: fixed; pointer-events: none; z-index: 2147483646; background: rgba(59, 130, 246, 0.25); outline: 1px solid rgb(59, 130, 246);";
+            document.body.appendChild(highlight);
+        }}
+        const rects = {roots:?}
+            .map(id => window.interpreter && window.interpreter.getNode(id))
+            .filter(node => node && node.isConnected)
+            .map(node => {{
+                if (node.getBoundingClientRect) return node.getBoundingClientRect();
+                const range = document.createRange();
+                range.selectNode(node);
+                return range.getBoundingClientRect();
+            }})
+            .filter(rect => rect.width || rect.height);
+        if (!rects.length) {{
+            highlight.style.display = "none";
+            return;
+        }}
+        const left = Math.min(...rects.map(rect => rect.left));
+        const top = Math.min(...rects.map(rect => rect.top));
+        const right = Math.max(...rects.map(rect => rect.right));
+        const bottom = Math.max(...rects.map(rect => rect.bottom));
+        Object.assign(highlight.style, {{
+            display: "block",
+            left: left + "px",
+            top: top + "px",
+            width: right - left + "px",
+            height: bottom - top + "px",
+        }});"#
+    ));
+}
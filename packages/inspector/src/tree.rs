@@ -0,0 +1,47 @@
+use dioxus_lib::prelude::*;
+use std::collections::HashMap;
+
+/// A component in the tree shown by the [`Inspector`](crate::Inspector)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNode {
+    /// The snapshot of the component
+    pub component: InspectedComponent,
+    /// The number of ancestors of the component in the tree
+    pub depth: usize,
+}
+
+/// Order components depth first so each component comes directly after its parent, and children keep the order they
+/// were created in. The `hidden` component and all of its descendants are left out of the tree.
+pub fn component_tree(
+    components: Vec<InspectedComponent>,
+    hidden: Option<ScopeId>,
+) -> Vec<TreeNode> {
+    let ids = components.iter().map(|c| c.id).collect::<Vec<_>>();
+    let mut children: HashMap<Option<ScopeId>, Vec<InspectedComponent>> = HashMap::new();
+    for component in components {
+        // Components whose parent is missing from the snapshot are shown at the top level
+        let parent = component.parent.filter(|parent| ids.contains(parent));
+        children.entry(parent).or_default().push(component);
+    }
+
+    fn visit(
+        parent: Option<ScopeId>,
+        depth: usize,
+        hidden: Option<ScopeId>,
+        children: &mut HashMap<Option<ScopeId>, Vec<InspectedComponent>>,
+        tree: &mut Vec<TreeNode>,
+    ) {
+        for component in children.remove(&parent).unwrap_or_default() {
+            if Some(component.id) == hidden {
+                continue;
+            }
+            let id = component.id;
+            tree.push(TreeNode { component, depth });
+            visit(Some(id), depth + 1, hidden, children, tree);
+        }
+    }
+
+    let mut tree = Vec::new();
+    visit(None, 0, hidden, &mut children, &mut tree);
+    tree
+}
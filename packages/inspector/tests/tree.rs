@@ -0,0 +1,55 @@
+use dioxus::prelude::*;
+use dioxus_inspector::component_tree;
+
+fn component(id: usize, parent: Option<usize>) -> InspectedComponent {
+    InspectedComponent {
+        id: ScopeId(id),
+        name: "app::Component",
+        parent: parent.map(ScopeId),
+        height: 0,
+        render_count: 1,
+        props: Vec::new(),
+        values: Vec::new(),
+        roots: Vec::new(),
+    }
+}
+
+fn tree(components: Vec<InspectedComponent>, hidden: Option<usize>) -> Vec<(usize, usize)> {
+    component_tree(components, hidden.map(ScopeId))
+        .into_iter()
+        .map(|node| (node.component.id.0, node.depth))
+        .collect()
+}
+
+#[test]
+fn depth_first_order() {
+    let components = vec![
+        component(0, None),
+        component(1, Some(0)),
+        component(2, Some(1)),
+        component(3, Some(0)),
+        component(4, Some(2)),
+        component(5, Some(1)),
+    ];
+    assert_eq!(
+        tree(components, None),
+        [(0, 0), (1, 1), (2, 2), (4, 3), (5, 2), (3, 1)]
+    );
+}
+
+#[test]
+fn hidden_subtree() {
+    let components = vec![
+        component(0, None),
+        component(1, Some(0)),
+        component(2, Some(1)),
+        component(3, Some(0)),
+    ];
+    assert_eq!(tree(components, Some(1)), [(0, 0), (3, 1)]);
+}
+
+#[test]
+fn missing_parents_are_shown_at_the_top_level() {
+    let components = vec![component(2, Some(1)), component(3, Some(2))];
+    assert_eq!(tree(components, None), [(2, 0), (3, 1)]);
+}
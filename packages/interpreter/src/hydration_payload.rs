@@ -0,0 +1,233 @@
+//! The format of the hydration data fullstack streams to the client.
+//!
+//! The data for the initial page and for each suspense boundary that resolves later is sent in its own payload. A
+//! payload starts with a header and is followed by the serialized data, which is compressed with the
+//! [`HydrationCompression`] the server picked if that makes it smaller:
+//!
+//! ```text
+//! [version: u8] [flags: u8] [checksum: u32 LE] [length: u32 LE] [data]
+//! ```
+//!
+//! The flags hold the compression method of the data. The checksum is the crc32 of the uncompressed data and the length is the length of the uncompressed data. The client
+//! rejects payloads that fail the checksum so a truncated or corrupted chunk never hydrates the page with the wrong data.
+
+/// The version of the payload format
+const VERSION: u8 = 1;
+
+/// The compression methods stored in the flags
+const UNCOMPRESSED: u8 = 0;
+const DEFLATE: u8 = 1;
+#[cfg(feature = "brotli")]
+const BROTLI: u8 = 2;
+
+const HEADER_LEN: usize = 10;
+
+/// Data smaller than this is never compressed. Compression rarely saves enough on small payloads to be worth the time it
+/// takes to decompress them on the client.
+const COMPRESSION_THRESHOLD: usize = 512;
+
+/// How the server compresses large hydration payloads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum HydrationCompression {
+    /// Send the data as is
+    None,
+    /// Compress the data with deflate. This is fast to decompress and works with every client.
+    #[default]
+    Deflate,
+    /// Compress the data with brotli. This produces smaller payloads than deflate for large pages, but the client must
+    /// also be built with the `brotli` feature to read them.
+    #[cfg(feature = "brotli")]
+    Brotli,
+}
+
+impl HydrationCompression {
+    fn method(self) -> u8 {
+        match self {
+            Self::None => UNCOMPRESSED,
+            Self::Deflate => DEFLATE,
+            #[cfg(feature = "brotli")]
+            Self::Brotli => BROTLI,
+        }
+    }
+
+    fn compress(self, data: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            Self::None => None,
+            Self::Deflate => Some(miniz_oxide::deflate::compress_to_vec(data, 6)),
+            #[cfg(feature = "brotli")]
+            Self::Brotli => {
+                let mut compressed = Vec::new();
+                let params = brotli::enc::BrotliEncoderParams {
+                    quality: 9,
+                    ..Default::default()
+                };
+                brotli::BrotliCompress(&mut &*data, &mut compressed, &params).ok()?;
+                Some(compressed)
+            }
+        }
+    }
+}
+
+/// Wrap serialized hydration data in a payload with a checksum, compressing it with deflate if it is large enough
+pub fn encode_hydration_payload(data: &[u8]) -> Vec<u8> {
+    encode_hydration_payload_with(data, HydrationCompression::default())
+}
+
+/// Wrap serialized hydration data in a payload with a checksum, compressing it with the given method if it is large enough
+pub fn encode_hydration_payload_with(data: &[u8], compression: HydrationCompression) -> Vec<u8> {
+    let compressed = (data.len() >= COMPRESSION_THRESHOLD)
+        .then(|| compression.compress(data))
+        .flatten()
+        .filter(|compressed| compressed.len() < data.len());
+
+    let body = compressed.as_deref().unwrap_or(data);
+    let mut payload = Vec::with_capacity(HEADER_LEN + body.len());
+    payload.push(VERSION);
+    payload.push(match compressed {
+        Some(_) => compression.method(),
+        None => UNCOMPRESSED,
+    });
+    payload.extend_from_slice(&crc32fast::hash(data).to_le_bytes());
+    payload.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    payload.extend_from_slice(body);
+    payload
+}
+
+/// Get the serialized hydration data out of a payload created with [`encode_hydration_payload`]
+pub fn decode_hydration_payload(payload: &[u8]) -> Result<Vec<u8>, HydrationPayloadError> {
+    if payload.len() < HEADER_LEN {
+        return Err(HydrationPayloadError::Truncated);
+    }
+    let (header, body) = payload.split_at(HEADER_LEN);
+    if header[0] != VERSION {
+        return Err(HydrationPayloadError::UnsupportedVersion(header[0]));
+    }
+    let checksum = u32::from_le_bytes(header[2..6].try_into().unwrap());
+    let len = u32::from_le_bytes(header[6..10].try_into().unwrap()) as usize;
+
+    let data = match header[1] {
+        UNCOMPRESSED => body.to_vec(),
+        DEFLATE => miniz_oxide::inflate::decompress_to_vec_with_limit(body, len)
+            .map_err(|_| HydrationPayloadError::Decompress)?,
+        #[cfg(feature = "brotli")]
+        BROTLI => {
+            let mut data = Vec::with_capacity(len);
+            brotli::BrotliDecompress(&mut &*body, &mut data)
+                .map_err(|_| HydrationPayloadError::Decompress)?;
+            data
+        }
+        method => return Err(HydrationPayloadError::UnsupportedCompression(method)),
+    };
+    if data.len() != len {
+        return Err(HydrationPayloadError::Truncated);
+    }
+    let found = crc32fast::hash(&data);
+    if found != checksum {
+        return Err(HydrationPayloadError::ChecksumMismatch {
+            expected: checksum,
+            found,
+        });
+    }
+    Ok(data)
+}
+
+/// An error while decoding a hydration payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HydrationPayloadError {
+    /// The payload was created by a newer version of the server
+    UnsupportedVersion(u8),
+    /// The payload is compressed with a method this client was not built with
+    UnsupportedCompression(u8),
+    /// The payload is shorter than its header says
+    Truncated,
+    /// The compressed data is invalid
+    Decompress,
+    /// The data doesn't match the checksum in the header
+    ChecksumMismatch {
+        /// The checksum the server sent
+        expected: u32,
+        /// The checksum of the data the client received
+        found: u32,
+    },
+}
+
+impl std::fmt::Display for HydrationPayloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported hydration payload version {version}")
+            }
+            Self::UnsupportedCompression(method) => {
+                write!(f, "unsupported hydration payload compression method {method}")
+            }
+            Self::Truncated => write!(f, "the hydration payload is truncated"),
+            Self::Decompress => write!(f, "the hydration payload could not be decompressed"),
+            Self::ChecksumMismatch { expected, found } => write!(
+                f,
+                "the hydration payload checksum {found:#010x} doesn't match the expected checksum {expected:#010x}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HydrationPayloadError {}
+
+#[test]
+fn payloads_round_trip() {
+    let small = b"small".to_vec();
+    let large = b"hydration data ".repeat(100);
+
+    for data in [small, large.clone()] {
+        let payload = encode_hydration_payload(&data);
+        assert_eq!(decode_hydration_payload(&payload).unwrap(), data);
+    }
+
+    // Large repetitive payloads are compressed
+    assert!(encode_hydration_payload(&large).len() < large.len() / 4);
+}
+
+#[test]
+fn compression_methods_round_trip() {
+    let data = b"hydration data ".repeat(100);
+
+    let uncompressed = encode_hydration_payload_with(&data, HydrationCompression::None);
+    assert_eq!(uncompressed.len(), HEADER_LEN + data.len());
+    assert_eq!(decode_hydration_payload(&uncompressed).unwrap(), data);
+
+    #[cfg(feature = "brotli")]
+    {
+        let brotli = encode_hydration_payload_with(&data, HydrationCompression::Brotli);
+        assert_eq!(brotli[1], BROTLI);
+        assert_eq!(decode_hydration_payload(&brotli).unwrap(), data);
+    }
+
+    // Clients reject methods they don't know about instead of reading the compressed data as is
+    let mut unknown = encode_hydration_payload(&data);
+    unknown[1] = 7;
+    assert_eq!(
+        decode_hydration_payload(&unknown),
+        Err(HydrationPayloadError::UnsupportedCompression(7))
+    );
+}
+
+#[test]
+fn corrupted_payloads_are_rejected() {
+    let data = b"hydration data ".repeat(100);
+    let mut payload = encode_hydration_payload(&data);
+
+    let truncated = &payload[..payload.len() / 2];
+    assert!(decode_hydration_payload(truncated).is_err());
+
+    // Flip a byte of the uncompressed length so the data no longer matches
+    payload[6] ^= 1;
+    assert!(decode_hydration_payload(&payload).is_err());
+
+    let mut uncompressed = encode_hydration_payload(b"small");
+    *uncompressed.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        decode_hydration_payload(&uncompressed),
+        Err(HydrationPayloadError::ChecksumMismatch { .. })
+    ));
+}
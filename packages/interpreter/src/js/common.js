@@ -1 +1 @@
-function setAttributeInner(node,field,value,ns){if(ns==="style"){node.style.setProperty(field,value);return}if(ns){node.setAttributeNS(ns,field,value);return}switch(field){case"value":if(node.value!==value)node.value=value;break;case"initial_value":node.defaultValue=value;break;case"checked":node.checked=truthy(value);break;case"initial_checked":node.defaultChecked=truthy(value);break;case"selected":node.selected=truthy(value);break;case"initial_selected":node.defaultSelected=truthy(value);break;case"dangerous_inner_html":node.innerHTML=value;break;default:if(!truthy(value)&&isBoolAttr(field))node.removeAttribute(field);else node.setAttribute(field,value)}}var truthy=function(val){return val==="true"||val===!0},isBoolAttr=function(field){switch(field){case"allowfullscreen":case"allowpaymentrequest":case"async":case"autofocus":case"autoplay":case"checked":case"controls":case"default":case"defer":case"disabled":case"formnovalidate":case"hidden":case"ismap":case"itemscope":case"loop":case"multiple":case"muted":case"nomodule":case"novalidate":case"open":case"playsinline":case"readonly":case"required":case"reversed":case"selected":case"truespeed":case"webkitdirectory":return!0;default:return!1}};function retrieveFormValues(form){const formData=new FormData(form),contents={};return formData.forEach((value,key)=>{if(contents[key])contents[key].push(value);else contents[key]=[value]}),{valid:form.checkValidity(),values:contents}}export{setAttributeInner,retrieveFormValues};
+function setAttributeInner(node,field,value,ns){if(ns==="style"){node.style.setProperty(field,value);return}if(ns){node.setAttributeNS(ns,field,value);return}switch(field){case"value":if(node.dioxusValue=value,node.dioxusComposing)node.dioxusComposedValue=value;else if(node.value!==value)node.value=value;break;case"initial_value":node.defaultValue=value;break;case"checked":node.dioxusChecked=truthy(value),node.checked=truthy(value);break;case"initial_checked":node.defaultChecked=truthy(value);break;case"selected":node.selected=truthy(value);break;case"initial_selected":node.defaultSelected=truthy(value);break;case"dangerous_inner_html":node.innerHTML=value;break;default:if(!truthy(value)&&isBoolAttr(field))node.removeAttribute(field);else node.setAttribute(field,value)}}var truthy=function(val){return val==="true"||val===!0},isBoolAttr=function(field){switch(field){case"allowfullscreen":case"allowpaymentrequest":case"async":case"autofocus":case"autoplay":case"checked":case"controls":case"default":case"defer":case"disabled":case"formnovalidate":case"hidden":case"inert":case"ismap":case"itemscope":case"loop":case"multiple":case"muted":case"nomodule":case"novalidate":case"open":case"playsinline":case"readonly":case"required":case"reversed":case"selected":case"truespeed":case"webkitdirectory":return!0;default:return!1}};function retrieveFormValues(form){const formData=new FormData(form),contents={};return formData.forEach((value,key)=>{if(contents[key])contents[key].push(value);else contents[key]=[value]}),{valid:form.checkValidity(),values:contents}}const FOCUSABLE_SELECTOR=["a[href]","area[href]","button:not([disabled])",'input:not([disabled]):not([type="hidden"])',"select:not([disabled])","textarea:not([disabled])","iframe","summary",'[contenteditable]:not([contenteditable="false"])','[tabindex]:not([tabindex="-1"])'].join(",");function focusableElements(container){const elements=container.querySelectorAll(FOCUSABLE_SELECTOR);return Array.from(elements).filter((element)=>element.getClientRects().length>0)}function focusFirst(container){const first=focusableElements(container)[0];if(first){first.focus();return}if(!container.hasAttribute("tabindex"))container.tabIndex=-1;container.focus()}function trapFocus(container){if(!(container instanceof HTMLElement)||container.dioxusFocusTrap)return;const onKeyDown=(event)=>{if(event.key!=="Tab")return;const elements=focusableElements(container);if(elements.length===0){event.preventDefault(),container.focus();return}const first=elements[0],last=elements[elements.length-1],active=document.activeElement;if(event.shiftKey&&(active===first||!container.contains(active)))event.preventDefault(),last.focus();else if(!event.shiftKey&&(active===last||!container.contains(active)))event.preventDefault(),first.focus()},onFocusIn=(event)=>{if(!container.contains(event.target))focusFirst(container)},trap={previous:document.activeElement,onKeyDown,onFocusIn};if(container.dioxusFocusTrap=trap,document.addEventListener("keydown",onKeyDown,!0),document.addEventListener("focusin",onFocusIn,!0),!container.contains(document.activeElement))focusFirst(container)}function releaseFocusTrap(container){const trap=container&&container.dioxusFocusTrap;if(!trap)return;if(document.removeEventListener("keydown",trap.onKeyDown,!0),document.removeEventListener("focusin",trap.onFocusIn,!0),delete container.dioxusFocusTrap,trap.previous instanceof HTMLElement&&trap.previous.isConnected)trap.previous.focus()}export{trapFocus,setAttributeInner,retrieveFormValues,releaseFocusTrap};
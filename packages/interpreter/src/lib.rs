@@ -12,6 +12,12 @@ pub static NATIVE_JS: &str = include_str!("./js/native.js");
 /// The code that handles initializing data used for fullstack data streaming
 pub static INITIALIZE_STREAMING_JS: &str = include_str!("./js/initialize_streaming.js");
 
+#[cfg(feature = "hydration-payload")]
+mod hydration_payload;
+
+#[cfg(feature = "hydration-payload")]
+pub use hydration_payload::*;
+
 #[cfg(all(feature = "binary-protocol", feature = "sledgehammer"))]
 mod write_native_mutations;
 
@@ -39,6 +45,12 @@ pub mod minimal_bindings {
 
         /// Roll up all the values from the node into a JS object that we can deserialize
         pub fn collectFormValues(node: JsValue) -> JsValue;
+
+        /// Keep keyboard focus inside of the node until the trap is released
+        pub fn trapFocus(node: JsValue);
+
+        /// Release the focus trap on the node and restore focus to the element that had it before the trap
+        pub fn releaseFocusTrap(node: JsValue);
     }
 
     #[wasm_bindgen(module = "/src/js/hydrate.js")]
@@ -1,2 +1,3 @@
 export { setAttributeInner } from "./set_attribute";
 export { retrieveFormValues } from "./form";
+export { trapFocus, releaseFocusTrap } from "./focus";
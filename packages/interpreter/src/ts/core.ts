@@ -1,14 +1,19 @@
 // The root interpreter class that holds state about the mapping between DOM and VirtualDom
 // This always lives in the JS side of things, and is extended by the native and web interpreters
 
-import { setAttributeInner } from "./set_attribute";
+import {
+  applyComposedValue,
+  restoreControlledValue,
+  setAttributeInner,
+} from "./set_attribute";
 
 export type NodeId = number;
 
 export class BaseInterpreter {
   // non bubbling events listen at the element the listener was created at
+  // passive listeners are counted separately so the root listener is only passive if every listener of the event is
   global: {
-    [key: string]: { active: number; callback: EventListener };
+    [key: string]: { active: number; passive: number; callback: EventListener };
   };
   // bubbling events can listen at the root element
   local: {
@@ -18,7 +23,19 @@ export class BaseInterpreter {
   };
 
   root: HTMLElement;
+  // portals render outside of the root element, so bubbling events are also listened to at each portal container
+  portals: HTMLElement[];
   handler: EventListener;
+  // events this interpreter already handled. Each interpreter keeps its own set so an app nested
+  // inside another app, like a custom element, doesn't hide composed events from the outer app
+  handledEvents: WeakSet<Event>;
+  // capturing listeners that keep controlled inputs in sync with the value the app set
+  controlledInputListeners: {
+    [key: string]: EventListener;
+  };
+  // revert edits to controlled inputs the app didn't accept on the next frame. This is only safe if the edits from
+  // the event are applied before the next frame
+  restoresControlledInputs: boolean;
   resizeObserver: ResizeObserver;
   intersectionObserver: IntersectionObserver;
 
@@ -37,17 +54,71 @@ export class BaseInterpreter {
     this.global = {};
     this.local = {};
     this.root = root;
+    this.portals = [];
 
     this.nodes = [root];
     this.stack = [root];
     this.templates = {};
 
-    this.handler = handler;
+    // an event can reach both a direct listener and the listener at the root, but the virtual dom only handles it once
+    this.handledEvents = new WeakSet();
+    this.handler =
+      handler &&
+      ((event) => {
+        if (this.handledEvents.has(event)) {
+          return;
+        }
+        this.handledEvents.add(event);
+        handler(event);
+      });
+
+    this.restoresControlledInputs = true;
+    this.controlledInputListeners = this.createControlledInputListeners();
+    this.addControlledInputListeners(root);
 
     // make sure to set the root element's ID so it still registers events
     root.setAttribute("data-dioxus-id", "0");
   }
 
+  createControlledInputListeners(): { [key: string]: EventListener } {
+    const scheduleRestore = (event: Event, name: string) => {
+      // Only the edits the app saw can be rejected. An input that only listens to change keeps what the user
+      // types until the change event
+      if (!this.restoresControlledInputs || !listensTo(event, name)) {
+        return;
+      }
+      const target = event.target;
+      // Wait until the app handled the event and rendered the new value before the next frame is painted
+      requestAnimationFrame(() => restoreControlledValue(target));
+    };
+    return {
+      input: (event: Event) => scheduleRestore(event, "input"),
+      change: (event: Event) => scheduleRestore(event, "change"),
+      compositionstart: (event: Event) => {
+        // @ts-ignore
+        event.target.dioxusComposing = true;
+      },
+      compositionend: (event: Event) => {
+        // @ts-ignore
+        event.target.dioxusComposing = false;
+        applyComposedValue(event.target);
+        scheduleRestore(event, "input");
+      },
+    };
+  }
+
+  addControlledInputListeners(element: HTMLElement) {
+    for (const event_name in this.controlledInputListeners) {
+      element.addEventListener(event_name, this.controlledInputListeners[event_name], true);
+    }
+  }
+
+  removeControlledInputListeners(element: HTMLElement) {
+    for (const event_name in this.controlledInputListeners) {
+      element.removeEventListener(event_name, this.controlledInputListeners[event_name], true);
+    }
+  }
+
   handleResizeEvent(entry: ResizeObserverEntry) {
     const target = entry.target;
 
@@ -106,49 +177,108 @@ export class BaseInterpreter {
     }
   }
 
-  createListener(event_name: string, element: HTMLElement, bubbles: boolean) {
+  createListener(
+    event_name: string,
+    element: HTMLElement,
+    bubbles: boolean,
+    passive: boolean = false,
+    direct: boolean = false
+  ) {
     if (event_name == "resize") {
       this.createResizeObserver(element);
     } else if (event_name == "visible") {
       this.createIntersectionObserver(element);
     }
 
-    if (bubbles) {
-      if (this.global[event_name] === undefined) {
-        this.global[event_name] = { active: 1, callback: this.handler };
-        this.root.addEventListener(event_name, this.handler);
+    // count the listeners on the element so controlled inputs know which events reach the app
+    const listeners = ((element as any).dioxusListeners ||= {});
+    listeners[event_name] = (listeners[event_name] || 0) + 1;
+
+    // direct listeners are attached to the element like non bubbling events so they see events that never reach the root
+    if (bubbles && !direct) {
+      const listener = this.global[event_name];
+      if (listener === undefined) {
+        this.global[event_name] = {
+          active: 1,
+          passive: passive ? 1 : 0,
+          callback: this.handler,
+        };
+        this.addGlobalListener(event_name);
       } else {
-        this.global[event_name].active++;
+        const wasPassive = listener.active === listener.passive;
+        listener.active++;
+        if (passive) {
+          listener.passive++;
+        }
+        // the root listener stops being passive once a listener that can prevent the default action is added
+        if (wasPassive !== (listener.active === listener.passive)) {
+          this.removeGlobalListener(event_name);
+          this.addGlobalListener(event_name);
+        }
       }
     } else {
       const id = element.getAttribute("data-dioxus-id");
       if (!this.local[id]) {
         this.local[id] = {};
       }
-      element.addEventListener(event_name, this.handler);
+      element.addEventListener(event_name, this.handler, { passive });
     }
   }
 
-  removeListener(element: HTMLElement, event_name: string, bubbles: boolean) {
+  removeListener(
+    element: HTMLElement,
+    event_name: string,
+    bubbles: boolean,
+    passive: boolean = false,
+    direct: boolean = false
+  ) {
+    const listeners = (element as any).dioxusListeners;
+    if (listeners && --listeners[event_name] === 0) {
+      delete listeners[event_name];
+    }
+
     if (event_name == "resize") {
       this.removeResizeObserver(element);
     } else if (event_name == "visible") {
       this.removeIntersectionObserver(element);
-    } else if (bubbles) {
-      this.removeBubblingListener(event_name);
+    } else if (bubbles && !direct) {
+      this.removeBubblingListener(event_name, passive);
     } else {
       this.removeNonBubblingListener(element, event_name);
     }
   }
 
-  removeBubblingListener(event_name: string) {
-    this.global[event_name].active--;
-    if (this.global[event_name].active === 0) {
-      this.root.removeEventListener(
-        event_name,
-        this.global[event_name].callback
-      );
+  removeBubblingListener(event_name: string, passive: boolean = false) {
+    const listener = this.global[event_name];
+    const wasPassive = listener.active === listener.passive;
+    listener.active--;
+    if (passive) {
+      listener.passive--;
+    }
+    if (listener.active === 0) {
+      this.removeGlobalListener(event_name);
       delete this.global[event_name];
+    } else if (wasPassive !== (listener.active === listener.passive)) {
+      this.removeGlobalListener(event_name);
+      this.addGlobalListener(event_name);
+    }
+  }
+
+  // listen to a bubbling event at the root and every portal
+  addGlobalListener(event_name: string) {
+    const { active, passive, callback } = this.global[event_name];
+    const options = { passive: active === passive };
+    this.root.addEventListener(event_name, callback, options);
+    for (const portal of this.portals) {
+      portal.addEventListener(event_name, callback, options);
+    }
+  }
+
+  removeGlobalListener(event_name: string) {
+    const callback = this.global[event_name].callback;
+    this.root.removeEventListener(event_name, callback);
+    for (const portal of this.portals) {
+      portal.removeEventListener(event_name, callback);
     }
   }
 
@@ -166,6 +296,35 @@ export class BaseInterpreter {
     delete this.local[id];
   }
 
+  mountPortal(container: HTMLElement, target: HTMLElement) {
+    target.appendChild(container);
+    if (!this.portals.includes(container)) {
+      this.portals.push(container);
+      for (const event_name in this.global) {
+        const { active, passive, callback } = this.global[event_name];
+        container.addEventListener(event_name, callback, {
+          passive: active === passive,
+        });
+      }
+      this.addControlledInputListeners(container);
+    }
+  }
+
+  unmountPortal(container: HTMLElement) {
+    const index = this.portals.indexOf(container);
+    if (index === -1) {
+      return;
+    }
+    this.portals.splice(index, 1);
+    for (const event_name in this.global) {
+      container.removeEventListener(
+        event_name,
+        this.global[event_name].callback
+      );
+    }
+    this.removeControlledInputListeners(container);
+  }
+
   getNode(id: NodeId): Node {
     return this.nodes[id];
   }
@@ -216,8 +375,15 @@ export class BaseInterpreter {
         const listener = split[j];
         const split2 = listener.split(":");
         const event_name = split2[0];
-        const bubbles = split2[1] === "1";
-        this.createListener(event_name, hydrateNode, bubbles);
+        // bit 0 is set if the event bubbles, bit 1 if the listener is passive and bit 2 if the listener is direct
+        const flags = parseInt(split2[1]);
+        this.createListener(
+          event_name,
+          hydrateNode,
+          (flags & 1) === 1,
+          (flags & 2) === 2,
+          (flags & 4) === 4
+        );
       }
     }
   }
@@ -323,3 +489,10 @@ export class BaseInterpreter {
     setAttributeInner(node, field, value, ns);
   }
 }
+
+// Check if the app listens to an event on the target of the event or one of its ancestors
+function listensTo(event: Event, name: string): boolean {
+  return event
+    .composedPath()
+    .some((node: any) => node.dioxusListeners && node.dioxusListeners[name] > 0);
+}
@@ -0,0 +1,98 @@
+// Focus traps keep keyboard focus inside of an element like a dialog, and restore focus when they are released
+
+const FOCUSABLE_SELECTOR = [
+  "a[href]",
+  "area[href]",
+  "button:not([disabled])",
+  "input:not([disabled]):not([type=\"hidden\"])",
+  "select:not([disabled])",
+  "textarea:not([disabled])",
+  "iframe",
+  "summary",
+  "[contenteditable]:not([contenteditable=\"false\"])",
+  "[tabindex]:not([tabindex=\"-1\"])",
+].join(",");
+
+type FocusTrap = {
+  previous: Element | null;
+  onKeyDown: (event: KeyboardEvent) => void;
+  onFocusIn: (event: FocusEvent) => void;
+};
+
+function focusableElements(container: HTMLElement): HTMLElement[] {
+  const elements = container.querySelectorAll<HTMLElement>(FOCUSABLE_SELECTOR);
+  // Skip elements that are not rendered
+  return Array.from(elements).filter((element) => element.getClientRects().length > 0);
+}
+
+function focusFirst(container: HTMLElement) {
+  const first = focusableElements(container)[0];
+  if (first) {
+    first.focus();
+    return;
+  }
+  // If there is nothing to focus inside the container, focus the container itself
+  if (!container.hasAttribute("tabindex")) {
+    container.tabIndex = -1;
+  }
+  container.focus();
+}
+
+export function trapFocus(container: any) {
+  if (!(container instanceof HTMLElement) || container.dioxusFocusTrap) {
+    return;
+  }
+
+  const onKeyDown = (event: KeyboardEvent) => {
+    if (event.key !== "Tab") {
+      return;
+    }
+    const elements = focusableElements(container);
+    if (elements.length === 0) {
+      event.preventDefault();
+      container.focus();
+      return;
+    }
+    const first = elements[0];
+    const last = elements[elements.length - 1];
+    const active = document.activeElement;
+    if (event.shiftKey && (active === first || !container.contains(active))) {
+      event.preventDefault();
+      last.focus();
+    } else if (!event.shiftKey && (active === last || !container.contains(active))) {
+      event.preventDefault();
+      first.focus();
+    }
+  };
+
+  // Pull focus back into the container if it escapes some other way, like a click outside
+  const onFocusIn = (event: FocusEvent) => {
+    if (!container.contains(event.target as Node)) {
+      focusFirst(container);
+    }
+  };
+
+  const trap: FocusTrap = { previous: document.activeElement, onKeyDown, onFocusIn };
+  container.dioxusFocusTrap = trap;
+  document.addEventListener("keydown", onKeyDown, true);
+  document.addEventListener("focusin", onFocusIn, true);
+
+  if (!container.contains(document.activeElement)) {
+    focusFirst(container);
+  }
+}
+
+export function releaseFocusTrap(container: any) {
+  const trap: FocusTrap | undefined = container && container.dioxusFocusTrap;
+  if (!trap) {
+    return;
+  }
+  document.removeEventListener("keydown", trap.onKeyDown, true);
+  document.removeEventListener("focusin", trap.onFocusIn, true);
+  delete container.dioxusFocusTrap;
+
+  // Give focus back to the element that had it before the trap was created
+  if (trap.previous instanceof HTMLElement && trap.previous.isConnected) {
+    trap.previous.focus();
+  }
+}
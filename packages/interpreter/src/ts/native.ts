@@ -4,6 +4,7 @@
 // provide since it doesn't have access to the dom.
 
 import { BaseInterpreter, NodeId } from "./core";
+import { releaseFocusTrap, trapFocus } from "./focus";
 import { SerializedEvent, serializeEvent } from "./serialize";
 
 // okay so, we've got this JSChannel thing from sledgehammer, implicitly imported into our scope
@@ -100,6 +101,10 @@ export class NativeInterpreter extends JSChannel_ {
       this.handleEvent(event, event.type, true);
 
     super.initialize(root, handler);
+
+    // The edits from an event arrive from the host after the next frame, so reverting the input then would also
+    // revert edits the app accepted
+    this.restoresControlledInputs = false;
   }
 
   serializeIpcMessage(method: string, params = {}) {
@@ -167,6 +172,16 @@ export class NativeInterpreter extends JSChannel_ {
     }
   }
 
+  setFocusTrap(id: NodeId, trap: boolean) {
+    const node = this.nodes[id];
+    if (trap) {
+      trapFocus(node);
+    } else {
+      releaseFocusTrap(node);
+    }
+    return true;
+  }
+
   // Windows drag-n-drop fix code. Called by wry drag-n-drop handler over the event loop.
   handleWindowsDragDrop() {
     if (window.dxDragLastElement) {
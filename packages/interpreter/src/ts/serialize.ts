@@ -64,6 +64,11 @@ export function serializeEvent(
       extend(serializeResizeEventDetail(detail));
     } else if (detail instanceof IntersectionObserverEntry) {
       extend(serializeIntersectionEventDetail(detail));
+    } else if (detail !== undefined && detail !== null) {
+      // The custom events of web components carry a detail the app can deserialize
+      try {
+        extend({ detail: JSON.parse(JSON.stringify(detail)) });
+      } catch {}
     }
   }
 
@@ -316,5 +321,26 @@ function serializeDragEvent(event: DragEvent): SerializedEvent {
       ...serializeMouseEvent(event),
     },
     files,
+    data_transfer: serializeDataTransfer(event.dataTransfer),
+  };
+}
+
+function serializeDataTransfer(transfer: DataTransfer | null) {
+  if (!transfer) {
+    return undefined;
+  }
+  const items = [];
+  for (const item of Array.from(transfer.items)) {
+    items.push({
+      kind: item.kind,
+      mime_type: item.type,
+      // Browsers only expose the data during dragstart and drop. Otherwise this is an empty string
+      data: item.kind === "string" ? transfer.getData(item.type) : null,
+    });
+  }
+  return {
+    drop_effect: transfer.dropEffect,
+    effect_allowed: transfer.effectAllowed,
+    items,
   };
 }
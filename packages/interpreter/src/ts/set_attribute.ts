@@ -17,8 +17,15 @@ export function setAttributeInner(node: HTMLElement, field: string, value: strin
   // A few attributes are need to be set with either boolean values or require some sort of translation
   switch (field) {
     case "value":
+      // Remember the value the app set so the input can be reverted if the app doesn't accept an edit
       // @ts-ignore
-      if (node.value !== value) {
+      node.dioxusValue = value;
+      // Changing the value while an IME composition is in progress cancels it. The value is applied when it ends
+      // @ts-ignore
+      if (node.dioxusComposing) {
+        // @ts-ignore
+        node.dioxusComposedValue = value;
+      } else if ((node as any).value !== value) {
         // @ts-ignore
         node.value = value;
       }
@@ -30,6 +37,8 @@ export function setAttributeInner(node: HTMLElement, field: string, value: strin
       break;
 
     case "checked":
+      // @ts-ignore
+      node.dioxusChecked = truthy(value);
       // @ts-ignore
       node.checked = truthy(value);
       break;
@@ -65,6 +74,38 @@ export function setAttributeInner(node: HTMLElement, field: string, value: strin
   }
 }
 
+// Apply the last value the app set while an IME composition was in progress
+export function applyComposedValue(node: any) {
+  if ("dioxusComposedValue" in node) {
+    node.value = node.dioxusComposedValue;
+    delete node.dioxusComposedValue;
+  }
+}
+
+// Controlled inputs always show the value the app set. If the user edits an input and the app doesn't update the
+// value in response, the edit is reverted.
+export function restoreControlledValue(node: any) {
+  // Reverting the value in the middle of an IME composition would break it. The input is restored when it ends
+  if (node.dioxusComposing) {
+    return;
+  }
+  if ("dioxusValue" in node && node.value !== node.dioxusValue) {
+    node.value = node.dioxusValue;
+  }
+  if ("dioxusChecked" in node) {
+    // Checking a radio button unchecks the other buttons in the group, so restore the whole group
+    let group = [node];
+    if (node.type === "radio" && node.name) {
+      const scope = node.form || node.getRootNode();
+      group = scope.querySelectorAll(`input[type="radio"][name="${CSS.escape(node.name)}"]`);
+    }
+    for (const input of group) {
+      if ("dioxusChecked" in input && input.checked !== input.dioxusChecked) {
+        input.checked = input.dioxusChecked;
+      }
+    }
+  }
+}
 
 function truthy(val: string | boolean) {
   return val === "true" || val === true;
@@ -84,6 +125,7 @@ function isBoolAttr(field: string): boolean {
     case "disabled":
     case "formnovalidate":
     case "hidden":
+    case "inert":
     case "ismap":
     case "itemscope":
     case "loop":
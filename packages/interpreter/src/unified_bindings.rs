@@ -22,6 +22,12 @@ extern "C" {
 
     #[wasm_bindgen(method, js_name = "pushRoot")]
     pub fn push_root(this: &BaseInterpreter, node: Node);
+
+    #[wasm_bindgen(method, js_name = "mountPortal")]
+    pub fn mount_portal(this: &BaseInterpreter, container: Node, target: Node);
+
+    #[wasm_bindgen(method, js_name = "unmountPortal")]
+    pub fn unmount_portal(this: &BaseInterpreter, container: Node);
 }
 
 // Note that this impl is for the sledgehammer interpreter to allow us dropping down to the base interpreter
@@ -75,16 +81,22 @@ mod js {
         "{let node = document.createComment('placeholder'); this.stack.push(node); this.nodes[$id$] = node;}"
     }
 
-    fn new_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
+    /// Bit 0 of the flags is set if the event bubbles, bit 1 if the listener is passive and bit 2 if the listener is direct
+    fn new_event_listener(event_name: &str<u8, evt>, id: u32, flags: u8) {
         r#"
             const node = this.nodes[id];
             if(node.listening){node.listening += 1;}else{node.listening = 1;}
             node.setAttribute('data-dioxus-id', `\${id}`);
-            this.createListener($event_name$, node, $bubbles$);
+            this.createListener($event_name$, node, (flags & 1) === 1, (flags & 2) === 2, (flags & 4) === 4);
         "#
     }
-    fn remove_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-        "{let node = this.nodes[$id$]; node.listening -= 1; node.removeAttribute('data-dioxus-id'); this.removeListener(node, $event_name$, $bubbles$);}"
+    fn remove_event_listener(event_name: &str<u8, evt>, id: u32, flags: u8) {
+        r#"
+            const node = this.nodes[id];
+            node.listening -= 1;
+            node.removeAttribute('data-dioxus-id');
+            this.removeListener(node, $event_name$, (flags & 1) === 1, (flags & 2) === 2, (flags & 4) === 4);
+        "#
     }
     fn set_text(id: u32, text: &str) {
         "{this.nodes[$id$].textContent = $text$;}"
@@ -92,16 +104,23 @@ mod js {
     fn set_attribute(id: u32, field: &str<u8, attr>, value: &str, ns: &str<u8, ns_cache>) {
         "{let node = this.nodes[$id$]; this.setAttributeInner(node, $field$, $value$, $ns$);}"
     }
+    /// Set a property of the node to a value encoded as JSON
+    fn set_property(id: u32, field: &str<u8, attr>, value: &str) {
+        "{this.nodes[$id$][$field$] = JSON.parse($value$);}"
+    }
     fn remove_attribute(id: u32, field: &str<u8, attr>, ns: &str<u8, ns_cache>) {
         r#"{
             let node = this.nodes[$id$];
             if (!ns) {
                 switch (field) {
                     case "value":
+                        delete node.dioxusValue;
+                        delete node.dioxusComposedValue;
                         node.value = "";
                         node.removeAttribute("value");
                         break;
                     case "checked":
+                        delete node.dioxusChecked;
                         node.checked = false;
                         break;
                     case "selected":
@@ -168,9 +187,11 @@ mod js {
     }
 
     #[cfg(feature = "binary-protocol")]
-    fn foreign_event_listener(event: &str<u8, evt>, id: u32, bubbles: u8) {
+    fn foreign_event_listener(event: &str<u8, evt>, id: u32, flags: u8) {
         r#"
-    bubbles = bubbles == 1;
+    const bubbles = (flags & 1) === 1;
+    const passive = (flags & 2) === 2;
+    const direct = (flags & 4) === 4;
     let this_node = this.nodes[id];
     if(this_node.listening){
         this_node.listening += 1;
@@ -191,9 +212,7 @@ mod js {
             })
         );
     } else {
-        this.createListener(event_name, this_node, bubbles, (event) => {
-            this.handler(event, event_name, bubbles);
-        });
+        this.createListener(event_name, this_node, bubbles, passive, direct);
     }"#
     }
 
@@ -1,6 +1,6 @@
 use crate::unified_bindings::Interpreter as Channel;
 use dioxus_core::{Template, TemplateAttribute, TemplateNode, WriteMutations};
-use dioxus_core_types::event_bubbles;
+use dioxus_core_types::{listener_flags, ListenerOptions};
 use rustc_hash::FxHashMap;
 
 /// The state needed to apply mutations to a channel. This state should be kept across all mutations for the app
@@ -164,6 +164,19 @@ impl WriteMutations for MutationState {
                 self.channel
                     .remove_attribute(id.0 as u32, name, ns.unwrap_or_default())
             }
+            dioxus_core::AttributeValue::Any(value) => {
+                match value
+                    .as_any()
+                    .downcast_ref::<dioxus_html::ElementProperty>()
+                {
+                    Some(property) => self
+                        .channel
+                        .set_property(id.0 as u32, name, property.json()),
+                    None => {
+                        unreachable!("Any attributes are not supported by the current renderer")
+                    }
+                }
+            }
             _ => unreachable!("Any attributes are not supported by the current renderer"),
         }
     }
@@ -175,13 +188,39 @@ impl WriteMutations for MutationState {
     fn create_event_listener(&mut self, name: &'static str, id: dioxus_core::ElementId) {
         // note that we use the foreign event listener here instead of the native one
         // the native method assumes we have direct access to the dom, which we don't.
-        self.channel
-            .foreign_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
+        self.channel.foreign_event_listener(
+            name,
+            id.0 as u32,
+            listener_flags(name, Default::default()),
+        );
     }
 
     fn remove_event_listener(&mut self, name: &'static str, id: dioxus_core::ElementId) {
+        self.channel.remove_event_listener(
+            name,
+            id.0 as u32,
+            listener_flags(name, Default::default()),
+        );
+    }
+
+    fn create_event_listener_with_options(
+        &mut self,
+        name: &'static str,
+        id: dioxus_core::ElementId,
+        options: ListenerOptions,
+    ) {
+        self.channel
+            .foreign_event_listener(name, id.0 as u32, listener_flags(name, options));
+    }
+
+    fn remove_event_listener_with_options(
+        &mut self,
+        name: &'static str,
+        id: dioxus_core::ElementId,
+        options: ListenerOptions,
+    ) {
         self.channel
-            .remove_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
+            .remove_event_listener(name, id.0 as u32, listener_flags(name, options));
     }
 
     fn remove_node(&mut self, id: dioxus_core::ElementId) {
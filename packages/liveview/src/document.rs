@@ -1,8 +1,10 @@
+use dioxus_clipboard::{Clipboard, EvalClipboard};
 use dioxus_core::prelude::queue_effect;
 use dioxus_core::ScopeId;
 use dioxus_document::{
-    create_element_in_head, Document, Eval, EvalError, Evaluator, LinkProps, MetaProps,
-    ScriptProps, StyleProps,
+    create_element_in_head, insert_element_in_head, remove_element_in_head, Document, Eval,
+    EvalError, Evaluator, HeadElement, HeadElementId, LinkProps, MetaProps, ScriptProps,
+    StyleProps,
 };
 use dioxus_history::History;
 use generational_box::{AnyStorage, GenerationalBox, UnsyncStorage};
@@ -64,16 +66,23 @@ impl Evaluator for LiveviewEvaluator {
     }
 }
 
-/// Provides the LiveviewDocument through [`ScopeId::provide_context`].
-pub fn init_document() {
+/// Provides the LiveviewDocument through [`ScopeId::provide_context`]. The history starts at `initial_route` if the
+/// session was restored from a snapshot.
+pub fn init_document(initial_route: Option<String>) {
     let query = ScopeId::ROOT.consume_context::<QueryEngine>().unwrap();
     let provider: Rc<dyn Document> = Rc::new(LiveviewDocument {
         query: query.clone(),
     });
-    ScopeId::ROOT.provide_context(provider);
-    let history = LiveviewHistory::new(Rc::new(move |script: &str| {
+    ScopeId::ROOT.provide_context(provider.clone());
+    let clipboard: Rc<dyn Clipboard> = Rc::new(EvalClipboard::new(provider));
+    ScopeId::ROOT.provide_context(clipboard);
+    let eval: Rc<dyn Fn(&str) -> Eval> = Rc::new(move |script: &str| {
         Eval::new(LiveviewEvaluator::create(query.clone(), script.to_string()))
-    }));
+    });
+    let history = match initial_route {
+        Some(route) => LiveviewHistory::new_with_initial_path(route, eval),
+        None => LiveviewHistory::new(eval),
+    };
     let history: Rc<dyn History> = Rc::new(history);
     ScopeId::ROOT.provide_context(history);
 }
@@ -136,4 +145,17 @@ impl Document for LiveviewDocument {
             myself.eval(create_element_in_head("link", &props.attributes(), None));
         });
     }
+
+    /// Insert an element a head component rendered into the head
+    fn insert_head_element(&self, element: HeadElement) {
+        let myself = self.clone();
+        queue_effect(move || {
+            myself.eval(insert_element_in_head(&element));
+        });
+    }
+
+    /// Remove an element a head component rendered from the head
+    fn remove_head_element(&self, id: HeadElementId) {
+        self.eval(remove_element_in_head(id));
+    }
 }
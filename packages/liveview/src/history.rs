@@ -1,6 +1,6 @@
 use dioxus_core::prelude::spawn;
 use dioxus_document::Eval;
-use dioxus_history::History;
+use dioxus_history::{History, HistoryEntry, ScrollPosition};
 use serde::{Deserialize, Serialize};
 use std::rc::Rc;
 use std::sync::{Mutex, RwLock};
@@ -16,6 +16,9 @@ pub(crate) struct LiveviewHistory {
 struct Timeline {
     current_index: usize,
     routes: BTreeMap<usize, String>,
+    scroll_positions: BTreeMap<usize, ScrollPosition>,
+    /// The state stored with each entry
+    states: BTreeMap<usize, String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -33,8 +36,8 @@ struct Session {
 enum Action {
     GoBack,
     GoForward,
-    Push(String),
-    Replace(String),
+    Push(String, Option<String>),
+    Replace(String, Option<String>),
     External(String),
 }
 
@@ -43,6 +46,8 @@ impl Timeline {
         Self {
             current_index: 0,
             routes: BTreeMap::from([(0, initial_path)]),
+            scroll_positions: BTreeMap::new(),
+            states: BTreeMap::new(),
         }
     }
 
@@ -92,6 +97,8 @@ impl Timeline {
         self.current_index = index;
         self.routes.insert(index, route);
         self.routes.retain(|&rhs, _| index >= rhs);
+        self.scroll_positions.retain(|&rhs, _| index > rhs);
+        self.states.retain(|&rhs, _| index > rhs);
         State {
             index: self.current_index,
         }
@@ -99,11 +106,18 @@ impl Timeline {
 
     fn replace(&mut self, route: String) -> State {
         self.routes.insert(self.current_index, route);
+        self.states.remove(&self.current_index);
         State {
             index: self.current_index,
         }
     }
 
+    fn set_state(&mut self, state: Option<String>) {
+        if let Some(state) = state {
+            self.states.insert(self.current_index, state);
+        }
+    }
+
     fn current_route(&self) -> &str {
         &self.routes[&self.current_index]
     }
@@ -138,7 +152,10 @@ impl LiveviewHistory {
     /// # Panics
     ///
     /// Panics if the function is not called in a dioxus runtime with a Liveview context.
-    fn new_with_initial_path(initial_path: String, eval: Rc<dyn Fn(&str) -> Eval>) -> Self {
+    pub(crate) fn new_with_initial_path(
+        initial_path: String,
+        eval: Rc<dyn Fn(&str) -> Eval>,
+    ) -> Self {
         let (action_tx, mut action_rx) = tokio::sync::mpsc::unbounded_channel::<Action>();
 
         let timeline = Arc::new(Mutex::new(Timeline::new(initial_path)));
@@ -166,9 +183,10 @@ impl LiveviewHistory {
                                 history.forward();
                             "#,
                         ),
-                        Action::Push(route) => {
+                        Action::Push(route, entry_state) => {
                             let mut timeline = timeline.lock().expect("unpoisoned mutex");
                             let state = timeline.push(route.clone());
+                            timeline.set_state(entry_state);
                             let state = serde_json::to_string(&state).expect("serializable state");
                             let session = serde_json::to_string(&timeline.session())
                                 .expect("serializable session");
@@ -180,9 +198,10 @@ impl LiveviewHistory {
                             "#
                             ))
                         }
-                        Action::Replace(route) => {
+                        Action::Replace(route, entry_state) => {
                             let mut timeline = timeline.lock().expect("unpoisoned mutex");
                             let state = timeline.replace(route.clone());
+                            timeline.set_state(entry_state);
                             let state = serde_json::to_string(&state).expect("serializable state");
                             let session = serde_json::to_string(&timeline.session())
                                 .expect("serializable session");
@@ -296,11 +315,50 @@ impl History for LiveviewHistory {
     }
 
     fn push(&self, route: String) {
-        let _ = self.action_tx.send(Action::Push(route));
+        let _ = self.action_tx.send(Action::Push(route, None));
     }
 
     fn replace(&self, route: String) {
-        let _ = self.action_tx.send(Action::Replace(route));
+        let _ = self.action_tx.send(Action::Replace(route, None));
+    }
+
+    fn push_with_state(&self, route: String, state: String) {
+        let _ = self.action_tx.send(Action::Push(route, Some(state)));
+    }
+
+    fn replace_with_state(&self, route: String, state: String) {
+        let _ = self.action_tx.send(Action::Replace(route, Some(state)));
+    }
+
+    fn current_state(&self) -> Option<String> {
+        let timeline = self.timeline.lock().expect("unpoisoned mutex");
+        timeline.states.get(&timeline.current_index).cloned()
+    }
+
+    fn length(&self) -> usize {
+        let timeline = self.timeline.lock().expect("unpoisoned mutex");
+        timeline.routes.len()
+    }
+
+    fn entries(&self) -> Option<Vec<HistoryEntry>> {
+        let timeline = self.timeline.lock().expect("unpoisoned mutex");
+        let entries = timeline
+            .routes
+            .iter()
+            .map(|(index, route)| HistoryEntry {
+                route: route.clone(),
+                state: timeline.states.get(index).cloned(),
+            })
+            .collect();
+        Some(entries)
+    }
+
+    fn current_index(&self) -> Option<usize> {
+        let timeline = self.timeline.lock().expect("unpoisoned mutex");
+        timeline
+            .routes
+            .keys()
+            .position(|&index| index == timeline.current_index)
     }
 
     fn external(&self, url: String) -> bool {
@@ -343,6 +401,20 @@ impl History for LiveviewHistory {
         *updater_callback = callback;
     }
 
+    fn set_scroll_position(&self, position: ScrollPosition) {
+        let mut timeline = self.timeline.lock().expect("unpoisoned mutex");
+        let index = timeline.current_index;
+        timeline.scroll_positions.insert(index, position);
+    }
+
+    fn scroll_position(&self) -> Option<ScrollPosition> {
+        let timeline = self.timeline.lock().expect("unpoisoned mutex");
+        timeline
+            .scroll_positions
+            .get(&timeline.current_index)
+            .copied()
+    }
+
     fn include_prevent_default(&self) -> bool {
         true
     }
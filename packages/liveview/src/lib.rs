@@ -7,8 +7,11 @@ mod adapters;
 pub use adapters::*;
 
 mod element;
+mod persist;
+pub use persist::{MemorySessionStore, SessionSnapshot, SessionStore};
 pub mod pool;
 mod query;
+mod session;
 use dioxus_interpreter_js::NATIVE_JS;
 use futures_util::{SinkExt, StreamExt};
 pub use pool::*;
@@ -31,6 +34,8 @@ impl<T> WebsocketRx for T where T: StreamExt<Item = Result<String, LiveViewError
 pub enum LiveViewError {
     #[error("Sending to client error")]
     SendingFailed,
+    #[error("Session store error: {0}")]
+    SessionStore(String),
 }
 
 fn handle_edits_code() -> String {
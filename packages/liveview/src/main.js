@@ -1,5 +1,9 @@
 const intercept_link_redirects = false;
 
+// The kinds of frames the server sends
+const TEXT_FRAME = 0;
+const EDITS_FRAME = 1;
+
 function main() {
   let root = window.document.getElementById("main");
   if (root != null) {
@@ -9,59 +13,131 @@ function main() {
 
 class IPC {
   constructor(root) {
+    this.createInterpreter(root);
+
+    // The server keeps the session alive for a while after the socket closes so we can resume it
+    this.session = null;
+    // The last frame we applied. Frames after it are replayed when we resume the session
+    this.lastFrame = 0;
+    this.heartbeat = 30000;
+    this.retryDelay = 250;
+    this.connect();
+  }
+
+  createInterpreter(root) {
+    this.root = root;
     window.interpreter = new NativeInterpreter();
     window.interpreter.initialize(root);
     window.interpreter.liveview = true;
     window.interpreter.ipc = this;
+  }
+
+  connect() {
     const ws = new WebSocket(WS_ADDR);
     ws.binaryType = "arraybuffer";
-
-    function ping() {
-      ws.send("__ping__");
-    }
+    this.ws = ws;
+    this.lastMessage = Date.now();
 
     ws.onopen = () => {
-      // we ping every 30 seconds to keep the websocket alive
-      setInterval(ping, 30000);
-      ws.send(window.interpreter.serializeIpcMessage("initialize"));
+      this.retryDelay = 250;
+      if (this.session === null) {
+        ws.send(window.interpreter.serializeIpcMessage("initialize"));
+      } else {
+        ws.send(
+          window.interpreter.serializeIpcMessage("resume", {
+            session: this.session,
+            frame: this.lastFrame,
+          })
+        );
+      }
+      this.startHeartbeat();
     };
 
-    ws.onerror = (err) => {
-      // todo: retry the connection
+    ws.onclose = () => {
+      clearInterval(this.heartbeatTimer);
+      // Sessions without an id end with the socket
+      if (this.session === null) {
+        return;
+      }
+      setTimeout(() => this.connect(), this.retryDelay);
+      this.retryDelay = Math.min(this.retryDelay * 2, 5000);
     };
 
-    ws.onmessage = (message) => {
-      const u8view = new Uint8Array(message.data);
-      const binaryFrame = u8view[0] == 1;
-      const messageData = message.data.slice(1);
-      // The first byte tells the shim if this is a binary of text frame
-      if (binaryFrame) {
-        // binary frame
-        window.interpreter.run_from_bytes(messageData);
-      } else {
-        // text frame
-
-        let decoder = new TextDecoder("utf-8");
-
-        // Using decode method to get string output
-        let str = decoder.decode(messageData);
-        // Ignore pongs
-        if (str != "__pong__") {
-          const event = JSON.parse(str);
-          switch (event.type) {
-            case "query":
-              Function("Eval", `"use strict";${event.data};`)();
-              break;
+    ws.onmessage = (message) => this.handleFrame(message.data);
+  }
+
+  startHeartbeat() {
+    clearInterval(this.heartbeatTimer);
+    this.heartbeatTimer = setInterval(() => {
+      // If the server stopped answering, the socket is dead even if the browser hasn't noticed yet
+      if (Date.now() - this.lastMessage > this.heartbeat * 2) {
+        this.ws.close();
+        return;
+      }
+      this.ws.send("__ping__");
+    }, this.heartbeat);
+  }
+
+  handleFrame(data) {
+    this.lastMessage = Date.now();
+    // Every frame starts with its kind and its sequence number
+    const view = new DataView(data);
+    const kind = view.getUint8(0);
+    const frame = view.getUint32(1, true);
+    // Frames that are replayed after we resume may have already been applied
+    if (frame !== 0 && frame <= this.lastFrame) {
+      return;
+    }
+    const payload = data.slice(5);
+
+    if (kind == EDITS_FRAME) {
+      window.interpreter.run_from_bytes(payload);
+    } else if (kind == TEXT_FRAME) {
+      let decoder = new TextDecoder("utf-8");
+
+      // Using decode method to get string output
+      let str = decoder.decode(payload);
+      // Ignore pongs
+      if (str != "__pong__") {
+        const event = JSON.parse(str);
+        switch (event.type) {
+          case "query":
+            Function("Eval", `"use strict";${event.data};`)();
+            break;
+          case "session":
+            this.session = event.data.id;
+            this.heartbeat = event.data.heartbeat;
+            this.startHeartbeat();
+            break;
+          case "reload":
+            // The session is gone, so the page is out of date
+            window.location.reload();
+            break;
+          case "reset": {
+            // Another server restored the session and renders it from scratch. Swap the root for an empty copy
+            // so the listeners of the old interpreter are dropped with it
+            const root = this.root.cloneNode(false);
+            this.root.replaceWith(root);
+            this.createInterpreter(root);
+            this.lastFrame = 0;
+            break;
           }
         }
       }
-    };
+    }
 
-    this.ws = ws;
+    // Let the server know it can send more frames and forget this one
+    if (frame !== 0) {
+      this.lastFrame = frame;
+      this.ws.send("__ack__" + frame);
+    }
   }
 
   postMessage(msg) {
-    this.ws.send(msg);
+    // Events that happen while we reconnect are dropped
+    if (this.ws.readyState === WebSocket.OPEN) {
+      this.ws.send(msg);
+    }
   }
 }
 
@@ -0,0 +1,115 @@
+//! Persisted sessions let a client move to another server behind a load balancer without losing its state.
+//!
+//! While a session runs, it saves a [`SessionSnapshot`] of its session storage and route to the [`SessionStore`] of the
+//! pool whenever either changes. If a client tries to resume a session the server doesn't know about, the pool loads the
+//! snapshot, tells the client to clear the page and starts a new virtual dom from the snapshot under the same id.
+
+use crate::LiveViewError;
+use dioxus_signals::StorageBackend;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// The state of a liveview session that is carried over to another server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// The session storage of the app. Signals created with
+    /// `use_persistent_signal_with(PersistOptions::new(key).session(), ..)` are saved here.
+    pub storage: BTreeMap<String, String>,
+    /// The route of the router
+    pub route: Option<String>,
+}
+
+/// Where the [`LiveViewPool`](crate::LiveViewPool) keeps the snapshots of its sessions.
+///
+/// [`MemorySessionStore`] keeps the snapshots in memory, so they can only be shared between pools in the same process.
+/// Implement this trait to keep them in Redis or a database instead.
+#[async_trait::async_trait]
+pub trait SessionStore: Send + Sync + 'static {
+    /// Load the snapshot of a session. Returns `None` if the session doesn't exist or expired.
+    async fn load(&self, id: &str) -> Result<Option<SessionSnapshot>, LiveViewError>;
+
+    /// Save the snapshot of a session until it expires
+    async fn save(
+        &self,
+        id: &str,
+        snapshot: &SessionSnapshot,
+        expires: SystemTime,
+    ) -> Result<(), LiveViewError>;
+}
+
+/// A [`SessionStore`] that keeps the snapshots in the memory of the server
+#[derive(Clone, Default)]
+pub struct MemorySessionStore {
+    sessions: Arc<Mutex<HashMap<String, (SessionSnapshot, SystemTime)>>>,
+}
+
+#[async_trait::async_trait]
+impl SessionStore for MemorySessionStore {
+    async fn load(&self, id: &str) -> Result<Option<SessionSnapshot>, LiveViewError> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let now = SystemTime::now();
+        // Drop the expired sessions while the lock is held anyway
+        sessions.retain(|_, (_, expires)| *expires > now);
+        Ok(sessions.get(id).map(|(snapshot, _)| snapshot.clone()))
+    }
+
+    async fn save(
+        &self,
+        id: &str,
+        snapshot: &SessionSnapshot,
+        expires: SystemTime,
+    ) -> Result<(), LiveViewError> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(id.to_string(), (snapshot.clone(), expires));
+        Ok(())
+    }
+}
+
+/// The session storage of a liveview session. It remembers if it changed since the last snapshot.
+#[derive(Default)]
+pub(crate) struct SessionStorage {
+    values: RefCell<BTreeMap<String, String>>,
+    changed: Cell<bool>,
+}
+
+impl SessionStorage {
+    pub(crate) fn new(values: BTreeMap<String, String>) -> Self {
+        Self {
+            values: RefCell::new(values),
+            changed: Cell::new(false),
+        }
+    }
+
+    pub(crate) fn values(&self) -> BTreeMap<String, String> {
+        self.values.borrow().clone()
+    }
+
+    /// Check if any value changed since the last call
+    pub(crate) fn take_changed(&self) -> bool {
+        self.changed.take()
+    }
+}
+
+impl StorageBackend for SessionStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: String) {
+        self.values.borrow_mut().insert(key.to_string(), value);
+        self.changed.set(true);
+    }
+
+    fn remove(&self, key: &str) {
+        if self.values.borrow_mut().remove(key).is_some() {
+            self.changed.set(true);
+        }
+    }
+}
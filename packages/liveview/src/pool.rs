@@ -1,21 +1,24 @@
 use crate::{
-    document::init_document,
-    element::LiveviewElement,
     events::SerializedHtmlEventConverter,
-    query::{QueryEngine, QueryResult},
+    persist::{SessionSnapshot, SessionStore},
+    session::{
+        new_session_id, parse_resume, reload_frame, reset_frame, Connection, Session,
+        SessionOptions, Sessions,
+    },
     LiveViewError,
 };
 use dioxus_core::prelude::*;
-use dioxus_html::{EventData, HtmlEvent, PlatformEventData};
-use dioxus_interpreter_js::MutationState;
 use futures_util::{pin_mut, SinkExt, StreamExt};
-use serde::Serialize;
-use std::{any::Any, rc::Rc, time::Duration};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use tokio_util::task::LocalPoolHandle;
 
 #[derive(Clone)]
 pub struct LiveViewPool {
     pub(crate) pool: LocalPoolHandle,
+    sessions: Sessions,
+    options: SessionOptions,
+    store: Option<Arc<dyn SessionStore>>,
 }
 
 impl Default for LiveViewPool {
@@ -31,9 +34,57 @@ impl LiveViewPool {
 
         LiveViewPool {
             pool: LocalPoolHandle::new(16),
+            sessions: Default::default(),
+            options: Default::default(),
+            store: None,
         }
     }
 
+    /// Set how often the client pings the server. The server drops sockets that miss two heartbeats and waits for
+    /// the client to resume the session over a new socket. Defaults to 30 seconds.
+    pub fn heartbeat_interval(mut self, interval: Duration) -> Self {
+        self.options.heartbeat_interval = interval;
+        self
+    }
+
+    /// Set how long a session stays alive after its socket drops. If the client reconnects before the timeout, it picks
+    /// up the session where it left off instead of starting over. Defaults to 30 seconds.
+    pub fn resume_timeout(mut self, timeout: Duration) -> Self {
+        self.options.resume_timeout = timeout;
+        self
+    }
+
+    /// Set how many frames can be sent before the client acknowledges them. While the client is behind, renders are
+    /// batched into a single frame. Defaults to 8.
+    pub fn max_frames_in_flight(mut self, frames: usize) -> Self {
+        self.options.max_frames_in_flight = frames.max(1);
+        self
+    }
+
+    /// Save snapshots of the sessions to a store that is shared with the other servers of the app.
+    ///
+    /// The snapshot of a session holds its session storage and the route of its router. Use
+    /// `use_persistent_signal_with(PersistOptions::new(key).session(), ..)` for the signals that should be kept. If the
+    /// client of a session reconnects to a server that doesn't know the session, like after a restart or when a load
+    /// balancer sends it somewhere else, that server restarts the app from the snapshot instead of reloading the page.
+    ///
+    /// ```rust, no_run
+    /// use dioxus_liveview::{LiveViewPool, MemorySessionStore};
+    ///
+    /// let pool = LiveViewPool::new().session_store(MemorySessionStore::default());
+    /// ```
+    pub fn session_store(mut self, store: impl SessionStore) -> Self {
+        self.store = Some(Arc::new(store));
+        self
+    }
+
+    /// Set how long the snapshot of a session is kept after it was last saved. Snapshots are saved again before they
+    /// expire while the session is alive. Defaults to an hour.
+    pub fn snapshot_expiry(mut self, expiry: Duration) -> Self {
+        self.options.snapshot_expiry = expiry;
+        self
+    }
+
     pub async fn launch(
         &self,
         ws: impl LiveViewSocket,
@@ -52,16 +103,102 @@ impl LiveViewPool {
             .await
     }
 
+    /// Render a new virtual dom over the socket, or resume the session the client was rendering before it reconnected.
+    ///
+    /// This returns when the socket closes. The session stays alive in the pool until the client resumes it or the
+    /// resume timeout runs out. If the session isn't alive in this pool, it is restored from the session store.
     pub async fn launch_virtualdom<F: FnOnce() -> VirtualDom + Send + 'static>(
         &self,
         ws: impl LiveViewSocket,
         make_app: F,
     ) -> Result<(), LiveViewError> {
-        match self.pool.spawn_pinned(move || run(make_app(), ws)).await {
-            Ok(Ok(_)) => Ok(()),
-            Ok(Err(e)) => Err(e),
-            Err(_) => Err(LiveViewError::SendingFailed),
+        let mut ws = Box::pin(ws);
+        // Drop clients that never say which session they want like clients that miss their heartbeats
+        let first_message = tokio::time::timeout(self.options.heartbeat_interval * 2, ws.next());
+        let Ok(Some(first_message)) = first_message.await else {
+            return Ok(());
+        };
+        let first_message = first_message?;
+
+        let (to_session, incoming) = unbounded_channel();
+        let (outgoing, from_session) = unbounded_channel();
+
+        if let Some((id, frame)) = parse_resume(&first_message) {
+            let connection = Connection {
+                incoming,
+                outgoing,
+                resume_from: Some(frame),
+            };
+            let session = self.sessions.lock().unwrap().get(&id).cloned();
+            let unclaimed = match session {
+                Some(session) => session.send(connection).err().map(|err| err.0),
+                None => Some(connection),
+            };
+            if let Some(connection) = unclaimed {
+                // The session timed out or was created by another server. Restore it from its snapshot, or make the
+                // client start over if there is none
+                let Some(snapshot) = self.load_snapshot(&id).await else {
+                    return ws.send(reload_frame()).await;
+                };
+                ws.send(reset_frame()).await?;
+                self.spawn_session(id, Some(snapshot), connection, make_app);
+            }
+        } else {
+            let connection = Connection {
+                incoming,
+                outgoing,
+                resume_from: None,
+            };
+            // Let the session handle the first message like any other message
+            _ = to_session.send(first_message);
+            self.spawn_session(new_session_id(), None, connection, make_app);
         }
+
+        forward(ws, to_session, from_session).await
+    }
+
+    async fn load_snapshot(&self, id: &str) -> Option<SessionSnapshot> {
+        let store = self.store.as_ref()?;
+        store.load(id).await.unwrap_or_else(|err| {
+            tracing::error!("Failed to load liveview session {id}: {err}");
+            None
+        })
+    }
+
+    /// Register a session so clients can resume it and run it in the background, because it outlives the socket
+    fn spawn_session<F: FnOnce() -> VirtualDom + Send + 'static>(
+        &self,
+        id: String,
+        snapshot: Option<SessionSnapshot>,
+        connection: Connection,
+        make_app: F,
+    ) {
+        let (connect, connections) = unbounded_channel();
+        _ = connect.send(connection);
+        self.sessions.lock().unwrap().insert(id.clone(), connect);
+
+        let sessions = self.sessions.clone();
+        let options = self.options;
+        let store = self.store.clone();
+        let session_id = id.clone();
+        let task = self.pool.spawn_pinned(move || async move {
+            let mut session = Session::new(make_app(), Some(session_id), options);
+            if let Some(store) = store {
+                session = session.persisted(store, snapshot);
+            }
+            session.run(connections).await
+        });
+
+        // Clean up the session even if it panicked
+        tokio::spawn(async move {
+            let result = task.await;
+            sessions.lock().unwrap().remove(&id);
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => tracing::error!("Liveview session {id} failed: {err}"),
+                Err(err) => tracing::error!("Liveview session {id} stopped: {err}"),
+            }
+        });
     }
 }
 
@@ -115,156 +252,51 @@ impl<S> LiveViewSocket for S where
 /// As long as your framework can provide a Sink and Stream of Bytes, you can use this function.
 ///
 /// You might need to transform the error types of the web backend into the LiveView error type.
-pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
-    #[cfg(all(feature = "devtools", debug_assertions))]
-    let mut hot_reload_rx = {
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-        if let Some(endpoint) = dioxus_cli_config::devserver_ws_endpoint() {
-            dioxus_devtools::connect(endpoint, move |template| _ = tx.send(template));
-        }
-        rx
-    };
-
-    let mut mutations = MutationState::default();
-
-    // Create the a proxy for query engine
-    let (query_tx, mut query_rx) = tokio::sync::mpsc::unbounded_channel();
-    let query_engine = QueryEngine::new(query_tx);
-    vdom.runtime().on_scope(ScopeId::ROOT, || {
-        provide_context(query_engine.clone());
-        init_document();
+///
+/// The session ends when the socket closes. Use [`LiveViewPool`] to let clients resume their session after they
+/// reconnect.
+pub async fn run(vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
+    let (to_session, incoming) = unbounded_channel();
+    let (outgoing, from_session) = unbounded_channel();
+    let (connect, connections) = unbounded_channel();
+    _ = connect.send(Connection {
+        incoming,
+        outgoing,
+        resume_from: None,
     });
 
-    // pin the futures so we can use select!
-    pin_mut!(ws);
-
-    if let Some(edits) = {
-        vdom.rebuild(&mut mutations);
-        take_edits(&mut mutations)
-    } {
-        // send the initial render to the client
-        ws.send(edits).await?;
-    }
+    let session = Session::new(vdom, None, SessionOptions::default());
+    let (session, socket) = tokio::join!(
+        session.run(connections),
+        forward(ws, to_session, from_session)
+    );
+    session.and(socket)
+}
 
-    // desktop uses this wrapper struct thing around the actual event itself
-    // this is sorta driven by tao/wry
-    #[derive(serde::Deserialize, Debug)]
-    #[serde(tag = "method", content = "params")]
-    enum IpcMessage {
-        #[serde(rename = "user_event")]
-        Event(Box<HtmlEvent>),
-        #[serde(rename = "query")]
-        Query(QueryResult),
-    }
+/// Forward messages between the socket and the session until either of them closes
+async fn forward(
+    ws: impl LiveViewSocket,
+    to_session: UnboundedSender<Vec<u8>>,
+    mut from_session: UnboundedReceiver<Vec<u8>>,
+) -> Result<(), LiveViewError> {
+    pin_mut!(ws);
 
     loop {
-        #[cfg(all(feature = "devtools", debug_assertions))]
-        let hot_reload_wait = hot_reload_rx.recv();
-        #[cfg(not(all(feature = "devtools", debug_assertions)))]
-        let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
-
         tokio::select! {
-            // poll any futures or suspense
-            _ = vdom.wait_for_work() => {}
-
-            evt = ws.next() => {
-                match evt.as_ref().map(|o| o.as_deref()) {
-                    // respond with a pong every ping to keep the websocket alive
-                    Some(Ok(b"__ping__")) => {
-                        ws.send(text_frame("__pong__")).await?;
-                    }
-                    Some(Ok(evt)) => {
-                        if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
-                            match message {
-                                IpcMessage::Event(evt) => {
-                                    // Intercept the mounted event and insert a custom element type
-                                    let event = if let EventData::Mounted = &evt.data {
-                                        let element = LiveviewElement::new(evt.element, query_engine.clone());
-                                        Event::new(
-                                            Rc::new(PlatformEventData::new(Box::new(element))) as Rc<dyn Any>,
-                                            evt.bubbles,
-                                        )
-                                    } else {
-                                        Event::new(
-                                            evt.data.into_any(),
-                                            evt.bubbles,
-                                        )
-                                    };
-                                    vdom.runtime().handle_event(
-                                        &evt.name,
-                                        event,
-                                        evt.element,
-                                    );
-                                }
-                                IpcMessage::Query(result) => {
-                                    query_engine.send(result);
-                                },
-                            }
-                        }
-                    }
-                    // log this I guess? when would we get an error here?
-                    Some(Err(_e)) => {}
-                    None => return Ok(()),
-                }
-            }
-
-            // handle any new queries
-            Some(query) = query_rx.recv() => {
-                ws.send(text_frame(&serde_json::to_string(&ClientUpdate::Query(query)).unwrap())).await?;
-            }
-
-            Some(msg) = hot_reload_wait => {
-                #[cfg(all(feature = "devtools", debug_assertions))]
-                match msg{
-                    dioxus_devtools::DevserverMsg::HotReload(msg)=> {
-                        dioxus_devtools::apply_changes(&vdom, &msg);
+            message = ws.next() => match message {
+                Some(Ok(message)) => {
+                    if to_session.send(message).is_err() {
+                        return Ok(());
                     }
-                    dioxus_devtools::DevserverMsg::Shutdown => {
-                        std::process::exit(0);
-                    },
-                    dioxus_devtools::DevserverMsg::FullReloadCommand
-                    | dioxus_devtools::DevserverMsg::FullReloadStart
-                    | dioxus_devtools::DevserverMsg::FullReloadFailed => {
-                        // usually only web gets this message - what are we supposed to do?
-                        // Maybe we could just binary patch ourselves in place without losing window state?
-                    },
                 }
-                #[cfg(not(all(feature = "devtools", debug_assertions)))]
-                let () = msg;
-            }
-        }
-
-        // wait for suspense to resolve in a 10ms window
-        tokio::select! {
-            _ = tokio::time::sleep(Duration::from_millis(10)) => {}
-            _ = vdom.wait_for_suspense() => {}
-        }
-
-        // render the vdom
-        vdom.render_immediate(&mut mutations);
-
-        if let Some(edits) = take_edits(&mut mutations) {
-            ws.send(edits).await?;
+                // log this I guess? when would we get an error here?
+                Some(Err(_e)) => {}
+                None => return Ok(()),
+            },
+            frame = from_session.recv() => match frame {
+                Some(frame) => ws.send(frame).await?,
+                None => return Ok(()),
+            },
         }
     }
 }
-
-fn text_frame(text: &str) -> Vec<u8> {
-    let mut bytes = vec![0];
-    bytes.extend(text.as_bytes());
-    bytes
-}
-
-fn take_edits(mutations: &mut MutationState) -> Option<Vec<u8>> {
-    // Add an extra one at the beginning to tell the shim this is a binary frame
-    let mut bytes = vec![1];
-    mutations.write_memory_into(&mut bytes);
-    (bytes.len() > 1).then_some(bytes)
-}
-
-#[derive(Serialize)]
-#[serde(tag = "type", content = "data")]
-enum ClientUpdate {
-    #[serde(rename = "query")]
-    Query(String),
-}
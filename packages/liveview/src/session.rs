@@ -0,0 +1,585 @@
+//! A session owns the virtual dom of one client and outlives the socket it is rendered over.
+//!
+//! Every frame the server sends starts with a kind byte and a sequence number:
+//!
+//! ```text
+//! [kind: u8] [frame: u32 LE] [payload]
+//! ```
+//!
+//! Edit frames carry the binary mutations of the interpreter and text frames carry JSON messages. The client acknowledges
+//! every frame it applies. The server keeps the frames that have not been acknowledged yet, so if the socket drops, the
+//! client can reconnect with the id of its session and the last frame it applied and the server replays the rest.
+//!
+//! The number of unacknowledged frames is also how the session applies backpressure. Once too many frames are in flight,
+//! the session keeps rendering but holds the edits back and sends them as a single frame when the client catches up.
+//!
+//! If the pool has a [`SessionStore`], the session also saves a snapshot of its state so another server can pick it up.
+//! See the [`persist`](crate::persist) module.
+
+use crate::{
+    document::init_document,
+    element::LiveviewElement,
+    persist::{SessionSnapshot, SessionStorage, SessionStore},
+    query::{QueryEngine, QueryResult},
+    LiveViewError,
+};
+use dioxus_core::{
+    prelude::*, AttributeValue, ElementId, ListenerOptions, Template, WriteMutations,
+};
+use dioxus_history::History;
+use dioxus_html::{EventData, HtmlEvent, PlatformEventData};
+use dioxus_interpreter_js::MutationState;
+use dioxus_signals::{MemoryStorage, PlatformStorage};
+use serde::Serialize;
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    sync::mpsc::{UnboundedReceiver, UnboundedSender},
+    time::Instant,
+};
+
+const TEXT_FRAME: u8 = 0;
+const EDITS_FRAME: u8 = 1;
+
+/// The sessions that can be resumed, by id
+pub(crate) type Sessions = Arc<Mutex<HashMap<String, UnboundedSender<Connection>>>>;
+
+/// How long sessions wait for their client
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SessionOptions {
+    pub(crate) heartbeat_interval: Duration,
+    pub(crate) resume_timeout: Duration,
+    pub(crate) max_frames_in_flight: usize,
+    pub(crate) snapshot_expiry: Duration,
+}
+
+impl Default for SessionOptions {
+    fn default() -> Self {
+        Self {
+            heartbeat_interval: Duration::from_secs(30),
+            resume_timeout: Duration::from_secs(30),
+            max_frames_in_flight: 8,
+            snapshot_expiry: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// The channels between a session and the socket it is rendered over
+pub(crate) struct Connection {
+    /// Messages from the client
+    pub(crate) incoming: UnboundedReceiver<Vec<u8>>,
+    /// Frames for the client
+    pub(crate) outgoing: UnboundedSender<Vec<u8>>,
+    /// The last frame the client applied if it is resuming the session
+    pub(crate) resume_from: Option<u32>,
+}
+
+/// The first message of a client that reconnects to its session
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "method", content = "params")]
+enum ResumeMessage {
+    #[serde(rename = "resume")]
+    Resume { session: String, frame: u32 },
+}
+
+/// Get the session and the last applied frame out of a resume message
+pub(crate) fn parse_resume(message: &[u8]) -> Option<(String, u32)> {
+    let ResumeMessage::Resume { session, frame } = serde_json::from_slice(message).ok()?;
+    Some((session, frame))
+}
+
+/// Create an id for a new session that clients can't guess
+pub(crate) fn new_session_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// Tell a client that its session is gone and it needs to reload the page
+pub(crate) fn reload_frame() -> Vec<u8> {
+    text_frame(0, r#"{"type":"reload"}"#)
+}
+
+/// Tell a client that its session moved to this server and it needs to clear the page before the session renders it again
+pub(crate) fn reset_frame() -> Vec<u8> {
+    text_frame(0, r#"{"type":"reset"}"#)
+}
+
+pub(crate) struct Session {
+    id: Option<String>,
+    vdom: VirtualDom,
+    mutations: MutationState,
+    /// If the mutations have edits that were not sent yet
+    pending_edits: bool,
+    options: SessionOptions,
+    connection: Option<Connection>,
+    /// The sequence number of the next frame. Frame 0 is never acknowledged or replayed.
+    next_frame: u32,
+    /// The frames the client has not acknowledged yet
+    unacked: VecDeque<(u32, Vec<u8>)>,
+    /// When the client was last heard from, or when the socket dropped if it is disconnected
+    last_heard: Instant,
+    /// The session storage of the app, which is part of the snapshot
+    storage: Rc<SessionStorage>,
+    store: Option<Arc<dyn SessionStore>>,
+    /// The route the session started at, then the route of the last saved snapshot
+    route: Option<String>,
+    last_saved: Instant,
+}
+
+impl Session {
+    /// Create a session. Sessions without an id end when their first socket closes.
+    pub(crate) fn new(vdom: VirtualDom, id: Option<String>, options: SessionOptions) -> Self {
+        Self {
+            id,
+            vdom,
+            mutations: MutationState::default(),
+            pending_edits: false,
+            options,
+            connection: None,
+            next_frame: 1,
+            unacked: VecDeque::new(),
+            last_heard: Instant::now(),
+            storage: Default::default(),
+            store: None,
+            route: None,
+            last_saved: Instant::now(),
+        }
+    }
+
+    /// Save snapshots of the session to the store, starting from an earlier snapshot if the session moved from another
+    /// server
+    pub(crate) fn persisted(
+        mut self,
+        store: Arc<dyn SessionStore>,
+        snapshot: Option<SessionSnapshot>,
+    ) -> Self {
+        if let Some(snapshot) = snapshot {
+            self.storage = Rc::new(SessionStorage::new(snapshot.storage));
+            self.route = snapshot.route;
+        }
+        self.store = Some(store);
+        self
+    }
+
+    /// The primary event loop for the VirtualDom waiting for user input. New sockets for the session arrive through
+    /// `connections`.
+    pub(crate) async fn run(
+        mut self,
+        mut connections: UnboundedReceiver<Connection>,
+    ) -> Result<(), LiveViewError> {
+        #[cfg(all(feature = "devtools", debug_assertions))]
+        let mut hot_reload_rx = {
+            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            if let Some(endpoint) = dioxus_cli_config::devserver_ws_endpoint() {
+                dioxus_devtools::connect(endpoint, move |template| _ = tx.send(template));
+            }
+            rx
+        };
+
+        // Create the a proxy for query engine
+        let (query_tx, mut query_rx) = tokio::sync::mpsc::unbounded_channel();
+        let query_engine = QueryEngine::new(query_tx);
+        self.vdom.runtime().on_scope(ScopeId::ROOT, || {
+            provide_context(query_engine.clone());
+            provide_context(PlatformStorage {
+                local: Rc::new(MemoryStorage::default()),
+                session: self.storage.clone(),
+            });
+            init_document(self.route.clone());
+        });
+
+        let Some(connection) = connections.recv().await else {
+            return Ok(());
+        };
+        self.connection = Some(connection);
+        self.last_heard = Instant::now();
+
+        // Tell the client how to keep the session alive and send the initial render
+        let session = ClientUpdate::Session {
+            id: self.id.clone(),
+            heartbeat: self.options.heartbeat_interval.as_millis() as u64,
+        };
+        self.send_text(&serde_json::to_string(&session).unwrap());
+        self.vdom.rebuild(&mut RecordEdits {
+            mutations: &mut self.mutations,
+            pending_edits: &mut self.pending_edits,
+        });
+        self.flush_edits(true);
+        self.save_snapshot(true).await;
+
+        // desktop uses this wrapper struct thing around the actual event itself
+        // this is sorta driven by tao/wry
+        #[derive(serde::Deserialize, Debug)]
+        #[serde(tag = "method", content = "params")]
+        enum IpcMessage {
+            #[serde(rename = "user_event")]
+            Event(Box<HtmlEvent>),
+            #[serde(rename = "query")]
+            Query(QueryResult),
+        }
+
+        loop {
+            #[cfg(all(feature = "devtools", debug_assertions))]
+            let hot_reload_wait = hot_reload_rx.recv();
+            #[cfg(not(all(feature = "devtools", debug_assertions)))]
+            let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
+
+            // A connected client must send a heartbeat and a disconnected client must resume before the deadline
+            let connected = self.connection.is_some();
+            let deadline = match connected {
+                true => self.last_heard + self.options.heartbeat_interval * 2,
+                false => self.last_heard + self.options.resume_timeout,
+            };
+
+            tokio::select! {
+                // poll any futures or suspense
+                _ = self.vdom.wait_for_work() => {}
+
+                Some(connection) = connections.recv() => self.attach(connection),
+
+                message = recv(&mut self.connection) => {
+                    let Some(message) = message else {
+                        if self.detach() {
+                            return Ok(());
+                        }
+                        self.save_snapshot(true).await;
+                        continue;
+                    };
+                    self.last_heard = Instant::now();
+                    match message.as_slice() {
+                        // respond with a pong every ping to keep the websocket alive
+                        b"__ping__" => self.send_unsequenced(text_frame(0, "__pong__")),
+                        message if message.starts_with(b"__ack__") => {
+                            if let Some(frame) = std::str::from_utf8(&message[7..])
+                                .ok()
+                                .and_then(|frame| frame.parse().ok())
+                            {
+                                self.acknowledge(frame);
+                            }
+                        }
+                        message => {
+                            if let Ok(message) = serde_json::from_slice::<IpcMessage>(message) {
+                                match message {
+                                    IpcMessage::Event(evt) => {
+                                        // Intercept the mounted event and insert a custom element type
+                                        let event = if let EventData::Mounted = &evt.data {
+                                            let element = LiveviewElement::new(evt.element, query_engine.clone());
+                                            Event::new(
+                                                Rc::new(PlatformEventData::new(Box::new(element))) as Rc<dyn Any>,
+                                                evt.bubbles,
+                                            )
+                                        } else {
+                                            Event::new(
+                                                evt.data.into_any(),
+                                                evt.bubbles,
+                                            )
+                                        };
+                                        self.vdom.runtime().handle_event(
+                                            &evt.name,
+                                            event,
+                                            evt.element,
+                                        );
+                                    }
+                                    IpcMessage::Query(result) => {
+                                        query_engine.send(result);
+                                    },
+                                }
+                            }
+                        }
+                    }
+                }
+
+                _ = tokio::time::sleep_until(deadline) => {
+                    if !connected {
+                        // The client never came back
+                        return Ok(());
+                    }
+                    // The socket is dead even if the transport hasn't noticed yet. Wait for the client to reconnect
+                    if self.detach() {
+                        return Ok(());
+                    }
+                    self.save_snapshot(true).await;
+                }
+
+                // handle any new queries
+                Some(query) = query_rx.recv() => {
+                    self.send_text(&serde_json::to_string(&ClientUpdate::Query(query)).unwrap());
+                }
+
+                Some(msg) = hot_reload_wait => {
+                    #[cfg(all(feature = "devtools", debug_assertions))]
+                    match msg{
+                        dioxus_devtools::DevserverMsg::HotReload(msg)=> {
+                            dioxus_devtools::apply_changes(&self.vdom, &msg);
+                        }
+                        dioxus_devtools::DevserverMsg::Shutdown => {
+                            std::process::exit(0);
+                        },
+                        dioxus_devtools::DevserverMsg::FullReloadCommand
+                        | dioxus_devtools::DevserverMsg::FullReloadStart
+                        | dioxus_devtools::DevserverMsg::FullReloadFailed => {
+                            // usually only web gets this message - what are we supposed to do?
+                            // Maybe we could just binary patch ourselves in place without losing window state?
+                        },
+                    }
+                    #[cfg(not(all(feature = "devtools", debug_assertions)))]
+                    let () = msg;
+                }
+            }
+
+            // wait for suspense to resolve in a 10ms window
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+                _ = self.vdom.wait_for_suspense() => {}
+            }
+
+            // render the vdom
+            self.vdom.render_immediate(&mut RecordEdits {
+                mutations: &mut self.mutations,
+                pending_edits: &mut self.pending_edits,
+            });
+            self.flush_edits(false);
+            // A detached session may be picked up by another server, so it stops overwriting the snapshot
+            if self.connection.is_some() {
+                self.save_snapshot(false).await;
+            }
+        }
+    }
+
+    /// Save a snapshot of the session storage and route to the store. Unless the save is forced, the snapshot is only
+    /// saved if it changed or the saved snapshot is about to expire.
+    async fn save_snapshot(&mut self, force: bool) {
+        let (Some(store), Some(id)) = (self.store.clone(), self.id.clone()) else {
+            return;
+        };
+        let route = self.vdom.in_runtime(|| {
+            ScopeId::ROOT
+                .consume_context::<Rc<dyn History>>()
+                .map(|history| history.current_route())
+        });
+        let changed = self.storage.take_changed() || route != self.route;
+        let stale = self.last_saved.elapsed() > self.options.snapshot_expiry / 2;
+        if !(force || changed || stale) {
+            return;
+        }
+
+        let snapshot = SessionSnapshot {
+            storage: self.storage.values(),
+            route: route.clone(),
+        };
+        let expires = SystemTime::now() + self.options.snapshot_expiry;
+        match store.save(&id, &snapshot, expires).await {
+            Ok(()) => {
+                self.route = route;
+                self.last_saved = Instant::now();
+            }
+            Err(err) => tracing::error!("Failed to save liveview session {id}: {err}"),
+        }
+    }
+
+    /// Switch the session to a new socket and replay the frames the client missed
+    fn attach(&mut self, connection: Connection) {
+        if let Some(frame) = connection.resume_from {
+            self.acknowledge(frame);
+        }
+        for (_, frame) in &self.unacked {
+            _ = connection.outgoing.send(frame.clone());
+        }
+        self.connection = Some(connection);
+        self.last_heard = Instant::now();
+        self.flush_edits(false);
+    }
+
+    /// Drop the current socket. Returns true if the session can't be resumed and should end.
+    fn detach(&mut self) -> bool {
+        self.connection = None;
+        self.last_heard = Instant::now();
+        self.id.is_none()
+    }
+
+    /// Forget every frame up to and including the frame the client acknowledged
+    fn acknowledge(&mut self, frame: u32) {
+        while self
+            .unacked
+            .front()
+            .is_some_and(|(sequence, _)| *sequence <= frame)
+        {
+            self.unacked.pop_front();
+        }
+    }
+
+    /// Send the pending edits as one frame. Unless the edits are forced out, they stay pending while the client is
+    /// disconnected or has too many frames in flight so the next renders are batched into the same frame.
+    fn flush_edits(&mut self, force: bool) {
+        let backed_up =
+            self.connection.is_none() || self.unacked.len() >= self.options.max_frames_in_flight;
+        if backed_up && !force {
+            return;
+        }
+        if std::mem::take(&mut self.pending_edits) {
+            let edits = take_edits(&mut self.mutations, self.next_frame);
+            self.send_sequenced(edits);
+        }
+    }
+
+    /// Send a JSON message after any pending edits it may refer to
+    fn send_text(&mut self, text: &str) {
+        self.flush_edits(true);
+        let frame = text_frame(self.next_frame, text);
+        self.send_sequenced(frame);
+    }
+
+    fn send_sequenced(&mut self, frame: Vec<u8>) {
+        self.unacked.push_back((self.next_frame, frame.clone()));
+        self.next_frame += 1;
+        self.send_unsequenced(frame);
+    }
+
+    fn send_unsequenced(&mut self, frame: Vec<u8>) {
+        if let Some(connection) = &self.connection {
+            _ = connection.outgoing.send(frame);
+        }
+    }
+}
+
+/// Wait for the next message from the client, or forever if it is disconnected
+async fn recv(connection: &mut Option<Connection>) -> Option<Vec<u8>> {
+    match connection {
+        Some(connection) => connection.incoming.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn header(kind: u8, frame: u32) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(5);
+    bytes.push(kind);
+    bytes.extend_from_slice(&frame.to_le_bytes());
+    bytes
+}
+
+fn text_frame(frame: u32, text: &str) -> Vec<u8> {
+    let mut bytes = header(TEXT_FRAME, frame);
+    bytes.extend(text.as_bytes());
+    bytes
+}
+
+fn take_edits(mutations: &mut MutationState, frame: u32) -> Vec<u8> {
+    let mut bytes = header(EDITS_FRAME, frame);
+    mutations.write_memory_into(&mut bytes);
+    bytes
+}
+
+/// Writes mutations and remembers if there were any. The exported memory of the interpreter is never empty, so it
+/// can't tell us if a render changed anything.
+struct RecordEdits<'a> {
+    mutations: &'a mut MutationState,
+    pending_edits: &'a mut bool,
+}
+
+impl RecordEdits<'_> {
+    fn record(&mut self) -> &mut MutationState {
+        *self.pending_edits = true;
+        self.mutations
+    }
+}
+
+impl WriteMutations for RecordEdits<'_> {
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.record().append_children(id, m)
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        self.record().assign_node_id(path, id)
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.record().create_placeholder(id)
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        self.record().create_text_node(value, id)
+    }
+
+    fn load_template(&mut self, template: Template, index: usize, id: ElementId) {
+        self.record().load_template(template, index, id)
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        self.record().replace_node_with(id, m)
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        self.record().replace_placeholder_with_nodes(path, m)
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        self.record().insert_nodes_after(id, m)
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        self.record().insert_nodes_before(id, m)
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        self.record().set_attribute(name, ns, value, id)
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        self.record().set_node_text(value, id)
+    }
+
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.record().create_event_listener(name, id)
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.record().remove_event_listener(name, id)
+    }
+
+    fn create_event_listener_with_options(
+        &mut self,
+        name: &'static str,
+        id: ElementId,
+        options: ListenerOptions,
+    ) {
+        self.record()
+            .create_event_listener_with_options(name, id, options)
+    }
+
+    fn remove_event_listener_with_options(
+        &mut self,
+        name: &'static str,
+        id: ElementId,
+        options: ListenerOptions,
+    ) {
+        self.record()
+            .remove_event_listener_with_options(name, id, options)
+    }
+
+    fn remove_node(&mut self, id: ElementId) {
+        self.record().remove_node(id)
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.record().push_root(id)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", content = "data")]
+enum ClientUpdate {
+    #[serde(rename = "query")]
+    Query(String),
+    #[serde(rename = "session")]
+    Session { id: Option<String>, heartbeat: u64 },
+}
@@ -0,0 +1,214 @@
+use dioxus::prelude::*;
+use dioxus_liveview::{LiveViewError, LiveViewPool, MemorySessionStore, SessionStore};
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::{Sink, Stream, StreamExt};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// An in memory socket. The test plays the client on the other end.
+struct TestSocket {
+    incoming: UnboundedReceiver<Vec<u8>>,
+    outgoing: UnboundedSender<Vec<u8>>,
+}
+
+impl Stream for TestSocket {
+    type Item = Result<Vec<u8>, LiveViewError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.incoming
+            .poll_next_unpin(cx)
+            .map(|message| message.map(Ok))
+    }
+}
+
+impl Sink<Vec<u8>> for TestSocket {
+    type Error = LiveViewError;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, frame: Vec<u8>) -> Result<(), Self::Error> {
+        self.outgoing
+            .unbounded_send(frame)
+            .map_err(|_| LiveViewError::SendingFailed)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+struct Client {
+    send: UnboundedSender<Vec<u8>>,
+    frames: UnboundedReceiver<Vec<u8>>,
+}
+
+impl Client {
+    fn connect(pool: &LiveViewPool, app: fn() -> Element, first_message: &str) -> Self {
+        let (send, incoming) = unbounded();
+        let (outgoing, frames) = unbounded();
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.launch(TestSocket { incoming, outgoing }, app).await });
+        let client = Self { send, frames };
+        client.send(first_message);
+        client
+    }
+
+    fn send(&self, message: &str) {
+        self.send.unbounded_send(message.into()).unwrap();
+    }
+
+    /// Get the kind, sequence number and payload of the next frame
+    async fn next_frame(&mut self) -> Option<(u8, u32, Vec<u8>)> {
+        let frame = tokio::time::timeout(Duration::from_millis(500), self.frames.next())
+            .await
+            .ok()??;
+        let sequence = u32::from_le_bytes(frame[1..5].try_into().unwrap());
+        Some((frame[0], sequence, frame[5..].to_vec()))
+    }
+
+    /// Get every frame the server sends until it stops sending
+    async fn frames_until_idle(&mut self) -> Vec<(u8, u32, Vec<u8>)> {
+        let mut frames = Vec::new();
+        while let Some(frame) = self.next_frame().await {
+            frames.push(frame);
+        }
+        frames
+    }
+}
+
+const EDITS: u8 = 1;
+
+fn session_id(payload: &[u8]) -> String {
+    let message: serde_json::Value = serde_json::from_slice(payload).unwrap();
+    assert_eq!(message["type"], "session");
+    message["data"]["id"].as_str().unwrap().to_string()
+}
+
+fn app() -> Element {
+    rsx! { div { "Hello world" } }
+}
+
+#[tokio::test]
+async fn sessions_resume_after_reconnect() {
+    let pool = LiveViewPool::new();
+    let mut client = Client::connect(&pool, app, r#"{"method":"initialize","params":{}}"#);
+
+    let (kind, sequence, payload) = client.next_frame().await.unwrap();
+    assert_eq!((kind, sequence), (0, 1));
+    let id = session_id(&payload);
+    let (kind, sequence, edits) = client.next_frame().await.unwrap();
+    assert_eq!((kind, sequence), (1, 2));
+
+    // The client only applied the session frame before the socket dropped
+    drop(client);
+    let resume = format!(r#"{{"method":"resume","params":{{"session":"{id}","frame":1}}}}"#);
+    let mut client = Client::connect(&pool, app, &resume);
+    assert_eq!(client.next_frame().await, Some((1, 2, edits)));
+
+    // Sessions the server doesn't know about can't be resumed
+    let resume = r#"{"method":"resume","params":{"session":"unknown","frame":1}}"#;
+    let mut client = Client::connect(&pool, app, resume);
+    let (kind, sequence, payload) = client.next_frame().await.unwrap();
+    assert_eq!((kind, sequence), (0, 0));
+    assert_eq!(payload, br#"{"type":"reload"}"#);
+}
+
+fn ticking_app() -> Element {
+    let mut count = use_signal(|| 0);
+    use_future(move || async move {
+        for _ in 0..5 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            count += 1;
+        }
+    });
+    rsx! { div { "{count}" } }
+}
+
+#[tokio::test]
+async fn renders_are_batched_while_the_client_is_behind() {
+    let pool = LiveViewPool::new().max_frames_in_flight(2);
+    let mut client = Client::connect(&pool, ticking_app, r#"{"method":"initialize","params":{}}"#);
+
+    // The session and initial render fill the window, so the updates wait for an acknowledgement
+    let frames = client.frames_until_idle().await;
+    let edits = frames.iter().filter(|(kind, ..)| *kind == EDITS).count();
+    assert_eq!(edits, 1);
+
+    // Every update is sent in one frame once the client catches up
+    let (_, last, _) = frames.last().unwrap();
+    client.send(&format!("__ack__{last}"));
+    let frames = client.frames_until_idle().await;
+    assert_eq!(frames.len(), 1);
+    assert_eq!((frames[0].0, frames[0].1), (EDITS, last + 1));
+}
+
+fn persisted_app() -> Element {
+    let mut count = use_persistent_signal_with(
+        PersistOptions::new("count")
+            .session()
+            .debounce(Duration::ZERO),
+        || 0,
+    );
+    // Count how many times the app started
+    use_future(move || async move { count += 1 });
+    rsx! { div { "count is {count}" } }
+}
+
+#[tokio::test]
+async fn sessions_move_to_another_server() {
+    let store = MemorySessionStore::default();
+    let first = LiveViewPool::new().session_store(store.clone());
+    let mut client = Client::connect(
+        &first,
+        persisted_app,
+        r#"{"method":"initialize","params":{}}"#,
+    );
+    let frames = client.frames_until_idle().await;
+    let id = session_id(&frames[0].2);
+    let (_, last, _) = frames.last().unwrap();
+
+    // The session storage and route are saved as the session runs
+    let snapshot = store.load(&id).await.unwrap().unwrap();
+    let count: serde_json::Value = serde_json::from_str(&snapshot.storage["count"]).unwrap();
+    assert_eq!(count["value"], 1);
+    assert_eq!(snapshot.route.as_deref(), Some("/"));
+
+    // A server that doesn't know the session restores it from the snapshot after the client clears the page
+    drop(client);
+    let second = LiveViewPool::new().session_store(store);
+    let resume = format!(r#"{{"method":"resume","params":{{"session":"{id}","frame":{last}}}}}"#);
+    let mut client = Client::connect(&second, persisted_app, &resume);
+    let (kind, sequence, payload) = client.next_frame().await.unwrap();
+    assert_eq!((kind, sequence), (0, 0));
+    assert_eq!(payload, br#"{"type":"reset"}"#);
+
+    let frames = client.frames_until_idle().await;
+    assert_eq!((frames[0].0, frames[0].1), (0, 1));
+    assert_eq!(session_id(&frames[0].2), id);
+    let rendered = frames
+        .iter()
+        .filter(|(kind, ..)| *kind == EDITS)
+        .any(|(_, _, edits)| edits.windows(10).any(|text| text == b"count is 2"));
+    assert!(rendered);
+}
+
+#[tokio::test]
+async fn silent_clients_are_dropped_after_two_heartbeats() {
+    let pool = LiveViewPool::new().heartbeat_interval(Duration::from_millis(10));
+    let (_send, incoming) = unbounded();
+    let (outgoing, _frames) = unbounded();
+
+    // The client connects but never sends its first message
+    let launch = pool.launch(TestSocket { incoming, outgoing }, app);
+    let result = tokio::time::timeout(Duration::from_millis(500), launch).await;
+    assert!(matches!(result, Ok(Ok(()))));
+}
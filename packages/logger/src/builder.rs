@@ -0,0 +1,159 @@
+use crate::{sink::SinkLayer, LogSink};
+use std::sync::Arc;
+use tracing::{
+    subscriber::{set_global_default, SetGlobalDefaultError},
+    Level, Subscriber,
+};
+use tracing_subscriber::{filter::Targets, layer::SubscriberExt, Layer, Registry};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// The default size a log file can grow to before it is rotated
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// The default number of rotated log files that are kept
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_MAX_FILES: usize = 5;
+
+/// A builder for the logger. Every record is sent to the sink of the platform and any sinks you add:
+///
+/// - On web, records are written to the browser console.
+/// - On android, records are written to logcat.
+/// - On desktop and servers, records are printed to stdout and can also be written to a rotating log file.
+/// - When the app is running under `dx serve`, records are streamed to the CLI instead of stdout so it can filter them
+///   by level and module.
+///
+/// ```rust,no_run
+/// use dioxus_logger::LoggerBuilder;
+/// use dioxus_logger::tracing::Level;
+///
+/// LoggerBuilder::new(Level::INFO)
+///     // Show the debug logs of the database module
+///     .module("my_app::db", Level::DEBUG)
+///     // Hide everything but errors from a noisy dependency
+///     .module("hyper", Level::ERROR)
+///     .file("logs/app.log")
+///     .init()
+///     .expect("logger failed to init");
+/// ```
+pub struct LoggerBuilder {
+    level: Level,
+    modules: Vec<(String, Level)>,
+    sinks: Vec<Arc<dyn LogSink>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    file: Option<PathBuf>,
+    #[cfg(not(target_arch = "wasm32"))]
+    max_file_size: u64,
+    #[cfg(not(target_arch = "wasm32"))]
+    max_files: usize,
+}
+
+impl LoggerBuilder {
+    /// Create a builder for a logger that logs events at the level and above
+    pub fn new(level: Level) -> Self {
+        Self {
+            level,
+            modules: Vec::new(),
+            sinks: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            file: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            #[cfg(not(target_arch = "wasm32"))]
+            max_files: DEFAULT_MAX_FILES,
+        }
+    }
+
+    /// Set the level of a module and its children, overriding the level of the logger
+    pub fn module(mut self, module: impl Into<String>, level: Level) -> Self {
+        self.modules.push((module.into(), level));
+        self
+    }
+
+    /// Send every record to a sink in addition to the sink of the platform
+    pub fn sink(mut self, sink: impl LogSink) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Also write logs to a file. The file is rotated once it grows past [`LoggerBuilder::max_file_size`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    /// Set the size in bytes the log file can grow to before it is rotated. Defaults to 10MB.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// Set the number of rotated log files to keep next to the log file. Defaults to 5.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn max_files(mut self, count: usize) -> Self {
+        self.max_files = count;
+        self
+    }
+
+    /// Build the subscriber without installing it. This is useful to scope the logger to a test or thread
+    /// with [`tracing::subscriber::with_default`].
+    pub fn build(self) -> impl Subscriber + Send + Sync {
+        let filter = Targets::new()
+            .with_default(self.level)
+            .with_targets(self.modules.clone());
+
+        let mut layers: Vec<BoxedLayer> = vec![self.platform_layer()];
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = &self.file {
+            let file =
+                crate::file::RotatingFile::new(path.clone(), self.max_file_size, self.max_files);
+            layers.push(Box::new(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(std::sync::Mutex::new(file)),
+            ));
+        }
+
+        layers.extend(
+            self.sinks
+                .iter()
+                .map(|sink| Box::new(SinkLayer::new(sink.clone())) as BoxedLayer),
+        );
+
+        Registry::default().with(layers.with_filter(filter))
+    }
+
+    /// Build the logger and install it as the global default
+    pub fn init(self) -> Result<(), SetGlobalDefaultError> {
+        #[cfg(target_arch = "wasm32")]
+        console_error_panic_hook::set_once();
+
+        set_global_default(self.build())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn platform_layer(&self) -> BoxedLayer {
+        Box::new(SinkLayer::new(Arc::new(crate::sink::console_sink)))
+    }
+
+    #[cfg(target_os = "android")]
+    fn platform_layer(&self) -> BoxedLayer {
+        Box::new(SinkLayer::new(Arc::new(crate::sink::logcat_sink)))
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+    fn platform_layer(&self) -> BoxedLayer {
+        if dioxus_cli_config::is_cli_enabled() {
+            return Box::new(SinkLayer::new(Arc::new(crate::sink::dx_sink)));
+        }
+
+        Box::new(tracing_subscriber::fmt::layer())
+    }
+}
@@ -0,0 +1,79 @@
+use dioxus_core::prelude::use_hook;
+use std::future::Future;
+use tracing::{instrument::Instrumented, span::Entered, Instrument, Level, Span};
+
+/// The name of the span that tags the logs of a component
+pub(crate) const COMPONENT_SPAN: &str = "component";
+
+/// The name of the component a span belongs to, stored in the extensions of the span
+pub(crate) struct ComponentName(pub(crate) String);
+
+/// A logger scoped to a component. Logs written in its scope are tagged with the name of the component. Get it with
+/// [`use_logger`].
+#[derive(Clone)]
+pub struct ComponentLogger {
+    span: Span,
+}
+
+impl ComponentLogger {
+    /// Create a logger that tags logs with a name
+    pub fn new(name: &str) -> Self {
+        // The span is created at the error level so filters never disable it. It doesn't log anything on its own
+        Self {
+            span: tracing::span!(Level::ERROR, COMPONENT_SPAN, name),
+        }
+    }
+
+    /// Run a closure in the scope of the logger
+    pub fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        self.span.in_scope(f)
+    }
+
+    /// Enter the scope of the logger until the guard is dropped
+    pub fn enter(&self) -> Entered<'_> {
+        self.span.enter()
+    }
+
+    /// Tag the logs of a future, like a task spawned by the component, with the component
+    pub fn instrument<F: Future>(&self, future: F) -> Instrumented<F> {
+        future.instrument(self.span.clone())
+    }
+
+    /// The tracing span of the logger
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+}
+
+/// Get a logger that tags logs with the name of the current component.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus::logger::{tracing::info, use_logger};
+///
+/// #[component]
+/// fn Counter() -> Element {
+///     let logger = use_logger();
+///     let mut count = use_signal(|| 0);
+///     logger.in_scope(|| info!(count = count(), "Rendering"));
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| logger.in_scope(|| {
+///                 count += 1;
+///                 info!("Clicked");
+///             }),
+///             "{count}"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_logger() -> ComponentLogger {
+    use_hook(|| {
+        let name = dioxus_core::component_stack()
+            .first()
+            .map(|name| name.rsplit("::").next().unwrap_or(name))
+            .unwrap_or("unknown");
+        ComponentLogger::new(name)
+    })
+}
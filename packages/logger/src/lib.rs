@@ -1,10 +1,21 @@
-use tracing::{
-    subscriber::{set_global_default, SetGlobalDefaultError},
-    Level,
-};
+use tracing::{subscriber::SetGlobalDefaultError, Level};
 
 pub use tracing;
 
+mod builder;
+mod component;
+#[cfg(not(target_arch = "wasm32"))]
+mod file;
+mod record;
+mod sink;
+
+pub use builder::LoggerBuilder;
+pub use component::{use_logger, ComponentLogger};
+pub use record::LogRecord;
+pub use sink::LogSink;
+
+pub(crate) use component::COMPONENT_SPAN;
+
 /// Attempt to initialize the subscriber if it doesn't already exist, with default settings.
 ///
 /// See [`crate::init`] for more info.
@@ -51,7 +62,8 @@ pub fn initialize_default() {
 /// Initialize `dioxus-logger` with a specified max filter.
 ///
 /// Generally it is best to initialize the logger before launching your Dioxus app.
-/// Works on Web, Desktop, Fullstack, and Liveview.
+/// Works on Web, Desktop, Mobile, Fullstack, and Liveview. Use [`LoggerBuilder`] to set the levels of modules,
+/// write logs to a file, or add your own sinks.
 ///
 /// # Example
 ///
@@ -73,38 +85,5 @@ pub fn initialize_default() {
 /// }
 /// ```
 pub fn init(level: Level) -> Result<(), SetGlobalDefaultError> {
-    /*
-    The default logger is currently set to log in fmt mode (meaning print directly to stdout)
-
-    Eventually we want to change the output mode to be `json` when running under `dx`. This would let
-    use re-format the tracing spans to be better integrated with `dx`
-    */
-
-    #[cfg(target_arch = "wasm32")]
-    {
-        use tracing_subscriber::layer::SubscriberExt;
-        use tracing_subscriber::Registry;
-
-        let layer_config = tracing_wasm::WASMLayerConfigBuilder::new()
-            .set_max_level(level)
-            .build();
-        let layer = tracing_wasm::WASMLayer::new(layer_config);
-        let reg = Registry::default().with(layer);
-
-        console_error_panic_hook::set_once();
-        set_global_default(reg)
-    }
-
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        let sub = tracing_subscriber::FmtSubscriber::builder().with_max_level(level);
-
-        if !dioxus_cli_config::is_cli_enabled() {
-            return set_global_default(sub.finish());
-        }
-
-        // todo(jon): this is a small hack to clean up logging when running under the CLI
-        // eventually we want to emit everything as json and let the CLI manage the parsing + display
-        set_global_default(sub.without_time().with_target(false).finish())
-    }
+    LoggerBuilder::new(level).init()
 }
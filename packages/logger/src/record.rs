@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use tracing::Level;
+
+/// The prefix of the lines apps running under `dx serve` print their logs with. `dx` parses these lines back
+/// into [`LogRecord`]s so it can filter them by level and module.
+const DX_LINE_PREFIX: &str = "\u{1b}[dx-log]";
+
+/// A structured log record. Every event that passes the filters of the logger is turned into a record and handed
+/// to the [`LogSink`](crate::LogSink)s of the logger.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// The level of the event
+    #[serde(with = "level")]
+    pub level: Level,
+    /// The module path the event was logged from, or the target that was passed to the macro
+    pub target: String,
+    /// The message of the event
+    pub message: String,
+    /// The key-value pairs of the event, in the order they were written
+    pub fields: Vec<(String, String)>,
+    /// The name of the component the event was logged in. This is set for events logged in the scope of a
+    /// [`ComponentLogger`](crate::ComponentLogger)
+    pub component: Option<String>,
+}
+
+impl LogRecord {
+    /// Get the value of a field of the record
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Encode the record as a single line that `dx serve` can parse with [`LogRecord::from_dx_line`]
+    pub fn to_dx_line(&self) -> String {
+        // Serializing a struct of strings can't fail
+        let json = serde_json::to_string(self).unwrap_or_default();
+        format!("{DX_LINE_PREFIX}{json}")
+    }
+
+    /// Parse a line of output that was written with [`LogRecord::to_dx_line`]. Returns `None` for any other line.
+    pub fn from_dx_line(line: &str) -> Option<Self> {
+        let json = line.trim_end().strip_prefix(DX_LINE_PREFIX)?;
+        serde_json::from_str(json).ok()
+    }
+}
+
+impl Display for LogRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.target)?;
+        if let Some(component) = &self.component {
+            write!(f, " <{component}>")?;
+        }
+        write!(f, ": {}", self.message)?;
+        for (key, value) in &self.fields {
+            write!(f, " {key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+mod level {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use tracing::Level;
+
+    pub fn serialize<S: Serializer>(level: &Level, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(level.as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Level, D::Error> {
+        let level = String::deserialize(deserializer)?;
+        level.parse().map_err(serde::de::Error::custom)
+    }
+}
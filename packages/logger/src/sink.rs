@@ -0,0 +1,179 @@
+use crate::{component::ComponentName, LogRecord, COMPONENT_SPAN};
+use std::{fmt::Debug, sync::Arc};
+use tracing::{
+    field::{Field, Visit},
+    span::Attributes,
+    Event, Id, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+/// A destination for the records of the logger. Add sinks to the logger with [`LoggerBuilder::sink`](crate::LoggerBuilder::sink).
+///
+/// Closures that take a [`LogRecord`] are sinks:
+///
+/// ```rust
+/// use dioxus_logger::{LogRecord, LoggerBuilder};
+/// use dioxus_logger::tracing::Level;
+///
+/// let logger = LoggerBuilder::new(Level::INFO).sink(|record: &LogRecord| {
+///     // Send the record to your log collector
+/// });
+/// ```
+pub trait LogSink: Send + Sync + 'static {
+    /// Write a record to the sink
+    fn log(&self, record: &LogRecord);
+}
+
+impl<F: Fn(&LogRecord) + Send + Sync + 'static> LogSink for F {
+    fn log(&self, record: &LogRecord) {
+        self(record)
+    }
+}
+
+/// A tracing layer that turns events into [`LogRecord`]s for a sink
+pub(crate) struct SinkLayer {
+    sink: Arc<dyn LogSink>,
+}
+
+impl SinkLayer {
+    pub(crate) fn new(sink: Arc<dyn LogSink>) -> Self {
+        Self { sink }
+    }
+}
+
+impl<S> Layer<S> for SinkLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != COMPONENT_SPAN {
+            return;
+        }
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        // Several layers may see the same span
+        if span.extensions().get::<ComponentName>().is_some() {
+            return;
+        }
+        let mut visitor = RecordVisitor::default();
+        attrs.record(&mut visitor);
+        if let Some(name) = visitor.field("name") {
+            span.extensions_mut()
+                .insert(ComponentName(name.to_string()));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = RecordVisitor::default();
+        event.record(&mut visitor);
+
+        // Tag the record with the innermost component the event was logged in
+        let component = ctx.event_scope(event).and_then(|scope| {
+            scope.into_iter().find_map(|span| {
+                span.extensions()
+                    .get::<ComponentName>()
+                    .map(|name| name.0.clone())
+            })
+        });
+
+        self.sink.log(&LogRecord {
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+            component,
+        });
+    }
+}
+
+#[derive(Default)]
+struct RecordVisitor {
+    message: String,
+    fields: Vec<(String, String)>,
+}
+
+impl RecordVisitor {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn push(&mut self, field: &Field, value: String) {
+        match field.name() {
+            "message" => self.message = value,
+            name => self.fields.push((name.to_string(), value)),
+        }
+    }
+}
+
+impl Visit for RecordVisitor {
+    // Strings are recorded without the quotes their debug representation adds
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.push(field, format!("{value:?}"));
+    }
+}
+
+/// Print records as lines that `dx serve` parses, so the TUI can filter them by level and module
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn dx_sink(record: &LogRecord) {
+    println!("{}", record.to_dx_line());
+}
+
+/// Write records to the browser console with the console method of their level
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn console_sink(record: &LogRecord) {
+    use tracing::Level;
+    use wasm_bindgen::JsValue;
+
+    let message = JsValue::from_str(&format!("{} {record}", record.level));
+    match record.level {
+        Level::ERROR => web_sys::console::error_1(&message),
+        Level::WARN => web_sys::console::warn_1(&message),
+        Level::INFO => web_sys::console::info_1(&message),
+        _ => web_sys::console::debug_1(&message),
+    }
+}
+
+/// Write records to logcat with the tag of their module
+#[cfg(target_os = "android")]
+pub(crate) fn logcat_sink(record: &LogRecord) {
+    use std::ffi::{c_char, c_int, CString};
+    use tracing::Level;
+
+    #[link(name = "log")]
+    extern "C" {
+        fn __android_log_write(prio: c_int, tag: *const c_char, text: *const c_char) -> c_int;
+    }
+
+    // The priorities of android/log.h
+    let priority = match record.level {
+        Level::ERROR => 6,
+        Level::WARN => 5,
+        Level::INFO => 4,
+        Level::DEBUG => 3,
+        _ => 2,
+    };
+    let tag = record.target.split("::").next().unwrap_or("dioxus");
+    // Logcat shows the tag next to the message, so the target is left out
+    let mut message = match &record.component {
+        Some(component) => format!("<{component}> {}", record.message),
+        None => record.message.clone(),
+    };
+    for (key, value) in &record.fields {
+        message.push_str(&format!(" {key}={value}"));
+    }
+    let (Ok(tag), Ok(message)) = (CString::new(tag), CString::new(message.replace('\0', "")))
+    else {
+        return;
+    };
+    unsafe {
+        __android_log_write(priority, tag.as_ptr(), message.as_ptr());
+    }
+}
@@ -0,0 +1,110 @@
+use dioxus::prelude::*;
+use dioxus_logger::{
+    tracing::{self, Level},
+    use_logger, LogRecord, LoggerBuilder,
+};
+use std::sync::{Arc, Mutex};
+
+fn capture(builder: LoggerBuilder, f: impl FnOnce()) -> Vec<LogRecord> {
+    let records = Arc::new(Mutex::new(Vec::new()));
+    let sink = records.clone();
+    let subscriber = builder
+        .sink(move |record: &LogRecord| sink.lock().unwrap().push(record.clone()))
+        .build();
+    tracing::subscriber::with_default(subscriber, f);
+    let records = records.lock().unwrap().clone();
+    records
+}
+
+#[test]
+fn records_have_structured_fields() {
+    let records = capture(LoggerBuilder::new(Level::INFO), || {
+        tracing::info!(user = "ferris", attempts = 3, "Logged in");
+    });
+
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(record.level, Level::INFO);
+    assert_eq!(record.message, "Logged in");
+    assert_eq!(record.field("user"), Some("ferris"));
+    assert_eq!(record.field("attempts"), Some("3"));
+    assert_eq!(
+        record.to_string(),
+        "logger: Logged in user=ferris attempts=3"
+    );
+
+    // Records survive the round trip through the lines dx serve parses
+    assert_eq!(
+        LogRecord::from_dx_line(&record.to_dx_line()).as_ref(),
+        Some(record)
+    );
+    assert_eq!(LogRecord::from_dx_line("Logged in"), None);
+}
+
+#[test]
+fn modules_override_the_level() {
+    let records = capture(
+        LoggerBuilder::new(Level::WARN).module("logger::db", Level::DEBUG),
+        || {
+            tracing::debug!(target: "logger::db", "query");
+            tracing::debug!(target: "logger::ui", "render");
+            tracing::warn!(target: "logger::ui", "slow render");
+        },
+    );
+
+    let messages: Vec<_> = records
+        .iter()
+        .map(|record| record.message.as_str())
+        .collect();
+    assert_eq!(messages, ["query", "slow render"]);
+}
+
+#[test]
+fn use_logger_tags_logs_with_the_component() {
+    fn app() -> Element {
+        rsx! { Counter {} }
+    }
+
+    #[component]
+    fn Counter() -> Element {
+        let logger = use_logger();
+        logger.in_scope(|| tracing::info!("rendered"));
+        tracing::info!("untagged");
+        rsx! {}
+    }
+
+    let records = capture(LoggerBuilder::new(Level::INFO), || {
+        let mut dom = VirtualDom::new(app);
+        dom.rebuild_in_place();
+    });
+
+    let tagged: Vec<_> = records
+        .iter()
+        .map(|record| (record.message.as_str(), record.component.as_deref()))
+        .collect();
+    assert_eq!(tagged, [("rendered", Some("Counter")), ("untagged", None)]);
+}
+
+#[test]
+fn log_files_are_rotated() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("logs").join("app.log");
+
+    let subscriber = LoggerBuilder::new(Level::INFO)
+        .file(&path)
+        .max_file_size(200)
+        .max_files(2)
+        .build();
+    tracing::subscriber::with_default(subscriber, || {
+        for line in 0..20 {
+            tracing::info!("line {line}");
+        }
+    });
+
+    let current = std::fs::read_to_string(&path).unwrap();
+    assert!(current.contains("line 19"));
+    assert!(current.len() <= 200);
+    assert!(path.with_extension("log.1").exists());
+    assert!(path.with_extension("log.2").exists());
+    assert!(!path.with_extension("log.3").exists());
+}
@@ -1,4 +1,4 @@
-use crate::AssetOptions;
+use crate::{AssetOptions, ImageFormat};
 use const_serialize::{ConstStr, SerializeConst};
 use std::path::PathBuf;
 
@@ -25,6 +25,8 @@ pub struct BundledAsset {
     bundled_path: ConstStr,
     /// The options for the asset
     options: AssetOptions,
+    /// The hex sha256 of the contents of a remote asset. Empty for local assets
+    sha256: ConstStr,
 }
 
 impl BundledAsset {
@@ -40,6 +42,17 @@ impl BundledAsset {
             absolute_source_path: ConstStr::new(absolute_source_path),
             bundled_path: ConstStr::new(bundled_path),
             options,
+            sha256: ConstStr::new(""),
+        }
+    }
+
+    #[doc(hidden)]
+    /// This should only be called from the macro
+    /// Pin a remote asset to the sha256 of its contents
+    pub const fn with_sha256(self, sha256: &'static str) -> Self {
+        Self {
+            sha256: ConstStr::new(sha256),
+            ..self
         }
     }
 
@@ -64,16 +77,70 @@ impl BundledAsset {
         self.bundled_path.as_str()
     }
 
-    /// Get the absolute path of the asset source. This path will not be available when the asset is bundled
+    /// Get the hash of the contents and options of the asset that is part of its bundled name. The hash changes
+    /// whenever the bundled asset changes, so servers can cache the asset forever.
+    pub fn content_hash(&self) -> Option<&str> {
+        bundled_path_hash(self.bundled_path())
+    }
+
+    /// Get the bundled paths of the responsive variants the CLI generates next to an image asset
+    pub fn responsive_variant_paths(&self) -> Vec<String> {
+        let AssetOptions::Image(options) = self.options else {
+            return Vec::new();
+        };
+        options
+            .responsive_widths()
+            .iter()
+            .flat_map(|width| {
+                options.responsive_formats().into_iter().map(|format| {
+                    crate::responsive_variant_path(self.bundled_path(), *width, format)
+                })
+            })
+            .collect()
+    }
+
+    /// Get the absolute path of the asset source. This path will not be available when the asset is bundled.
+    /// Remote assets use their url instead
     pub fn absolute_source_path(&self) -> &str {
         self.absolute_source_path.as_str()
     }
+
+    /// Get the hex sha256 a remote asset must match after it is downloaded. Local assets don't have a sha256
+    pub fn sha256(&self) -> Option<&str> {
+        match self.sha256.as_str() {
+            "" => None,
+            sha256 => Some(sha256),
+        }
+    }
     /// Get the options for the asset
     pub const fn options(&self) -> &AssetOptions {
         &self.options
     }
 }
 
+/// Get the hash from the name of a bundled asset. Bundled assets are named `{name}-{hash}.{extension}` where the hash
+/// is 16 hex digits.
+///
+/// ```rust
+/// # use manganis_core::bundled_path_hash;
+/// assert_eq!(bundled_path_hash("style-603a88fe296462a3.css"), Some("603a88fe296462a3"));
+/// assert_eq!(bundled_path_hash("hero-603a88fe296462a3-640w.avif"), Some("603a88fe296462a3"));
+/// assert_eq!(bundled_path_hash("index.html"), None);
+/// ```
+pub fn bundled_path_hash(bundled_path: &str) -> Option<&str> {
+    let name = bundled_path.rsplit(['/', '\\']).next()?;
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    let (rest, mut hash) = stem.rsplit_once('-')?;
+    // Responsive variants of images add their width after the hash
+    if hash
+        .strip_suffix('w')
+        .is_some_and(|width| !width.is_empty() && width.bytes().all(|byte| byte.is_ascii_digit()))
+    {
+        hash = rest.rsplit_once('-')?.1;
+    }
+    (hash.len() == 16 && hash.bytes().all(|byte| byte.is_ascii_hexdigit())).then_some(hash)
+}
+
 /// A bundled asset with some options. The asset can be used in rsx! to reference the asset.
 /// It should not be read directly with [`std::fs::read`] because the path needs to be resolved
 /// relative to the bundle
@@ -124,6 +191,32 @@ impl Asset {
             return PathBuf::from(self.bundled.absolute_source_path.as_str());
         }
 
+        Self::resolve_bundled(self.bundled.bundled_path.as_str())
+    }
+
+    /// Return the path to the responsive variant of an image asset with a width and format. Variants are only
+    /// generated when the app is bundled, so this returns `None` in development or if the image isn't responsive.
+    pub fn resolve_responsive_variant(&self, width: u32, format: ImageFormat) -> Option<PathBuf> {
+        let AssetOptions::Image(options) = self.bundled.options else {
+            return None;
+        };
+        if !options.responsive_widths().contains(&width) {
+            return None;
+        }
+
+        #[cfg(feature = "dioxus")]
+        if !dioxus_core_types::is_bundled_app() {
+            return None;
+        }
+
+        Some(Self::resolve_bundled(&crate::responsive_variant_path(
+            self.bundled.bundled_path.as_str(),
+            width,
+            format,
+        )))
+    }
+
+    fn resolve_bundled(bundled_path: &str) -> PathBuf {
         #[cfg(feature = "dioxus")]
         let bundle_root = {
             let base_path = dioxus_cli_config::base_path();
@@ -140,9 +233,7 @@ impl Asset {
         let bundle_root = PathBuf::from("/assets/");
 
         // Otherwise presumably we're bundled and we can use the bundled path
-        bundle_root.join(PathBuf::from(
-            self.bundled.bundled_path.as_str().trim_start_matches('/'),
-        ))
+        bundle_root.join(PathBuf::from(bundled_path.trim_start_matches('/')))
     }
 }
 
@@ -0,0 +1,319 @@
+use const_serialize::SerializeConst;
+
+use crate::AssetOptions;
+
+/// The bundled path every css module is written to. Bundlers combine all css modules into this one stylesheet so
+/// the app only loads a single file no matter how many components declare styles.
+pub const CSS_MODULES_BUNDLED_PATH: &str = "css-modules.css";
+
+/// Options for a css module asset. Css modules are created with the `#[css_module]` attribute.
+#[derive(
+    Debug,
+    PartialEq,
+    PartialOrd,
+    Clone,
+    Copy,
+    Hash,
+    SerializeConst,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct CssModuleAssetOptions {
+    minify: bool,
+    scope: u32,
+}
+
+impl Default for CssModuleAssetOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CssModuleAssetOptions {
+    /// Create a new css module asset using the builder
+    pub const fn new() -> Self {
+        Self {
+            minify: true,
+            scope: 0,
+        }
+    }
+
+    /// Sets whether the css should be minified (default: true)
+    ///
+    /// ```rust
+    /// # use manganis::{css_module, CssModuleAssetOptions};
+    /// #[css_module("/assets/style.css", CssModuleAssetOptions::new().with_minify(false))]
+    /// struct Styles;
+    /// ```
+    #[allow(unused)]
+    pub const fn with_minify(self, minify: bool) -> Self {
+        Self { minify, ..self }
+    }
+
+    /// Check if the asset is minified
+    pub const fn minified(&self) -> bool {
+        self.minify
+    }
+
+    #[doc(hidden)]
+    /// This should only be called from the macro
+    /// Set the scope the classes of the module are renamed with
+    pub const fn with_scope(self, scope: u32) -> Self {
+        Self { scope, ..self }
+    }
+
+    /// Get the scope the classes of the module are renamed with
+    pub const fn scope(&self) -> u32 {
+        self.scope
+    }
+
+    /// Convert the options into options for a generic asset
+    pub const fn into_asset_options(self) -> AssetOptions {
+        AssetOptions::CssModule(self)
+    }
+}
+
+/// A css module with every class renamed to a scoped name
+#[derive(Debug, PartialEq, Clone)]
+pub struct ScopedCssModule {
+    /// The css with the scoped class names
+    pub css: String,
+    /// The original names of the classes in the order they first appear
+    pub classes: Vec<String>,
+}
+
+/// Get the name a class in a css module is renamed to
+pub fn scoped_class_name(class: &str, scope: u32) -> String {
+    format!("{class}-{scope:08x}")
+}
+
+/// Rename every class selector in a css module with the scope. The macro uses the class names to generate constants,
+/// and bundlers use the css to write the stylesheet, so both always agree on the scoped names.
+///
+/// Classes are only renamed in selectors. The values of declarations, strings, comments, attribute selectors and
+/// the preludes of at-rules are left alone.
+pub fn scope_css_module(css: &str, scope: u32) -> ScopedCssModule {
+    let chars: Vec<char> = css.chars().collect();
+    let mut module = ScopedCssModule {
+        css: String::with_capacity(css.len()),
+        classes: Vec::new(),
+    };
+
+    let mut start = 0;
+    let mut index = 0;
+    let mut depth = 0;
+    while index < chars.len() {
+        match chars[index] {
+            '/' if chars.get(index + 1) == Some(&'*') => index = skip_comment(&chars, index),
+            '"' | '\'' => index = skip_string(&chars, index),
+            '\\' => index += 2,
+            '(' | '[' => {
+                depth += 1;
+                index += 1;
+            }
+            ')' | ']' => {
+                depth -= 1;
+                index += 1;
+            }
+            // Everything before a `{` is a selector or the prelude of an at-rule
+            '{' if depth <= 0 => {
+                let segment = &chars[start..index];
+                if is_at_rule(segment) {
+                    module.css.extend(segment);
+                } else {
+                    scope_selector(segment, scope, &mut module);
+                }
+                module.css.push('{');
+                index += 1;
+                start = index;
+            }
+            // Everything before a `;` or `}` is a declaration or an at-rule statement
+            ';' | '}' if depth <= 0 => {
+                module.css.extend(&chars[start..=index]);
+                index += 1;
+                start = index;
+            }
+            _ => index += 1,
+        }
+    }
+    module.css.extend(&chars[start.min(chars.len())..]);
+
+    module
+}
+
+fn is_at_rule(segment: &[char]) -> bool {
+    let mut index = 0;
+    while index < segment.len() {
+        match segment[index] {
+            '/' if segment.get(index + 1) == Some(&'*') => index = skip_comment(segment, index),
+            c if c.is_whitespace() => index += 1,
+            c => return c == '@',
+        }
+    }
+    false
+}
+
+fn scope_selector(selector: &[char], scope: u32, module: &mut ScopedCssModule) {
+    let mut index = 0;
+    while index < selector.len() {
+        match selector[index] {
+            '/' if selector.get(index + 1) == Some(&'*') => {
+                let end = skip_comment(selector, index);
+                module.css.extend(&selector[index..end]);
+                index = end;
+            }
+            '"' | '\'' => {
+                let end = skip_string(selector, index);
+                module.css.extend(&selector[index..end]);
+                index = end;
+            }
+            // Attribute selectors can contain anything
+            '[' => {
+                let mut end = index + 1;
+                while end < selector.len() && selector[end] != ']' {
+                    end = match selector[end] {
+                        '"' | '\'' => skip_string(selector, end),
+                        '\\' => end + 2,
+                        _ => end + 1,
+                    };
+                }
+                let end = (end + 1).min(selector.len());
+                module.css.extend(&selector[index..end]);
+                index = end;
+            }
+            '.' if starts_identifier(&selector[index + 1..]) => {
+                let end = identifier_end(selector, index + 1);
+                let class = unescape(&selector[index + 1..end]);
+                module.css.push('.');
+                module.css.extend(&selector[index + 1..end]);
+                module.css.push_str(&format!("-{scope:08x}"));
+                if !module.classes.contains(&class) {
+                    module.classes.push(class);
+                }
+                index = end;
+            }
+            c => {
+                module.css.push(c);
+                index += 1;
+            }
+        }
+    }
+}
+
+/// Find the end of the comment that starts at the index
+fn skip_comment(chars: &[char], index: usize) -> usize {
+    let mut end = index + 2;
+    while end < chars.len() {
+        if chars[end] == '*' && chars.get(end + 1) == Some(&'/') {
+            return end + 2;
+        }
+        end += 1;
+    }
+    chars.len()
+}
+
+/// Find the end of the string that starts at the index
+fn skip_string(chars: &[char], index: usize) -> usize {
+    let quote = chars[index];
+    let mut end = index + 1;
+    while end < chars.len() {
+        match chars[end] {
+            '\\' => end += 2,
+            c if c == quote => return end + 1,
+            _ => end += 1,
+        }
+    }
+    chars.len()
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+fn starts_identifier(chars: &[char]) -> bool {
+    let starts_name = |c: Option<&char>| match c {
+        Some(&c) => c.is_ascii_alphabetic() || c == '_' || c == '\\' || !c.is_ascii(),
+        None => false,
+    };
+    match chars.first() {
+        Some('-') => starts_name(chars.get(1)) || chars.get(1) == Some(&'-'),
+        c => starts_name(c),
+    }
+}
+
+fn identifier_end(chars: &[char], mut index: usize) -> usize {
+    while index < chars.len() {
+        match chars[index] {
+            '\\' => index += 2,
+            c if is_identifier_char(c) => index += 1,
+            _ => break,
+        }
+    }
+    index.min(chars.len())
+}
+
+/// Resolve the escapes in an identifier like `md\:flex` or `\31 0`
+fn unescape(identifier: &[char]) -> String {
+    let mut unescaped = String::new();
+    let mut index = 0;
+    while index < identifier.len() {
+        if identifier[index] != '\\' {
+            unescaped.push(identifier[index]);
+            index += 1;
+            continue;
+        }
+        index += 1;
+        let hex: String = identifier[index..]
+            .iter()
+            .take(6)
+            .take_while(|c| c.is_ascii_hexdigit())
+            .collect();
+        if hex.is_empty() {
+            unescaped.extend(identifier.get(index));
+            index += 1;
+        } else {
+            index += hex.len();
+            // A single space after a hex escape ends the escape
+            if identifier.get(index).is_some_and(|c| c.is_whitespace()) {
+                index += 1;
+            }
+            unescaped.extend(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32));
+        }
+    }
+    unescaped
+}
+
+#[test]
+fn scopes_class_selectors() {
+    let css = r#"
+/* .comment { } */
+.button, a.link:hover > .icon { color: red; background: url(image.png); }
+.button.primary:not(.disabled) { font-size: 1.5em; }
+[data-kind=".button"] { content: ".fake"; }
+@media (min-width: 600px) {
+    .md\:flex { display: flex; }
+}
+.card { &.active { margin: 0.5rem; } }
+@keyframes spin { 12.5% { opacity: 0.5; } }
+"#;
+    let module = scope_css_module(css, 0xab);
+    assert_eq!(
+        module.classes,
+        ["button", "link", "icon", "primary", "disabled", "md:flex", "card", "active"]
+    );
+    assert_eq!(
+        module.css,
+        r#"
+/* .comment { } */
+.button-000000ab, a.link-000000ab:hover > .icon-000000ab { color: red; background: url(image.png); }
+.button-000000ab.primary-000000ab:not(.disabled-000000ab) { font-size: 1.5em; }
+[data-kind=".button"] { content: ".fake"; }
+@media (min-width: 600px) {
+    .md\:flex-000000ab { display: flex; }
+}
+.card-000000ab { &.active-000000ab { margin: 0.5rem; } }
+@keyframes spin { 12.5% { opacity: 0.5; } }
+"#
+    );
+    assert_eq!(scoped_class_name("md:flex", 0xab), "md:flex-000000ab");
+}
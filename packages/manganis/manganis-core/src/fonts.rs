@@ -0,0 +1,237 @@
+use const_serialize::SerializeConst;
+
+use crate::AssetOptions;
+
+/// The maximum number of unicode ranges a font can be subset to
+pub const MAX_UNICODE_RANGES: usize = 16;
+
+/// An inclusive range of unicode code points
+#[derive(
+    Debug,
+    PartialEq,
+    PartialOrd,
+    Clone,
+    Copy,
+    Hash,
+    SerializeConst,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct UnicodeRange {
+    start: u32,
+    end: u32,
+}
+
+impl UnicodeRange {
+    /// Basic latin and the latin-1 supplement, which covers most western european languages
+    pub const LATIN_1: Self = Self::new(0x0000, 0x00FF);
+    /// Latin extended-a and extended-b, which covers most other languages written in the latin alphabet
+    pub const LATIN_EXTENDED: Self = Self::new(0x0100, 0x024F);
+    /// General punctuation like dashes, quotes and ellipses
+    pub const PUNCTUATION: Self = Self::new(0x2000, 0x206F);
+    /// Greek and coptic
+    pub const GREEK: Self = Self::new(0x0370, 0x03FF);
+    /// Cyrillic
+    pub const CYRILLIC: Self = Self::new(0x0400, 0x04FF);
+
+    /// Create a range from the first to the last code point, inclusive
+    pub const fn new(start: u32, end: u32) -> Self {
+        if start > end {
+            panic!("The start of a unicode range must not be after its end");
+        }
+        Self { start, end }
+    }
+
+    /// Create a range with a single code point
+    pub const fn single(code_point: u32) -> Self {
+        Self::new(code_point, code_point)
+    }
+
+    /// The first code point in the range
+    pub const fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// The last code point in the range
+    pub const fn end(&self) -> u32 {
+        self.end
+    }
+}
+
+impl std::fmt::Display for UnicodeRange {
+    /// Format the range like the css `unicode-range` descriptor: `U+0000-00FF`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.start == self.end {
+            write!(f, "U+{:04X}", self.start)
+        } else {
+            write!(f, "U+{:04X}-{:04X}", self.start, self.end)
+        }
+    }
+}
+
+/// How a font is displayed while it loads. You can read more about the tradeoffs [here](https://developer.mozilla.org/en-US/docs/Web/CSS/@font-face/font-display)
+#[derive(
+    Debug,
+    PartialEq,
+    PartialOrd,
+    Clone,
+    Copy,
+    Hash,
+    SerializeConst,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[repr(u8)]
+pub enum FontDisplay {
+    /// Let the browser decide
+    Auto,
+    /// Hide the text for a short time, then fall back until the font loads
+    Block,
+    /// Show the fallback font until the font loads
+    Swap,
+    /// Show the fallback font and only swap if the font loads very quickly
+    Fallback,
+    /// Only use the font if it is already cached or loads almost immediately
+    Optional,
+}
+
+impl FontDisplay {
+    /// Get the value of the css `font-display` descriptor
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            FontDisplay::Auto => "auto",
+            FontDisplay::Block => "block",
+            FontDisplay::Swap => "swap",
+            FontDisplay::Fallback => "fallback",
+            FontDisplay::Optional => "optional",
+        }
+    }
+}
+
+/// Options for a font asset. Fonts are converted to woff2 and optionally subset to the characters the app uses
+#[derive(
+    Debug,
+    PartialEq,
+    PartialOrd,
+    Clone,
+    Copy,
+    Hash,
+    SerializeConst,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct FontAssetOptions {
+    weight: u16,
+    italic: bool,
+    display: FontDisplay,
+    preload: bool,
+    unicode_ranges: [UnicodeRange; MAX_UNICODE_RANGES],
+    unicode_ranges_len: u8,
+}
+
+impl Default for FontAssetOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FontAssetOptions {
+    /// Create a new font asset options
+    pub const fn new() -> Self {
+        Self {
+            weight: 0,
+            italic: false,
+            display: FontDisplay::Swap,
+            preload: true,
+            unicode_ranges: [UnicodeRange::new(0, 0); MAX_UNICODE_RANGES],
+            unicode_ranges_len: 0,
+        }
+    }
+
+    /// Set the weight of the font, like `400` for regular or `700` for bold
+    pub const fn with_weight(self, weight: u16) -> Self {
+        Self { weight, ..self }
+    }
+
+    /// Get the weight of the font. Zero if the weight isn't set
+    pub const fn weight(&self) -> u16 {
+        self.weight
+    }
+
+    /// Mark the font as the italic style of the family
+    pub const fn with_italic(self, italic: bool) -> Self {
+        Self { italic, ..self }
+    }
+
+    /// Check if the font is italic
+    pub const fn italic(&self) -> bool {
+        self.italic
+    }
+
+    /// Set how the font is displayed while it loads (default: [`FontDisplay::Swap`])
+    pub const fn with_display(self, display: FontDisplay) -> Self {
+        Self { display, ..self }
+    }
+
+    /// Get how the font is displayed while it loads
+    pub const fn display(&self) -> FontDisplay {
+        self.display
+    }
+
+    /// Sets whether the font should be preloaded (default: true)
+    ///
+    /// Preloading the font starts downloading it before the css that uses it is parsed, which avoids a flash of
+    /// the fallback font
+    ///
+    /// ```rust, ignore
+    /// # use manganis::{asset, Asset, FontAssetOptions};
+    /// const _: Asset = asset!("/assets/font.ttf", FontAssetOptions::new().with_preload(false));
+    /// ```
+    pub const fn with_preload(self, preload: bool) -> Self {
+        Self { preload, ..self }
+    }
+
+    /// Check if the asset is preloaded
+    pub const fn preloaded(&self) -> bool {
+        self.preload
+    }
+
+    /// Subset the font to a range of unicode code points. Every glyph outside of the ranges is removed from the font,
+    /// which can make fonts with many scripts much smaller. Fonts keep every glyph if no ranges are set
+    ///
+    /// ```rust, ignore
+    /// # use manganis::{asset, Asset, FontAssetOptions, UnicodeRange};
+    /// const _: Asset = asset!(
+    ///     "/assets/font.ttf",
+    ///     FontAssetOptions::new()
+    ///         .with_unicode_range(UnicodeRange::LATIN_1)
+    ///         .with_unicode_range(UnicodeRange::PUNCTUATION)
+    /// );
+    /// ```
+    pub const fn with_unicode_range(self, range: UnicodeRange) -> Self {
+        if self.unicode_ranges_len as usize >= MAX_UNICODE_RANGES {
+            panic!("Fonts can be subset to at most 16 unicode ranges");
+        }
+        let mut unicode_ranges = self.unicode_ranges;
+        unicode_ranges[self.unicode_ranges_len as usize] = range;
+        Self {
+            unicode_ranges,
+            unicode_ranges_len: self.unicode_ranges_len + 1,
+            ..self
+        }
+    }
+
+    /// Get the unicode ranges the font is subset to. Empty if the font keeps every glyph
+    pub fn unicode_ranges(&self) -> &[UnicodeRange] {
+        &self.unicode_ranges[..self.unicode_ranges_len as usize]
+    }
+
+    /// Convert the options into options for a generic asset
+    pub const fn into_asset_options(self) -> AssetOptions {
+        AssetOptions::Font(self)
+    }
+
+    pub(crate) const fn extension(&self) -> Option<&'static str> {
+        Some("woff2")
+    }
+}
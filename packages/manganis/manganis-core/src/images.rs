@@ -28,6 +28,36 @@ pub enum ImageFormat {
     Unknown,
 }
 
+impl ImageFormat {
+    /// Get the file extension of the format. Unknown images keep the extension of their source
+    pub const fn extension(&self) -> Option<&'static str> {
+        match self {
+            ImageFormat::Png => Some("png"),
+            ImageFormat::Jpg => Some("jpg"),
+            ImageFormat::Webp => Some("webp"),
+            ImageFormat::Avif => Some("avif"),
+            ImageFormat::Unknown => None,
+        }
+    }
+
+    /// Get the mime type of the format
+    pub const fn mime_type(&self) -> Option<&'static str> {
+        match self {
+            ImageFormat::Png => Some("image/png"),
+            ImageFormat::Jpg => Some("image/jpeg"),
+            ImageFormat::Webp => Some("image/webp"),
+            ImageFormat::Avif => Some("image/avif"),
+            ImageFormat::Unknown => None,
+        }
+    }
+}
+
+/// The maximum number of widths a responsive image can be generated at
+pub const MAX_RESPONSIVE_WIDTHS: usize = 8;
+
+/// The widths responsive images are generated at by default
+pub const DEFAULT_RESPONSIVE_WIDTHS: [u32; 5] = [320, 640, 960, 1280, 1920];
+
 /// The size of an image asset
 #[derive(
     Debug,
@@ -70,6 +100,9 @@ pub struct ImageAssetOptions {
     low_quality_preview: bool,
     size: ImageSize,
     preload: bool,
+    // Unused slots are zero
+    widths: [u32; MAX_RESPONSIVE_WIDTHS],
+    modern_formats: bool,
 }
 
 impl Default for ImageAssetOptions {
@@ -86,6 +119,8 @@ impl ImageAssetOptions {
             low_quality_preview: false,
             size: ImageSize::Automatic,
             preload: false,
+            widths: [0; MAX_RESPONSIVE_WIDTHS],
+            modern_formats: false,
         }
     }
 
@@ -190,6 +225,90 @@ impl ImageAssetOptions {
         self.size
     }
 
+    /// Generate responsive variants of the image at the [`DEFAULT_RESPONSIVE_WIDTHS`] in avif and webp along with the
+    /// format of the image
+    ///
+    /// The `Image` component picks the variants up automatically and lets the browser download the smallest image
+    /// that fits the layout
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, ImageAssetOptions};
+    /// const _: Asset = asset!("/assets/image.png", ImageAssetOptions::new().with_responsive(true));
+    /// ```
+    pub const fn with_responsive(self, responsive: bool) -> Self {
+        if responsive {
+            self.with_widths(&DEFAULT_RESPONSIVE_WIDTHS)
+                .with_modern_formats(true)
+        } else {
+            Self {
+                widths: [0; MAX_RESPONSIVE_WIDTHS],
+                modern_formats: false,
+                ..self
+            }
+        }
+    }
+
+    /// Set the widths in pixels the responsive variants of the image are generated at. Images are never scaled up, so
+    /// widths larger than the source image reuse its original size.
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, ImageAssetOptions};
+    /// const _: Asset = asset!("/assets/image.png", ImageAssetOptions::new().with_widths(&[480, 960]));
+    /// ```
+    pub const fn with_widths(self, widths: &[u32]) -> Self {
+        if widths.len() > MAX_RESPONSIVE_WIDTHS {
+            panic!("Responsive images can have at most 8 widths");
+        }
+        let mut all_widths = [0; MAX_RESPONSIVE_WIDTHS];
+        let mut i = 0;
+        while i < widths.len() {
+            if widths[i] == 0 {
+                panic!("The widths of responsive images must be larger than zero");
+            }
+            all_widths[i] = widths[i];
+            i += 1;
+        }
+        Self {
+            widths: all_widths,
+            ..self
+        }
+    }
+
+    /// Also generate avif and webp versions of the responsive variants of the image
+    pub const fn with_modern_formats(self, modern_formats: bool) -> Self {
+        Self {
+            modern_formats,
+            ..self
+        }
+    }
+
+    /// Get the widths the responsive variants of the image are generated at
+    pub fn responsive_widths(&self) -> &[u32] {
+        let len = self
+            .widths
+            .iter()
+            .position(|width| *width == 0)
+            .unwrap_or(MAX_RESPONSIVE_WIDTHS);
+        &self.widths[..len]
+    }
+
+    /// Check if the responsive variants of the image are also generated in avif and webp
+    pub const fn modern_formats(&self) -> bool {
+        self.modern_formats
+    }
+
+    /// Get the formats the responsive variants of the image are generated in, from the smallest to the largest. The
+    /// last format is always the format of the bundled image.
+    pub fn responsive_formats(&self) -> Vec<ImageFormat> {
+        let mut formats = Vec::new();
+        if self.modern_formats {
+            formats.extend([ImageFormat::Avif, ImageFormat::Webp]);
+        }
+        formats.retain(|format| *format != self.ty);
+        formats.push(self.ty);
+        formats
+    }
+
     // LQIP is currently disabled until we have the CLI set up to inject the low quality image preview after the crate is built through the linker
     // /// Make the image use a low quality preview
     // ///
@@ -213,12 +332,36 @@ impl ImageAssetOptions {
     }
 
     pub(crate) const fn extension(&self) -> Option<&'static str> {
-        match self.ty {
-            ImageFormat::Png => Some("png"),
-            ImageFormat::Jpg => Some("jpg"),
-            ImageFormat::Webp => Some("webp"),
-            ImageFormat::Avif => Some("avif"),
-            ImageFormat::Unknown => None,
-        }
+        self.ty.extension()
+    }
+}
+
+/// Get the bundled path of the responsive variant of an image with a width and format. Variants are named
+/// `{name}-{hash}-{width}w.{extension}` and live next to the bundled image.
+///
+/// ```rust
+/// # use manganis_core::{responsive_variant_path, ImageFormat};
+/// assert_eq!(
+///     responsive_variant_path("hero-603a88fe296462a3.png", 640, ImageFormat::Avif),
+///     "hero-603a88fe296462a3-640w.avif"
+/// );
+/// ```
+pub fn responsive_variant_path(bundled_path: &str, width: u32, format: ImageFormat) -> String {
+    let (directory, name) = match bundled_path.rsplit_once('/') {
+        Some((directory, name)) => (Some(directory), name),
+        None => (None, bundled_path),
+    };
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) => (stem, Some(extension)),
+        None => (name, None),
+    };
+    let mut variant = match directory {
+        Some(directory) => format!("{directory}/{stem}-{width}w"),
+        None => format!("{stem}-{width}w"),
+    };
+    if let Some(extension) = format.extension().or(extension) {
+        variant.push('.');
+        variant.push_str(extension);
     }
+    variant
 }
@@ -10,9 +10,15 @@ pub use options::*;
 mod css;
 pub use css::*;
 
+mod css_module;
+pub use css_module::*;
+
 mod js;
 pub use js::*;
 
+mod fonts;
+pub use fonts::*;
+
 mod asset;
 pub use asset::*;
 
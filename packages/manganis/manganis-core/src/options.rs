@@ -1,6 +1,9 @@
 use const_serialize::SerializeConst;
 
-use crate::{CssAssetOptions, FolderAssetOptions, ImageAssetOptions, JsAssetOptions};
+use crate::{
+    CssAssetOptions, CssModuleAssetOptions, FolderAssetOptions, FontAssetOptions,
+    ImageAssetOptions, JsAssetOptions,
+};
 
 /// Settings for a generic asset
 #[derive(
@@ -27,6 +30,10 @@ pub enum AssetOptions {
     Js(JsAssetOptions),
     /// An unknown asset
     Unknown,
+    /// A css module with scoped class names
+    CssModule(CssModuleAssetOptions),
+    /// A font asset
+    Font(FontAssetOptions),
 }
 
 impl AssetOptions {
@@ -36,6 +43,8 @@ impl AssetOptions {
             AssetOptions::Image(image) => image.extension(),
             AssetOptions::Css(_) => Some("css"),
             AssetOptions::Js(_) => Some("js"),
+            AssetOptions::CssModule(_) => Some("css"),
+            AssetOptions::Font(font) => font.extension(),
             AssetOptions::Folder(_) => None,
             AssetOptions::Unknown => None,
         }
@@ -93,6 +93,17 @@ fn hash_file_contents(file_path: &Path) -> Result<u64, AssetParseError> {
     Ok(hash.finish())
 }
 
+/// Check if the source of an asset is a url the CLI downloads at build time instead of a file in the crate
+fn is_remote(raw: &str) -> bool {
+    raw.starts_with("https://") || raw.starts_with("http://")
+}
+
+/// Get the name of the file at a url without the query or fragment
+fn remote_file_name(url: &str) -> &str {
+    let end = url.find(['?', '#']).unwrap_or(url.len());
+    url[..end].trim_end_matches('/')
+}
+
 #[derive(Debug)]
 pub(crate) enum AssetParseError {
     AssetDoesntExist { path: PathBuf },
@@ -124,13 +135,16 @@ impl std::fmt::Display for AssetParseError {
 
 pub struct AssetParser {
     /// The span of the source string
-    path_span: proc_macro2::Span,
+    pub(crate) path_span: proc_macro2::Span,
 
     /// The asset itself
-    asset: Result<PathBuf, AssetParseError>,
+    pub(crate) asset: Result<PathBuf, AssetParseError>,
+
+    /// The sha256 of the contents of a remote asset
+    pub(crate) sha256: Option<String>,
 
     /// The source of the trailing options
-    options: TokenStream2,
+    pub(crate) options: TokenStream2,
 }
 
 impl Parse for AssetParser {
@@ -156,13 +170,51 @@ impl Parse for AssetParser {
         // And then parse the options
         let src = input.parse::<LitStr>()?;
         let path_span = src.span();
-        let asset = resolve_path(&src.value());
         let _comma = input.parse::<Token![,]>();
+
+        // Remote assets are pinned to the sha256 of their contents: `asset!("https://...", sha256 = "...")`
+        let mut sha256 = None;
+        if input.peek(syn::Ident) && input.peek2(Token![=]) {
+            let key = input.parse::<syn::Ident>()?;
+            if key != "sha256" {
+                return Err(syn::Error::new(key.span(), "expected `sha256`"));
+            }
+            input.parse::<Token![=]>()?;
+            let hash = input.parse::<LitStr>()?;
+            let value = hash.value().to_lowercase();
+            if value.len() != 64 || !value.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return Err(syn::Error::new(
+                    hash.span(),
+                    "expected the sha256 of the file as 64 hex characters",
+                ));
+            }
+            sha256 = Some(value);
+            let _comma = input.parse::<Token![,]>();
+        }
+
+        let asset = if is_remote(&src.value()) {
+            if sha256.is_none() {
+                return Err(syn::Error::new(
+                    path_span,
+                    "remote assets must be pinned to the sha256 of their contents: `asset!(\"https://...\", sha256 = \"...\")`",
+                ));
+            }
+            Ok(PathBuf::from(src.value()))
+        } else {
+            if sha256.is_some() {
+                return Err(syn::Error::new(
+                    path_span,
+                    "only remote assets can be pinned to a sha256",
+                ));
+            }
+            resolve_path(&src.value())
+        };
         let options = input.parse()?;
 
         Ok(Self {
             path_span,
             asset,
+            sha256,
             options,
         })
     }
@@ -189,7 +241,16 @@ impl ToTokens for AssetParser {
         let mut asset_str = proc_macro2::Literal::string(&asset_str);
         asset_str.set_span(self.path_span);
 
-        let hash = match hash_file_contents(asset) {
+        // Remote assets are pinned to their contents, so we can hash the sha256 instead of downloading them
+        let hash = match &self.sha256 {
+            Some(sha256) => {
+                let mut hash = std::collections::hash_map::DefaultHasher::new();
+                hash.write(sha256.as_bytes());
+                Ok(hash.finish())
+            }
+            None => hash_file_contents(asset),
+        };
+        let hash = match hash {
             Ok(hash) => hash,
             Err(err) => {
                 let err = err.to_string();
@@ -198,16 +259,27 @@ impl ToTokens for AssetParser {
             }
         };
 
+        // The bundled name comes from the file name of the url without the query
+        let name_str = match self.sha256 {
+            Some(_) => proc_macro2::Literal::string(remote_file_name(&asset.display().to_string())),
+            None => asset_str.clone(),
+        };
+
         // Generate the link section for the asset
         // The link section includes the source path and the output path of the asset
         let link_section = crate::generate_link_section(quote!(__ASSET));
 
         // generate the asset::new method to deprecate the `./assets/blah.css` syntax
-        let constructor = if asset.is_relative() {
+        let constructor = if self.sha256.is_none() && asset.is_relative() {
             quote::quote! { new_relative }
         } else {
             quote::quote! { new }
         };
+        // The CLI verifies remote assets against their sha256 after downloading them
+        let sha256 = self
+            .sha256
+            .as_ref()
+            .map(|sha256| quote::quote! { .with_sha256(#sha256) });
 
         let options = if self.options.is_empty() {
             quote! { manganis::AssetOptions::Unknown }
@@ -227,13 +299,13 @@ impl ToTokens for AssetParser {
                 const __ASSET_OPTIONS: manganis::AssetOptions = #options.into_asset_options();
                 // We calculate the bundled path from the hash and any transformations done by the options
                 // This is the final path that the asset will be written to
-                const __ASSET_BUNDLED_PATH: manganis::macro_helpers::const_serialize::ConstStr = manganis::macro_helpers::generate_unique_path(__ASSET_SOURCE_PATH, __ASSET_HASH, &__ASSET_OPTIONS);
+                const __ASSET_BUNDLED_PATH: manganis::macro_helpers::const_serialize::ConstStr = manganis::macro_helpers::generate_unique_path(#name_str, __ASSET_HASH, &__ASSET_OPTIONS);
                 // Get the reference to the string that was generated. We cannot return &'static str from
                 // generate_unique_path because it would return a reference to data generated in the function
                 const __ASSET_BUNDLED_PATH_STR: &'static str = __ASSET_BUNDLED_PATH.as_str();
                 // Create the asset that the crate will use. This is used both in the return value and
                 // added to the linker for the bundler to copy later
-                const __ASSET: manganis::BundledAsset = manganis::BundledAsset::#constructor(__ASSET_SOURCE_PATH, __ASSET_BUNDLED_PATH_STR, __ASSET_OPTIONS);
+                const __ASSET: manganis::BundledAsset = manganis::BundledAsset::#constructor(__ASSET_SOURCE_PATH, __ASSET_BUNDLED_PATH_STR, __ASSET_OPTIONS)#sha256;
 
                 #link_section
 
@@ -242,3 +314,57 @@ impl ToTokens for AssetParser {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://cdn.example.com/lib.css";
+    const SHA256: &str = "6C6E0CA29CCB4B5CBA8C4CD2C8EA4B1DD2A0B0AB0AB48AB4EE6B7F1A0D1E6F2C";
+
+    fn parse_error(input: &str) -> String {
+        match syn::parse_str::<AssetParser>(input) {
+            Ok(_) => panic!("expected {input} to fail to parse"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn remote_assets_are_pinned_to_their_sha256() {
+        let asset =
+            syn::parse_str::<AssetParser>(&format!(r#""{URL}", sha256 = "{SHA256}""#)).unwrap();
+        let sha256 = SHA256.to_lowercase();
+        assert_eq!(asset.sha256.as_deref(), Some(sha256.as_str()));
+
+        let tokens = asset.into_token_stream().to_string();
+        assert!(
+            tokens.contains(&format!(r#". with_sha256 ("{sha256}")"#)),
+            "{tokens}"
+        );
+    }
+
+    #[test]
+    fn malformed_hashes_are_compile_errors() {
+        let short = &SHA256[..63];
+        let not_hex = SHA256.replace('C', "G");
+        for sha256 in ["", short, &not_hex, &format!("{SHA256}0")] {
+            let error = parse_error(&format!(r#""{URL}", sha256 = "{sha256}""#));
+            assert_eq!(
+                error,
+                "expected the sha256 of the file as 64 hex characters"
+            );
+        }
+
+        let error = parse_error(&format!(r#""{URL}", sha512 = "{SHA256}""#));
+        assert_eq!(error, "expected `sha256`");
+    }
+
+    #[test]
+    fn remote_assets_require_a_sha256() {
+        let error = parse_error(&format!(r#""{URL}""#));
+        assert!(error.starts_with("remote assets must be pinned"), "{error}");
+
+        let error = parse_error(&format!(r#""/assets/style.css", sha256 = "{SHA256}""#));
+        assert_eq!(error, "only remote assets can be pinned to a sha256");
+    }
+}
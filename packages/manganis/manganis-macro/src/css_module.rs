@@ -0,0 +1,132 @@
+use crate::asset::AssetParser;
+use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+use syn::{Ident, ItemStruct};
+
+pub struct CssModuleParser {
+    /// The stylesheet and its options
+    pub(crate) asset: AssetParser,
+
+    /// The struct the classes are added to
+    pub(crate) item: ItemStruct,
+}
+
+/// Get the scope the classes of a stylesheet are renamed with. The scope only depends on the crate and the path of
+/// the stylesheet, so the class names stay the same while the stylesheet is hot reloaded.
+fn scope(path: &Path) -> u32 {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let manifest_dir = dunce::canonicalize(&manifest_dir).unwrap_or(PathBuf::from(manifest_dir));
+    let relative = path.strip_prefix(&manifest_dir).unwrap_or(path);
+
+    let mut hash = std::collections::hash_map::DefaultHasher::new();
+    std::env::var("CARGO_PKG_NAME")
+        .unwrap_or_default()
+        .hash(&mut hash);
+    for component in relative.components() {
+        component.as_os_str().to_string_lossy().hash(&mut hash);
+    }
+    hash.finish() as u32
+}
+
+/// Turn a class name like `primary-button` into a constant name like `primary_button`
+fn class_ident(class: &str) -> Option<Ident> {
+    let mut name: String = class
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c,
+            false => '_',
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    if let Ok(ident) = syn::parse_str::<Ident>(&name) {
+        return Some(ident);
+    }
+    // Keywords like `type` need to be raw identifiers
+    syn::parse_str::<Ident>(&format!("r#{name}")).ok()
+}
+
+impl ToTokens for CssModuleParser {
+    // The css module macro outputs the struct with a constant for the stylesheet and each class in it:
+    // ```
+    // struct Styles;
+    // impl Styles {
+    //     pub const STYLESHEET: Asset = asset!("/assets/styles.css", CssModuleAssetOptions::new());
+    //     pub const button: &str = "button-1a2b3c4d";
+    // }
+    // ```
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let path = match self.asset.asset.as_ref() {
+            Ok(path) => path,
+            Err(err) => {
+                let err = err.to_string();
+                tokens.append_all(quote! { compile_error!(#err) });
+                return;
+            }
+        };
+        let css = match std::fs::read_to_string(path) {
+            Ok(css) => css,
+            Err(err) => {
+                let err = format!("Failed to read css module {}: {err}", path.display());
+                tokens.append_all(quote! { compile_error!(#err) });
+                return;
+            }
+        };
+
+        let scope = scope(path);
+        let module = manganis_core::scope_css_module(&css, scope);
+
+        let mut idents: Vec<Ident> = vec![format_ident!("STYLESHEET")];
+        let mut classes = Vec::new();
+        for class in &module.classes {
+            // Skip classes that would collide with another constant, like `a-b` and `a_b`
+            let Some(ident) = class_ident(class).filter(|ident| !idents.contains(ident)) else {
+                continue;
+            };
+            let scoped = manganis_core::scoped_class_name(class, scope);
+            let doc = format!("The scoped name of the `.{class}` class");
+            classes.push(quote! {
+                #[doc = #doc]
+                pub const #ident: &'static str = #scoped;
+            });
+            idents.push(ident);
+        }
+
+        let options = if self.asset.options.is_empty() {
+            quote! { manganis::CssModuleAssetOptions::new() }
+        } else {
+            self.asset.options.clone()
+        };
+        let asset = AssetParser {
+            path_span: self.asset.path_span,
+            asset: Ok(path.clone()),
+            sha256: None,
+            options: quote! { (#options).with_scope(#scope) },
+        };
+
+        let path_str = path.display().to_string();
+        let item = &self.item;
+        let ident = &item.ident;
+        let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+        tokens.extend(quote! {
+            #item
+
+            #[allow(non_upper_case_globals)]
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// The stylesheet the scoped classes are defined in. Render it with `document::Stylesheet` to
+                /// load the classes.
+                pub const STYLESHEET: manganis::Asset = #asset;
+
+                #(#classes)*
+            }
+
+            // Rebuild the crate when the stylesheet changes so the classes stay in sync
+            const _: &[u8] = include_bytes!(#path_str);
+        })
+    }
+}
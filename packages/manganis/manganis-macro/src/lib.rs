@@ -6,6 +6,7 @@ use quote::{quote, ToTokens};
 use syn::parse_macro_input;
 
 pub(crate) mod asset;
+pub(crate) mod css_module;
 pub(crate) mod linker;
 
 use linker::generate_link_section;
@@ -42,9 +43,55 @@ use linker::generate_link_section;
 /// # use manganis::{asset, Asset, ImageAssetOptions};
 /// const _: Asset = asset!("/assets/image.png", ImageAssetOptions::new().with_preload(true));
 /// ```
+///
+/// # Remote assets
+///
+/// Assets can also come from a url. Bundlers download the asset at build time, check that its contents match the
+/// sha256 and bundle it with the rest of your assets, so the app never loads it from a third party at runtime:
+/// ```rust
+/// # use manganis::{asset, Asset, CssAssetOptions};
+/// const _: Asset = asset!(
+///     "https://cdn.jsdelivr.net/npm/water.css@2/out/water.css",
+///     sha256 = "6c6e0ca29ccb4b5cba8c4cd2c8ea4b1dd2a0b0ab0ab48ab4ee6b7f1a0d1e6f2c",
+///     CssAssetOptions::new()
+/// );
+/// ```
 #[proc_macro]
 pub fn asset(input: TokenStream) -> TokenStream {
     let asset = parse_macro_input!(input as asset::AssetParser);
 
     quote! { #asset }.into_token_stream().into()
 }
+
+/// The css module attribute scopes the classes of a stylesheet to a struct
+///
+/// Every class in the stylesheet is renamed with a hash of the crate and the path of the stylesheet, so classes with
+/// the same name in different stylesheets never collide. The struct gets a constant with the scoped name of each class
+/// and a `STYLESHEET` constant with the stylesheet itself. Paths are resolved relative to the package root like
+/// [`asset!`](macro@asset).
+///
+/// ```rust
+/// # use manganis::{css_module, Asset};
+/// #[css_module("/assets/button.css")]
+/// struct Styles;
+///
+/// // The `.primary-button` class in the stylesheet
+/// let class: &str = Styles::primary_button;
+/// // The stylesheet that defines the class
+/// const _: Asset = Styles::STYLESHEET;
+/// ```
+///
+/// Class names are turned into constant names by replacing every character that can't be used in a rust identifier
+/// with `_`.
+///
+/// Bundlers that support manganis combine every css module into a single stylesheet, so rendering the `STYLESHEET`
+/// of several modules only loads one file.
+#[proc_macro_attribute]
+pub fn css_module(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let asset = parse_macro_input!(attr as asset::AssetParser);
+    let item = parse_macro_input!(item as syn::ItemStruct);
+
+    css_module::CssModuleParser { asset, item }
+        .into_token_stream()
+        .into()
+}
@@ -0,0 +1,94 @@
+use dioxus_lib::{document, prelude::*};
+use manganis_core::{Asset, AssetOptions, FontAssetOptions};
+use std::fmt::Write;
+
+/// The properties for a [`Font`].
+#[derive(Props, Clone, PartialEq)]
+pub struct FontProps {
+    /// The font asset to load
+    pub src: Asset,
+    /// The font family the font is registered as. Defaults to the name of the font file
+    #[props(into)]
+    pub family: Option<String>,
+}
+
+/// Register a font asset with an `@font-face` rule and preload it in the head of the page.
+///
+/// The rule uses the weight, style and unicode ranges from the [`FontAssetOptions`](crate::FontAssetOptions) of
+/// the asset, so text styled with the family only downloads the
+/// font if it uses characters the font was subset to. The head elements are rendered during SSR and on desktop, so
+/// the font starts loading before the app hydrates.
+///
+/// ```rust, ignore
+/// # use dioxus::prelude::*;
+/// const INTER: Asset = asset!(
+///     "/assets/inter.ttf",
+///     FontAssetOptions::new().with_unicode_range(UnicodeRange::LATIN_1)
+/// );
+///
+/// fn App() -> Element {
+///     rsx! {
+///         Font { src: INTER, family: "Inter" }
+///         p { font_family: "Inter, sans-serif", "Hello world" }
+///     }
+/// }
+/// ```
+#[component]
+pub fn Font(props: FontProps) -> Element {
+    let options = match props.src.bundled().options() {
+        AssetOptions::Font(options) => *options,
+        _ => FontAssetOptions::new(),
+    };
+    let href = props.src.to_string();
+    let css = font_face(&props.src, props.family.as_deref(), &options, &href);
+
+    rsx! {
+        if options.preloaded() {
+            document::Link {
+                rel: "preload",
+                href: href.clone(),
+                r#as: "font",
+                r#type: "font/woff2",
+                crossorigin: "anonymous",
+            }
+        }
+        document::Style { "{css}" }
+    }
+}
+
+/// Create the `@font-face` rule of a font asset
+fn font_face(
+    asset: &Asset,
+    family: Option<&str>,
+    options: &FontAssetOptions,
+    href: &str,
+) -> String {
+    let family = match family {
+        Some(family) => family.to_string(),
+        None => std::path::Path::new(asset.bundled().absolute_source_path())
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+
+    let mut css = format!(
+        "@font-face {{ font-family: \"{family}\"; src: url(\"{href}\"); font-display: {};",
+        options.display().as_str()
+    );
+    if options.weight() != 0 {
+        _ = write!(css, " font-weight: {};", options.weight());
+    }
+    if options.italic() {
+        css.push_str(" font-style: italic;");
+    }
+    if !options.unicode_ranges().is_empty() {
+        let ranges = options
+            .unicode_ranges()
+            .iter()
+            .map(|range| range.to_string())
+            .collect::<Vec<_>>();
+        _ = write!(css, " unicode-range: {};", ranges.join(", "));
+    }
+    css.push_str(" }");
+    css
+}
@@ -0,0 +1,96 @@
+use dioxus_lib::prelude::*;
+use manganis_core::{Asset, AssetOptions};
+
+/// The properties for an [`Image`].
+#[derive(Props, Clone, PartialEq)]
+pub struct ImageProps {
+    /// The image asset to render
+    pub src: Asset,
+
+    /// The alternative text of the image
+    #[props(into)]
+    pub alt: String,
+
+    /// How wide the image is rendered at different viewport sizes. The browser uses it to pick the responsive variant
+    /// to download. Defaults to `100vw`.
+    #[props(into, default = "100vw".to_string())]
+    pub sizes: String,
+
+    /// Wait to load the image until it is close to the viewport. Defaults to true.
+    #[props(default = true)]
+    pub lazy: bool,
+
+    /// Extra attributes for the img element
+    #[props(extends = GlobalAttributes, extends = img)]
+    pub attributes: Vec<Attribute>,
+}
+
+/// An image that lets the browser download the smallest variant of a responsive image asset that fits the layout.
+///
+/// Make the image responsive with [`ImageAssetOptions::with_responsive`](crate::ImageAssetOptions::with_responsive).
+/// The CLI generates the variants when the app is bundled and the component renders them as a `srcset` with avif and
+/// webp sources. Renderers without `srcset` support, and development builds where the variants don't exist yet, fall
+/// back to the bundled image.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// const HERO: Asset = asset!("/assets/image.png", ImageAssetOptions::new().with_responsive(true));
+///
+/// fn Hero() -> Element {
+///     rsx! {
+///         Image {
+///             src: HERO,
+///             alt: "The view from the office",
+///             sizes: "(max-width: 640px) 100vw, 50vw",
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn Image(props: ImageProps) -> Element {
+    let loading = if props.lazy { "lazy" } else { "eager" };
+    let mut sources = responsive_sources(&props.src);
+    // The format of the bundled image is always the last one, the rest are alternatives for browsers that support them
+    let srcset = sources.pop().map(|(_, srcset)| srcset);
+    let sizes = srcset.as_ref().map(|_| props.sizes.clone());
+
+    rsx! {
+        picture {
+            for (mime_type, srcset) in sources {
+                source { r#type: mime_type, srcset, sizes: props.sizes.clone() }
+            }
+            img {
+                src: props.src,
+                srcset,
+                sizes,
+                alt: props.alt,
+                loading,
+                decoding: "async",
+                ..props.attributes,
+            }
+        }
+    }
+}
+
+/// Get the mime type and srcset of every format of the responsive variants of an image
+fn responsive_sources(asset: &Asset) -> Vec<(Option<&'static str>, String)> {
+    let AssetOptions::Image(options) = asset.bundled().options() else {
+        return Vec::new();
+    };
+
+    options
+        .responsive_formats()
+        .into_iter()
+        .filter_map(|format| {
+            let srcset = options
+                .responsive_widths()
+                .iter()
+                .map(|width| {
+                    let path = asset.resolve_responsive_variant(*width, format)?;
+                    Some(format!("{} {width}w", path.display()))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some((format.mime_type(), srcset.join(", ")))
+        })
+        .collect()
+}
@@ -1,12 +1,21 @@
 #![doc = include_str!("../README.md")]
 #![deny(missing_docs)]
 
+#[cfg(feature = "dioxus")]
+mod font;
+#[cfg(feature = "dioxus")]
+pub use font::*;
 mod hash;
+#[cfg(feature = "dioxus")]
+mod image;
+#[cfg(feature = "dioxus")]
+pub use image::*;
 #[doc(hidden)]
 pub mod macro_helpers;
-pub use manganis_macro::asset;
+pub use manganis_macro::{asset, css_module};
 
 pub use manganis_core::{
-    Asset, AssetOptions, BundledAsset, CssAssetOptions, FolderAssetOptions, ImageAssetOptions,
-    ImageFormat, ImageSize, JsAssetOptions,
+    Asset, AssetOptions, BundledAsset, CssAssetOptions, CssModuleAssetOptions, FolderAssetOptions,
+    FontAssetOptions, FontDisplay, ImageAssetOptions, ImageFormat, ImageSize, JsAssetOptions,
+    UnicodeRange, DEFAULT_RESPONSIVE_WIDTHS,
 };
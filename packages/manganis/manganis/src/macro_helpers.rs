@@ -10,6 +10,11 @@ pub const fn generate_unique_path(
     content_hash: u64,
     asset_config: &AssetOptions,
 ) -> ConstStr {
+    // Every css module is combined into one stylesheet
+    if let AssetOptions::CssModule(_) = asset_config {
+        return ConstStr::new(manganis_core::CSS_MODULES_BUNDLED_PATH);
+    }
+
     // Format the unique path with the format `{input_path}-{hash}.{extension}`
     // Start with the input path
     let mut input_path = ConstStr::new(input_path);
@@ -117,7 +122,11 @@ fn test_unique_path() {
     let asset_config = AssetOptions::Image(ImageAssetOptions::new().with_format(ImageFormat::Avif));
     let output_path =
         generate_unique_path(&input_path.to_string_lossy(), content_hash, &asset_config);
-    assert_eq!(output_path.as_str(), "test-603a88fe296462a3.avif");
+    assert_eq!(output_path.as_str(), "test-e05b9f0994b77224.avif");
+    assert_eq!(
+        manganis_core::bundled_path_hash(output_path.as_str()),
+        Some("e05b9f0994b77224")
+    );
 
     // Changing the path without changing the contents shouldn't change the hash
     let mut input_path = PathBuf::from("some");
@@ -128,7 +137,7 @@ fn test_unique_path() {
     let asset_config = AssetOptions::Image(ImageAssetOptions::new().with_format(ImageFormat::Avif));
     let output_path =
         generate_unique_path(&input_path.to_string_lossy(), content_hash, &asset_config);
-    assert_eq!(output_path.as_str(), "test-603a88fe296462a3.avif");
+    assert_eq!(output_path.as_str(), "test-e05b9f0994b77224.avif");
 
     let mut input_path = PathBuf::from("test");
     input_path.push("ing");
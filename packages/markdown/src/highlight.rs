@@ -0,0 +1,76 @@
+//! Highlight fenced code blocks with the syntaxes and themes that ship with syntect.
+
+use crate::DEFAULT_CODE_THEME;
+use dioxus_lib::prelude::*;
+use std::sync::OnceLock;
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Color, FontStyle, Theme, ThemeSet},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+
+fn syntaxes() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn themes() -> &'static ThemeSet {
+    static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+    THEMES.get_or_init(ThemeSet::load_defaults)
+}
+
+/// The names of the themes code blocks can be highlighted with
+pub fn code_themes() -> impl Iterator<Item = &'static str> {
+    themes().themes.keys().map(|name| name.as_str())
+}
+
+fn css_color(color: Color) -> String {
+    format!(
+        "rgba({}, {}, {}, {})",
+        color.r,
+        color.g,
+        color.b,
+        color.a as f32 / 255.0
+    )
+}
+
+pub(crate) fn render(theme: &str, language: Option<&str>, code: &str) -> Element {
+    let syntaxes = syntaxes();
+    let theme: &Theme = themes()
+        .themes
+        .get(theme)
+        .unwrap_or_else(|| &themes().themes[DEFAULT_CODE_THEME]);
+    let syntax = language
+        .and_then(|language| syntaxes.find_syntax_by_token(language))
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let class = language.map(|language| format!("language-{language}"));
+    let background = theme.settings.background.map(css_color);
+
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut spans = Vec::new();
+    for line in LinesWithEndings::from(code) {
+        // Fall back to the plain text if the syntax fails to parse the line
+        let Ok(regions) = highlighter.highlight_line(line, syntaxes) else {
+            spans.push(rsx! { "{line}" });
+            continue;
+        };
+        for (style, text) in regions {
+            spans.push(rsx! {
+                span {
+                    color: css_color(style.foreground),
+                    font_weight: style.font_style.contains(FontStyle::BOLD).then_some("bold"),
+                    font_style: style.font_style.contains(FontStyle::ITALIC).then_some("italic"),
+                    text_decoration: style.font_style.contains(FontStyle::UNDERLINE).then_some("underline"),
+                    "{text}"
+                }
+            });
+        }
+    }
+
+    rsx! {
+        pre { background_color: background,
+            code { class, {spans.into_iter()} }
+        }
+    }
+}
@@ -0,0 +1,442 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+use dioxus_lib::prelude::*;
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
+use std::collections::HashMap;
+
+#[cfg(feature = "highlight")]
+mod highlight;
+#[cfg(feature = "highlight")]
+pub use highlight::code_themes;
+
+/// The theme code blocks are highlighted with if no other theme is set
+pub const DEFAULT_CODE_THEME: &str = "base16-ocean.dark";
+
+/// The properties of the [`Markdown`] component
+#[derive(Props, Clone, PartialEq)]
+pub struct MarkdownProps {
+    /// The markdown to render
+    #[props(into)]
+    pub content: String,
+
+    /// Add an id and a link to itself to every heading so sections of the document can be linked to. Defaults to
+    /// `true`.
+    #[props(default = true)]
+    pub heading_anchors: bool,
+
+    /// The name of the theme fenced code blocks are highlighted with. Only used with the `highlight` feature. Defaults
+    /// to [`DEFAULT_CODE_THEME`].
+    #[props(into, default = DEFAULT_CODE_THEME.to_string())]
+    pub code_theme: String,
+
+    /// Render an image in the markdown. Use this to resolve images to assets or to render them with your own image
+    /// component. Images are rendered as lazily loaded `img` elements by default.
+    pub render_image: Option<Callback<MarkdownImage, Element>>,
+
+    /// Additional attributes for the element that wraps the document
+    #[props(extends = GlobalAttributes)]
+    pub attributes: Vec<Attribute>,
+}
+
+/// An image in the markdown passed to [`MarkdownProps::render_image`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkdownImage {
+    /// The url of the image as written in the markdown
+    pub src: String,
+    /// The text of the image description
+    pub alt: String,
+    /// The title of the image. Empty if the image doesn't have a title
+    pub title: String,
+}
+
+/// Render [CommonMark](https://commonmark.org) with the [GitHub flavored](https://github.github.com/gfm/) tables,
+/// task lists, strikethrough and footnotes to Dioxus elements.
+///
+/// The markdown is rendered to elements instead of an html string, so it can be diffed like the rest of the app and
+/// works in every renderer. Html blocks in the markdown are rendered with [`RawHtml`] and sanitized with the
+/// [`Sanitizer`] in the context. Inline html is skipped.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_markdown::Markdown;
+///
+/// fn App() -> Element {
+///     rsx! {
+///         Markdown { class: "prose", content: "# Hello\n\nThis is **markdown**" }
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Markdown(props: MarkdownProps) -> Element {
+    let document = parse(&props.content);
+    let mut renderer = Renderer::new(&props);
+    let children = renderer.render_nodes(document);
+
+    rsx! {
+        div { ..props.attributes, {children.into_iter()} }
+    }
+}
+
+/// A node of the parsed markdown document
+enum Node {
+    Tag(Tag<'static>, Vec<Node>),
+    Event(Event<'static>),
+}
+
+fn parse(content: &str) -> Vec<Node> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_HEADING_ATTRIBUTES;
+
+    // The parser emits a flat list of events. Collect them into a tree so every tag can be rendered with its children.
+    let mut stack: Vec<(Tag<'static>, Vec<Node>)> = Vec::new();
+    let mut root = Vec::new();
+    for event in Parser::new_ext(content, options) {
+        match event {
+            Event::Start(tag) => stack.push((tag.into_static(), Vec::new())),
+            Event::End(_) => {
+                let Some((tag, children)) = stack.pop() else {
+                    continue;
+                };
+                let parent = match stack.last_mut() {
+                    Some((_, children)) => children,
+                    None => &mut root,
+                };
+                parent.push(Node::Tag(tag, children));
+            }
+            event => {
+                let parent = match stack.last_mut() {
+                    Some((_, children)) => children,
+                    None => &mut root,
+                };
+                parent.push(Node::Event(event.into_static()));
+            }
+        }
+    }
+
+    root
+}
+
+/// Renders a parsed document. The renderer keeps track of the ids of headings and the numbers of footnotes across
+/// the whole document.
+struct Renderer<'a> {
+    props: &'a MarkdownProps,
+    heading_ids: HashMap<String, usize>,
+    footnotes: HashMap<String, usize>,
+}
+
+impl<'a> Renderer<'a> {
+    fn new(props: &'a MarkdownProps) -> Self {
+        Self {
+            props,
+            heading_ids: HashMap::new(),
+            footnotes: HashMap::new(),
+        }
+    }
+
+    fn render_nodes(&mut self, nodes: Vec<Node>) -> Vec<Element> {
+        nodes
+            .into_iter()
+            .map(|node| self.render_node(node))
+            .collect()
+    }
+
+    fn render_node(&mut self, node: Node) -> Element {
+        match node {
+            Node::Tag(tag, children) => self.render_tag(tag, children),
+            Node::Event(event) => self.render_event(event),
+        }
+    }
+
+    fn render_tag(&mut self, tag: Tag<'static>, children: Vec<Node>) -> Element {
+        match tag {
+            Tag::Heading {
+                level, id, classes, ..
+            } => self.render_heading(level, id.map(|id| id.to_string()), classes, children),
+            Tag::CodeBlock(kind) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .map(|language| language.to_string()),
+                    CodeBlockKind::Indented => None,
+                };
+                self.render_code_block(language, text_content(&children))
+            }
+            Tag::HtmlBlock => {
+                let html = text_content(&children);
+                rsx! { RawHtml { html } }
+            }
+            Tag::Table(alignments) => self.render_table(alignments, children),
+            Tag::Image {
+                dest_url, title, ..
+            } => {
+                let image = MarkdownImage {
+                    src: dest_url.to_string(),
+                    alt: text_content(&children),
+                    title: title.to_string(),
+                };
+                match &self.props.render_image {
+                    Some(render_image) => render_image(image),
+                    None => rsx! {
+                        img {
+                            src: image.src,
+                            alt: image.alt,
+                            title: (!image.title.is_empty()).then_some(image.title),
+                            loading: "lazy",
+                            decoding: "async",
+                        }
+                    },
+                }
+            }
+            Tag::Link {
+                dest_url, title, ..
+            } => {
+                let children = self.render_nodes(children);
+                rsx! {
+                    a {
+                        href: dest_url.to_string(),
+                        title: (!title.is_empty()).then(|| title.to_string()),
+                        {children.into_iter()}
+                    }
+                }
+            }
+            Tag::List(start) => {
+                let children = self.render_nodes(children);
+                match start {
+                    Some(start) => rsx! {
+                        ol { start: (start != 1).then_some(start as i64), {children.into_iter()} }
+                    },
+                    None => rsx! {
+                        ul { {children.into_iter()} }
+                    },
+                }
+            }
+            Tag::FootnoteDefinition(label) => {
+                let number = self.footnote_number(&label);
+                let children = self.render_nodes(children);
+                rsx! {
+                    div { class: "footnote-definition", id: "fn-{label}",
+                        sup { class: "footnote-definition-label", "{number}" }
+                        {children.into_iter()}
+                        a { class: "footnote-backref", href: "#fnref-{label}", "↩" }
+                    }
+                }
+            }
+            tag => {
+                let children = self.render_nodes(children);
+                let children = children.into_iter();
+                match tag {
+                    Tag::Paragraph => rsx! { p { {children} } },
+                    Tag::BlockQuote(_) => rsx! { blockquote { {children} } },
+                    Tag::Item => rsx! { li { {children} } },
+                    Tag::DefinitionList => rsx! { dl { {children} } },
+                    Tag::DefinitionListTitle => rsx! { dt { {children} } },
+                    Tag::DefinitionListDefinition => rsx! { dd { {children} } },
+                    Tag::Emphasis => rsx! { em { {children} } },
+                    Tag::Strong => rsx! { strong { {children} } },
+                    Tag::Strikethrough => rsx! { del { {children} } },
+                    Tag::Superscript => rsx! { sup { {children} } },
+                    Tag::Subscript => rsx! { sub { {children} } },
+                    // Table parts are rendered by the table and metadata blocks are not enabled
+                    _ => rsx! { {children} },
+                }
+            }
+        }
+    }
+
+    fn render_event(&mut self, event: Event<'static>) -> Element {
+        match event {
+            Event::Text(text) => rsx! { "{text}" },
+            Event::Code(code) => rsx! { code { "{code}" } },
+            Event::InlineMath(math) | Event::DisplayMath(math) => rsx! { code { "{math}" } },
+            Event::SoftBreak => rsx! { "\n" },
+            Event::HardBreak => rsx! { br {} },
+            Event::Rule => rsx! { hr {} },
+            Event::TaskListMarker(checked) => rsx! {
+                input { r#type: "checkbox", disabled: true, checked }
+            },
+            Event::FootnoteReference(label) => {
+                let number = self.footnote_number(&label);
+                rsx! {
+                    sup { class: "footnote-reference",
+                        a { id: "fnref-{label}", href: "#fn-{label}", "{number}" }
+                    }
+                }
+            }
+            // Html outside of html blocks can't be rendered as elements without the rest of the paragraph
+            Event::Html(_) | Event::InlineHtml(_) => VNode::empty(),
+            _ => VNode::empty(),
+        }
+    }
+
+    fn render_heading(
+        &mut self,
+        level: HeadingLevel,
+        id: Option<String>,
+        classes: Vec<pulldown_cmark::CowStr<'static>>,
+        children: Vec<Node>,
+    ) -> Element {
+        let id = match id {
+            Some(id) => Some(self.unique_id(id)),
+            None if self.props.heading_anchors => {
+                Some(self.unique_id(slugify(&text_content(&children))))
+            }
+            None => None,
+        };
+        let class = (!classes.is_empty()).then(|| classes.join(" "));
+        let children = self.render_nodes(children);
+        let anchor = match (&id, self.props.heading_anchors) {
+            (Some(id), true) => rsx! {
+                a { class: "heading-anchor", href: "#{id}", aria_hidden: true, "#" }
+            },
+            _ => VNode::empty(),
+        };
+        let children = children.into_iter();
+
+        match level {
+            HeadingLevel::H1 => rsx! { h1 { id, class, {children} {anchor} } },
+            HeadingLevel::H2 => rsx! { h2 { id, class, {children} {anchor} } },
+            HeadingLevel::H3 => rsx! { h3 { id, class, {children} {anchor} } },
+            HeadingLevel::H4 => rsx! { h4 { id, class, {children} {anchor} } },
+            HeadingLevel::H5 => rsx! { h5 { id, class, {children} {anchor} } },
+            HeadingLevel::H6 => rsx! { h6 { id, class, {children} {anchor} } },
+        }
+    }
+
+    fn render_table(&mut self, alignments: Vec<Alignment>, children: Vec<Node>) -> Element {
+        let mut head = None;
+        let mut rows = Vec::new();
+        for child in children {
+            match child {
+                Node::Tag(Tag::TableHead, cells) => {
+                    let cells = self.render_cells(&alignments, cells, true);
+                    head = Some(rsx! {
+                        thead {
+                            tr { {cells.into_iter()} }
+                        }
+                    });
+                }
+                Node::Tag(Tag::TableRow, cells) => {
+                    let cells = self.render_cells(&alignments, cells, false);
+                    rows.push(rsx! {
+                        tr { {cells.into_iter()} }
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        rsx! {
+            table {
+                {head}
+                tbody { {rows.into_iter()} }
+            }
+        }
+    }
+
+    fn render_cells(
+        &mut self,
+        alignments: &[Alignment],
+        cells: Vec<Node>,
+        header: bool,
+    ) -> Vec<Element> {
+        cells
+            .into_iter()
+            .enumerate()
+            .map(|(column, cell)| {
+                let Node::Tag(Tag::TableCell, children) = cell else {
+                    return self.render_node(cell);
+                };
+                let text_align = match alignments.get(column) {
+                    Some(Alignment::Left) => Some("left"),
+                    Some(Alignment::Center) => Some("center"),
+                    Some(Alignment::Right) => Some("right"),
+                    Some(Alignment::None) | None => None,
+                };
+                let children = self.render_nodes(children).into_iter();
+                match header {
+                    true => rsx! { th { text_align, {children} } },
+                    false => rsx! { td { text_align, {children} } },
+                }
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "highlight")]
+    fn render_code_block(&mut self, language: Option<String>, code: String) -> Element {
+        highlight::render(&self.props.code_theme, language.as_deref(), &code)
+    }
+
+    #[cfg(not(feature = "highlight"))]
+    fn render_code_block(&mut self, language: Option<String>, code: String) -> Element {
+        let class = language.map(|language| format!("language-{language}"));
+        rsx! {
+            pre {
+                code { class, "{code}" }
+            }
+        }
+    }
+
+    /// Get an id that is not used by any other heading in the document
+    fn unique_id(&mut self, id: String) -> String {
+        let mut unique = id.clone();
+        while let Some(count) = self.heading_ids.get_mut(&unique) {
+            *count += 1;
+            unique = format!("{id}-{count}");
+        }
+        self.heading_ids.insert(unique.clone(), 0);
+        unique
+    }
+
+    /// Footnotes are numbered in the order they are first referenced or defined
+    fn footnote_number(&mut self, label: &str) -> usize {
+        let next = self.footnotes.len() + 1;
+        *self.footnotes.entry(label.to_string()).or_insert(next)
+    }
+}
+
+/// The plain text of a tree of nodes
+fn text_content(nodes: &[Node]) -> String {
+    let mut text = String::new();
+    fn collect(nodes: &[Node], text: &mut String) {
+        for node in nodes {
+            match node {
+                Node::Tag(_, children) => collect(children, text),
+                Node::Event(
+                    Event::Text(content)
+                    | Event::Code(content)
+                    | Event::Html(content)
+                    | Event::InlineHtml(content),
+                ) => text.push_str(content),
+                Node::Event(Event::SoftBreak | Event::HardBreak) => text.push(' '),
+                _ => {}
+            }
+        }
+    }
+    collect(nodes, &mut text);
+    text
+}
+
+/// Turn the text of a heading into an id the same way GitHub does: lowercase letters, numbers, `-` and `_` are kept,
+/// spaces become `-` and everything else is removed.
+fn slugify(text: &str) -> String {
+    let slug: String = text
+        .trim()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .flat_map(char::to_lowercase)
+        .collect();
+    match slug.is_empty() {
+        true => "section".to_string(),
+        false => slug,
+    }
+}
@@ -0,0 +1,105 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_markdown::*;
+
+fn render(content: &str) -> String {
+    let content = content.to_string();
+    dioxus_ssr::render_element(rsx! {
+        Markdown { content }
+    })
+}
+
+#[test]
+fn renders_commonmark_to_elements() {
+    let html = render("A *quick* **brown** `fox`\n\n- one\n- two\n\n3. three\n\n> quote\n\n---");
+    assert_eq!(
+        html,
+        "<div><p>A <em>quick</em> <strong>brown</strong> <code>fox</code></p>\
+         <ul><li>one</li><li>two</li></ul>\
+         <ol start=3><li>three</li></ol>\
+         <blockquote><p>quote</p></blockquote><hr/></div>"
+    );
+}
+
+#[test]
+fn headings_have_unique_anchors() {
+    let html = render("# Getting Started!\n\n## Getting started\n\n## Custom {#custom}");
+    assert_eq!(
+        html,
+        "<div><h1 id=\"getting-started\">Getting Started!<a class=\"heading-anchor\" href=\"#getting-started\" aria-hidden=true>#</a></h1>\
+         <h2 id=\"getting-started-1\">Getting started<a class=\"heading-anchor\" href=\"#getting-started-1\" aria-hidden=true>#</a></h2>\
+         <h2 id=\"custom\">Custom<a class=\"heading-anchor\" href=\"#custom\" aria-hidden=true>#</a></h2></div>"
+    );
+
+    let html = dioxus_ssr::render_element(rsx! {
+        Markdown { content: "# Title", heading_anchors: false }
+    });
+    assert_eq!(html, "<div><h1>Title</h1></div>");
+}
+
+#[test]
+fn renders_gfm_extensions() {
+    let html = render("| a | b |\n|:--|--:|\n| 1 | 2 |");
+    assert_eq!(
+        html,
+        "<div><table><thead><tr><th style=\"text-align:left;\">a</th><th style=\"text-align:right;\">b</th></tr></thead>\
+         <tbody><tr><td style=\"text-align:left;\">1</td><td style=\"text-align:right;\">2</td></tr></tbody></table></div>"
+    );
+
+    let html = render("- [x] done\n- [ ] todo\n\n~~old~~ text[^note]\n\n[^note]: A note");
+    assert_eq!(
+        html,
+        "<div><ul><li><input type=\"checkbox\" disabled=true checked=true/>done</li>\
+         <li><input type=\"checkbox\" disabled=true/>todo</li></ul>\
+         <p><del>old</del> text<sup class=\"footnote-reference\"><a id=\"fnref-note\" href=\"#fn-note\">1</a></sup></p>\
+         <div class=\"footnote-definition\" id=\"fn-note\"><sup class=\"footnote-definition-label\">1</sup><p>A note</p>\
+         <a class=\"footnote-backref\" href=\"#fnref-note\">↩</a></div></div>"
+    );
+}
+
+#[cfg(not(feature = "highlight"))]
+#[test]
+fn code_blocks_have_a_language_class() {
+    let html = render("```rust\nfn main() {}\n```");
+    assert_eq!(
+        html,
+        "<div><pre><code class=\"language-rust\">fn main() {}\n</code></pre></div>"
+    );
+}
+
+#[cfg(feature = "highlight")]
+#[test]
+fn code_blocks_are_highlighted() {
+    let html = render("```rust\nfn main() {}\n```");
+    assert!(html.starts_with("<div><pre style=\"background-color:"));
+    assert!(html.contains("<span style=\"color:"));
+    assert!(code_themes().any(|theme| theme == DEFAULT_CODE_THEME));
+}
+
+#[test]
+fn images_and_html() {
+    let html = render(
+        "![A cat](cat.png \"Cat\")\n\n<div onclick=\"steal()\">Hi<script>alert(1)</script></div>",
+    );
+    assert_eq!(
+        html,
+        "<div><p><img src=\"cat.png\" alt=\"A cat\" title=\"Cat\" loading=\"lazy\" decoding=\"async\"/></p>\
+         <div><div>Hi</div></div></div>"
+    );
+
+    fn App() -> Element {
+        rsx! {
+            Markdown {
+                content: "![A cat](cat.png)",
+                render_image: move |image: MarkdownImage| rsx! { img { src: "/assets/{image.src}" } },
+            }
+        }
+    }
+    let mut dom = VirtualDom::new(App);
+    dom.rebuild_in_place();
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div><p><img src=\"/assets/cat.png\"/></p></div>"
+    );
+}
@@ -0,0 +1,417 @@
+use dioxus_lib::prelude::*;
+use std::fmt::Display;
+
+/// The kind of biometric sensor of the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BiometryKind {
+    /// Face ID on iOS
+    FaceId,
+    /// Touch ID on iOS and macOS
+    TouchId,
+    /// Optic ID on visionOS
+    OpticId,
+    /// A fingerprint, face or iris sensor. Android doesn't tell which one it is.
+    Biometric,
+}
+
+/// An error checking or asking for biometric authentication
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BiometricError {
+    /// The platform doesn't support biometric authentication
+    Unsupported,
+    /// The device has no biometric sensor or it can't be used right now
+    NotAvailable,
+    /// The user didn't set up a fingerprint or face on the device
+    NotEnrolled,
+    /// Too many attempts failed. The user has to unlock the device with their passcode first.
+    LockedOut,
+    /// The user or the system cancelled the prompt
+    Cancelled,
+    /// The user could not be recognized
+    Failed,
+    /// Another error of the platform
+    Other(String),
+}
+
+impl Display for BiometricError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsupported => write!(
+                f,
+                "biometric authentication is not supported on this platform"
+            ),
+            Self::NotAvailable => write!(f, "biometric authentication is not available"),
+            Self::NotEnrolled => write!(f, "no biometrics are enrolled on the device"),
+            Self::LockedOut => write!(
+                f,
+                "biometric authentication is locked after too many attempts"
+            ),
+            Self::Cancelled => write!(f, "biometric authentication was cancelled"),
+            Self::Failed => write!(f, "the user could not be recognized"),
+            Self::Other(message) => write!(f, "biometric authentication failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for BiometricError {}
+
+/// The state of the biometric authentication of a [`BiometricAuth`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum BiometricStatus {
+    /// The user wasn't asked to authenticate yet
+    #[default]
+    Idle,
+    /// The prompt is shown
+    Pending,
+    /// The user authenticated
+    Authenticated,
+    /// The authentication failed or was cancelled
+    Failed(BiometricError),
+}
+
+/// Biometric authentication with a reactive status, returned by [`use_biometric_auth`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiometricAuth {
+    status: Signal<BiometricStatus>,
+}
+
+impl BiometricAuth {
+    /// Get the status of the last authentication. Reading the status subscribes the component to it.
+    pub fn status(&self) -> BiometricStatus {
+        self.status.read().clone()
+    }
+
+    /// Check if the user authenticated
+    pub fn is_authenticated(&self) -> bool {
+        *self.status.read() == BiometricStatus::Authenticated
+    }
+
+    /// Get the kind of biometric sensor of the device, or why biometric authentication can't be used
+    pub async fn biometry(&self) -> Result<BiometryKind, BiometricError> {
+        biometry().await
+    }
+
+    /// Ask the user to authenticate with their face or fingerprint. The reason is shown in the prompt.
+    pub async fn authenticate(mut self, reason: &str) -> Result<(), BiometricError> {
+        self.status.set(BiometricStatus::Pending);
+        let result = authenticate(reason).await;
+        self.status.set(match &result {
+            Ok(()) => BiometricStatus::Authenticated,
+            Err(err) => BiometricStatus::Failed(err.clone()),
+        });
+        result
+    }
+
+    /// Forget the authentication, like when the user signs out or the app goes to the background
+    pub fn reset(mut self) {
+        self.status.set(BiometricStatus::Idle);
+    }
+}
+
+/// Authenticate the user with Face ID, Touch ID or the biometric prompt of Android.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::mobile::{use_biometric_auth, BiometricStatus, SecureStorage};
+///
+/// fn App() -> Element {
+///     let auth = use_biometric_auth();
+///
+///     if auth.is_authenticated() {
+///         let token = SecureStorage::new("com.example.app").get("token").ok().flatten();
+///         return rsx! { "Your token is {token:?}" };
+///     }
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| async move {
+///                 _ = auth.authenticate("Unlock your account").await;
+///             },
+///             "Unlock"
+///         }
+///         if let BiometricStatus::Failed(err) = auth.status() {
+///             "{err}"
+///         }
+///     }
+/// }
+/// ```
+///
+/// Face ID requires a usage description in the `Info.plist` of the app, which the CLI adds from the
+/// `face_id_usage_description` in the `[bundle]` section of `Dioxus.toml`.
+pub fn use_biometric_auth() -> BiometricAuth {
+    let status = use_signal(BiometricStatus::default);
+    BiometricAuth { status }
+}
+
+/// Get the kind of biometric sensor of the device, or why biometric authentication can't be used
+pub async fn biometry() -> Result<BiometryKind, BiometricError> {
+    imp::biometry().await
+}
+
+/// Ask the user to authenticate with their face or fingerprint. The reason is shown in the prompt.
+///
+/// Use [`use_biometric_auth`] to keep the status of the authentication in a component.
+pub async fn authenticate(reason: &str) -> Result<(), BiometricError> {
+    imp::authenticate(reason).await
+}
+
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+mod imp {
+    use super::{BiometricError, BiometryKind};
+    use block::ConcreteBlock;
+    use futures_channel::oneshot;
+    use objc::runtime::{Object, BOOL, NO};
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::{ffi::CStr, sync::Mutex};
+
+    #[link(name = "LocalAuthentication", kind = "framework")]
+    extern "C" {}
+
+    /// `LAPolicyDeviceOwnerAuthenticationWithBiometrics`
+    const POLICY_BIOMETRICS: isize = 1;
+
+    pub(super) async fn biometry() -> Result<BiometryKind, BiometricError> {
+        unsafe {
+            let context: *mut Object = msg_send![class!(LAContext), new];
+            let mut error: *mut Object = std::ptr::null_mut();
+            let can_evaluate: BOOL =
+                msg_send![context, canEvaluatePolicy: POLICY_BIOMETRICS error: &mut error];
+            // The type is only known after the policy was evaluated
+            let kind: isize = msg_send![context, biometryType];
+            let result = match can_evaluate == NO {
+                true => Err(to_error(error)),
+                false => Ok(match kind {
+                    1 => BiometryKind::TouchId,
+                    2 => BiometryKind::FaceId,
+                    4 => BiometryKind::OpticId,
+                    _ => BiometryKind::Biometric,
+                }),
+            };
+            let _: () = msg_send![context, release];
+            result
+        }
+    }
+
+    pub(super) async fn authenticate(reason: &str) -> Result<(), BiometricError> {
+        let (sender, receiver) = oneshot::channel();
+        let sender = Mutex::new(Some(sender));
+
+        unsafe {
+            let context: *mut Object = msg_send![class!(LAContext), new];
+            let reason = ns_string(reason);
+
+            // The context has to live until the reply, which is called on a private queue
+            let context_ptr = context as usize;
+            let reply = ConcreteBlock::new(move |success: BOOL, error: *mut Object| {
+                let result = match success == NO {
+                    true => Err(to_error(error)),
+                    false => Ok(()),
+                };
+                if let Some(sender) = sender.lock().unwrap().take() {
+                    _ = sender.send(result);
+                }
+                let _: () = msg_send![context_ptr as *mut Object, release];
+            })
+            .copy();
+
+            let _: () = msg_send![
+                context,
+                evaluatePolicy: POLICY_BIOMETRICS
+                localizedReason: reason
+                reply: &*reply
+            ];
+            let _: () = msg_send![reason, release];
+        }
+
+        receiver.await.unwrap_or(Err(BiometricError::Cancelled))
+    }
+
+    unsafe fn ns_string(text: &str) -> *mut Object {
+        // NSUTF8StringEncoding
+        let string: *mut Object = msg_send![class!(NSString), alloc];
+        msg_send![string, initWithBytes: text.as_ptr() length: text.len() encoding: 4usize]
+    }
+
+    /// Convert an `NSError` of the `LAErrorDomain` into an error
+    unsafe fn to_error(error: *mut Object) -> BiometricError {
+        if error.is_null() {
+            return BiometricError::Failed;
+        }
+        let code: isize = msg_send![error, code];
+        match code {
+            // LAErrorAuthenticationFailed
+            -1 => BiometricError::Failed,
+            // LAErrorUserCancel, LAErrorUserFallback, LAErrorSystemCancel and LAErrorAppCancel
+            -2 | -3 | -4 | -9 => BiometricError::Cancelled,
+            // LAErrorPasscodeNotSet and LAErrorBiometryNotAvailable
+            -5 | -6 => BiometricError::NotAvailable,
+            // LAErrorBiometryNotEnrolled
+            -7 => BiometricError::NotEnrolled,
+            // LAErrorBiometryLockout
+            -8 => BiometricError::LockedOut,
+            _ => {
+                let description: *mut Object = msg_send![error, localizedDescription];
+                let utf8: *const std::ffi::c_char = msg_send![description, UTF8String];
+                match utf8.is_null() {
+                    true => BiometricError::Other(format!("error {code}")),
+                    false => {
+                        BiometricError::Other(CStr::from_ptr(utf8).to_string_lossy().into_owned())
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+mod imp {
+    use super::{BiometricError, BiometryKind};
+    use dioxus_android::{clear_exception, load_class};
+    use dioxus_desktop::wry::prelude::{dispatch, jni, JNIEnv, JObject};
+    use futures_channel::oneshot;
+    use jni::{
+        objects::{JClass, JString, JValue},
+        sys::{jint, jlong},
+    };
+    use std::{
+        collections::BTreeMap,
+        sync::{
+            atomic::{AtomicI64, Ordering},
+            Mutex,
+        },
+    };
+
+    type Reply = oneshot::Sender<Result<i32, BiometricError>>;
+
+    /// The requests that wait for `dev.dioxus.main.Biometric` to answer
+    static PENDING: Mutex<BTreeMap<i64, Reply>> = Mutex::new(BTreeMap::new());
+    static NEXT_ID: AtomicI64 = AtomicI64::new(0);
+
+    /// Returned by `Biometric` if the version of Android is too old
+    const UNSUPPORTED: i32 = -1;
+
+    pub(super) async fn biometry() -> Result<BiometryKind, BiometricError> {
+        let code = call(|env, activity, class, _id| {
+            let code = env
+                .call_static_method(
+                    class,
+                    "availability",
+                    "(Landroid/app/Activity;)I",
+                    &[activity.into()],
+                )?
+                .i()?;
+            Ok(Some(code))
+        })
+        .await?;
+        to_result(code, None).map(|_| BiometryKind::Biometric)
+    }
+
+    pub(super) async fn authenticate(reason: &str) -> Result<(), BiometricError> {
+        let reason = reason.to_string();
+        let code = call(move |env, activity, class, id| {
+            let title = env.new_string(&reason)?;
+            let cancel = env.new_string("Cancel")?;
+            env.call_static_method(
+                class,
+                "authenticate",
+                "(Landroid/app/Activity;Ljava/lang/String;Ljava/lang/String;J)V",
+                &[
+                    activity.into(),
+                    (&title).into(),
+                    (&cancel).into(),
+                    JValue::Long(id),
+                ],
+            )?;
+            // The prompt answers later through `onResult`
+            Ok(None)
+        })
+        .await?;
+        to_result(code, None)
+    }
+
+    /// Call into `dev.dioxus.main.Biometric` on the UI thread and wait for the answer. The function either answers
+    /// right away or lets the prompt answer later with the id.
+    async fn call(
+        f: impl FnOnce(&mut JNIEnv, &JObject, &JClass, i64) -> jni::errors::Result<Option<i32>>
+            + Send
+            + 'static,
+    ) -> Result<i32, BiometricError> {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = oneshot::channel();
+        PENDING.lock().unwrap().insert(id, sender);
+
+        dispatch(move |env, activity, _webview| {
+            let result = load_class(env, activity, "dev/dioxus/main/Biometric")
+                .and_then(|class| f(env, activity, &class, id));
+            match clear_exception(env, result) {
+                Ok(Some(code)) => finish(id, Ok(code)),
+                Ok(None) => {}
+                Err(err) => finish(id, Err(BiometricError::Other(err.to_string()))),
+            }
+        });
+
+        receiver.await.unwrap_or(Err(BiometricError::Cancelled))
+    }
+
+    fn finish(id: i64, result: Result<i32, BiometricError>) {
+        if let Some(sender) = PENDING.lock().unwrap().remove(&id) {
+            _ = sender.send(result);
+        }
+    }
+
+    /// Convert a code of `BiometricManager` or `BiometricPrompt` into a result
+    fn to_result(code: i32, message: Option<String>) -> Result<(), BiometricError> {
+        match code {
+            0 => Ok(()),
+            UNSUPPORTED => Err(BiometricError::Unsupported),
+            // BIOMETRIC_ERROR_HW_UNAVAILABLE and BIOMETRIC_ERROR_HW_NOT_PRESENT
+            1 | 12 => Err(BiometricError::NotAvailable),
+            // BIOMETRIC_ERROR_NO_BIOMETRICS
+            11 => Err(BiometricError::NotEnrolled),
+            // BIOMETRIC_ERROR_LOCKOUT and BIOMETRIC_ERROR_LOCKOUT_PERMANENT
+            7 | 9 => Err(BiometricError::LockedOut),
+            // BIOMETRIC_ERROR_CANCELED and BIOMETRIC_ERROR_USER_CANCELED
+            5 | 10 => Err(BiometricError::Cancelled),
+            // BIOMETRIC_ERROR_TIMEOUT
+            3 => Err(BiometricError::Failed),
+            code => Err(BiometricError::Other(
+                message.unwrap_or_else(|| format!("error {code}")),
+            )),
+        }
+    }
+
+    /// Called by `dev.dioxus.main.Biometric` when the prompt is answered
+    #[no_mangle]
+    pub extern "system" fn Java_dev_dioxus_main_Biometric_onResult(
+        mut env: JNIEnv,
+        _class: JClass,
+        id: jlong,
+        code: jint,
+        message: JString,
+    ) {
+        let message = match message.is_null() {
+            true => None,
+            false => env.get_string(&message).ok().map(String::from),
+        };
+        let result = match to_result(code, message) {
+            Ok(()) => Ok(0),
+            Err(err) => Err(err),
+        };
+        finish(id, result);
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "macos", target_os = "android")))]
+mod imp {
+    use super::{BiometricError, BiometryKind};
+
+    pub(super) async fn biometry() -> Result<BiometryKind, BiometricError> {
+        Err(BiometricError::Unsupported)
+    }
+
+    pub(super) async fn authenticate(_reason: &str) -> Result<(), BiometricError> {
+        Err(BiometricError::Unsupported)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::watch::use_watch;
+use dioxus_lib::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The state of the soft keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct KeyboardState {
+    /// If the keyboard is shown
+    pub visible: bool,
+    /// How much of the bottom of the page the keyboard covers in CSS pixels. This is zero on Android, where the
+    /// webview is resized to end above the keyboard.
+    pub height: f64,
+}
+
+const WATCH_KEYBOARD: &str = r#"
+const root = document.documentElement;
+// The height of the page without the keyboard. It only changes when the page gets wider or narrower, like when the
+// device is rotated.
+let width = window.innerWidth;
+let fullHeight = window.innerHeight;
+let last = null;
+const send = () => {
+    if (window.innerWidth !== width) {
+        width = window.innerWidth;
+        fullHeight = window.innerHeight;
+    }
+    fullHeight = Math.max(fullHeight, window.innerHeight);
+    const visual = window.visualViewport;
+    const visibleHeight = visual ? visual.height : window.innerHeight;
+    const height = visual ? Math.max(0, window.innerHeight - visual.height - visual.offsetTop) : 0;
+    // Smaller changes are toolbars, not the keyboard
+    const visible = fullHeight - visibleHeight > 100;
+    root.style.setProperty("--dioxus-keyboard-height", `${height}px`);
+    if (!last || last.visible !== visible || last.height !== height) {
+        last = { visible, height };
+        dioxus.send(last);
+    }
+};
+send();
+window.addEventListener("resize", send);
+window.visualViewport?.addEventListener("resize", send);
+window.visualViewport?.addEventListener("scroll", send);
+stop.then(() => {
+    window.removeEventListener("resize", send);
+    window.visualViewport?.removeEventListener("resize", send);
+    window.visualViewport?.removeEventListener("scroll", send);
+    root.style.removeProperty("--dioxus-keyboard-height");
+});
+"#;
+
+/// Watch the soft keyboard of the device.
+///
+/// On Android the webview is resized to end above the keyboard. On iOS the keyboard covers the bottom of the page, so
+/// the hook sets the `--dioxus-keyboard-height` CSS variable to the height it covers while the component is mounted.
+/// Pad the page with it to keep inputs and buttons at the bottom of the page above the keyboard:
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::mobile::use_keyboard;
+///
+/// fn App() -> Element {
+///     let keyboard = use_keyboard();
+///
+///     rsx! {
+///         div {
+///             padding_bottom: "var(--dioxus-keyboard-height)",
+///             if !keyboard().visible {
+///                 nav { "Tabs are hidden while typing" }
+///             }
+///             input { placeholder: "Message" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_keyboard() -> ReadOnlySignal<KeyboardState> {
+    use_watch(WATCH_KEYBOARD, KeyboardState::default)
+}
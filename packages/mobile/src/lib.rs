@@ -2,8 +2,25 @@
 #![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 
+mod biometric;
+mod keyboard;
+mod safe_area;
+mod secure_storage;
+mod status_bar;
+mod watch;
+
+pub use biometric::{
+    authenticate, biometry, use_biometric_auth, BiometricAuth, BiometricError, BiometricStatus,
+    BiometryKind,
+};
 pub use dioxus_desktop::*;
 use dioxus_lib::prelude::*;
+pub use keyboard::{use_keyboard, KeyboardState};
+pub use safe_area::{use_safe_area, SafeAreaInsets};
+pub use secure_storage::{SecureStorage, SecureStorageError};
+pub use status_bar::{
+    set_status_bar_color, set_status_bar_hidden, set_status_bar_style, StatusBarStyle,
+};
 use std::sync::Mutex;
 
 pub mod launch_bindings {
@@ -0,0 +1,71 @@
+use crate::watch::use_watch;
+use dioxus_lib::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The parts of the screen at each edge that are covered by the notch, the status bar, the home indicator or rounded
+/// corners, in CSS pixels
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct SafeAreaInsets {
+    /// The inset at the top of the screen
+    pub top: f64,
+    /// The inset at the right of the screen
+    pub right: f64,
+    /// The inset at the bottom of the screen
+    pub bottom: f64,
+    /// The inset at the left of the screen
+    pub left: f64,
+}
+
+const WATCH_SAFE_AREA: &str = r#"
+// The page only reaches under the notch and the status bar if the viewport covers the whole screen
+const viewport = document.querySelector('meta[name="viewport"]');
+if (viewport && !viewport.content.includes("viewport-fit")) {
+    viewport.content += ", viewport-fit=cover";
+}
+const probe = document.createElement("div");
+probe.style.cssText = "position: fixed; visibility: hidden; pointer-events: none; "
+    + "padding: env(safe-area-inset-top) env(safe-area-inset-right) env(safe-area-inset-bottom) env(safe-area-inset-left);";
+document.body.appendChild(probe);
+const send = () => {
+    const style = getComputedStyle(probe);
+    dioxus.send({
+        top: parseFloat(style.paddingTop) || 0,
+        right: parseFloat(style.paddingRight) || 0,
+        bottom: parseFloat(style.paddingBottom) || 0,
+        left: parseFloat(style.paddingLeft) || 0,
+    });
+};
+send();
+window.addEventListener("resize", send);
+stop.then(() => {
+    window.removeEventListener("resize", send);
+    probe.remove();
+});
+"#;
+
+/// Watch the safe area insets of the screen. They change when the device is rotated.
+///
+/// Using the hook lets the page reach under the notch and the status bar by adding `viewport-fit=cover` to the
+/// viewport, so pad the content with the insets to keep it visible:
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus::mobile::use_safe_area;
+///
+/// fn App() -> Element {
+///     let insets = use_safe_area();
+///
+///     rsx! {
+///         div {
+///             padding_top: "{insets().top}px",
+///             padding_bottom: "{insets().bottom}px",
+///             "Hello from below the notch"
+///         }
+///     }
+/// }
+/// ```
+///
+/// The insets are zero on desktop and on Android devices that don't draw the app edge to edge.
+pub fn use_safe_area() -> ReadOnlySignal<SafeAreaInsets> {
+    use_watch(WATCH_SAFE_AREA, SafeAreaInsets::default)
+}
@@ -0,0 +1,406 @@
+use std::fmt::Display;
+
+/// A key-value store for secrets like auth tokens that is encrypted by the platform.
+///
+/// The values are stored in the Keychain on iOS and macOS. On Android they are encrypted with a key from the Android
+/// Keystore that never leaves the secure hardware of the device, and stored in the shared preferences of the app.
+///
+/// ```rust, no_run
+/// use dioxus::mobile::SecureStorage;
+///
+/// let storage = SecureStorage::new("com.example.app");
+/// storage.set("refresh_token", "secret").unwrap();
+/// assert_eq!(storage.get("refresh_token").unwrap().as_deref(), Some("secret"));
+/// storage.remove("refresh_token").unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SecureStorage {
+    service: String,
+}
+
+impl SecureStorage {
+    /// Open the storage of a service. Apps usually use their bundle identifier as the service.
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    /// Store a value, replacing the value that was stored for the key before
+    pub fn set(&self, key: &str, value: &str) -> Result<(), SecureStorageError> {
+        imp::set(&self.service, key, value)
+    }
+
+    /// Read the value of a key. Returns `None` if nothing is stored for the key.
+    pub fn get(&self, key: &str) -> Result<Option<String>, SecureStorageError> {
+        imp::get(&self.service, key)
+    }
+
+    /// Remove the value of a key. Removing a key that has no value is not an error.
+    pub fn remove(&self, key: &str) -> Result<(), SecureStorageError> {
+        imp::remove(&self.service, key)
+    }
+}
+
+/// An error reading or writing the [`SecureStorage`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SecureStorageError {
+    /// The platform has no secure storage
+    Unsupported,
+    /// The stored value could not be decrypted or is not valid UTF-8
+    Corrupted,
+    /// The platform failed to read or write the value
+    Platform(String),
+}
+
+impl Display for SecureStorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "secure storage is not supported on this platform"),
+            Self::Corrupted => write!(f, "the stored value is corrupted"),
+            Self::Platform(message) => write!(f, "secure storage failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SecureStorageError {}
+
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+mod imp {
+    use super::SecureStorageError;
+    use security_framework::passwords::{
+        delete_generic_password, get_generic_password, set_generic_password,
+    };
+
+    /// errSecItemNotFound
+    const ITEM_NOT_FOUND: i32 = -25300;
+
+    pub(super) fn set(service: &str, key: &str, value: &str) -> Result<(), SecureStorageError> {
+        set_generic_password(service, key, value.as_bytes()).map_err(platform)
+    }
+
+    pub(super) fn get(service: &str, key: &str) -> Result<Option<String>, SecureStorageError> {
+        match get_generic_password(service, key) {
+            Ok(value) => String::from_utf8(value)
+                .map(Some)
+                .map_err(|_| SecureStorageError::Corrupted),
+            Err(err) if err.code() == ITEM_NOT_FOUND => Ok(None),
+            Err(err) => Err(platform(err)),
+        }
+    }
+
+    pub(super) fn remove(service: &str, key: &str) -> Result<(), SecureStorageError> {
+        match delete_generic_password(service, key) {
+            Err(err) if err.code() != ITEM_NOT_FOUND => Err(platform(err)),
+            _ => Ok(()),
+        }
+    }
+
+    fn platform(err: security_framework::base::Error) -> SecureStorageError {
+        SecureStorageError::Platform(err.to_string())
+    }
+}
+
+#[cfg(target_os = "android")]
+mod imp {
+    use super::SecureStorageError;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use dioxus_android::clear_exception;
+    use jni::{
+        objects::{JByteArray, JObject, JString, JValue},
+        JNIEnv,
+    };
+
+    /// The length of the nonce `Cipher` generates for AES-GCM
+    const IV_LENGTH: usize = 12;
+    /// `Cipher.ENCRYPT_MODE` and `Cipher.DECRYPT_MODE`
+    const ENCRYPT_MODE: i32 = 1;
+    const DECRYPT_MODE: i32 = 2;
+
+    pub(super) fn set(service: &str, key: &str, value: &str) -> Result<(), SecureStorageError> {
+        with_env(|env, context| {
+            let secret = secret_key(env, service)?;
+            let cipher = cipher(env, &secret, None)?;
+            let iv = call_bytes(env, &cipher, "getIV", &[])?;
+            let input = env.byte_array_from_slice(value.as_bytes())?;
+            let encrypted = call_bytes(env, &cipher, "doFinal", &[(&input).into()])?;
+
+            let stored = env.new_string(STANDARD.encode([iv, encrypted].concat()))?;
+            let key = env.new_string(key)?;
+            let editor = editor(env, context, service)?;
+            env.call_method(
+                &editor,
+                "putString",
+                "(Ljava/lang/String;Ljava/lang/String;)Landroid/content/SharedPreferences$Editor;",
+                &[(&key).into(), (&stored).into()],
+            )?;
+            env.call_method(&editor, "commit", "()Z", &[])?;
+            Ok(Ok(()))
+        })
+    }
+
+    pub(super) fn get(service: &str, key: &str) -> Result<Option<String>, SecureStorageError> {
+        with_env(|env, context| {
+            let preferences = preferences(env, context, service)?;
+            let key = env.new_string(key)?;
+            let stored = env
+                .call_method(
+                    &preferences,
+                    "getString",
+                    "(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/String;",
+                    &[(&key).into(), (&JObject::null()).into()],
+                )?
+                .l()?;
+            if stored.is_null() {
+                return Ok(Ok(None));
+            }
+            let stored: String = env.get_string(&JString::from(stored))?.into();
+
+            let Some(stored) = STANDARD
+                .decode(stored)
+                .ok()
+                .filter(|stored| stored.len() > IV_LENGTH)
+            else {
+                return Ok(Err(SecureStorageError::Corrupted));
+            };
+            let (iv, encrypted) = stored.split_at(IV_LENGTH);
+
+            let secret = secret_key(env, service)?;
+            let cipher = cipher(env, &secret, Some(iv))?;
+            let input = env.byte_array_from_slice(encrypted)?;
+            let decrypted = call_bytes(env, &cipher, "doFinal", &[(&input).into()]);
+            let Ok(decrypted) = clear_exception(env, decrypted) else {
+                // The key was replaced or the value was changed, so it can't be read anymore
+                return Ok(Err(SecureStorageError::Corrupted));
+            };
+            Ok(String::from_utf8(decrypted)
+                .map(Some)
+                .map_err(|_| SecureStorageError::Corrupted))
+        })
+    }
+
+    pub(super) fn remove(service: &str, key: &str) -> Result<(), SecureStorageError> {
+        with_env(|env, context| {
+            let key = env.new_string(key)?;
+            let editor = editor(env, context, service)?;
+            env.call_method(
+                &editor,
+                "remove",
+                "(Ljava/lang/String;)Landroid/content/SharedPreferences$Editor;",
+                &[(&key).into()],
+            )?;
+            env.call_method(&editor, "commit", "()Z", &[])?;
+            Ok(Ok(()))
+        })
+    }
+
+    /// Run a function with the JNI environment of the current thread and the context of the app
+    fn with_env<T>(
+        f: impl FnOnce(&mut JNIEnv, &JObject) -> jni::errors::Result<Result<T, SecureStorageError>>,
+    ) -> Result<T, SecureStorageError> {
+        dioxus_android::with_env(f).unwrap_or_else(|err| Err(platform(err)))
+    }
+
+    /// Get the AES key of the service from the Android Keystore, or create it if it doesn't exist yet
+    fn secret_key<'a>(env: &mut JNIEnv<'a>, service: &str) -> jni::errors::Result<JObject<'a>> {
+        let provider = env.new_string("AndroidKeyStore")?;
+        let alias = env.new_string(format!("dioxus.secure_storage.{service}"))?;
+
+        let keystore = env
+            .call_static_method(
+                "java/security/KeyStore",
+                "getInstance",
+                "(Ljava/lang/String;)Ljava/security/KeyStore;",
+                &[(&provider).into()],
+            )?
+            .l()?;
+        env.call_method(
+            &keystore,
+            "load",
+            "(Ljava/security/KeyStore$LoadStoreParameter;)V",
+            &[(&JObject::null()).into()],
+        )?;
+
+        let exists = env
+            .call_method(
+                &keystore,
+                "containsAlias",
+                "(Ljava/lang/String;)Z",
+                &[(&alias).into()],
+            )?
+            .z()?;
+        if !exists {
+            let algorithm = env.new_string("AES")?;
+            let generator = env
+                .call_static_method(
+                    "javax/crypto/KeyGenerator",
+                    "getInstance",
+                    "(Ljava/lang/String;Ljava/lang/String;)Ljavax/crypto/KeyGenerator;",
+                    &[(&algorithm).into(), (&provider).into()],
+                )?
+                .l()?;
+
+            // KeyProperties.PURPOSE_ENCRYPT | KeyProperties.PURPOSE_DECRYPT
+            let builder = env.new_object(
+                "android/security/keystore/KeyGenParameterSpec$Builder",
+                "(Ljava/lang/String;I)V",
+                &[(&alias).into(), JValue::Int(1 | 2)],
+            )?;
+            let block_modes = string_array(env, "GCM")?;
+            env.call_method(
+                &builder,
+                "setBlockModes",
+                "([Ljava/lang/String;)Landroid/security/keystore/KeyGenParameterSpec$Builder;",
+                &[(&block_modes).into()],
+            )?;
+            let paddings = string_array(env, "NoPadding")?;
+            env.call_method(
+                &builder,
+                "setEncryptionPaddings",
+                "([Ljava/lang/String;)Landroid/security/keystore/KeyGenParameterSpec$Builder;",
+                &[(&paddings).into()],
+            )?;
+            let spec = env
+                .call_method(
+                    &builder,
+                    "build",
+                    "()Landroid/security/keystore/KeyGenParameterSpec;",
+                    &[],
+                )?
+                .l()?;
+
+            env.call_method(
+                &generator,
+                "init",
+                "(Ljava/security/spec/AlgorithmParameterSpec;)V",
+                &[(&spec).into()],
+            )?;
+            env.call_method(&generator, "generateKey", "()Ljavax/crypto/SecretKey;", &[])?;
+        }
+
+        env.call_method(
+            &keystore,
+            "getKey",
+            "(Ljava/lang/String;[C)Ljava/security/Key;",
+            &[(&alias).into(), (&JObject::null()).into()],
+        )?
+        .l()
+    }
+
+    /// Create an AES-GCM cipher that encrypts with a new nonce, or decrypts with the nonce of the value
+    fn cipher<'a>(
+        env: &mut JNIEnv<'a>,
+        secret: &JObject,
+        iv: Option<&[u8]>,
+    ) -> jni::errors::Result<JObject<'a>> {
+        let transformation = env.new_string("AES/GCM/NoPadding")?;
+        let cipher = env
+            .call_static_method(
+                "javax/crypto/Cipher",
+                "getInstance",
+                "(Ljava/lang/String;)Ljavax/crypto/Cipher;",
+                &[(&transformation).into()],
+            )?
+            .l()?;
+
+        match iv {
+            None => {
+                env.call_method(
+                    &cipher,
+                    "init",
+                    "(ILjava/security/Key;)V",
+                    &[JValue::Int(ENCRYPT_MODE), secret.into()],
+                )?;
+            }
+            Some(iv) => {
+                let iv = env.byte_array_from_slice(iv)?;
+                let spec = env.new_object(
+                    "javax/crypto/spec/GCMParameterSpec",
+                    "(I[B)V",
+                    &[JValue::Int(128), (&iv).into()],
+                )?;
+                env.call_method(
+                    &cipher,
+                    "init",
+                    "(ILjava/security/Key;Ljava/security/spec/AlgorithmParameterSpec;)V",
+                    &[JValue::Int(DECRYPT_MODE), secret.into(), (&spec).into()],
+                )?;
+            }
+        }
+
+        Ok(cipher)
+    }
+
+    /// Call a method of the cipher that returns a `byte[]`
+    fn call_bytes(
+        env: &mut JNIEnv,
+        cipher: &JObject,
+        method: &str,
+        args: &[JValue],
+    ) -> jni::errors::Result<Vec<u8>> {
+        let signature = match args.is_empty() {
+            true => "()[B",
+            false => "([B)[B",
+        };
+        let bytes = env.call_method(cipher, method, signature, args)?.l()?;
+        env.convert_byte_array(JByteArray::from(bytes))
+    }
+
+    fn string_array<'a>(env: &mut JNIEnv<'a>, value: &str) -> jni::errors::Result<JObject<'a>> {
+        let value = env.new_string(value)?;
+        Ok(env.new_object_array(1, "java/lang/String", &value)?.into())
+    }
+
+    fn preferences<'a>(
+        env: &mut JNIEnv<'a>,
+        context: &JObject,
+        service: &str,
+    ) -> jni::errors::Result<JObject<'a>> {
+        let name = env.new_string(format!("dioxus.secure_storage.{service}"))?;
+        // Context.MODE_PRIVATE
+        env.call_method(
+            context,
+            "getSharedPreferences",
+            "(Ljava/lang/String;I)Landroid/content/SharedPreferences;",
+            &[(&name).into(), JValue::Int(0)],
+        )?
+        .l()
+    }
+
+    fn editor<'a>(
+        env: &mut JNIEnv<'a>,
+        context: &JObject,
+        service: &str,
+    ) -> jni::errors::Result<JObject<'a>> {
+        let preferences = preferences(env, context, service)?;
+        env.call_method(
+            &preferences,
+            "edit",
+            "()Landroid/content/SharedPreferences$Editor;",
+            &[],
+        )?
+        .l()
+    }
+
+    fn platform(err: jni::errors::Error) -> SecureStorageError {
+        SecureStorageError::Platform(err.to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "macos", target_os = "android")))]
+mod imp {
+    use super::SecureStorageError;
+
+    pub(super) fn set(_service: &str, _key: &str, _value: &str) -> Result<(), SecureStorageError> {
+        Err(SecureStorageError::Unsupported)
+    }
+
+    pub(super) fn get(_service: &str, _key: &str) -> Result<Option<String>, SecureStorageError> {
+        Err(SecureStorageError::Unsupported)
+    }
+
+    pub(super) fn remove(_service: &str, _key: &str) -> Result<(), SecureStorageError> {
+        Err(SecureStorageError::Unsupported)
+    }
+}
@@ -0,0 +1,147 @@
+/// The color of the text and icons in the status bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StatusBarStyle {
+    /// Follow the light or dark appearance of the system
+    #[default]
+    Default,
+    /// Light text and icons for dark backgrounds
+    LightContent,
+    /// Dark text and icons for light backgrounds
+    DarkContent,
+}
+
+/// Set the color of the text and icons in the status bar.
+///
+/// This is only supported on Android. The status bar on iOS follows the appearance of the system.
+pub fn set_status_bar_style(style: StatusBarStyle) {
+    #[cfg(target_os = "android")]
+    android::with_status_bar(move |env, activity, _window, controller| {
+        let light_status_bar = match style {
+            StatusBarStyle::LightContent => false,
+            StatusBarStyle::DarkContent => true,
+            StatusBarStyle::Default => !android::is_dark_mode(env, activity)?,
+        };
+        env.call_method(
+            controller,
+            "setAppearanceLightStatusBars",
+            "(Z)V",
+            &[light_status_bar.into()],
+        )?;
+        Ok(())
+    });
+
+    #[cfg(not(target_os = "android"))]
+    let _ = style;
+}
+
+/// Set the background color of the status bar as RGBA.
+///
+/// This is only supported on Android. The status bar on iOS is transparent and shows the page below it if the page
+/// covers the whole screen, like after [`use_safe_area`](crate::use_safe_area) is used.
+pub fn set_status_bar_color(color: (u8, u8, u8, u8)) {
+    #[cfg(target_os = "android")]
+    android::with_status_bar(move |env, _activity, window, _controller| {
+        let (r, g, b, a) = color;
+        let argb = u32::from_be_bytes([a, r, g, b]) as i32;
+        env.call_method(window, "setStatusBarColor", "(I)V", &[argb.into()])?;
+        Ok(())
+    });
+
+    #[cfg(not(target_os = "android"))]
+    let _ = color;
+}
+
+/// Hide or show the status bar
+///
+/// ## Panics
+///
+/// This function will panic on iOS if it is called outside of the context of a Dioxus App.
+pub fn set_status_bar_hidden(hidden: bool) {
+    #[cfg(target_os = "android")]
+    android::with_status_bar(move |env, activity, _window, controller| {
+        let insets_type = dioxus_android::load_class(
+            env,
+            activity,
+            "androidx/core/view/WindowInsetsCompat$Type",
+        )?;
+        let status_bars = env
+            .call_static_method(insets_type, "statusBars", "()I", &[])?
+            .i()?;
+        let method = if hidden { "hide" } else { "show" };
+        env.call_method(controller, method, "(I)V", &[status_bars.into()])?;
+        Ok(())
+    });
+
+    #[cfg(target_os = "ios")]
+    {
+        use dioxus_desktop::tao::platform::ios::WindowExtIOS;
+        dioxus_desktop::window()
+            .window
+            .set_prefers_status_bar_hidden(hidden);
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    let _ = hidden;
+}
+
+#[cfg(target_os = "android")]
+mod android {
+    use dioxus_android::{clear_exception, load_class};
+    use dioxus_desktop::wry::prelude::{dispatch, jni, JNIEnv, JObject};
+
+    /// Run a function with the activity, its window and the `WindowInsetsControllerCompat` of the window on the UI
+    /// thread
+    pub(super) fn with_status_bar(
+        f: impl FnOnce(&mut JNIEnv, &JObject, &JObject, &JObject) -> jni::errors::Result<()>
+            + Send
+            + 'static,
+    ) {
+        dispatch(move |env, activity, _webview| {
+            let result = (|| {
+                let window = env
+                    .call_method(activity, "getWindow", "()Landroid/view/Window;", &[])?
+                    .l()?;
+                let decor_view = env
+                    .call_method(&window, "getDecorView", "()Landroid/view/View;", &[])?
+                    .l()?;
+                let window_compat = load_class(env, activity, "androidx/core/view/WindowCompat")?;
+                let controller = env
+                    .call_static_method(
+                        window_compat,
+                        "getInsetsController",
+                        "(Landroid/view/Window;Landroid/view/View;)Landroidx/core/view/WindowInsetsControllerCompat;",
+                        &[(&window).into(), (&decor_view).into()],
+                    )?
+                    .l()?;
+                f(env, activity, &window, &controller)
+            })();
+
+            if let Err(err) = clear_exception(env, result) {
+                tracing::error!("Failed to update the status bar: {err}");
+            }
+        });
+    }
+
+    /// Check if the system uses the dark theme
+    pub(super) fn is_dark_mode(env: &mut JNIEnv, activity: &JObject) -> jni::errors::Result<bool> {
+        let resources = env
+            .call_method(
+                activity,
+                "getResources",
+                "()Landroid/content/res/Resources;",
+                &[],
+            )?
+            .l()?;
+        let configuration = env
+            .call_method(
+                &resources,
+                "getConfiguration",
+                "()Landroid/content/res/Configuration;",
+                &[],
+            )?
+            .l()?;
+        let ui_mode = env.get_field(&configuration, "uiMode", "I")?.i()?;
+        // Configuration.UI_MODE_NIGHT_MASK and Configuration.UI_MODE_NIGHT_YES
+        Ok(ui_mode & 0x30 == 0x20)
+    }
+}
@@ -0,0 +1,35 @@
+use dioxus_lib::document::{eval, Eval};
+use dioxus_lib::prelude::*;
+use serde::de::DeserializeOwned;
+
+/// Run a script that sends a value every time it changes and keep the latest value in a signal while the component is
+/// mounted.
+///
+/// The script watches until rust sends a message, which happens when the component is dropped. It runs with a `stop`
+/// promise that resolves at that point and should remove its listeners after it.
+pub(crate) fn use_watch<T: DeserializeOwned + 'static>(
+    script: &'static str,
+    initial: impl FnOnce() -> T,
+) -> ReadOnlySignal<T> {
+    let mut value = use_signal(initial);
+    use_hook(|| {
+        let mut watch = Watch(eval(&format!(
+            "const stop = dioxus.recv();\n{script}\nawait stop;"
+        )));
+        // The watch is dropped with the task when the component is dropped, which stops the script
+        spawn(async move {
+            while let Ok(next) = watch.0.recv::<T>().await {
+                value.set(next);
+            }
+        })
+    });
+    value.into()
+}
+
+struct Watch(Eval);
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        _ = self.0.send(());
+    }
+}
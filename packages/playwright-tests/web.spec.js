@@ -63,7 +63,7 @@ test("input value", async ({ page }) => {
   await page.goto("http://localhost:9999");
 
   // Expect the page to contain the input with the value.
-  const input = page.locator("input");
+  const input = page.locator("input.value-input");
   await expect(input).toHaveValue("hello input");
 });
 
@@ -124,3 +124,32 @@ test("web-sys closure", async ({ page }) => {
   await page.keyboard.press("Enter");
   await expect(scrollDiv).toHaveText("the keydown event was triggered");
 });
+
+test("controlled input accepts edits", async ({ page }) => {
+  await page.goto("http://localhost:9999");
+
+  // The app sets the value to whatever the user types, so the edit stays
+  const input = page.locator("input.accepted-input");
+  await input.pressSequentially("hello");
+  await expect(input).toHaveValue("hello");
+});
+
+test("controlled input rejects edits", async ({ page }) => {
+  await page.goto("http://localhost:9999");
+
+  // The app only accepts digits, so the letters are reverted
+  const input = page.locator("input.rejected-input");
+  await input.pressSequentially("1a2b", { delay: 50 });
+  await expect(input).toHaveValue("12");
+});
+
+test("controlled input with only onchange", async ({ page }) => {
+  await page.goto("http://localhost:9999");
+
+  // The input doesn't listen to input events, so typing isn't reverted before the change event
+  const input = page.locator("input.onchange-input");
+  await input.pressSequentially("typed");
+  await expect(input).toHaveValue("typed");
+  await input.press("Enter");
+  await expect(page.locator("div.onchange-value")).toHaveText("typed");
+});
@@ -20,7 +20,7 @@ fn app() -> Element {
             class: "dangerous-inner-html-div",
             dangerous_inner_html: "<p>hello dangerous inner html</p>"
         }
-        input { value: "hello input" }
+        input { class: "value-input", value: "hello input" }
         div { class: "style-div", color: "red", "colored text" }
         button {
             class: "eval-button",
@@ -55,6 +55,7 @@ fn app() -> Element {
         PreventDefault {}
         OnMounted {}
         WebSysClosure {}
+        ControlledInputs {}
     }
 }
 
@@ -88,6 +89,37 @@ fn OnMounted() -> Element {
     }
 }
 
+// This component tests that controlled inputs only keep the edits the app accepts
+#[component]
+fn ControlledInputs() -> Element {
+    let mut accepted = use_signal(String::new);
+    let mut digits = use_signal(String::new);
+    let mut committed = use_signal(String::new);
+    rsx! {
+        input {
+            class: "accepted-input",
+            value: "{accepted}",
+            oninput: move |evt| accepted.set(evt.value()),
+        }
+        input {
+            class: "rejected-input",
+            value: "{digits}",
+            oninput: move |evt| {
+                let value = evt.value();
+                if value.chars().all(|c| c.is_ascii_digit()) {
+                    digits.set(value);
+                }
+            },
+        }
+        input {
+            class: "onchange-input",
+            value: "{committed}",
+            onchange: move |evt| committed.set(evt.value()),
+        }
+        div { class: "onchange-value", "{committed}" }
+    }
+}
+
 // This component tests attaching an event listener to the document with a web-sys closure
 // and effect
 #[component]
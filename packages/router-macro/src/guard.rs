@@ -0,0 +1,22 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Expr, Ident};
+
+/// Create the arm of `Routable::guard` for a route that runs each guard in order. The first guard
+/// that doesn't allow the navigation decides where the router goes.
+pub fn guard_match(route_name: &Ident, guards: &[&Expr]) -> TokenStream {
+    quote! {
+        Self::#route_name { .. } => {
+            let myself = self.clone();
+            Some(Box::pin(async move {
+                #(
+                    match (#guards)(myself.clone()).await {
+                        dioxus_router::navigation::GuardResult::Allow => {}
+                        result => return result,
+                    }
+                )*
+                dioxus_router::navigation::GuardResult::Allow
+            }))
+        }
+    }
+}
@@ -3,6 +3,8 @@ use syn::{Ident, Type};
 
 use proc_macro2::TokenStream as TokenStream2;
 
+use crate::query::strip_option;
+
 #[derive(Debug)]
 pub struct HashFragment {
     pub ident: Ident,
@@ -17,15 +19,34 @@ impl HashFragment {
     pub fn parse(&self) -> TokenStream2 {
         let ident = &self.ident;
         let ty = &self.ty;
-        quote! {
-            let #ident = <#ty as dioxus_router::routable::FromHashFragment>::from_hash_fragment(&*hash);
+        match strip_option(ty) {
+            // An optional hash fragment is only set if the route has a hash
+            Some(inner) => quote! {
+                let #ident = (!hash.is_empty()).then(|| <#inner as dioxus_router::routable::FromHashFragment>::from_hash_fragment(&*hash));
+            },
+            None => quote! {
+                let #ident = <#ty as dioxus_router::routable::FromHashFragment>::from_hash_fragment(&*hash);
+            },
         }
     }
 
     pub fn write(&self) -> TokenStream2 {
         let ident = &self.ident;
-        quote! {
-            write!(f, "#{}", #ident)?;
+        let write = quote! {
+            let as_string = #ident.to_string();
+            write!(f, "#{}", dioxus_router::exports::urlencoding::encode(&as_string))?;
+        };
+        match strip_option(&self.ty) {
+            Some(_) => quote! {
+                if let Some(#ident) = #ident {
+                    #write
+                }
+            },
+            None => quote! {
+                {
+                    #write
+                }
+            },
         }
     }
 
@@ -1,8 +1,10 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::Path;
+use syn::{Expr, Path};
 
+use crate::loader::render_with_loader;
 use crate::nest::{Nest, NestId};
+use crate::options::RouteOptions;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LayoutId(pub usize);
@@ -11,20 +13,32 @@ pub struct LayoutId(pub usize);
 pub struct Layout {
     pub comp: Path,
     pub active_nests: Vec<NestId>,
+    pub loader: Option<Expr>,
+    pub guard: Option<Expr>,
 }
 
 impl Layout {
     pub fn routable_match(&self, nests: &[Nest]) -> TokenStream {
         let comp_name = &self.comp;
-        let dynamic_segments = self
+        let dynamic_segments: Vec<_> = self
             .active_nests
             .iter()
-            .flat_map(|id| nests[id.0].dynamic_segments());
+            .flat_map(|id| nests[id.0].dynamic_segments())
+            .collect();
 
-        quote! {
+        let render = quote! {
             rsx! {
                 #comp_name { #(#dynamic_segments: #dynamic_segments,)* }
             }
+        };
+
+        match &self.loader {
+            Some(loader) => {
+                // The layout loads again when the segments of its nests change
+                let deps = quote! { vec![#(#dynamic_segments.to_string(),)*] };
+                render_with_loader(loader, deps, &dynamic_segments, render)
+            }
+            None => render,
         }
     }
 }
@@ -34,7 +48,13 @@ impl Layout {
         // Then parse the component name
         let _ = input.parse::<syn::Token![,]>();
         let comp: Path = input.parse()?;
+        let RouteOptions { loader, guard } = RouteOptions::parse(input)?;
 
-        Ok(Self { comp, active_nests })
+        Ok(Self {
+            comp,
+            active_nests,
+            loader,
+            guard,
+        })
     }
 }
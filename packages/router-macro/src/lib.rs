@@ -16,9 +16,12 @@ use proc_macro2::TokenStream as TokenStream2;
 
 use crate::{layout::LayoutId, route_tree::ParseRouteTree};
 
+mod guard;
 mod hash;
 mod layout;
+mod loader;
 mod nest;
+mod options;
 mod query;
 mod redirect;
 mod route;
@@ -37,6 +40,9 @@ mod segment;
 /// 2. Dynamic Segments: "/:dynamic" (where dynamic has a type that is FromStr in all child Variants)
 /// 3. Catch all Segments: "/:..segments" (where segments has a type that is FromSegments in all child Variants)
 /// 4. Query Segments: "/?:..query" (where query has a type that is FromQuery in all child Variants) or "/?:query&:other_query" (where query and other_query has a type that is FromQueryArgument in all child Variants)
+/// 5. Hash Segments: "/#:hash" (where hash has a type that is FromHashFragment in all child Variants)
+///
+/// Query arguments and hash segments can be optional by using a type of `Option<T>` where `T` is FromQueryArgument or FromHashFragment. Optional values that are `None` are left out of the route.
 ///
 /// Routes are matched:
 /// 1. By there specificity this order: Query Routes ("/?:query"), Static Routes ("/route"), Dynamic Routes ("/:route"), Catch All Routes ("/:..route")
@@ -97,6 +103,10 @@ mod segment;
 /// - `path`: The path to the enum variant (relative to the parent nest)
 /// - (optional) `component`: The component to render when the route is matched. If not specified, the name of the variant is used
 ///
+/// After the component, routes (and layouts) accept these optional named arguments:
+/// - `loader = function`: An async function that loads data for the component. It takes the dynamic parameters of the route and its output is read with `use_loader`
+/// - `guard = function`: An async function that takes the route and decides if the router can navigate to it by returning a `GuardResult`
+///
 /// Routes are the most basic attribute. They allow you to define a route and the component to render when the route is matched. The component must take all dynamic parameters of the route and all parent nests.
 /// The next variant will be tied to the component. If you link to that variant, the component will be rendered.
 ///
@@ -114,6 +124,36 @@ mod segment;
 /// # fn Index() -> Element { VNode::empty() }
 /// ```
 ///
+/// Guards run before the router navigates to the route. They can allow the navigation, cancel it,
+/// or redirect it to another route:
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_router::navigation::GuardResult;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/login")]
+///     Login {},
+///     // Admin only renders if require_admin allows it
+///     #[route("/admin", guard = require_admin)]
+///     Admin {},
+/// }
+///
+/// async fn require_admin(_route: Route) -> GuardResult<Route> {
+///     let is_admin = false;
+///     if is_admin {
+///         GuardResult::Allow
+///     } else {
+///         GuardResult::Redirect(Route::Login {}.into())
+///     }
+/// }
+/// # #[component]
+/// # fn Login() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Admin() -> Element { VNode::empty() }
+/// ```
+///
 /// # `#[redirect("path", function)]`
 ///
 /// The `#[redirect]` attribute is used to define a redirect. It takes 2 parameters:
@@ -600,11 +640,11 @@ impl RouteEnum {
                 fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
                     let route = s;
                     let (route, hash) = route.split_once('#').unwrap_or((route, ""));
-                    let (route, query) = route.split_once('?').unwrap_or((route, ""));
+                    let (route, raw_query) = route.split_once('?').unwrap_or((route, ""));
                     // Remove any trailing slashes. We parse /route/ and /route in the same way
                     // Note: we don't use trim because it includes more code
                     let route = route.strip_suffix('/').unwrap_or(route);
-                    let query = dioxus_router::exports::urlencoding::decode(query).unwrap_or(query.into());
+                    let query = dioxus_router::exports::urlencoding::decode(raw_query).unwrap_or(raw_query.into());
                     let hash = dioxus_router::exports::urlencoding::decode(hash).unwrap_or(hash.into());
                     let mut segments = route.split('/').map(|s| dioxus_router::exports::urlencoding::decode(s).unwrap_or(s.into()));
                     // skip the first empty segment
@@ -733,14 +773,28 @@ impl RouteEnum {
         let site_map = &self.site_map;
 
         let mut matches = Vec::new();
+        let mut guard_matches = Vec::new();
 
         // Collect all routes matches
         for route in &self.endpoints {
             if let RouteEndpoint::Route(route) = route {
                 matches.push(route.routable_match(&self.layouts, &self.nests));
+                guard_matches.extend(route.guard_match(&self.layouts));
             }
         }
 
+        // Only override the default guard if some route has a guard
+        let guard_impl = (!guard_matches.is_empty()).then(|| {
+            quote! {
+                fn guard(&self) -> Option<dioxus_router::navigation::GuardFuture<Self>> {
+                    match self {
+                        #(#guard_matches)*
+                        _ => None,
+                    }
+                }
+            }
+        });
+
         quote! {
             impl dioxus_router::routable::Routable for #name where Self: Clone {
                 const SITE_MAP: &'static [dioxus_router::routable::SiteMapSegment] = &[
@@ -754,6 +808,8 @@ impl RouteEnum {
                         _ => VNode::empty()
                     }
                 }
+
+                #guard_impl
             }
         }
     }
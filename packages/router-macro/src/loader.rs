@@ -0,0 +1,31 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Expr;
+
+/// Wrap the rendering of a route or layout in a component that runs the loader with `args` and
+/// suspends until the data is ready
+pub fn render_with_loader(
+    loader: &Expr,
+    deps: TokenStream,
+    args: &[TokenStream],
+    render: TokenStream,
+) -> TokenStream {
+    quote! {
+        {
+            let __loader_deps: Vec<String> = #deps;
+            let __loader = {
+                #(let #args = #args.clone();)*
+                std::rc::Rc::new(move || {
+                    Box::pin((#loader)(#(#args.clone(),)*)) as dioxus_router::components::LoaderFuture<_>
+                }) as dioxus_router::components::Loader<_>
+            };
+            rsx! {
+                dioxus_router::components::RouteLoader {
+                    deps: __loader_deps,
+                    loader: __loader,
+                    {#render}
+                }
+            }
+        }
+    }
+}
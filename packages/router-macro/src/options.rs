@@ -0,0 +1,48 @@
+use syn::{parse::ParseStream, Expr, Ident, Token};
+
+/// The optional arguments after the component of a `#[route]` or `#[layout]` attribute like
+/// `loader = load_data` or `guard = require_login`
+#[derive(Debug, Default)]
+pub struct RouteOptions {
+    pub loader: Option<Expr>,
+    pub guard: Option<Expr>,
+}
+
+impl RouteOptions {
+    pub fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut options = Self::default();
+        loop {
+            let _ = input.parse::<Token![,]>();
+            if input.is_empty() {
+                return Ok(options);
+            }
+            let name: Ident = input.parse()?;
+            let slot = if name == "loader" {
+                &mut options.loader
+            } else if name == "guard" {
+                &mut options.guard
+            } else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Expected a loader or guard argument like `loader = load_data` or `guard = require_login`",
+                ));
+            };
+            input.parse::<Token![=]>()?;
+            if slot.replace(input.parse()?).is_some() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("The {name} argument can only be set once"),
+                ));
+            }
+        }
+    }
+
+    /// Check if the next argument of an attribute is an option instead of a component
+    pub fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        let _ = fork.parse::<Token![,]>();
+        fork.parse::<Ident>()
+            .is_ok_and(|name| name == "loader" || name == "guard")
+            && fork.peek(Token![=])
+    }
+}
@@ -24,7 +24,17 @@ impl QuerySegment {
             QuerySegment::Single(segment) => segment.parse(),
             QuerySegment::Segments(segments) => {
                 let mut tokens = TokenStream2::new();
-                tokens.extend(quote! { let split_query: std::collections::HashMap<&str, &str> = query.split('&').filter_map(|s| s.split_once('=')).collect(); });
+                // Split the query before decoding it so encoded `&` and `=` characters stay in the values
+                tokens.extend(quote! {
+                    let split_query: std::collections::HashMap<_, _> = raw_query
+                        .split('&')
+                        .filter_map(|s| s.split_once('='))
+                        .map(|(name, value)| {
+                            let decode = |s: &str| dioxus_router::exports::urlencoding::decode(s).map_or_else(|_| s.to_string(), |s| s.into_owned());
+                            (decode(name), decode(value))
+                        })
+                        .collect();
+                });
                 for segment in segments {
                     tokens.extend(segment.parse());
                 }
@@ -37,17 +47,14 @@ impl QuerySegment {
         match self {
             QuerySegment::Single(segment) => segment.write(),
             QuerySegment::Segments(segments) => {
-                let mut tokens = TokenStream2::new();
-                tokens.extend(quote! { write!(f, "?")?; });
-                let mut segments_iter = segments.iter();
-                if let Some(first_segment) = segments_iter.next() {
-                    tokens.extend(first_segment.write());
-                }
-                for segment in segments_iter {
-                    tokens.extend(quote! { write!(f, "&")?; });
-                    tokens.extend(segment.write());
+                let segments = segments.iter().map(|segment| segment.write());
+                quote! {
+                    {
+                        // Optional arguments that are not set are left out of the query
+                        let mut separator = '?';
+                        #(#segments)*
+                    }
                 }
-                tokens
             }
         }
     }
@@ -149,21 +156,58 @@ impl QueryArgument {
     pub fn parse(&self) -> TokenStream2 {
         let ident = &self.ident;
         let ty = &self.ty;
-        quote! {
-            let #ident = match split_query.get(stringify!(#ident)) {
-                Some(query_argument) => <#ty as dioxus_router::routable::FromQueryArgument>::from_query_argument(query_argument).unwrap_or_default(),
-                None => <#ty as Default>::default(),
-            };
+        match strip_option(ty) {
+            Some(inner) => quote! {
+                let #ident = split_query
+                    .get(stringify!(#ident))
+                    .and_then(|query_argument| <#inner as dioxus_router::routable::FromQueryArgument>::from_query_argument(query_argument).ok());
+            },
+            None => quote! {
+                let #ident = match split_query.get(stringify!(#ident)) {
+                    Some(query_argument) => <#ty as dioxus_router::routable::FromQueryArgument>::from_query_argument(query_argument).unwrap_or_default(),
+                    None => <#ty as Default>::default(),
+                };
+            },
         }
     }
 
+    /// Write the argument after the `separator` variable in the query
     pub fn write(&self) -> TokenStream2 {
         let ident = &self.ident;
-        quote! {
-            {
-                let as_string = #ident.to_string();
-                write!(f, "{}={}", stringify!(#ident), dioxus_router::exports::urlencoding::encode(&as_string))?;
-            }
+        let write = quote! {
+            let as_string = #ident.to_string();
+            write!(f, "{}{}={}", separator, stringify!(#ident), dioxus_router::exports::urlencoding::encode(&as_string))?;
+            separator = '&';
+        };
+        match strip_option(&self.ty) {
+            Some(_) => quote! {
+                if let Some(#ident) = #ident {
+                    #write
+                }
+            },
+            None => quote! {
+                {
+                    #write
+                }
+            },
         }
     }
 }
+
+/// Get the `T` in a field with the type `Option<T>`
+pub fn strip_option(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
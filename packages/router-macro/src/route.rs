@@ -2,6 +2,7 @@ use quote::{format_ident, quote};
 use syn::parse::Parse;
 use syn::parse::ParseStream;
 use syn::parse_quote;
+use syn::Expr;
 use syn::Field;
 use syn::Path;
 use syn::Type;
@@ -9,11 +10,14 @@ use syn::{Ident, LitStr};
 
 use proc_macro2::TokenStream as TokenStream2;
 
+use crate::guard::guard_match;
 use crate::hash::HashFragment;
 use crate::layout::Layout;
 use crate::layout::LayoutId;
+use crate::loader::render_with_loader;
 use crate::nest::Nest;
 use crate::nest::NestId;
+use crate::options::RouteOptions;
 use crate::query::QuerySegment;
 use crate::segment::create_error_type;
 use crate::segment::parse_route_segments;
@@ -22,18 +26,24 @@ use crate::segment::RouteSegment;
 struct RouteArgs {
     route: LitStr,
     comp_name: Option<Path>,
+    options: RouteOptions,
 }
 
 impl Parse for RouteArgs {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         let route = input.parse::<LitStr>()?;
 
+        let comp_name = if RouteOptions::peek(input) {
+            None
+        } else {
+            let _ = input.parse::<syn::Token![,]>();
+            input.parse().ok()
+        };
+
         Ok(RouteArgs {
             route,
-            comp_name: {
-                let _ = input.parse::<syn::Token![,]>();
-                input.parse().ok()
-            },
+            comp_name,
+            options: RouteOptions::parse(input)?,
         })
     }
 }
@@ -60,6 +70,8 @@ pub(crate) struct Route {
     pub hash: Option<HashFragment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
+    loader: Option<Expr>,
+    guard: Option<Expr>,
     fields: Vec<(Ident, Type)>,
 }
 
@@ -75,6 +87,7 @@ impl Route {
             .find(|attr| attr.path().is_ident("route"));
         let route;
         let ty;
+        let mut options = RouteOptions::default();
         let route_name = variant.ident.clone();
         match route_attr {
             Some(attr) => {
@@ -84,6 +97,7 @@ impl Route {
                     component: comp_name,
                 };
                 route = args.route.value();
+                options = args.options;
             }
             None => {
                 if let Some(route_attr) = variant
@@ -163,6 +177,8 @@ impl Route {
             hash,
             nests,
             layouts,
+            loader: options.loader,
+            guard: options.guard,
             fields,
         })
     }
@@ -264,15 +280,24 @@ impl Route {
             }
             RouteType::Leaf { component } => {
                 let dynamic_segments = self.dynamic_segments();
-                let dynamic_segments_from_route = self.dynamic_segments();
+                let dynamic_segments_from_route: Vec<_> = self.dynamic_segments().collect();
+                let mut render = quote! {
+                    rsx! {
+                        #component {
+                            #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
+                        }
+                    }
+                };
+                if let Some(loader) = &self.loader {
+                    // The route loads again when any part of the route changes
+                    let deps = quote! { vec![self.to_string()] };
+                    render =
+                        render_with_loader(loader, deps, &dynamic_segments_from_route, render);
+                }
                 quote! {
                     #[allow(unused)]
                     (#last_index, Self::#name { #(#dynamic_segments,)* }) => {
-                        rsx! {
-                            #component {
-                                #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
-                            }
-                        }
+                        #render
                     }
                 }
             }
@@ -287,6 +312,17 @@ impl Route {
         })
     }
 
+    /// The arm of `Routable::guard` for this route if it or any of its layouts has a guard
+    pub fn guard_match(&self, layouts: &[Layout]) -> Option<TokenStream2> {
+        let guards: Vec<_> = self
+            .layouts
+            .iter()
+            .filter_map(|id| layouts[id.0].guard.as_ref())
+            .chain(self.guard.as_ref())
+            .collect();
+        (!guards.is_empty()).then(|| guard_match(&self.route_name, &guards))
+    }
+
     pub fn construct(&self, nests: &[Nest], enum_name: Ident) -> TokenStream2 {
         let segments = self.fields.iter().map(|(name, _)| {
             let mut from_route = false;
@@ -0,0 +1,97 @@
+//! Components that allow the macro to run the loaders of routes and layouts before they render.
+
+use std::{future::Future, pin::Pin, rc::Rc};
+
+use dioxus_lib::prelude::*;
+
+/// The future returned by a route loader
+pub type LoaderFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+/// A function that starts loading the data for a route
+pub type Loader<T> = Rc<dyn Fn() -> LoaderFuture<T>>;
+
+/// Data that a route loader can return.
+///
+/// With the `fullstack` feature enabled, the data is serialized into the page on the server and
+/// hydrated on the client, so it must implement `Serialize` and `DeserializeOwned`.
+#[cfg(feature = "fullstack")]
+pub trait LoaderOutput: serde::Serialize + serde::de::DeserializeOwned + 'static {}
+
+#[cfg(feature = "fullstack")]
+impl<T: serde::Serialize + serde::de::DeserializeOwned + 'static> LoaderOutput for T {}
+
+/// Data that a route loader can return.
+///
+/// With the `fullstack` feature enabled, the data is serialized into the page on the server and
+/// hydrated on the client, so it must implement `Serialize` and `DeserializeOwned`.
+#[cfg(not(feature = "fullstack"))]
+pub trait LoaderOutput: 'static {}
+
+#[cfg(not(feature = "fullstack"))]
+impl<T: 'static> LoaderOutput for T {}
+
+/// The data loaded for the closest route or layout with a loader of type `T`
+pub(crate) struct LoaderData<T: 'static>(pub(crate) Resource<T>);
+
+impl<T> Clone for LoaderData<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for LoaderData<T> {}
+
+/// Props for the [`RouteLoader`] component.
+#[derive(Props)]
+pub struct RouteLoaderProps<T: LoaderOutput> {
+    /// The values the loader depends on. The loader runs again when they change
+    deps: Vec<String>,
+    /// Load the data for the route
+    loader: Loader<T>,
+    /// The route or layout that uses the data
+    children: Element,
+}
+
+impl<T: LoaderOutput> Clone for RouteLoaderProps<T> {
+    fn clone(&self) -> Self {
+        Self {
+            deps: self.deps.clone(),
+            loader: self.loader.clone(),
+            children: self.children.clone(),
+        }
+    }
+}
+
+impl<T: LoaderOutput> PartialEq for RouteLoaderProps<T> {
+    fn eq(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+/// A component that runs the loader of a route and suspends until the data is ready. The `loader`
+/// argument of the `#[route]` and `#[layout]` attributes on the router macro will insert this
+/// automatically.
+///
+/// With the `fullstack` feature, the loader runs on the server during server side rendering and the
+/// data is hydrated on the client without running the loader again.
+#[allow(missing_docs)]
+pub fn RouteLoader<T: LoaderOutput>(props: RouteLoaderProps<T>) -> Element {
+    // The resource keeps the loader from the first render, so it reads the latest loader
+    let mut loader = use_hook(|| CopyValue::new(props.loader.clone()));
+    loader.set(props.loader.clone());
+    let load = use_reactive((&props.deps,), move |_| (loader.read())());
+
+    #[cfg(feature = "fullstack")]
+    let data = dioxus_fullstack::prelude::use_server_future(load)?;
+
+    #[cfg(not(feature = "fullstack"))]
+    let data = {
+        let data = use_resource(load);
+        data.suspend()?;
+        data
+    };
+
+    use_context_provider(|| LoaderData(data));
+
+    props.children
+}
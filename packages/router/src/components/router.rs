@@ -6,6 +6,7 @@ use crate::{
     prelude::{provide_router_context, Outlet},
     routable::Routable,
     router_cfg::RouterConfig,
+    transition::RouterTransitions,
 };
 
 /// The props for [`Router`].
@@ -45,14 +46,26 @@ where
 {
     use crate::prelude::{outlet::OutletContext, RouterContext};
 
-    use_hook(|| {
-        provide_router_context(RouterContext::new(props.config.call(())));
+    let router = use_hook(|| {
+        let mut config = props.config.call(());
+        if let Some(transition) = config.transition.take() {
+            provide_context(RouterTransitions(transition));
+        }
+        let router = RouterContext::new(config);
+        provide_router_context(router);
 
         provide_context(OutletContext::<R> {
             current_level: 0,
             _marker: std::marker::PhantomData,
         });
+
+        router
     });
 
+    router.scroll_after_render();
+    if !router.guard_current_route()? {
+        return VNode::empty();
+    }
+
     rsx! { Outlet::<R> {} }
 }
@@ -1,6 +1,10 @@
 use dioxus_lib::prelude::*;
 
-use crate::{routable::Routable, utils::use_router_internal::use_router_internal};
+use crate::{
+    routable::Routable,
+    transition::{render_transition_outlet, FrameRoute, RouterTransitions},
+    utils::use_router_internal::use_router_internal,
+};
 
 pub(crate) struct OutletContext<R> {
     pub current_level: usize,
@@ -48,6 +52,19 @@ impl<R> OutletContext<R> {
             };
         }
 
-        router.current::<R>().render(current_level)
+        // The root outlet animates between routes if the router has transitions
+        if current_level == 0 {
+            if let Some(transitions) = try_consume_context::<RouterTransitions<R>>() {
+                return render_transition_outlet(router, transitions, current_level);
+            }
+        }
+
+        let route = router.current::<R>();
+        // Inside of an outgoing route, keep rendering the route the transition started from
+        let route = match try_consume_context::<FrameRoute<R>>() {
+            Some(frame) => frame.0.cloned(),
+            None => route,
+        };
+        route.render(current_level)
     }
 }
@@ -1,14 +1,20 @@
 use std::{
     collections::HashSet,
+    rc::Rc,
     sync::{Arc, Mutex},
 };
 
 use dioxus_history::history;
 use dioxus_lib::prelude::*;
+use futures_util::FutureExt;
 
 use crate::{
-    components::child_router::consume_child_route_mapping, navigation::NavigationTarget,
-    prelude::SiteMapSegment, routable::Routable, router_cfg::RouterConfig,
+    components::child_router::{consume_child_route_mapping, ChildRouteMapping},
+    navigation::{GuardFuture, GuardResult, NavigationTarget},
+    prelude::SiteMapSegment,
+    routable::Routable,
+    router_cfg::RouterConfig,
+    scroll::{NavigationKind, RouteScroll, ScrollState},
 };
 
 /// This context is set in the root of the virtual dom if there is a router present.
@@ -46,6 +52,31 @@ pub(crate) type RoutingCallback<R> =
     Arc<dyn Fn(GenericRouterContext<R>) -> Option<NavigationTarget<R>>>;
 pub(crate) type AnyRoutingCallback = Arc<dyn Fn(RouterContext) -> Option<NavigationTarget>>;
 
+/// Runs the guards of a route. Returns `None` if the route doesn't have any guards.
+type AnyGuard = Rc<dyn Fn(&str) -> Option<GuardFuture<String>>>;
+
+/// Parse a route from the root router, mapping it to the child route if this is a child router
+fn parse_route<R: Routable>(route: &str, mapping: Option<&ChildRouteMapping<R>>) -> Option<R> {
+    match mapping {
+        Some(mapping) => mapping.parse_route_from_root_route(route),
+        None => R::from_str(route).ok(),
+    }
+}
+
+/// Convert a target of this router to a target of the root router
+fn root_target<R: Routable>(
+    target: NavigationTarget<R>,
+    mapping: Option<&ChildRouteMapping<R>>,
+) -> NavigationTarget {
+    match target {
+        NavigationTarget::Internal(r) => match mapping {
+            Some(mapping) => NavigationTarget::Internal(mapping.format_route_as_root_route(r)),
+            None => NavigationTarget::Internal(r.to_string()),
+        },
+        NavigationTarget::External(s) => NavigationTarget::External(s),
+    }
+}
+
 struct RouterContextInner {
     unresolved_error: Option<ExternalNavigationFailure>,
 
@@ -57,6 +88,23 @@ struct RouterContextInner {
     internal_route: fn(&str) -> bool,
 
     site_map: &'static [SiteMapSegment],
+
+    scroll: ScrollState,
+    /// The navigation the page still needs to scroll for. This is set from outside of the runtime by the history
+    /// updater, so it needs to be thread safe
+    pending_scroll: Arc<Mutex<Option<NavigationKind>>>,
+
+    guard: AnyGuard,
+    /// The scope of the router component. Guards that need to wait run in this scope
+    scope: ScopeId,
+    /// A navigation from the router that is waiting for the guards of the new route
+    pending_navigation: Option<Task>,
+    /// The last route the guards allowed
+    allowed_route: Option<String>,
+    /// A route that the guards cancelled without another route to go back to
+    blocked_route: Option<String>,
+    /// The guards of a route that was opened without going through the router
+    pending_guard: Option<(String, Task)>,
 }
 
 impl RouterContextInner {
@@ -72,6 +120,11 @@ impl RouterContextInner {
         }
     }
 
+    fn before_navigation(&self, kind: NavigationKind) {
+        self.scroll.save();
+        *self.pending_scroll.lock().unwrap() = Some(kind);
+    }
+
     fn external(&mut self, external: String) -> Option<ExternalNavigationFailure> {
         match history().external(external.clone()) {
             true => None,
@@ -99,8 +152,38 @@ impl RouterContext {
         <R as std::str::FromStr>::Err: std::fmt::Display,
     {
         let subscribers = Arc::new(Mutex::new(HashSet::new()));
+        let pending_scroll = Arc::new(Mutex::new(None));
         let mapping = consume_child_route_mapping();
 
+        let route_scroll: Rc<dyn Fn(&str) -> RouteScroll> = match cfg.scroll {
+            Some(scroll) => {
+                let mapping = mapping.clone();
+                Rc::new(move |route| {
+                    parse_route::<R>(route, mapping.as_ref())
+                        .map(|route| scroll(&route))
+                        .unwrap_or_default()
+                })
+            }
+            None => Rc::new(|_| RouteScroll::default()),
+        };
+
+        let guard: AnyGuard = {
+            let mapping = mapping.clone();
+            Rc::new(move |route| {
+                let guard = parse_route::<R>(route, mapping.as_ref())?.guard()?;
+                let mapping = mapping.clone();
+                Some(Box::pin(async move {
+                    match guard.await {
+                        GuardResult::Allow => GuardResult::Allow,
+                        GuardResult::Cancel => GuardResult::Cancel,
+                        GuardResult::Redirect(target) => {
+                            GuardResult::Redirect(root_target(target, mapping.as_ref()))
+                        }
+                    }
+                }))
+            })
+        };
+
         let myself = RouterContextInner {
             unresolved_error: None,
             subscribers: subscribers.clone(),
@@ -110,15 +193,7 @@ impl RouterContext {
                         inner: ctx,
                         _marker: std::marker::PhantomData,
                     };
-                    update(ctx).map(|t| match t {
-                        NavigationTarget::Internal(r) => match mapping.as_ref() {
-                            Some(mapping) => {
-                                NavigationTarget::Internal(mapping.format_route_as_root_route(r))
-                            }
-                            None => NavigationTarget::Internal(r.to_string()),
-                        },
-                        NavigationTarget::External(s) => NavigationTarget::External(s),
-                    })
+                    update(ctx).map(|t| root_target(t, mapping.as_ref()))
                 }) as Arc<dyn Fn(RouterContext) -> Option<NavigationTarget>>
             }),
 
@@ -127,10 +202,23 @@ impl RouterContext {
             internal_route: |route| R::from_str(route).is_ok(),
 
             site_map: R::SITE_MAP,
+
+            scroll: ScrollState::new(route_scroll),
+            pending_scroll: pending_scroll.clone(),
+
+            guard,
+            scope: current_scope_id().expect("the router must be created in a component"),
+            pending_navigation: None,
+            allowed_route: None,
+            blocked_route: None,
+            pending_guard: None,
         };
+        myself.scroll.track();
 
         // set the updater
         history().updater(Arc::new(move || {
+            // Updates from outside the router come from the back and forward buttons of the platform
+            *pending_scroll.lock().unwrap() = Some(NavigationKind::Traverse);
             for &rc in subscribers.lock().unwrap().iter() {
                 rc.mark_dirty();
             }
@@ -163,6 +251,7 @@ impl RouterContext {
     ///
     /// Will fail silently if there is no previous location to go to.
     pub fn go_back(&self) {
+        self.before_navigation(NavigationKind::Traverse);
         history().go_back();
         self.change_route();
     }
@@ -171,60 +260,104 @@ impl RouterContext {
     ///
     /// Will fail silently if there is no next location to go to.
     pub fn go_forward(&self) {
+        self.before_navigation(NavigationKind::Traverse);
         history().go_forward();
         self.change_route();
     }
 
     pub(crate) fn push_any(&self, target: NavigationTarget) -> Option<ExternalNavigationFailure> {
-        {
-            let mut write = self.inner.write_unchecked();
-            match target {
-                NavigationTarget::Internal(p) => history().push(p),
-                NavigationTarget::External(e) => return write.external(e),
-            }
-        }
-
-        self.change_route()
+        self.navigate(target, NavigationKind::Push)
     }
 
     /// Push a new location.
     ///
     /// The previous location will be available to go back to.
+    ///
+    /// If the new route has a guard that needs to wait, the router navigates after the guard
+    /// finishes.
     pub fn push(&self, target: impl Into<NavigationTarget>) -> Option<ExternalNavigationFailure> {
-        let target = target.into();
-        {
-            let mut write = self.inner.write_unchecked();
-            match target {
-                NavigationTarget::Internal(p) => {
-                    let history = history();
-                    history.push(p)
-                }
-                NavigationTarget::External(e) => return write.external(e),
-            }
-        }
-
-        self.change_route()
+        self.navigate(target.into(), NavigationKind::Push)
     }
 
     /// Replace the current location.
     ///
     /// The previous location will **not** be available to go back to.
+    ///
+    /// If the new route has a guard that needs to wait, the router navigates after the guard
+    /// finishes.
     pub fn replace(
         &self,
         target: impl Into<NavigationTarget>,
     ) -> Option<ExternalNavigationFailure> {
-        let target = target.into();
-        {
-            let mut state = self.inner.write_unchecked();
-            match target {
-                NavigationTarget::Internal(p) => {
-                    let history = history();
-                    history.replace(p)
-                }
-                NavigationTarget::External(e) => return state.external(e),
+        self.navigate(target.into(), NavigationKind::Replace)
+    }
+
+    /// Navigate to a target once the guards of the target route allow it
+    fn navigate(
+        &self,
+        target: NavigationTarget,
+        kind: NavigationKind,
+    ) -> Option<ExternalNavigationFailure> {
+        let route = match target {
+            NavigationTarget::Internal(route) => route,
+            NavigationTarget::External(e) => return self.inner.write_unchecked().external(e),
+        };
+
+        // A new navigation replaces any navigation that is still waiting for its guards
+        let (pending, guard, scope) = {
+            let mut inner = self.inner.write_unchecked();
+            let guard = (inner.guard)(&route);
+            (inner.pending_navigation.take(), guard, inner.scope)
+        };
+        if let Some(task) = pending {
+            task.cancel();
+        }
+        let Some(mut guard) = guard else {
+            return self.navigate_unguarded(route, kind);
+        };
+
+        // Guards that don't need to wait are resolved immediately so the navigation stays synchronous
+        if let Some(result) = (&mut guard).now_or_never() {
+            return self.finish_navigation(route, result, kind);
+        }
+        let myself = *self;
+        let task = scope.push_future(async move {
+            let result = guard.await;
+            myself.inner.write_unchecked().pending_navigation = None;
+            myself.finish_navigation(route, result, kind);
+        });
+        self.inner.write_unchecked().pending_navigation = task;
+
+        None
+    }
+
+    /// Apply the result of the guards of a route the router is navigating to
+    fn finish_navigation(
+        &self,
+        route: String,
+        result: GuardResult,
+        kind: NavigationKind,
+    ) -> Option<ExternalNavigationFailure> {
+        match result {
+            GuardResult::Allow => {
+                self.inner.write_unchecked().allowed_route = Some(route.clone());
+                self.navigate_unguarded(route, kind)
             }
+            GuardResult::Cancel => None,
+            GuardResult::Redirect(target) => self.navigate(target, kind),
         }
+    }
 
+    fn navigate_unguarded(
+        &self,
+        route: String,
+        kind: NavigationKind,
+    ) -> Option<ExternalNavigationFailure> {
+        self.before_navigation(kind);
+        match kind {
+            NavigationKind::Replace => history().replace(route),
+            _ => history().push(route),
+        }
         self.change_route()
     }
 
@@ -304,6 +437,89 @@ impl RouterContext {
         None
     }
 
+    fn before_navigation(&self, kind: NavigationKind) {
+        self.inner.read().before_navigation(kind);
+    }
+
+    /// Scroll the page after the current render if the route changed. This should be called while rendering the
+    /// router so it rerenders when the route changes.
+    pub(crate) fn scroll_after_render(&self) {
+        let inner = self.inner.read();
+        inner.subscribe_to_current_context();
+        let kind = inner.pending_scroll.lock().unwrap().take();
+        if let Some(kind) = kind {
+            inner.scroll.scroll_after_render(kind);
+        }
+    }
+
+    /// Run the guards of the current route if it was opened without going through the router, like
+    /// when the app starts or when the user presses the back button of the platform. This should be
+    /// called while rendering the router.
+    ///
+    /// Rendering suspends while a guard is waiting. Returns `false` if the guards cancelled the route
+    /// and there is no route to go back to.
+    pub(crate) fn guard_current_route(&self) -> Result<bool, RenderError> {
+        loop {
+            let route = self.full_route_string();
+            let mut inner = self.inner.write_unchecked();
+            if inner.allowed_route.as_ref() == Some(&route) {
+                return Ok(true);
+            }
+            if inner.blocked_route.as_ref() == Some(&route) {
+                return Ok(false);
+            }
+            match inner.pending_guard.take() {
+                Some((pending, task)) if pending == route => {
+                    inner.pending_guard = Some((pending, task));
+                    return Err(RenderError::Suspended(SuspendedFuture::new(task)));
+                }
+                // The route changed while the guards were running
+                Some((_, task)) => task.cancel(),
+                None => {}
+            }
+
+            let Some(mut guard) = (inner.guard)(&route) else {
+                inner.allowed_route = Some(route);
+                return Ok(true);
+            };
+            drop(inner);
+
+            match (&mut guard).now_or_never() {
+                Some(result) => self.finish_current_route(route, result),
+                None => {
+                    let myself = *self;
+                    let pending_route = route.clone();
+                    let task = spawn(async move {
+                        let result = guard.await;
+                        myself.inner.write_unchecked().pending_guard = None;
+                        myself.finish_current_route(pending_route, result);
+                    });
+                    self.inner.write_unchecked().pending_guard = Some((route, task));
+                    return Err(RenderError::Suspended(SuspendedFuture::new(task)));
+                }
+            }
+        }
+    }
+
+    /// Apply the result of the guards of a route that is already in the history
+    fn finish_current_route(&self, route: String, result: GuardResult) {
+        let mut inner = self.inner.write_unchecked();
+        match result {
+            GuardResult::Allow => inner.allowed_route = Some(route),
+            GuardResult::Cancel => match inner.allowed_route.clone() {
+                Some(allowed) => history().replace(allowed),
+                None => inner.blocked_route = Some(route),
+            },
+            GuardResult::Redirect(NavigationTarget::Internal(target)) => history().replace(target),
+            GuardResult::Redirect(NavigationTarget::External(target)) => {
+                // Nothing is rendered while the platform leaves the page
+                inner.blocked_route = Some(route);
+                inner.external(target);
+            }
+        }
+        inner.update_subscribers();
+    }
+
     pub(crate) fn internal_route(&self, route: &str) -> bool {
         (self.inner.read().internal_route)(route)
     }
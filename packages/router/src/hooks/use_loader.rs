@@ -0,0 +1,51 @@
+use dioxus_lib::prelude::*;
+
+use crate::components::LoaderData;
+
+/// A hook that returns the data loaded by the loader of the current route or one of its layouts.
+///
+/// The data comes from the closest route or layout above the component with a loader that returns
+/// `T`. The route doesn't render until its loaders finish, so the data is always ready. The
+/// component reruns when the data is reloaded.
+///
+/// Loaders are declared with the `loader` argument of the `#[route]` and `#[layout]` attributes.
+/// The loader is called with the dynamic segments of the route or layout in the order they are
+/// declared in the variant and returns a future.
+///
+/// # Panic
+/// - When the calling component is not inside of a route or layout with a loader that returns `T`.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// async fn load_post(id: usize) -> String {
+///     format!("Post {id}")
+/// }
+///
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/post/:id", Post, loader = load_post)]
+///     Post { id: usize },
+/// }
+///
+/// #[component]
+/// fn Post(id: usize) -> Element {
+///     let post: String = use_loader();
+///     rsx! { h1 { "{post}" } }
+/// }
+/// ```
+#[must_use]
+pub fn use_loader<T: Clone + 'static>() -> T {
+    let data = use_hook(|| {
+        try_consume_context::<LoaderData<T>>().unwrap_or_else(|| {
+            panic!(
+                "`use_loader::<{}>` must be called inside of a route or layout with a loader that returns that type",
+                std::any::type_name::<T>()
+            )
+        })
+    });
+    let data = data.0.read();
+    data.clone()
+        .expect("route loaders finish before the route renders")
+}
@@ -30,6 +30,9 @@ pub mod components {
 
     #[doc(hidden)]
     pub mod child_router;
+
+    mod route_loader;
+    pub use route_loader::*;
 }
 
 mod contexts {
@@ -43,6 +46,10 @@ mod contexts {
 
 mod router_cfg;
 
+mod scroll;
+
+mod transition;
+
 /// Hooks for interacting with the router in components.
 pub mod hooks {
     mod use_router;
@@ -53,10 +60,18 @@ pub mod hooks {
 
     mod use_navigator;
     pub use use_navigator::*;
+
+    mod use_loader;
+    pub use use_loader::*;
 }
 
 pub use hooks::router;
 
+/// The history providers the router navigates with. Implement [`History`](history::History) to
+/// store the navigation of the router in a custom backend and provide it with the
+/// [`HistoryProvider`](components::HistoryProvider) component.
+pub use dioxus_history as history;
+
 /// A collection of useful items most applications might need.
 pub mod prelude {
     pub use crate::components::{
@@ -68,6 +83,9 @@ pub mod prelude {
     pub use crate::navigation::*;
     pub use crate::routable::*;
     pub use crate::router_cfg::RouterConfig;
+    pub use crate::scroll::RouteScroll;
+    pub use crate::transition::RouteTransition;
+    pub use dioxus_history::{History, HistoryEntry, MemoryHistory};
     pub use dioxus_router_macro::Routable;
 
     #[doc(hidden)]
@@ -2,6 +2,8 @@
 
 use std::{
     fmt::{Debug, Display},
+    future::Future,
+    pin::Pin,
     str::FromStr,
 };
 
@@ -121,6 +123,52 @@ impl<R: Routable> From<NavigationTarget<R>> for NavigationTarget {
     }
 }
 
+/// What the router should do with a navigation after running the guard of the target route.
+///
+/// Guards are declared on routes and layouts with the `guard` argument of the
+/// [`Routable`](dioxus_router_macro::Routable) derive macro.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// # use dioxus_router::navigation::GuardResult;
+/// # #[component]
+/// # fn Index() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Settings() -> Element { VNode::empty() }
+/// #[derive(Clone, Routable, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     #[route("/settings", guard = require_login)]
+///     Settings {},
+/// }
+///
+/// async fn require_login(_to: Route) -> GuardResult<Route> {
+///     let logged_in = false;
+///     match logged_in {
+///         true => GuardResult::Allow,
+///         false => GuardResult::Redirect(Route::Index {}.into()),
+///     }
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GuardResult<R = String> {
+    /// Continue navigating to the route.
+    Allow,
+    /// Stay on the current route.
+    ///
+    /// If the route was opened without going through the router, like when the app starts or the
+    /// user presses the back button of the platform, the router goes back to the last route the
+    /// guards allowed. If there is no such route, nothing is rendered.
+    Cancel,
+    /// Navigate to a different target instead.
+    Redirect(NavigationTarget<R>),
+}
+
+/// The future returned by [`Routable::guard`].
+pub type GuardFuture<R> = Pin<Box<dyn Future<Output = GuardResult<R>>>>;
+
 impl<R: Routable> Display for NavigationTarget<R> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
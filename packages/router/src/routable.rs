@@ -3,6 +3,8 @@
 #![allow(non_snake_case)]
 use dioxus_lib::prelude::*;
 
+use crate::navigation::GuardFuture;
+
 use std::iter::FlatMap;
 use std::slice::Iter;
 use std::{fmt::Display, str::FromStr};
@@ -599,6 +601,15 @@ pub trait Routable: FromStr + Display + Clone + 'static {
     /// Render the route at the given level
     fn render(&self, level: usize) -> Element;
 
+    /// Run the guards of this route and the layouts around it.
+    ///
+    /// The router calls this before navigating to the route. Returns [`None`] if the route doesn't
+    /// have any guards. The derive macro implements this for routes and layouts with a `guard`
+    /// argument.
+    fn guard(&self) -> Option<GuardFuture<Self>> {
+        None
+    }
+
     /// Checks if this route is a child of the given route.
     ///
     /// # Example
@@ -712,26 +723,130 @@ pub trait Routable: FromStr + Display + Clone + 'static {
     /// Gets a list of all the static routes.
     /// Example static route: `#[route("/static/route")]`
     fn static_routes() -> Vec<Self> {
+        Self::static_routes_with(|_, _| Vec::new())
+    }
+
+    /// Gets a list of all the routes that can be pre-rendered, including dynamic routes.
+    ///
+    /// `segment_values` is called with the path of every route with a dynamic or catch all segment, like
+    /// `/blog/:slug`, and the name of the segment. It should return every value that segment can take in that route,
+    /// so `/blog/:id` and `/user/:id` can have different ids. Each combination of values is turned into a route.
+    /// Routes with a dynamic segment that has no values are skipped.
+    ///
+    /// During static site generation, `dx` asks the `static_routes` server function for the routes to render. Return these
+    /// routes from it to pre-render every value of a dynamic segment. Each page is written with the hydration data of
+    /// its server futures, so it hydrates without fetching the data again:
+    ///
+    /// ```rust, ignore
+    /// #[server(endpoint = "static_routes")]
+    /// async fn static_routes() -> Result<Vec<String>, ServerFnError> {
+    ///     let slugs = load_blog_slugs().await?;
+    ///     let routes = Route::static_routes_with(|route, segment| match (route, segment) {
+    ///         ("/blog/:slug", "slug") => slugs.clone(),
+    ///         _ => Vec::new(),
+    ///     });
+    ///     Ok(routes.iter().map(ToString::to_string).collect())
+    /// }
+    /// ```
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_router::prelude::*;
+    /// use dioxus::prelude::*;
+    ///
+    /// #[component]
+    /// fn Home() -> Element { VNode::empty() }
+    /// #[component]
+    /// fn Post(id: String) -> Element { VNode::empty() }
+    /// #[component]
+    /// fn User(id: String) -> Element { VNode::empty() }
+    ///
+    /// #[derive(Routable, Clone, PartialEq, Debug)]
+    /// enum Route {
+    ///     #[route("/")]
+    ///     Home {},
+    ///     #[route("/blog/:id")]
+    ///     Post { id: String },
+    ///     #[route("/user/:id")]
+    ///     User { id: String },
+    /// }
+    ///
+    /// let routes = Route::static_routes_with(|route, _| match route {
+    ///     "/blog/:id" => vec!["hello".to_string(), "world".to_string()],
+    ///     "/user/:id" => vec!["ferris".to_string()],
+    ///     _ => Vec::new(),
+    /// });
+    /// assert_eq!(
+    ///     routes,
+    ///     vec![
+    ///         Route::Home {},
+    ///         Route::Post { id: "hello".to_string() },
+    ///         Route::Post { id: "world".to_string() },
+    ///         Route::User { id: "ferris".to_string() },
+    ///     ]
+    /// );
+    /// ```
+    fn static_routes_with(
+        mut segment_values: impl FnMut(&str, &'static str) -> Vec<String>,
+    ) -> Vec<Self> {
         Self::flatten_site_map()
-            .filter_map(|segments| {
-                let mut route = String::new();
+            .flat_map(|segments| {
+                let path: String = segments.iter().map(ToString::to_string).collect();
+                let mut routes = vec![String::new()];
                 for segment in segments.iter() {
                     match segment {
                         SegmentType::Static(s) => {
-                            route.push('/');
-                            route.push_str(s)
+                            for route in routes.iter_mut() {
+                                route.push('/');
+                                route.push_str(s);
+                            }
+                        }
+                        SegmentType::Dynamic(name) => {
+                            let values = segment_values(&path, name);
+                            routes = expand_segment(&routes, &values, |value| {
+                                urlencoding::encode(value).into_owned()
+                            });
+                        }
+                        SegmentType::CatchAll(name) => {
+                            let values = segment_values(&path, name);
+                            // Catch all segments may span multiple path segments, so only encode each segment
+                            routes = expand_segment(&routes, &values, |value| {
+                                value
+                                    .trim_matches('/')
+                                    .split('/')
+                                    .map(|segment| urlencoding::encode(segment).into_owned())
+                                    .collect::<Vec<_>>()
+                                    .join("/")
+                            });
                         }
                         SegmentType::Child => {}
-                        _ => return None,
                     }
                 }
 
-                route.parse().ok()
+                routes
             })
+            .filter_map(|route| route.parse().ok())
             .collect()
     }
 }
 
+/// Combine every partial route with every value of the next segment
+fn expand_segment(
+    routes: &[String],
+    values: &[String],
+    encode: impl Fn(&str) -> String,
+) -> Vec<String> {
+    routes
+        .iter()
+        .flat_map(|route| {
+            values
+                .iter()
+                .map(|value| format!("{route}/{}", encode(value)))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
 /// A type erased map of the site structure.
 #[derive(Debug, Clone, PartialEq)]
 pub struct SiteMapSegment {
@@ -1,6 +1,11 @@
-use crate::{components::FailureExternalNavigation, prelude::*};
+use crate::{
+    components::FailureExternalNavigation,
+    prelude::*,
+    scroll::{RouteScroll, RouteScrollCallback},
+    transition::{RouteTransition, TransitionCallback},
+};
 use dioxus_lib::prelude::*;
-use std::sync::Arc;
+use std::{rc::Rc, sync::Arc};
 
 /// Global configuration options for the router.
 ///
@@ -29,6 +34,8 @@ use std::sync::Arc;
 pub struct RouterConfig<R> {
     pub(crate) failure_external_navigation: fn() -> Element,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    pub(crate) scroll: Option<RouteScrollCallback<R>>,
+    pub(crate) transition: Option<TransitionCallback<R>>,
 }
 
 impl<R> Default for RouterConfig<R> {
@@ -36,6 +43,8 @@ impl<R> Default for RouterConfig<R> {
         Self {
             failure_external_navigation: FailureExternalNavigation,
             on_update: None,
+            scroll: None,
+            transition: None,
         }
     }
 }
@@ -77,4 +86,85 @@ where
             ..self
         }
     }
+
+    /// Choose how the page scrolls after navigating to each route.
+    ///
+    /// Scrolling only happens on platforms that can evaluate javascript in the document like web,
+    /// desktop and liveview.
+    ///
+    /// Defaults to [`RouteScroll::Auto`] for every route.
+    ///
+    /// ```rust,no_run
+    /// # use dioxus_router::prelude::*;
+    /// # use dioxus::prelude::*;
+    /// # #[component]
+    /// # fn Index() -> Element { VNode::empty() }
+    /// # #[component]
+    /// # fn Chat() -> Element { VNode::empty() }
+    /// #[derive(Clone, Routable)]
+    /// enum Route {
+    ///     #[route("/")]
+    ///     Index {},
+    ///     #[route("/chat")]
+    ///     Chat {},
+    /// }
+    ///
+    /// let cfg = RouterConfig::<Route>::default().scroll(|route| match route {
+    ///     // Keep the chat scrolled to the latest message
+    ///     Route::Chat {} => RouteScroll::Preserve,
+    ///     _ => RouteScroll::Auto,
+    /// });
+    /// ```
+    pub fn scroll(self, scroll: impl Fn(&R) -> RouteScroll + 'static) -> Self {
+        Self {
+            scroll: Some(Rc::new(scroll)),
+            ..self
+        }
+    }
+
+    /// Animate between routes when the router navigates.
+    ///
+    /// The callback is called with the old and the new route whenever the route changes. If it
+    /// returns a [`RouteTransition`], the [`Outlet`] of the router renders both routes until the
+    /// transition ends. If it returns [`None`], the route changes without a transition.
+    ///
+    /// Transitions only animate on platforms that can evaluate javascript in the document like web,
+    /// desktop and liveview.
+    ///
+    /// Defaults to [`None`].
+    ///
+    /// ```rust,no_run
+    /// # use dioxus_router::prelude::*;
+    /// # use dioxus::prelude::*;
+    /// # use std::time::Duration;
+    /// # #[component]
+    /// # fn Index() -> Element { VNode::empty() }
+    /// # #[component]
+    /// # fn Settings() -> Element { VNode::empty() }
+    /// #[derive(Clone, Routable)]
+    /// enum Route {
+    ///     #[route("/")]
+    ///     Index {},
+    ///     #[route("/settings")]
+    ///     Settings {},
+    /// }
+    ///
+    /// let cfg = RouterConfig::<Route>::default().transition(|_from, to| match to {
+    ///     Route::Settings {} => Some(RouteTransition::view_transition()),
+    ///     _ => Some(
+    ///         RouteTransition::new(Duration::from_millis(200))
+    ///             .enter_class("fade-in")
+    ///             .exit_class("fade-out"),
+    ///     ),
+    /// });
+    /// ```
+    pub fn transition(
+        self,
+        transition: impl Fn(&R, &R) -> Option<RouteTransition> + 'static,
+    ) -> Self {
+        Self {
+            transition: Some(Rc::new(transition)),
+            ..self
+        }
+    }
 }
@@ -0,0 +1,196 @@
+//! Scroll the page after the router navigates.
+
+use std::{cell::Cell, rc::Rc};
+
+use dioxus_history::{history, ScrollPosition};
+use dioxus_lib::document::Document;
+use dioxus_lib::prelude::*;
+
+/// How the router scrolls the page after navigating to a route.
+///
+/// The behavior can be configured for each route with [`RouterConfig::scroll`](crate::prelude::RouterConfig::scroll).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RouteScroll {
+    /// Scroll to the top of the page when the route is pushed, restore the previous scroll position when the user
+    /// navigates back or forward to the route, and scroll to the element the hash of the route points to if it has
+    /// one.
+    #[default]
+    Auto,
+    /// Scroll to the top of the page after every navigation to the route, including back and forward navigation.
+    Top,
+    /// Only scroll if the route has a hash, scrolling to the element with an id or name that matches the hash.
+    ScrollToAnchor,
+    /// Never scroll the page when navigating to the route.
+    Preserve,
+}
+
+/// A function that picks the [`RouteScroll`] for a route
+pub(crate) type RouteScrollCallback<R> = Rc<dyn Fn(&R) -> RouteScroll>;
+
+/// How the current route was navigated to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NavigationKind {
+    Push,
+    Replace,
+    /// Back or forward navigation, either from the router or from outside the router like the browser's back button
+    Traverse,
+}
+
+/// Where the page should scroll to after a navigation
+#[derive(Debug, Clone, PartialEq)]
+enum ScrollTarget {
+    Position(ScrollPosition),
+    Anchor(String),
+}
+
+impl RouteScroll {
+    fn target(
+        self,
+        kind: NavigationKind,
+        saved: Option<ScrollPosition>,
+        hash: Option<&str>,
+    ) -> Option<ScrollTarget> {
+        let anchor = hash.map(|hash| ScrollTarget::Anchor(hash.to_string()));
+        match self {
+            RouteScroll::Preserve => None,
+            RouteScroll::Top => Some(ScrollTarget::Position(ScrollPosition::default())),
+            RouteScroll::ScrollToAnchor => anchor,
+            RouteScroll::Auto => match kind {
+                NavigationKind::Push => {
+                    anchor.or(Some(ScrollTarget::Position(ScrollPosition::default())))
+                }
+                NavigationKind::Replace => anchor,
+                // If the history didn't save a position, leave the page where the platform put it
+                NavigationKind::Traverse => saved.map(ScrollTarget::Position).or(anchor),
+            },
+        }
+    }
+}
+
+/// Tracks the scroll position of the window and scrolls it after navigation.
+///
+/// Scrolling is only supported if the renderer provides a [`Document`] that can evaluate javascript.
+pub(crate) struct ScrollState {
+    document: Option<Rc<dyn Document>>,
+    /// The last known scroll position of the window
+    position: Rc<Cell<Option<ScrollPosition>>>,
+    route_scroll: Rc<dyn Fn(&str) -> RouteScroll>,
+}
+
+impl ScrollState {
+    pub(crate) fn new(route_scroll: Rc<dyn Fn(&str) -> RouteScroll>) -> Self {
+        Self {
+            document: try_consume_context::<Rc<dyn Document>>(),
+            position: Rc::new(Cell::new(None)),
+            route_scroll,
+        }
+    }
+
+    /// Start tracking the scroll position of the window. The position is saved in the history as the user scrolls so it
+    /// can be restored when the user comes back to the page.
+    pub(crate) fn track(&self) {
+        let Some(document) = &self.document else {
+            return;
+        };
+        let mut eval = document.eval(
+            r#"let timeout;
+            const send = () => dioxus.send([window.scrollX, window.scrollY]);
+            window.addEventListener("scroll", () => {
+                clearTimeout(timeout);
+                timeout = setTimeout(send, 100);
+            }, { passive: true });
+            send();
+            await new Promise(() => {});"#
+                .to_string(),
+        );
+        let position = self.position.clone();
+        spawn(async move {
+            while let Ok([x, y]) = eval.recv::<[f64; 2]>().await {
+                let current = ScrollPosition { x, y };
+                position.set(Some(current));
+                history().set_scroll_position(current);
+            }
+        });
+    }
+
+    /// Save the current scroll position in the history before navigating away from the page
+    pub(crate) fn save(&self) {
+        if let Some(position) = self.position.get() {
+            history().set_scroll_position(position);
+        }
+    }
+
+    /// Scroll the window for the current route after the next render
+    pub(crate) fn scroll_after_render(&self, kind: NavigationKind) {
+        let Some(document) = self.document.clone() else {
+            return;
+        };
+        let route_scroll = self.route_scroll.clone();
+        queue_effect(move || {
+            let history = history();
+            let route = history.current_route();
+            let hash = route
+                .split_once('#')
+                .map(|(_, hash)| urlencoding::decode(hash).map_or(hash.into(), |hash| hash))
+                .filter(|hash| !hash.is_empty());
+            let target =
+                route_scroll(&route).target(kind, history.scroll_position(), hash.as_deref());
+
+            let script = match target {
+                Some(ScrollTarget::Position(ScrollPosition { x, y })) => {
+                    format!("window.scrollTo({x}, {y});")
+                }
+                Some(ScrollTarget::Anchor(id)) => format!(
+                    "const target = document.getElementById({id:?}) || document.getElementsByName({id:?})[0];
+                    if (target) target.scrollIntoView();"
+                ),
+                None => return,
+            };
+            _ = document.eval(script);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAVED: ScrollPosition = ScrollPosition { x: 0.0, y: 300.0 };
+    const TOP: ScrollTarget = ScrollTarget::Position(ScrollPosition { x: 0.0, y: 0.0 });
+
+    #[test]
+    fn auto_scroll() {
+        let auto = RouteScroll::Auto;
+        assert_eq!(auto.target(NavigationKind::Push, None, None), Some(TOP));
+        assert_eq!(auto.target(NavigationKind::Replace, None, None), None);
+        assert_eq!(
+            auto.target(NavigationKind::Traverse, Some(SAVED), Some("section")),
+            Some(ScrollTarget::Position(SAVED))
+        );
+        assert_eq!(auto.target(NavigationKind::Traverse, None, None), None);
+        assert_eq!(
+            auto.target(NavigationKind::Push, None, Some("section")),
+            Some(ScrollTarget::Anchor("section".to_string()))
+        );
+    }
+
+    #[test]
+    fn configured_scroll() {
+        assert_eq!(
+            RouteScroll::Top.target(NavigationKind::Traverse, Some(SAVED), None),
+            Some(TOP)
+        );
+        assert_eq!(
+            RouteScroll::ScrollToAnchor.target(NavigationKind::Push, None, None),
+            None
+        );
+        assert_eq!(
+            RouteScroll::ScrollToAnchor.target(NavigationKind::Traverse, Some(SAVED), Some("a")),
+            Some(ScrollTarget::Anchor("a".to_string()))
+        );
+        assert_eq!(
+            RouteScroll::Preserve.target(NavigationKind::Push, None, Some("a")),
+            None
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Animated transitions between routes.
+
+use std::{rc::Rc, time::Duration};
+
+use dioxus_lib::document::{Document, Eval};
+use dioxus_lib::prelude::*;
+
+use crate::{contexts::router::RouterContext, routable::Routable};
+
+/// An animation that plays when the router navigates between two routes.
+///
+/// While a transition with a duration is running, the [`Outlet`](crate::prelude::Outlet) of the
+/// router renders both the outgoing and the incoming route. Each route is wrapped in a `div` with a
+/// `data-transition` attribute that is `"exit"` for the outgoing route, `"enter"` for the incoming
+/// route while the transition runs, and `"idle"` otherwise. The outgoing route stays mounted until
+/// the transition ends, so its state is kept alive during the animation.
+///
+/// Transitions are enabled with [`RouterConfig::transition`](crate::prelude::RouterConfig::transition).
+///
+/// ```rust
+/// # use dioxus_router::prelude::*;
+/// # use std::time::Duration;
+/// let fade = RouteTransition::new(Duration::from_millis(300))
+///     .enter_class("fade-in")
+///     .exit_class("fade-out");
+/// ```
+#[derive(Clone)]
+pub struct RouteTransition {
+    pub(crate) duration: Duration,
+    pub(crate) enter_class: Option<String>,
+    pub(crate) exit_class: Option<String>,
+    pub(crate) on_start: Option<Rc<dyn Fn()>>,
+    pub(crate) on_end: Option<Rc<dyn Fn()>>,
+    pub(crate) view_transition: bool,
+}
+
+impl RouteTransition {
+    /// Create a transition where the outgoing and incoming routes overlap for `duration`.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            enter_class: None,
+            exit_class: None,
+            on_start: None,
+            on_end: None,
+            view_transition: false,
+        }
+    }
+
+    /// Create a transition that uses the browser's
+    /// [View Transitions API](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API).
+    ///
+    /// The browser animates between snapshots of the old and new page, so the outgoing route is
+    /// removed as soon as the incoming route renders. If the platform doesn't support view
+    /// transitions, the route changes without an animation.
+    pub fn view_transition() -> Self {
+        Self {
+            view_transition: true,
+            ..Self::new(Duration::ZERO)
+        }
+    }
+
+    /// A class to add to the incoming route while the transition runs.
+    pub fn enter_class(self, class: impl ToString) -> Self {
+        Self {
+            enter_class: Some(class.to_string()),
+            ..self
+        }
+    }
+
+    /// A class to add to the outgoing route while the transition runs.
+    pub fn exit_class(self, class: impl ToString) -> Self {
+        Self {
+            exit_class: Some(class.to_string()),
+            ..self
+        }
+    }
+
+    /// A function to call when the transition starts. This can be used to start animations that
+    /// can't be expressed with classes.
+    pub fn on_start(self, callback: impl Fn() + 'static) -> Self {
+        Self {
+            on_start: Some(Rc::new(callback)),
+            ..self
+        }
+    }
+
+    /// A function to call after the transition ends and the outgoing route is removed.
+    pub fn on_end(self, callback: impl Fn() + 'static) -> Self {
+        Self {
+            on_end: Some(Rc::new(callback)),
+            ..self
+        }
+    }
+}
+
+/// A function that picks the [`RouteTransition`] between the old and the new route
+pub(crate) type TransitionCallback<R> = Rc<dyn Fn(&R, &R) -> Option<RouteTransition>>;
+
+/// The transitions of a router. This is provided by the [`Router`](crate::prelude::Router) if
+/// transitions are configured.
+pub(crate) struct RouterTransitions<R>(pub(crate) TransitionCallback<R>);
+
+impl<R> Clone for RouterTransitions<R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// The route rendered by a [`TransitionFrame`]. Outlets inside of the frame render this route
+/// instead of the current route so the outgoing route keeps rendering the route it was created for.
+pub(crate) struct FrameRoute<R: 'static>(pub(crate) CopyValue<R>);
+
+impl<R> Clone for FrameRoute<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for FrameRoute<R> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Idle,
+    Enter,
+    Exit,
+}
+
+impl Phase {
+    fn as_str(self) -> &'static str {
+        match self {
+            Phase::Idle => "idle",
+            Phase::Enter => "enter",
+            Phase::Exit => "exit",
+        }
+    }
+}
+
+struct Frame<R> {
+    id: usize,
+    route: R,
+    phase: Phase,
+    class: Option<String>,
+}
+
+struct TransitionState<R> {
+    /// The frames of outgoing routes that are still animating
+    exiting: Vec<Frame<R>>,
+    /// The frame of the route that is shown, or is being animated in
+    shown: Option<Frame<R>>,
+    next_id: usize,
+    /// If a view transition is waiting for the browser to snapshot the old page
+    view_transition_pending: bool,
+    /// A view transition that is waiting for the new route to render
+    finish_view_transition: Option<Eval>,
+}
+
+/// Render the root outlet of a router with transitions between routes
+pub(crate) fn render_transition_outlet<R: Routable>(
+    router: RouterContext,
+    transitions: RouterTransitions<R>,
+    level: usize,
+) -> Element {
+    let mut handle = use_hook(|| {
+        CopyValue::new(TransitionState::<R> {
+            exiting: Vec::new(),
+            shown: None,
+            next_id: 0,
+            view_transition_pending: false,
+            finish_view_transition: None,
+        })
+    });
+    let document = use_hook(try_consume_context::<Rc<dyn Document>>);
+
+    let current = router.current::<R>();
+
+    {
+        // A copy of the handle for the tasks that finish the transition
+        let task_handle = handle;
+        let mut write = handle.write();
+        let state = &mut *write;
+
+        if let Some(eval) = state.finish_view_transition.take() {
+            // Let the browser animate to the new page after the new route renders
+            queue_effect(move || _ = eval.send(true));
+        }
+
+        match &mut state.shown {
+            None => {
+                state.shown = Some(Frame {
+                    id: state.next_id,
+                    route: current,
+                    phase: Phase::Idle,
+                    class: None,
+                });
+                state.next_id += 1;
+            }
+            Some(shown) if shown.route.to_string() != current.to_string() => {
+                let transition = (transitions.0)(&shown.route, &current);
+                match (transition, &document) {
+                    (Some(transition), Some(document)) if transition.view_transition => {
+                        if !state.view_transition_pending {
+                            state.view_transition_pending = true;
+                            start_view_transition(document, task_handle, router);
+                        }
+                    }
+                    (Some(transition), Some(_)) if !transition.duration.is_zero() => {
+                        let id = state.next_id;
+                        state.next_id += 1;
+                        let mut old = std::mem::replace(
+                            shown,
+                            Frame {
+                                id,
+                                route: current,
+                                phase: Phase::Enter,
+                                class: transition.enter_class.clone(),
+                            },
+                        );
+                        old.phase = Phase::Exit;
+                        old.class = transition.exit_class.clone();
+                        let old_id = old.id;
+                        state.exiting.push(old);
+
+                        if let Some(on_start) = &transition.on_start {
+                            on_start();
+                        }
+                        let wait = dioxus_core::sleep(transition.duration);
+                        spawn(async move {
+                            wait.await;
+                            {
+                                let mut handle = task_handle;
+                                let mut state = handle.write();
+                                state.exiting.retain(|frame| frame.id != old_id);
+                                if let Some(shown) = state.shown.as_mut().filter(|f| f.id == id) {
+                                    shown.phase = Phase::Idle;
+                                    shown.class = None;
+                                }
+                            }
+                            if let Some(on_end) = &transition.on_end {
+                                on_end();
+                            }
+                            needs_update();
+                        });
+                    }
+                    // Without a transition, the route changes in place
+                    _ => shown.route = current,
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    let state = handle.read();
+    let frames = state
+        .exiting
+        .iter()
+        .chain(state.shown.as_ref())
+        .map(|frame| {
+            (
+                frame.id,
+                frame.route.clone(),
+                frame.phase.as_str(),
+                frame.class.clone(),
+            )
+        });
+
+    rsx! {
+        for (id , route , phase , class) in frames {
+            TransitionFrame::<R> {
+                key: "{id}",
+                route,
+                level,
+                phase,
+                class,
+            }
+        }
+    }
+}
+
+/// Start a view transition. The old route keeps rendering until the browser has captured the old
+/// page, then the new route renders and the browser animates between them.
+fn start_view_transition<R: Routable>(
+    document: &Rc<dyn Document>,
+    mut handle: CopyValue<TransitionState<R>>,
+    router: RouterContext,
+) {
+    let mut eval = document.eval(
+        r#"if (!document.startViewTransition) {
+            dioxus.send(false);
+            return;
+        }
+        document.startViewTransition(async () => {
+            dioxus.send(true);
+            await dioxus.recv();
+        });"#
+            .to_string(),
+    );
+    spawn(async move {
+        let started = matches!(eval.recv::<bool>().await, Ok(true));
+        {
+            let mut state = handle.write();
+            state.view_transition_pending = false;
+            if let Some(shown) = &mut state.shown {
+                shown.route = router.current();
+            }
+            if started {
+                state.finish_view_transition = Some(eval);
+            }
+        }
+        needs_update();
+    });
+}
+
+#[derive(Props)]
+struct TransitionFrameProps<R: Routable> {
+    route: R,
+    level: usize,
+    phase: &'static str,
+    class: Option<String>,
+}
+
+impl<R: Routable> Clone for TransitionFrameProps<R> {
+    fn clone(&self) -> Self {
+        Self {
+            route: self.route.clone(),
+            level: self.level,
+            phase: self.phase,
+            class: self.class.clone(),
+        }
+    }
+}
+
+impl<R: Routable> PartialEq for TransitionFrameProps<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.route.to_string() == other.route.to_string()
+            && self.level == other.level
+            && self.phase == other.phase
+            && self.class == other.class
+    }
+}
+
+/// Render a route inside of the outlet of a router with transitions
+#[allow(non_snake_case)]
+fn TransitionFrame<R: Routable>(props: TransitionFrameProps<R>) -> Element {
+    let mut route = use_hook(|| {
+        let route = CopyValue::new(props.route.clone());
+        provide_context(FrameRoute(route));
+        route
+    });
+    route.set(props.route.clone());
+
+    rsx! {
+        div { "data-transition": props.phase, class: props.class, {props.route.render(props.level)} }
+    }
+}
@@ -0,0 +1,70 @@
+use dioxus::prelude::*;
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/search?:q&:page")]
+    Search { q: Option<String>, page: Option<u32> },
+    #[route("/docs?:version#:section")]
+    Docs { version: usize, section: Option<String> },
+}
+
+#[component]
+fn Search(q: Option<String>, page: Option<u32>) -> Element {
+    VNode::empty()
+}
+
+#[component]
+fn Docs(version: usize, section: Option<String>) -> Element {
+    VNode::empty()
+}
+
+#[test]
+fn optional_query_arguments() {
+    let search = Route::Search {
+        q: Some("cats & dogs".to_string()),
+        page: Some(2),
+    };
+    assert_eq!(search.to_string(), "/search?q=cats%20%26%20dogs&page=2");
+    assert_eq!(search.to_string().parse::<Route>().unwrap(), search);
+
+    let search = Route::Search {
+        q: None,
+        page: Some(3),
+    };
+    assert_eq!(search.to_string(), "/search?page=3");
+    assert_eq!(search.to_string().parse::<Route>().unwrap(), search);
+
+    let empty = Route::Search {
+        q: None,
+        page: None,
+    };
+    assert_eq!(empty.to_string(), "/search");
+    assert_eq!("/search".parse::<Route>().unwrap(), empty);
+
+    // Arguments that fail to parse are treated as missing
+    assert_eq!(
+        "/search?q=rust&page=first".parse::<Route>().unwrap(),
+        Route::Search {
+            q: Some("rust".to_string()),
+            page: None,
+        }
+    );
+}
+
+#[test]
+fn optional_hash_fragment() {
+    let docs = Route::Docs {
+        version: 6,
+        section: Some("getting started".to_string()),
+    };
+    assert_eq!(docs.to_string(), "/docs?version=6#getting%20started");
+    assert_eq!(docs.to_string().parse::<Route>().unwrap(), docs);
+
+    let docs = Route::Docs {
+        version: 6,
+        section: None,
+    };
+    assert_eq!(docs.to_string(), "/docs?version=6");
+    assert_eq!(docs.to_string().parse::<Route>().unwrap(), docs);
+}
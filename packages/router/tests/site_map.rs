@@ -51,3 +51,119 @@ fn with_class() {
         ],
     );
 }
+
+#[test]
+fn with_dynamic_segments() {
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum ChildRoute {
+        #[route("/")]
+        ChildRoot {},
+        #[route("/:not_static")]
+        NotStatic { not_static: String },
+    }
+
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum Route {
+        #[route("/")]
+        Root {},
+        #[route("/blog/:slug")]
+        Post { slug: String },
+        #[route("/docs/:..path")]
+        Docs { path: Vec<String> },
+        #[child("/child")]
+        Nested { child: ChildRoute },
+    }
+
+    #[component]
+    fn Root() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn Post(slug: String) -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn Docs(path: Vec<String>) -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn ChildRoot() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn NotStatic(not_static: String) -> Element {
+        unimplemented!()
+    }
+
+    let routes = Route::static_routes_with(|route, segment| match (route, segment) {
+        ("/blog/:slug", "slug") => vec!["hello world".to_string(), "second".to_string()],
+        ("/docs/:..path", "path") => vec!["guide/intro".to_string()],
+        _ => Vec::new(),
+    });
+
+    assert_eq!(
+        routes,
+        vec![
+            Route::Root {},
+            Route::Post {
+                slug: "hello world".to_string()
+            },
+            Route::Post {
+                slug: "second".to_string()
+            },
+            Route::Docs {
+                path: vec!["guide".to_string(), "intro".to_string()]
+            },
+            Route::Nested {
+                child: ChildRoute::ChildRoot {}
+            },
+        ],
+    );
+}
+
+#[test]
+fn dynamic_segments_are_enumerated_per_route() {
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum Route {
+        #[route("/blog/:id")]
+        Post { id: String },
+        #[route("/user/:id")]
+        User { id: String },
+    }
+
+    #[component]
+    fn Post(id: String) -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn User(id: String) -> Element {
+        unimplemented!()
+    }
+
+    // Segments with the same name get the values of their own route
+    let routes = Route::static_routes_with(|route, _| match route {
+        "/blog/:id" => vec!["first-post".to_string()],
+        "/user/:id" => vec!["ferris".to_string(), "crab".to_string()],
+        _ => Vec::new(),
+    });
+
+    assert_eq!(
+        routes,
+        vec![
+            Route::Post {
+                id: "first-post".to_string()
+            },
+            Route::User {
+                id: "ferris".to_string()
+            },
+            Route::User {
+                id: "crab".to_string()
+            },
+        ],
+    );
+}
@@ -0,0 +1,49 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+
+#[derive(Routable, Clone, PartialEq)]
+enum Route {
+    #[route("/")]
+    Index {},
+    #[route("/notes/:id")]
+    Note { id: usize },
+}
+
+#[component]
+fn Index() -> Element {
+    rsx! { h1 { "Index" } }
+}
+
+#[component]
+fn Note(id: usize) -> Element {
+    rsx! { h1 { "Note {id}" } }
+}
+
+#[test]
+fn navigating_memory_history_from_outside_updates_the_router() {
+    let history = Rc::new(MemoryHistory::default());
+    let mut vdom = VirtualDom::new_with_props(
+        |history: Rc<MemoryHistory>| {
+            rsx! {
+                HistoryProvider {
+                    history: move |_| history.clone() as Rc<dyn History>,
+                    Router::<Route> {}
+                }
+            }
+        },
+        history.clone(),
+    );
+    vdom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Index</h1>");
+
+    // A deep link the platform received while the app was running
+    history.navigate("/notes/42".to_string());
+    vdom.render_immediate(&mut NoOpMutations);
+
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Note 42</h1>");
+    assert!(history.can_go_back());
+}
@@ -0,0 +1,97 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::{components::HistoryProvider, navigation::GuardResult};
+
+#[derive(Routable, Clone, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Index {},
+    #[route("/login")]
+    Login {},
+    #[route("/private", guard = cancel)]
+    Private {},
+    #[layout(Admin, guard = require_admin)]
+        #[route("/admin")]
+        Settings {},
+}
+
+async fn cancel(_: Route) -> GuardResult<Route> {
+    GuardResult::Cancel
+}
+
+async fn require_admin(_: Route) -> GuardResult<Route> {
+    // Wait like a guard that checks the user with the server
+    tokio::task::yield_now().await;
+    GuardResult::Redirect(Route::Login {}.into())
+}
+
+fn app(path: &'static str) -> VirtualDom {
+    VirtualDom::new_with_props(
+        |path: &'static str| {
+            rsx! {
+                HistoryProvider {
+                    history: move |_| Rc::new(MemoryHistory::with_initial_path(path)) as Rc<dyn History>,
+                    Router::<Route> {}
+                }
+            }
+        },
+        path,
+    )
+}
+
+#[component]
+fn Index() -> Element {
+    // Try to navigate to the private route once the task runs
+    use_hook(|| {
+        spawn(async {
+            router().push(Route::Private {});
+        })
+    });
+    rsx! { h1 { "Index" } }
+}
+
+#[component]
+fn Login() -> Element {
+    rsx! { h1 { "Login" } }
+}
+
+#[component]
+fn Private() -> Element {
+    rsx! { h1 { "Private" } }
+}
+
+#[component]
+fn Admin() -> Element {
+    rsx! { Outlet::<Route> {} }
+}
+
+#[component]
+fn Settings() -> Element {
+    rsx! { h1 { "Settings" } }
+}
+
+#[test]
+fn guards_cancel_navigation() {
+    let mut vdom = app("/");
+    vdom.rebuild_in_place();
+
+    for _ in 0..3 {
+        vdom.process_events();
+        vdom.render_immediate(&mut NoOpMutations);
+    }
+
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Index</h1>");
+}
+
+#[tokio::test]
+async fn layout_guards_redirect_the_initial_route() {
+    let mut vdom = app("/admin");
+    vdom.rebuild_in_place();
+    vdom.wait_for_suspense().await;
+
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Login</h1>");
+}
@@ -0,0 +1,102 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_router::{components::HistoryProvider, history::History};
+
+/// A history that keeps the routes in a shared list, like a backend that persists them
+#[derive(Clone)]
+struct ListHistory {
+    routes: Rc<RefCell<Vec<String>>>,
+}
+
+impl History for ListHistory {
+    fn current_route(&self) -> String {
+        self.routes
+            .borrow()
+            .last()
+            .cloned()
+            .unwrap_or_else(|| "/".to_string())
+    }
+
+    fn can_go_back(&self) -> bool {
+        self.routes.borrow().len() > 1
+    }
+
+    fn go_back(&self) {
+        self.routes.borrow_mut().pop();
+    }
+
+    fn can_go_forward(&self) -> bool {
+        false
+    }
+
+    fn go_forward(&self) {}
+
+    fn push(&self, route: String) {
+        self.routes.borrow_mut().push(route);
+    }
+
+    fn replace(&self, path: String) {
+        let mut routes = self.routes.borrow_mut();
+        routes.pop();
+        routes.push(path);
+    }
+
+    fn length(&self) -> usize {
+        self.routes.borrow().len()
+    }
+}
+
+#[derive(Routable, Clone, PartialEq)]
+enum Route {
+    #[route("/")]
+    Index {},
+    #[route("/settings")]
+    Settings {},
+}
+
+#[component]
+fn Index() -> Element {
+    // Navigate to the settings once the task runs
+    use_hook(|| {
+        spawn(async {
+            router().push(Route::Settings {});
+        })
+    });
+    rsx! { h1 { "Index" } }
+}
+
+#[component]
+fn Settings() -> Element {
+    rsx! { h1 { "Settings" } }
+}
+
+#[test]
+fn router_navigates_with_custom_history() {
+    let history = ListHistory {
+        routes: Rc::new(RefCell::new(vec!["/".to_string()])),
+    };
+    let mut vdom = VirtualDom::new_with_props(
+        |history: ListHistory| {
+            rsx! {
+                HistoryProvider {
+                    history: move |_| Rc::new(history.clone()) as Rc<dyn History>,
+                    Router::<Route> {}
+                }
+            }
+        },
+        history.clone(),
+    );
+    vdom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Index</h1>");
+
+    for _ in 0..3 {
+        vdom.process_events();
+        vdom.render_immediate(&mut NoOpMutations);
+    }
+
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Settings</h1>");
+    assert_eq!(*history.routes.borrow(), ["/", "/settings"]);
+    assert_eq!(history.length(), 2);
+}
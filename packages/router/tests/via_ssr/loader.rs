@@ -0,0 +1,59 @@
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+use std::rc::Rc;
+
+async fn load_user(user: String) -> String {
+    format!("Profile of {user}")
+}
+
+async fn load_post(_user: String, post: usize) -> usize {
+    post * 10
+}
+
+#[derive(Routable, Clone, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[nest("/:user")]
+        #[layout(Profile, loader = load_user)]
+            #[route("/:post", Post, loader = load_post)]
+            Post { user: String, post: usize },
+}
+
+#[component]
+fn App() -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| Rc::new(MemoryHistory::with_initial_path("/ada/4")) as Rc<dyn History>,
+            Router::<Route> {}
+        }
+    }
+}
+
+#[component]
+fn Profile(user: String) -> Element {
+    let profile: String = use_loader();
+    rsx! {
+        h1 { "{profile}" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Post(user: String, post: usize) -> Element {
+    let profile: String = use_loader();
+    let views: usize = use_loader();
+    rsx! { p { "{profile} - {views}" } }
+}
+
+#[tokio::test]
+async fn loaders_run_before_rendering() {
+    let mut vdom = VirtualDom::new(App);
+    vdom.rebuild_in_place();
+    vdom.wait_for_suspense().await;
+
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<h1>Profile of ada</h1><p>Profile of ada - 40</p>"
+    );
+}
@@ -1,4 +1,9 @@
+mod deep_link;
+mod guard;
+mod history;
 mod link;
+mod loader;
 mod outlet;
 mod redirect;
+mod transition;
 mod without_index;
@@ -0,0 +1,78 @@
+use std::{rc::Rc, time::Duration};
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+
+#[derive(Routable, Clone, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Index {},
+    #[layout(Frame)]
+        #[route("/nested")]
+        Nested {},
+}
+
+#[component]
+fn App() -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| Rc::new(MemoryHistory::default()) as Rc<dyn History>,
+            Router::<Route> {
+                config: |_| {
+                    RouterConfig::default().transition(|_, _| {
+                        Some(RouteTransition::new(Duration::from_millis(200)).exit_class("fade-out"))
+                    })
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn Index() -> Element {
+    // Navigate away from the index once the task runs
+    use_hook(|| {
+        spawn(async {
+            router().push(Route::Nested {});
+        })
+    });
+    rsx! { h2 { "Index" } }
+}
+
+#[component]
+fn Frame() -> Element {
+    rsx! {
+        h2 { "Frame" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Nested() -> Element {
+    rsx! { h3 { "Nested" } }
+}
+
+#[test]
+fn routes_are_wrapped_in_frames() {
+    let mut vdom = VirtualDom::new(App);
+    vdom.rebuild_in_place();
+
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<div data-transition=\"idle\"><h2>Index</h2></div>"
+    );
+
+    for _ in 0..3 {
+        vdom.process_events();
+        vdom.render_immediate(&mut NoOpMutations);
+    }
+
+    // Without a document to run the animation in, the route changes in place
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<div data-transition=\"idle\"><h2>Frame</h2><h3>Nested</h3></div>"
+    );
+}
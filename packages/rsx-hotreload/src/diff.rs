@@ -64,12 +64,12 @@
 //! more difficult to match future templates.
 
 use dioxus_core::internal::{
-    FmtedSegments, HotReloadAttributeValue, HotReloadDynamicAttribute, HotReloadDynamicNode,
-    HotReloadLiteral, HotReloadedTemplate, NamedAttribute,
+    FmtSegment, FmtedSegments, HotReloadAttributeValue, HotReloadDynamicAttribute,
+    HotReloadDynamicNode, HotReloadLiteral, HotReloadedTemplate, NamedAttribute,
 };
 use dioxus_core_types::HotReloadingContext;
 use dioxus_rsx::*;
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Debug, rc::Rc};
 
 use crate::extensions::{html_tag_and_namespace, intern, to_template_node};
 
@@ -113,6 +113,55 @@ pub struct HotReloadResult {
 
     /// The literal component properties for the current node
     literal_component_properties: Vec<HotReloadLiteral>,
+
+    /// Resolves `asset!()` calls that were not in the last full rebuild
+    assets: AssetResolver,
+}
+
+/// Resolves the path passed to an `asset!()` call that was added since the last full rebuild into the url the
+/// running app can load the asset from.
+///
+/// Without a resolver, adding a new `asset!()` call requires a full rebuild. The resolver is responsible for
+/// bundling the asset so it is available when the hot reloaded template is applied. It may be called more than
+/// once for the same path and should return `None` if the asset cannot be bundled.
+///
+/// ```rust, ignore
+/// let assets = AssetResolver::new(|path| Some(format!("/assets/{}", path.trim_start_matches('/'))));
+/// let result = HotReloadResult::new_with_assets::<HtmlCtx>(&old, &new, name, &assets);
+/// ```
+#[derive(Clone, Default)]
+pub struct AssetResolver(Option<Rc<ResolveAsset>>);
+
+type ResolveAsset = dyn Fn(&str) -> Option<String>;
+
+impl AssetResolver {
+    /// Create a new asset resolver from a function that takes the path passed to `asset!()` and returns the url
+    /// of the bundled asset
+    pub fn new(resolve: impl Fn(&str) -> Option<String> + 'static) -> Self {
+        Self(Some(Rc::new(resolve)))
+    }
+
+    fn resolve(&self, path: &str) -> Option<String> {
+        self.0.as_ref()?(path)
+    }
+}
+
+impl Debug for AssetResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AssetResolver")
+            .field(&self.0.is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for AssetResolver {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
 }
 
 impl HotReloadResult {
@@ -121,6 +170,21 @@ impl HotReloadResult {
         full_rebuild_state: &TemplateBody,
         new: &TemplateBody,
         name: String,
+    ) -> Option<Self> {
+        Self::new_with_assets::<Ctx>(full_rebuild_state, new, name, &AssetResolver::default())
+    }
+
+    /// Calculate the hot reload diff between two template bodies, allowing new `asset!()` calls that can be
+    /// resolved with the [`AssetResolver`]
+    ///
+    /// A new `asset!()` call can be hot reloaded if it only contains the path of the asset and is used as the value
+    /// of an element attribute or as a child node. String literals in braces like `{"hello"}` can also be added
+    /// as child nodes without a rebuild.
+    pub fn new_with_assets<Ctx: HotReloadingContext>(
+        full_rebuild_state: &TemplateBody,
+        new: &TemplateBody,
+        name: String,
+        assets: &AssetResolver,
     ) -> Option<Self> {
         let full_rebuild_state = LastBuildState::new(full_rebuild_state, name);
         let mut s = Self {
@@ -129,6 +193,7 @@ impl HotReloadResult {
             dynamic_nodes: Default::default(),
             dynamic_attributes: Default::default(),
             literal_component_properties: Default::default(),
+            assets: assets.clone(),
         };
 
         s.hotreload_body::<Ctx>(new)?;
@@ -168,7 +233,7 @@ impl HotReloadResult {
     /// Generally we can't hot reload a node if:
     /// - We add or modify a new rust expression
     ///   - Adding a new formatted segment we haven't seen before
-    ///   - Adding a new dynamic node (loop, fragment, if chain, etc)
+    ///   - Adding a new dynamic node (loop, fragment, if chain, etc) other than a string literal or an asset
     /// - We add a new component field
     /// - We remove a component field
     /// - We change the type of a component field
@@ -254,15 +319,49 @@ impl HotReloadResult {
             .position(|node| match &node {
                 BodyNode::RawExpr(raw_expr) => raw_expr.expr == expr.expr,
                 _ => false,
-            })?;
+            });
 
         // If we find it, push it as a dynamic node
+        if let Some(expr_index) = expr_index {
+            self.dynamic_nodes
+                .push(HotReloadDynamicNode::Dynamic(expr_index));
+            return Some(());
+        }
+
+        // Otherwise, the expression is new. We can only hot reload it if we know the text it renders
+        let text = match syn::parse2::<syn::Expr>(expr.expr.expr.clone()).ok()? {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => lit.value(),
+            expr => self.resolve_new_asset(&expr)?,
+        };
         self.dynamic_nodes
-            .push(HotReloadDynamicNode::Dynamic(expr_index));
+            .push(HotReloadDynamicNode::Formatted(literal_segments(text)));
 
         Some(())
     }
 
+    /// Try to resolve an `asset!()` call that wasn't in the last build to the url of the asset
+    fn resolve_new_asset(&self, expr: &syn::Expr) -> Option<String> {
+        let syn::Expr::Macro(expr) = expr else {
+            return None;
+        };
+        if expr.mac.path.segments.last()?.ident != "asset" {
+            return None;
+        }
+        // Assets with options need to be processed by the compiler, so we only support the path
+        let path = expr
+            .mac
+            .parse_body_with(|input: syn::parse::ParseStream| {
+                let path = input.parse::<syn::LitStr>()?;
+                input.parse::<Option<syn::Token![,]>>()?;
+                Ok(path.value())
+            })
+            .ok()?;
+        self.assets.resolve(&path)
+    }
+
     fn hotreload_for_loop<Ctx>(&mut self, forloop: &ForLoop) -> Option<()>
     where
         Ctx: HotReloadingContext,
@@ -333,9 +432,12 @@ impl HotReloadResult {
             if self.templates.contains_key(&body.template_idx.get()) {
                 continue;
             }
-            if let Some(state) =
-                Self::new::<Ctx>(body, new_call_body, self.full_rebuild_state.name.clone())
-            {
+            if let Some(state) = Self::new_with_assets::<Ctx>(
+                body,
+                new_call_body,
+                self.full_rebuild_state.name.clone(),
+                &self.assets,
+            ) {
                 let score = state.full_rebuild_state.unused_dynamic_items();
                 if score < best_score {
                     best_score = score;
@@ -480,6 +582,7 @@ impl HotReloadResult {
                 old_if_chain,
                 new_if_chain,
                 self.full_rebuild_state.name.clone(),
+                &self.assets,
             ) else {
                 continue;
             };
@@ -516,6 +619,7 @@ impl HotReloadResult {
         old_if_chain: &IfChain,
         new_if_chain: &IfChain,
         name: String,
+        assets: &AssetResolver,
     ) -> Option<Vec<Self>> {
         // Go through each part of the if chain and find the best match
         let mut old_chain = old_if_chain;
@@ -530,8 +634,12 @@ impl HotReloadResult {
             }
 
             // If the branches are the same, we can hotreload them
-            let hot_reload =
-                Self::new::<Ctx>(&old_chain.then_branch, &new_chain.then_branch, name.clone())?;
+            let hot_reload = Self::new_with_assets::<Ctx>(
+                &old_chain.then_branch,
+                &new_chain.then_branch,
+                name.clone(),
+                assets,
+            )?;
             chain_templates.push(hot_reload);
 
             // Make sure the if else branches match
@@ -552,7 +660,7 @@ impl HotReloadResult {
         // Make sure the else branches match
         match (&old_chain.else_branch, &new_chain.else_branch) {
             (Some(old), Some(new)) => {
-                let template = Self::new::<Ctx>(old, new, name.clone())?;
+                let template = Self::new_with_assets::<Ctx>(old, new, name.clone(), assets)?;
                 chain_templates.push(template);
             }
             (None, None) => {}
@@ -618,6 +726,18 @@ impl HotReloadResult {
                 let hot_reload_literal = self.full_rebuild_state.hotreload_hot_literal(literal)?;
                 HotReloadAttributeValue::Literal(hot_reload_literal)
             }
+            // If the attribute is an asset that wasn't in the last build, we can hot reload it as the url of the asset
+            AttributeValue::AttrExpr(expr)
+                if !self
+                    .full_rebuild_state
+                    .dynamic_attributes
+                    .inner
+                    .iter()
+                    .any(|a| a.inner.value == attribute.value) =>
+            {
+                let url = self.resolve_new_asset(&syn::parse2(expr.expr.clone()).ok()?)?;
+                HotReloadAttributeValue::Literal(HotReloadLiteral::Fmted(literal_segments(url)))
+            }
             // If it isn't a literal, try to find an exact match for the attribute value from the last build
             _ => {
                 let value_index = self.full_rebuild_state.dynamic_attributes.position(|a| {
@@ -648,3 +768,10 @@ impl HotReloadResult {
         Some(())
     }
 }
+
+/// Create formatted segments that only contain a single literal
+fn literal_segments(value: String) -> FmtedSegments {
+    FmtedSegments::new(vec![FmtSegment::Literal {
+        value: Box::leak(value.into_boxed_str()),
+    }])
+}
@@ -12,7 +12,7 @@ use dioxus_core::{
 };
 use dioxus_core_types::HotReloadingContext;
 use dioxus_rsx::CallBody;
-use dioxus_rsx_hotreload::{self, diff_rsx, ChangedRsx, HotReloadResult};
+use dioxus_rsx_hotreload::{self, diff_rsx, AssetResolver, ChangedRsx, HotReloadResult};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::{parse::Parse, spanned::Spanned, token::Token, File};
@@ -1261,3 +1261,98 @@ fn valid_spread() {
 
     assert!(valid);
 }
+
+fn hot_reload_with_assets(
+    old: TokenStream,
+    new: TokenStream,
+) -> Option<HashMap<usize, HotReloadedTemplate>> {
+    let old: CallBody = syn::parse2(old).unwrap();
+    let new: CallBody = syn::parse2(new).unwrap();
+    let assets = AssetResolver::new(|path| {
+        path.ends_with(".png")
+            .then(|| format!("/assets/{}-hash.png", path.trim_start_matches('/')))
+    });
+
+    let results = HotReloadResult::new_with_assets::<Mock>(
+        &old.body,
+        &new.body,
+        Default::default(),
+        &assets,
+    )?;
+    Some(results.templates)
+}
+
+// New assets can be hot reloaded if the resolver can bundle them
+#[test]
+fn valid_new_asset() {
+    let old = quote! {
+        img { src: asset!("/logo.png") }
+    };
+    let new = quote! {
+        img { src: asset!("/logo.png") }
+        img { src: asset!("/icon.png") }
+        {asset!("/text.png")}
+    };
+
+    let templates = hot_reload_with_assets(old.clone(), new.clone()).unwrap();
+    let template = &templates[&0];
+    assert_eq!(
+        template.dynamic_attributes,
+        &[
+            HotReloadDynamicAttribute::Named(NamedAttribute::new(
+                "src",
+                None,
+                HotReloadAttributeValue::Dynamic(0)
+            )),
+            HotReloadDynamicAttribute::Named(NamedAttribute::new(
+                "src",
+                None,
+                HotReloadAttributeValue::Literal(HotReloadLiteral::Fmted(FmtedSegments::new(
+                    vec![FmtSegment::Literal {
+                        value: "/assets/icon.png-hash.png"
+                    }]
+                )))
+            )),
+        ]
+    );
+    assert_eq!(
+        template.dynamic_nodes,
+        &[HotReloadDynamicNode::Formatted(FmtedSegments::new(vec![
+            FmtSegment::Literal {
+                value: "/assets/text.png-hash.png"
+            }
+        ]))]
+    );
+
+    // Without a resolver, new assets require a rebuild
+    assert!(!can_hotreload(old, new));
+
+    // Assets the resolver can't bundle and assets with options require a rebuild
+    assert!(hot_reload_with_assets(
+        quote! { img {} },
+        quote! { img { src: asset!("/style.css") } },
+    )
+    .is_none());
+    assert!(hot_reload_with_assets(
+        quote! { img {} },
+        quote! { img { src: asset!("/logo.png", ImageAssetOptions::new()) } },
+    )
+    .is_none());
+}
+
+// String literals can be added as child nodes
+#[test]
+fn valid_new_string_literal_node() {
+    let templates = hot_reload_from_tokens(
+        quote! { div { "hello" } },
+        quote! { div { "hello" {"world"} } },
+    )
+    .unwrap();
+
+    assert_eq!(
+        templates[&0].dynamic_nodes,
+        &[HotReloadDynamicNode::Formatted(FmtedSegments::new(vec![
+            FmtSegment::Literal { value: "world" }
+        ]))]
+    );
+}
@@ -0,0 +1,388 @@
+//! Validation for ARIA roles and attributes on elements.
+//!
+//! Proc macros can't emit warnings on stable rust, so warnings are emitted as uses of deprecated items with the
+//! warning as the deprecation note. They show up like any other lint and don't stop the build.
+
+use crate::{Attribute, AttributeName};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+
+/// Every role defined by WAI-ARIA 1.2 and the ARIA in HTML specification
+const ROLES: &[&str] = &[
+    "alert",
+    "alertdialog",
+    "application",
+    "article",
+    "banner",
+    "blockquote",
+    "button",
+    "caption",
+    "cell",
+    "checkbox",
+    "code",
+    "columnheader",
+    "combobox",
+    "comment",
+    "complementary",
+    "contentinfo",
+    "definition",
+    "deletion",
+    "dialog",
+    "directory",
+    "document",
+    "emphasis",
+    "feed",
+    "figure",
+    "form",
+    "generic",
+    "grid",
+    "gridcell",
+    "group",
+    "heading",
+    "img",
+    "image",
+    "insertion",
+    "link",
+    "list",
+    "listbox",
+    "listitem",
+    "log",
+    "main",
+    "mark",
+    "marquee",
+    "math",
+    "menu",
+    "menubar",
+    "menuitem",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "meter",
+    "navigation",
+    "none",
+    "note",
+    "option",
+    "paragraph",
+    "presentation",
+    "progressbar",
+    "radio",
+    "radiogroup",
+    "region",
+    "row",
+    "rowgroup",
+    "rowheader",
+    "scrollbar",
+    "search",
+    "searchbox",
+    "separator",
+    "slider",
+    "spinbutton",
+    "status",
+    "strong",
+    "subscript",
+    "suggestion",
+    "superscript",
+    "switch",
+    "tab",
+    "table",
+    "tablist",
+    "tabpanel",
+    "term",
+    "textbox",
+    "time",
+    "timer",
+    "toolbar",
+    "tooltip",
+    "tree",
+    "treegrid",
+    "treeitem",
+];
+
+/// Roles from the DPUB-ARIA and Graphics ARIA modules use a common prefix
+const ROLE_PREFIXES: &[&str] = &["doc-", "graphics-"];
+
+/// ARIA attributes that are only supported by some roles and the roles that support them
+const ROLE_SPECIFIC_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("aria-autocomplete", &["combobox", "searchbox", "textbox"]),
+    (
+        "aria-checked",
+        &[
+            "checkbox",
+            "menuitemcheckbox",
+            "menuitemradio",
+            "option",
+            "radio",
+            "switch",
+            "treeitem",
+        ],
+    ),
+    (
+        "aria-expanded",
+        &[
+            "application",
+            "button",
+            "checkbox",
+            "combobox",
+            "gridcell",
+            "link",
+            "listbox",
+            "menuitem",
+            "menuitemcheckbox",
+            "menuitemradio",
+            "row",
+            "rowheader",
+            "switch",
+            "tab",
+            "treeitem",
+        ],
+    ),
+    (
+        "aria-level",
+        &["comment", "heading", "listitem", "row", "treeitem"],
+    ),
+    ("aria-modal", &["alertdialog", "dialog"]),
+    ("aria-multiline", &["searchbox", "textbox"]),
+    (
+        "aria-multiselectable",
+        &["grid", "listbox", "tablist", "tree", "treegrid"],
+    ),
+    (
+        "aria-orientation",
+        &[
+            "listbox",
+            "menu",
+            "menubar",
+            "radiogroup",
+            "scrollbar",
+            "separator",
+            "slider",
+            "tablist",
+            "toolbar",
+            "tree",
+            "treegrid",
+        ],
+    ),
+    ("aria-placeholder", &["searchbox", "textbox"]),
+    ("aria-pressed", &["button"]),
+    (
+        "aria-readonly",
+        &[
+            "checkbox",
+            "columnheader",
+            "combobox",
+            "grid",
+            "gridcell",
+            "listbox",
+            "menuitemcheckbox",
+            "menuitemradio",
+            "radiogroup",
+            "rowheader",
+            "searchbox",
+            "slider",
+            "spinbutton",
+            "switch",
+            "textbox",
+            "treegrid",
+        ],
+    ),
+    (
+        "aria-required",
+        &[
+            "checkbox",
+            "columnheader",
+            "combobox",
+            "gridcell",
+            "listbox",
+            "radiogroup",
+            "rowheader",
+            "searchbox",
+            "spinbutton",
+            "switch",
+            "textbox",
+            "tree",
+            "treegrid",
+        ],
+    ),
+    (
+        "aria-selected",
+        &[
+            "columnheader",
+            "gridcell",
+            "option",
+            "row",
+            "rowheader",
+            "tab",
+            "treeitem",
+        ],
+    ),
+    ("aria-sort", &["columnheader", "rowheader"]),
+    (
+        "aria-valuemax",
+        &[
+            "meter",
+            "progressbar",
+            "scrollbar",
+            "separator",
+            "slider",
+            "spinbutton",
+        ],
+    ),
+    (
+        "aria-valuemin",
+        &[
+            "meter",
+            "progressbar",
+            "scrollbar",
+            "separator",
+            "slider",
+            "spinbutton",
+        ],
+    ),
+    (
+        "aria-valuenow",
+        &[
+            "meter",
+            "progressbar",
+            "scrollbar",
+            "separator",
+            "slider",
+            "spinbutton",
+        ],
+    ),
+    (
+        "aria-valuetext",
+        &[
+            "meter",
+            "progressbar",
+            "scrollbar",
+            "separator",
+            "slider",
+            "spinbutton",
+        ],
+    ),
+];
+
+/// ARIA attributes that every role supports
+const GLOBAL_ATTRIBUTES: &[&str] = &[
+    "aria-activedescendant",
+    "aria-atomic",
+    "aria-braillelabel",
+    "aria-brailleroledescription",
+    "aria-busy",
+    "aria-colcount",
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-controls",
+    "aria-current",
+    "aria-describedby",
+    "aria-description",
+    "aria-details",
+    "aria-disabled",
+    "aria-dropeffect",
+    "aria-errormessage",
+    "aria-flowto",
+    "aria-grabbed",
+    "aria-haspopup",
+    "aria-hidden",
+    "aria-invalid",
+    "aria-keyshortcuts",
+    "aria-label",
+    "aria-labelledby",
+    "aria-live",
+    "aria-owns",
+    "aria-posinset",
+    "aria-relevant",
+    "aria-roledescription",
+    "aria-rowcount",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-setsize",
+];
+
+fn is_known_role(role: &str) -> bool {
+    ROLES.contains(&role) || ROLE_PREFIXES.iter().any(|prefix| role.starts_with(prefix))
+}
+
+/// The html name of an attribute like `aria-checked` for `aria_checked`
+fn html_name(name: &AttributeName) -> Option<String> {
+    match name {
+        AttributeName::BuiltIn(ident) => Some(ident.to_string().replace('_', "-")),
+        AttributeName::Custom(lit) => Some(lit.value()),
+        AttributeName::Spread(_) => None,
+    }
+}
+
+/// Check the static `role` and `aria-*` attributes of an element and return tokens that emit warnings for unknown
+/// roles, unknown ARIA attributes and ARIA attributes the role doesn't support.
+pub(crate) fn aria_warnings(attributes: &[Attribute]) -> TokenStream2 {
+    let mut warnings = Vec::new();
+
+    // The role attribute is a list of roles where the first role the platform supports is used
+    let mut role = None;
+    for attr in attributes {
+        let Some((AttributeName::BuiltIn(name), value)) = attr.as_static_str_literal() else {
+            continue;
+        };
+        if name != "role" {
+            continue;
+        }
+        let Some(value) = value.to_static() else {
+            continue;
+        };
+        let span = attr.value.span();
+        for token in value.split_whitespace() {
+            if is_known_role(token) {
+                role = role.or(Some(token.to_string()));
+            } else {
+                warnings.push((format!("`{token}` is not a valid ARIA role"), span));
+            }
+        }
+    }
+
+    for attr in attributes {
+        let Some(name) = html_name(&attr.name) else {
+            continue;
+        };
+        if !name.starts_with("aria-") {
+            continue;
+        }
+        let span = attr.name.span();
+        match ROLE_SPECIFIC_ATTRIBUTES
+            .iter()
+            .find(|(attr, _)| *attr == name)
+        {
+            Some((_, supported)) => {
+                if let Some(role) = &role {
+                    if !supported.contains(&role.as_str()) {
+                        warnings.push((
+                            format!("`{name}` is not supported by the ARIA role `{role}`"),
+                            span,
+                        ));
+                    }
+                }
+            }
+            None if !GLOBAL_ATTRIBUTES.contains(&name.as_str()) => {
+                warnings.push((format!("`{name}` is not a known ARIA attribute"), span));
+            }
+            None => {}
+        }
+    }
+
+    let warnings = warnings
+        .into_iter()
+        .map(|(note, span)| warning(&note, span));
+    quote! { #(#warnings)* }
+}
+
+fn warning(note: &str, span: Span) -> TokenStream2 {
+    let use_warning = quote_spanned! { span => __aria_warning };
+    quote! {
+        {
+            #[deprecated(note = #note)]
+            #[allow(non_camel_case_types)]
+            struct __aria_warning;
+            let _ = #use_warning;
+        }
+    }
+}
@@ -206,6 +206,22 @@ impl Attribute {
             AttributeName::Spread(_) => unreachable!("Spread attributes are handled elsewhere"),
         };
 
+        // The style attribute can be a typed style that sets each property as its own attribute
+        if self.is_style_expr() {
+            let name = &self.name;
+            let ns = ns(name);
+            let volatile = volatile(name);
+            let attribute = attribute(name);
+            let value = &self.value;
+            let completion_hints = self.completion_hints();
+            return quote! {
+                {
+                    #completion_hints
+                    dioxus_core::IntoAttributes::into_attributes(#value, #attribute, #ns, #volatile)
+                }
+            };
+        }
+
         let attribute = {
             let value = &self.value;
             let name = &self.name;
@@ -259,6 +275,7 @@ impl Attribute {
         };
 
         let completion_hints = self.completion_hints();
+
         quote! {
             Box::new([
                 {
@@ -270,6 +287,18 @@ impl Attribute {
         .to_token_stream()
     }
 
+    /// Check if this is the `style` attribute of a built in element with an expression as the value
+    fn is_style_expr(&self) -> bool {
+        matches!(
+            (&self.el_name, &self.name, &self.value),
+            (
+                Some(ElementName::Ident(_)),
+                AttributeName::BuiltIn(name),
+                AttributeValue::AttrExpr(_) | AttributeValue::Shorthand(_)
+            ) if name == "style"
+        )
+    }
+
     pub fn can_be_shorthand(&self) -> bool {
         // If it's a shorthand...
         if matches!(self.value, AttributeValue::Shorthand(_)) {
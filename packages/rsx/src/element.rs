@@ -191,6 +191,7 @@ impl ToTokens for Element {
         let el_name = el_name.tag_name();
         let diagnostics = &el.diagnostics;
         let completion_hints = &el.completion_hints();
+        let aria_warnings = crate::aria::aria_warnings(&el.merged_attributes);
 
         // todo: generate less code if there's no diagnostics by not including the curlies
         tokens.append_all(quote! {
@@ -199,6 +200,8 @@ impl ToTokens for Element {
 
                 #diagnostics
 
+                #aria_warnings
+
                 dioxus_core::TemplateNode::Element {
                     tag: #el_name,
                     namespace: #ns,
@@ -141,3 +141,29 @@ fn complex_kitchen_sink() {
 
     let _cb: CallBody = syn::parse2(item).unwrap();
 }
+
+#[test]
+fn aria_warnings() {
+    let expand = |item: proc_macro2::TokenStream| {
+        let cb: CallBody = syn::parse2(item).unwrap();
+        cb.to_token_stream().to_string()
+    };
+
+    let valid = expand(quote::quote! {
+        div { role: "checkbox", aria_checked: "true", aria_label: "Accept" }
+    });
+    assert!(!valid.contains("deprecated"));
+
+    let unknown_role = expand(quote::quote! { div { role: "buton" } });
+    assert!(unknown_role.contains("`buton` is not a valid ARIA role"));
+
+    let unsupported = expand(quote::quote! { div { role: "button", aria_checked: "true" } });
+    assert!(unsupported.contains("`aria-checked` is not supported by the ARIA role `button`"));
+
+    let unknown_attribute = expand(quote::quote! { div { "aria-unknown": "true" } });
+    assert!(unknown_attribute.contains("`aria-unknown` is not a known ARIA attribute"));
+
+    // Dynamic roles can't be checked at compile time
+    let dynamic = expand(quote::quote! { div { role: role, aria_checked: "true" } });
+    assert!(!dynamic.contains("deprecated"));
+}
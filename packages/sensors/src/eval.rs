@@ -0,0 +1,524 @@
+use std::{
+    future::{ready, Future},
+    marker::PhantomData,
+    pin::{pin, Pin},
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use dioxus_lib::document::{Document, Eval, EvalError};
+use futures_util::{stream, Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    AudioSamples, BatteryStatus, MediaConstraints, MediaDevice, MediaStream, MediaStreamSource,
+    Notification, Orientation, Permission, Position, PositionOptions, Sensor, SensorError,
+    SensorFuture, SensorProvider, SensorStream, VideoFrame,
+};
+
+/// Helpers shared by all sensor scripts that convert the outcome into something serde can read as a `Result`
+const PRELUDE: &str = r#"
+const sensorError = (e) => ({ name: (e && e.name) || "Error", message: String((e && e.message) || e) });
+const sensorUnsupported = (message) => ({ name: "NotSupportedError", message });
+const sensorPermission = (state) => ({ granted: "Granted", denied: "Denied" })[state] || "Prompt";
+const sensorResult = async (action) => {
+    try {
+        return { Ok: await action() };
+    } catch (e) {
+        return { Err: sensorError(e) };
+    }
+};
+const sensorWatch = async (start) => {
+    const options = await dioxus.recv();
+    const fail = (e) => dioxus.send({ Err: sensorError(e) });
+    let stop = () => {};
+    try {
+        stop = await start(options, (reading) => dioxus.send({ Ok: reading }), fail);
+    } catch (e) {
+        fail(e);
+    }
+    // Rust sends a message when the stream is dropped
+    await dioxus.recv();
+    stop();
+};
+const geolocationError = (e) => ({
+    name: ["Error", "NotAllowedError", "NotFoundError", "TimeoutError"][e.code] || "Error",
+    message: e.message,
+});
+const orientationSupported = () => typeof DeviceOrientationEvent !== "undefined";
+// Safari on iOS hides the orientation until the page asks for permission
+const orientationNeedsPermission = () => typeof DeviceOrientationEvent.requestPermission === "function";
+const mediaSupported = () => {
+    if (!navigator.mediaDevices) {
+        throw sensorUnsupported("navigator.mediaDevices is not available");
+    }
+};
+const mediaPermission = async (name) => {
+    mediaSupported();
+    try {
+        return sensorPermission((await navigator.permissions.query({ name })).state);
+    } catch {
+        // Firefox can't query the camera and microphone permissions
+        return "Prompt";
+    }
+};
+const mediaRequestPermission = async (constraints) => {
+    mediaSupported();
+    try {
+        const stream = await navigator.mediaDevices.getUserMedia(constraints);
+        stream.getTracks().forEach((track) => track.stop());
+        return "Granted";
+    } catch (e) {
+        if (e.name === "NotAllowedError") {
+            return "Denied";
+        }
+        throw e;
+    }
+};
+const notificationsSupported = () => {
+    if (typeof Notification === "undefined") {
+        throw sensorUnsupported("Notification is not available");
+    }
+};
+const mediaStreams = () => (window.dioxusMediaStreams ??= new Map());
+const mediaStream = (id) => {
+    const stream = mediaStreams().get(id);
+    if (!stream) {
+        throw { name: "InvalidStateError", message: "the media stream was stopped" };
+    }
+    return stream;
+};
+"#;
+
+const PERMISSION: &str = r#"
+switch (await dioxus.recv()) {
+    case "Geolocation":
+        if (!navigator.geolocation) {
+            throw sensorUnsupported("navigator.geolocation is not available");
+        }
+        if (!navigator.permissions) {
+            return "Prompt";
+        }
+        return sensorPermission((await navigator.permissions.query({ name: "geolocation" })).state);
+    case "Orientation":
+        if (!orientationSupported()) {
+            throw sensorUnsupported("DeviceOrientationEvent is not available");
+        }
+        // Safari can only check the permission by asking for it
+        return orientationNeedsPermission() ? "Prompt" : "Granted";
+    case "Battery":
+        if (!navigator.getBattery) {
+            throw sensorUnsupported("navigator.getBattery is not available");
+        }
+        return "Granted";
+    case "Camera":
+        return await mediaPermission("camera");
+    case "Microphone":
+        return await mediaPermission("microphone");
+    case "Notifications":
+        notificationsSupported();
+        return sensorPermission(Notification.permission);
+}
+"#;
+
+const REQUEST_PERMISSION: &str = r#"
+switch (await dioxus.recv()) {
+    case "Geolocation":
+        if (!navigator.geolocation) {
+            throw sensorUnsupported("navigator.geolocation is not available");
+        }
+        // Reading the position once shows the prompt
+        return await new Promise((resolve) => navigator.geolocation.getCurrentPosition(
+            () => resolve("Granted"),
+            (e) => resolve(e.code === e.PERMISSION_DENIED ? "Denied" : "Granted"),
+        ));
+    case "Orientation":
+        if (!orientationSupported()) {
+            throw sensorUnsupported("DeviceOrientationEvent is not available");
+        }
+        if (!orientationNeedsPermission()) {
+            return "Granted";
+        }
+        return sensorPermission(await DeviceOrientationEvent.requestPermission());
+    case "Battery":
+        if (!navigator.getBattery) {
+            throw sensorUnsupported("navigator.getBattery is not available");
+        }
+        return "Granted";
+    case "Camera":
+        return await mediaRequestPermission({ video: true });
+    case "Microphone":
+        return await mediaRequestPermission({ audio: true });
+    case "Notifications":
+        notificationsSupported();
+        return sensorPermission(await Notification.requestPermission());
+}
+"#;
+
+const WATCH_POSITION: &str = r#"
+if (!navigator.geolocation) {
+    throw sensorUnsupported("navigator.geolocation is not available");
+}
+const id = navigator.geolocation.watchPosition(
+    (position) => send({
+        latitude: position.coords.latitude,
+        longitude: position.coords.longitude,
+        accuracy: position.coords.accuracy,
+        altitude: position.coords.altitude,
+        altitude_accuracy: position.coords.altitudeAccuracy,
+        heading: position.coords.heading,
+        speed: position.coords.speed,
+        timestamp: position.timestamp,
+    }),
+    (e) => fail(geolocationError(e)),
+    options,
+);
+return () => navigator.geolocation.clearWatch(id);
+"#;
+
+const WATCH_ORIENTATION: &str = r#"
+if (!orientationSupported()) {
+    throw sensorUnsupported("DeviceOrientationEvent is not available");
+}
+if (orientationNeedsPermission() && (await DeviceOrientationEvent.requestPermission()) !== "granted") {
+    throw { name: "NotAllowedError", message: "permission to read the device orientation was denied" };
+}
+const listener = (e) => send({ alpha: e.alpha, beta: e.beta, gamma: e.gamma, absolute: !!e.absolute });
+window.addEventListener("deviceorientation", listener);
+return () => window.removeEventListener("deviceorientation", listener);
+"#;
+
+const WATCH_BATTERY: &str = r#"
+if (!navigator.getBattery) {
+    throw sensorUnsupported("navigator.getBattery is not available");
+}
+const battery = await navigator.getBattery();
+const finite = (time) => (Number.isFinite(time) ? time : null);
+const update = () => send({
+    level: battery.level,
+    charging: battery.charging,
+    charging_time: finite(battery.chargingTime),
+    discharging_time: finite(battery.dischargingTime),
+});
+const events = ["levelchange", "chargingchange", "chargingtimechange", "dischargingtimechange"];
+events.forEach((event) => battery.addEventListener(event, update));
+update();
+return () => events.forEach((event) => battery.removeEventListener(event, update));
+"#;
+
+const SHOW_NOTIFICATION: &str = r#"
+notificationsSupported();
+if (Notification.permission !== "granted") {
+    throw { name: "NotAllowedError", message: "permission to show notifications was not granted" };
+}
+const { title, ...options } = await dioxus.recv();
+// Some browsers like Chrome on Android can only show notifications from a service worker
+const registration = navigator.serviceWorker && (await navigator.serviceWorker.getRegistration());
+if (registration) {
+    await registration.showNotification(title, options);
+} else {
+    new Notification(title, options);
+}
+return null;
+"#;
+
+const MEDIA_DEVICES: &str = r#"
+mediaSupported();
+const kinds = { videoinput: "VideoInput", audioinput: "AudioInput", audiooutput: "AudioOutput" };
+return (await navigator.mediaDevices.enumerateDevices())
+    .filter((device) => kinds[device.kind])
+    .map((device) => ({
+        device_id: device.deviceId,
+        group_id: device.groupId,
+        kind: kinds[device.kind],
+        label: device.label,
+    }));
+"#;
+
+/// Opens the stream and keeps it in a map for the other media scripts until rust drops the stream
+const OPEN_MEDIA: &str = r#"
+mediaSupported();
+const ideal = (value) => (value == null ? undefined : { ideal: value });
+const exact = (value) => (value == null ? undefined : { exact: value });
+const { video, audio } = options;
+const stream = await navigator.mediaDevices.getUserMedia({
+    video: video ? {
+        deviceId: exact(video.device_id),
+        facingMode: ideal(video.facing),
+        width: ideal(video.width),
+        height: ideal(video.height),
+        frameRate: ideal(video.frame_rate),
+    } : false,
+    audio: audio ? {
+        deviceId: exact(audio.device_id),
+        echoCancellation: ideal(audio.echo_cancellation),
+        noiseSuppression: ideal(audio.noise_suppression),
+    } : false,
+});
+window.dioxusMediaStreamCount = (window.dioxusMediaStreamCount || 0) + 1;
+const id = window.dioxusMediaStreamCount;
+mediaStreams().set(id, stream);
+send(id);
+return () => {
+    stream.getTracks().forEach((track) => track.stop());
+    mediaStreams().delete(id);
+};
+"#;
+
+const ATTACH_MEDIA: &str = r#"
+const [id, element] = await dioxus.recv();
+const video = document.getElementById(element);
+if (!(video instanceof HTMLVideoElement)) {
+    throw { name: "NotFoundError", message: `there is no video element with the id ${element}` };
+}
+video.srcObject = mediaStream(id);
+return null;
+"#;
+
+const CAPTURE_FRAME: &str = r#"
+const video = document.createElement("video");
+video.muted = true;
+video.playsInline = true;
+video.srcObject = mediaStream(await dioxus.recv());
+await video.play();
+const canvas = document.createElement("canvas");
+canvas.width = video.videoWidth;
+canvas.height = video.videoHeight;
+video.pause();
+if (!canvas.width || !canvas.height) {
+    throw { name: "NotFoundError", message: "the media stream has no video" };
+}
+const context = canvas.getContext("2d");
+context.drawImage(video, 0, 0);
+video.srcObject = null;
+const { data } = context.getImageData(0, 0, canvas.width, canvas.height);
+return { width: canvas.width, height: canvas.height, rgba: Array.from(data) };
+"#;
+
+const WATCH_AUDIO: &str = r#"
+const context = new AudioContext();
+const source = context.createMediaStreamSource(mediaStream(options));
+const processor = context.createScriptProcessor(4096, 1, 1);
+processor.onaudioprocess = (e) => send({
+    sample_rate: context.sampleRate,
+    samples: Array.from(e.inputBuffer.getChannelData(0)),
+});
+source.connect(processor);
+// The processor only runs while it is connected to the output. It doesn't write anything, so it stays silent.
+processor.connect(context.destination);
+return () => {
+    processor.disconnect();
+    source.disconnect();
+    context.close();
+};
+"#;
+
+/// A [`SensorProvider`] that uses the [Geolocation](https://developer.mozilla.org/en-US/docs/Web/API/Geolocation_API),
+/// [Device Orientation](https://developer.mozilla.org/en-US/docs/Web/API/Device_orientation_events),
+/// [Battery Status](https://developer.mozilla.org/en-US/docs/Web/API/Battery_Status_API),
+/// [Media Capture](https://developer.mozilla.org/en-US/docs/Web/API/Media_Capture_and_Streams_API),
+/// [Notifications](https://developer.mozilla.org/en-US/docs/Web/API/Notifications_API) and
+/// [Permissions](https://developer.mozilla.org/en-US/docs/Web/API/Permissions_API) APIs through [`Document::eval`].
+///
+/// This works with any renderer that runs a javascript context like web, desktop, mobile and liveview. Which sensors
+/// are available depends on the browser or webview: most browsers only expose the position in a secure context, and
+/// Firefox and Safari don't expose the battery.
+#[derive(Clone)]
+pub struct EvalSensors {
+    document: Rc<dyn Document>,
+}
+
+impl EvalSensors {
+    /// Create a new [`EvalSensors`] that evaluates javascript in the given document.
+    pub fn new(document: Rc<dyn Document>) -> Self {
+        Self { document }
+    }
+
+    fn run<T: DeserializeOwned + 'static>(
+        &self,
+        script: &str,
+        data: impl Serialize,
+    ) -> SensorFuture<T> {
+        let eval = self.document.eval(format!(
+            "{PRELUDE}\nreturn await sensorResult(async () => {{{script}}});"
+        ));
+        if let Err(err) = eval.send(data) {
+            return Box::pin(ready(Err(err.into())));
+        }
+        Box::pin(async move {
+            let result: Result<T, JsSensorError> = eval.join().await?;
+            result.map_err(Into::into)
+        })
+    }
+
+    fn watch<T: DeserializeOwned + 'static>(
+        &self,
+        script: &str,
+        options: impl Serialize,
+    ) -> SensorStream<T> {
+        let eval = self.document.eval(format!(
+            "{PRELUDE}\nawait sensorWatch(async (options, send, fail) => {{{script}}});\nreturn null;"
+        ));
+        if let Err(err) = eval.send(options) {
+            return Box::pin(stream::once(ready(Err(err.into()))));
+        }
+        Box::pin(EvalSensorStream {
+            eval,
+            finished: false,
+            reading: PhantomData,
+        })
+    }
+}
+
+impl SensorProvider for EvalSensors {
+    fn permission(&self, sensor: Sensor) -> SensorFuture<Permission> {
+        self.run(PERMISSION, sensor)
+    }
+
+    fn request_permission(&self, sensor: Sensor) -> SensorFuture<Permission> {
+        self.run(REQUEST_PERMISSION, sensor)
+    }
+
+    fn watch_position(&self, options: PositionOptions) -> SensorStream<Position> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct JsPositionOptions {
+            enable_high_accuracy: bool,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            timeout: Option<u128>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            maximum_age: Option<u128>,
+        }
+
+        self.watch(
+            WATCH_POSITION,
+            JsPositionOptions {
+                enable_high_accuracy: options.is_high_accuracy(),
+                timeout: options.get_timeout().map(|timeout| timeout.as_millis()),
+                maximum_age: options.get_maximum_age().map(|age| age.as_millis()),
+            },
+        )
+    }
+
+    fn watch_orientation(&self) -> SensorStream<Orientation> {
+        self.watch(WATCH_ORIENTATION, ())
+    }
+
+    fn watch_battery(&self) -> SensorStream<BatteryStatus> {
+        self.watch(WATCH_BATTERY, ())
+    }
+
+    fn media_devices(&self) -> SensorFuture<Vec<MediaDevice>> {
+        self.run(MEDIA_DEVICES, ())
+    }
+
+    fn show_notification(&self, notification: Notification) -> SensorFuture<()> {
+        self.run(SHOW_NOTIFICATION, notification)
+    }
+
+    fn open_media(&self, constraints: MediaConstraints) -> SensorFuture<MediaStream> {
+        let mut capture = self.watch::<u32>(OPEN_MEDIA, constraints);
+        let sensors = self.clone();
+        Box::pin(async move {
+            let id = capture
+                .next()
+                .await
+                .unwrap_or(Err(SensorError::Unavailable))?;
+            Ok(MediaStream::new(EvalMediaStream {
+                id,
+                sensors,
+                _capture: capture,
+            }))
+        })
+    }
+}
+
+/// A media stream that lives in javascript. Dropping the capture script stops the stream.
+struct EvalMediaStream {
+    id: u32,
+    sensors: EvalSensors,
+    _capture: SensorStream<u32>,
+}
+
+impl MediaStreamSource for EvalMediaStream {
+    fn attach(&self, element_id: &str) -> SensorFuture<()> {
+        self.sensors.run(ATTACH_MEDIA, (self.id, element_id))
+    }
+
+    fn capture_frame(&self) -> SensorFuture<VideoFrame> {
+        self.sensors.run(CAPTURE_FRAME, self.id)
+    }
+
+    fn watch_audio(&self) -> SensorStream<AudioSamples> {
+        self.sensors.watch(WATCH_AUDIO, self.id)
+    }
+}
+
+/// The readings a watch script sends. The stream ends after the first error, and dropping it tells the script to stop
+/// watching the sensor.
+struct EvalSensorStream<T> {
+    eval: Eval,
+    finished: bool,
+    reading: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Stream for EvalSensorStream<T> {
+    type Item = Result<T, SensorError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+        // Receiving is cancel safe, so a new future can be polled every time
+        let next = {
+            let recv = pin!(self.eval.recv::<Result<T, JsSensorError>>());
+            match recv.poll(cx) {
+                Poll::Ready(next) => next,
+                Poll::Pending => return Poll::Pending,
+            }
+        };
+        let next = match next {
+            Ok(Ok(reading)) => Ok(reading),
+            Ok(Err(err)) => Err(err.into()),
+            Err(err) => Err(err.into()),
+        };
+        self.finished = next.is_err();
+        Poll::Ready(Some(next))
+    }
+}
+
+impl<T> Drop for EvalSensorStream<T> {
+    fn drop(&mut self) {
+        _ = self.eval.send(());
+    }
+}
+
+/// A javascript exception thrown while reading a sensor
+#[derive(Deserialize)]
+struct JsSensorError {
+    name: String,
+    message: String,
+}
+
+impl From<JsSensorError> for SensorError {
+    fn from(err: JsSensorError) -> Self {
+        match err.name.as_str() {
+            "NotAllowedError" | "SecurityError" => SensorError::PermissionDenied,
+            "NotSupportedError" => SensorError::Unsupported,
+            "NotFoundError" | "NotReadableError" | "OverconstrainedError" => {
+                SensorError::Unavailable
+            }
+            "TimeoutError" => SensorError::Timeout,
+            _ => SensorError::Platform(format!("{}: {}", err.name, err.message)),
+        }
+    }
+}
+
+impl From<EvalError> for SensorError {
+    fn from(err: EvalError) -> Self {
+        match err {
+            EvalError::Unsupported => SensorError::Unsupported,
+            err => SensorError::Platform(err.to_string()),
+        }
+    }
+}
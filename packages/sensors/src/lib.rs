@@ -0,0 +1,333 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+use dioxus_lib::prelude::*;
+use futures_util::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    future::{ready, Future},
+    pin::Pin,
+    rc::Rc,
+    time::Duration,
+};
+
+mod eval;
+mod media;
+mod mock;
+mod notification;
+pub use eval::*;
+pub use media::*;
+pub use mock::*;
+pub use notification::*;
+
+/// Get the sensor provider of the app.
+///
+/// If the app didn't provide a [`SensorProvider`] in the root context, this falls back to [`EvalSensors`], which reads
+/// the sensors through the browser APIs of the webview or browser the app runs in.
+pub fn sensors() -> Rc<dyn SensorProvider> {
+    match try_consume_context::<Rc<dyn SensorProvider>>() {
+        Some(sensors) => sensors,
+        None => {
+            provide_root_context(Rc::new(EvalSensors::new(dioxus_lib::document::document()))
+                as Rc<dyn SensorProvider>)
+        }
+    }
+}
+
+/// The future returned by [`SensorProvider`] operations
+pub type SensorFuture<T> = Pin<Box<dyn Future<Output = Result<T, SensorError>>>>;
+
+/// The stream of readings returned by the watch methods of [`SensorProvider`]. Dropping the stream stops watching the
+/// sensor.
+pub type SensorStream<T> = Pin<Box<dyn Stream<Item = Result<T, SensorError>>>>;
+
+/// The latest reading of a sensor returned by the sensor hooks. It is `None` until the first reading arrives.
+pub type SensorReading<T> = ReadOnlySignal<Option<Result<T, SensorError>>>;
+
+/// Access to the sensors of the device.
+///
+/// Provide your own implementation in the root context to read sensors from a native API instead of the browser APIs:
+///
+/// ```rust, ignore
+/// fn App() -> Element {
+///     use_context_provider(|| Rc::new(MyNativeSensors::new()) as Rc<dyn SensorProvider>);
+///     rsx! { Map {} }
+/// }
+/// ```
+pub trait SensorProvider {
+    /// Check if the app may read the sensor without asking the user
+    fn permission(&self, sensor: Sensor) -> SensorFuture<Permission>;
+
+    /// Ask the user for permission to read the sensor. Some platforms only show the prompt in response to user input,
+    /// like a click.
+    fn request_permission(&self, sensor: Sensor) -> SensorFuture<Permission>;
+
+    /// Watch the position of the device
+    fn watch_position(&self, options: PositionOptions) -> SensorStream<Position>;
+
+    /// Watch the orientation of the device
+    fn watch_orientation(&self) -> SensorStream<Orientation>;
+
+    /// Watch the battery of the device
+    fn watch_battery(&self) -> SensorStream<BatteryStatus>;
+
+    /// List the cameras, microphones and speakers connected to the device. Providers without media capture report
+    /// [`SensorError::Unsupported`].
+    fn media_devices(&self) -> SensorFuture<Vec<MediaDevice>> {
+        Box::pin(ready(Err(SensorError::Unsupported)))
+    }
+
+    /// Start capturing video and audio. Providers without media capture report [`SensorError::Unsupported`].
+    fn open_media(&self, constraints: MediaConstraints) -> SensorFuture<MediaStream> {
+        _ = constraints;
+        Box::pin(ready(Err(SensorError::Unsupported)))
+    }
+
+    /// Show a notification. Providers without notifications report [`SensorError::Unsupported`].
+    fn show_notification(&self, notification: Notification) -> SensorFuture<()> {
+        _ = notification;
+        Box::pin(ready(Err(SensorError::Unsupported)))
+    }
+}
+
+/// A sensor the app can ask for permission to read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Sensor {
+    /// The position of the device
+    Geolocation,
+    /// The orientation of the device
+    Orientation,
+    /// The battery of the device
+    Battery,
+    /// The cameras of the device
+    Camera,
+    /// The microphones of the device
+    Microphone,
+    /// Showing notifications to the user
+    Notifications,
+}
+
+/// Whether the app may read a sensor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Permission {
+    /// The app may read the sensor
+    Granted,
+    /// The user or platform denied access to the sensor
+    Denied,
+    /// The user will be asked the next time the app reads the sensor or requests permission
+    Prompt,
+}
+
+/// The position of the device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    /// The latitude in degrees
+    pub latitude: f64,
+    /// The longitude in degrees
+    pub longitude: f64,
+    /// The accuracy of the latitude and longitude in meters
+    pub accuracy: f64,
+    /// The altitude in meters above the WGS84 ellipsoid, if the device knows it
+    pub altitude: Option<f64>,
+    /// The accuracy of the altitude in meters
+    pub altitude_accuracy: Option<f64>,
+    /// The direction the device is moving in, in degrees clockwise from true north
+    pub heading: Option<f64>,
+    /// The speed of the device in meters per second
+    pub speed: Option<f64>,
+    /// When the position was measured, in milliseconds since the unix epoch
+    pub timestamp: f64,
+}
+
+/// Options for watching the position of the device
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PositionOptions {
+    high_accuracy: bool,
+    timeout: Option<Duration>,
+    maximum_age: Option<Duration>,
+}
+
+impl PositionOptions {
+    /// Create options with the default accuracy and no timeout
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask for the most accurate position the device can measure. This can be slower and use more power.
+    pub fn high_accuracy(mut self, high_accuracy: bool) -> Self {
+        self.high_accuracy = high_accuracy;
+        self
+    }
+
+    /// Report [`SensorError::Timeout`] if measuring a position takes longer than this
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Accept a cached position that is at most this old
+    pub fn maximum_age(mut self, maximum_age: Duration) -> Self {
+        self.maximum_age = Some(maximum_age);
+        self
+    }
+
+    /// Check if the most accurate position is requested
+    pub fn is_high_accuracy(&self) -> bool {
+        self.high_accuracy
+    }
+
+    /// Get the timeout for measuring a position
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Get the maximum age of a cached position
+    pub fn get_maximum_age(&self) -> Option<Duration> {
+        self.maximum_age
+    }
+}
+
+/// The orientation of the device. See the [device orientation spec](https://w3c.github.io/deviceorientation/) for
+/// the coordinate system.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Orientation {
+    /// The rotation around the z axis in degrees, from 0 to 360
+    pub alpha: Option<f64>,
+    /// The rotation around the x axis in degrees, from -180 to 180
+    pub beta: Option<f64>,
+    /// The rotation around the y axis in degrees, from -90 to 90
+    pub gamma: Option<f64>,
+    /// If the orientation is relative to the earth instead of an arbitrary starting orientation
+    pub absolute: bool,
+}
+
+/// The state of the battery of the device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatteryStatus {
+    /// How full the battery is, from 0 to 1
+    pub level: f64,
+    /// If the battery is charging
+    pub charging: bool,
+    /// The seconds until the battery is full, if it is charging and the platform knows
+    pub charging_time: Option<f64>,
+    /// The seconds until the battery is empty, if it is discharging and the platform knows
+    pub discharging_time: Option<f64>,
+}
+
+/// An error while reading a sensor
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum SensorError {
+    /// The platform doesn't have the sensor or doesn't expose it
+    Unsupported,
+    /// The user or platform denied access to the sensor
+    PermissionDenied,
+    /// The sensor couldn't produce a reading, like a position without a gps fix
+    Unavailable,
+    /// The sensor took too long to produce a reading
+    Timeout,
+    /// The platform returned an error while reading the sensor
+    Platform(String),
+}
+
+impl Display for SensorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SensorError::Unsupported => write!(
+                f,
+                "SensorError::Unsupported - the sensor is not supported on the current platform"
+            ),
+            SensorError::PermissionDenied => write!(
+                f,
+                "SensorError::PermissionDenied - access to the sensor was denied"
+            ),
+            SensorError::Unavailable => write!(
+                f,
+                "SensorError::Unavailable - the sensor couldn't produce a reading"
+            ),
+            SensorError::Timeout => write!(
+                f,
+                "SensorError::Timeout - the sensor took too long to produce a reading"
+            ),
+            SensorError::Platform(err) => write!(f, "SensorError::Platform - {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SensorError {}
+
+/// Ask the user for permission to read a sensor. Call this from an event handler: some platforms, like Safari on iOS,
+/// only show the prompt in response to user input.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_sensors::{request_permission, Permission, Sensor};
+///
+/// fn Compass() -> Element {
+///     let mut allowed = use_signal(|| false);
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| async move {
+///                 allowed.set(request_permission(Sensor::Orientation).await == Ok(Permission::Granted));
+///             },
+///             "Enable compass"
+///         }
+///     }
+/// }
+/// ```
+pub async fn request_permission(sensor: Sensor) -> Result<Permission, SensorError> {
+    sensors().request_permission(sensor).await
+}
+
+/// Watch the position of the device while the component is mounted.
+///
+/// The first reading asks the user for permission if they haven't answered yet. If they deny it, the reading is
+/// [`SensorError::PermissionDenied`].
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_sensors::{use_geolocation, PositionOptions};
+///
+/// fn Location() -> Element {
+///     let position = use_geolocation(PositionOptions::new().high_accuracy(true));
+///
+///     match position() {
+///         Some(Ok(position)) => rsx! { "You are at {position.latitude}, {position.longitude}" },
+///         Some(Err(err)) => rsx! { "Failed to get your position: {err}" },
+///         None => rsx! { "Locating..." },
+///     }
+/// }
+/// ```
+pub fn use_geolocation(options: PositionOptions) -> SensorReading<Position> {
+    use_sensor(move |sensors| sensors.watch_position(options))
+}
+
+/// Watch the orientation of the device while the component is mounted.
+///
+/// Safari on iOS only allows reading the orientation after [`request_permission`] was called from an event handler.
+pub fn use_device_orientation() -> SensorReading<Orientation> {
+    use_sensor(|sensors| sensors.watch_orientation())
+}
+
+/// Watch the battery of the device while the component is mounted
+pub fn use_battery() -> SensorReading<BatteryStatus> {
+    use_sensor(|sensors| sensors.watch_battery())
+}
+
+fn use_sensor<T: 'static>(
+    watch: impl FnOnce(&dyn SensorProvider) -> SensorStream<T>,
+) -> SensorReading<T> {
+    let mut reading = use_signal(|| None);
+    use_hook(|| {
+        let mut readings = watch(&*sensors());
+        // The stream is dropped with the task when the component is dropped, which stops watching the sensor
+        spawn(async move {
+            while let Some(next) = readings.next().await {
+                reading.set(Some(next));
+            }
+        })
+    });
+    reading.into()
+}
@@ -0,0 +1,307 @@
+use dioxus_lib::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Debug, rc::Rc};
+
+use crate::{sensors, SensorError, SensorFuture, SensorStream};
+
+/// The kind of a media device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MediaDeviceKind {
+    /// A camera
+    VideoInput,
+    /// A microphone
+    AudioInput,
+    /// A speaker or headphones
+    AudioOutput,
+}
+
+/// A camera, microphone or speaker connected to the device
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MediaDevice {
+    /// The id of the device. Pass this to [`VideoConstraints::device`] or [`AudioConstraints::device`] to capture from
+    /// this device.
+    pub device_id: String,
+    /// The id shared by devices that belong to the same hardware, like the camera and microphone of a webcam
+    pub group_id: String,
+    /// The kind of the device
+    pub kind: MediaDeviceKind,
+    /// The name of the device. Browsers leave this empty until the user allowed the app to capture media.
+    pub label: String,
+}
+
+/// The direction a camera faces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Facing {
+    /// The camera facing the user, like a selfie camera
+    User,
+    /// The camera facing away from the user
+    Environment,
+}
+
+/// The media to capture with [`use_media_stream`]
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct MediaConstraints {
+    /// Capture video with these constraints, or no video if this is `None`
+    pub video: Option<VideoConstraints>,
+    /// Capture audio with these constraints, or no audio if this is `None`
+    pub audio: Option<AudioConstraints>,
+}
+
+impl MediaConstraints {
+    /// Capture video from the default camera
+    pub fn camera() -> Self {
+        Self {
+            video: Some(VideoConstraints::default()),
+            audio: None,
+        }
+    }
+
+    /// Capture audio from the default microphone
+    pub fn microphone() -> Self {
+        Self {
+            video: None,
+            audio: Some(AudioConstraints::default()),
+        }
+    }
+
+    /// Capture video with these constraints
+    pub fn video(mut self, video: VideoConstraints) -> Self {
+        self.video = Some(video);
+        self
+    }
+
+    /// Capture audio with these constraints
+    pub fn audio(mut self, audio: AudioConstraints) -> Self {
+        self.audio = Some(audio);
+        self
+    }
+}
+
+/// The camera and video quality to capture. The platform picks the closest match for the size and frame rate.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct VideoConstraints {
+    /// The id of the camera to capture from
+    pub device_id: Option<String>,
+    /// The direction the camera should face. Ignored if a device is set.
+    pub facing: Option<Facing>,
+    /// The ideal width of the video in pixels
+    pub width: Option<u32>,
+    /// The ideal height of the video in pixels
+    pub height: Option<u32>,
+    /// The ideal number of frames per second
+    pub frame_rate: Option<f64>,
+}
+
+impl VideoConstraints {
+    /// Capture from the camera with this id
+    pub fn device(mut self, device_id: impl Into<String>) -> Self {
+        self.device_id = Some(device_id.into());
+        self
+    }
+
+    /// Capture from a camera facing this direction
+    pub fn facing(mut self, facing: Facing) -> Self {
+        self.facing = Some(facing);
+        self
+    }
+
+    /// Capture video close to this size
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    /// Capture video close to this frame rate
+    pub fn frame_rate(mut self, frame_rate: f64) -> Self {
+        self.frame_rate = Some(frame_rate);
+        self
+    }
+}
+
+/// The microphone and processing to capture audio with
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct AudioConstraints {
+    /// The id of the microphone to capture from
+    pub device_id: Option<String>,
+    /// Remove the sound of the speakers from the audio. Uses the platform default if this is `None`.
+    pub echo_cancellation: Option<bool>,
+    /// Remove background noise from the audio. Uses the platform default if this is `None`.
+    pub noise_suppression: Option<bool>,
+}
+
+impl AudioConstraints {
+    /// Capture from the microphone with this id
+    pub fn device(mut self, device_id: impl Into<String>) -> Self {
+        self.device_id = Some(device_id.into());
+        self
+    }
+
+    /// Turn echo cancellation on or off
+    pub fn echo_cancellation(mut self, enabled: bool) -> Self {
+        self.echo_cancellation = Some(enabled);
+        self
+    }
+
+    /// Turn noise suppression on or off
+    pub fn noise_suppression(mut self, enabled: bool) -> Self {
+        self.noise_suppression = Some(enabled);
+        self
+    }
+}
+
+/// A single frame of video
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoFrame {
+    /// The width of the frame in pixels
+    pub width: u32,
+    /// The height of the frame in pixels
+    pub height: u32,
+    /// The pixels of the frame row by row, with four bytes of red, green, blue and alpha per pixel
+    pub rgba: Vec<u8>,
+}
+
+/// A chunk of audio from the first channel of a stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioSamples {
+    /// The number of samples per second
+    pub sample_rate: f32,
+    /// The samples, from -1 to 1
+    pub samples: Vec<f32>,
+}
+
+/// The platform side of a [`MediaStream`]. Implement this to return media streams from a custom [`SensorProvider`](crate::SensorProvider).
+///
+/// The platform should stop capturing when the source is dropped.
+pub trait MediaStreamSource {
+    /// Show the video of the stream in the `video` element with this id
+    fn attach(&self, element_id: &str) -> SensorFuture<()>;
+
+    /// Capture the current frame of the video
+    fn capture_frame(&self) -> SensorFuture<VideoFrame>;
+
+    /// Watch the audio of the stream
+    fn watch_audio(&self) -> SensorStream<AudioSamples>;
+}
+
+/// Video and audio captured from a camera and microphone. Capturing stops when the last clone of the stream is
+/// dropped.
+#[derive(Clone)]
+pub struct MediaStream {
+    source: Rc<dyn MediaStreamSource>,
+}
+
+impl MediaStream {
+    /// Create a media stream from the platform source
+    pub fn new(source: impl MediaStreamSource + 'static) -> Self {
+        Self {
+            source: Rc::new(source),
+        }
+    }
+
+    /// Show the video of the stream in the `video` element with this id. [`MediaPreview`] does this for you.
+    pub async fn attach(&self, element_id: &str) -> Result<(), SensorError> {
+        self.source.attach(element_id).await
+    }
+
+    /// Capture the current frame of the video to process it in rust
+    pub async fn capture_frame(&self) -> Result<VideoFrame, SensorError> {
+        self.source.capture_frame().await
+    }
+
+    /// Watch the audio of the stream. Browsers deliver about 4096 samples at a time. Dropping the stream of samples
+    /// stops watching without stopping the capture.
+    pub fn audio_samples(&self) -> SensorStream<AudioSamples> {
+        self.source.watch_audio()
+    }
+}
+
+impl PartialEq for MediaStream {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.source, &other.source)
+    }
+}
+
+impl Debug for MediaStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MediaStream").finish_non_exhaustive()
+    }
+}
+
+/// List the cameras, microphones and speakers connected to the device. Call [`Resource::restart`] to list them again
+/// after the user plugs in a device.
+#[track_caller]
+pub fn use_media_devices() -> Resource<Result<Vec<MediaDevice>, SensorError>> {
+    let sensors = use_hook(sensors);
+    use_resource(move || sensors.media_devices())
+}
+
+/// Capture video and audio while the component is mounted.
+///
+/// Opening the stream asks the user for permission to use the camera and microphone if they haven't answered yet. If
+/// they deny it, the stream fails with [`SensorError::PermissionDenied`]. The stream is reopened when the signals read
+/// in the closure change, so switching cameras only needs a signal with the device id.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_sensors::{use_media_stream, MediaConstraints, MediaPreview};
+///
+/// fn Camera() -> Element {
+///     let stream = use_media_stream(MediaConstraints::camera);
+///
+///     match &*stream.read_unchecked() {
+///         Some(Ok(stream)) => rsx! { MediaPreview { stream: stream.clone(), class: "preview" } },
+///         Some(Err(err)) => rsx! { "Failed to open the camera: {err}" },
+///         None => rsx! { "Opening the camera..." },
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_media_stream(
+    mut constraints: impl FnMut() -> MediaConstraints + 'static,
+) -> Resource<Result<MediaStream, SensorError>> {
+    let sensors = use_hook(sensors);
+    use_resource(move || sensors.open_media(constraints()))
+}
+
+/// The properties for a [`MediaPreview`].
+#[derive(Props, Clone, PartialEq)]
+pub struct MediaPreviewProps {
+    /// The stream to show
+    pub stream: ReadOnlySignal<MediaStream>,
+
+    /// Extra attributes for the video element
+    #[props(extends = video, extends = GlobalAttributes)]
+    pub attributes: Vec<Attribute>,
+}
+
+/// A muted `video` element that plays a [`MediaStream`].
+#[component]
+pub fn MediaPreview(props: MediaPreviewProps) -> Element {
+    let id = use_hook(|| format!("dioxus-media-preview-{}", current_scope_id().unwrap().0));
+    let stream = props.stream;
+
+    use_effect({
+        let id = id.clone();
+        move || {
+            let stream = stream();
+            let id = id.clone();
+            spawn(async move {
+                if let Err(err) = stream.attach(&id).await {
+                    tracing::error!("Failed to show the media stream: {err}");
+                }
+            });
+        }
+    });
+
+    rsx! {
+        video {
+            id,
+            autoplay: true,
+            muted: true,
+            playsinline: true,
+            ..props.attributes,
+        }
+    }
+}
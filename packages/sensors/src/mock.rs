@@ -0,0 +1,297 @@
+use std::{cell::RefCell, collections::HashMap, future::ready, rc::Rc};
+
+use futures_channel::mpsc::{unbounded, UnboundedSender};
+use futures_util::stream;
+
+use crate::{
+    AudioSamples, BatteryStatus, MediaConstraints, MediaDevice, MediaStream, MediaStreamSource,
+    Notification, Orientation, Permission, Position, PositionOptions, Sensor, SensorError,
+    SensorFuture, SensorProvider, SensorStream, VideoFrame,
+};
+
+/// A [`SensorProvider`] with readings that are set from rust. Use this to test components that read sensors, or to
+/// simulate a device on platforms without the sensor.
+///
+/// Every sensor is granted by default, and the mock user accepts every permission prompt. Clones share the same
+/// readings.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_sensors::{BatteryStatus, MockSensors, SensorProvider};
+/// use std::rc::Rc;
+///
+/// fn App() -> Element {
+///     let sensors = use_hook(|| {
+///         let sensors = MockSensors::new();
+///         provide_root_context(Rc::new(sensors.clone()) as Rc<dyn SensorProvider>);
+///         sensors
+///     });
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| sensors.set_battery(BatteryStatus {
+///                 level: 0.05,
+///                 charging: false,
+///                 charging_time: None,
+///                 discharging_time: Some(600.0),
+///             }),
+///             "Simulate a low battery"
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockSensors {
+    state: Rc<RefCell<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    permissions: HashMap<Sensor, Permission>,
+    position: MockSensor<Position>,
+    orientation: MockSensor<Orientation>,
+    battery: MockSensor<BatteryStatus>,
+    media_devices: Vec<MediaDevice>,
+    video_frame: Option<Result<VideoFrame, SensorError>>,
+    audio: MockSensor<AudioSamples>,
+    open_media_streams: usize,
+    notifications: Vec<Notification>,
+    notification_error: Option<SensorError>,
+}
+
+impl MockState {
+    fn permission(&self, sensor: Sensor) -> Permission {
+        self.permissions
+            .get(&sensor)
+            .copied()
+            .unwrap_or(Permission::Granted)
+    }
+
+    fn watch<T: Clone + 'static>(
+        &mut self,
+        sensor: Sensor,
+        mock: impl FnOnce(&mut Self) -> &mut MockSensor<T>,
+    ) -> SensorStream<T> {
+        match self.permission(sensor) {
+            Permission::Denied => Box::pin(stream::once(ready(Err(SensorError::PermissionDenied)))),
+            _ => mock(self).watch(),
+        }
+    }
+}
+
+impl MockSensors {
+    /// Create a new [`MockSensors`] without any readings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the permission of a sensor. Watching a denied sensor fails with [`SensorError::PermissionDenied`].
+    pub fn set_permission(&self, sensor: Sensor, permission: Permission) {
+        self.state
+            .borrow_mut()
+            .permissions
+            .insert(sensor, permission);
+    }
+
+    /// Move the device to a new position
+    pub fn set_position(&self, position: Position) {
+        self.state.borrow_mut().position.publish(Ok(position));
+    }
+
+    /// Rotate the device to a new orientation
+    pub fn set_orientation(&self, orientation: Orientation) {
+        self.state.borrow_mut().orientation.publish(Ok(orientation));
+    }
+
+    /// Change the state of the battery
+    pub fn set_battery(&self, battery: BatteryStatus) {
+        self.state.borrow_mut().battery.publish(Ok(battery));
+    }
+
+    /// Set the cameras, microphones and speakers connected to the device
+    pub fn set_media_devices(&self, devices: Vec<MediaDevice>) {
+        self.state.borrow_mut().media_devices = devices;
+    }
+
+    /// Set the frame the cameras capture
+    pub fn set_video_frame(&self, frame: VideoFrame) {
+        self.state.borrow_mut().video_frame = Some(Ok(frame));
+    }
+
+    /// Send audio to every open media stream that is watching the microphone
+    pub fn push_audio(&self, samples: AudioSamples) {
+        let mut state = self.state.borrow_mut();
+        state.audio.publish(Ok(samples));
+        // Audio is a stream of chunks, so new watchers shouldn't hear old ones
+        state.audio.latest = None;
+    }
+
+    /// Get the number of media streams that are still capturing
+    pub fn open_media_streams(&self) -> usize {
+        self.state.borrow().open_media_streams
+    }
+
+    /// Get the notifications the app showed
+    pub fn notifications(&self) -> Vec<Notification> {
+        self.state.borrow().notifications.clone()
+    }
+
+    /// Make the sensor fail with an error. Watchers receive the error instead of the next reading.
+    pub fn fail(&self, sensor: Sensor, error: SensorError) {
+        let mut state = self.state.borrow_mut();
+        match sensor {
+            Sensor::Geolocation => state.position.publish(Err(error)),
+            Sensor::Orientation => state.orientation.publish(Err(error)),
+            Sensor::Battery => state.battery.publish(Err(error)),
+            Sensor::Camera => state.video_frame = Some(Err(error)),
+            Sensor::Microphone => state.audio.publish(Err(error)),
+            Sensor::Notifications => state.notification_error = Some(error),
+        }
+    }
+}
+
+impl SensorProvider for MockSensors {
+    fn permission(&self, sensor: Sensor) -> SensorFuture<Permission> {
+        Box::pin(ready(Ok(self.state.borrow().permission(sensor))))
+    }
+
+    fn request_permission(&self, sensor: Sensor) -> SensorFuture<Permission> {
+        let mut state = self.state.borrow_mut();
+        let permission = match state.permission(sensor) {
+            Permission::Prompt => Permission::Granted,
+            permission => permission,
+        };
+        state.permissions.insert(sensor, permission);
+        Box::pin(ready(Ok(permission)))
+    }
+
+    fn watch_position(&self, _: PositionOptions) -> SensorStream<Position> {
+        self.state
+            .borrow_mut()
+            .watch(Sensor::Geolocation, |state| &mut state.position)
+    }
+
+    fn watch_orientation(&self) -> SensorStream<Orientation> {
+        self.state
+            .borrow_mut()
+            .watch(Sensor::Orientation, |state| &mut state.orientation)
+    }
+
+    fn watch_battery(&self) -> SensorStream<BatteryStatus> {
+        self.state
+            .borrow_mut()
+            .watch(Sensor::Battery, |state| &mut state.battery)
+    }
+
+    fn media_devices(&self) -> SensorFuture<Vec<MediaDevice>> {
+        Box::pin(ready(Ok(self.state.borrow().media_devices.clone())))
+    }
+
+    fn open_media(&self, constraints: MediaConstraints) -> SensorFuture<MediaStream> {
+        let mut state = self.state.borrow_mut();
+        let requested = [
+            (Sensor::Camera, constraints.video.is_some()),
+            (Sensor::Microphone, constraints.audio.is_some()),
+        ];
+        if !requested.iter().any(|(_, requested)| *requested) {
+            return Box::pin(ready(Err(SensorError::Platform(
+                "at least one of video or audio must be requested".to_string(),
+            ))));
+        }
+        let denied = requested.iter().any(|(sensor, requested)| {
+            *requested && state.permission(*sensor) == Permission::Denied
+        });
+        if denied {
+            return Box::pin(ready(Err(SensorError::PermissionDenied)));
+        }
+
+        state.open_media_streams += 1;
+        Box::pin(ready(Ok(MediaStream::new(MockMediaStream {
+            state: self.state.clone(),
+            video: constraints.video.is_some(),
+            audio: constraints.audio.is_some(),
+        }))))
+    }
+
+    fn show_notification(&self, notification: Notification) -> SensorFuture<()> {
+        let mut state = self.state.borrow_mut();
+        let result = match state.permission(Sensor::Notifications) {
+            Permission::Granted => match state.notification_error.clone() {
+                Some(error) => Err(error),
+                None => {
+                    state.notifications.push(notification);
+                    Ok(())
+                }
+            },
+            _ => Err(SensorError::PermissionDenied),
+        };
+        Box::pin(ready(result))
+    }
+}
+
+/// A media stream opened by [`MockSensors`]
+struct MockMediaStream {
+    state: Rc<RefCell<MockState>>,
+    video: bool,
+    audio: bool,
+}
+
+impl MediaStreamSource for MockMediaStream {
+    fn attach(&self, _: &str) -> SensorFuture<()> {
+        Box::pin(ready(Ok(())))
+    }
+
+    fn capture_frame(&self) -> SensorFuture<VideoFrame> {
+        let frame = match self.video {
+            true => self.state.borrow().video_frame.clone(),
+            false => None,
+        };
+        Box::pin(ready(frame.unwrap_or(Err(SensorError::Unavailable))))
+    }
+
+    fn watch_audio(&self) -> SensorStream<AudioSamples> {
+        match self.audio {
+            true => self.state.borrow_mut().audio.watch(),
+            false => Box::pin(stream::once(ready(Err(SensorError::Unavailable)))),
+        }
+    }
+}
+
+impl Drop for MockMediaStream {
+    fn drop(&mut self) {
+        self.state.borrow_mut().open_media_streams -= 1;
+    }
+}
+
+/// The latest reading of a mocked sensor and the streams watching it
+struct MockSensor<T> {
+    latest: Option<Result<T, SensorError>>,
+    watchers: Vec<UnboundedSender<Result<T, SensorError>>>,
+}
+
+impl<T> Default for MockSensor<T> {
+    fn default() -> Self {
+        Self {
+            latest: None,
+            watchers: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone + 'static> MockSensor<T> {
+    fn publish(&mut self, reading: Result<T, SensorError>) {
+        self.watchers
+            .retain(|watcher| watcher.unbounded_send(reading.clone()).is_ok());
+        self.latest = Some(reading);
+    }
+
+    /// Watch the sensor, starting with the latest reading like a real sensor would
+    fn watch(&mut self) -> SensorStream<T> {
+        let (sender, receiver) = unbounded();
+        if let Some(latest) = self.latest.clone() {
+            _ = sender.unbounded_send(latest);
+        }
+        self.watchers.push(sender);
+        Box::pin(receiver)
+    }
+}
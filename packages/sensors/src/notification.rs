@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{sensors, SensorError};
+
+/// A notification shown outside of the app, like a toast on desktop or an entry in the notification center on mobile
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Notification {
+    /// The title of the notification
+    pub title: String,
+    /// The text below the title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// The url of an image shown next to the text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Notifications with the same tag replace each other instead of stacking up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Show the notification without a sound or vibration
+    pub silent: bool,
+}
+
+impl Notification {
+    /// Create a notification with a title
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the text below the title
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Set the url of an image shown next to the text
+    pub fn icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Replace the notification with the same tag instead of showing another one
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Show the notification without a sound or vibration
+    pub fn silent(mut self, silent: bool) -> Self {
+        self.silent = silent;
+        self
+    }
+}
+
+/// Show a notification. The user needs to allow notifications first with
+/// [`request_permission(Sensor::Notifications)`](crate::request_permission), or this fails with
+/// [`SensorError::PermissionDenied`].
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_sensors::{request_permission, show_notification, Notification, Permission, Sensor};
+///
+/// fn Timer() -> Element {
+///     rsx! {
+///         button {
+///             onclick: move |_| async move {
+///                 if request_permission(Sensor::Notifications).await == Ok(Permission::Granted) {
+///                     _ = show_notification(Notification::new("Time is up").body("Your tea is ready")).await;
+///                 }
+///             },
+///             "Start"
+///         }
+///     }
+/// }
+/// ```
+pub async fn show_notification(notification: Notification) -> Result<(), SensorError> {
+    sensors().show_notification(notification).await
+}
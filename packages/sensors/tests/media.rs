@@ -0,0 +1,144 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_sensors::*;
+use dioxus_ssr::testing::ComponentHarness;
+use futures_util::StreamExt;
+use std::rc::Rc;
+
+fn harness(app: fn() -> Element, sensors: &MockSensors) -> ComponentHarness {
+    ComponentHarness::new(app).with_context(Rc::new(sensors.clone()) as Rc<dyn SensorProvider>)
+}
+
+#[test]
+fn streams_are_reopened_and_stopped_with_the_component() {
+    fn App() -> Element {
+        let mut show = use_signal(|| true);
+        let mut device = use_signal(|| "front".to_string());
+
+        rsx! {
+            button { id: "switch", onclick: move |_| device.set("back".to_string()) }
+            button { id: "hide", onclick: move |_| show.set(false) }
+            if show() {
+                Camera { device }
+            }
+        }
+    }
+
+    #[component]
+    fn Camera(device: Signal<String>) -> Element {
+        let stream = use_media_stream(move || {
+            MediaConstraints::default().video(VideoConstraints::default().device(device()))
+        });
+
+        match &*stream.read_unchecked() {
+            Some(Ok(stream)) => rsx! { MediaPreview { stream: stream.clone(), class: "preview" } },
+            Some(Err(err)) => rsx! { "{err:?}" },
+            None => rsx! { "opening" },
+        }
+    }
+
+    let sensors = MockSensors::new();
+    let mut harness = harness(App, &sensors);
+    assert!(harness.render().contains("<video"));
+    assert_eq!(sensors.open_media_streams(), 1);
+
+    harness.find_by_attribute("id", "switch").click();
+    assert!(harness.render().contains("<video"));
+    assert_eq!(sensors.open_media_streams(), 1);
+
+    harness.find_by_attribute("id", "hide").click();
+    harness.render();
+    assert_eq!(sensors.open_media_streams(), 0);
+}
+
+#[test]
+fn denied_camera_reports_an_error() {
+    fn App() -> Element {
+        let stream = use_media_stream(MediaConstraints::camera);
+
+        match &*stream.read_unchecked() {
+            Some(Ok(_)) => rsx! { "open" },
+            Some(Err(err)) => rsx! { "{err:?}" },
+            None => rsx! { "opening" },
+        }
+    }
+
+    let sensors = MockSensors::new();
+    sensors.set_permission(Sensor::Camera, Permission::Denied);
+
+    let mut harness = harness(App, &sensors);
+    assert_eq!(harness.render(), "PermissionDenied");
+    assert_eq!(sensors.open_media_streams(), 0);
+}
+
+#[test]
+fn lists_devices() {
+    fn App() -> Element {
+        let devices = use_media_devices();
+
+        match &*devices.read_unchecked() {
+            Some(Ok(devices)) => rsx! {
+                for device in devices {
+                    "{device.label} "
+                }
+            },
+            _ => rsx! {},
+        }
+    }
+
+    let sensors = MockSensors::new();
+    sensors.set_media_devices(vec![
+        MediaDevice {
+            device_id: "1".to_string(),
+            group_id: "webcam".to_string(),
+            kind: MediaDeviceKind::VideoInput,
+            label: "Webcam".to_string(),
+        },
+        MediaDevice {
+            device_id: "2".to_string(),
+            group_id: "webcam".to_string(),
+            kind: MediaDeviceKind::AudioInput,
+            label: "Webcam microphone".to_string(),
+        },
+    ]);
+
+    let mut harness = harness(App, &sensors);
+    assert_eq!(harness.render(), "Webcam Webcam microphone ");
+}
+
+#[tokio::test]
+async fn captures_frames_and_audio() {
+    let sensors = MockSensors::new();
+    sensors.set_video_frame(VideoFrame {
+        width: 1,
+        height: 1,
+        rgba: vec![255, 0, 0, 255],
+    });
+
+    let stream = sensors
+        .open_media(MediaConstraints::camera().audio(AudioConstraints::default()))
+        .await
+        .unwrap();
+    assert_eq!(stream.capture_frame().await.unwrap().rgba, [255, 0, 0, 255]);
+
+    let mut audio = stream.audio_samples();
+    sensors.push_audio(AudioSamples {
+        sample_rate: 48_000.0,
+        samples: vec![0.0, 0.5, -0.5],
+    });
+    assert_eq!(
+        audio.next().await.unwrap().unwrap().samples,
+        [0.0, 0.5, -0.5]
+    );
+
+    let microphone = sensors
+        .open_media(MediaConstraints::microphone())
+        .await
+        .unwrap();
+    assert_eq!(
+        microphone.capture_frame().await,
+        Err(SensorError::Unavailable)
+    );
+    assert_eq!(sensors.open_media_streams(), 2);
+}
@@ -0,0 +1,148 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_sensors::*;
+use dioxus_ssr::testing::ComponentHarness;
+use std::rc::Rc;
+
+fn position(latitude: f64, longitude: f64) -> Position {
+    Position {
+        latitude,
+        longitude,
+        accuracy: 10.0,
+        altitude: None,
+        altitude_accuracy: None,
+        heading: None,
+        speed: None,
+        timestamp: 0.0,
+    }
+}
+
+fn harness(app: fn() -> Element, sensors: &MockSensors) -> ComponentHarness {
+    ComponentHarness::new(app).with_context(Rc::new(sensors.clone()) as Rc<dyn SensorProvider>)
+}
+
+fn Location() -> Element {
+    match use_geolocation(PositionOptions::new())() {
+        Some(Ok(position)) => rsx! { "{position.latitude},{position.longitude}" },
+        Some(Err(err)) => rsx! { "{err:?}" },
+        None => rsx! { "locating" },
+    }
+}
+
+#[test]
+fn geolocation_follows_the_device() {
+    let sensors = MockSensors::new();
+    let mut harness = harness(Location, &sensors);
+    assert_eq!(harness.render(), "locating");
+
+    sensors.set_position(position(52.5, 13.4));
+    assert_eq!(harness.render(), "52.5,13.4");
+
+    sensors.set_position(position(48.9, 2.3));
+    assert_eq!(harness.render(), "48.9,2.3");
+
+    sensors.fail(Sensor::Geolocation, SensorError::Timeout);
+    assert_eq!(harness.render(), "Timeout");
+}
+
+#[test]
+fn new_watchers_start_with_the_latest_reading() {
+    fn App() -> Element {
+        let battery = use_battery();
+        let orientation = use_device_orientation();
+
+        rsx! {
+            if let Some(Ok(battery)) = battery() {
+                "{battery.level} {battery.charging}"
+            }
+            if let Some(Ok(orientation)) = orientation() {
+                " {orientation.alpha:?}"
+            }
+        }
+    }
+
+    let sensors = MockSensors::new();
+    sensors.set_battery(BatteryStatus {
+        level: 0.5,
+        charging: true,
+        charging_time: Some(1800.0),
+        discharging_time: None,
+    });
+    sensors.set_orientation(Orientation {
+        alpha: Some(90.0),
+        beta: Some(0.0),
+        gamma: Some(0.0),
+        absolute: true,
+    });
+
+    let mut harness = harness(App, &sensors);
+    assert_eq!(harness.render(), "0.5 true Some(90.0)");
+}
+
+#[test]
+fn denied_sensors_report_an_error() {
+    let sensors = MockSensors::new();
+    sensors.set_permission(Sensor::Geolocation, Permission::Denied);
+    sensors.set_position(position(52.5, 13.4));
+
+    let mut harness = harness(Location, &sensors);
+    assert_eq!(harness.render(), "PermissionDenied");
+}
+
+#[test]
+fn requesting_permission_resolves_the_prompt() {
+    fn App() -> Element {
+        let mut permission = use_signal(|| None);
+
+        rsx! {
+            button {
+                onclick: move |_| async move {
+                    permission.set(Some(request_permission(Sensor::Orientation).await));
+                },
+            }
+            "{permission:?}"
+        }
+    }
+
+    let sensors = MockSensors::new();
+    sensors.set_permission(Sensor::Orientation, Permission::Prompt);
+
+    let mut harness = harness(App, &sensors);
+    harness.find_by_role("button").click();
+    assert_eq!(harness.render(), "<button></button>Some(Ok(Granted))");
+}
+
+#[test]
+fn notifications_need_permission() {
+    fn App() -> Element {
+        let mut result = use_signal(|| None);
+
+        rsx! {
+            button {
+                onclick: move |_| async move {
+                    result.set(Some(show_notification(Notification::new("Done").body("The upload finished")).await));
+                },
+            }
+            "{result:?}"
+        }
+    }
+
+    let sensors = MockSensors::new();
+    sensors.set_permission(Sensor::Notifications, Permission::Prompt);
+
+    let mut harness = harness(App, &sensors);
+    harness.find_by_role("button").click();
+    assert_eq!(
+        harness.render(),
+        "<button></button>Some(Err(PermissionDenied))"
+    );
+
+    sensors.set_permission(Sensor::Notifications, Permission::Granted);
+    harness.find_by_role("button").click();
+    assert_eq!(harness.render(), "<button></button>Some(Ok(()))");
+    assert_eq!(
+        sensors.notifications(),
+        [Notification::new("Done").body("The upload finished")]
+    );
+}
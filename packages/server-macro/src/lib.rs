@@ -6,8 +6,13 @@
 //! See the [server_fn_macro] crate for more information.
 
 use proc_macro::TokenStream;
+use quote::quote;
 use server_fn_macro::server_macro_impl;
 use syn::__private::ToTokens;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, Ident, ItemFn, LitInt, LitStr, Token,
+};
 
 /// Declares that a function is a [server function](https://docs.rs/server_fn/).
 /// This means that its body will only run on the server, i.e., when the `ssr`
@@ -98,6 +103,46 @@ use syn::__private::ToTokens;
 /// }
 /// ```
 ///
+/// ## Rate limiting server functions
+///
+/// Limit how often each client can call a server function with the limit attribute. Requests over the limit get a
+/// `429 Too Many Requests` response before the server function runs, and `RateLimited::from_error` reads how long the
+/// client should wait from the error. The window accepts `ms`, `s`, `m`, `h` and `d` units:
+///
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// #[server]
+/// // Each IP address can call the server function 10 times a minute
+/// #[limit(10 per "60s")]
+/// pub async fn my_wacky_server_fn(input: Vec<String>) -> Result<usize, ServerFnError> {
+///     unimplemented!()
+/// }
+///
+/// #[server]
+/// // Count the calls of each session instead, with the `auth` feature
+/// #[limit(3 per "1h", by = session)]
+/// pub async fn reset_password(email: String) -> Result<(), ServerFnError> {
+///     unimplemented!()
+/// }
+/// ```
+///
+/// ## Limiting the size and duration of requests
+///
+/// Server functions read request bodies of up to 2MB and run as long as they need to by default. Override the
+/// defaults of the server with the body_limit and timeout attributes. Larger bodies get a `413 Payload Too Large`
+/// response, even if they are streamed, and server functions that run too long are cancelled with a
+/// `408 Request Timeout`. `LimitExceeded::from_error` reads which limit the request went over from the error:
+///
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// #[server]
+/// #[body_limit("50MB")]
+/// #[timeout("5m")]
+/// pub async fn upload_video(video: Vec<u8>) -> Result<(), ServerFnError> {
+///     unimplemented!()
+/// }
+/// ```
+///
 /// ## Extracting additional data from requests
 ///
 /// Server functions automatically handle serialization and deserialization of arguments and responses.
@@ -143,9 +188,13 @@ use syn::__private::ToTokens;
 /// ```
 #[proc_macro_attribute]
 pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
+    let s = match limits_to_middleware(s.into()) {
+        Ok(s) => s,
+        Err(e) => return e.to_compile_error().into(),
+    };
     match server_macro_impl(
         args.into(),
-        s.into(),
+        s,
         Some(syn::parse_quote!(server_fn)),
         "/api",
         None,
@@ -155,3 +204,154 @@ pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
         Ok(s) => s.to_token_stream().into(),
     }
 }
+
+/// Turn the `#[limit(10 per "60s")]`, `#[body_limit("10MB")]` and `#[timeout("30s")]` attributes of a server function
+/// into the middleware that enforces them
+fn limits_to_middleware(s: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    // Let the server function macro report the errors of anything that isn't a function
+    let Ok(mut function) = syn::parse2::<ItemFn>(s.clone()) else {
+        return Ok(s);
+    };
+
+    let mut attrs = Vec::new();
+    for attr in std::mem::take(&mut function.attrs) {
+        if attr.path().is_ident("body_limit") {
+            let limit: LitStr = attr.parse_args()?;
+            let bytes = parse_size(&limit.value()).ok_or_else(|| {
+                syn::Error::new(
+                    limit.span(),
+                    "expected a size like \"512B\", \"64KB\", \"10MB\" or \"1GB\"",
+                )
+            })?;
+            attrs.push(parse_quote! {
+                #[middleware(dioxus_fullstack::limits::BodyLimit::new(#bytes))]
+            });
+            continue;
+        }
+        if attr.path().is_ident("timeout") {
+            let timeout: LitStr = attr.parse_args()?;
+            let millis = parse_window(&timeout.value()).ok_or_else(|| {
+                syn::Error::new(
+                    timeout.span(),
+                    "expected a timeout like \"500ms\", \"30s\", \"5m\" or \"1h\"",
+                )
+            })?;
+            attrs.push(parse_quote! {
+                #[middleware(dioxus_fullstack::limits::Timeout::new(
+                    ::std::time::Duration::from_millis(#millis),
+                ))]
+            });
+            continue;
+        }
+        if !attr.path().is_ident("limit") {
+            attrs.push(attr);
+            continue;
+        }
+        let RateLimitArgs {
+            requests,
+            window,
+            by,
+        } = attr.parse_args()?;
+        let by = by.map(|by| quote! { .by(dioxus_fullstack::rate_limit::RateLimitKey::#by) });
+        attrs.push(parse_quote! {
+            #[middleware(
+                dioxus_fullstack::rate_limit::RateLimit::new(
+                    #requests,
+                    ::std::time::Duration::from_millis(#window),
+                )
+                #by
+            )]
+        });
+    }
+    function.attrs = attrs;
+
+    Ok(function.into_token_stream())
+}
+
+/// The arguments of the limit attribute: `10 per "60s"` with an optional `, by = ip` or `, by = session`
+struct RateLimitArgs {
+    requests: LitInt,
+    window: u64,
+    by: Option<Ident>,
+}
+
+impl Parse for RateLimitArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let requests: LitInt = input.parse()?;
+        requests.base10_parse::<u64>()?;
+
+        let per: Ident = input.parse()?;
+        if per != "per" {
+            return Err(syn::Error::new(
+                per.span(),
+                "expected `per`, like `10 per \"60s\"`",
+            ));
+        }
+
+        let window: LitStr = input.parse()?;
+        let window = parse_window(&window.value()).ok_or_else(|| {
+            syn::Error::new(
+                window.span(),
+                "expected a window like \"500ms\", \"60s\", \"5m\", \"1h\" or \"1d\"",
+            )
+        })?;
+
+        let mut by = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "by" {
+                return Err(syn::Error::new(key.span(), "expected `by`"));
+            }
+            input.parse::<Token![=]>()?;
+            let value: Ident = input.parse()?;
+            by = Some(match value.to_string().as_str() {
+                "ip" => Ident::new("Ip", value.span()),
+                "session" => Ident::new("Session", value.span()),
+                _ => return Err(syn::Error::new(value.span(), "expected `ip` or `session`")),
+            });
+        }
+
+        Ok(Self {
+            requests,
+            window,
+            by,
+        })
+    }
+}
+
+/// Parse a window like `60s` into milliseconds
+fn parse_window(window: &str) -> Option<u64> {
+    let window = window.trim();
+    let split = window.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = window.split_at(split);
+    let millis = match unit.trim() {
+        "ms" => 1,
+        "s" => 1000,
+        "m" => 60 * 1000,
+        "h" => 60 * 60 * 1000,
+        "d" => 24 * 60 * 60 * 1000,
+        _ => return None,
+    };
+    amount
+        .parse::<u64>()
+        .ok()?
+        .checked_mul(millis)
+        .filter(|millis| *millis > 0)
+}
+
+/// Parse a size like `10MB` into bytes
+fn parse_size(size: &str) -> Option<usize> {
+    let size = size.trim();
+    let split = size
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (amount, unit) = size.split_at(split);
+    let bytes = match unit.trim() {
+        "" | "B" => 1,
+        "KB" => 1024,
+        "MB" => 1024 * 1024,
+        "GB" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    amount.parse::<usize>().ok()?.checked_mul(bytes)
+}
@@ -0,0 +1,277 @@
+use crate::{read::Readable, ReadableRef, Store, Writable, WritableRef, Write};
+use dioxus_core::prelude::{IntoAttributeValue, IntoDynNode, ReactiveContext};
+use generational_box::{AnyStorage, BorrowResult, UnsyncStorage};
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Display},
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+
+/// A field of a [`Store`] that can be read and written on its own.
+///
+/// Reading a lens only subscribes to writes that may change the field: writes through the lens, through lenses of the
+/// fields around it and through the store itself. Writing through a lens runs the middleware of the store and reruns the
+/// components that read the whole store, but not the components that only read the other fields.
+///
+/// Create lenses with the [`lens!`](crate::lens!) macro:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Default)]
+/// struct AppState {
+///     user: User,
+///     todos: Vec<String>,
+/// }
+///
+/// #[derive(Default)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// fn App() -> Element {
+///     let store = use_hook(|| Store::new(AppState::default()));
+///     rsx! {
+///         Todos { todos: lens!(store.todos) }
+///         UserName { name: lens!(store.user.name) }
+///     }
+/// }
+///
+/// // Adding a todo doesn't rerun this component
+/// #[component]
+/// fn UserName(name: Lens<AppState, String>) -> Element {
+///     rsx! { "{name}" }
+/// }
+///
+/// #[component]
+/// fn Todos(mut todos: Lens<AppState, Vec<String>>) -> Element {
+///     rsx! {
+///         button { onclick: move |_| todos.write().push("New todo".into()), "Add" }
+///         for todo in todos.iter() {
+///             p { "{todo}" }
+///         }
+///     }
+/// }
+/// ```
+pub struct Lens<T: 'static, F: 'static> {
+    store: Store<T>,
+    path: &'static str,
+    read: fn(&T) -> &F,
+    write: fn(&mut T) -> &mut F,
+}
+
+impl<T: 'static> Store<T> {
+    /// Create a lens into a field of the store.
+    ///
+    /// The path names the field with its fields separated by dots, like `user.name`. Lenses with the same path share
+    /// their subscribers, and writes to a path also update the lenses of the paths inside and around it. Prefer the
+    /// [`lens!`](crate::lens!) macro which builds the path and the accessors from the field.
+    pub fn lens<F: 'static>(
+        &self,
+        path: &'static str,
+        read: fn(&T) -> &F,
+        write: fn(&mut T) -> &mut F,
+    ) -> Lens<T, F> {
+        Lens {
+            store: *self,
+            path,
+            read,
+            write,
+        }
+    }
+}
+
+impl<T: 'static, F: 'static> Lens<T, F> {
+    /// Get the store the lens points into
+    pub fn store(&self) -> Store<T> {
+        self.store
+    }
+
+    /// Get the path of the field the lens points to
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+}
+
+/// Create a [`Lens`] into a field of a [`Store`].
+///
+/// `lens!(store.user.name)` is the same as `store.lens("user.name", |s| &s.user.name, |s| &mut s.user.name)`.
+#[macro_export]
+macro_rules! lens {
+    ($store:ident . $first:tt $(. $rest:tt)*) => {
+        $store.lens(
+            concat!(stringify!($first) $(, ".", stringify!($rest))*),
+            |value| &value.$first $(.$rest)*,
+            |value| &mut value.$first $(.$rest)*,
+        )
+    };
+}
+
+type Subscribers = Arc<Mutex<HashSet<ReactiveContext>>>;
+
+/// The subscribers of every path of a store that has been read through a lens
+#[derive(Default)]
+pub(crate) struct LensSubscribers {
+    paths: Vec<(&'static str, Subscribers)>,
+}
+
+impl LensSubscribers {
+    fn get(&mut self, path: &'static str) -> Subscribers {
+        if let Some((_, subscribers)) = self.paths.iter().find(|(other, _)| *other == path) {
+            return subscribers.clone();
+        }
+        let subscribers = Subscribers::default();
+        self.paths.push((path, subscribers.clone()));
+        subscribers
+    }
+
+    /// Get the subscribers that a write to the path may affect. Writes to the whole store affect every lens.
+    pub(crate) fn affected_by(&self, path: Option<&str>) -> Vec<Subscribers> {
+        self.paths
+            .iter()
+            .filter(|(other, _)| path.map_or(true, |path| overlaps(path, other)))
+            .map(|(_, subscribers)| subscribers.clone())
+            .collect()
+    }
+}
+
+/// Check if one path is the same as the other or inside of it
+fn overlaps(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    long.strip_prefix(short)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Mark the subscribers dirty
+pub(crate) fn update_subscribers(subscribers: Vec<Subscribers>) {
+    for subscribers in subscribers {
+        // We cannot hold the subscribers lock while calling mark_dirty, because mark_dirty can run user code which may cause a new subscriber to be added
+        #[allow(clippy::mutable_key_type)]
+        let mut current = std::mem::take(&mut *subscribers.lock().unwrap());
+        current.retain(|reactive_context| reactive_context.mark_dirty());
+        subscribers.lock().unwrap().extend(current);
+    }
+}
+
+impl<T: 'static, F: 'static> Readable for Lens<T, F> {
+    type Target = F;
+    type Storage = UnsyncStorage;
+
+    #[track_caller]
+    fn try_read_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        let value = self.store.inner.try_peek_unchecked()?;
+        if let Some(reactive_context) = ReactiveContext::current() {
+            let subscribers = self.store.lenses.write_unchecked().get(self.path);
+            reactive_context.subscribe(subscribers);
+        }
+        Ok(UnsyncStorage::map(value, self.read))
+    }
+
+    #[track_caller]
+    fn try_peek_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        let value = self.store.inner.try_peek_unchecked()?;
+        Ok(UnsyncStorage::map(value, self.read))
+    }
+}
+
+impl<T: 'static, F: 'static> Writable for Lens<T, F> {
+    type Mut<'a, R: ?Sized + 'static> = Write<'a, R, UnsyncStorage>;
+
+    fn map_mut<I: ?Sized, U: ?Sized + 'static, M: FnOnce(&mut I) -> &mut U>(
+        ref_: Self::Mut<'_, I>,
+        f: M,
+    ) -> Self::Mut<'_, U> {
+        Write::map(ref_, f)
+    }
+
+    fn try_map_mut<
+        I: ?Sized + 'static,
+        U: ?Sized + 'static,
+        M: FnOnce(&mut I) -> Option<&mut U>,
+    >(
+        ref_: Self::Mut<'_, I>,
+        f: M,
+    ) -> Option<Self::Mut<'_, U>> {
+        Write::filter_map(ref_, f)
+    }
+
+    fn downcast_lifetime_mut<'a: 'b, 'b, R: ?Sized + 'static>(
+        mut_: Self::Mut<'a, R>,
+    ) -> Self::Mut<'b, R> {
+        Write::downcast_lifetime(mut_)
+    }
+
+    #[track_caller]
+    fn try_write_unchecked(
+        &self,
+    ) -> Result<WritableRef<'static, Self>, generational_box::BorrowMutError> {
+        let write = self.store.try_write_path(Some(self.path), true)?;
+        Ok(Write::map(write, self.write))
+    }
+}
+
+impl<T: 'static, F> IntoAttributeValue for Lens<T, F>
+where
+    F: Clone + IntoAttributeValue,
+{
+    fn into_value(self) -> dioxus_core::AttributeValue {
+        self.with(|f| f.clone().into_value())
+    }
+}
+
+impl<T: 'static, F> IntoDynNode for Lens<T, F>
+where
+    F: Clone + IntoDynNode,
+{
+    fn into_dyn_node(self) -> dioxus_core::DynamicNode {
+        self().into_dyn_node()
+    }
+}
+
+impl<T: 'static, F: 'static> PartialEq for Lens<T, F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.store == other.store && self.path == other.path
+    }
+}
+
+impl<T: 'static, F: 'static> Eq for Lens<T, F> {}
+
+/// Allow calling a lens with lens() syntax
+impl<T: 'static, F: Clone> Deref for Lens<T, F> {
+    type Target = dyn Fn() -> F;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { Readable::deref_impl(self) }
+    }
+}
+
+impl<T: 'static, F: Display + 'static> Display for Lens<T, F> {
+    #[track_caller]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.with(|v| Display::fmt(v, f))
+    }
+}
+
+impl<T: 'static, F: Debug + 'static> Debug for Lens<T, F> {
+    #[track_caller]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.with(|v| Debug::fmt(v, f))
+    }
+}
+
+impl<T: 'static, F: 'static> Clone for Lens<T, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, F: 'static> Copy for Lens<T, F> {}
+
+#[test]
+fn overlapping_paths() {
+    assert!(overlaps("user", "user"));
+    assert!(overlaps("user", "user.name"));
+    assert!(overlaps("user.name", "user"));
+    assert!(!overlaps("user", "username"));
+    assert!(!overlaps("user.name", "user.email"));
+}
@@ -22,6 +22,19 @@ pub use set_compare::*;
 mod memo;
 pub use memo::*;
 
+mod rate_limit;
+
+mod store;
+pub use store::*;
+
+mod lens;
+pub use lens::*;
+
+pub mod time_travel;
+
+mod storage;
+pub use storage::*;
+
 mod global;
 pub use global::*;
 
@@ -0,0 +1,93 @@
+use crate::{read::Readable, ReadOnlySignal, Signal, Writable};
+use dioxus_core::{prelude::*, sleep};
+use futures_channel::mpsc::UnboundedReceiver;
+use futures_util::{future::Either, StreamExt};
+use std::{panic::Location, time::Duration};
+
+impl<T: Clone + 'static> Signal<T> {
+    /// Create a signal that follows this signal once its value stops changing for the delay.
+    ///
+    /// Every write to this signal restarts the timer, so the debounced signal skips the values in a burst of writes
+    /// and only takes the last one. The timer runs on the clock of the virtual dom, so it works on every platform and
+    /// with a [`MockClock`](dioxus_core::MockClock) in tests.
+    ///
+    /// The debounced signal is owned by the current scope, so create it once in a hook:
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # use std::time::Duration;
+    /// # async fn search(query: &str) -> Vec<String> { todo!() }
+    /// fn Search() -> Element {
+    ///     let mut query = use_signal(String::new);
+    ///     // Only search once the user stops typing
+    ///     let debounced = use_hook(|| query.debounce(Duration::from_millis(300)));
+    ///     let results = use_resource(move || async move { search(&debounced.read()).await });
+    ///
+    ///     rsx! {
+    ///         input { value: "{query}", oninput: move |event| query.set(event.value()) }
+    ///         for result in results.cloned().unwrap_or_default() {
+    ///             p { "{result}" }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn debounce(&self, delay: Duration) -> ReadOnlySignal<T> {
+        let (mut output, rc, mut changes) = derive(*self, Location::caller());
+        let source = *self;
+
+        spawn(async move {
+            while changes.next().await.is_some() {
+                // Restart the timer until the source stays the same for the delay
+                loop {
+                    match futures_util::future::select(sleep(delay), changes.next()).await {
+                        Either::Left(_) => break,
+                        Either::Right((Some(_), _)) => {}
+                        Either::Right((None, _)) => return,
+                    }
+                }
+                output.set(rc.reset_and_run_in(|| source.cloned()));
+            }
+        });
+
+        output.into()
+    }
+
+    /// Create a signal that follows this signal at most once per interval.
+    ///
+    /// The first write to this signal updates the throttled signal immediately. The latest write during the interval
+    /// updates it again when the interval ends. This is useful for values that change every frame, like the size of
+    /// a window. Create the throttled signal once in a hook like [`Signal::debounce`].
+    #[track_caller]
+    pub fn throttle(&self, interval: Duration) -> ReadOnlySignal<T> {
+        let (mut output, rc, mut changes) = derive(*self, Location::caller());
+        let source = *self;
+
+        spawn(async move {
+            while changes.next().await.is_some() {
+                // Remove any pending updates
+                while changes.try_next().is_ok() {}
+                output.set(rc.reset_and_run_in(|| source.cloned()));
+                // Writes during the interval stay in the channel for the next update
+                sleep(interval).await;
+            }
+        });
+
+        output.into()
+    }
+}
+
+/// Create a signal with the value of the source and a reactive context that is notified when the source changes
+fn derive<T: Clone + 'static>(
+    source: Signal<T>,
+    location: &'static Location<'static>,
+) -> (Signal<T>, ReactiveContext, UnboundedReceiver<()>) {
+    let (tx, rx) = futures_channel::mpsc::unbounded();
+    let rc = ReactiveContext::new_with_callback(
+        move || _ = tx.unbounded_send(()),
+        current_scope_id().unwrap(),
+        location,
+    );
+    let output = Signal::new_with_caller(rc.reset_and_run_in(|| source.cloned()), location);
+    (output, rc, rx)
+}
@@ -621,8 +621,8 @@ impl<'de, T: serde::Deserialize<'de> + 'static, Store: Storage<SignalData<T>>>
 /// - T is the current type of the write
 /// - S is the storage type of the signal. This type determines if the signal is local to the current thread, or it can be shared across threads.
 pub struct Write<'a, T: ?Sized + 'static, S: AnyStorage = UnsyncStorage> {
-    write: S::Mut<'a, T>,
-    drop_signal: Box<dyn Any>,
+    pub(crate) write: S::Mut<'a, T>,
+    pub(crate) drop_signal: Box<dyn Any>,
 }
 
 impl<'a, T: ?Sized + 'static, S: AnyStorage> Write<'a, T, S> {
@@ -697,6 +697,7 @@ impl<T: 'static, S: Storage<SignalData<T>>> Drop for SignalSubscriberDrop<T, S>
                 self.origin,
                 self.signal,
             );
+            crate::time_travel::record_write(self.signal.id(), self.origin);
         }
         self.signal.update_subscribers();
     }
@@ -0,0 +1,82 @@
+use dioxus_core::prelude::{try_consume_context, ScopeId};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// A string key value store that persisted state is saved to.
+///
+/// Renderers provide a [`PlatformStorage`] with the best backend for the platform like local storage on the web or a
+/// file on desktop.
+pub trait StorageBackend {
+    /// Get the value saved for the key
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Save the value for the key
+    fn set(&self, key: &str, value: String);
+
+    /// Remove the value saved for the key
+    fn remove(&self, key: &str);
+}
+
+/// A storage backend that keeps values in memory. Values are lost when the app closes.
+#[derive(Default)]
+pub struct MemoryStorage {
+    values: RefCell<HashMap<String, String>>,
+}
+
+impl StorageBackend for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: String) {
+        self.values.borrow_mut().insert(key.to_string(), value);
+    }
+
+    fn remove(&self, key: &str) {
+        self.values.borrow_mut().remove(key);
+    }
+}
+
+/// Which [`StorageBackend`] of the [`PlatformStorage`] to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageKind {
+    /// Storage that is kept after the app closes. This is local storage on the web.
+    #[default]
+    Local,
+    /// Storage that is only kept for the current session. This is session storage on the web.
+    Session,
+}
+
+/// The storage backends of the current platform.
+///
+/// Renderers provide this as a root context. If the renderer doesn't provide storage, values are kept in memory.
+#[derive(Clone)]
+pub struct PlatformStorage {
+    /// Storage that is kept after the app closes
+    pub local: Rc<dyn StorageBackend>,
+    /// Storage that is only kept for the current session
+    pub session: Rc<dyn StorageBackend>,
+}
+
+impl PlatformStorage {
+    /// Create platform storage that keeps everything in memory
+    pub fn memory() -> Self {
+        Self {
+            local: Rc::new(MemoryStorage::default()),
+            session: Rc::new(MemoryStorage::default()),
+        }
+    }
+
+    /// Get the storage for the current app. Falls back to memory storage if the renderer doesn't provide any.
+    pub fn current() -> Self {
+        try_consume_context::<PlatformStorage>()
+            .unwrap_or_else(|| ScopeId::ROOT.provide_context(Self::memory()))
+    }
+
+    /// Get the backend for the kind of storage
+    pub fn backend(&self, kind: StorageKind) -> Rc<dyn StorageBackend> {
+        match kind {
+            StorageKind::Local => self.local.clone(),
+            StorageKind::Session => self.session.clone(),
+        }
+    }
+}
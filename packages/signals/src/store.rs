@@ -0,0 +1,498 @@
+use crate::lens::{update_subscribers, LensSubscribers};
+use crate::{fmt_impls, read::Readable, write_impls, ReadableRef, Signal, Write};
+use crate::{CopyValue, Memo, ReadOnlySignal, Writable, WritableRef};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    fmt::Debug,
+    ops::Deref,
+    rc::Rc,
+};
+
+use dioxus_core::prelude::{IntoAttributeValue, IntoDynNode};
+use generational_box::{BorrowResult, UnsyncStorage};
+
+/// A layer that every write to a [`Store`] passes through.
+///
+/// Middleware can observe the value before and after each write to implement things like logging, undo history or
+/// persistence. Middleware must not write to the store it is attached to.
+pub trait Middleware<T>: 'static {
+    /// Called when a write to the store starts, before the value is changed
+    fn before_write(&mut self, value: &T) {
+        _ = value;
+    }
+
+    /// Called after the write to the store finishes, before the subscribers of the store are updated
+    fn after_write(&mut self, value: &T) {
+        _ = value;
+    }
+}
+
+/// A signal for app state where every write passes through a list of [`Middleware`].
+///
+/// Stores can be read and written just like a [`Signal`]. Middleware runs in the order it was added. The fields of a
+/// store can be read and written on their own with a [`Lens`](crate::Lens), values derived from the store can be
+/// memoized with [`Store::select`] and several changes can be applied at once with [`Store::transaction`].
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn App() -> Element {
+///     let history = use_hook(UndoHistory::new);
+///     let mut count = use_hook(|| {
+///         Store::new(0)
+///             .with_middleware(LogWrites::new("count"))
+///             .with_middleware(history.clone())
+///     });
+///
+///     let redo = history.clone();
+///
+///     rsx! {
+///         button { onclick: move |_| count += 1, "{count}" }
+///         button { onclick: move |_| _ = history.undo(count), "Undo" }
+///         button { onclick: move |_| _ = redo.redo(count), "Redo" }
+///     }
+/// }
+/// ```
+pub struct Store<T: 'static> {
+    pub(crate) inner: Signal<T>,
+    middleware: CopyValue<Vec<Box<dyn Middleware<T>>>>,
+    pub(crate) lenses: CopyValue<LensSubscribers>,
+}
+
+impl<T: 'static> Store<T> {
+    /// Create a new store without any middleware. The store is owned by the current component like a [`Signal`].
+    #[track_caller]
+    pub fn new(value: T) -> Self {
+        let caller = std::panic::Location::caller();
+        Self {
+            inner: Signal::new_with_caller(value, caller),
+            middleware: CopyValue::new_with_caller(Vec::new(), caller),
+            lenses: CopyValue::new_with_caller(LensSubscribers::default(), caller),
+        }
+    }
+
+    /// Add a middleware to the store and return the store
+    pub fn with_middleware(self, middleware: impl Middleware<T>) -> Self {
+        self.add_middleware(middleware);
+        self
+    }
+
+    /// Add a middleware to the store. The middleware will see every write after this point.
+    pub fn add_middleware(&self, middleware: impl Middleware<T>) {
+        self.middleware.write_unchecked().push(Box::new(middleware));
+    }
+
+    /// Get the signal the store writes to. Writes to the signal directly skip the middleware.
+    pub fn signal(&self) -> ReadOnlySignal<T> {
+        ReadOnlySignal::new(self.inner)
+    }
+
+    /// Create a memo that derives a value from the store.
+    ///
+    /// The selector reruns after every write to the store, but the memo only updates the components that read it when
+    /// the derived value changes. Like [`Memo::new`], create the selector once in a hook:
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// fn App() -> Element {
+    ///     let mut todos = use_hook(|| Store::new(vec![("Write docs", false)]));
+    ///     let remaining = use_hook(|| todos.select(|todos| todos.iter().filter(|(_, done)| !done).count()));
+    ///
+    ///     rsx! {
+    ///         button { onclick: move |_| todos.write()[0].1 = true, "Done" }
+    ///         "{remaining} remaining"
+    ///     }
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn select<O: PartialEq + 'static>(
+        &self,
+        mut selector: impl FnMut(&T) -> O + 'static,
+    ) -> Memo<O> {
+        let store = *self;
+        Memo::new_with_location(
+            move || selector(&store.read()),
+            std::panic::Location::caller(),
+        )
+    }
+
+    /// Apply several changes to the store at once.
+    ///
+    /// The changes are made to a copy of the value. If the closure returns `Ok`, the copy replaces the value of the
+    /// store in a single write: the middleware sees one write and the components that read the store rerun once. If the
+    /// closure returns `Err`, the store is left untouched.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// #[derive(Clone)]
+    /// struct Bank {
+    ///     accounts: Vec<Account>,
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// struct Account {
+    ///     balance: i32,
+    ///     history: Vec<i32>,
+    /// }
+    ///
+    /// fn transfer(bank: Store<Bank>, from: usize, to: usize, amount: i32) -> Result<(), String> {
+    ///     bank.transaction(|bank| {
+    ///         bank.accounts[from].balance -= amount;
+    ///         bank.accounts[from].history.push(-amount);
+    ///         if bank.accounts[from].balance < 0 {
+    ///             return Err("Not enough money".to_string());
+    ///         }
+    ///         bank.accounts[to].balance += amount;
+    ///         bank.accounts[to].history.push(amount);
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn transaction<R, E>(&self, f: impl FnOnce(&mut T) -> Result<R, E>) -> Result<R, E>
+    where
+        T: Clone,
+    {
+        let mut value = self.inner.peek_unchecked().clone();
+        let result = f(&mut value)?;
+        *self.try_write_path(None, true).unwrap() = value;
+        Ok(result)
+    }
+
+    /// Write to the store. The path is the field of a [`Lens`](crate::Lens) that is written to, or `None` for the whole
+    /// store.
+    #[track_caller]
+    pub(crate) fn try_write_path(
+        &self,
+        path: Option<&'static str>,
+        middleware: bool,
+    ) -> Result<Write<'static, T>, generational_box::BorrowMutError> {
+        let write = self.inner.try_write_unchecked()?;
+        if middleware {
+            for middleware in self.middleware.write_unchecked().iter_mut() {
+                middleware.before_write(&write);
+            }
+        }
+        Ok(Write {
+            write: write.write,
+            drop_signal: Box::new(StoreWriteDrop {
+                store: *self,
+                path,
+                middleware,
+                signal_drop: write.drop_signal,
+            }),
+        })
+    }
+}
+
+impl<T> Readable for Store<T> {
+    type Target = T;
+    type Storage = UnsyncStorage;
+
+    #[track_caller]
+    fn try_read_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        self.inner.try_read_unchecked()
+    }
+
+    #[track_caller]
+    fn try_peek_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        self.inner.try_peek_unchecked()
+    }
+}
+
+impl<T: 'static> Writable for Store<T> {
+    type Mut<'a, R: ?Sized + 'static> = Write<'a, R, UnsyncStorage>;
+
+    fn map_mut<I: ?Sized, U: ?Sized + 'static, F: FnOnce(&mut I) -> &mut U>(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Self::Mut<'_, U> {
+        Write::map(ref_, f)
+    }
+
+    fn try_map_mut<
+        I: ?Sized + 'static,
+        U: ?Sized + 'static,
+        F: FnOnce(&mut I) -> Option<&mut U>,
+    >(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Option<Self::Mut<'_, U>> {
+        Write::filter_map(ref_, f)
+    }
+
+    fn downcast_lifetime_mut<'a: 'b, 'b, R: ?Sized + 'static>(
+        mut_: Self::Mut<'a, R>,
+    ) -> Self::Mut<'b, R> {
+        Write::downcast_lifetime(mut_)
+    }
+
+    #[track_caller]
+    fn try_write_unchecked(
+        &self,
+    ) -> Result<WritableRef<'static, Self>, generational_box::BorrowMutError> {
+        self.try_write_path(None, true)
+    }
+}
+
+/// Runs the middleware and updates the lenses after the write borrow is released and before the subscribers of the
+/// signal are updated
+struct StoreWriteDrop<T: 'static> {
+    store: Store<T>,
+    path: Option<&'static str>,
+    middleware: bool,
+    // Dropping this updates the subscribers of the signal
+    #[allow(unused)]
+    signal_drop: Box<dyn Any>,
+}
+
+impl<T: 'static> Drop for StoreWriteDrop<T> {
+    fn drop(&mut self) {
+        if self.middleware {
+            let value = self.store.inner.peek_unchecked();
+            for middleware in self.store.middleware.write_unchecked().iter_mut() {
+                middleware.after_write(&value);
+            }
+        }
+        let affected = self.store.lenses.peek().affected_by(self.path);
+        update_subscribers(affected);
+    }
+}
+
+impl<T> From<Store<T>> for ReadOnlySignal<T> {
+    fn from(store: Store<T>) -> Self {
+        store.signal()
+    }
+}
+
+impl<T> IntoAttributeValue for Store<T>
+where
+    T: Clone + IntoAttributeValue,
+{
+    fn into_value(self) -> dioxus_core::AttributeValue {
+        self.with(|f| f.clone().into_value())
+    }
+}
+
+impl<T> IntoDynNode for Store<T>
+where
+    T: Clone + IntoDynNode,
+{
+    fn into_dyn_node(self) -> dioxus_core::DynamicNode {
+        self().into_dyn_node()
+    }
+}
+
+impl<T: 'static> PartialEq for Store<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T: 'static> Eq for Store<T> {}
+
+/// Allow calling a store with store() syntax
+impl<T: Clone> Deref for Store<T> {
+    type Target = dyn Fn() -> T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { Readable::deref_impl(self) }
+    }
+}
+
+fmt_impls!(Store<T>);
+write_impls!(Store<T>);
+
+impl<T: 'static> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for Store<T> {}
+
+/// A middleware that logs every write to the store with [`tracing`].
+pub struct LogWrites {
+    name: &'static str,
+}
+
+impl LogWrites {
+    /// Create a new logger. The name is included in every log message.
+    pub fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+}
+
+impl<T: Debug> Middleware<T> for LogWrites {
+    fn after_write(&mut self, value: &T) {
+        tracing::info!("{} changed to {:?}", self.name, value);
+    }
+}
+
+struct UndoHistoryState<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    limit: usize,
+}
+
+/// A middleware that records the values of a store so writes can be undone and redone.
+///
+/// `UndoHistory` is a cheap handle to shared state. Clone it into the store with [`Store::with_middleware`] and keep the
+/// handle around to call [`UndoHistory::undo`] and [`UndoHistory::redo`].
+pub struct UndoHistory<T> {
+    state: Rc<RefCell<UndoHistoryState<T>>>,
+    // Set while undo or redo write to the store so those writes are not recorded
+    restoring: Rc<Cell<bool>>,
+}
+
+impl<T: Clone + 'static> UndoHistory<T> {
+    /// Create a new history that remembers the last 100 values
+    pub fn new() -> Self {
+        Self::with_limit(100)
+    }
+
+    /// Create a new history that remembers at most `limit` values
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(UndoHistoryState {
+                undo: VecDeque::new(),
+                redo: Vec::new(),
+                limit,
+            })),
+            restoring: Default::default(),
+        }
+    }
+
+    /// Check if there is a value to go back to
+    pub fn can_undo(&self) -> bool {
+        !self.state.borrow().undo.is_empty()
+    }
+
+    /// Check if there is an undone value to go forward to
+    pub fn can_redo(&self) -> bool {
+        !self.state.borrow().redo.is_empty()
+    }
+
+    /// Restore the value before the last write. Returns false if there is nothing to undo.
+    pub fn undo(&self, store: Store<T>) -> bool {
+        let Some(previous) = self.state.borrow_mut().undo.pop_back() else {
+            return false;
+        };
+        let current = self.restore(store, previous);
+        self.state.borrow_mut().redo.push(current);
+        true
+    }
+
+    /// Restore the last value that was undone. Returns false if there is nothing to redo.
+    pub fn redo(&self, store: Store<T>) -> bool {
+        let Some(next) = self.state.borrow_mut().redo.pop() else {
+            return false;
+        };
+        let current = self.restore(store, next);
+        self.state.borrow_mut().undo.push_back(current);
+        true
+    }
+
+    /// Forget all recorded values
+    pub fn clear(&self) {
+        let mut state = self.state.borrow_mut();
+        state.undo.clear();
+        state.redo.clear();
+    }
+
+    fn restore(&self, mut store: Store<T>, value: T) -> T {
+        self.restoring.set(true);
+        let current = std::mem::replace(&mut *store.write(), value);
+        self.restoring.set(false);
+        current
+    }
+}
+
+impl<T: Clone + 'static> Default for UndoHistory<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for UndoHistory<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            restoring: self.restoring.clone(),
+        }
+    }
+}
+
+impl<T: Clone + 'static> Middleware<T> for UndoHistory<T> {
+    fn before_write(&mut self, value: &T) {
+        if self.restoring.get() {
+            return;
+        }
+        let mut state = self.state.borrow_mut();
+        state.undo.push_back(value.clone());
+        if state.undo.len() > state.limit {
+            state.undo.pop_front();
+        }
+        state.redo.clear();
+    }
+}
+
+/// A place to save the value of a store between sessions like local storage or a file
+pub trait PersistentStorage<T>: 'static {
+    /// Load the saved value if there is one
+    fn load(&mut self) -> Option<T>;
+
+    /// Save the new value
+    fn save(&mut self, value: &T);
+}
+
+/// A middleware that saves the value of the store to a [`PersistentStorage`] after every write.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use std::collections::HashMap;
+/// struct Memory(HashMap<&'static str, i32>);
+///
+/// impl PersistentStorage<i32> for Memory {
+///     fn load(&mut self) -> Option<i32> {
+///         self.0.get("count").copied()
+///     }
+///     fn save(&mut self, value: &i32) {
+///         self.0.insert("count", *value);
+///     }
+/// }
+///
+/// fn App() -> Element {
+///     let mut count = use_hook(|| {
+///         let mut persist = Persist::new(Memory(HashMap::new()));
+///         Store::new(persist.load().unwrap_or_default()).with_middleware(persist)
+///     });
+///
+///     rsx! { button { onclick: move |_| count += 1, "{count}" } }
+/// }
+/// ```
+pub struct Persist<P> {
+    storage: P,
+}
+
+impl<P> Persist<P> {
+    /// Create a new persistence middleware that saves to the storage
+    pub fn new(storage: P) -> Self {
+        Self { storage }
+    }
+
+    /// Load the saved value from the storage. This is typically used for the initial value of the store.
+    pub fn load<T>(&mut self) -> Option<T>
+    where
+        P: PersistentStorage<T>,
+    {
+        self.storage.load()
+    }
+}
+
+impl<T, P: PersistentStorage<T>> Middleware<T> for Persist<P> {
+    fn after_write(&mut self, value: &T) {
+        self.storage.save(value);
+    }
+}
@@ -0,0 +1,238 @@
+//! Record writes to signals so you can see how your app state changed and step back and forth through it.
+//!
+//! In debug builds, every write to a signal is recorded in a timeline with the location of the write, the component that
+//! was running and a timestamp. The timeline is a ring buffer that keeps the last [`DEFAULT_CAPACITY`] writes by default.
+//!
+//! Stores created with [`Store::with_time_travel`] also record a snapshot of their value before and after each write.
+//! [`step_back`] and [`step_forward`] restore those snapshots which reruns every component that reads the store.
+//!
+//! In release builds nothing is recorded and the timeline is always empty.
+
+use crate::Store;
+use dioxus_core::ScopeId;
+use generational_box::GenerationalBoxId;
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+/// The number of writes the timeline keeps by default
+pub const DEFAULT_CAPACITY: usize = 1000;
+
+/// A write to a signal that was recorded in the timeline
+#[derive(Debug, Clone)]
+pub struct SignalWrite {
+    /// The id of the signal that was written to
+    pub signal: GenerationalBoxId,
+    /// The location of the code that wrote to the signal
+    pub origin: &'static std::panic::Location<'static>,
+    /// The component that was running when the signal was written to, if any
+    pub scope: Option<ScopeId>,
+    /// When the write finished
+    pub timestamp: web_time::SystemTime,
+    /// If the write recorded a snapshot that [`step_back`] and [`step_forward`] can restore
+    pub restorable: bool,
+}
+
+/// Restores the value of a store to the value before or after a write
+#[derive(Clone)]
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+struct Snapshot {
+    before: Rc<dyn Fn()>,
+    after: Rc<dyn Fn()>,
+}
+
+struct Entry {
+    write: SignalWrite,
+    snapshot: Option<Snapshot>,
+}
+
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+struct Timeline {
+    entries: VecDeque<Entry>,
+    capacity: usize,
+    // The number of entries that are currently applied. Everything after the cursor was stepped back over
+    cursor: usize,
+    // Set while a snapshot is restored so the restore isn't recorded
+    restoring: bool,
+    // The snapshot of the store write that is finishing. It is attached to the next write recorded for the same signal
+    pending: Option<(GenerationalBoxId, Snapshot)>,
+}
+
+thread_local! {
+    static TIMELINE: RefCell<Timeline> = const { RefCell::new(Timeline {
+        entries: VecDeque::new(),
+        capacity: DEFAULT_CAPACITY,
+        cursor: 0,
+        restoring: false,
+        pending: None,
+    }) };
+}
+
+/// Record a finished write to a signal
+#[cfg(debug_assertions)]
+pub(crate) fn record_write(
+    signal: GenerationalBoxId,
+    origin: &'static std::panic::Location<'static>,
+) {
+    TIMELINE.with_borrow_mut(|timeline| {
+        if timeline.restoring {
+            return;
+        }
+        let snapshot = match timeline.pending.take() {
+            Some((id, snapshot)) if id == signal => Some(snapshot),
+            _ => None,
+        };
+
+        // Writing after stepping back starts a new branch of the timeline
+        let cursor = timeline.cursor;
+        timeline.entries.truncate(cursor);
+        timeline.entries.push_back(Entry {
+            write: SignalWrite {
+                signal,
+                origin,
+                scope: dioxus_core::prelude::current_scope_id().ok(),
+                timestamp: web_time::SystemTime::now(),
+                restorable: snapshot.is_some(),
+            },
+            snapshot,
+        });
+        while timeline.entries.len() > timeline.capacity {
+            timeline.entries.pop_front();
+        }
+        timeline.cursor = timeline.entries.len();
+    })
+}
+
+/// Get the writes in the timeline from oldest to newest. Writes that were stepped back over are included.
+pub fn timeline() -> Vec<SignalWrite> {
+    TIMELINE.with_borrow(|timeline| {
+        timeline
+            .entries
+            .iter()
+            .map(|entry| entry.write.clone())
+            .collect()
+    })
+}
+
+/// Get the number of writes in the timeline that are currently applied
+pub fn cursor() -> usize {
+    TIMELINE.with_borrow(|timeline| timeline.cursor)
+}
+
+/// Set the number of writes the timeline keeps. Older writes are forgotten first.
+pub fn set_capacity(capacity: usize) {
+    TIMELINE.with_borrow_mut(|timeline| {
+        timeline.capacity = capacity;
+        while timeline.entries.len() > capacity {
+            timeline.entries.pop_front();
+        }
+        timeline.cursor = timeline.cursor.min(timeline.entries.len());
+    })
+}
+
+/// Forget every write in the timeline
+pub fn clear() {
+    TIMELINE.with_borrow_mut(|timeline| {
+        timeline.entries.clear();
+        timeline.cursor = 0;
+    })
+}
+
+/// Undo the last restorable write in the timeline. Returns false if there is nothing to step back to.
+pub fn step_back() -> bool {
+    let snapshot = TIMELINE.with_borrow_mut(|timeline| {
+        while timeline.cursor > 0 {
+            timeline.cursor -= 1;
+            if let Some(snapshot) = &timeline.entries[timeline.cursor].snapshot {
+                return Some(snapshot.before.clone());
+            }
+        }
+        None
+    });
+    restore(snapshot)
+}
+
+/// Redo the next restorable write in the timeline that was stepped back over. Returns false if there is nothing to step forward to.
+pub fn step_forward() -> bool {
+    let snapshot = TIMELINE.with_borrow_mut(|timeline| {
+        while timeline.cursor < timeline.entries.len() {
+            timeline.cursor += 1;
+            if let Some(snapshot) = &timeline.entries[timeline.cursor - 1].snapshot {
+                return Some(snapshot.after.clone());
+            }
+        }
+        None
+    });
+    restore(snapshot)
+}
+
+fn restore(snapshot: Option<Rc<dyn Fn()>>) -> bool {
+    let Some(snapshot) = snapshot else {
+        return false;
+    };
+    TIMELINE.with_borrow_mut(|timeline| timeline.restoring = true);
+    snapshot();
+    TIMELINE.with_borrow_mut(|timeline| timeline.restoring = false);
+    true
+}
+
+/// A middleware that records snapshots of a store for the timeline
+#[cfg(debug_assertions)]
+struct TimeTravel<T: 'static> {
+    store: Store<T>,
+    before: Option<T>,
+}
+
+#[cfg(debug_assertions)]
+impl<T: Clone + 'static> crate::Middleware<T> for TimeTravel<T> {
+    fn before_write(&mut self, value: &T) {
+        self.before = Some(value.clone());
+    }
+
+    fn after_write(&mut self, value: &T) {
+        let Some(before) = self.before.take() else {
+            return;
+        };
+
+        let after = value.clone();
+        let store = self.store;
+        // Restoring a snapshot skips the middleware, but still updates the lenses of the store
+        let restore = move |value: T| *store.try_write_path(None, false).unwrap() = value;
+        let snapshot = Snapshot {
+            before: Rc::new(move || restore(before.clone())),
+            after: Rc::new(move || restore(after.clone())),
+        };
+        TIMELINE.with_borrow_mut(|timeline| {
+            if !timeline.restoring {
+                timeline.pending = Some((store.inner.id(), snapshot));
+            }
+        });
+    }
+}
+
+impl<T: Clone + 'static> Store<T> {
+    /// Record a snapshot of the store before and after every write so [`step_back`] and [`step_forward`] can restore it.
+    ///
+    /// Snapshots are only recorded in debug builds. In release builds this does nothing.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// use dioxus_signals::time_travel;
+    ///
+    /// fn App() -> Element {
+    ///     let mut count = use_hook(|| Store::new(0).with_time_travel());
+    ///
+    ///     rsx! {
+    ///         button { onclick: move |_| count += 1, "{count}" }
+    ///         button { onclick: move |_| _ = time_travel::step_back(), "Back" }
+    ///         button { onclick: move |_| _ = time_travel::step_forward(), "Forward" }
+    ///     }
+    /// }
+    /// ```
+    pub fn with_time_travel(self) -> Self {
+        #[cfg(debug_assertions)]
+        self.add_middleware(TimeTravel {
+            store: self,
+            before: None,
+        });
+        self
+    }
+}
@@ -0,0 +1,60 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::cell::Cell;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_core::{MockClock, NoOpMutations};
+use dioxus_ssr::testing::settle;
+
+type Handles = (Signal<i32>, ReadOnlySignal<i32>, ReadOnlySignal<i32>);
+
+thread_local! {
+    static HANDLES: Cell<Option<Handles>> = const { Cell::new(None) };
+}
+
+fn app() -> Element {
+    let source = use_signal(|| 0);
+    let debounced = use_hook(|| source.debounce(Duration::from_millis(100)));
+    let throttled = use_hook(|| source.throttle(Duration::from_millis(100)));
+    HANDLES.set(Some((source, debounced, throttled)));
+    rsx! {}
+}
+
+#[test]
+fn debounced_signals_wait_for_writes_to_stop() {
+    let clock = MockClock::new();
+    let mut dom = VirtualDom::new(app).with_clock(clock.clone());
+    dom.rebuild_in_place();
+    let (mut source, debounced, _) = HANDLES.get().unwrap();
+
+    for value in 1..=3 {
+        dom.in_runtime(|| source.set(value));
+        settle(&mut dom, &mut NoOpMutations);
+        clock.advance(Duration::from_millis(50));
+        settle(&mut dom, &mut NoOpMutations);
+    }
+    dom.in_runtime(|| assert_eq!(debounced(), 0));
+
+    clock.advance(Duration::from_millis(50));
+    settle(&mut dom, &mut NoOpMutations);
+    dom.in_runtime(|| assert_eq!(debounced(), 3));
+}
+
+#[test]
+fn throttled_signals_update_at_most_once_per_interval() {
+    let clock = MockClock::new();
+    let mut dom = VirtualDom::new(app).with_clock(clock.clone());
+    dom.rebuild_in_place();
+    let (mut source, _, throttled) = HANDLES.get().unwrap();
+
+    // The first write updates the signal immediately and the latest write waits for the end of the interval
+    for value in 1..=3 {
+        dom.in_runtime(|| source.set(value));
+        settle(&mut dom, &mut NoOpMutations);
+    }
+    dom.in_runtime(|| assert_eq!(throttled(), 1));
+
+    clock.advance(Duration::from_millis(100));
+    settle(&mut dom, &mut NoOpMutations);
+    dom.in_runtime(|| assert_eq!(throttled(), 3));
+}
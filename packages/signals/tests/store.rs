@@ -0,0 +1,241 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::prelude::*;
+use dioxus_signals::*;
+
+#[test]
+fn store_middleware_sees_every_write() {
+    struct Record(Rc<RefCell<Vec<String>>>);
+
+    impl Middleware<i32> for Record {
+        fn before_write(&mut self, value: &i32) {
+            self.0.borrow_mut().push(format!("before {value}"));
+        }
+
+        fn after_write(&mut self, value: &i32) {
+            self.0.borrow_mut().push(format!("after {value}"));
+        }
+    }
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new_with_props(
+        |log: Rc<RefCell<Vec<String>>>| {
+            let mut store = use_hook(|| Store::new(0).with_middleware(Record(log.clone())));
+            use_hook(|| {
+                store += 1;
+                *store.write() *= 10;
+            });
+            assert_eq!(store(), 10);
+            rsx! {}
+        },
+        log.clone(),
+    );
+    dom.rebuild_in_place();
+
+    assert_eq!(
+        *log.borrow(),
+        ["before 0", "after 1", "before 1", "after 10"]
+    );
+}
+
+#[test]
+fn store_undo_redo() {
+    let mut dom = VirtualDom::new(|| {
+        let history = use_hook(UndoHistory::new);
+        let mut store = use_hook(|| Store::new(0).with_middleware(history.clone()));
+
+        use_hook(|| {
+            store.set(1);
+            store.set(2);
+            assert!(history.undo(store));
+            assert_eq!(store(), 1);
+            assert!(history.undo(store));
+            assert_eq!(store(), 0);
+            assert!(!history.undo(store));
+
+            assert!(history.redo(store));
+            assert_eq!(store(), 1);
+
+            // A new write clears the redo stack
+            store.set(3);
+            assert!(!history.can_redo());
+            assert!(history.undo(store));
+            assert_eq!(store(), 1);
+        });
+
+        rsx! {}
+    });
+
+    dom.rebuild_in_place();
+}
+
+#[test]
+fn store_persists_writes() {
+    #[derive(Clone, Default)]
+    struct Memory(Rc<RefCell<Option<String>>>);
+
+    impl PersistentStorage<String> for Memory {
+        fn load(&mut self) -> Option<String> {
+            self.0.borrow().clone()
+        }
+
+        fn save(&mut self, value: &String) {
+            *self.0.borrow_mut() = Some(value.clone());
+        }
+    }
+
+    let storage = Memory::default();
+    *storage.0.borrow_mut() = Some("saved".to_string());
+    let mut dom = VirtualDom::new_with_props(
+        |storage: Memory| {
+            let mut store = use_hook(|| {
+                let mut persist = Persist::new(storage.clone());
+                Store::new(persist.load().unwrap_or_default()).with_middleware(persist)
+            });
+            use_hook(|| {
+                assert_eq!(store(), "saved");
+                store.write().push_str(" and changed");
+            });
+            rsx! {}
+        },
+        storage.clone(),
+    );
+    dom.rebuild_in_place();
+
+    assert_eq!(storage.0.borrow().as_deref(), Some("saved and changed"));
+}
+
+#[test]
+fn lenses_only_rerun_components_that_read_the_field() {
+    #[derive(Clone, Default)]
+    struct User {
+        name: String,
+        age: u32,
+    }
+
+    #[derive(Clone, Default)]
+    struct AppState {
+        user: User,
+        todos: Vec<String>,
+    }
+
+    thread_local! {
+        static STORE: std::cell::Cell<Option<Store<AppState>>> = const { std::cell::Cell::new(None) };
+        static RUNS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn app() -> Element {
+        let store = use_hook(|| Store::new(AppState::default()));
+        STORE.set(Some(store));
+        rsx! {
+            Name { name: lens!(store.user.name) }
+            Todos { todos: lens!(store.todos) }
+            UserInfo { user: lens!(store.user) }
+        }
+    }
+
+    #[component]
+    fn Name(name: Lens<AppState, String>) -> Element {
+        RUNS.with_borrow_mut(|runs| runs.push("name"));
+        rsx! { "{name}" }
+    }
+
+    #[component]
+    fn Todos(todos: Lens<AppState, Vec<String>>) -> Element {
+        RUNS.with_borrow_mut(|runs| runs.push("todos"));
+        rsx! { "{todos.len()}" }
+    }
+
+    #[component]
+    fn UserInfo(user: Lens<AppState, User>) -> Element {
+        RUNS.with_borrow_mut(|runs| runs.push("user"));
+        rsx! { "{user.read().age}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let store = STORE.get().unwrap();
+    let mut rerun = |write: &dyn Fn()| {
+        RUNS.with_borrow_mut(|runs| runs.clear());
+        dom.in_runtime(write);
+        dom.render_immediate(&mut dioxus_core::NoOpMutations);
+        RUNS.with_borrow_mut(|runs| {
+            runs.sort();
+            runs.clone()
+        })
+    };
+
+    assert_eq!(
+        rerun(&|| lens!(store.todos).write().push("Test".into())),
+        ["todos"]
+    );
+    // Writes to a field update the lenses of the fields that contain it, but not of its siblings
+    assert_eq!(rerun(&|| lens!(store.user.age).set(30)), ["user"]);
+    // And the lenses of the fields inside of it
+    assert_eq!(
+        rerun(&|| lens!(store.user).write().name = "Ada".into()),
+        ["name", "user"]
+    );
+    // Writes to the whole store update every lens
+    assert_eq!(
+        rerun(&|| store.write_unchecked().todos.clear()),
+        ["name", "todos", "user"]
+    );
+}
+
+#[test]
+fn store_selectors_are_memoized() {
+    let mut dom = VirtualDom::new(|| {
+        let mut store = use_hook(|| Store::new(vec![1, 2, 3]));
+        let runs = use_hook(|| Rc::new(std::cell::Cell::new(0)));
+        let sum = use_hook(|| {
+            let runs = runs.clone();
+            store.select(move |values| {
+                runs.set(runs.get() + 1);
+                values.iter().sum::<i32>()
+            })
+        });
+        use_hook(|| {
+            assert_eq!(sum(), 6);
+            store.write().push(4);
+        });
+        rsx! { "{sum}" }
+    });
+    dom.rebuild_in_place();
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+}
+
+#[test]
+fn transactions_apply_every_change_or_none() {
+    let mut dom = VirtualDom::new(|| {
+        let history = use_hook(UndoHistory::new);
+        let store = use_hook(|| Store::new(vec![1, 2]).with_middleware(history.clone()));
+
+        use_hook(|| {
+            let failed: Result<(), &str> = store.transaction(|values| {
+                values.push(3);
+                Err("rolled back")
+            });
+            assert_eq!(failed, Err("rolled back"));
+            assert_eq!(store(), [1, 2]);
+            assert!(!history.can_undo());
+
+            let len = store.transaction(|values| {
+                values.push(3);
+                values.push(4);
+                Ok::<_, ()>(values.len())
+            });
+            assert_eq!(len, Ok(4));
+            assert_eq!(store(), [1, 2, 3, 4]);
+
+            // The middleware saw a single write
+            assert!(history.undo(store));
+            assert_eq!(store(), [1, 2]);
+        });
+
+        rsx! {}
+    });
+    dom.rebuild_in_place();
+}
@@ -0,0 +1,48 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_signals::*;
+
+#[cfg(debug_assertions)]
+#[test]
+fn step_through_store_snapshots() {
+    let mut dom = VirtualDom::new(|| {
+        let mut store = use_hook(|| Store::new(0).with_time_travel());
+        let mut signal = use_signal(|| 0);
+
+        use_hook(|| {
+            time_travel::clear();
+            store.set(1);
+            signal.set(1);
+            store.set(2);
+
+            let timeline = time_travel::timeline();
+            assert_eq!(timeline.len(), 3);
+            assert!(timeline[0].restorable);
+            assert!(!timeline[1].restorable);
+            assert_eq!(timeline[1].signal, signal.id());
+            assert_eq!(timeline[1].scope, Some(ScopeId::APP));
+
+            assert!(time_travel::step_back());
+            assert_eq!(store(), 1);
+            // Stepping back skips writes without a snapshot
+            assert!(time_travel::step_back());
+            assert_eq!(store(), 0);
+            assert_eq!(time_travel::cursor(), 0);
+            assert!(!time_travel::step_back());
+
+            assert!(time_travel::step_forward());
+            assert_eq!(store(), 1);
+            assert!(time_travel::step_forward());
+            assert_eq!(store(), 2);
+            assert!(!time_travel::step_forward());
+
+            // Restoring a snapshot isn't recorded
+            assert_eq!(time_travel::timeline().len(), 3);
+        });
+
+        rsx! {}
+    });
+
+    dom.rebuild_in_place();
+}
@@ -6,6 +6,7 @@ mod cache;
 pub mod config;
 pub mod renderer;
 pub mod template;
+pub mod testing;
 
 use dioxus_core::{Element, VirtualDom};
 
@@ -16,6 +16,11 @@ pub struct Renderer {
     /// Choose to write ElementIDs into elements so the page can be re-hydrated later on
     pub pre_render: bool,
 
+    /// Write the dynamic attributes, styles and listeners of every element sorted by name so the same dom always renders
+    /// to the same bytes. Attributes spread from props are otherwise written in the order they were collected in, which
+    /// may change between runs. Enable this if the html is cached, diffed or compared against a snapshot.
+    pub deterministic: bool,
+
     /// A callback used to render components. You can set this callback to control what components are rendered and add wrappers around components that are not present in CSR
     render_components: Option<ComponentRenderCallback>,
 
@@ -135,26 +140,24 @@ impl Renderer {
                 }
                 Segment::Attr(idx) => {
                     let attrs = &*template.dynamic_attrs[*idx];
-                    for attr in attrs {
-                        if attr.name == "dangerous_inner_html" {
-                            inner_html = Some(attr);
-                        } else if attr.namespace == Some("style") {
-                            accumulated_dynamic_styles.push(attr);
-                        } else if BOOL_ATTRS.contains(&attr.name) {
-                            if truthy(&attr.value) {
-                                write_attribute(buf, attr)?;
-                            }
-                        } else {
-                            write_attribute(buf, attr)?;
+                    let mut write_attr = |buf: &mut W, attr| {
+                        self.write_dynamic_attribute(
+                            buf,
+                            attr,
+                            &mut inner_html,
+                            &mut accumulated_dynamic_styles,
+                            &mut accumulated_listeners,
+                        )
+                    };
+                    if self.deterministic {
+                        let mut sorted: Vec<_> = attrs.iter().collect();
+                        sorted.sort_by_key(|attr| attr.name);
+                        for attr in sorted {
+                            write_attr(buf, attr)?;
                         }
-
-                        if self.pre_render {
-                            if let AttributeValue::Listener(_) = &attr.value {
-                                // The onmounted event doesn't need a DOM listener
-                                if attr.name != "onmounted" {
-                                    accumulated_listeners.push(attr.name);
-                                }
-                            }
+                    } else {
+                        for attr in attrs {
+                            write_attr(buf, attr)?;
                         }
                     }
                 }
@@ -209,6 +212,9 @@ impl Renderer {
                         if !*inside_style_tag {
                             write!(buf, " style=\"")?;
                         }
+                        if self.deterministic {
+                            accumulated_dynamic_styles.sort_by_key(|attr| attr.name);
+                        }
                         for attr in &accumulated_dynamic_styles {
                             write!(buf, "{}:", attr.name)?;
                             write_value_unquoted(buf, &attr.value)?;
@@ -241,13 +247,12 @@ impl Renderer {
                     write!(buf, "{}", self.dynamic_node_id)?;
                     self.dynamic_node_id += 1;
                     // then write any listeners
-                    for name in accumulated_listeners.drain(..) {
-                        write!(buf, ",{}:", &name[2..])?;
-                        write!(
-                            buf,
-                            "{}",
-                            dioxus_core_types::event_bubbles(&name[2..]) as u8
-                        )?;
+                    if self.deterministic {
+                        accumulated_listeners.sort_by_key(|listener| listener.event);
+                    }
+                    for listener in accumulated_listeners.drain(..) {
+                        write!(buf, ",{}:", listener.event)?;
+                        write!(buf, "{}", listener.interpreter_flags())?;
                     }
                 }
 
@@ -262,6 +267,40 @@ impl Renderer {
 
         Ok(())
     }
+
+    fn write_dynamic_attribute<'a, W: Write + ?Sized>(
+        &self,
+        buf: &mut W,
+        attr: &'a Attribute,
+        inner_html: &mut Option<&'a Attribute>,
+        accumulated_dynamic_styles: &mut Vec<&'a Attribute>,
+        accumulated_listeners: &mut Vec<dioxus_core_types::ListenerName>,
+    ) -> std::fmt::Result {
+        if attr.name == "dangerous_inner_html" {
+            *inner_html = Some(attr);
+        } else if attr.namespace == Some("style") {
+            accumulated_dynamic_styles.push(attr);
+        } else if BOOL_ATTRS.contains(&attr.name) {
+            if truthy(&attr.value) {
+                write_attribute(buf, attr)?;
+            }
+        } else {
+            write_attribute(buf, attr)?;
+        }
+
+        if self.pre_render {
+            if let AttributeValue::Listener(callback) = &attr.value {
+                // The onmounted event doesn't need a DOM listener
+                if attr.name != "onmounted" {
+                    let mut listener = dioxus_core_types::ListenerName::parse(attr.name);
+                    listener.options = listener.options.union(callback.listener_options());
+                    accumulated_listeners.push(listener);
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[test]
@@ -407,6 +446,7 @@ pub(crate) const BOOL_ATTRS: &[&str] = &[
     "disabled",
     "formnovalidate",
     "hidden",
+    "inert",
     "ismap",
     "itemscope",
     "loop",
@@ -0,0 +1,503 @@
+//! A headless mirror of the real dom, built by applying the mutations the virtual dom emits.
+//!
+//! The mirror follows the same stack machine as the web interpreter so queries see exactly the tree a renderer would,
+//! including the element ids needed to dispatch events.
+
+use dioxus_core::{
+    AttributeValue, ElementId, Template, TemplateAttribute, TemplateNode, WriteMutations,
+};
+use rustc_hash::FxHashMap;
+
+/// The index of a node in the headless dom
+pub(crate) type NodeIndex = usize;
+
+/// The index of the root node every app is mounted to
+const ROOT: NodeIndex = 0;
+
+enum NodeKind {
+    Element {
+        tag: &'static str,
+        attributes: Vec<(&'static str, String)>,
+        listeners: Vec<&'static str>,
+    },
+    Text(String),
+    Placeholder,
+}
+
+struct HeadlessNode {
+    kind: NodeKind,
+    parent: Option<NodeIndex>,
+    children: Vec<NodeIndex>,
+    id: Option<ElementId>,
+}
+
+/// A node in the dom that tests can query for
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selector {
+    /// An element whose text content matches the text, ignoring leading and trailing whitespace. If an element and its
+    /// child both match, only the child is selected.
+    Text(String),
+    /// An element with the explicit `role` attribute or the implicit aria role of its tag, like `button` for
+    /// `<button>` or `textbox` for `<input type="text">`
+    Role(String),
+    /// An element with the attribute set. If a value is given, the attribute must have that value.
+    Attribute(String, Option<String>),
+    /// An element with the tag name
+    Tag(String),
+}
+
+impl std::fmt::Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Selector::Text(text) => write!(f, "text {text:?}"),
+            Selector::Role(role) => write!(f, "role {role:?}"),
+            Selector::Attribute(name, Some(value)) => write!(f, "attribute {name}={value:?}"),
+            Selector::Attribute(name, None) => write!(f, "attribute {name}"),
+            Selector::Tag(tag) => write!(f, "tag {tag:?}"),
+        }
+    }
+}
+
+pub(crate) struct HeadlessDom {
+    nodes: Vec<HeadlessNode>,
+    ids: FxHashMap<ElementId, NodeIndex>,
+    stack: Vec<NodeIndex>,
+}
+
+impl Default for HeadlessDom {
+    fn default() -> Self {
+        let mut dom = Self {
+            nodes: Vec::new(),
+            ids: FxHashMap::default(),
+            stack: Vec::new(),
+        };
+        let root = dom.create(NodeKind::Element {
+            tag: "main",
+            attributes: Vec::new(),
+            listeners: Vec::new(),
+        });
+        dom.nodes[root].id = Some(ElementId(0));
+        dom.ids.insert(ElementId(0), root);
+        dom
+    }
+}
+
+impl HeadlessDom {
+    /// Check if the element has a listener for the event
+    pub(crate) fn has_listener(&self, index: NodeIndex, name: &str) -> bool {
+        match &self.nodes[index].kind {
+            NodeKind::Element { listeners, .. } => listeners.contains(&name),
+            _ => false,
+        }
+    }
+
+    /// Find all elements attached to the dom that match the selector, in document order
+    pub(crate) fn select(&self, selector: &Selector) -> Vec<NodeIndex> {
+        let mut matches = Vec::new();
+        self.select_in(ROOT, selector, &mut matches);
+        matches
+    }
+
+    fn select_in(&self, index: NodeIndex, selector: &Selector, matches: &mut Vec<NodeIndex>) {
+        for &child in &self.nodes[index].children {
+            if self.matches(child, selector) {
+                matches.push(child);
+            }
+            self.select_in(child, selector, matches);
+        }
+    }
+
+    fn matches(&self, index: NodeIndex, selector: &Selector) -> bool {
+        let NodeKind::Element { tag, .. } = &self.nodes[index].kind else {
+            return false;
+        };
+
+        match selector {
+            Selector::Text(text) => {
+                let text = text.trim();
+                self.text_content(index).trim() == text
+                    && !self.nodes[index].children.iter().any(|&child| {
+                        matches!(self.nodes[child].kind, NodeKind::Element { .. })
+                            && self.text_content(child).trim() == text
+                    })
+            }
+            Selector::Role(role) => self.role(index).as_deref() == Some(role.as_str()),
+            Selector::Attribute(name, value) => match (self.attribute(index, name), value) {
+                (Some(found), Some(value)) => found == value,
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+            Selector::Tag(expected) => tag == expected,
+        }
+    }
+
+    /// The text of all text nodes below this node
+    pub(crate) fn text_content(&self, index: NodeIndex) -> String {
+        let mut text = String::new();
+        self.write_text(index, &mut text);
+        text
+    }
+
+    fn write_text(&self, index: NodeIndex, out: &mut String) {
+        match &self.nodes[index].kind {
+            NodeKind::Text(text) => out.push_str(text),
+            NodeKind::Element { .. } => {
+                for &child in &self.nodes[index].children {
+                    self.write_text(child, out);
+                }
+            }
+            NodeKind::Placeholder => {}
+        }
+    }
+
+    pub(crate) fn tag(&self, index: NodeIndex) -> Option<&'static str> {
+        match &self.nodes[index].kind {
+            NodeKind::Element { tag, .. } => Some(tag),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn attribute(&self, index: NodeIndex, name: &str) -> Option<&str> {
+        match &self.nodes[index].kind {
+            NodeKind::Element { attributes, .. } => attributes
+                .iter()
+                .find(|(attr, _)| *attr == name)
+                .map(|(_, value)| value.as_str()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn set_attribute_value(
+        &mut self,
+        index: NodeIndex,
+        name: &'static str,
+        value: String,
+    ) {
+        if let NodeKind::Element { attributes, .. } = &mut self.nodes[index].kind {
+            match attributes.iter_mut().find(|(attr, _)| *attr == name) {
+                Some((_, existing)) => *existing = value,
+                None => attributes.push((name, value)),
+            }
+        }
+    }
+
+    pub(crate) fn remove_attribute(&mut self, index: NodeIndex, name: &str) {
+        if let NodeKind::Element { attributes, .. } = &mut self.nodes[index].kind {
+            attributes.retain(|(attr, _)| *attr != name);
+        }
+    }
+
+    /// The explicit or implicit aria role of an element
+    pub(crate) fn role(&self, index: NodeIndex) -> Option<String> {
+        if let Some(role) = self.attribute(index, "role") {
+            return Some(role.to_string());
+        }
+
+        let role = match self.tag(index)? {
+            "button" => "button",
+            "a" if self.attribute(index, "href").is_some() => "link",
+            "input" => match self.attribute(index, "type").unwrap_or("text") {
+                "button" | "submit" | "reset" | "image" => "button",
+                "checkbox" => "checkbox",
+                "radio" => "radio",
+                "range" => "slider",
+                "number" => "spinbutton",
+                "search" => "searchbox",
+                "text" | "email" | "tel" | "url" | "password" => "textbox",
+                _ => return None,
+            },
+            "textarea" => "textbox",
+            "select" => "combobox",
+            "option" => "option",
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+            "ul" | "ol" => "list",
+            "li" => "listitem",
+            "p" => "paragraph",
+            "img" => "img",
+            "form" => "form",
+            "nav" => "navigation",
+            "main" => "main",
+            "header" => "banner",
+            "footer" => "contentinfo",
+            "dialog" => "dialog",
+            "table" => "table",
+            "tr" => "row",
+            "td" => "cell",
+            "th" => "columnheader",
+            _ => return None,
+        };
+
+        Some(role.to_string())
+    }
+
+    /// The closest element at or above this node that the virtual dom knows the id of. Events dispatched to this
+    /// element bubble to the listeners of the original node. Returns `None` if the node is no longer mounted.
+    pub(crate) fn event_target(&self, mut index: NodeIndex) -> Option<ElementId> {
+        if !self.is_mounted(index) {
+            return None;
+        }
+        loop {
+            let node = &self.nodes[index];
+            match (node.id, &node.kind) {
+                (Some(id), NodeKind::Element { .. }) if index != ROOT => return Some(id),
+                _ => index = node.parent.filter(|parent| *parent != ROOT)?,
+            }
+        }
+    }
+
+    fn is_mounted(&self, mut index: NodeIndex) -> bool {
+        while index != ROOT {
+            match self.nodes[index].parent {
+                Some(parent) => index = parent,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// The closest ancestor with the given tag
+    pub(crate) fn closest(&self, mut index: NodeIndex, tag: &str) -> Option<NodeIndex> {
+        loop {
+            if self.tag(index) == Some(tag) {
+                return Some(index);
+            }
+            index = self.nodes[index].parent?;
+        }
+    }
+
+    /// All elements below this node, in document order
+    pub(crate) fn descendants(&self, index: NodeIndex) -> Vec<NodeIndex> {
+        let mut out = Vec::new();
+        let mut stack: Vec<_> = self.nodes[index].children.iter().rev().copied().collect();
+        while let Some(next) = stack.pop() {
+            out.push(next);
+            stack.extend(self.nodes[next].children.iter().rev().copied());
+        }
+        out
+    }
+
+    fn create(&mut self, kind: NodeKind) -> NodeIndex {
+        self.nodes.push(HeadlessNode {
+            kind,
+            parent: None,
+            children: Vec::new(),
+            id: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn create_template_node(&mut self, node: &TemplateNode) -> NodeIndex {
+        match node {
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => {
+                let attributes = attrs
+                    .iter()
+                    .filter_map(|attr| match attr {
+                        TemplateAttribute::Static { name, value, .. } => {
+                            Some((*name, value.to_string()))
+                        }
+                        TemplateAttribute::Dynamic { .. } => None,
+                    })
+                    .collect();
+
+                let element = self.create(NodeKind::Element {
+                    tag,
+                    attributes,
+                    listeners: Vec::new(),
+                });
+
+                for child in children.iter() {
+                    let child = self.create_template_node(child);
+                    self.nodes[child].parent = Some(element);
+                    self.nodes[element].children.push(child);
+                }
+
+                element
+            }
+            TemplateNode::Text { text } => self.create(NodeKind::Text(text.to_string())),
+            TemplateNode::Dynamic { .. } => self.create(NodeKind::Placeholder),
+        }
+    }
+
+    fn assign(&mut self, index: NodeIndex, id: ElementId) {
+        self.nodes[index].id = Some(id);
+        self.ids.insert(id, index);
+    }
+
+    fn get(&self, id: ElementId) -> NodeIndex {
+        *self
+            .ids
+            .get(&id)
+            .unwrap_or_else(|| panic!("the virtual dom referenced {id:?} before creating it"))
+    }
+
+    /// Follow a template path from the top of the stack
+    fn load_child(&self, path: &[u8]) -> NodeIndex {
+        let mut node = *self.stack.last().expect("the stack should not be empty");
+        for &child in path {
+            node = self.nodes[node].children[child as usize];
+        }
+        node
+    }
+
+    fn pop_nodes(&mut self, m: usize) -> Vec<NodeIndex> {
+        let nodes = self.stack.split_off(self.stack.len() - m);
+        for &node in &nodes {
+            self.detach(node);
+        }
+        nodes
+    }
+
+    fn detach(&mut self, index: NodeIndex) {
+        if let Some(parent) = self.nodes[index].parent.take() {
+            self.nodes[parent].children.retain(|child| *child != index);
+        }
+    }
+
+    /// Insert nodes into the parent of the target, at the given offset from the target
+    fn insert_at(&mut self, target: NodeIndex, nodes: Vec<NodeIndex>, after: bool) {
+        let Some(parent) = self.nodes[target].parent else {
+            return;
+        };
+        let position = self.nodes[parent]
+            .children
+            .iter()
+            .position(|child| *child == target)
+            .unwrap_or_default()
+            + after as usize;
+
+        for &node in &nodes {
+            self.nodes[node].parent = Some(parent);
+        }
+        self.nodes[parent]
+            .children
+            .splice(position..position, nodes);
+    }
+
+    fn replace(&mut self, target: NodeIndex, nodes: Vec<NodeIndex>) {
+        self.insert_at(target, nodes, false);
+        self.detach(target);
+    }
+}
+
+impl WriteMutations for HeadlessDom {
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        let parent = self.get(id);
+        for node in self.pop_nodes(m) {
+            self.nodes[node].parent = Some(parent);
+            self.nodes[parent].children.push(node);
+        }
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        let node = self.load_child(path);
+        self.assign(node, id);
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        let node = self.create(NodeKind::Placeholder);
+        self.assign(node, id);
+        self.stack.push(node);
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        let node = self.create(NodeKind::Text(value.to_string()));
+        self.assign(node, id);
+        self.stack.push(node);
+    }
+
+    fn load_template(&mut self, template: Template, index: usize, id: ElementId) {
+        let node = self.create_template_node(&template.roots[index]);
+        self.assign(node, id);
+        self.stack.push(node);
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        let target = self.get(id);
+        let nodes = self.pop_nodes(m);
+        self.replace(target, nodes);
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        let nodes = self.pop_nodes(m);
+        let target = self.load_child(path);
+        self.replace(target, nodes);
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        let target = self.get(id);
+        let nodes = self.pop_nodes(m);
+        self.insert_at(target, nodes, true);
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        let target = self.get(id);
+        let nodes = self.pop_nodes(m);
+        self.insert_at(target, nodes, false);
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        _ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        let node = self.get(id);
+        let value = match value {
+            AttributeValue::Text(text) => text.clone(),
+            AttributeValue::Float(f) => f.to_string(),
+            AttributeValue::Int(i) => i.to_string(),
+            AttributeValue::Bool(b) => b.to_string(),
+            AttributeValue::None => return self.remove_attribute(node, name),
+            AttributeValue::Listener(_) | AttributeValue::Any(_) => return,
+        };
+        self.set_attribute_value(node, name, value);
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        let node = self.get(id);
+        match &mut self.nodes[node].kind {
+            NodeKind::Text(text) => *text = value.to_string(),
+            _ => {
+                for child in std::mem::take(&mut self.nodes[node].children) {
+                    self.nodes[child].parent = None;
+                }
+                let text = self.create(NodeKind::Text(value.to_string()));
+                self.nodes[text].parent = Some(node);
+                self.nodes[node].children.push(text);
+            }
+        }
+    }
+
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        let node = self.get(id);
+        if let NodeKind::Element { listeners, .. } = &mut self.nodes[node].kind {
+            listeners.push(name);
+        }
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        let node = self.get(id);
+        if let NodeKind::Element { listeners, .. } = &mut self.nodes[node].kind {
+            if let Some(position) = listeners.iter().position(|listener| *listener == name) {
+                listeners.remove(position);
+            }
+        }
+    }
+
+    fn remove_node(&mut self, id: ElementId) {
+        if let Some(node) = self.ids.remove(&id) {
+            self.detach(node);
+        }
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        let node = self.get(id);
+        self.stack.push(node);
+    }
+}
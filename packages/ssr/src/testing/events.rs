@@ -0,0 +1,219 @@
+//! Simulated user input for elements in the headless dom.
+
+use super::{dom::NodeIndex, ComponentHarness};
+use dioxus_core::Event;
+use dioxus_html::{
+    input_data::keyboard_types::{Code, Key, Location, Modifiers},
+    EventData, FormValue, SerializedFocusData, SerializedFormData, SerializedKeyboardData,
+    SerializedMouseData,
+};
+use std::collections::HashMap;
+
+/// An element in the headless dom of a [`ComponentHarness`].
+///
+/// Dispatching an event runs the listeners of the element and its ancestors and settles the dom before returning, so
+/// the next query sees the result of the event.
+pub struct ElementHandle<'a> {
+    pub(crate) harness: &'a mut ComponentHarness,
+    pub(crate) node: NodeIndex,
+}
+
+impl ElementHandle<'_> {
+    /// The tag name of the element
+    pub fn tag(&self) -> &'static str {
+        self.harness.headless.tag(self.node).unwrap_or_default()
+    }
+
+    /// The text of all text nodes inside of the element
+    pub fn text(&self) -> String {
+        self.harness.headless.text_content(self.node)
+    }
+
+    /// The current value of an attribute of the element
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        self.harness
+            .headless
+            .attribute(self.node, name)
+            .map(|value| value.to_string())
+    }
+
+    /// Check if the element has a listener for the event, like `click`. Listeners on ancestors are not included.
+    pub fn has_listener(&self, event: &str) -> bool {
+        self.harness.headless.has_listener(self.node, event)
+    }
+
+    /// Dispatch a `click` event
+    pub fn click(self) {
+        self.dispatch("click", EventData::Mouse(SerializedMouseData::default()))
+    }
+
+    /// Dispatch a `dblclick` event
+    pub fn double_click(self) {
+        self.dispatch("dblclick", EventData::Mouse(SerializedMouseData::default()))
+    }
+
+    /// Dispatch a `focus` event
+    pub fn focus(self) {
+        self.dispatch("focus", EventData::Focus(SerializedFocusData::default()))
+    }
+
+    /// Dispatch a `blur` event
+    pub fn blur(self) {
+        self.dispatch("blur", EventData::Focus(SerializedFocusData::default()))
+    }
+
+    /// Set the value of an input and dispatch an `input` event, like a user editing the field
+    pub fn input(mut self, value: &str) {
+        self.set_value(value);
+        let data = self.form_data(value);
+        self.dispatch("input", data)
+    }
+
+    /// Set the value of an input and dispatch a `change` event, like a user committing an edit or picking an option
+    pub fn change(mut self, value: &str) {
+        self.set_value(value);
+        let data = self.form_data(value);
+        self.dispatch("change", data)
+    }
+
+    /// Check or uncheck a checkbox and dispatch `input` and `change` events
+    pub fn set_checked(self, checked: bool) {
+        let harness = self.harness;
+        let node = self.node;
+        match checked {
+            true => harness
+                .headless
+                .set_attribute_value(node, "checked", "true".to_string()),
+            false => harness.headless.remove_attribute(node, "checked"),
+        }
+
+        for name in ["input", "change"] {
+            let data = ElementHandle { harness, node }.form_data(&checked.to_string());
+            ElementHandle { harness, node }.dispatch(name, data);
+        }
+    }
+
+    /// Type text into the element one character at a time. Each character dispatches `keydown`, `keypress`, `input`
+    /// and `keyup` events, and the value of the element grows by that character.
+    pub fn type_text(self, text: &str) {
+        let harness = self.harness;
+        let node = self.node;
+        let mut value = harness
+            .headless
+            .attribute(node, "value")
+            .unwrap_or_default()
+            .to_string();
+
+        for c in text.chars() {
+            let key = Key::Character(c.to_string());
+            value.push(c);
+
+            ElementHandle { harness, node }.key("keydown", key.clone());
+            ElementHandle { harness, node }.key("keypress", key.clone());
+            if harness.headless.event_target(node).is_none() {
+                // The element was removed by one of the key listeners
+                return;
+            }
+            ElementHandle { harness, node }.input(&value);
+            ElementHandle { harness, node }.key("keyup", key);
+        }
+    }
+
+    /// Dispatch a `keydown` event for the key
+    pub fn key_down(self, key: Key) {
+        self.key("keydown", key)
+    }
+
+    /// Dispatch a `keyup` event for the key
+    pub fn key_up(self, key: Key) {
+        self.key("keyup", key)
+    }
+
+    /// Dispatch a `keydown`, `keypress` and `keyup` event for the key
+    pub fn press_key(self, key: Key) {
+        let harness = self.harness;
+        let node = self.node;
+        for name in ["keydown", "keypress", "keyup"] {
+            ElementHandle { harness, node }.key(name, key.clone());
+        }
+    }
+
+    /// Dispatch a `submit` event on the form this element is in, with the values of the named inputs in the form
+    pub fn submit(self) {
+        let form = self
+            .harness
+            .headless
+            .closest(self.node, "form")
+            .unwrap_or(self.node);
+
+        let mut values: HashMap<String, FormValue> = HashMap::new();
+        for input in self.harness.headless.descendants(form) {
+            let dom = &self.harness.headless;
+            let (Some(name), Some(tag)) = (dom.attribute(input, "name"), dom.tag(input)) else {
+                continue;
+            };
+            if !matches!(tag, "input" | "select" | "textarea") {
+                continue;
+            }
+
+            let value = match dom.attribute(input, "type") {
+                Some("checkbox" | "radio") if dom.attribute(input, "checked").is_none() => continue,
+                Some("checkbox" | "radio") => dom.attribute(input, "value").unwrap_or("on"),
+                _ => dom.attribute(input, "value").unwrap_or_default(),
+            };
+
+            values
+                .entry(name.to_string())
+                .or_insert_with(|| FormValue(Vec::new()))
+                .0
+                .push(value.to_string());
+        }
+
+        let data = EventData::Form(SerializedFormData::new(String::new(), values));
+        ElementHandle {
+            harness: self.harness,
+            node: form,
+        }
+        .dispatch("submit", data)
+    }
+
+    /// Dispatch any event to the element. The name is the name of the event without the `on` prefix, like `click`.
+    pub fn dispatch(self, name: &str, data: EventData) {
+        let Some(target) = self.harness.headless.event_target(self.node) else {
+            panic!(
+                "Cannot dispatch `{name}` to a <{}> that is no longer mounted",
+                self.tag()
+            );
+        };
+
+        let event = Event::new(data.into_any(), dioxus_core_types::event_bubbles(name));
+        self.harness.dom.runtime().handle_event(name, event, target);
+        self.harness.settle();
+    }
+
+    fn key(self, name: &str, key: Key) {
+        let data = SerializedKeyboardData::new(
+            key,
+            Code::Unidentified,
+            Location::Standard,
+            false,
+            Modifiers::empty(),
+            false,
+        );
+        self.dispatch(name, EventData::Keyboard(data))
+    }
+
+    fn set_value(&mut self, value: &str) {
+        self.harness
+            .headless
+            .set_attribute_value(self.node, "value", value.to_string());
+    }
+
+    fn form_data(&self, value: &str) -> EventData {
+        let mut values = HashMap::new();
+        if let Some(name) = self.harness.headless.attribute(self.node, "name") {
+            values.insert(name.to_string(), FormValue(vec![value.to_string()]));
+        }
+        EventData::Form(SerializedFormData::new(value.to_string(), values))
+    }
+}
@@ -0,0 +1,582 @@
+//! Utilities for snapshot testing components.
+//!
+//! A [`ComponentHarness`] runs a component in a headless [`VirtualDom`] and renders it to normalized, indented HTML
+//! that can be compared against a snapshot stored next to your tests:
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! # use dioxus_ssr::testing::ComponentHarness;
+//! fn Counter() -> Element {
+//!     let count = use_context::<Signal<i32>>();
+//!     rsx! { p { "Count: {count}" } }
+//! }
+//!
+//! let mut harness = ComponentHarness::new(Counter);
+//! let mut count = harness.in_root_scope(|| Signal::new(0));
+//! harness.provide_context(count);
+//! harness.assert_snapshot("counter_initial");
+//!
+//! harness.in_root_scope(|| count.set(5));
+//! harness.assert_snapshot("counter_updated");
+//! ```
+//!
+//! Snapshots are stored as `tests/snapshots/<name>.html` in the crate that runs the test. A missing snapshot is written on
+//! the first run. Run the tests with `DIOXUS_UPDATE_SNAPSHOTS=1` to overwrite the snapshots that don't match.
+//!
+//! The harness also keeps a headless copy of the dom that can be queried by text, role or attribute. The elements it
+//! finds can receive simulated clicks, key presses and form input:
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! # use dioxus_ssr::testing::ComponentHarness;
+//! fn Counter() -> Element {
+//!     let mut count = use_signal(|| 0);
+//!     rsx! {
+//!         p { "Count: {count}" }
+//!         button { onclick: move |_| count += 1, "Increment" }
+//!     }
+//! }
+//!
+//! let mut harness = ComponentHarness::new(Counter);
+//! harness.find_by_text("Increment").click();
+//! assert_eq!(harness.find_by_role("paragraph").text(), "Count: 1");
+//! ```
+//!
+//! Timers run on a [`MockClock`] that only moves when the test calls [`ComponentHarness::advance_time`], so suspense
+//! timeouts, debounces and futures that await [`dioxus_core::sleep`] finish instantly and in a deterministic order.
+
+use crate::Renderer;
+use dioxus_core::{prelude::*, Clock, MockClock, VirtualDom, WriteMutations};
+use std::{
+    fmt::Write,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::pin,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Wake, Waker},
+    time::Duration,
+};
+
+mod dom;
+mod events;
+
+pub use dom::Selector;
+pub use events::ElementHandle;
+
+/// The environment variable that switches [`ComponentHarness::assert_snapshot`] into update mode
+pub const UPDATE_SNAPSHOTS_ENV: &str = "DIOXUS_UPDATE_SNAPSHOTS";
+
+/// How many times the harness runs the scheduler before assuming a component will never settle
+const MAX_SETTLE_ITERATIONS: usize = 10_000;
+
+/// A headless [`VirtualDom`] with a controllable executor for testing components.
+///
+/// The dom is built lazily the first time it is rendered or settled so contexts can be provided before any component
+/// runs. Work is never run in the background: tasks, effects and rerenders only run when you call
+/// [`ComponentHarness::settle`], [`ComponentHarness::wait_for_work`] or render the dom.
+pub struct ComponentHarness {
+    dom: VirtualDom,
+    renderer: Renderer,
+    headless: dom::HeadlessDom,
+    clock: MockClock,
+    built: bool,
+    snapshot_dir: Option<PathBuf>,
+}
+
+impl ComponentHarness {
+    /// Create a harness for a component without props
+    pub fn new(app: fn() -> Element) -> Self {
+        Self::from_dom(VirtualDom::new(app))
+    }
+
+    /// Create a harness for a component with props
+    pub fn new_with_props<P: Clone + 'static, M: 'static>(
+        root: impl ComponentFunction<P, M>,
+        root_props: P,
+    ) -> Self {
+        Self::from_dom(VirtualDom::new_with_props(root, root_props))
+    }
+
+    /// Create a harness from an existing virtual dom that hasn't been rebuilt yet
+    pub fn from_dom(dom: VirtualDom) -> Self {
+        // Simulated events carry serialized event data
+        dioxus_html::set_event_converter(Box::new(dioxus_html::SerializedHtmlEventConverter));
+
+        let clock = MockClock::new();
+        dom.runtime().set_clock(Rc::new(clock.clone()));
+
+        // Snapshots should only change when the component renders something different
+        let mut renderer = Renderer::new();
+        renderer.deterministic = true;
+
+        Self {
+            dom,
+            renderer,
+            headless: dom::HeadlessDom::default(),
+            clock,
+            built: false,
+            snapshot_dir: None,
+        }
+    }
+
+    /// Provide a context to the root scope. This can be used to mock the contexts the component reads, like a signal
+    /// created with [`ComponentHarness::in_root_scope`].
+    pub fn with_context<T: Clone + 'static>(self, context: T) -> Self {
+        self.provide_context(context);
+        self
+    }
+
+    /// Provide a context to the root scope. See [`ComponentHarness::with_context`].
+    pub fn provide_context<T: Clone + 'static>(&self, context: T) {
+        self.dom.provide_root_context(context);
+    }
+
+    /// Store snapshots in this directory instead of `tests/snapshots` in the crate that runs the test
+    pub fn with_snapshot_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.snapshot_dir = Some(dir.into());
+        self
+    }
+
+    /// Run a closure in the root scope of the dom. Signals created here live as long as the harness, and writing to
+    /// them from here schedules the components that read them to rerun.
+    pub fn in_root_scope<O>(&self, f: impl FnOnce() -> O) -> O {
+        self.dom.in_runtime(|| ScopeId::ROOT.in_runtime(f))
+    }
+
+    /// Get the underlying virtual dom
+    pub fn dom(&self) -> &VirtualDom {
+        &self.dom
+    }
+
+    /// Get the underlying virtual dom mutably
+    pub fn dom_mut(&mut self) -> &mut VirtualDom {
+        &mut self.dom
+    }
+
+    /// Run all work that is ready without blocking: queued tasks, effects and the rerenders they cause.
+    ///
+    /// Tasks waiting on something outside of the dom, like a timer or a channel, are left pending. Use
+    /// [`ComponentHarness::wait_for_work`] to wait for them.
+    ///
+    /// Returns `true` if any work was done.
+    pub fn settle(&mut self) -> bool {
+        if !self.built {
+            self.dom.rebuild(&mut self.headless);
+            self.built = true;
+        }
+
+        settle(&mut self.dom, &mut self.headless)
+    }
+
+    /// Wait for the next piece of work outside of the dom to complete, then settle the dom.
+    ///
+    /// This needs to be awaited in the async runtime the component's tasks depend on, for example inside of a
+    /// `#[tokio::test]` if the component uses tokio timers.
+    pub async fn wait_for_work(&mut self) {
+        self.settle();
+        self.dom.wait_for_work().await;
+        self.dom.render_immediate(&mut self.headless);
+        self.settle();
+    }
+
+    /// Get the mock clock that timers in the dom read time from
+    pub fn clock(&self) -> &MockClock {
+        &self.clock
+    }
+
+    /// Move the clock of the dom forward, settling the dom after every timer that finishes along the way.
+    ///
+    /// Timers started by the work that runs while advancing also fire if they end before the new time, so advancing by
+    /// a second runs all ten iterations of a loop that sleeps for 100 milliseconds.
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.settle();
+        let target = self.clock.now() + duration;
+
+        while let Some(deadline) = self
+            .clock
+            .next_deadline()
+            .filter(|deadline| *deadline <= target)
+        {
+            self.clock
+                .advance(deadline.saturating_duration_since(self.clock.now()));
+            self.settle();
+        }
+
+        self.clock
+            .advance(target.saturating_duration_since(self.clock.now()));
+        self.settle();
+    }
+
+    /// Settle the dom and render it to an html string
+    pub fn render(&mut self) -> String {
+        self.settle();
+        self.renderer.render(&self.dom)
+    }
+
+    /// Settle the dom and render it to normalized html with one element per line
+    pub fn render_normalized(&mut self) -> String {
+        normalize_html(&self.render())
+    }
+
+    /// Compare the normalized html of the dom against the snapshot with the given name.
+    ///
+    /// If the snapshot doesn't exist yet, it is created. If it doesn't match, this panics with a diff of the expected
+    /// and actual html unless the `DIOXUS_UPDATE_SNAPSHOTS` environment variable is set, in which case the snapshot is
+    /// overwritten.
+    #[track_caller]
+    pub fn assert_snapshot(&mut self, name: &str) {
+        let actual = self.render_normalized();
+        let path = self.snapshot_path(name);
+
+        let update = std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some_and(|value| value != "0");
+
+        let expected = match std::fs::read_to_string(&path) {
+            Ok(expected) => expected,
+            Err(_) => {
+                write_snapshot(&path, &actual);
+                return;
+            }
+        };
+
+        if expected == actual {
+            return;
+        }
+
+        if update {
+            write_snapshot(&path, &actual);
+            return;
+        }
+
+        panic!(
+            "Snapshot `{name}` does not match {}\n\n{}\nRerun the test with {UPDATE_SNAPSHOTS_ENV}=1 to update the snapshot.",
+            path.display(),
+            diff(&expected, &actual)
+        );
+    }
+
+    fn snapshot_path(&self, name: &str) -> PathBuf {
+        let dir = self.snapshot_dir.clone().unwrap_or_else(|| {
+            let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+                .expect("CARGO_MANIFEST_DIR should be set when running tests with cargo");
+            Path::new(&manifest_dir).join("tests").join("snapshots")
+        });
+
+        dir.join(format!("{name}.html"))
+    }
+
+    /// Settle the dom and find the first element that matches the selector, in document order.
+    ///
+    /// Panics with the rendered html if no element matches.
+    #[track_caller]
+    pub fn find(&mut self, selector: Selector) -> ElementHandle<'_> {
+        match self.find_all(&selector).first() {
+            Some(&node) => ElementHandle {
+                harness: self,
+                node,
+            },
+            None => panic!(
+                "No element matches {selector} in:\n\n{}",
+                self.render_normalized()
+            ),
+        }
+    }
+
+    /// Settle the dom and find the first element that matches the selector, if there is one
+    pub fn query(&mut self, selector: Selector) -> Option<ElementHandle<'_>> {
+        let node = *self.find_all(&selector).first()?;
+        Some(ElementHandle {
+            harness: self,
+            node,
+        })
+    }
+
+    /// Settle the dom and count the elements that match the selector
+    pub fn count(&mut self, selector: Selector) -> usize {
+        self.find_all(&selector).len()
+    }
+
+    /// Settle the dom and get the text of every element that matches the selector, in document order
+    pub fn texts(&mut self, selector: Selector) -> Vec<String> {
+        self.find_all(&selector)
+            .into_iter()
+            .map(|node| self.headless.text_content(node))
+            .collect()
+    }
+
+    /// Find the element whose text matches. See [`Selector::Text`].
+    #[track_caller]
+    pub fn find_by_text(&mut self, text: impl Into<String>) -> ElementHandle<'_> {
+        self.find(Selector::Text(text.into()))
+    }
+
+    /// Find the first element with the aria role. See [`Selector::Role`].
+    #[track_caller]
+    pub fn find_by_role(&mut self, role: impl Into<String>) -> ElementHandle<'_> {
+        self.find(Selector::Role(role.into()))
+    }
+
+    /// Find the first element where the attribute has the value
+    #[track_caller]
+    pub fn find_by_attribute(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> ElementHandle<'_> {
+        self.find(Selector::Attribute(name.into(), Some(value.into())))
+    }
+
+    fn find_all(&mut self, selector: &Selector) -> Vec<dom::NodeIndex> {
+        self.settle();
+        self.headless.select(selector)
+    }
+}
+
+fn write_snapshot(path: &Path, html: &str) {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).expect("failed to create the snapshot directory");
+    }
+    std::fs::write(path, html).expect("failed to write the snapshot");
+}
+
+/// Run all work that is ready in a [`VirtualDom`] without blocking: queued tasks, effects and the rerenders they cause.
+///
+/// This is how [`ComponentHarness::settle`] runs the dom, for tests that drive a [`VirtualDom`] directly. Tasks that
+/// wait for a [`MockClock`] stay pending until the clock is advanced, so the tests never wait in real time.
+///
+/// Returns `true` if any work was done.
+pub fn settle(dom: &mut VirtualDom, to: &mut impl WriteMutations) -> bool {
+    let woken = Arc::new(WakeFlag::default());
+    let waker = Waker::from(woken.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut did_work = false;
+    for _ in 0..MAX_SETTLE_ITERATIONS {
+        let ready = pin!(dom.wait_for_work()).poll(&mut cx).is_ready();
+
+        if ready {
+            dom.render_immediate(to);
+            did_work = true;
+        } else if !woken.0.swap(false, Ordering::SeqCst) {
+            return did_work;
+        }
+    }
+
+    panic!("The component did not settle after {MAX_SETTLE_ITERATIONS} iterations. Is a component or task rerunning itself in a loop?");
+}
+
+/// A waker that remembers if it was woken so the harness knows if polling again could make progress
+#[derive(Default)]
+struct WakeFlag(AtomicBool);
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Format html with one tag or text node per line, indented by depth.
+///
+/// Elements that only contain text are kept on a single line. The html rendered by [`Renderer`] doesn't contain any
+/// formatting whitespace, so this only makes the output readable and diffable without changing its meaning.
+pub fn normalize_html(html: &str) -> String {
+    let tokens = tokenize(html);
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let mut index = 0;
+
+    while let Some(token) = tokens.get(index) {
+        match token {
+            HtmlToken::Open { tag, raw } => {
+                // Keep elements with a single text child or no children on one line
+                match (tokens.get(index + 1), tokens.get(index + 2)) {
+                    (
+                        Some(HtmlToken::Close {
+                            tag: close,
+                            raw: close_raw,
+                        }),
+                        _,
+                    ) if close == tag => {
+                        writeln!(out, "{}{raw}{close_raw}", indent(depth)).unwrap();
+                        index += 2;
+                        continue;
+                    }
+                    (
+                        Some(HtmlToken::Text(text)),
+                        Some(HtmlToken::Close {
+                            tag: close,
+                            raw: close_raw,
+                        }),
+                    ) if close == tag => {
+                        writeln!(out, "{}{raw}{text}{close_raw}", indent(depth)).unwrap();
+                        index += 3;
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                writeln!(out, "{}{raw}", indent(depth)).unwrap();
+                depth += 1;
+            }
+            HtmlToken::Close { raw, .. } => {
+                depth = depth.saturating_sub(1);
+                writeln!(out, "{}{raw}", indent(depth)).unwrap();
+            }
+            HtmlToken::Void(raw) | HtmlToken::Text(raw) => {
+                writeln!(out, "{}{raw}", indent(depth)).unwrap();
+            }
+        }
+
+        index += 1;
+    }
+
+    out
+}
+
+fn indent(depth: usize) -> String {
+    "  ".repeat(depth)
+}
+
+#[derive(Debug, PartialEq)]
+enum HtmlToken<'a> {
+    Open {
+        tag: &'a str,
+        raw: &'a str,
+    },
+    Close {
+        tag: &'a str,
+        raw: &'a str,
+    },
+    /// A self closing tag or a comment
+    Void(&'a str),
+    Text(&'a str),
+}
+
+fn tokenize(html: &str) -> Vec<HtmlToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map(|end| end + 3).unwrap_or(rest.len());
+            tokens.push(HtmlToken::Void(&rest[..end]));
+            rest = &rest[end..];
+        } else if rest.starts_with('<') {
+            let end = tag_end(rest);
+            let raw = &rest[..end];
+            rest = &rest[end..];
+
+            if let Some(tag) = raw.strip_prefix("</") {
+                let tag = tag.trim_end_matches('>').trim();
+                tokens.push(HtmlToken::Close { tag, raw });
+                continue;
+            }
+
+            let tag = raw[1..]
+                .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .next()
+                .unwrap_or_default();
+
+            if raw.ends_with("/>") || is_void(tag) {
+                tokens.push(HtmlToken::Void(raw));
+                continue;
+            }
+
+            tokens.push(HtmlToken::Open { tag, raw });
+
+            // The contents of script and style tags are raw text that may contain `<`
+            if matches!(tag, "script" | "style") {
+                let close = format!("</{tag}>");
+                let end = rest.find(&close).unwrap_or(rest.len());
+                if end > 0 {
+                    tokens.push(HtmlToken::Text(&rest[..end]));
+                }
+                rest = &rest[end..];
+            }
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(HtmlToken::Text(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+/// Find the end of the tag at the start of the string, skipping over `>` inside of quoted attribute values
+fn tag_end(html: &str) -> usize {
+    let mut quote = None;
+    for (index, c) in html.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '>') => return index + 1,
+            _ => {}
+        }
+    }
+    html.len()
+}
+
+fn is_void(tag: &str) -> bool {
+    matches!(
+        tag,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "param"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+/// Create a line diff between the expected and actual text. Removed lines are prefixed with `-`, added lines with `+`
+/// and unchanged lines with a space.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+
+    // The length of the longest common subsequence of the remaining lines at each position
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            writeln!(out, "  {}", expected[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            writeln!(out, "- {}", expected[i]).unwrap();
+            i += 1;
+        } else {
+            writeln!(out, "+ {}", actual[j]).unwrap();
+            j += 1;
+        }
+    }
+
+    out
+}
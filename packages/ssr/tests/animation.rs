@@ -0,0 +1,77 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_ssr::testing::ComponentHarness;
+use std::time::Duration;
+
+#[test]
+fn tween_reaches_target_after_duration() {
+    fn App() -> Element {
+        let mut open = use_signal(|| false);
+        let width = use_tween(
+            move || if open() { 100.0_f64 } else { 0.0 },
+            Tween::new(Duration::from_millis(100)).easing(Easing::Linear),
+        );
+
+        rsx! {
+            button { onclick: move |_| open.set(true) }
+            "{width().round()}"
+        }
+    }
+
+    let mut harness = ComponentHarness::new(App);
+    assert_eq!(harness.render(), "<button></button>0");
+
+    harness.find_by_role("button").click();
+    // Frames land every 16.7ms, so the last frame before this is at 50ms
+    harness.advance_time(Duration::from_millis(60));
+    let halfway: f64 = harness.render()["<button></button>".len()..]
+        .parse()
+        .unwrap();
+    assert!((40.0..=60.0).contains(&halfway), "{halfway}");
+
+    harness.advance_time(Duration::from_millis(100));
+    assert_eq!(harness.render(), "<button></button>100");
+}
+
+#[test]
+fn spring_settles_at_target() {
+    fn App() -> Element {
+        let value = use_animated_value(|| 0.0);
+
+        rsx! {
+            button { onclick: move |_| value.animate_to(10.0, Spring::wobbly()) }
+            "{value.get()} {value.is_animating()}"
+        }
+    }
+
+    let mut harness = ComponentHarness::new(App);
+    assert_eq!(harness.render(), "<button></button>0 false");
+
+    harness.find_by_role("button").click();
+    harness.advance_time(Duration::from_millis(100));
+    assert!(harness.render().ends_with("true"));
+
+    harness.advance_time(Duration::from_secs(5));
+    assert_eq!(harness.render(), "<button></button>10 false");
+}
+
+#[test]
+fn jumping_stops_the_animation() {
+    fn App() -> Element {
+        let value = use_animated_value(|| 0.0);
+
+        rsx! {
+            button { id: "animate", onclick: move |_| value.animate_to(10.0, Spring::default()) }
+            button { id: "jump", onclick: move |_| value.set(-5.0) }
+            "{value.get()}"
+        }
+    }
+
+    let mut harness = ComponentHarness::new(App);
+    harness.find_by_attribute("id", "animate").click();
+    harness.advance_time(Duration::from_millis(50));
+    harness.find_by_attribute("id", "jump").click();
+    harness.advance_time(Duration::from_secs(1));
+    assert!(harness.render().ends_with("-5"));
+}
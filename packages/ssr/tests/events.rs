@@ -0,0 +1,126 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_html::input_data::keyboard_types::Key;
+use dioxus_ssr::testing::{ComponentHarness, Selector};
+
+#[test]
+fn clicks_bubble_to_listeners() {
+    fn Counter() -> Element {
+        let mut count = use_signal(|| 0);
+        rsx! {
+            p { "Count: {count}" }
+            div { onclick: move |_| count += 10,
+                button { onclick: move |_| count += 1, "Increment" }
+            }
+        }
+    }
+
+    let mut harness = ComponentHarness::new(Counter);
+    assert!(harness.find_by_role("button").has_listener("click"));
+
+    harness.find_by_text("Increment").click();
+    assert_eq!(harness.find_by_role("paragraph").text(), "Count: 11");
+}
+
+#[test]
+fn input_updates_bound_value() {
+    fn Form() -> Element {
+        let mut name = use_signal(String::new);
+        rsx! {
+            input { r#type: "text", value: "{name}", oninput: move |evt| name.set(evt.value()) }
+            if !name().is_empty() {
+                p { "Hello {name}" }
+            }
+        }
+    }
+
+    let mut harness = ComponentHarness::new(Form);
+    assert_eq!(harness.count(Selector::Tag("p".into())), 0);
+
+    harness.find_by_role("textbox").input("Dioxus");
+    assert_eq!(harness.find_by_role("paragraph").text(), "Hello Dioxus");
+
+    harness.find_by_role("textbox").type_text("!");
+    assert_eq!(
+        harness.find_by_role("textbox").attribute("value").unwrap(),
+        "Dioxus!"
+    );
+    assert_eq!(harness.find_by_role("paragraph").text(), "Hello Dioxus!");
+}
+
+#[test]
+fn key_events() {
+    fn Keys() -> Element {
+        let mut keys = use_signal(Vec::new);
+        rsx! {
+            div {
+                tabindex: 0,
+                "data-testid": "target",
+                onkeydown: move |evt| keys.push(format!("down {}", evt.key())),
+                onkeyup: move |evt| keys.push(format!("up {}", evt.key())),
+            }
+            for key in keys() {
+                li { "{key}" }
+            }
+        }
+    }
+
+    let mut harness = ComponentHarness::new(Keys);
+    harness
+        .find_by_attribute("data-testid", "target")
+        .key_down(Key::Enter);
+    harness
+        .find_by_attribute("data-testid", "target")
+        .key_up(Key::Character("a".into()));
+
+    assert_eq!(
+        harness.texts(Selector::Role("listitem".into())),
+        ["down Enter", "up a"]
+    );
+}
+
+#[test]
+fn submits_form_values() {
+    fn Login() -> Element {
+        let mut submitted = use_signal(String::new);
+        rsx! {
+            form {
+                onsubmit: move |evt| {
+                    let values = evt.values();
+                    submitted.set(format!("{} {}", values["user"].as_value(), values.contains_key("remember")));
+                },
+                input { name: "user", r#type: "text" }
+                input { name: "remember", r#type: "checkbox" }
+                button { r#type: "submit", "Submit" }
+            }
+            p { "{submitted}" }
+        }
+    }
+
+    let mut harness = ComponentHarness::new(Login);
+    harness.find_by_role("textbox").input("ferris");
+    harness.find_by_role("checkbox").set_checked(true);
+    harness.find_by_text("Submit").submit();
+
+    assert_eq!(harness.find_by_role("paragraph").text(), "ferris true");
+}
+
+#[test]
+fn missing_element_panics_with_html() {
+    fn App() -> Element {
+        rsx! { div { "hello" } }
+    }
+
+    let mut harness = ComponentHarness::new(App);
+    assert!(harness.query(Selector::Text("goodbye".into())).is_none());
+
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        harness.find_by_text("goodbye");
+    }))
+    .unwrap_err();
+
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.contains("No element matches text \"goodbye\""));
+    assert!(message.contains("<div>hello</div>"));
+}
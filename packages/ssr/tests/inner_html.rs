@@ -24,3 +24,20 @@ fn dynamic_inner_html() {
 
     assert_eq!(dioxus_ssr::render(&dom), r#"<div><div>1234</div></div>"#);
 }
+
+#[test]
+fn raw_html_is_sanitized() {
+    fn app() -> Element {
+        rsx! {
+            RawHtml { class: "post", html: "<p onclick=\"steal()\">Hello<script>alert(1)</script></p>" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        r#"<div class="post"><p>Hello</p></div>"#
+    );
+}
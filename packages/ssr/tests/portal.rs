@@ -0,0 +1,100 @@
+use dioxus::prelude::*;
+use dioxus_core::{ElementId, Mutation, Mutations, NoOpMutations};
+use dioxus_html::{MountedData, PortalHost, SerializedHtmlEventConverter};
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+#[test]
+fn portals_render_in_place_without_a_host() {
+    fn app() -> Element {
+        rsx! {
+            p { "before" }
+            Portal { target: "#modal", span { "inside" } }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        r##"<p>before</p><div style="display: contents" data-dioxus-portal="#modal"><span>inside</span></div>"##
+    );
+}
+
+/// Records the calls a renderer would use to move the portal container
+#[derive(Default)]
+struct RecordingHost(RefCell<Vec<String>>);
+
+impl PortalHost for RecordingHost {
+    fn mount(&self, _: &MountedData, target: &str) {
+        self.0.borrow_mut().push(format!("mount {target}"));
+    }
+
+    fn unmount(&self, _: &MountedData) {
+        self.0.borrow_mut().push("unmount".to_string());
+    }
+}
+
+#[test]
+fn portals_move_their_container_into_the_host() {
+    fn app() -> Element {
+        let mut target = use_signal(|| "#modal");
+        let mut open = use_signal(|| true);
+        rsx! {
+            button { onclick: move |_| target.set("#tooltip"), "Move" }
+            button { onclick: move |_| open.set(false), "Close" }
+            if open() {
+                Portal { target: target(), "inside" }
+            }
+        }
+    }
+
+    dioxus_html::set_event_converter(Box::new(SerializedHtmlEventConverter));
+    let host = Rc::new(RecordingHost::default());
+    let mut dom = VirtualDom::new(app);
+    dom.provide_root_context(host.clone() as Rc<dyn PortalHost>);
+
+    let mut mutations = Mutations::default();
+    dom.rebuild(&mut mutations);
+    let listeners = |event: &str| -> Vec<ElementId> {
+        mutations
+            .edits
+            .iter()
+            .filter_map(|edit| match edit {
+                Mutation::NewEventListener { name, id } if name == event => Some(*id),
+                _ => None,
+            })
+            .collect()
+    };
+    let container = listeners("mounted")[0];
+    let buttons = listeners("click");
+
+    // Nothing moves until the renderer reports the container as mounted
+    assert!(host.0.borrow().is_empty());
+    let event = Event::new(
+        Rc::new(PlatformEventData::new(Box::new(()))) as Rc<dyn Any>,
+        false,
+    );
+    dom.runtime().handle_event("mounted", event, container);
+    assert_eq!(*host.0.borrow(), ["mount #modal"]);
+
+    let click = |dom: &mut VirtualDom, id: ElementId| {
+        let event = Event::new(
+            Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())) as Rc<dyn Any>,
+            true,
+        );
+        dom.runtime().handle_event("click", event, id);
+        dom.render_immediate(&mut NoOpMutations);
+    };
+
+    // Changing the target moves the mounted container
+    click(&mut dom, buttons[0]);
+    assert_eq!(*host.0.borrow(), ["mount #modal", "mount #tooltip"]);
+
+    // Removing the portal cleans up the container
+    click(&mut dom, buttons[1]);
+    assert_eq!(
+        *host.0.borrow(),
+        ["mount #modal", "mount #tooltip", "unmount"]
+    );
+}
@@ -0,0 +1,133 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_ssr::testing::{diff, normalize_html, ComponentHarness};
+use std::time::Duration;
+
+#[test]
+fn normalizes_html() {
+    let html = normalize_html(
+        r#"<div class="a"><h1>Title</h1><ul><li>one</li><li></li></ul><input type="text"/><!--placeholder--><p>text<b>bold</b></p></div>"#,
+    );
+
+    assert_eq!(
+        html,
+        r#"<div class="a">
+  <h1>Title</h1>
+  <ul>
+    <li>one</li>
+    <li></li>
+  </ul>
+  <input type="text"/>
+  <!--placeholder-->
+  <p>
+    text
+    <b>bold</b>
+  </p>
+</div>
+"#
+    );
+}
+
+#[test]
+fn normalize_skips_raw_text() {
+    let html = normalize_html(r#"<style>a > b { color: red; }</style><div title="a > b"></div>"#);
+
+    assert_eq!(
+        html,
+        r#"<style>a > b { color: red; }</style>
+<div title="a > b"></div>
+"#
+    );
+}
+
+#[test]
+fn diffs_lines() {
+    let expected = "<div>\n  <p>one</p>\n  <p>two</p>\n</div>\n";
+    let actual = "<div>\n  <p>one</p>\n  <p>three</p>\n</div>\n";
+
+    assert_eq!(
+        diff(expected, actual),
+        "  <div>\n    <p>one</p>\n-   <p>two</p>\n+   <p>three</p>\n  </div>\n"
+    );
+}
+
+#[test]
+fn snapshot_with_mocked_signal() {
+    fn Counter() -> Element {
+        let count = use_context::<Signal<i32>>();
+        rsx! {
+            div {
+                h1 { "Counter" }
+                p { "Count: {count}" }
+            }
+        }
+    }
+
+    let mut harness = ComponentHarness::new(Counter);
+    let mut count = harness.in_root_scope(|| Signal::new(0));
+    harness.provide_context(count);
+    harness.assert_snapshot("counter_initial");
+
+    harness.in_root_scope(|| count.set(5));
+    harness.assert_snapshot("counter_updated");
+}
+
+#[test]
+fn settles_tasks_and_effects() {
+    fn App() -> Element {
+        let mut effect_ran = use_signal(|| false);
+        let resource = use_resource(|| async { "loaded" });
+
+        use_effect(move || effect_ran.set(true));
+
+        rsx! {
+            p { "effect: {effect_ran}" }
+            p { "resource: {resource.cloned().unwrap_or(\"loading\")}" }
+        }
+    }
+
+    let mut harness = ComponentHarness::new(App);
+    assert_eq!(
+        harness.render_normalized(),
+        "<p>effect: true</p>\n<p>resource: loaded</p>\n"
+    );
+}
+
+#[tokio::test]
+async fn waits_for_external_work() {
+    fn App() -> Element {
+        let resource = use_resource(|| async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            "done"
+        });
+
+        rsx! { "{resource.cloned().unwrap_or(\"waiting\")}" }
+    }
+
+    let mut harness = ComponentHarness::new(App);
+    assert_eq!(harness.render(), "waiting");
+
+    harness.wait_for_work().await;
+    assert_eq!(harness.render(), "done");
+}
+
+#[test]
+fn mismatched_snapshot_panics_with_diff() {
+    fn App() -> Element {
+        rsx! { div { "new text" } }
+    }
+
+    let dir = std::env::temp_dir().join("dioxus-ssr-snapshot-mismatch");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("app.html"), "<div>old text</div>\n").unwrap();
+
+    let mut harness = ComponentHarness::new(App).with_snapshot_dir(&dir);
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        harness.assert_snapshot("app")
+    }))
+    .unwrap_err();
+
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.contains("- <div>old text</div>\n+ <div>new text</div>"));
+}
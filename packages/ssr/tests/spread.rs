@@ -12,6 +12,33 @@ fn spread() {
     );
 }
 
+#[test]
+fn deterministic_spread() {
+    fn render(attributes: Vec<Attribute>) -> String {
+        let mut renderer = dioxus_ssr::Renderer::new();
+        renderer.deterministic = true;
+        renderer.render_element(rsx! {
+            div { ..attributes }
+        })
+    }
+
+    let attributes = vec![
+        Attribute::new("title", "hello", None, false),
+        Attribute::new("height", "10px", Some("style"), false),
+        Attribute::new("data-id", "1", None, false),
+        Attribute::new("width", "10px", Some("style"), false),
+    ];
+    let mut reversed = attributes.clone();
+    reversed.reverse();
+
+    let html = render(attributes);
+    assert_eq!(html, render(reversed));
+    assert_eq!(
+        html,
+        r#"<div data-id="1" title="hello" style="height:10px;width:10px;"></div>"#
+    );
+}
+
 fn app() -> Element {
     rsx! {
         SpreadableComponent {
@@ -38,3 +38,20 @@ fn dynamic_styles() {
         r#"<div style="width:123px;"></div>"#
     );
 }
+
+#[test]
+fn typed_styles() {
+    use dioxus::html::css::*;
+
+    let style = Style::new()
+        .display(Display::Flex)
+        .gap(px(8))
+        .background_color(rgb(0, 0, 0));
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div { width: "100px", style }
+        }),
+        r#"<div style="width:100px;background-color:rgb(0 0 0);display:flex;gap:8px;"></div>"#
+    );
+}
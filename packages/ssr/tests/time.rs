@@ -0,0 +1,67 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_ssr::testing::ComponentHarness;
+use std::time::Duration;
+
+#[test]
+fn advancing_time_runs_chained_timers() {
+    fn Ticker() -> Element {
+        let mut ticks = use_signal(|| 0);
+        use_future(move || async move {
+            loop {
+                dioxus_core::sleep(Duration::from_millis(100)).await;
+                ticks += 1;
+            }
+        });
+
+        rsx! { "ticks: {ticks}" }
+    }
+
+    let mut harness = ComponentHarness::new(Ticker);
+    assert_eq!(harness.render(), "ticks: 0");
+
+    harness.advance_time(Duration::from_millis(99));
+    assert_eq!(harness.render(), "ticks: 0");
+
+    harness.advance_time(Duration::from_millis(1));
+    assert_eq!(harness.render(), "ticks: 1");
+
+    harness.advance_time(Duration::from_secs(1));
+    assert_eq!(harness.render(), "ticks: 11");
+    assert_eq!(harness.clock().elapsed(), Duration::from_millis(1100));
+}
+
+#[test]
+fn suspense_timeout_without_waiting() {
+    fn App() -> Element {
+        rsx! {
+            SuspenseBoundary {
+                fallback: |_| rsx! { "loading" },
+                slow_fallback: |_| rsx! { "still loading" },
+                timeout: Duration::from_secs(5),
+                Slow {}
+            }
+        }
+    }
+
+    #[component]
+    fn Slow() -> Element {
+        let value = use_resource(|| async {
+            dioxus_core::sleep(Duration::from_secs(30)).await;
+            "done"
+        })
+        .suspend()?;
+
+        rsx! { "{value}" }
+    }
+
+    let mut harness = ComponentHarness::new(App);
+    assert_eq!(harness.render(), "loading");
+
+    harness.advance_time(Duration::from_secs(5));
+    assert_eq!(harness.render(), "still loading");
+
+    harness.advance_time(Duration::from_secs(25));
+    assert_eq!(harness.render(), "done");
+}
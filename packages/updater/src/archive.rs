@@ -0,0 +1,168 @@
+use crate::{Error, Result};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::path::{Component, Path, PathBuf};
+
+/// The file in a delta archive that lists the files removed since the older version, one path per line
+const REMOVED_FILES: &str = ".dioxus-update-removed";
+
+/// The directory archives are extracted to before the files are moved into the install directory
+const STAGING_DIR: &str = ".dioxus-update";
+
+/// Create a `.tar.gz` archive with every file in the directory
+pub fn create_archive(dir: &Path) -> Result<Vec<u8>> {
+    let files = relative_files(dir)?;
+    write_archive(dir, &files, &[])
+}
+
+/// Create a `.tar.gz` archive with the files in `new_dir` that are new or different from the files in `old_dir`.
+/// Applying the archive to an install of the old version also removes the files that are missing from `new_dir`.
+pub fn create_delta_archive(old_dir: &Path, new_dir: &Path) -> Result<Vec<u8>> {
+    let old_files = relative_files(old_dir)?;
+    let new_files = relative_files(new_dir)?;
+
+    let mut changed = Vec::new();
+    for file in &new_files {
+        let new = std::fs::read(new_dir.join(file))?;
+        match std::fs::read(old_dir.join(file)) {
+            Ok(old) if old == new => {}
+            _ => changed.push(file.clone()),
+        }
+    }
+    let removed = old_files
+        .into_iter()
+        .filter(|file| !new_files.contains(file))
+        .collect::<Vec<_>>();
+
+    write_archive(new_dir, &changed, &removed)
+}
+
+/// Extract a full or delta archive into the install directory of the app.
+///
+/// Files are first extracted into a staging directory and then renamed over the installed files, so the executable
+/// of the running app can be replaced. The new version is used the next time the app starts.
+pub fn apply_archive(archive: &[u8], install_dir: &Path) -> Result<()> {
+    let staging = install_dir.join(STAGING_DIR);
+    _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging)?;
+
+    let mut unpacker = tar::Archive::new(GzDecoder::new(archive));
+    unpacker.set_preserve_permissions(true);
+    unpacker.unpack(&staging)?;
+
+    let removed = match std::fs::read_to_string(staging.join(REMOVED_FILES)) {
+        Ok(removed) => {
+            std::fs::remove_file(staging.join(REMOVED_FILES))?;
+            removed
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(safe_relative_path)
+                .collect::<Result<Vec<_>>>()?
+        }
+        Err(_) => Vec::new(),
+    };
+
+    for file in relative_files(&staging)? {
+        replace_file(&staging.join(&file), &install_dir.join(&file))?;
+    }
+    for file in removed {
+        match std::fs::remove_file(install_dir.join(file)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+    }
+
+    _ = std::fs::remove_dir_all(&staging);
+
+    Ok(())
+}
+
+fn write_archive(root: &Path, files: &[PathBuf], removed: &[PathBuf]) -> Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::best()));
+    builder.follow_symlinks(false);
+    for file in files {
+        builder.append_path_with_name(root.join(file), file)?;
+    }
+
+    if !removed.is_empty() {
+        let list = removed
+            .iter()
+            .map(|file| archive_path(file))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut header = tar::Header::new_gnu();
+        header.set_size(list.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, REMOVED_FILES, list.as_bytes())?;
+    }
+
+    Ok(builder.into_inner()?.finish()?)
+}
+
+/// Every file in the directory relative to the directory, sorted so archives are reproducible
+fn relative_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    fn visit(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let file_type = std::fs::symlink_metadata(&path)?.file_type();
+            if file_type.is_dir() {
+                visit(root, &path, files)?;
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                files.push(relative.to_path_buf());
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    visit(dir, dir, &mut files)?;
+    files.retain(|file| !file.starts_with(STAGING_DIR));
+    files.sort();
+    Ok(files)
+}
+
+/// Move a file over another file. The destination is renamed first because some platforms don't allow replacing the
+/// executable of a running process.
+fn replace_file(source: &Path, destination: &Path) -> Result<()> {
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut old = destination.as_os_str().to_owned();
+    old.push(".old");
+    let old = PathBuf::from(old);
+    if destination.exists() {
+        _ = std::fs::remove_file(&old);
+        std::fs::rename(destination, &old)?;
+    }
+
+    std::fs::rename(source, destination)?;
+
+    // This fails on windows while the old executable is still running. It is removed by the next update.
+    _ = std::fs::remove_file(&old);
+
+    Ok(())
+}
+
+/// Paths in archives always use `/` as the separator
+fn archive_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Parse a path from an archive, rejecting paths that would escape the install directory
+fn safe_relative_path(path: &str) -> Result<PathBuf> {
+    let path = PathBuf::from_iter(path.split('/'));
+    match path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        true => Ok(path),
+        false => Err(Error::InvalidArchive(format!(
+            "{} is not a relative path",
+            path.display()
+        ))),
+    }
+}
@@ -0,0 +1,52 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+mod archive;
+mod manifest;
+mod signature;
+
+pub use archive::*;
+pub use manifest::*;
+pub use signature::*;
+
+#[cfg(feature = "client")]
+mod updater;
+#[cfg(feature = "client")]
+pub use updater::*;
+
+/// An error that occurred while creating, checking for or installing an update
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Reading or writing the files of the update failed
+    #[error("Failed to read or write the update: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Downloading the manifest or the update failed
+    #[cfg(feature = "client")]
+    #[error("Failed to download the update: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The update manifest could not be parsed
+    #[error("Invalid update manifest: {0}")]
+    InvalidManifest(String),
+
+    /// A version in the manifest or the version of the app is not a valid semver version
+    #[error("Invalid version {0}")]
+    InvalidVersion(String),
+
+    /// The public or private key is not a valid ed25519 key
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+
+    /// The update was not signed by the private key that matches the public key of the app
+    #[error("The signature of the update does not match the public key")]
+    InvalidSignature,
+
+    /// The update archive contains files that cannot be installed
+    #[error("Invalid update archive: {0}")]
+    InvalidArchive(String),
+}
+
+/// The result type of the updater
+pub type Result<T, E = Error> = std::result::Result<T, E>;
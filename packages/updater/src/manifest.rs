@@ -0,0 +1,146 @@
+use crate::{Error, Result};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The manifest `dx bundle --updater` writes next to the update archives. Apps download it to check if a newer
+/// version is available.
+///
+/// ```json
+/// {
+///   "version": "0.2.0",
+///   "notes": "Bug fixes",
+///   "platforms": {
+///     "linux-x86_64": {
+///       "url": "my-app-0.2.0-linux-x86_64.tar.gz",
+///       "signature": "...",
+///       "size": 3145728,
+///       "deltas": [
+///         {
+///           "from": "0.1.0",
+///           "url": "my-app-0.1.0-0.2.0-linux-x86_64.delta.tar.gz",
+///           "signature": "...",
+///           "size": 52428
+///         }
+///       ]
+///     }
+///   }
+/// }
+/// ```
+///
+/// Urls may be absolute or relative to the url of the manifest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateManifest {
+    /// The version of the latest release
+    pub version: String,
+    /// The release notes of the latest release
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// When the latest release was published
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pub_date: Option<String>,
+    /// The archives for each target, keyed by `{os}-{arch}`. See [`current_target`].
+    pub platforms: BTreeMap<String, PlatformUpdate>,
+}
+
+/// The archives of the latest release for a single target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlatformUpdate {
+    /// The url of the archive with every file of the app
+    pub url: String,
+    /// The signature of the archive
+    pub signature: String,
+    /// The size of the archive in bytes
+    pub size: u64,
+    /// Smaller archives that only contain the files that changed since an older version
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deltas: Vec<DeltaUpdate>,
+}
+
+/// An archive with the files that changed between an older version and the latest release
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeltaUpdate {
+    /// The version this archive updates from
+    pub from: String,
+    /// The url of the archive
+    pub url: String,
+    /// The signature of the archive
+    pub signature: String,
+    /// The size of the archive in bytes
+    pub size: u64,
+}
+
+/// An update that is newer than the running app. Returned by [`UpdateManifest::update_for`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update {
+    /// The version of the update
+    pub version: String,
+    /// The release notes of the update
+    pub notes: Option<String>,
+    /// When the update was published
+    pub pub_date: Option<String>,
+    /// The url of the archive to download, as written in the manifest
+    pub url: String,
+    /// The signature of the archive
+    pub signature: String,
+    /// The size of the archive in bytes
+    pub size: u64,
+    /// If the archive only contains the files that changed since the running version
+    pub delta: bool,
+}
+
+impl UpdateManifest {
+    /// Create a manifest for a release without any archives
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            notes: None,
+            pub_date: None,
+            platforms: BTreeMap::new(),
+        }
+    }
+
+    /// Find the update for the target if the manifest has a newer version than the current version. A delta
+    /// archive is preferred if the manifest has one for the current version.
+    pub fn update_for(&self, current_version: &str, target: &str) -> Result<Option<Update>> {
+        let latest = parse_version(&self.version)?;
+        let current = parse_version(current_version)?;
+        if latest <= current {
+            return Ok(None);
+        }
+
+        let Some(platform) = self.platforms.get(target) else {
+            return Ok(None);
+        };
+
+        let delta = platform
+            .deltas
+            .iter()
+            .find(|delta| parse_version(&delta.from).is_ok_and(|from| from == current));
+        let (url, signature, size) = match delta {
+            Some(delta) => (&delta.url, &delta.signature, delta.size),
+            None => (&platform.url, &platform.signature, platform.size),
+        };
+
+        Ok(Some(Update {
+            version: self.version.clone(),
+            notes: self.notes.clone(),
+            pub_date: self.pub_date.clone(),
+            url: url.clone(),
+            signature: signature.clone(),
+            size,
+            delta: delta.is_some(),
+        }))
+    }
+}
+
+/// The name of the target the app was compiled for in the form `{os}-{arch}`, like `linux-x86_64` or
+/// `macos-aarch64`. This is the key of the platform in the [`UpdateManifest`].
+pub fn current_target() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+fn parse_version(version: &str) -> Result<Version> {
+    Version::parse(version.trim_start_matches('v'))
+        .map_err(|err| Error::InvalidVersion(format!("{version}: {err}")))
+}
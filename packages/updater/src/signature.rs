@@ -0,0 +1,56 @@
+use crate::{Error, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+/// A pair of keys used to sign and verify updates. Both keys are encoded as base64.
+///
+/// Keep the private key secret and only give it to the machine that runs `dx bundle`. The public key is compiled into
+/// the app and passed to [`Updater::new`](crate::Updater::new).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPair {
+    /// The key used to sign update archives
+    pub private_key: String,
+    /// The key used to verify update archives
+    pub public_key: String,
+}
+
+impl KeyPair {
+    /// Generate a new random key pair
+    pub fn generate() -> Result<Self> {
+        let mut seed = [0; 32];
+        getrandom::getrandom(&mut seed)
+            .map_err(|err| Error::InvalidKey(format!("Failed to generate a key: {err}")))?;
+        let key = SigningKey::from_bytes(&seed);
+        Ok(Self {
+            private_key: STANDARD.encode(key.to_bytes()),
+            public_key: STANDARD.encode(key.verifying_key().to_bytes()),
+        })
+    }
+}
+
+/// Sign the contents of an update archive with a base64 encoded private key. Returns the base64 encoded signature.
+pub fn sign(contents: &[u8], private_key: &str) -> Result<String> {
+    let key = SigningKey::from_bytes(&decode_key(private_key)?);
+    Ok(STANDARD.encode(key.sign(contents).to_bytes()))
+}
+
+/// Verify the base64 encoded signature of an update archive with a base64 encoded public key
+pub fn verify(contents: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    let key = VerifyingKey::from_bytes(&decode_key(public_key)?)
+        .map_err(|err| Error::InvalidKey(err.to_string()))?;
+    let signature: [u8; 64] = STANDARD
+        .decode(signature.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(Error::InvalidSignature)?;
+    key.verify(contents, &Signature::from_bytes(&signature))
+        .map_err(|_| Error::InvalidSignature)
+}
+
+fn decode_key(key: &str) -> Result<[u8; 32]> {
+    STANDARD
+        .decode(key.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| Error::InvalidKey("Expected 32 base64 encoded bytes".to_string()))
+}
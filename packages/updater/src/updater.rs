@@ -0,0 +1,163 @@
+use crate::{apply_archive, current_target, verify, Error, Result, Update, UpdateManifest};
+use std::path::{Path, PathBuf};
+
+/// Checks for, downloads and installs updates published with `dx bundle --updater`.
+///
+/// ```rust, no_run
+/// use dioxus_updater::Updater;
+///
+/// # async fn update() -> dioxus_updater::Result<()> {
+/// let updater = Updater::new(
+///     "https://example.com/releases/latest.json",
+///     "Ixkt7uVeCXQ+6EH5IF2qDMPfCdKSHRmDLmWNbuWsOIA=",
+///     env!("CARGO_PKG_VERSION"),
+/// );
+/// if let Some(update) = updater.check().await? {
+///     let downloaded = updater.download(&update).await?;
+///     updater.install(&downloaded)?;
+///     dioxus_updater::relaunch();
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Updater {
+    endpoint: String,
+    public_key: String,
+    current_version: String,
+    target: String,
+    install_dir: Option<PathBuf>,
+    client: reqwest::Client,
+}
+
+/// An update archive that was downloaded and verified by [`Updater::download`]
+#[derive(Debug, Clone)]
+pub struct DownloadedUpdate {
+    update: Update,
+    archive: Vec<u8>,
+}
+
+impl DownloadedUpdate {
+    /// The update that was downloaded
+    pub fn update(&self) -> &Update {
+        &self.update
+    }
+}
+
+impl Updater {
+    /// Create an updater that reads the [`UpdateManifest`] at the endpoint and verifies updates with the base64
+    /// encoded public key from [`KeyPair::generate`](crate::KeyPair::generate). The current version is usually
+    /// `env!("CARGO_PKG_VERSION")`.
+    pub fn new(
+        endpoint: impl Into<String>,
+        public_key: impl Into<String>,
+        current_version: impl Into<String>,
+    ) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            public_key: public_key.into(),
+            current_version: current_version.into(),
+            target: current_target(),
+            install_dir: None,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Set the target to look up in the manifest. Defaults to [`current_target`].
+    pub fn with_target(self, target: impl Into<String>) -> Self {
+        Self {
+            target: target.into(),
+            ..self
+        }
+    }
+
+    /// Set the directory updates are installed into. Defaults to the directory of the executable, or the `.app`
+    /// bundle on macOS.
+    pub fn with_install_dir(self, install_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            install_dir: Some(install_dir.into()),
+            ..self
+        }
+    }
+
+    /// Download the manifest and return the update if there is a newer version for the target
+    pub async fn check(&self) -> Result<Option<Update>> {
+        let manifest = self
+            .client
+            .get(&self.endpoint)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let manifest: UpdateManifest = serde_json::from_str(&manifest)
+            .map_err(|err| Error::InvalidManifest(err.to_string()))?;
+
+        manifest.update_for(&self.current_version, &self.target)
+    }
+
+    /// Download the archive of the update and verify its signature
+    pub async fn download(&self, update: &Update) -> Result<DownloadedUpdate> {
+        let url = reqwest::Url::parse(&self.endpoint)
+            .and_then(|endpoint| endpoint.join(&update.url))
+            .map_err(|err| Error::InvalidManifest(format!("{}: {err}", update.url)))?;
+
+        tracing::debug!("Downloading update {} from {url}", update.version);
+        let archive = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?
+            .to_vec();
+        verify(&archive, &update.signature, &self.public_key)?;
+
+        Ok(DownloadedUpdate {
+            update: update.clone(),
+            archive,
+        })
+    }
+
+    /// Install a downloaded update. The new version is used the next time the app starts.
+    pub fn install(&self, update: &DownloadedUpdate) -> Result<()> {
+        apply_archive(&update.archive, &self.install_dir()?)
+    }
+
+    /// The directory updates are installed into
+    pub fn install_dir(&self) -> Result<PathBuf> {
+        if let Some(install_dir) = &self.install_dir {
+            return Ok(install_dir.clone());
+        }
+
+        let exe = std::env::current_exe()?;
+        let exe_dir = exe.parent().unwrap_or(Path::new("."));
+
+        // On macOS the executable is in `My App.app/Contents/MacOS`
+        if cfg!(target_os = "macos") {
+            if let Some(bundle) = exe_dir
+                .ancestors()
+                .nth(2)
+                .filter(|bundle| bundle.extension().is_some_and(|ext| ext == "app"))
+            {
+                return Ok(bundle.to_path_buf());
+            }
+        }
+
+        Ok(exe_dir.to_path_buf())
+    }
+}
+
+/// Start a new instance of the app with the same arguments and exit the current process
+pub fn relaunch() -> ! {
+    if let Ok(exe) = std::env::current_exe() {
+        let result = std::process::Command::new(exe)
+            .args(std::env::args_os().skip(1))
+            .spawn();
+        if let Err(err) = result {
+            tracing::error!("Failed to relaunch the app: {err}");
+        }
+    }
+    std::process::exit(0)
+}
@@ -0,0 +1,134 @@
+//! Tests for creating, signing and applying updates.
+use dioxus_updater::*;
+use std::{fs, path::Path};
+
+fn write(dir: &Path, file: &str, contents: &str) {
+    let path = dir.join(file);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+fn read(dir: &Path, file: &str) -> Option<String> {
+    fs::read_to_string(dir.join(file)).ok()
+}
+
+#[test]
+fn signatures() {
+    let keys = KeyPair::generate().unwrap();
+    let signature = sign(b"archive", &keys.private_key).unwrap();
+    verify(b"archive", &signature, &keys.public_key).unwrap();
+
+    assert!(matches!(
+        verify(b"tampered", &signature, &keys.public_key),
+        Err(Error::InvalidSignature)
+    ));
+    let other = KeyPair::generate().unwrap();
+    assert!(matches!(
+        verify(b"archive", &signature, &other.public_key),
+        Err(Error::InvalidSignature)
+    ));
+    assert!(matches!(
+        sign(b"archive", "not a key"),
+        Err(Error::InvalidKey(_))
+    ));
+}
+
+#[test]
+fn finds_updates() {
+    let mut manifest = UpdateManifest::new("1.1.0");
+    manifest.platforms.insert(
+        "linux-x86_64".to_string(),
+        PlatformUpdate {
+            url: "app-1.1.0.tar.gz".to_string(),
+            signature: "full".to_string(),
+            size: 100,
+            deltas: vec![DeltaUpdate {
+                from: "1.0.0".to_string(),
+                url: "app-1.0.0-1.1.0.delta.tar.gz".to_string(),
+                signature: "delta".to_string(),
+                size: 10,
+            }],
+        },
+    );
+
+    // The manifest round trips through json
+    let json = serde_json::to_string(&manifest).unwrap();
+    assert_eq!(
+        serde_json::from_str::<UpdateManifest>(&json).unwrap(),
+        manifest
+    );
+
+    let update = manifest
+        .update_for("1.0.0", "linux-x86_64")
+        .unwrap()
+        .unwrap();
+    assert!(update.delta);
+    assert_eq!(update.signature, "delta");
+
+    let update = manifest
+        .update_for("v0.9.0", "linux-x86_64")
+        .unwrap()
+        .unwrap();
+    assert!(!update.delta);
+    assert_eq!(update.url, "app-1.1.0.tar.gz");
+
+    assert_eq!(manifest.update_for("1.1.0", "linux-x86_64").unwrap(), None);
+    assert_eq!(
+        manifest.update_for("1.0.0", "windows-x86_64").unwrap(),
+        None
+    );
+    assert!(manifest.update_for("latest", "linux-x86_64").is_err());
+}
+
+#[test]
+fn applies_full_and_delta_archives() {
+    let old = tempfile::tempdir().unwrap();
+    write(old.path(), "app", "old app");
+    write(old.path(), "assets/style.css", "body {}");
+    write(old.path(), "assets/removed.png", "png");
+
+    let new = tempfile::tempdir().unwrap();
+    write(new.path(), "app", "new app");
+    write(new.path(), "assets/style.css", "body {}");
+    write(new.path(), "assets/added.js", "js");
+
+    // Install the old version from a full archive
+    let install = tempfile::tempdir().unwrap();
+    apply_archive(&create_archive(old.path()).unwrap(), install.path()).unwrap();
+    assert_eq!(read(install.path(), "app").as_deref(), Some("old app"));
+    assert_eq!(
+        read(install.path(), "assets/removed.png").as_deref(),
+        Some("png")
+    );
+
+    // The delta only contains the changed files and the list of removed files
+    let delta = create_delta_archive(old.path(), new.path()).unwrap();
+    let changed = tempfile::tempdir().unwrap();
+    apply_archive(&delta, changed.path()).unwrap();
+    assert_eq!(read(changed.path(), "app").as_deref(), Some("new app"));
+    assert_eq!(
+        read(changed.path(), "assets/added.js").as_deref(),
+        Some("js")
+    );
+    assert_eq!(read(changed.path(), "assets/style.css"), None);
+
+    apply_archive(&delta, install.path()).unwrap();
+    assert_eq!(read(install.path(), "app").as_deref(), Some("new app"));
+    assert_eq!(
+        read(install.path(), "assets/style.css").as_deref(),
+        Some("body {}")
+    );
+    assert_eq!(
+        read(install.path(), "assets/added.js").as_deref(),
+        Some("js")
+    );
+    assert_eq!(read(install.path(), "assets/removed.png"), None);
+
+    // Nothing is left behind after installing
+    let mut entries = fs::read_dir(install.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(entries, ["app", "assets"]);
+}
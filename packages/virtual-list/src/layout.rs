@@ -0,0 +1,121 @@
+use std::ops::Range;
+
+/// Tracks the size of every item in a virtualized list.
+///
+/// Items that haven't been measured yet use the estimated size. Sizes are measured along the scroll axis in pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualLayout {
+    estimated_size: f64,
+    sizes: Vec<Option<f64>>,
+}
+
+/// The items of a [`VirtualLayout`] that should be rendered and the space taken up by the items around them
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisibleRange {
+    /// The indexes of the items to render
+    pub items: Range<usize>,
+    /// The size of all of the items before the rendered items
+    pub offset_before: f64,
+    /// The size of all of the items after the rendered items
+    pub offset_after: f64,
+}
+
+impl VirtualLayout {
+    /// Create a layout for `len` items that are all estimated to be `estimated_size` pixels
+    pub fn new(len: usize, estimated_size: f64) -> Self {
+        Self {
+            estimated_size,
+            sizes: vec![None; len],
+        }
+    }
+
+    /// The number of items in the layout
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// Check if the layout doesn't have any items
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_empty()
+    }
+
+    /// Change the number of items in the layout. Measurements of items that are still in the layout are kept
+    pub fn set_len(&mut self, len: usize) {
+        self.sizes.resize(len, None);
+    }
+
+    /// Change the size used for items that haven't been measured
+    pub fn set_estimated_size(&mut self, estimated_size: f64) {
+        self.estimated_size = estimated_size;
+    }
+
+    /// Record the measured size of an item. Returns `true` if the size changed
+    pub fn set_size(&mut self, index: usize, size: f64) -> bool {
+        match self.sizes.get_mut(index) {
+            Some(slot) if *slot != Some(size) => {
+                *slot = Some(size);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the measured or estimated size of an item
+    pub fn size(&self, index: usize) -> f64 {
+        self.sizes
+            .get(index)
+            .copied()
+            .flatten()
+            .unwrap_or(self.estimated_size)
+    }
+
+    /// Get the distance from the start of the list to the start of an item
+    pub fn offset(&self, index: usize) -> f64 {
+        (0..index.min(self.len())).map(|i| self.size(i)).sum()
+    }
+
+    /// Get the size of every item in the list combined
+    pub fn total_size(&self) -> f64 {
+        self.offset(self.len())
+    }
+
+    /// Find the items that are visible in a viewport of `viewport_size` pixels scrolled `scroll_offset` pixels from the
+    /// start of the list, plus `overscan` items on either side
+    pub fn visible_range(
+        &self,
+        scroll_offset: f64,
+        viewport_size: f64,
+        overscan: usize,
+    ) -> VisibleRange {
+        let scroll_offset = scroll_offset.max(0.0);
+        let viewport_end = scroll_offset + viewport_size.max(0.0);
+
+        let mut start = None;
+        let mut end = self.len();
+        let mut offset = 0.0;
+        for index in 0..self.len() {
+            let size = self.size(index);
+            if start.is_none() && offset + size > scroll_offset {
+                start = Some(index);
+            }
+            if offset >= viewport_end {
+                end = index;
+                break;
+            }
+            offset += size;
+        }
+        // If the list is scrolled past the end, show the last items
+        let start = start.unwrap_or(self.len()).min(end);
+
+        let start = start.saturating_sub(overscan);
+        let end = (end + overscan).min(self.len()).max(start);
+        let offset_before = self.offset(start);
+        let offset_after = self.total_size() - self.offset(end);
+
+        VisibleRange {
+            items: start..end,
+            offset_before,
+            offset_after: offset_after.max(0.0),
+        }
+    }
+}
@@ -0,0 +1,231 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+use dioxus_lib::prelude::*;
+
+mod layout;
+pub use layout::*;
+
+/// The height used for the viewport until the container is measured
+const DEFAULT_VIEWPORT_HEIGHT: f64 = 800.0;
+
+/// The properties for a [`VirtualList`].
+#[derive(Props, Clone, PartialEq)]
+pub struct VirtualListProps {
+    /// The number of items in the list
+    pub len: usize,
+
+    /// The height of items in pixels before they are measured. Defaults to 40 pixels.
+    #[props(default = 40.0)]
+    pub estimated_item_height: f64,
+
+    /// The number of items to render above and below the visible items. Defaults to 3.
+    #[props(default = 3)]
+    pub overscan: usize,
+
+    /// Render the item at an index
+    pub render_item: Callback<usize, Element>,
+
+    /// A header that stays at the top of the list while it scrolls
+    pub header: Option<Element>,
+
+    /// Extra attributes for the scroll container. The container needs a fixed height to scroll.
+    #[props(extends = GlobalAttributes)]
+    pub attributes: Vec<Attribute>,
+}
+
+/// A scrollable list that only renders the items that are visible.
+///
+/// Items can have different heights. Each rendered item is measured when it is mounted or resized, and items that
+/// haven't been measured yet use [`VirtualListProps::estimated_item_height`]. Rendered items are keyed by their index.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_virtual_list::VirtualList;
+///
+/// fn App() -> Element {
+///     let rows = use_signal(|| (0..10_000).map(|i| format!("Row {i}")).collect::<Vec<_>>());
+///     rsx! {
+///         VirtualList {
+///             style: "height: 400px;",
+///             len: rows.len(),
+///             estimated_item_height: 24.0,
+///             header: rsx! { strong { "Rows" } },
+///             render_item: move |index: usize| rsx! { div { "{rows.read()[index]}" } },
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn VirtualList(props: VirtualListProps) -> Element {
+    let mut layout =
+        use_hook(|| CopyValue::new(VirtualLayout::new(props.len, props.estimated_item_height)));
+    // Incremented whenever a measurement changes the layout
+    let mut revision = use_signal(|| 0usize);
+    let mut container = use_signal(|| None::<std::rc::Rc<MountedData>>);
+    let mut scroll_offset = use_signal(|| 0.0);
+    let mut viewport_height = use_signal(|| DEFAULT_VIEWPORT_HEIGHT);
+    let mut header_height = use_signal(|| 0.0);
+
+    {
+        let mut layout = layout.write();
+        layout.set_len(props.len);
+        layout.set_estimated_size(props.estimated_item_height);
+    }
+
+    revision.read();
+    let VisibleRange {
+        items,
+        offset_before,
+        offset_after,
+    } = layout.read().visible_range(
+        scroll_offset() - header_height(),
+        viewport_height(),
+        props.overscan,
+    );
+
+    let onscroll = move |_| async move {
+        let Some(container) = container.cloned() else {
+            return;
+        };
+        match container.get_scroll_offset().await {
+            Ok(offset) => scroll_offset.set(offset.y),
+            Err(err) => {
+                tracing::error!("Failed to read the scroll offset of a virtual list: {err}")
+            }
+        }
+    };
+
+    let render_item = props.render_item;
+
+    rsx! {
+        div {
+            style: "overflow-y: auto;",
+            onmounted: move |event| container.set(Some(event.data())),
+            onscroll,
+            onresize: move |event| {
+                if let Ok(size) = event.get_content_box_size() {
+                    viewport_height.set(size.height);
+                }
+            },
+            ..props.attributes,
+            if let Some(header) = props.header {
+                div {
+                    style: "position: sticky; top: 0; z-index: 1;",
+                    onresize: move |event| {
+                        if let Ok(size) = event.get_border_box_size() {
+                            header_height.set(size.height);
+                        }
+                    },
+                    {header}
+                }
+            }
+            div { style: "height: {offset_before}px;" }
+            for index in items {
+                div {
+                    key: "{index}",
+                    onresize: move |event| {
+                        if let Ok(size) = event.get_border_box_size() {
+                            if layout.write().set_size(index, size.height) {
+                                revision += 1;
+                            }
+                        }
+                    },
+                    {render_item(index)}
+                }
+            }
+            div { style: "height: {offset_after}px;" }
+        }
+    }
+}
+
+/// The properties for a [`VirtualGrid`].
+#[derive(Props, Clone, PartialEq)]
+pub struct VirtualGridProps {
+    /// The number of items in the grid
+    pub len: usize,
+
+    /// The number of items in each row
+    pub columns: usize,
+
+    /// The height of rows in pixels before they are measured. Defaults to 40 pixels.
+    #[props(default = 40.0)]
+    pub estimated_row_height: f64,
+
+    /// The space between rows and columns in pixels
+    #[props(default)]
+    pub gap: f64,
+
+    /// The number of rows to render above and below the visible rows. Defaults to 3.
+    #[props(default = 3)]
+    pub overscan: usize,
+
+    /// Render the item at an index
+    pub render_item: Callback<usize, Element>,
+
+    /// A header that stays at the top of the grid while it scrolls
+    pub header: Option<Element>,
+
+    /// Extra attributes for the scroll container. The container needs a fixed height to scroll.
+    #[props(extends = GlobalAttributes)]
+    pub attributes: Vec<Attribute>,
+}
+
+/// A scrollable grid that only renders the rows that are visible.
+///
+/// Items are laid out in rows of [`VirtualGridProps::columns`] equally sized columns. Rows can have different
+/// heights and are measured the same way as the items of a [`VirtualList`].
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_virtual_list::VirtualGrid;
+///
+/// fn App() -> Element {
+///     rsx! {
+///         VirtualGrid {
+///             style: "height: 600px;",
+///             len: 100_000,
+///             columns: 4,
+///             gap: 8.0,
+///             render_item: move |index: usize| rsx! { div { class: "card", "Card {index}" } },
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn VirtualGrid(props: VirtualGridProps) -> Element {
+    let columns = props.columns.max(1);
+    let len = props.len;
+    let gap = props.gap;
+    let render_item = props.render_item;
+
+    // A new callback every render makes sure the list re-renders the rows when the grid changes
+    let render_row = Callback::new(move |row: usize| {
+        let cells = row * columns..((row + 1) * columns).min(len);
+        rsx! {
+            div {
+                style: "display: grid; grid-template-columns: repeat({columns}, minmax(0, 1fr)); column-gap: {gap}px; padding-bottom: {gap}px;",
+                for index in cells {
+                    Fragment { key: "{index}", {render_item(index)} }
+                }
+            }
+        }
+    });
+
+    let list = VirtualListProps {
+        len: len.div_ceil(columns),
+        estimated_item_height: props.estimated_row_height + gap,
+        overscan: props.overscan,
+        render_item: render_row,
+        header: props.header,
+        attributes: props.attributes,
+    };
+
+    rsx! {
+        VirtualList { ..list }
+    }
+}
@@ -0,0 +1,56 @@
+use dioxus_virtual_list::{VirtualLayout, VisibleRange};
+
+#[test]
+fn estimated_sizes() {
+    let layout = VirtualLayout::new(100, 10.0);
+    assert_eq!(layout.total_size(), 1000.0);
+    assert_eq!(
+        layout.visible_range(0.0, 35.0, 0),
+        VisibleRange {
+            items: 0..4,
+            offset_before: 0.0,
+            offset_after: 960.0,
+        }
+    );
+    assert_eq!(
+        layout.visible_range(105.0, 20.0, 2),
+        VisibleRange {
+            items: 8..15,
+            offset_before: 80.0,
+            offset_after: 850.0,
+        }
+    );
+}
+
+#[test]
+fn measured_sizes() {
+    let mut layout = VirtualLayout::new(10, 10.0);
+    assert!(layout.set_size(0, 50.0));
+    assert!(!layout.set_size(0, 50.0));
+    // Items outside of the layout are ignored
+    assert!(!layout.set_size(10, 50.0));
+
+    assert_eq!(layout.offset(1), 50.0);
+    assert_eq!(layout.total_size(), 140.0);
+    assert_eq!(layout.visible_range(45.0, 20.0, 0).items, 0..3);
+    assert_eq!(layout.visible_range(50.0, 20.0, 0).items, 1..3);
+}
+
+#[test]
+fn scrolled_past_the_end() {
+    let layout = VirtualLayout::new(10, 10.0);
+    let range = layout.visible_range(500.0, 50.0, 2);
+    assert_eq!(range.items, 8..10);
+    assert_eq!(range.offset_after, 0.0);
+}
+
+#[test]
+fn changing_the_length_keeps_measurements() {
+    let mut layout = VirtualLayout::new(3, 10.0);
+    layout.set_size(1, 30.0);
+    layout.set_len(5);
+    assert_eq!(layout.size(1), 30.0);
+    assert_eq!(layout.total_size(), 70.0);
+    layout.set_len(1);
+    assert_eq!(layout.visible_range(0.0, 100.0, 3).items, 0..1);
+}
@@ -13,6 +13,12 @@ use wasm_bindgen::JsCast as _;
 pub struct Config {
     pub(crate) hydrate: bool,
     pub(crate) root: ConfigRoot,
+    #[cfg(feature = "hydrate")]
+    pub(crate) hydration_data: Option<Vec<u8>>,
+    #[cfg(feature = "hydrate")]
+    pub(crate) hydration_mismatch_limit: usize,
+    #[cfg(feature = "hydrate")]
+    pub(crate) hydration_mismatch_handler: Option<crate::HydrationMismatchHandler>,
 }
 
 impl LaunchConfig for Config {}
@@ -42,6 +48,49 @@ impl Config {
         self
     }
 
+    #[cfg(feature = "hydrate")]
+    /// Set the serialized server data used to hydrate the app.
+    ///
+    /// By default, the data is read from the `initial_dioxus_hydration_data` global the server writes into the page.
+    /// This is useful when several apps are hydrated on the same page, like islands, where each app has its own data.
+    pub fn hydration_data(mut self, data: Vec<u8>) -> Self {
+        self.hydration_data = Some(data);
+        self
+    }
+
+    #[cfg(feature = "hydrate")]
+    /// Set the maximum number of hydration mismatches that are reported each time part of the page is hydrated.
+    ///
+    /// Hydration mismatches are only checked in debug builds. Defaults to 10.
+    pub fn hydration_mismatch_limit(mut self, limit: usize) -> Self {
+        self.hydration_mismatch_limit = limit;
+        self
+    }
+
+    #[cfg(feature = "hydrate")]
+    /// Register a callback that is called with every hydration mismatch found while hydrating part of the page.
+    ///
+    /// Hydration mismatches are always logged to the console in debug builds. This callback lets you forward them
+    /// to your own reporting. It is never called in release builds.
+    ///
+    /// ```rust, ignore
+    /// dioxus_web::launch::launch_cfg(
+    ///     app,
+    ///     Config::new().hydrate(true).on_hydration_mismatch(|mismatches| {
+    ///         for mismatch in mismatches {
+    ///             tracing::warn!("{mismatch}");
+    ///         }
+    ///     }),
+    /// );
+    /// ```
+    pub fn on_hydration_mismatch(
+        mut self,
+        handler: impl Fn(&[crate::HydrationMismatch]) + 'static,
+    ) -> Self {
+        self.hydration_mismatch_handler = Some(std::rc::Rc::new(handler));
+        self
+    }
+
     /// Set the name of the element that Dioxus will use as the root.
     ///
     /// This is akin to calling React.render() on the element with the specified name.
@@ -74,6 +123,12 @@ impl Default for Config {
         Self {
             hydrate: false,
             root: ConfigRoot::RootName("main".to_string()),
+            #[cfg(feature = "hydrate")]
+            hydration_data: None,
+            #[cfg(feature = "hydrate")]
+            hydration_mismatch_limit: crate::DEFAULT_MISMATCH_LIMIT,
+            #[cfg(feature = "hydrate")]
+            hydration_mismatch_handler: None,
         }
     }
 }
@@ -0,0 +1,274 @@
+//! Export Dioxus apps as custom elements that plain html and other frameworks can render.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    str::FromStr,
+};
+
+use dioxus_core::prelude::*;
+use dioxus_signals::{Readable, Signal, Writable};
+use futures_util::future::{abortable, AbortHandle};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+use crate::Config;
+
+/// The property of the host element that holds the id of the app rendered into it
+const INSTANCE_KEY: &str = "__dioxusCustomElement";
+
+/// An app that is rendered into a connected custom element
+struct Instance {
+    runtime: Rc<Runtime>,
+    attributes: Signal<HashMap<String, String>>,
+    abort: AbortHandle,
+}
+
+thread_local! {
+    static INSTANCES: RefCell<HashMap<u32, Instance>> = RefCell::new(HashMap::new());
+    static NEXT_INSTANCE: Cell<u32> = const { Cell::new(0) };
+}
+
+/// The custom element an app registered with [`register_custom_element`] renders into. Get it with
+/// [`use_custom_element`].
+#[derive(Clone)]
+pub struct CustomElement {
+    host: web_sys::HtmlElement,
+    attributes: Signal<HashMap<String, String>>,
+}
+
+impl CustomElement {
+    /// The custom element in the page. Dispatch events on the host to send them to the page.
+    pub fn host(&self) -> &web_sys::HtmlElement {
+        &self.host
+    }
+
+    /// Read an attribute of the custom element. Reading an attribute subscribes the current component to changes of
+    /// the attributes that are observed.
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        self.attributes.read().get(name).cloned()
+    }
+
+    /// Parse an attribute of the custom element. Missing attributes and attributes that fail to parse use the
+    /// default value.
+    pub fn parse<T: FromStr + Default>(&self, name: &str) -> T {
+        self.attributes
+            .read()
+            .get(name)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Get the custom element the app renders into.
+///
+/// # Panics
+///
+/// Panics if the app was not registered with [`register_custom_element`] or the [`custom_element!`](crate::custom_element) macro.
+pub fn use_custom_element() -> CustomElement {
+    use_hook(|| {
+        try_consume_context::<CustomElement>()
+            .expect("use_custom_element can only be used in apps registered as custom elements")
+    })
+}
+
+/// Register a custom element that renders the app into its shadow root. Every element with the tag in the page runs
+/// its own copy of the app while it is connected to the document.
+///
+/// Changes to the observed attributes re-render the components that read them with [`CustomElement::attribute`].
+/// Children of the element are projected into `slot` elements the app renders.
+///
+/// ```rust, ignore
+/// fn app() -> Element {
+///     let element = dioxus_web::use_custom_element();
+///     let name = element.attribute("name").unwrap_or_default();
+///     rsx! {
+///         h1 { "Hello {name}" }
+///         slot {}
+///     }
+/// }
+///
+/// dioxus_web::register_custom_element("hello-name", &["name"], app).unwrap();
+/// ```
+pub fn register_custom_element(
+    tag: &str,
+    observed_attributes: &[&str],
+    app: fn() -> Element,
+) -> Result<(), JsValue> {
+    // Web workers load the same module to run background tasks. There is no document to render into there.
+    let Some(window) = web_sys::window() else {
+        return Ok(());
+    };
+
+    let connect = Closure::<dyn Fn(web_sys::HtmlElement)>::new(move |host| {
+        if let Err(err) = connect(host, app) {
+            web_sys::console::error_2(&"Failed to render the custom element:".into(), &err);
+        }
+    });
+    let disconnect = Closure::<dyn Fn(web_sys::HtmlElement)>::new(disconnect);
+    let changed =
+        Closure::<dyn Fn(web_sys::HtmlElement, String, Option<String>)>::new(attribute_changed);
+    let observed: js_sys::Array = observed_attributes
+        .iter()
+        .map(|name| JsValue::from_str(name))
+        .collect();
+
+    let create_class = js_sys::Function::new_with_args(
+        "connect, disconnect, changed, observed",
+        r#"
+            return class extends HTMLElement {
+                static get observedAttributes() { return observed; }
+                connectedCallback() { connect(this); }
+                disconnectedCallback() { disconnect(this); }
+                attributeChangedCallback(name, _, value) { changed(this, name, value); }
+            };
+        "#,
+    );
+    let class = create_class.apply(
+        &JsValue::NULL,
+        &js_sys::Array::of4(
+            connect.as_ref(),
+            disconnect.as_ref(),
+            changed.as_ref(),
+            &observed,
+        ),
+    )?;
+    window
+        .custom_elements()
+        .define(tag, class.unchecked_ref())?;
+
+    // The class keeps the callbacks for as long as the page is open
+    connect.forget();
+    disconnect.forget();
+    changed.forget();
+
+    Ok(())
+}
+
+fn connect(host: web_sys::HtmlElement, app: fn() -> Element) -> Result<(), JsValue> {
+    // Elements that move in the page are disconnected and connected again, which renders the app from scratch
+    let shadow = match host.shadow_root() {
+        Some(shadow) => {
+            shadow.set_inner_html("");
+            shadow
+        }
+        None => host.attach_shadow(&web_sys::ShadowRootInit::new(web_sys::ShadowRootMode::Open))?,
+    };
+    let document = host.owner_document().unwrap_or_else(crate::load_document);
+    // The interpreter needs an element as the root, so the app renders into a div inside the shadow root
+    let root = document.create_element("div")?;
+    shadow.append_child(&root)?;
+
+    let attributes = host
+        .get_attribute_names()
+        .iter()
+        .filter_map(|name| name.as_string())
+        .filter_map(|name| host.get_attribute(&name).map(|value| (name, value)))
+        .collect();
+
+    let vdom = VirtualDom::new(app);
+    let runtime = vdom.runtime();
+    let attributes = vdom.in_runtime(|| Signal::new_in_scope(attributes, ScopeId::ROOT));
+    vdom.provide_root_context(CustomElement {
+        host: host.clone(),
+        attributes,
+    });
+
+    let (app, abort) = abortable(crate::run(vdom, Config::new().rootelement(root)));
+    wasm_bindgen_futures::spawn_local(async move {
+        _ = app.await;
+    });
+
+    let id = NEXT_INSTANCE.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        id
+    });
+    js_sys::Reflect::set(&host, &INSTANCE_KEY.into(), &id.into())?;
+    INSTANCES.with(|instances| {
+        instances.borrow_mut().insert(
+            id,
+            Instance {
+                runtime,
+                attributes,
+                abort,
+            },
+        )
+    });
+
+    Ok(())
+}
+
+fn disconnect(host: web_sys::HtmlElement) {
+    let Some(id) = instance_id(&host) else {
+        return;
+    };
+    // Stopping the app drops the virtual dom and everything it rendered
+    if let Some(instance) = INSTANCES.with(|instances| instances.borrow_mut().remove(&id)) {
+        instance.abort.abort();
+    }
+}
+
+fn attribute_changed(host: web_sys::HtmlElement, name: String, value: Option<String>) {
+    // The initial attributes are read when the element is connected
+    let Some(id) = instance_id(&host) else {
+        return;
+    };
+    let Some((runtime, mut attributes)) = INSTANCES.with(|instances| {
+        instances
+            .borrow()
+            .get(&id)
+            .map(|instance| (instance.runtime.clone(), instance.attributes))
+    }) else {
+        return;
+    };
+
+    let _guard = RuntimeGuard::new(runtime);
+    match value {
+        Some(value) => attributes.write().insert(name, value),
+        None => attributes.write().remove(&name),
+    };
+}
+
+fn instance_id(host: &web_sys::HtmlElement) -> Option<u32> {
+    js_sys::Reflect::get(host, &INSTANCE_KEY.into())
+        .ok()?
+        .as_f64()
+        .map(|id| id as u32)
+}
+
+/// Register a custom element that renders a component. Every field of the props is read from the attribute with the
+/// same name in kebab case and parsed with [`FromStr`]. Missing attributes and attributes that fail to parse use the
+/// default value of the field.
+///
+/// ```rust, ignore
+/// #[component]
+/// fn Counter(start: i32, step_size: i32) -> Element {
+///     let mut count = use_signal(|| start);
+///     rsx! {
+///         button { onclick: move |_| count += step_size, "{count}" }
+///     }
+/// }
+///
+/// // <my-counter start="10" step-size="2"></my-counter>
+/// dioxus_web::custom_element!("my-counter", Counter { start: i32, step_size: i32 }).unwrap();
+/// ```
+///
+/// The component re-renders with new props when an attribute changes.
+#[macro_export]
+macro_rules! custom_element {
+    ($tag:expr, $component:ident { $($field:ident: $ty:ty),* $(,)? }) => {{
+        fn __custom_element_root() -> $crate::__private::Element {
+            let element = $crate::use_custom_element();
+            $component(
+                $crate::__private::fc_to_builder($component)
+                    $(.$field(element.parse::<$ty>(&stringify!($field).replace('_', "-"))))*
+                    .build(),
+            )
+        }
+        let observed: ::std::vec::Vec<::std::string::String> =
+            ::std::vec![$(stringify!($field).replace('_', "-")),*];
+        let observed: ::std::vec::Vec<&str> = observed.iter().map(|name| name.as_str()).collect();
+        $crate::register_custom_element($tag, &observed, __custom_element_root)
+    }};
+}
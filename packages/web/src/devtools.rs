@@ -3,13 +3,15 @@
 //! This sets up a websocket connection to the devserver and handles messages from it.
 //! We also set up a little recursive timer that will attempt to reconnect if the connection is lost.
 
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::rc::Rc;
+use std::sync::Once;
 use std::time::Duration;
 
 use dioxus_core::prelude::RuntimeGuard;
 use dioxus_core::{Runtime, ScopeId};
-use dioxus_devtools::{DevserverMsg, HotReloadMsg};
+use dioxus_devtools::{ClientMsg, DevserverMsg, ErrorReport, HotReloadMsg};
 use dioxus_document::eval;
 use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use js_sys::JsString;
@@ -25,10 +27,19 @@ const POLL_INTERVAL_SCALE_FACTOR: i32 = 2;
 const TOAST_TIMEOUT: Duration = Duration::from_secs(5);
 const TOAST_TIMEOUT_LONG: Duration = Duration::from_secs(3600); // Duration::MAX is too long for JS.
 
+thread_local! {
+    /// The connection to the devserver that errors are reported to
+    static DEVSERVER: RefCell<Option<WebSocket>> = const { RefCell::new(None) };
+}
+
 pub(crate) fn init(runtime: Rc<Runtime>) -> UnboundedReceiver<HotReloadMsg> {
     // Create the tx/rx pair that we'll use for the top-level future in the dioxus loop
     let (tx, rx) = unbounded();
 
+    // Apps rendered into custom elements share the page, so the panic hook is only installed once
+    static PANIC_HOOK: Once = Once::new();
+    PANIC_HOOK.call_once(|| dioxus_devtools::report_panics(report_error));
+
     // Wire up the websocket to the devserver
     make_ws(runtime, tx.clone(), POLL_INTERVAL_MIN, false);
     playground(tx);
@@ -55,6 +66,7 @@ fn make_ws(
     );
 
     let ws = WebSocket::new(&url).unwrap();
+    DEVSERVER.with(|devserver| *devserver.borrow_mut() = Some(ws.clone()));
 
     // Set the onmessage handler to bounce messages off to the main dioxus loop
     let tx_ = tx.clone();
@@ -182,6 +194,24 @@ fn make_ws(
     }
 }
 
+/// Show a panic or unhandled error in the error overlay and send it to the devserver.
+///
+/// Panics abort the app on the web, so the overlay is drawn without the virtual dom. It stays up until the devserver
+/// reloads the page with a fix or the reload button is pressed.
+pub(crate) fn report_error(report: ErrorReport) {
+    _ = js_sys::eval(&dioxus_devtools::show_error_overlay_script(&report));
+
+    DEVSERVER.with(|devserver| {
+        if let Some(ws) = devserver.borrow().as_ref() {
+            if ws.ready_state() == WebSocket::OPEN {
+                if let Ok(msg) = serde_json::to_string(&ClientMsg::Error(report)) {
+                    _ = ws.send_with_str(&msg);
+                }
+            }
+        }
+    });
+}
+
 /// Represents what color the toast should have.
 enum ToastLevel {
     /// Green
@@ -1,8 +1,10 @@
+use dioxus_clipboard::{Clipboard, EvalClipboard};
 use dioxus_core::prelude::queue_effect;
 use dioxus_core::ScopeId;
 use dioxus_document::{
-    create_element_in_head, Document, Eval, EvalError, Evaluator, LinkProps, MetaProps,
-    ScriptProps, StyleProps,
+    create_element_in_head, insert_element_in_head, remove_element_in_head, Document, Eval,
+    EvalError, Evaluator, HeadElement, HeadElementId, LinkProps, MetaProps, ScriptProps,
+    StyleProps,
 };
 use dioxus_history::History;
 use generational_box::{AnyStorage, GenerationalBox, UnsyncStorage};
@@ -64,7 +66,11 @@ extern "C" {
 pub fn init_document() {
     let provider: Rc<dyn Document> = Rc::new(WebDocument);
     if ScopeId::ROOT.has_context::<Rc<dyn Document>>().is_none() {
-        ScopeId::ROOT.provide_context(provider);
+        ScopeId::ROOT.provide_context(provider.clone());
+    }
+    let clipboard_provider: Rc<dyn Clipboard> = Rc::new(EvalClipboard::new(provider));
+    if ScopeId::ROOT.has_context::<Rc<dyn Clipboard>>().is_none() {
+        ScopeId::ROOT.provide_context(clipboard_provider);
     }
     let history_provider: Rc<dyn History> = Rc::new(WebHistory::default());
     if ScopeId::ROOT.has_context::<Rc<dyn History>>().is_none() {
@@ -127,6 +133,19 @@ impl Document for WebDocument {
             myself.eval(create_element_in_head("link", &props.attributes(), None));
         });
     }
+
+    /// Insert an element a head component rendered into the head
+    fn insert_head_element(&self, element: HeadElement) {
+        let myself = self.clone();
+        queue_effect(move || {
+            myself.eval(insert_element_in_head(&element));
+        });
+    }
+
+    /// Remove an element a head component rendered from the head
+    fn remove_head_element(&self, id: HeadElementId) {
+        self.eval(remove_element_in_head(id));
+    }
 }
 
 /// Required to avoid blocking the Rust WASM thread.
@@ -48,10 +48,20 @@ pub struct WebsysDom {
 
     #[cfg(feature = "hydrate")]
     pub(crate) suspense_hydration_ids: crate::hydration::SuspenseHydrationIds,
+
+    #[cfg(feature = "hydrate")]
+    pub(crate) hydration_mismatch_limit: usize,
+
+    #[cfg(feature = "hydrate")]
+    pub(crate) hydration_mismatch_handler: Option<crate::HydrationMismatchHandler>,
 }
 
 impl WebsysDom {
     pub fn new(cfg: Config, runtime: Rc<Runtime>) -> Self {
+        #[cfg(feature = "hydrate")]
+        let hydration_mismatch_limit = cfg.hydration_mismatch_limit;
+        #[cfg(feature = "hydrate")]
+        let hydration_mismatch_handler = cfg.hydration_mismatch_handler;
         let (document, root) = match cfg.root {
             crate::cfg::ConfigRoot::RootName(rootname) => {
                 // eventually, we just want to let the interpreter do all the work of decoding events into our event type
@@ -110,6 +120,11 @@ impl WebsysDom {
                 } else if prevent_default {
                     web_sys_event.prevent_default();
                 }
+
+                // Stop the browser event as well so listeners outside of the app see the same propagation as dioxus listeners
+                if event.propagation_stopped() {
+                    web_sys_event.stop_propagation();
+                }
             }
         }));
 
@@ -118,6 +133,10 @@ impl WebsysDom {
             root.clone().unchecked_into(),
             handler.as_ref().unchecked_ref(),
         );
+        // Expose the interpreter like the desktop and liveview renderers so scripts can look up nodes by id
+        if let Some(window) = web_sys::window() {
+            _ = js_sys::Reflect::set(&window, &"interpreter".into(), _interpreter);
+        }
 
         dioxus_html::set_event_converter(Box::new(WebEventConverter));
         handler.forget();
@@ -135,6 +154,10 @@ impl WebsysDom {
             skip_mutations: false,
             #[cfg(feature = "hydrate")]
             suspense_hydration_ids: Default::default(),
+            #[cfg(feature = "hydrate")]
+            hydration_mismatch_limit,
+            #[cfg(feature = "hydrate")]
+            hydration_mismatch_handler,
         }
     }
 }
@@ -0,0 +1,28 @@
+use super::{Synthetic, WebEventExt};
+use dioxus_html::HasCustomEventData;
+use wasm_bindgen::JsCast;
+
+impl HasCustomEventData for Synthetic<web_sys::Event> {
+    fn detail_json(&self) -> Option<String> {
+        // Events that are not custom events don't have a detail
+        let event = self.event.dyn_ref::<web_sys::CustomEvent>()?;
+        let detail = event.detail();
+        if detail.is_undefined() {
+            return None;
+        }
+        js_sys::JSON::stringify(&detail).ok()?.as_string()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        &self.event
+    }
+}
+
+impl WebEventExt for dioxus_html::CustomEventData {
+    type WebEvent = web_sys::Event;
+
+    #[inline(always)]
+    fn try_as_web_event(&self) -> Option<Self::WebEvent> {
+        self.downcast::<web_sys::Event>().cloned()
+    }
+}
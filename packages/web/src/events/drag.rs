@@ -6,7 +6,7 @@ use dioxus_html::{
         InteractionElementOffset, InteractionLocation, Modifiers, ModifiersInteraction,
         PointerInteraction,
     },
-    HasDragData, HasFileData, HasMouseData,
+    DataTransfer, DataTransferItem, HasDragData, HasFileData, HasMouseData,
 };
 use web_sys::DragEvent;
 
@@ -71,6 +71,37 @@ impl HasDragData for Synthetic<DragEvent> {
     fn as_any(&self) -> &dyn std::any::Any {
         &self.event
     }
+
+    fn data_transfer(&self) -> DataTransfer {
+        let Some(transfer) = self.event.data_transfer() else {
+            return DataTransfer::default();
+        };
+        let items = transfer
+            .types()
+            .iter()
+            .filter_map(|mime_type| mime_type.as_string())
+            .map(|mime_type| {
+                // Dragged files are listed under the "Files" type
+                if mime_type == "Files" {
+                    return DataTransferItem {
+                        kind: "file".to_string(),
+                        mime_type,
+                        data: None,
+                    };
+                }
+                DataTransferItem {
+                    kind: "string".to_string(),
+                    data: transfer.get_data(&mime_type).ok(),
+                    mime_type,
+                }
+            })
+            .collect();
+        DataTransfer {
+            drop_effect: transfer.drop_effect(),
+            effect_allowed: transfer.effect_allowed(),
+            items,
+        }
+    }
 }
 
 impl HasFileData for Synthetic<DragEvent> {
@@ -9,6 +9,7 @@ use web_sys::{Document, Element, Event};
 mod animation;
 mod clipboard;
 mod composition;
+mod custom;
 mod drag;
 mod file;
 mod focus;
@@ -75,6 +76,14 @@ impl HtmlEventConverter for WebEventConverter {
         Synthetic::<web_sys::CompositionEvent>::from(downcast_event(event).raw.clone()).into()
     }
 
+    #[inline(always)]
+    fn convert_custom_data(
+        &self,
+        event: &dioxus_html::PlatformEventData,
+    ) -> dioxus_html::CustomEventData {
+        Synthetic::new(downcast_event(event).raw.clone()).into()
+    }
+
     #[inline(always)]
     fn convert_drag_data(&self, event: &dioxus_html::PlatformEventData) -> dioxus_html::DragData {
         let event = downcast_event(event);
@@ -113,6 +113,19 @@ impl dioxus_html::RenderedElementBacking for Synthetic<web_sys::Element> {
             });
         Box::pin(async { result })
     }
+
+    fn set_focus_trap(
+        &self,
+        trap: bool,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = dioxus_html::MountedResult<()>>>> {
+        let node = self.event.clone().into();
+        if trap {
+            dioxus_interpreter_js::minimal_bindings::trapFocus(node);
+        } else {
+            dioxus_interpreter_js::minimal_bindings::releaseFocusTrap(node);
+        }
+        Box::pin(async { Ok(()) })
+    }
 }
 
 impl WebEventExt for MountedData {
@@ -4,7 +4,7 @@ use dioxus_html::FileEngine;
 use futures_channel::oneshot;
 use js_sys::Uint8Array;
 use wasm_bindgen::{prelude::Closure, JsCast};
-use web_sys::{File, FileList, FileReader};
+use web_sys::{Blob, File, FileList, FileReader};
 
 /// A file engine for the web platform
 pub struct WebFileEngine {
@@ -34,25 +34,8 @@ impl WebFileEngine {
             .filter_map(|i| self.get(i))
             .find(|f| f.name() == name)
     }
-}
-
-#[async_trait::async_trait(?Send)]
-impl FileEngine for WebFileEngine {
-    fn files(&self) -> Vec<String> {
-        (0..self.len())
-            .filter_map(|i| self.get(i).map(|f| f.name()))
-            .collect()
-    }
-
-    async fn file_size(&self, file: &str) -> Option<u64> {
-        let file = self.find(file)?;
-        Some(file.size() as u64)
-    }
-
-    // read a file to bytes
-    async fn read_file(&self, file: &str) -> Option<Vec<u8>> {
-        let file = self.find(file)?;
 
+    async fn read_blob(&self, blob: &Blob) -> Option<Vec<u8>> {
         let file_reader = self.file_reader.clone();
         let (rx, tx) = oneshot::channel();
         let on_load: Closure<dyn FnMut()> = Closure::new({
@@ -69,7 +52,7 @@ impl FileEngine for WebFileEngine {
         self.file_reader
             .set_onload(Some(on_load.as_ref().unchecked_ref()));
         on_load.forget();
-        self.file_reader.read_as_array_buffer(&file).ok()?;
+        self.file_reader.read_as_array_buffer(blob).ok()?;
 
         if let Ok(Ok(js_val)) = tx.await {
             let as_u8_arr = Uint8Array::new(&js_val);
@@ -80,6 +63,33 @@ impl FileEngine for WebFileEngine {
             None
         }
     }
+}
+
+#[async_trait::async_trait(?Send)]
+impl FileEngine for WebFileEngine {
+    fn files(&self) -> Vec<String> {
+        (0..self.len())
+            .filter_map(|i| self.get(i).map(|f| f.name()))
+            .collect()
+    }
+
+    async fn file_size(&self, file: &str) -> Option<u64> {
+        let file = self.find(file)?;
+        Some(file.size() as u64)
+    }
+
+    // read a file to bytes
+    async fn read_file(&self, file: &str) -> Option<Vec<u8>> {
+        let file = self.find(file)?;
+        self.read_blob(&file).await
+    }
+
+    // read part of a file to bytes without reading the rest of the file
+    async fn read_file_range(&self, file: &str, start: u64, end: u64) -> Option<Vec<u8>> {
+        let file = self.find(file)?;
+        let blob = file.slice_with_f64_and_f64(start as f64, end as f64).ok()?;
+        self.read_blob(&blob).await
+    }
 
     // read a file to string
     async fn read_file_to_string(&self, file: &str) -> Option<String> {
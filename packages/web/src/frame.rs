@@ -0,0 +1,26 @@
+use dioxus_core::{FrameSource, IntervalFrames};
+use std::{future::Future, pin::Pin};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// Frames that follow the paint loop of the browser. The browser stops producing frames while the page is hidden, so
+/// animations and other per frame work pause in background tabs.
+pub(crate) struct AnimationFrames;
+
+impl FrameSource for AnimationFrames {
+    fn next_frame(&self) -> Pin<Box<dyn Future<Output = ()>>> {
+        let Some(window) = web_sys::window() else {
+            return IntervalFrames.next_frame();
+        };
+
+        let (tx, rx) = futures_channel::oneshot::channel();
+        let callback = Closure::once_into_js(move || _ = tx.send(()));
+        if let Err(err) = window.request_animation_frame(callback.unchecked_ref()) {
+            tracing::error!("Failed to request an animation frame: {err:?}");
+            return IntervalFrames.next_frame();
+        }
+
+        Box::pin(async move {
+            _ = rx.await;
+        })
+    }
+}
@@ -15,7 +15,9 @@ fn base_path() -> Option<String> {
 #[allow(clippy::extra_unused_type_parameters)]
 fn update_scroll(window: &Window, history: &History) {
     let scroll = ScrollPosition::of_window(window);
-    if let Err(err) = replace_state_with_url(history, &[scroll.x, scroll.y], None) {
+    let state = get_current_state(history);
+    if let Err(err) = replace_state_with_url(history, &[scroll.x, scroll.y], state.as_deref(), None)
+    {
         web_sys::console::error_1(&err);
     }
 }
@@ -32,10 +34,20 @@ fn update_scroll(window: &Window, history: &History) {
 ///
 /// Application developers are responsible for not rendering the router if the prefix is not present
 /// in the URL. Otherwise, if a router navigation is triggered, the prefix will be added.
+///
+/// # Hash routing
+/// A [`WebHistory`] created with [`WebHistory::hash`] stores the route in the hash of the URL
+/// (`/index.html#/blog/1`) instead of the path. This works with static file hosts that can't serve
+/// the app for every path.
+///
+/// # State
+/// The state passed to [`History::push_with_state`](dioxus_history::History::push_with_state) is
+/// stored in the state of the browser history entry, next to the saved scroll position.
 pub struct WebHistory {
     do_scroll_restoration: bool,
     history: History,
     prefix: Option<String>,
+    hash_routing: bool,
     window: Window,
 }
 
@@ -51,16 +63,35 @@ impl WebHistory {
     /// If `do_scroll_restoration` is [`true`], [`WebHistory`] will take control of the history
     /// state. It'll also set the browsers scroll restoration to `manual`.
     pub fn new(prefix: Option<String>, do_scroll_restoration: bool) -> Self {
-        let myself = Self::new_inner(prefix, do_scroll_restoration);
+        Self::new_inner(prefix, do_scroll_restoration).init()
+    }
 
-        let current_route = dioxus_history::History::current_route(&myself);
-        let current_route_str = current_route.to_string();
-        let prefix_str = myself.prefix.as_deref().unwrap_or("");
-        let current_url = format!("{prefix_str}{current_route_str}");
-        let state = myself.create_state();
-        let _ = replace_state_with_url(&myself.history, &state, Some(&current_url));
+    /// Create a new [`WebHistory`] that stores the route in the hash of the URL.
+    ///
+    /// Links rendered by the router point to the hash of the current page, like `#/blog/1`.
+    pub fn hash(do_scroll_restoration: bool) -> Self {
+        Self {
+            prefix: Some("#".to_string()),
+            hash_routing: true,
+            ..Self::new_inner(None, do_scroll_restoration)
+        }
+        .init()
+    }
 
-        myself
+    /// Write the current route back to the URL so the history entry has the state of this history
+    fn init(self) -> Self {
+        let current_route = dioxus_history::History::current_route(&self);
+        let current_url = self.full_path(&current_route);
+        let position = self.create_state();
+        let state = get_current_state(&self.history);
+        let _ = replace_state_with_url(
+            &self.history,
+            &position,
+            state.as_deref(),
+            Some(&current_url),
+        );
+
+        self
     }
 
     fn new_inner(prefix: Option<String>, do_scroll_restoration: bool) -> Self {
@@ -88,6 +119,7 @@ impl WebHistory {
             do_scroll_restoration,
             history,
             prefix,
+            hash_routing: false,
             window,
         }
     }
@@ -107,6 +139,14 @@ impl WebHistory {
 impl WebHistory {
     fn route_from_location(&self) -> String {
         let location = self.window.location();
+        if self.hash_routing {
+            let hash = location.hash().unwrap_or_default();
+            let route = hash.strip_prefix('#').unwrap_or(&hash);
+            return match route.is_empty() {
+                true => "/".to_string(),
+                false => route.to_string(),
+            };
+        }
         let path = location.pathname().unwrap_or_else(|_| "/".into())
             + &location.search().unwrap_or("".into())
             + &location.hash().unwrap_or("".into());
@@ -129,16 +169,43 @@ impl WebHistory {
     }
 
     fn handle_nav(&self, result: Result<(), JsValue>) {
-        match result {
-            Ok(_) => {
-                if self.do_scroll_restoration {
-                    self.window.scroll_to_with_x_and_y(0.0, 0.0)
-                }
-            }
-            Err(e) => {
-                web_sys::console::error_2(&JsValue::from_str("failed to change state: "), &e);
+        // The router scrolls the page after the new route renders
+        if let Err(e) = result {
+            web_sys::console::error_2(&JsValue::from_str("failed to change state: "), &e);
+        }
+    }
+
+    fn push_entry(&self, route: String, state: Option<&str>) {
+        if route == self.route_from_location() {
+            // don't push the same route twice, but keep the new state
+            if state.is_some() {
+                self.replace_entry(route, state);
             }
+            return;
         }
+
+        let w = window().expect("access to `window`");
+        let h = w.history().expect("`window` has access to `history`");
+
+        // update the scroll position before pushing the new state
+        update_scroll(&w, &h);
+
+        let path = self.full_path(&route);
+
+        let position: [f64; 2] = self.create_state();
+        self.handle_nav(push_state_and_url(&self.history, &position, state, path));
+    }
+
+    fn replace_entry(&self, route: String, state: Option<&str>) {
+        let path = self.full_path(&route);
+
+        let position = self.create_state();
+        self.handle_nav(replace_state_with_url(
+            &self.history,
+            &position,
+            state,
+            Some(&path),
+        ));
     }
 
     fn navigate_external(&self, url: String) -> bool {
@@ -179,46 +246,56 @@ impl dioxus_history::History for WebHistory {
     }
 
     fn push(&self, state: String) {
-        if state == self.current_route() {
-            // don't push the same state twice
-            return;
-        }
-
-        let w = window().expect("access to `window`");
-        let h = w.history().expect("`window` has access to `history`");
+        self.push_entry(state, None);
+    }
 
-        // update the scroll position before pushing the new state
-        update_scroll(&w, &h);
+    fn replace(&self, state: String) {
+        self.replace_entry(state, None);
+    }
 
-        let path = self.full_path(&state);
+    fn push_with_state(&self, route: String, state: String) {
+        self.push_entry(route, Some(&state));
+    }
 
-        let state: [f64; 2] = self.create_state();
-        self.handle_nav(push_state_and_url(&self.history, &state, path));
+    fn replace_with_state(&self, path: String, state: String) {
+        self.replace_entry(path, Some(&state));
     }
 
-    fn replace(&self, state: String) {
-        let path = self.full_path(&state);
+    fn current_state(&self) -> Option<String> {
+        get_current_state(&self.history)
+    }
 
-        let state = self.create_state();
-        self.handle_nav(replace_state_with_url(&self.history, &state, Some(&path)));
+    fn length(&self) -> usize {
+        self.history.length().map_or(1, |length| length as usize)
     }
 
     fn external(&self, url: String) -> bool {
         self.navigate_external(url)
     }
 
-    fn updater(&self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
-        let w = self.window.clone();
-        let h = self.history.clone();
-        let d = self.do_scroll_restoration;
+    fn set_scroll_position(&self, position: dioxus_history::ScrollPosition) {
+        if self.do_scroll_restoration {
+            let position = [position.x, position.y];
+            let state = get_current_state(&self.history);
+            if let Err(err) =
+                replace_state_with_url(&self.history, &position, state.as_deref(), None)
+            {
+                web_sys::console::error_1(&err);
+            }
+        }
+    }
 
+    fn scroll_position(&self) -> Option<dioxus_history::ScrollPosition> {
+        if !self.do_scroll_restoration {
+            return None;
+        }
+        get_current(&self.history).map(|[x, y]| dioxus_history::ScrollPosition { x, y })
+    }
+
+    fn updater(&self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
+        // The router restores the scroll position saved in the history state after the new route renders
         let function = Closure::wrap(Box::new(move |_| {
             (*callback)();
-            if d {
-                if let Some([x, y]) = get_current(&h) {
-                    ScrollPosition { x, y }.scroll_to(w.clone())
-                }
-            }
         }) as Box<dyn FnMut(Event)>);
         self.window
             .add_event_listener_with_callback(
@@ -229,41 +306,53 @@ impl dioxus_history::History for WebHistory {
     }
 }
 
+/// Create the state of a history entry. The state holds the scroll position of the page and the
+/// state of the entry if it has one.
+fn entry_state(position: &[f64; 2], state: Option<&str>) -> js_sys::Array {
+    let entry = js_sys::Array::new();
+    entry.push(&JsValue::from(position[0]));
+    entry.push(&JsValue::from(position[1]));
+    if let Some(state) = state {
+        entry.push(&JsValue::from_str(state));
+    }
+    entry
+}
+
 pub(crate) fn replace_state_with_url(
     history: &History,
-    value: &[f64; 2],
+    position: &[f64; 2],
+    state: Option<&str>,
     url: Option<&str>,
 ) -> Result<(), JsValue> {
-    let position = js_sys::Array::new();
-    position.push(&JsValue::from(value[0]));
-    position.push(&JsValue::from(value[1]));
-
-    history.replace_state_with_url(&position, "", url)
+    history.replace_state_with_url(&entry_state(position, state), "", url)
 }
 
 pub(crate) fn push_state_and_url(
     history: &History,
-    value: &[f64; 2],
+    position: &[f64; 2],
+    state: Option<&str>,
     url: String,
 ) -> Result<(), JsValue> {
-    let position = js_sys::Array::new();
-    position.push(&JsValue::from(value[0]));
-    position.push(&JsValue::from(value[1]));
-
-    history.push_state_with_url(&position, "", Some(&url))
+    history.push_state_with_url(&entry_state(position, state), "", Some(&url))
 }
 
-pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
+fn current_entry_state(history: &History) -> Option<js_sys::Array> {
     use wasm_bindgen::JsCast;
 
     let state = history.state();
     if let Err(err) = &state {
         web_sys::console::error_1(err);
     }
-    state.ok().and_then(|state| {
-        let state = state.dyn_into::<js_sys::Array>().ok()?;
-        let x = state.get(0).as_f64()?;
-        let y = state.get(1).as_f64()?;
-        Some([x, y])
-    })
+    state.ok()?.dyn_into::<js_sys::Array>().ok()
+}
+
+pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
+    let state = current_entry_state(history)?;
+    let x = state.get(0).as_f64()?;
+    let y = state.get(1).as_f64()?;
+    Some([x, y])
+}
+
+pub(crate) fn get_current_state(history: &History) -> Option<String> {
+    current_entry_state(history)?.get(2).as_string()
 }
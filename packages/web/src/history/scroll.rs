@@ -1,4 +1,3 @@
-use wasm_bindgen::{prelude::Closure, JsCast};
 use web_sys::Window;
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -14,15 +13,4 @@ impl ScrollPosition {
             y: window.scroll_y().unwrap_or_default(),
         }
     }
-
-    pub(crate) fn scroll_to(&self, window: Window) {
-        let Self { x, y } = *self;
-        let f = Closure::wrap(
-            Box::new(move || window.scroll_to_with_x_and_y(x, y)) as Box<dyn FnMut()>
-        );
-        web_sys::window()
-            .expect("should be run in a context with a `Window` object (dioxus cannot be run from a web worker)")
-            .request_animation_frame(&f.into_js_value().unchecked_into())
-            .expect("should register `requestAnimationFrame` OK");
-    }
 }
@@ -49,13 +49,28 @@ pub(crate) struct HTMLDataCursor {
 }
 
 impl HTMLDataCursor {
+    /// Read the data out of a hydration payload from the server.
+    ///
+    /// If the payload is corrupted, the data is dropped and the components that would have used it run their futures
+    /// on the client instead.
     pub(crate) fn from_serialized(
-        data: &[u8],
+        payload: &[u8],
         debug_types: Option<Vec<String>>,
         debug_locations: Option<Vec<String>>,
     ) -> Self {
-        let deserialized = ciborium::from_reader(Cursor::new(data)).unwrap();
-        Self::new(deserialized, debug_types, debug_locations)
+        let deserialized = dioxus_interpreter_js::decode_hydration_payload(payload)
+            .map_err(|err| err.to_string())
+            .and_then(|data| {
+                ciborium::from_reader(Cursor::new(data)).map_err(|err| err.to_string())
+            });
+        let data = match deserialized {
+            Ok(data) => data,
+            Err(err) => {
+                tracing::error!("Failed to read the hydration data from the server: {err}");
+                Vec::new()
+            }
+        };
+        Self::new(data, debug_types, debug_locations)
     }
 
     /// Get the error if there is one
@@ -0,0 +1,508 @@
+//! Debug-only diagnostics for hydration mismatches.
+//!
+//! Hydration assumes the server rendered html matches the virtual dom the client builds on the first render. When it
+//! doesn't, the client will silently attach to the wrong nodes or panic deep inside the interpreter. This module walks
+//! the server rendered dom alongside the client virtual dom and collects the first few differences it finds so they
+//! can be reported with the component stack and element path that produced them.
+
+use dioxus_core::prelude::*;
+use dioxus_core::{AttributeValue, DynamicNode, TemplateAttribute};
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::Node;
+
+/// The default number of mismatches that are collected before diagnostics stop walking the dom
+pub(crate) const DEFAULT_MISMATCH_LIMIT: usize = 10;
+
+/// A callback that receives every mismatch found while hydrating a part of the page
+pub(crate) type HydrationMismatchHandler = Rc<dyn Fn(&[HydrationMismatch])>;
+
+/// A difference between the html the server rendered and the virtual dom the client rendered during hydration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HydrationMismatch {
+    /// The names of the components the mismatch is nested inside of, from the root component to the innermost component
+    pub component_stack: Vec<String>,
+    /// The path to the element the mismatch was found in. Each segment is the tag name and index of the element in its parent
+    pub path: String,
+    /// The difference between the server and client
+    pub kind: HydrationMismatchKind,
+}
+
+impl Display for HydrationMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.kind)?;
+        writeln!(f, "  at element: {}", self.path)?;
+        write!(f, "  in component: {}", self.component_stack.join(" > "))
+    }
+}
+
+/// The kind of difference found in a [`HydrationMismatch`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum HydrationMismatchKind {
+    /// The server rendered a different element than the client expected
+    Tag {
+        /// The tag the client rendered
+        expected: String,
+        /// The tag the server rendered
+        found: String,
+    },
+    /// The server rendered different text than the client expected
+    Text {
+        /// The text the client rendered
+        expected: String,
+        /// The text the server rendered
+        found: String,
+    },
+    /// The server rendered a different attribute value than the client expected
+    Attribute {
+        /// The name of the attribute
+        name: String,
+        /// The value the client rendered or `None` if the attribute is missing on the client
+        expected: Option<String>,
+        /// The value the server rendered or `None` if the attribute is missing on the server
+        found: Option<String>,
+    },
+    /// The client rendered a node that is missing from the server html
+    MissingNode {
+        /// A description of the node the client rendered
+        expected: String,
+    },
+    /// The server rendered a node that the client did not render
+    ExtraNode {
+        /// A description of the node the server rendered
+        found: String,
+    },
+}
+
+impl Display for HydrationMismatchKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HydrationMismatchKind::Tag { expected, found } => {
+                write!(
+                    f,
+                    "expected element <{expected}> but the server rendered <{found}>"
+                )
+            }
+            HydrationMismatchKind::Text { expected, found } => {
+                write!(
+                    f,
+                    "expected text {expected:?} but the server rendered {found:?}"
+                )
+            }
+            HydrationMismatchKind::Attribute {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected attribute {name}={} but the server rendered {name}={}",
+                describe_attribute(expected),
+                describe_attribute(found)
+            ),
+            HydrationMismatchKind::MissingNode { expected } => {
+                write!(f, "expected {expected} but the server did not render it")
+            }
+            HydrationMismatchKind::ExtraNode { found } => {
+                write!(
+                    f,
+                    "the server rendered {found} which the client did not render"
+                )
+            }
+        }
+    }
+}
+
+fn describe_attribute(value: &Option<String>) -> String {
+    match value {
+        Some(value) => format!("{value:?}"),
+        None => "(missing)".to_string(),
+    }
+}
+
+/// The reason walking stopped before every node was compared
+enum StopWalking {
+    /// We collected as many mismatches as the limit allows
+    LimitReached,
+    /// We hit a node that can't be compared. The rest of the siblings at this level are skipped
+    LostPosition,
+}
+
+type WalkResult = Result<(), StopWalking>;
+
+/// Walks the server rendered dom and the virtual dom in parallel, collecting mismatches
+pub(crate) struct HydrationDiff<'a> {
+    dom: &'a VirtualDom,
+    limit: usize,
+    mismatches: Vec<HydrationMismatch>,
+    component_stack: Vec<&'static str>,
+    path: Vec<String>,
+}
+
+impl<'a> HydrationDiff<'a> {
+    pub(crate) fn new(dom: &'a VirtualDom, limit: usize) -> Self {
+        Self {
+            dom,
+            limit,
+            mismatches: Vec::new(),
+            component_stack: Vec::new(),
+            path: Vec::new(),
+        }
+    }
+
+    /// Compare the server rendered nodes starting at `first` with the nodes rendered by `scope`.
+    ///
+    /// If `report_extra` is false, trailing server nodes after the scope are ignored. This is used for
+    /// streaming chunks where the resolved nodes are mixed in with unrelated siblings.
+    pub(crate) fn diff_scope(
+        mut self,
+        scope: &ScopeState,
+        first: Option<Node>,
+        report_extra: bool,
+    ) -> Vec<HydrationMismatch> {
+        let mut cursor = first;
+        if self.diff_scope_inner(scope, &mut cursor).is_ok() && report_extra {
+            _ = self.report_remaining(cursor);
+        }
+        self.mismatches
+    }
+
+    fn diff_scope_inner(&mut self, scope: &ScopeState, cursor: &mut Option<Node>) -> WalkResult {
+        // Pending suspense boundaries are resolved later by streaming. We don't know how many nodes the server
+        // rendered for them yet, so stop comparing the siblings at this level
+        if let Some(suspense) =
+            SuspenseContext::downcast_suspense_boundary_from_scope(&self.dom.runtime(), scope.id())
+        {
+            if suspense.has_suspended_tasks() {
+                return Err(StopWalking::LostPosition);
+            }
+        }
+
+        self.diff_vnode(scope.root_node(), cursor)
+    }
+
+    fn diff_vnode(&mut self, vnode: &VNode, cursor: &mut Option<Node>) -> WalkResult {
+        for root in vnode.template.roots {
+            self.diff_template_node(vnode, root, cursor)?;
+        }
+        Ok(())
+    }
+
+    fn diff_template_node(
+        &mut self,
+        vnode: &VNode,
+        node: &TemplateNode,
+        cursor: &mut Option<Node>,
+    ) -> WalkResult {
+        match node {
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => {
+                let Some(found) = next_node(cursor) else {
+                    return self.report(HydrationMismatchKind::MissingNode {
+                        expected: format!("element <{tag}>"),
+                    });
+                };
+                let Some(element) = found.dyn_ref::<web_sys::Element>() else {
+                    return self.report(HydrationMismatchKind::Tag {
+                        expected: tag.to_string(),
+                        found: describe_node(&found),
+                    });
+                };
+                let found_tag = element.tag_name();
+                if !found_tag.eq_ignore_ascii_case(tag) {
+                    self.report(HydrationMismatchKind::Tag {
+                        expected: tag.to_string(),
+                        found: found_tag.to_lowercase(),
+                    })?;
+                    // The rest of this subtree is almost certainly different too
+                    return Ok(());
+                }
+
+                self.path.push(element_path_segment(element));
+                let result = self.diff_element(vnode, element, attrs, children);
+                self.path.pop();
+                result
+            }
+            TemplateNode::Text { text } => self.diff_text(text, cursor),
+            TemplateNode::Dynamic { id } => {
+                self.diff_dynamic_node(vnode, *id, &vnode.dynamic_nodes[*id], cursor)
+            }
+        }
+    }
+
+    fn diff_element(
+        &mut self,
+        vnode: &VNode,
+        element: &web_sys::Element,
+        attrs: &[TemplateAttribute],
+        children: &[TemplateNode],
+    ) -> WalkResult {
+        let mut has_inner_html = false;
+        for attr in attrs {
+            match attr {
+                TemplateAttribute::Static {
+                    name,
+                    value,
+                    namespace: None,
+                } => self.diff_attribute(element, name, Some(value.to_string()))?,
+                TemplateAttribute::Static { .. } => {}
+                TemplateAttribute::Dynamic { id } => {
+                    for attribute in &*vnode.dynamic_attrs[*id] {
+                        if attribute.name == "dangerous_inner_html" {
+                            has_inner_html = true;
+                            continue;
+                        }
+                        // Style attributes are merged into a single attribute by the server, so they can't be compared one by one
+                        if attribute.namespace.is_some() {
+                            continue;
+                        }
+                        let expected = match &attribute.value {
+                            AttributeValue::Text(value) => Some(value.clone()),
+                            AttributeValue::Float(value) => Some(value.to_string()),
+                            AttributeValue::Int(value) => Some(value.to_string()),
+                            AttributeValue::Bool(true) => {
+                                // Boolean attributes may be rendered as either `name` or `name=true`
+                                if element.has_attribute(attribute.name) {
+                                    continue;
+                                }
+                                Some("true".to_string())
+                            }
+                            AttributeValue::Bool(false) | AttributeValue::None => {
+                                // The server may render false as `name=false` for non-boolean attributes
+                                if element.get_attribute(attribute.name).as_deref() == Some("false")
+                                {
+                                    continue;
+                                }
+                                None
+                            }
+                            AttributeValue::Listener(_) | AttributeValue::Any(_) => continue,
+                        };
+                        self.diff_attribute(element, attribute.name, expected)?;
+                    }
+                }
+            }
+        }
+
+        // The children of an element with inner html are not managed by dioxus
+        if has_inner_html {
+            return Ok(());
+        }
+
+        let mut child_cursor = element.first_child();
+        for child in children {
+            match self.diff_template_node(vnode, child, &mut child_cursor) {
+                Ok(()) => {}
+                // Keep comparing the siblings of this element even if we lost our place inside of it
+                Err(StopWalking::LostPosition) => return Ok(()),
+                Err(StopWalking::LimitReached) => return Err(StopWalking::LimitReached),
+            }
+        }
+        self.report_remaining(child_cursor)
+    }
+
+    fn diff_attribute(
+        &mut self,
+        element: &web_sys::Element,
+        name: &str,
+        expected: Option<String>,
+    ) -> WalkResult {
+        let found = element.get_attribute(name);
+        if found != expected {
+            self.report(HydrationMismatchKind::Attribute {
+                name: name.to_string(),
+                expected,
+                found,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn diff_text(&mut self, expected: &str, cursor: &mut Option<Node>) -> WalkResult {
+        // Empty text is never rendered on the server
+        if expected.is_empty() {
+            return Ok(());
+        }
+        match next_node(cursor) {
+            Some(found) if found.node_type() == Node::TEXT_NODE => {
+                let found = found.text_content().unwrap_or_default();
+                if found != expected {
+                    self.report(HydrationMismatchKind::Text {
+                        expected: expected.to_string(),
+                        found,
+                    })?;
+                }
+                Ok(())
+            }
+            Some(found) => self.report(HydrationMismatchKind::Text {
+                expected: expected.to_string(),
+                found: describe_node(&found),
+            }),
+            None => self.report(HydrationMismatchKind::MissingNode {
+                expected: format!("text {expected:?}"),
+            }),
+        }
+    }
+
+    fn diff_dynamic_node(
+        &mut self,
+        vnode: &VNode,
+        idx: usize,
+        node: &DynamicNode,
+        cursor: &mut Option<Node>,
+    ) -> WalkResult {
+        match node {
+            DynamicNode::Text(text) => {
+                // Dynamic text is wrapped in <!--node-id--> and <!--#--> markers so the nodes don't get merged
+                if !self.expect_marker(cursor, "node-id", "dynamic text")? {
+                    return Ok(());
+                }
+                self.diff_text(&text.value, cursor)?;
+                self.expect_marker(cursor, "#", "the end of dynamic text")?;
+                Ok(())
+            }
+            DynamicNode::Placeholder(_) => {
+                self.expect_marker(cursor, "placeholder", "placeholder")?;
+                Ok(())
+            }
+            DynamicNode::Component(component) => {
+                let Some(scope) = component.mounted_scope(idx, vnode, self.dom) else {
+                    return Err(StopWalking::LostPosition);
+                };
+                self.component_stack.push(component.name);
+                let result = self.diff_scope_inner(scope, cursor);
+                self.component_stack.pop();
+                result
+            }
+            DynamicNode::Fragment(fragment) => {
+                for vnode in fragment {
+                    self.diff_vnode(vnode, cursor)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Expect the next node to be a hydration marker comment that starts with `prefix`. Returns false if a mismatch was reported
+    fn expect_marker(
+        &mut self,
+        cursor: &mut Option<Node>,
+        prefix: &str,
+        description: &str,
+    ) -> Result<bool, StopWalking> {
+        let found = cursor.take();
+        *cursor = found.as_ref().and_then(|node| node.next_sibling());
+        match found {
+            Some(node)
+                if node.node_type() == Node::COMMENT_NODE
+                    && node.node_value().unwrap_or_default().starts_with(prefix) =>
+            {
+                Ok(true)
+            }
+            Some(node) => {
+                self.report(HydrationMismatchKind::Tag {
+                    expected: description.to_string(),
+                    found: describe_node(&node),
+                })?;
+                Ok(false)
+            }
+            None => {
+                self.report(HydrationMismatchKind::MissingNode {
+                    expected: description.to_string(),
+                })?;
+                Ok(false)
+            }
+        }
+    }
+
+    /// Report any nodes the server rendered after the last node the client expected
+    fn report_remaining(&mut self, mut cursor: Option<Node>) -> WalkResult {
+        while let Some(found) = next_node(&mut cursor) {
+            self.report(HydrationMismatchKind::ExtraNode {
+                found: describe_node(&found),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn report(&mut self, kind: HydrationMismatchKind) -> WalkResult {
+        self.mismatches.push(HydrationMismatch {
+            component_stack: self
+                .component_stack
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            path: if self.path.is_empty() {
+                "(root)".to_string()
+            } else {
+                self.path.join(" > ")
+            },
+            kind,
+        });
+        if self.mismatches.len() >= self.limit {
+            return Err(StopWalking::LimitReached);
+        }
+        Ok(())
+    }
+}
+
+/// Take the next node from the cursor, skipping over comments that are not used by hydration
+fn next_node(cursor: &mut Option<Node>) -> Option<Node> {
+    while let Some(node) = cursor.take() {
+        *cursor = node.next_sibling();
+        if node.node_type() != Node::COMMENT_NODE {
+            return Some(node);
+        }
+    }
+    None
+}
+
+fn element_path_segment(element: &web_sys::Element) -> String {
+    let mut index = 0;
+    let mut sibling = element.previous_element_sibling();
+    while let Some(current) = sibling {
+        index += 1;
+        sibling = current.previous_element_sibling();
+    }
+    format!("{}[{index}]", element.tag_name().to_lowercase())
+}
+
+fn describe_node(node: &Node) -> String {
+    if let Some(element) = node.dyn_ref::<web_sys::Element>() {
+        format!("element <{}>", element.tag_name().to_lowercase())
+    } else if node.node_type() == Node::TEXT_NODE {
+        format!("text {:?}", node.text_content().unwrap_or_default())
+    } else if node.node_type() == Node::COMMENT_NODE {
+        format!("comment <!--{}-->", node.node_value().unwrap_or_default())
+    } else {
+        node.node_name()
+    }
+}
+
+/// Log the mismatches to the console and forward them to the user's handler if there is one
+pub(crate) fn report_hydration_mismatches(
+    mismatches: &[HydrationMismatch],
+    handler: Option<&HydrationMismatchHandler>,
+) {
+    if mismatches.is_empty() {
+        return;
+    }
+
+    let mut report = format!(
+        "Hydration mismatch: the html rendered on the server does not match the first render on the client. Found {} difference(s):",
+        mismatches.len()
+    );
+    for (i, mismatch) in mismatches.iter().enumerate() {
+        report.push_str(&format!("\n\n{}) {mismatch}", i + 1));
+    }
+    report.push_str("\n\nMake sure the first render of each component is deterministic and only depends on data that is serialized from the server with `use_server_future` or `use_server_cached`.");
+    web_sys::console::error_1(&report.into());
+
+    if let Some(handler) = handler {
+        handler(mismatches);
+    }
+}
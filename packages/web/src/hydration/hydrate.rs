@@ -177,6 +177,11 @@ impl WebsysDom {
             return Ok(());
         };
 
+        // Check the resolved nodes against the server html before we attach to them. The resolved nodes were moved
+        // in place of the placeholder, so the nodes after them are not part of this boundary
+        #[cfg(debug_assertions)]
+        self.report_hydration_mismatches(root_scope, dom, children.first().cloned(), false);
+
         // As we hydrate the suspense boundary, set the current path to the path of the suspense boundary
         self.suspense_hydration_ids
             .current_path
@@ -234,6 +239,9 @@ impl WebsysDom {
         );
         closure.forget();
 
+        #[cfg(debug_assertions)]
+        self.report_hydration_mismatches(vdom.base_scope(), vdom, self.root.first_child(), true);
+
         // Rehydrate the root scope that was rendered on the server. We will likely run into suspense boundaries.
         // Any suspense boundaries we run into are stored for hydration later.
         self.start_hydration_at_scope(vdom.base_scope(), vdom, vec![self.root.clone()])?;
@@ -241,6 +249,23 @@ impl WebsysDom {
         Ok(rx)
     }
 
+    /// Compare the server rendered html with the virtual dom and report any differences
+    #[cfg(debug_assertions)]
+    fn report_hydration_mismatches(
+        &self,
+        scope: &ScopeState,
+        dom: &VirtualDom,
+        first: Option<web_sys::Node>,
+        report_extra: bool,
+    ) {
+        let mismatches = super::diagnostics::HydrationDiff::new(dom, self.hydration_mismatch_limit)
+            .diff_scope(scope, first, report_extra);
+        super::diagnostics::report_hydration_mismatches(
+            &mismatches,
+            self.hydration_mismatch_handler.as_ref(),
+        );
+    }
+
     fn rehydrate_scope(
         &mut self,
         scope: &ScopeState,
@@ -1,11 +1,17 @@
 #[cfg(feature = "hydrate")]
 mod deserialize;
 #[cfg(feature = "hydrate")]
+mod diagnostics;
+#[cfg(feature = "hydrate")]
 mod hydrate;
 
 #[cfg(feature = "hydrate")]
 pub use deserialize::*;
 #[cfg(feature = "hydrate")]
+pub use diagnostics::{HydrationMismatch, HydrationMismatchKind};
+#[cfg(feature = "hydrate")]
+pub(crate) use diagnostics::{HydrationMismatchHandler, DEFAULT_MISMATCH_LIMIT};
+#[cfg(feature = "hydrate")]
 #[allow(unused)]
 pub use hydrate::*;
 
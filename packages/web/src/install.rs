@@ -0,0 +1,113 @@
+//! Prompting the user to install the app. Browsers only offer this for apps with a web app manifest, which
+//! `dx` generates when `web.manifest` is enabled in the Dioxus.toml.
+
+use dioxus_core::prelude::{try_consume_context, use_hook, Runtime, RuntimeGuard, ScopeId};
+use dioxus_signals::{Signal, Writable};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(inline_js = r#"
+export function watch_install_prompt(on_change) {
+    // The page may have caught the prompt before the app started
+    window.addEventListener("beforeinstallprompt", (event) => {
+        window.__dx_install_prompt = event;
+        on_change(true);
+    });
+    window.addEventListener("appinstalled", () => {
+        window.__dx_install_prompt = undefined;
+        on_change(false);
+    });
+    return window.__dx_install_prompt !== undefined;
+}
+
+export async function show_install_prompt() {
+    const prompt = window.__dx_install_prompt;
+    if (prompt === undefined) {
+        return "unavailable";
+    }
+    // Each prompt can only be shown once
+    window.__dx_install_prompt = undefined;
+    await prompt.prompt();
+    const choice = await prompt.userChoice;
+    return choice.outcome;
+}
+"#)]
+extern "C" {
+    fn watch_install_prompt(on_change: &Closure<dyn FnMut(bool)>) -> bool;
+    fn show_install_prompt() -> js_sys::Promise;
+}
+
+/// What the user chose when they were asked to install the app
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallOutcome {
+    /// The user installed the app
+    Accepted,
+    /// The user closed the prompt
+    Dismissed,
+    /// The browser can't install the app right now. It may not support installing apps, the app may already be
+    /// installed, or the page may not have a web app manifest.
+    Unavailable,
+}
+
+/// The install prompt of the browser. Get it with [`use_install_prompt`].
+#[derive(Clone, Copy)]
+pub struct InstallPrompt {
+    available: Signal<bool>,
+}
+
+impl InstallPrompt {
+    pub(crate) fn new_in_root() -> Self {
+        let mut available = Signal::new_in_scope(false, ScopeId::ROOT);
+        let runtime = Runtime::current().ok();
+        let on_change = Closure::<dyn FnMut(bool)>::new(move |value| {
+            let _guard = runtime.clone().map(RuntimeGuard::new);
+            available.set(value);
+        });
+        available.set(watch_install_prompt(&on_change));
+        // The listeners live as long as the page
+        on_change.forget();
+        Self { available }
+    }
+
+    /// Check if the browser can install the app. Reading this subscribes the component to changes.
+    pub fn available(&self) -> bool {
+        (self.available)()
+    }
+
+    /// Ask the user to install the app. Browsers only show the prompt in response to a user action like a click.
+    pub async fn prompt(&self) -> InstallOutcome {
+        let outcome = wasm_bindgen_futures::JsFuture::from(show_install_prompt()).await;
+        let mut available = self.available;
+        available.set(false);
+        match outcome
+            .ok()
+            .and_then(|outcome| outcome.as_string())
+            .as_deref()
+        {
+            Some("accepted") => InstallOutcome::Accepted,
+            Some("dismissed") => InstallOutcome::Dismissed,
+            _ => InstallOutcome::Unavailable,
+        }
+    }
+}
+
+/// Get the install prompt of the browser to offer installing the app as a PWA.
+///
+/// ```rust, ignore
+/// fn InstallButton() -> Element {
+///     let install = dioxus_web::use_install_prompt();
+///
+///     rsx! {
+///         if install.available() {
+///             button { onclick: move |_| async move { install.prompt().await; }, "Install" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_install_prompt() -> InstallPrompt {
+    // Apps that are not rendered by dioxus-web can't be installed
+    use_hook(|| {
+        try_consume_context::<InstallPrompt>().unwrap_or_else(|| InstallPrompt {
+            available: Signal::new(false),
+        })
+    })
+}
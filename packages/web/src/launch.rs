@@ -28,6 +28,11 @@ pub fn launch(
 ///
 /// For a builder API, see `LaunchBuilder` defined in the `dioxus` crate.
 pub fn launch_virtual_dom(vdom: VirtualDom, platform_config: Config) {
+    // Web workers load the same module to run background tasks. There is no document to render into there.
+    if web_sys::window().is_none() {
+        return;
+    }
+
     wasm_bindgen_futures::spawn_local(async move {
         crate::run(vdom, platform_config).await;
     });
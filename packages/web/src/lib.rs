@@ -27,12 +27,22 @@ use dom::WebsysDom;
 use futures_util::{pin_mut, select, FutureExt, StreamExt};
 
 mod cfg;
+mod custom_element;
 mod dom;
 
 mod events;
+mod frame;
+mod install;
 pub mod launch;
 mod mutations;
+#[cfg(feature = "mounted")]
+mod portal;
+mod service_worker;
+mod storage;
+pub use custom_element::{register_custom_element, use_custom_element, CustomElement};
 pub use events::*;
+pub use install::{use_install_prompt, InstallOutcome, InstallPrompt};
+pub use service_worker::{use_service_worker, ServiceWorker};
 
 #[cfg(feature = "document")]
 mod document;
@@ -44,6 +54,8 @@ mod history;
 pub use document::WebDocument;
 #[cfg(feature = "file_engine")]
 pub use file_engine::*;
+#[cfg(feature = "document")]
+pub use history::WebHistory;
 
 #[cfg(all(feature = "devtools", debug_assertions))]
 mod devtools;
@@ -52,6 +64,12 @@ mod hydration;
 #[allow(unused)]
 pub use hydration::*;
 
+/// Items used by the macros of this crate
+#[doc(hidden)]
+pub mod __private {
+    pub use dioxus_core::{fc_to_builder, Element};
+}
+
 /// Runs the app as a future that can be scheduled around the main thread.
 ///
 /// Polls futures internal to the VirtualDOM, hence the async nature of this function.
@@ -69,12 +87,41 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
     let runtime = virtual_dom.runtime();
 
     #[cfg(all(feature = "devtools", debug_assertions))]
-    let mut hotreload_rx = devtools::init(runtime.clone());
+    let mut hotreload_rx = {
+        virtual_dom = dioxus_devtools::report_unhandled_errors(virtual_dom, devtools::report_error);
+        devtools::init(runtime.clone())
+    };
 
     let should_hydrate = web_config.hydrate;
+    #[cfg(feature = "hydrate")]
+    let provided_hydration_data = web_config.hydration_data.clone();
+
+    // Run frame callbacks and animations in the browser's animation frames
+    runtime.set_frame_source(std::rc::Rc::new(frame::AnimationFrames));
 
     let mut websys_dom = WebsysDom::new(web_config, runtime);
 
+    // Let portals move their children out of the root element
+    #[cfg(feature = "mounted")]
+    virtual_dom.provide_root_context(std::rc::Rc::new(portal::WebPortalHost::new(
+        websys_dom.interpreter.base(),
+    )) as std::rc::Rc<dyn dioxus_html::PortalHost>);
+
+    // Persist signals to local and session storage
+    virtual_dom.provide_root_context(storage::platform_storage());
+
+    // Register the service worker dx added to the page and surface its updates to the app
+    let service_worker = virtual_dom.in_runtime(|| {
+        let service_worker = service_worker::ServiceWorker::new_in_root();
+        service_worker::register_from_page(service_worker);
+        service_worker
+    });
+    virtual_dom.provide_root_context(service_worker);
+
+    // Keep track of whether the browser offers to install the app
+    let install_prompt = virtual_dom.in_runtime(install::InstallPrompt::new_in_root);
+    virtual_dom.provide_root_context(install_prompt);
+
     let mut hydration_receiver: Option<futures_channel::mpsc::UnboundedReceiver<SuspenseMessage>> =
         None;
 
@@ -100,17 +147,25 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
                 fn get_initial_hydration_debug_types() -> Option<Vec<String>>;
                 fn get_initial_hydration_debug_locations() -> Option<Vec<String>>;
             }
-            let hydration_data = get_initial_hydration_data().to_vec();
-
-            // If we are running in debug mode, also get the debug types and locations
-            #[cfg(debug_assertions)]
-            let debug_types = get_initial_hydration_debug_types();
-            #[cfg(not(debug_assertions))]
-            let debug_types = None;
-            #[cfg(debug_assertions)]
-            let debug_locations = get_initial_hydration_debug_locations();
-            #[cfg(not(debug_assertions))]
-            let debug_locations = None;
+            // If the data was passed in through the config, we don't have debug types or locations for it
+            let (hydration_data, debug_types, debug_locations) = match provided_hydration_data {
+                Some(data) => (data, None, None),
+                None => {
+                    let hydration_data = get_initial_hydration_data().to_vec();
+
+                    // If we are running in debug mode, also get the debug types and locations
+                    #[cfg(debug_assertions)]
+                    let debug_types = get_initial_hydration_debug_types();
+                    #[cfg(not(debug_assertions))]
+                    let debug_types = None;
+                    #[cfg(debug_assertions)]
+                    let debug_locations = get_initial_hydration_debug_locations();
+                    #[cfg(not(debug_assertions))]
+                    let debug_locations = None;
+
+                    (hydration_data, debug_types, debug_locations)
+                }
+            };
 
             let server_data =
                 HTMLDataCursor::from_serialized(&hydration_data, debug_types, debug_locations);
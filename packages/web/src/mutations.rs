@@ -2,7 +2,7 @@ use crate::dom::WebsysDom;
 use dioxus_core::prelude::*;
 use dioxus_core::WriteMutations;
 use dioxus_core::{AttributeValue, ElementId};
-use dioxus_core_types::event_bubbles;
+use dioxus_core_types::{listener_flags, ListenerOptions};
 use dioxus_interpreter_js::minimal_bindings;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
@@ -208,6 +208,15 @@ impl WriteMutations for WebsysDom {
                 self.interpreter
                     .remove_attribute(id.0 as u32, name, ns.unwrap_or_default())
             }
+            AttributeValue::Any(value) => match value
+                .as_any()
+                .downcast_ref::<dioxus_html::ElementProperty>()
+            {
+                Some(property) => self
+                    .interpreter
+                    .set_property(id.0 as u32, name, property.json()),
+                None => unreachable!(),
+            },
             _ => unreachable!(),
         }
     }
@@ -220,6 +229,19 @@ impl WriteMutations for WebsysDom {
     }
 
     fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.create_event_listener_with_options(name, id, ListenerOptions::default())
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.remove_event_listener_with_options(name, id, ListenerOptions::default())
+    }
+
+    fn create_event_listener_with_options(
+        &mut self,
+        name: &'static str,
+        id: ElementId,
+        options: ListenerOptions,
+    ) {
         if self.skip_mutations() {
             return;
         }
@@ -231,10 +253,15 @@ impl WriteMutations for WebsysDom {
         }
 
         self.interpreter
-            .new_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
+            .new_event_listener(name, id.0 as u32, listener_flags(name, options));
     }
 
-    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+    fn remove_event_listener_with_options(
+        &mut self,
+        name: &'static str,
+        id: ElementId,
+        options: ListenerOptions,
+    ) {
         if self.skip_mutations() {
             return;
         }
@@ -243,7 +270,7 @@ impl WriteMutations for WebsysDom {
         }
 
         self.interpreter
-            .remove_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
+            .remove_event_listener(name, id.0 as u32, listener_flags(name, options));
     }
 
     fn remove_node(&mut self, id: ElementId) {
@@ -0,0 +1,44 @@
+use dioxus_html::{MountedData, PortalHost};
+use dioxus_interpreter_js::unified_bindings::BaseInterpreter;
+use wasm_bindgen::JsCast;
+
+use crate::WebEventExt;
+
+/// Moves portal containers into their target element and keeps the bubbling event listeners of the interpreter
+/// attached to them
+pub(crate) struct WebPortalHost {
+    interpreter: BaseInterpreter,
+}
+
+impl WebPortalHost {
+    pub(crate) fn new(interpreter: &BaseInterpreter) -> Self {
+        Self {
+            interpreter: AsRef::<wasm_bindgen::JsValue>::as_ref(interpreter)
+                .clone()
+                .unchecked_into(),
+        }
+    }
+}
+
+impl PortalHost for WebPortalHost {
+    fn mount(&self, container: &MountedData, target: &str) {
+        let Some(container) = container.try_as_web_event() else {
+            return;
+        };
+        let document = crate::load_document();
+        match document.query_selector(target) {
+            Ok(Some(target)) => self
+                .interpreter
+                .mount_portal(container.into(), target.into()),
+            _ => tracing::error!(
+                "Portal target `{target}` was not found. The portal will be rendered in place."
+            ),
+        }
+    }
+
+    fn unmount(&self, container: &MountedData) {
+        if let Some(container) = container.try_as_web_event() {
+            self.interpreter.unmount_portal(container.into());
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Registration of the service worker `dx build --service-worker` generates to cache the app for offline use.
+//!
+//! The app registers the worker automatically when `dx` added it to the page. A new deploy installs a new worker in
+//! the background, which waits until the app calls [`ServiceWorker::apply_update`] so the page never runs a mix of
+//! old and new files.
+
+use dioxus_core::prelude::{try_consume_context, use_hook, Runtime, RuntimeGuard, ScopeId};
+use dioxus_signals::{Signal, Writable};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(inline_js = r#"
+export function service_worker_url() {
+    const meta = document.querySelector('meta[name="dioxus-service-worker"]');
+    return meta ? meta.getAttribute("content") : undefined;
+}
+
+export function register_service_worker(url, on_update) {
+    if (!("serviceWorker" in navigator)) {
+        return;
+    }
+    navigator.serviceWorker.register(url).then((registration) => {
+        // The first install has no older version to update from
+        const installed = () => {
+            if (navigator.serviceWorker.controller) {
+                on_update();
+            }
+        };
+        if (registration.waiting) {
+            installed();
+        }
+        registration.addEventListener("updatefound", () => {
+            const worker = registration.installing;
+            worker.addEventListener("statechange", () => {
+                if (worker.state === "installed") {
+                    installed();
+                }
+            });
+        });
+    }).catch((error) => console.error("Failed to register the service worker", error));
+}
+
+export function apply_service_worker_update() {
+    if (!("serviceWorker" in navigator)) {
+        return;
+    }
+    navigator.serviceWorker.getRegistration().then((registration) => {
+        if (!registration || !registration.waiting) {
+            return;
+        }
+        // Reload once the new worker takes over so every file comes from the new version
+        navigator.serviceWorker.addEventListener("controllerchange", () => window.location.reload(), { once: true });
+        registration.waiting.postMessage({ type: "SKIP_WAITING" });
+    });
+}
+"#)]
+extern "C" {
+    fn service_worker_url() -> Option<String>;
+    fn register_service_worker(url: &str, on_update: &Closure<dyn FnMut()>);
+    fn apply_service_worker_update();
+}
+
+/// The service worker of the app. Get it with [`use_service_worker`].
+#[derive(Clone, Copy)]
+pub struct ServiceWorker {
+    update_available: Signal<bool>,
+}
+
+impl ServiceWorker {
+    pub(crate) fn new_in_root() -> Self {
+        Self {
+            update_available: Signal::new_in_scope(false, ScopeId::ROOT),
+        }
+    }
+
+    /// Register a service worker script. Apps built with `dx build --service-worker` register theirs automatically.
+    pub fn register(&self, url: &str) {
+        let runtime = Runtime::current().ok();
+        let mut update_available = self.update_available;
+        let on_update = Closure::<dyn FnMut()>::new(move || {
+            let _guard = runtime.clone().map(RuntimeGuard::new);
+            update_available.set(true);
+        });
+        register_service_worker(url, &on_update);
+        // The registration lives as long as the page
+        on_update.forget();
+    }
+
+    /// Check if a new version of the app was installed and is waiting for [`ServiceWorker::apply_update`]. Reading
+    /// this subscribes the component to changes.
+    pub fn update_available(&self) -> bool {
+        (self.update_available)()
+    }
+
+    /// Activate the new version of the app and reload the page once it takes over
+    pub fn apply_update(&self) {
+        apply_service_worker_update();
+    }
+}
+
+/// Register the service worker `dx` added to the page, if there is one
+pub(crate) fn register_from_page(service_worker: ServiceWorker) {
+    if let Some(url) = service_worker_url() {
+        service_worker.register(&url);
+    }
+}
+
+/// Get the service worker of the app to show a prompt when a new version is available.
+///
+/// ```rust, ignore
+/// fn UpdatePrompt() -> Element {
+///     let service_worker = dioxus_web::use_service_worker();
+///
+///     rsx! {
+///         if service_worker.update_available() {
+///             button { onclick: move |_| service_worker.apply_update(), "Reload to update" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_service_worker() -> ServiceWorker {
+    // Apps that are not rendered by dioxus-web never have an update
+    use_hook(|| {
+        try_consume_context::<ServiceWorker>().unwrap_or_else(|| ServiceWorker {
+            update_available: Signal::new(false),
+        })
+    })
+}
@@ -0,0 +1,44 @@
+use dioxus_signals::{MemoryStorage, PlatformStorage, StorageBackend};
+use std::rc::Rc;
+
+/// A storage backend for the browser's local or session storage
+struct WebStorage(web_sys::Storage);
+
+impl StorageBackend for WebStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.0.get_item(key).ok().flatten()
+    }
+
+    fn set(&self, key: &str, value: String) {
+        if let Err(err) = self.0.set_item(key, &value) {
+            tracing::error!("Failed to save {key} to web storage: {err:?}");
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        _ = self.0.remove_item(key);
+    }
+}
+
+/// Get the local and session storage of the window. Storage can be disabled in the browser, so fall back to memory.
+pub(crate) fn platform_storage() -> PlatformStorage {
+    let window = web_sys::window();
+    let backend = |storage: Option<web_sys::Storage>| -> Rc<dyn StorageBackend> {
+        match storage {
+            Some(storage) => Rc::new(WebStorage(storage)),
+            None => Rc::new(MemoryStorage::default()),
+        }
+    };
+    PlatformStorage {
+        local: backend(
+            window
+                .as_ref()
+                .and_then(|w| w.local_storage().ok().flatten()),
+        ),
+        session: backend(
+            window
+                .as_ref()
+                .and_then(|w| w.session_storage().ok().flatten()),
+        ),
+    }
+}
@@ -0,0 +1,219 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+use dioxus_lib::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{any::Any, fmt::Display, future::Future, rc::Rc};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(not(target_arch = "wasm32"))]
+use native::Backend;
+
+#[cfg(feature = "threads")]
+pub mod threads;
+
+#[cfg(target_arch = "wasm32")]
+mod web;
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+pub use web::__dx_worker_run;
+#[cfg(target_arch = "wasm32")]
+use web::Backend;
+
+/// A value that can be sent to or from a worker.
+///
+/// On the web, values are serialized to JSON to cross into the web worker. On other platforms they are moved to the
+/// worker thread as is.
+pub trait WorkerMessage: Serialize + DeserializeOwned + Send + 'static {}
+
+impl<T: Serialize + DeserializeOwned + Send + 'static> WorkerMessage for T {}
+
+/// An error from running a job on a [`Worker`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkerError {
+    /// The job was cancelled with [`Worker::cancel`] or because the worker was dropped
+    Cancelled,
+    /// The task panicked
+    Panicked(String),
+    /// The input or output couldn't be serialized to send it between the page and the web worker
+    Serialization(String),
+    /// The web worker couldn't be started. Web workers need the entrypoint `dx` generates when it bundles the app.
+    Unavailable(String),
+}
+
+impl Display for WorkerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkerError::Cancelled => write!(f, "The worker job was cancelled"),
+            WorkerError::Panicked(message) => write!(f, "The worker panicked: {message}"),
+            WorkerError::Serialization(message) => {
+                write!(f, "Failed to send a value to or from the worker: {message}")
+            }
+            WorkerError::Unavailable(message) => {
+                write!(f, "The web worker is unavailable: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WorkerError {}
+
+// Panics abort on the web, so only threads on other platforms catch them
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "the worker panicked".to_string(),
+        },
+    }
+}
+
+/// Runs a function off of the UI thread.
+///
+/// On the web, jobs run one at a time in a web worker that belongs to this worker. On other platforms, jobs run on
+/// a thread pool shared by every worker. Dropping the last clone of a worker cancels its jobs.
+///
+/// The task is a function pointer instead of a closure because it runs in a different wasm instance on the web.
+/// Anything it needs has to be passed in as the input.
+pub struct Worker<I, O> {
+    task: fn(I) -> O,
+    backend: Rc<Backend>,
+}
+
+impl<I, O> Clone for Worker<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            task: self.task,
+            backend: self.backend.clone(),
+        }
+    }
+}
+
+impl<I: WorkerMessage, O: WorkerMessage> Worker<I, O> {
+    /// Create a worker for a task. The web worker or threads are started the first time a job runs.
+    pub fn new(task: fn(I) -> O) -> Self {
+        Self {
+            task,
+            backend: Backend::new(),
+        }
+    }
+
+    /// Run the task with the input off of the UI thread and wait for the output
+    pub fn run(&self, input: I) -> impl Future<Output = Result<O, WorkerError>> + 'static {
+        self.backend.run(self.task, input)
+    }
+
+    /// Cancel every job that hasn't finished. Their futures resolve to [`WorkerError::Cancelled`].
+    ///
+    /// On the web, the web worker is terminated, which stops the job that is running right away. Threads can't be
+    /// stopped from the outside, so on other platforms a job that already started keeps running in the background and
+    /// its output is thrown away.
+    pub fn cancel(&self) {
+        self.backend.cancel()
+    }
+}
+
+/// Create a [`Worker`] that runs a heavy computation without freezing the UI.
+///
+/// Jobs are cancelled when the component is dropped.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_worker::use_worker;
+/// fn fibonacci(n: u64) -> u64 {
+///     match n {
+///         0 | 1 => n,
+///         n => fibonacci(n - 1) + fibonacci(n - 2),
+///     }
+/// }
+///
+/// fn app() -> Element {
+///     let worker = use_worker(fibonacci);
+///     let mut result = use_signal(|| None);
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| async move {
+///                 result.set(worker.run(40).await.ok());
+///             },
+///             "Calculate"
+///         }
+///         button { onclick: move |_| worker.cancel(), "Cancel" }
+///         if worker.is_running() {
+///             "Calculating..."
+///         } else if let Some(result) = result() {
+///             "fib(40) = {result}"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_worker<I: WorkerMessage, O: WorkerMessage>(task: fn(I) -> O) -> UseWorker<I, O> {
+    let worker = use_hook(|| CopyValue::new(Worker::new(task)));
+    let running = use_signal(|| 0);
+
+    UseWorker { worker, running }
+}
+
+/// A handle to a [`Worker`] owned by a component. Created with [`use_worker`].
+pub struct UseWorker<I: 'static, O: 'static> {
+    worker: CopyValue<Worker<I, O>>,
+    running: Signal<usize>,
+}
+
+impl<I, O> Clone for UseWorker<I, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I, O> Copy for UseWorker<I, O> {}
+
+impl<I: WorkerMessage, O: WorkerMessage> UseWorker<I, O> {
+    /// Run the task with the input off of the UI thread and wait for the output. See [`Worker::run`].
+    pub fn run(&self, input: I) -> impl Future<Output = Result<O, WorkerError>> + 'static {
+        let job = self.worker.peek().run(input);
+        let running = RunningGuard::new(self.running);
+        async move {
+            let output = job.await;
+            drop(running);
+            output
+        }
+    }
+
+    /// Cancel every job that hasn't finished. See [`Worker::cancel`].
+    pub fn cancel(&self) {
+        self.worker.peek().cancel()
+    }
+
+    /// Check if any job is running. Reading this subscribes the component to changes.
+    pub fn is_running(&self) -> bool {
+        (self.running)() > 0
+    }
+
+    /// Get the underlying worker
+    pub fn worker(&self) -> Worker<I, O> {
+        self.worker.peek().clone()
+    }
+}
+
+/// Counts a job as running until the job finishes or its future is dropped
+struct RunningGuard(Signal<usize>);
+
+impl RunningGuard {
+    fn new(mut running: Signal<usize>) -> Self {
+        running += 1;
+        Self(running)
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        // The component may have been dropped before the job
+        if let Ok(mut running) = self.0.try_write() {
+            *running -= 1;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Workers on a shared thread pool for desktop, mobile and server targets.
+
+use crate::{panic_message, WorkerError};
+use futures_channel::oneshot;
+use futures_util::future::{AbortHandle, Abortable};
+use slab::Slab;
+use std::{
+    cell::RefCell,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    rc::{Rc, Weak},
+    sync::{mpsc, Arc, Mutex, OnceLock},
+    thread,
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Run a job on the thread pool. The pool starts one thread per core the first time it is used.
+fn execute(job: Job) {
+    static POOL: OnceLock<Mutex<mpsc::Sender<Job>>> = OnceLock::new();
+
+    let pool = POOL.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let threads = thread::available_parallelism().map_or(4, |threads| threads.get());
+
+        for index in 0..threads {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("dioxus-worker-{index}"))
+                .spawn(move || loop {
+                    let job = match receiver.lock() {
+                        Ok(receiver) => receiver.recv(),
+                        Err(_) => return,
+                    };
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => return,
+                    }
+                })
+                .expect("failed to spawn a worker thread");
+        }
+
+        Mutex::new(sender)
+    });
+
+    pool.lock()
+        .unwrap()
+        .send(job)
+        .expect("the worker threads should never stop");
+}
+
+pub(crate) struct Backend {
+    // The jobs that haven't finished yet
+    jobs: RefCell<Slab<AbortHandle>>,
+}
+
+impl Backend {
+    pub(crate) fn new() -> Rc<Self> {
+        Rc::new(Self {
+            jobs: RefCell::new(Slab::new()),
+        })
+    }
+
+    pub(crate) fn run<I: Send + 'static, O: Send + 'static>(
+        self: &Rc<Self>,
+        task: fn(I) -> O,
+        input: I,
+    ) -> impl Future<Output = Result<O, WorkerError>> + 'static {
+        let (abort, registration) = AbortHandle::new_pair();
+        let key = self.jobs.borrow_mut().insert(abort.clone());
+        let (sender, receiver) = oneshot::channel();
+
+        execute(Box::new(move || {
+            // Skip jobs that were cancelled while they waited for a thread
+            if abort.is_aborted() {
+                return;
+            }
+            let output = catch_unwind(AssertUnwindSafe(|| task(input)))
+                .map_err(|panic| WorkerError::Panicked(panic_message(panic)));
+            _ = sender.send(output);
+        }));
+
+        let backend = Rc::downgrade(self);
+        async move {
+            let output = Abortable::new(receiver, registration).await;
+            if let Some(backend) = Weak::upgrade(&backend) {
+                backend.jobs.borrow_mut().try_remove(key);
+            }
+            match output {
+                Ok(Ok(output)) => output,
+                _ => Err(WorkerError::Cancelled),
+            }
+        }
+    }
+
+    /// Cancel every job that hasn't finished. Threads can't be interrupted, so jobs that already started run to the
+    /// end but their output is thrown away.
+    pub(crate) fn cancel(&self) {
+        for job in self.jobs.borrow_mut().drain() {
+            job.abort();
+        }
+    }
+}
+
+impl Drop for Backend {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
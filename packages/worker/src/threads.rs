@@ -0,0 +1,196 @@
+//! A [rayon](https://docs.rs/rayon) thread pool that shares memory with the app.
+//!
+//! [`Worker`](crate::Worker)s copy their input and output between the page and the web worker. Apps built with
+//! `dx build --threads` instead share their memory with every web worker, so rayon can split work across threads the
+//! same way it does on desktop.
+//!
+//! On the web, each thread of the pool is a web worker that loads the `dx-thread.js` entrypoint `dx` generates for
+//! threaded builds. The page can't block while it waits for other threads, so parallel work is started with [`spawn`]
+//! instead of calling rayon directly from the UI thread.
+
+use crate::WorkerError;
+use futures_channel::oneshot;
+use std::future::Future;
+
+pub use rayon;
+
+/// The number of threads the device can run at the same time. This is a good default for [`init_thread_pool`].
+pub fn available_parallelism() -> usize {
+    imp::available_parallelism()
+}
+
+/// Start the global rayon thread pool with a number of threads.
+///
+/// The future resolves once every thread is running. On the web, this fails with [`WorkerError::Unavailable`] if the
+/// app wasn't built with `dx build --threads` or the page isn't cross-origin isolated.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_worker::threads::{self, rayon::prelude::*};
+///
+/// fn app() -> Element {
+///     let pool = use_resource(|| threads::init_thread_pool(threads::available_parallelism()));
+///     let mut sum = use_signal(|| None);
+///
+///     rsx! {
+///         button {
+///             disabled: !matches!(*pool.read(), Some(Ok(()))),
+///             onclick: move |_| async move {
+///                 let total = threads::spawn(|| (0..100_000_000u64).into_par_iter().sum::<u64>()).await;
+///                 sum.set(total.ok());
+///             },
+///             "Sum"
+///         }
+///         if let Some(sum) = sum() {
+///             "The sum is {sum}"
+///         }
+///     }
+/// }
+/// ```
+pub fn init_thread_pool(threads: usize) -> impl Future<Output = Result<(), WorkerError>> + 'static {
+    imp::init_thread_pool(threads)
+}
+
+/// Run a function on the thread pool and wait for its output without blocking the UI thread. The function can use
+/// rayon to split its work across the rest of the pool.
+///
+/// Start the pool with [`init_thread_pool`] first. On desktop, mobile and the server, rayon starts a default pool if
+/// it wasn't started.
+pub fn spawn<T: Send + 'static>(
+    task: impl FnOnce() -> T + Send + 'static,
+) -> impl Future<Output = Result<T, WorkerError>> + 'static {
+    let (sender, receiver) = oneshot::channel();
+    // Without shared memory, rayon would queue the job until the UI thread blocks on the pool, which it never does
+    #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+    {
+        _ = task;
+        _ = sender.send(Err(imp::unavailable()));
+    }
+    #[cfg(any(not(target_arch = "wasm32"), target_feature = "atomics"))]
+    rayon::spawn(move || {
+        let output = std::panic::catch_unwind(std::panic::AssertUnwindSafe(task))
+            .map_err(|panic| WorkerError::Panicked(crate::panic_message(panic)));
+        _ = sender.send(output);
+    });
+    async move { receiver.await.map_err(|_| WorkerError::Cancelled)? }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod imp {
+    use crate::WorkerError;
+    use std::future::Future;
+
+    pub(super) fn available_parallelism() -> usize {
+        std::thread::available_parallelism().map_or(4, |threads| threads.get())
+    }
+
+    pub(super) fn init_thread_pool(
+        threads: usize,
+    ) -> impl Future<Output = Result<(), WorkerError>> + 'static {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("dioxus-thread-{index}"))
+            .build_global()
+            .map_err(|err| WorkerError::Unavailable(err.to_string()));
+        std::future::ready(pool)
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+mod imp {
+    use crate::WorkerError;
+    use std::{future::Future, sync::Mutex};
+    use wasm_bindgen::prelude::*;
+
+    /// The rayon threads that are waiting for a web worker to run them
+    static THREADS: Mutex<Vec<rayon::ThreadBuilder>> = Mutex::new(Vec::new());
+
+    #[wasm_bindgen(inline_js = r#"
+export function hardware_concurrency() {
+    return navigator.hardwareConcurrency || 4;
+}
+
+export function spawn_thread(module, memory) {
+    // Snippets are written to wasm/snippets/<crate>/ next to the entrypoint dx generates
+    const url = new URL("../../dx-thread.js", import.meta.url);
+    const worker = new Worker(url, { type: "module" });
+    worker.postMessage({ module, memory });
+    return new Promise((resolve, reject) => {
+        worker.onmessage = () => resolve();
+        worker.onerror = (event) => reject(event.message);
+    });
+}
+"#)]
+    extern "C" {
+        fn hardware_concurrency() -> usize;
+        fn spawn_thread(module: &JsValue, memory: &JsValue) -> js_sys::Promise;
+    }
+
+    /// Run a thread of the pool. This is called by the thread entrypoint and never returns.
+    #[doc(hidden)]
+    #[wasm_bindgen]
+    pub fn __dx_thread_start() {
+        let thread = THREADS.lock().unwrap().pop();
+        if let Some(thread) = thread {
+            thread.run();
+        }
+    }
+
+    pub(super) fn available_parallelism() -> usize {
+        hardware_concurrency()
+    }
+
+    pub(super) fn init_thread_pool(
+        threads: usize,
+    ) -> impl Future<Output = Result<(), WorkerError>> + 'static {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .spawn_handler(|thread| {
+                THREADS.lock().unwrap().push(thread);
+                Ok(())
+            })
+            .build_global();
+
+        async move {
+            pool.map_err(|err| WorkerError::Unavailable(err.to_string()))?;
+            let started = (0..threads).map(|_| {
+                let thread = spawn_thread(&wasm_bindgen::module(), &wasm_bindgen::memory());
+                wasm_bindgen_futures::JsFuture::from(thread)
+            });
+            futures_util::future::try_join_all(started)
+                .await
+                .map_err(|err| {
+                    WorkerError::Unavailable(err.as_string().unwrap_or_else(|| {
+                        "the thread entrypoint failed to load. Threads are only available in apps built with `dx build --threads`".to_string()
+                    }))
+                })?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+mod imp {
+    use crate::WorkerError;
+    use std::future::Future;
+
+    pub(super) fn available_parallelism() -> usize {
+        1
+    }
+
+    pub(super) fn init_thread_pool(
+        _threads: usize,
+    ) -> impl Future<Output = Result<(), WorkerError>> + 'static {
+        std::future::ready(Err(unavailable()))
+    }
+
+    pub(super) fn unavailable() -> WorkerError {
+        WorkerError::Unavailable(
+            "the app was built without shared memory. Build it with `dx build --threads` to use threads".to_string(),
+        )
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+#[doc(hidden)]
+pub use imp::__dx_thread_start;
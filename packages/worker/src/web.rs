@@ -0,0 +1,185 @@
+//! Workers in a web worker that loads the same wasm module as the page.
+//!
+//! Closures can't be sent between wasm instances, but two instances of the same module share the same function
+//! table. The page sends the table index of the task and of a monomorphized trampoline that deserializes the input,
+//! runs the task and serializes the output. The worker entrypoint generated by `dx` calls [`__dx_worker_run`] with
+//! those indexes.
+
+use crate::{WorkerError, WorkerMessage};
+use futures_channel::oneshot;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+
+type Trampoline = fn(usize, &str) -> Result<String, String>;
+
+fn trampoline<I: DeserializeOwned, O: Serialize>(
+    task: usize,
+    input: &str,
+) -> Result<String, String> {
+    // SAFETY: the page created the index from a `fn(I) -> O` in the same wasm module
+    let task = unsafe { std::mem::transmute::<usize, fn(I) -> O>(task) };
+    let input = serde_json::from_str(input).map_err(|err| err.to_string())?;
+    serde_json::to_string(&task(input)).map_err(|err| err.to_string())
+}
+
+/// Run a task the page sent to the worker. This is called by the worker entrypoint and should not be used directly.
+#[doc(hidden)]
+#[wasm_bindgen]
+pub fn __dx_worker_run(trampoline: usize, task: usize, input: String) -> Result<String, String> {
+    // SAFETY: the page created the index from a `Trampoline` in the same wasm module
+    let trampoline = unsafe { std::mem::transmute::<usize, Trampoline>(trampoline) };
+    trampoline(task, &input)
+}
+
+#[wasm_bindgen(inline_js = r#"
+export function spawn_worker(module) {
+    // Snippets are written to wasm/snippets/<crate>/ next to the entrypoint dx generates
+    const url = new URL("../../dx-worker.js", import.meta.url);
+    const worker = new Worker(url, { type: "module" });
+    worker.postMessage({ type: "init", module });
+    return worker;
+}
+
+export function post_job(worker, id, trampoline, task, input) {
+    worker.postMessage({ id, trampoline, task, input });
+}
+"#)]
+extern "C" {
+    fn spawn_worker(module: &JsValue) -> web_sys::Worker;
+    fn post_job(worker: &web_sys::Worker, id: u32, trampoline: usize, task: usize, input: String);
+}
+
+type Pending = Rc<RefCell<HashMap<u32, oneshot::Sender<Result<String, WorkerError>>>>>;
+
+/// A web worker that is started the first time a job runs
+struct Running {
+    worker: web_sys::Worker,
+    _onmessage: Closure<dyn FnMut(web_sys::MessageEvent)>,
+    _onerror: Closure<dyn FnMut(web_sys::ErrorEvent)>,
+}
+
+pub(crate) struct Backend {
+    worker: Rc<RefCell<Option<Running>>>,
+    pending: Pending,
+    next_id: Cell<u32>,
+}
+
+impl Backend {
+    pub(crate) fn new() -> Rc<Self> {
+        Rc::new(Self {
+            worker: Default::default(),
+            pending: Default::default(),
+            next_id: Cell::new(0),
+        })
+    }
+
+    pub(crate) fn run<I: WorkerMessage, O: WorkerMessage>(
+        self: &Rc<Self>,
+        task: fn(I) -> O,
+        input: I,
+    ) -> impl Future<Output = Result<O, WorkerError>> + 'static {
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+
+        let (sender, receiver) = oneshot::channel();
+        match serde_json::to_string(&input) {
+            Ok(input) => {
+                self.pending.borrow_mut().insert(id, sender);
+                let trampoline = trampoline::<I, O> as Trampoline as usize;
+                post_job(&self.worker(), id, trampoline, task as usize, input);
+            }
+            Err(err) => _ = sender.send(Err(WorkerError::Serialization(err.to_string()))),
+        }
+
+        async move {
+            let output = receiver.await.map_err(|_| WorkerError::Cancelled)??;
+            serde_json::from_str(&output).map_err(|err| WorkerError::Serialization(err.to_string()))
+        }
+    }
+
+    /// Terminate the worker and cancel every job that hasn't finished. The next job starts a new worker.
+    pub(crate) fn cancel(&self) {
+        terminate(&self.worker, &self.pending);
+    }
+
+    fn worker(&self) -> web_sys::Worker {
+        if let Some(running) = &*self.worker.borrow() {
+            return running.worker.clone();
+        }
+
+        let worker = spawn_worker(&wasm_bindgen::module());
+
+        let onmessage = Closure::<dyn FnMut(_)>::new({
+            let pending = self.pending.clone();
+            let running = self.worker.clone();
+            move |event: web_sys::MessageEvent| {
+                let data = event.data();
+                let field = |name: &str| js_sys::Reflect::get(&data, &name.into()).ok();
+                let Some(id) = field("id").and_then(|id| id.as_f64()) else {
+                    return;
+                };
+                let output = match field("output").and_then(|output| output.as_string()) {
+                    Some(output) => Ok(output),
+                    None => {
+                        let error = field("error").and_then(|error| error.as_string());
+                        Err(WorkerError::Panicked(error.unwrap_or_default()))
+                    }
+                };
+
+                let failed = output.is_err();
+                if let Some(sender) = pending.borrow_mut().remove(&(id as u32)) {
+                    _ = sender.send(output);
+                }
+                // A panic leaves the wasm instance in the worker unusable
+                if failed {
+                    terminate(&running, &pending);
+                }
+            }
+        });
+        worker.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        let onerror = Closure::<dyn FnMut(_)>::new({
+            let pending = self.pending.clone();
+            let running = self.worker.clone();
+            move |event: web_sys::ErrorEvent| {
+                tracing::error!("Failed to start the web worker. Web workers are only available in apps built with dx: {}", event.message());
+                for (_, sender) in pending.borrow_mut().drain() {
+                    _ = sender.send(Err(WorkerError::Unavailable(event.message())));
+                }
+                terminate(&running, &pending);
+            }
+        });
+        worker.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+
+        *self.worker.borrow_mut() = Some(Running {
+            worker: worker.clone(),
+            _onmessage: onmessage,
+            _onerror: onerror,
+        });
+        worker
+    }
+}
+
+impl Drop for Backend {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+fn terminate(running: &RefCell<Option<Running>>, pending: &Pending) {
+    let running = running.borrow_mut().take();
+    if let Some(running) = &running {
+        running.worker.terminate();
+    }
+    pending.borrow_mut().clear();
+    if let Some(running) = running {
+        // This may be called from one of the closures of the worker, so drop them after it returns
+        wasm_bindgen_futures::spawn_local(async move { drop(running) });
+    }
+}
@@ -0,0 +1,19 @@
+#![cfg(feature = "threads")]
+
+use dioxus_worker::threads::{self, rayon::prelude::*};
+
+#[tokio::test]
+async fn splits_work_across_the_pool() {
+    threads::init_thread_pool(2).await.unwrap();
+    // The global pool can only be started once
+    assert!(threads::init_thread_pool(2).await.is_err());
+
+    let (sum, thread) = threads::spawn(|| {
+        let sum = (1..=1000u64).into_par_iter().sum::<u64>();
+        (sum, std::thread::current().name().map(str::to_string))
+    })
+    .await
+    .unwrap();
+    assert_eq!(sum, 500500);
+    assert!(thread.unwrap().starts_with("dioxus-thread-"));
+}
@@ -0,0 +1,83 @@
+use dioxus::prelude::*;
+use dioxus_worker::{use_worker, Worker, WorkerError};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+#[tokio::test]
+async fn runs_off_the_current_thread() {
+    let worker = Worker::new(|input: String| {
+        let thread = std::thread::current();
+        format!("{input} from {}", thread.name().unwrap_or_default())
+    });
+
+    let output = worker.run("hello".to_string()).await.unwrap();
+    assert!(output.starts_with("hello from dioxus-worker-"), "{output}");
+}
+
+#[tokio::test]
+async fn reports_panics() {
+    let worker = Worker::new(|input: u32| -> u32 {
+        if input == 0 {
+            panic!("division by zero");
+        }
+        100 / input
+    });
+
+    assert_eq!(
+        worker.run(0).await,
+        Err(WorkerError::Panicked("division by zero".into()))
+    );
+    // The worker keeps working after a panic
+    assert_eq!(worker.run(4).await, Ok(25));
+}
+
+#[tokio::test]
+async fn cancels_pending_jobs() {
+    let worker = Worker::new(|millis: u64| {
+        std::thread::sleep(Duration::from_millis(millis));
+        millis
+    });
+
+    let first = worker.run(100);
+    let second = worker.run(100);
+    worker.cancel();
+
+    assert_eq!(first.await, Err(WorkerError::Cancelled));
+    assert_eq!(second.await, Err(WorkerError::Cancelled));
+    assert_eq!(worker.run(1).await, Ok(1));
+}
+
+#[tokio::test]
+async fn hook_runs_jobs_from_components() {
+    static RESULT: AtomicU64 = AtomicU64::new(0);
+
+    fn app() -> Element {
+        let worker = use_worker(|n: u64| n * 2);
+        use_future(move || async move {
+            let output = worker.run(21).await.unwrap();
+            RESULT.store(output, Ordering::SeqCst);
+        });
+        // The component reruns when the job finishes
+        rsx! {
+            if worker.is_running() {
+                "running"
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while RESULT.load(Ordering::SeqCst) == 0 {
+            dom.wait_for_work().await;
+            dom.render_immediate(&mut dioxus_core::NoOpMutations);
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(RESULT.load(Ordering::SeqCst), 42);
+}